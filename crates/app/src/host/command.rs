@@ -43,6 +43,10 @@ pub enum HostCommand {
         stream: StreamNames,
         parser: ParserNames,
     },
+    /// Opens sessions setup for byte-source plugins.
+    PluginSourceSessionSetup {
+        parser: ParserNames,
+    },
     /// Reopen a recent-session snapshot with the requested intent.
     OpenRecentSession(Box<OpenRecentSessionParam>),
     DltStatistics(Box<DltStatisticsParam>),
@@ -97,6 +101,8 @@ pub enum HostCommand {
 pub struct StartSessionParam {
     pub parser: ParserConfig,
     pub source: ByteSourceConfig,
+    /// Recording of the raw bytes, which is only supported for stream sources.
+    pub recording: Option<stypes::StreamRecording>,
     pub session_setup_id: Option<Uuid>,
 }
 
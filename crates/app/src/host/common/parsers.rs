@@ -44,10 +44,33 @@ impl ParserNames {
         use StreamNames as Stream;
 
         match (self, stream) {
-            (Parser::Text, Stream::Process | Stream::Serial) => true,
-            (Parser::Text, Stream::Tcp | Stream::Udp) => false,
-            (Parser::Dlt | Parser::SomeIP, Stream::Tcp | Stream::Udp | Stream::Serial) => true,
-            (Parser::Dlt | Parser::SomeIP, Stream::Process) => false,
+            (
+                Parser::Text,
+                Stream::Process
+                | Stream::Serial
+                | Stream::UnixSocket
+                | Stream::Mqtt
+                | Stream::WebSocket
+                | Stream::HttpStream
+                | Stream::SocketCan,
+            ) => true,
+            // Replayed traces are binary files.
+            (Parser::Text, Stream::Tcp | Stream::Udp | Stream::Replay) => false,
+            // Binary parsers need the raw output of commands.
+            (
+                Parser::Dlt | Parser::SomeIP,
+                Stream::Tcp
+                | Stream::Udp
+                | Stream::Serial
+                | Stream::UnixSocket
+                | Stream::Process
+                | Stream::Replay
+                | Stream::Mqtt
+                | Stream::WebSocket
+                | Stream::HttpStream,
+            ) => true,
+            // CAN frames are delivered as text lines.
+            (Parser::Dlt | Parser::SomeIP, Stream::SocketCan) => false,
             (Parser::Plugins, _) => true,
         }
     }
@@ -133,18 +156,32 @@ mod tests {
 
     #[test]
     fn test_stream_compatibility() {
-        // Text: Compatible with Process, Serial. NOT Tcp, Udp
+        // Text: Compatible with Process, Serial, UnixSocket, Mqtt, WebSocket, HttpStream,
+        // SocketCan.
+        // NOT Tcp, Udp, Replay
         assert!(ParserNames::Text.is_compatible_stream(StreamNames::Process));
         assert!(ParserNames::Text.is_compatible_stream(StreamNames::Serial));
+        assert!(ParserNames::Text.is_compatible_stream(StreamNames::UnixSocket));
+        assert!(ParserNames::Text.is_compatible_stream(StreamNames::Mqtt));
+        assert!(ParserNames::Text.is_compatible_stream(StreamNames::WebSocket));
+        assert!(ParserNames::Text.is_compatible_stream(StreamNames::HttpStream));
+        assert!(ParserNames::Text.is_compatible_stream(StreamNames::SocketCan));
         assert!(!ParserNames::Text.is_compatible_stream(StreamNames::Tcp));
         assert!(!ParserNames::Text.is_compatible_stream(StreamNames::Udp));
+        assert!(!ParserNames::Text.is_compatible_stream(StreamNames::Replay));
 
-        // Dlt/SomeIP: Compatible with Tcp, Udp, Serial. NOT Process
+        // Dlt/SomeIP: Compatible with all streams except SocketCan
         for parser in [ParserNames::Dlt, ParserNames::SomeIP] {
             assert!(parser.is_compatible_stream(StreamNames::Tcp));
             assert!(parser.is_compatible_stream(StreamNames::Udp));
             assert!(parser.is_compatible_stream(StreamNames::Serial));
-            assert!(!parser.is_compatible_stream(StreamNames::Process));
+            assert!(parser.is_compatible_stream(StreamNames::UnixSocket));
+            assert!(parser.is_compatible_stream(StreamNames::Process));
+            assert!(parser.is_compatible_stream(StreamNames::Replay));
+            assert!(parser.is_compatible_stream(StreamNames::Mqtt));
+            assert!(parser.is_compatible_stream(StreamNames::WebSocket));
+            assert!(parser.is_compatible_stream(StreamNames::HttpStream));
+            assert!(!parser.is_compatible_stream(StreamNames::SocketCan));
         }
 
         // Plugins: Compatible with everything
@@ -153,6 +190,12 @@ mod tests {
             StreamNames::Serial,
             StreamNames::Tcp,
             StreamNames::Udp,
+            StreamNames::UnixSocket,
+            StreamNames::Replay,
+            StreamNames::Mqtt,
+            StreamNames::WebSocket,
+            StreamNames::HttpStream,
+            StreamNames::SocketCan,
         ] {
             assert!(ParserNames::Plugins.is_compatible_stream(stream));
         }
@@ -1,16 +1,23 @@
 use std::fmt::Display;
 
 use enum_iterator::Sequence;
+use serde::{Deserialize, Serialize};
 
 use crate::host::{common::parsers::ParserNames, ui::session_setup::state::sources::StreamConfig};
 
 /// Slim variant of [`stypes::Transport`] without their configurations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Sequence)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Sequence, Serialize, Deserialize)]
 pub enum StreamNames {
     Process,
     Tcp,
     Udp,
     Serial,
+    UnixSocket,
+    Replay,
+    Mqtt,
+    WebSocket,
+    HttpStream,
+    SocketCan,
 }
 
 impl StreamNames {
@@ -26,6 +33,12 @@ impl Display for StreamNames {
             StreamNames::Tcp => "TCP",
             StreamNames::Udp => "UDP",
             StreamNames::Serial => "Serial Port",
+            StreamNames::UnixSocket => "Unix Socket",
+            StreamNames::Replay => "Replay Trace",
+            StreamNames::Mqtt => "MQTT",
+            StreamNames::WebSocket => "WebSocket",
+            StreamNames::HttpStream => "HTTP Stream",
+            StreamNames::SocketCan => "SocketCAN",
         };
 
         f.write_str(name)
@@ -39,6 +52,12 @@ impl From<&StreamConfig> for StreamNames {
             StreamConfig::Tcp(..) => Self::Tcp,
             StreamConfig::Udp(..) => Self::Udp,
             StreamConfig::Serial(..) => Self::Serial,
+            StreamConfig::UnixSocket(..) => Self::UnixSocket,
+            StreamConfig::Replay(..) => Self::Replay,
+            StreamConfig::Mqtt(..) => Self::Mqtt,
+            StreamConfig::WebSocket(..) => Self::WebSocket,
+            StreamConfig::HttpStream(..) => Self::HttpStream,
+            StreamConfig::SocketCan(..) => Self::SocketCan,
         }
     }
 }
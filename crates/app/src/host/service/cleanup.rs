@@ -9,14 +9,14 @@ use std::{
 
 use log::error;
 use semver::Version;
-use session_core::paths::{get_chipmunk_downloads_dir, get_streams_dir};
+use session_core::paths::{get_chipmunk_downloads_dir, get_search_indexes_dir, get_streams_dir};
 use stypes::{NativeError, NativeErrorKind, Severity};
 
 use crate::{common::app_info, host::service::update::version_dir_prefix};
 
 /// Cleans up temporary files that were generated by Chipmunk on previous sessions.
 ///
-/// Both cleanups are always attempted, failures are logged only since the caller
+/// All cleanups are always attempted, failures are logged only since the caller
 /// has no way to recover from them.
 pub fn cleanup_temp_files() {
     if let Err(err) = cleanup_temp_dir() {
@@ -26,6 +26,10 @@ pub fn cleanup_temp_files() {
     if let Err(err) = cleanup_download_dir() {
         error!("Error while cleaning up downloaded files. Error: {err:?}");
     }
+
+    if let Err(err) = cleanup_search_indexes_dir() {
+        error!("Error while cleaning up search indexes. Error: {err:?}");
+    }
 }
 
 /// Iterates through chipmunk temporary directory and remove the entries older
@@ -39,6 +43,49 @@ fn cleanup_temp_dir() -> Result<(), NativeError> {
     Ok(())
 }
 
+/// Removes the search indexes of linked files, which haven't been used for one month,
+/// and the least recently used ones beyond the size limit.
+///
+/// Indexes are modified whenever they are opened, so their modification time is the
+/// time they were last used.
+fn cleanup_search_indexes_dir() -> Result<(), NativeError> {
+    const ONE_MONTH: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+    const MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+    let indexes_dir = get_search_indexes_dir()?;
+    cleanup_dir(&indexes_dir, stale_before(ONE_MONTH)?)?;
+    cleanup_dir_beyond_size(&indexes_dir, MAX_BYTES)?;
+
+    Ok(())
+}
+
+/// Removes the least recently modified files within the given path until their total
+/// size is within the given limit.
+fn cleanup_dir_beyond_size(path: &Path, max_bytes: u64) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = fs::read_dir(path)?
+        .flat_map(Result::ok)
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|meta| meta.is_file())?;
+            Some((meta.modified().ok()?, meta.len(), entry.path()))
+        })
+        .collect();
+    files.sort_by(|(left, ..), (right, ..)| right.cmp(left));
+
+    let mut total = 0;
+    files
+        .into_iter()
+        .filter(|(_, len, _)| {
+            total += len;
+            total > max_bytes
+        })
+        .map(|(.., path)| path)
+        .try_for_each(remove_entry)
+}
+
 /// Modification time before which an entry counts as stale.
 fn stale_before(max_age: Duration) -> Result<SystemTime, NativeError> {
     SystemTime::now()
@@ -142,7 +189,7 @@ mod tests {
 
     use semver::Version;
 
-    use super::{cleanup_dir, cleanup_download_entries};
+    use super::{cleanup_dir, cleanup_dir_beyond_size, cleanup_download_entries};
 
     #[test]
     fn cleanup_download_entries_removes_matching_prefix_dirs() {
@@ -207,4 +254,32 @@ mod tests {
             assert!(!entry.exists());
         }
     }
+
+    #[test]
+    fn cleanup_dir_beyond_size_removes_least_recently_modified() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let temp_path = tempdir.path();
+
+        let now = SystemTime::now();
+        let files: Vec<_> = (0..4)
+            .map(|i| {
+                let path = temp_path.join(format!("{i}.index"));
+                fs::write(&path, [0u8; 100]).unwrap();
+                File::options()
+                    .append(true)
+                    .open(&path)
+                    .unwrap()
+                    .set_modified(now - Duration::from_secs(3600 * i))
+                    .unwrap();
+                path
+            })
+            .collect();
+
+        cleanup_dir_beyond_size(temp_path, 250).unwrap();
+
+        assert!(files[0].exists());
+        assert!(files[1].exists());
+        assert!(!files[2].exists());
+        assert!(!files[3].exists());
+    }
 }
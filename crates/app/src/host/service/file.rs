@@ -9,7 +9,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use session_core::paths::get_streams_dir;
 use stypes::{FileFormat, NativeError, NativeErrorKind, Severity};
+use uuid::Uuid;
 
 use crate::host::{command::CopyFileInfo, error::HostError};
 
@@ -23,11 +25,15 @@ pub enum FileFormatDetection {
 }
 
 /// Detects the [`FileFormat`] of a file at the given path.
+///
+/// Compressed files are detected by their decompressed content and the extension
+/// preceding the compression one.
 pub fn detect_file_format(file_path: &Path) -> io::Result<FileFormatDetection> {
     if file_tools::is_utf8_text(file_path)? {
         return Ok(FileFormatDetection::Supported(FileFormat::Text));
     }
 
+    let file_path = file_tools::strip_compression_extension(file_path);
     let extension = file_path.extension();
     let detection = match extension {
         Some(ext) if ext.eq_ignore_ascii_case("pcap") => {
@@ -51,6 +57,57 @@ fn is_text_extension(extension: &OsStr) -> bool {
     .any(|text_extension| extension.eq_ignore_ascii_case(text_extension))
 }
 
+/// Replaces the zip and tar archives among the given paths with their members, which are
+/// extracted into the Chipmunk temporary directory.
+///
+/// Archives that fail to extract are skipped with a warning.
+pub fn expand_archives(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut expanded = Vec::with_capacity(paths.len());
+    for path in paths {
+        match file_tools::ArchiveKind::detect(&path) {
+            Ok(Some(kind)) => match extract_archive(&path, kind) {
+                Ok(members) => expanded.extend(members),
+                Err(err) => log::warn!(
+                    "Error while extracting archive. Archive will be skipped. \
+                    Path: {}. Error {err:?}",
+                    path.display()
+                ),
+            },
+            Ok(None) => expanded.push(path),
+            Err(err) => log::warn!(
+                "Error while checking file type. File will be skipped. \
+                Path: {}. Error {err:?}",
+                path.display()
+            ),
+        }
+    }
+
+    expanded
+}
+
+/// Returns whether the file at the given path is a zip or tar archive.
+pub fn is_archive(file_path: &Path) -> io::Result<bool> {
+    file_tools::ArchiveKind::detect(file_path).map(|kind| kind.is_some())
+}
+
+fn extract_archive(file_path: &Path, kind: file_tools::ArchiveKind) -> io::Result<Vec<PathBuf>> {
+    let tmp_dir = get_streams_dir().map_err(|err| {
+        io::Error::other(
+            err.message
+                .unwrap_or_else(|| String::from("Failed to resolve temporary directory")),
+        )
+    })?;
+    let archive_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| String::from("archive"));
+    // Extract into a unique directory inside the temporary directory so it gets cleaned up
+    // with the other stale temporary files.
+    let dest_dir = tmp_dir.join(format!("{archive_name}-{}", Uuid::new_v4()));
+
+    file_tools::extract_members(file_path, kind, dest_dir)
+}
+
 /// Builds the user-facing message for text files with unsupported encoding.
 pub fn unsupported_text_encoding_message(path: &Path) -> String {
     let name = path
@@ -200,6 +257,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detect_compressed_formats_by_inner_extension() {
+        use std::io::Write as _;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.pcap.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(&[0xd4, 0xc3, 0xb2, 0xa1, 0xff]).unwrap();
+        encoder.finish().unwrap();
+
+        assert_eq!(
+            detect_file_format(&path).unwrap(),
+            FileFormatDetection::Supported(FileFormat::PcapLegacy)
+        );
+    }
+
     fn write_non_utf8_file(dir: &Path, file_name: &str) -> PathBuf {
         let path = dir.join(file_name);
         fs::write(&path, [0xff, 0xfe, b'a', 0x00]).unwrap();
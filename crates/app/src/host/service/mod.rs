@@ -40,8 +40,9 @@ use crate::{
                     DltParserConfig, ParserConfig, PluginParserConfig, someip::SomeIpParserConfig,
                 },
                 sources::{
-                    ByteSourceConfig, ProcessConfig, SerialConfig, SourceFileInfo, StreamConfig,
-                    TcpConfig, UdpConfig,
+                    ByteSourceConfig, HttpStreamConfig, MqttConfig, PluginSourceConfig,
+                    ProcessConfig, ReplayConfig, SerialConfig, SocketCanConfig, SourceFileInfo,
+                    StreamConfig, TcpConfig, UdpConfig, UnixSocketConfig, WebSocketConfig,
                 },
             },
             state::plugin::PluginsState,
@@ -215,6 +216,9 @@ impl HostService {
             HostCommand::ConnectionSessionSetup { stream, parser } => {
                 self.connection_session_setup(stream, parser).await
             }
+            HostCommand::PluginSourceSessionSetup { parser } => {
+                self.plugin_source_session_setup(parser).await
+            }
             HostCommand::DltStatistics(statistics_param) => {
                 let DltStatisticsParam {
                     session_setup_id,
@@ -237,10 +241,12 @@ impl HostService {
                 let StartSessionParam {
                     parser,
                     source,
+                    recording,
                     session_setup_id,
                 } = *start_params;
 
-                self.start_session(source, parser, session_setup_id).await?;
+                self.start_session(source, parser, recording, session_setup_id)
+                    .await?;
             }
             HostCommand::OpenRecentSession(params) => {
                 let session_setup_id = params.session_setup_id;
@@ -420,6 +426,9 @@ impl HostService {
             RecentSessionOpenRequest::OpenStreamSetup { stream, parser } => {
                 self.connection_session_setup(stream, parser).await;
             }
+            RecentSessionOpenRequest::OpenPluginSourceSetup { parser } => {
+                self.plugin_source_session_setup(parser).await;
+            }
         }
 
         Ok(())
@@ -428,6 +437,11 @@ impl HostService {
     async fn open_single_file(&self, file_path: PathBuf) -> Result<(), HostError> {
         log::trace!("Opening file: {}", file_path.display());
 
+        if file::is_archive(&file_path).map_err(InitSessionError::IO)? {
+            // Archive members are selected in the multiple files setup.
+            return self.open_multi_files(vec![file_path]).await;
+        }
+
         let format = match file::detect_file_format(&file_path).map_err(InitSessionError::IO)? {
             file::FileFormatDetection::Supported(format) => format,
             file::FileFormatDetection::UnsupportedTextEncoding => {
@@ -499,7 +513,7 @@ impl HostService {
 
     async fn open_files_with_plugin(&self, paths: Vec<PathBuf>) -> Result<(), HostError> {
         let files = tokio::task::spawn_blocking(move || {
-            paths
+            file::expand_archives(paths)
                 .into_iter()
                 .map(|path| {
                     file::detect_file_format(&path).map(|detection| {
@@ -543,7 +557,7 @@ impl HostService {
     pub async fn open_multi_files(&self, paths: Vec<PathBuf>) -> Result<(), HostError> {
         let (files, unsupported_text_files): (Vec<(PathBuf, FileFormat)>, Vec<PathBuf>) =
             tokio::task::spawn_blocking(move || {
-                paths
+                file::expand_archives(paths)
                     .into_iter()
                     .filter_map(|path| {
                         let detection = file::detect_file_format(&path)
@@ -582,6 +596,11 @@ impl HostService {
         }
 
         if files.is_empty() {
+            self.communication
+                .senders
+                .send_notification(AppNotification::Info("No files has been found".into()))
+                .await;
+
             return Ok(());
         }
 
@@ -720,6 +739,7 @@ impl HostService {
                     let origin = ObserveOptions {
                         origin: ObserveOrigin::Concat(files),
                         parser: ParserType::Text(()),
+                        recording: None,
                     };
 
                     let session = match SessionService::spawn(
@@ -768,16 +788,37 @@ impl HostService {
             StreamNames::Serial => {
                 ByteSourceConfig::Stream(StreamConfig::Serial(SerialConfig::new()))
             }
+            StreamNames::UnixSocket => {
+                ByteSourceConfig::Stream(StreamConfig::UnixSocket(UnixSocketConfig::new()))
+            }
+            StreamNames::Replay => {
+                ByteSourceConfig::Stream(StreamConfig::Replay(ReplayConfig::new()))
+            }
+            StreamNames::Mqtt => ByteSourceConfig::Stream(StreamConfig::Mqtt(MqttConfig::new())),
+            StreamNames::WebSocket => {
+                ByteSourceConfig::Stream(StreamConfig::WebSocket(WebSocketConfig::new()))
+            }
+            StreamNames::HttpStream => {
+                ByteSourceConfig::Stream(StreamConfig::HttpStream(HttpStreamConfig::new()))
+            }
+            StreamNames::SocketCan => {
+                ByteSourceConfig::Stream(StreamConfig::SocketCan(SocketCanConfig::new()))
+            }
         };
 
-        let parser = match parser {
-            ParserNames::Dlt => ParserConfig::Dlt(Box::new(DltParserConfig::new(false, None))),
-            ParserNames::SomeIP => ParserConfig::SomeIP(Box::new(SomeIpParserConfig::new(None))),
-            ParserNames::Text => ParserConfig::Text,
-            ParserNames::Plugins => ParserConfig::Plugins(Box::new(PluginParserConfig::new())),
-        };
+        let session_setup =
+            SessionSetupState::new(Uuid::new_v4(), source_type, Self::stream_parser(parser));
 
-        let session_setup = SessionSetupState::new(Uuid::new_v4(), source_type, parser);
+        self.communication
+            .senders
+            .send_message(HostMessage::SessionSetupOpened(Box::new(session_setup)))
+            .await;
+    }
+
+    async fn plugin_source_session_setup(&self, parser: ParserNames) {
+        let source = ByteSourceConfig::Plugin(PluginSourceConfig::new());
+        let session_setup =
+            SessionSetupState::new(Uuid::new_v4(), source, Self::stream_parser(parser));
 
         self.communication
             .senders
@@ -785,6 +826,16 @@ impl HostService {
             .await;
     }
 
+    /// Creates the default configurations of parsers for sources without files.
+    fn stream_parser(parser: ParserNames) -> ParserConfig {
+        match parser {
+            ParserNames::Dlt => ParserConfig::Dlt(Box::new(DltParserConfig::new(false, None))),
+            ParserNames::SomeIP => ParserConfig::SomeIP(Box::new(SomeIpParserConfig::new(None))),
+            ParserNames::Text => ParserConfig::Text,
+            ParserNames::Plugins => ParserConfig::Plugins(Box::new(PluginParserConfig::new())),
+        }
+    }
+
     async fn collect_dlt_statistics(
         &self,
         setup_session_id: Uuid,
@@ -971,6 +1022,7 @@ impl HostService {
         &self,
         source: ByteSourceConfig,
         parser: ParserConfig,
+        recording: Option<stypes::StreamRecording>,
         session_setup_id: Option<Uuid>,
     ) -> Result<(), HostError> {
         let origin = match source {
@@ -998,6 +1050,39 @@ impl HostService {
             ByteSourceConfig::Stream(StreamConfig::Serial(config)) => {
                 ObserveOrigin::Stream(Uuid::new_v4().to_string(), Transport::Serial(config.into()))
             }
+            ByteSourceConfig::Stream(StreamConfig::UnixSocket(config)) => ObserveOrigin::Stream(
+                Uuid::new_v4().to_string(),
+                Transport::UnixSocket(config.into()),
+            ),
+            ByteSourceConfig::Stream(StreamConfig::Replay(config)) => {
+                ObserveOrigin::Stream(Uuid::new_v4().to_string(), Transport::Replay(config.into()))
+            }
+            ByteSourceConfig::Stream(StreamConfig::Mqtt(config)) => {
+                ObserveOrigin::Stream(Uuid::new_v4().to_string(), Transport::Mqtt(config.into()))
+            }
+            ByteSourceConfig::Stream(StreamConfig::WebSocket(config)) => ObserveOrigin::Stream(
+                Uuid::new_v4().to_string(),
+                Transport::WebSocket(config.into()),
+            ),
+            ByteSourceConfig::Stream(StreamConfig::HttpStream(config)) => ObserveOrigin::Stream(
+                Uuid::new_v4().to_string(),
+                Transport::HttpStream(config.into()),
+            ),
+            ByteSourceConfig::Stream(StreamConfig::SocketCan(config)) => ObserveOrigin::Stream(
+                Uuid::new_v4().to_string(),
+                Transport::SocketCan(config.into()),
+            ),
+            ByteSourceConfig::Plugin(config) => {
+                let settings = config.source_settings().map_err(|_| {
+                    let errors = config.validation_errors().join(", ");
+                    let message =
+                        format!("Plugin source configuration is invalid. Errors: {errors}");
+                    let init_error = InitSessionError::Other(message);
+                    HostError::InitSessionError(init_error)
+                })?;
+
+                ObserveOrigin::Plugin(Uuid::new_v4().to_string(), settings)
+            }
         };
 
         let parser = match parser {
@@ -1099,7 +1184,11 @@ impl HostService {
         };
 
         let schema_spec = self.schema_spec_for_parser(&parser)?;
-        let origin = ObserveOptions { origin, parser };
+        let origin = ObserveOptions {
+            origin,
+            parser,
+            recording,
+        };
 
         let session = SessionService::spawn(
             self.communication.senders.get_shared_senders(),
@@ -1142,7 +1231,7 @@ impl HostService {
     }
 
     fn is_dlt_file(file_path: &Path) -> bool {
-        file_path
+        file_tools::strip_compression_extension(file_path)
             .extension()
             .is_some_and(|ext| ext.eq_ignore_ascii_case("dlt"))
     }
@@ -251,6 +251,7 @@ fn parse_legacy_collection(content: &str) -> Result<LegacyCollectionOutcome, Jso
         name,
         filters,
         search_values,
+        query: None,
     };
     let outcome = LegacyCollectionOutcome::Preset { preset, warnings };
 
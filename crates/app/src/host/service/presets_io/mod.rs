@@ -12,7 +12,7 @@ use std::fmt;
 
 use serde_json::Value;
 
-use processor::search::filter::SearchFilter;
+use processor::search::{filter::SearchFilter, query::FilterQuery};
 
 use crate::{
     common::validation::{ValidationEligibility, validate_filter, validate_search_value_filter},
@@ -167,6 +167,13 @@ pub fn validate_filter_entry(preset_name: &str, filter: &SearchFilter) -> Result
     }
 }
 
+/// Validates the boolean filter query of a named preset.
+pub fn validate_query(preset_name: &str, text: &str) -> Result<(), String> {
+    text.parse::<FilterQuery>()
+        .map(|_| ())
+        .map_err(|reason| format!("invalid query in preset '{preset_name}': {reason}"))
+}
+
 /// Validates a search-value row for a named preset.
 pub fn validate_search_value_entry(preset_name: &str, filter: &SearchFilter) -> Result<(), String> {
     match validate_search_value_filter(filter) {
@@ -1,7 +1,7 @@
 //! Current preset document serialization and import.
 //!
 //! Version 2 documents store named filter and search-value row snapshots,
-//! including enabled state and colors.
+//! including enabled state and colors, and optionally the boolean filter query.
 
 use processor::search::filter::SearchFilter;
 use serde::{Deserialize, Serialize};
@@ -10,11 +10,11 @@ use uuid::Uuid;
 
 use crate::host::{
     common::colors::{ColorPair, StoredColorPair, StoredRgba, color_from_rgba, color_to_rgba},
-    ui::registry::presets::{Preset, PresetFilterEntry, PresetSearchValueEntry},
+    ui::registry::presets::{Preset, PresetFilterEntry, PresetQuery, PresetSearchValueEntry},
 };
 
 use super::{
-    DOCUMENT_KIND, DOCUMENT_VERSION, validate_filter_entry, validate_name,
+    DOCUMENT_KIND, DOCUMENT_VERSION, validate_filter_entry, validate_name, validate_query,
     validate_search_value_entry,
 };
 
@@ -24,6 +24,15 @@ struct DocumentPreset {
     name: String,
     filters: Vec<DocumentFilterEntry>,
     search_values: Vec<DocumentSearchValueEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query: Option<DocumentQuery>,
+}
+
+/// Boolean filter query stored in the current preset document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct DocumentQuery {
+    text: String,
+    enabled: bool,
 }
 
 /// Filter row payload stored in the current preset document.
@@ -86,6 +95,7 @@ fn validate_preset(preset: &DocumentPreset) -> Result<(), String> {
         name,
         filters,
         search_values,
+        query,
     } = preset;
 
     validate_name(name)?;
@@ -108,6 +118,10 @@ fn validate_preset(preset: &DocumentPreset) -> Result<(), String> {
         validate_search_value_entry(name, filter)?;
     }
 
+    if let Some(DocumentQuery { text, enabled: _ }) = query {
+        validate_query(name, text)?;
+    }
+
     Ok(())
 }
 
@@ -125,6 +139,7 @@ impl From<Preset> for DocumentPreset {
                 .into_iter()
                 .map(DocumentSearchValueEntry::from)
                 .collect(),
+            query: value.query.map(DocumentQuery::from),
         }
     }
 }
@@ -166,6 +181,25 @@ impl From<DocumentPreset> for Preset {
                 .into_iter()
                 .map(PresetSearchValueEntry::from)
                 .collect(),
+            query: value.query.map(PresetQuery::from),
+        }
+    }
+}
+
+impl From<PresetQuery> for DocumentQuery {
+    fn from(value: PresetQuery) -> Self {
+        Self {
+            text: value.text,
+            enabled: value.enabled,
+        }
+    }
+}
+
+impl From<DocumentQuery> for PresetQuery {
+    fn from(value: DocumentQuery) -> Self {
+        Self {
+            text: value.text,
+            enabled: value.enabled,
         }
     }
 }
@@ -193,7 +227,7 @@ mod tests {
         common::colors::ColorPair,
         message::ImportFormat,
         service::presets_io::{import_named_presets, serialize_named_presets},
-        ui::registry::presets::{Preset, PresetFilterEntry, PresetSearchValueEntry},
+        ui::registry::presets::{Preset, PresetFilterEntry, PresetQuery, PresetSearchValueEntry},
     };
 
     fn plain(value: &str) -> SearchFilter {
@@ -255,6 +289,10 @@ mod tests {
                 false,
                 Color32::from_rgba_unmultiplied(9, 10, 11, 12),
             )],
+            query: Some(PresetQuery {
+                text: "error AND NOT heartbeat".to_owned(),
+                enabled: false,
+            }),
         }];
 
         let json = serialize_named_presets(source.clone()).unwrap();
@@ -273,6 +311,7 @@ mod tests {
             parsed.presets[0].search_values[0].color,
             source[0].search_values[0].color
         );
+        assert_eq!(parsed.presets[0].query, source[0].query);
     }
 
     #[test]
@@ -291,6 +330,14 @@ mod tests {
         .unwrap_err();
     }
 
+    #[test]
+    fn rejects_invalid_query() {
+        import_named_presets(
+            r#"{"kind":"chipmunk_named_presets","version":2,"presets":[{"name":"Broken","filters":[],"search_values":[],"query":{"text":"error AND (","enabled":true}}]}"#,
+        )
+        .unwrap_err();
+    }
+
     #[test]
     fn rejects_invalid_search_value() {
         import_named_presets(
@@ -315,6 +362,7 @@ mod tests {
         assert!(!json.contains("\"searchValues\""));
         assert!(!json.contains("\"content\""));
         assert!(!json.contains("\"uuid\""));
+        assert!(!json.contains("\"query\""));
     }
 
     #[test]
@@ -12,8 +12,9 @@ use serde_json::{Map, Value, from_value};
 
 use stypes::{
     DltParserSettings, FileFormat, MulticastInfo, ParserType, PluginParserGeneralSettings,
-    PluginParserSettings, ProcessTransportConfig, SerialTransportConfig, SomeIpParserSettings,
-    TCPTransportConfig, Transport, UDPTransportConfig,
+    PluginParserSettings, ProcessOutputMode, ProcessRestartPolicy, ProcessTransportConfig,
+    SerialReadMode, SerialReconnect, SerialTransportConfig, SomeIpParserSettings,
+    TCPTransportConfig, TcpMode, Transport, UDPTransportConfig,
 };
 
 use crate::host::{
@@ -163,10 +164,13 @@ fn parse_stream_variant(variant: &str, payload: &Value) -> Result<RecentSessionS
         "Process" => Transport::Process(parse_process_config(payload)?),
         "TCP" => Transport::TCP(TCPTransportConfig {
             bind_addr: parse_bind_addr(payload)?,
+            mode: TcpMode::Connect,
         }),
         "UDP" => Transport::UDP(UDPTransportConfig {
             bind_addr: parse_bind_addr(payload)?,
             multicast: parse_multicast(payload),
+            allowed_senders: Vec::new(),
+            split_senders: false,
         }),
         "Serial" => Transport::Serial(parse_serial_config(payload)?),
         other => return Err(format!("unsupported stream source {other}")),
@@ -188,6 +192,10 @@ fn parse_process_config(payload: &Value) -> Result<ProcessTransportConfig, Strin
             cwd: PathBuf::new(),
             command: command.to_owned(),
             shell: None,
+            envs: Vec::new(),
+            output: ProcessOutputMode::default(),
+            pty: false,
+            restart: ProcessRestartPolicy::default(),
         });
     }
 
@@ -205,6 +213,10 @@ fn parse_process_config(payload: &Value) -> Result<ProcessTransportConfig, Strin
         cwd,
         command,
         shell: None,
+        envs: Vec::new(),
+        output: ProcessOutputMode::default(),
+        pty: false,
+        restart: ProcessRestartPolicy::default(),
     })
 }
 
@@ -279,6 +291,8 @@ fn parse_serial_config(payload: &Value) -> Result<SerialTransportConfig, String>
         stop_bits: u8_field(object, &["stop_bits", "stopBits"]).unwrap_or(1),
         send_data_delay: u8_field(object, &["send_data_delay", "sendDataDelay"]).unwrap_or(0),
         exclusive: bool_field(object, &["exclusive"]).unwrap_or(false),
+        read_mode: SerialReadMode::default(),
+        reconnect: SerialReconnect::default(),
     })
 }
 
@@ -292,6 +306,8 @@ fn default_serial_config(path: String) -> SerialTransportConfig {
         stop_bits: 1,
         send_data_delay: 0,
         exclusive: false,
+        read_mode: SerialReadMode::default(),
+        reconnect: SerialReconnect::default(),
     }
 }
 
@@ -420,7 +436,7 @@ pub fn match_source_from_sources(sources: &[RecentSessionSource]) -> Option<Matc
             .iter()
             .map(|source| match source {
                 RecentSessionSource::File { path, .. } => Some(normalize_path(path)),
-                RecentSessionSource::Stream { .. } => None,
+                RecentSessionSource::Stream { .. } | RecentSessionSource::Plugin { .. } => None,
             })
             .collect::<Option<Vec<_>>>()
             .map(MatchSource::Files),
@@ -438,8 +454,16 @@ pub fn match_source_from_sources(sources: &[RecentSessionSource]) -> Option<Matc
             Transport::Serial(config) => Some(MatchSource::Serial {
                 path: Some(config.path.clone()),
             }),
+            // Legacy history doesn't contain Unix socket, replay, MQTT, web or CAN sources.
+            Transport::UnixSocket(_)
+            | Transport::Replay(_)
+            | Transport::Mqtt(_)
+            | Transport::WebSocket(_)
+            | Transport::HttpStream(_)
+            | Transport::SocketCan(_) => None,
         },
-        RecentSessionSource::Stream { .. } => None,
+        // Legacy history doesn't contain plugin sources either.
+        RecentSessionSource::Stream { .. } | RecentSessionSource::Plugin { .. } => None,
     }
 }
 
@@ -604,7 +628,9 @@ mod tests {
             .iter()
             .map(|source| match source {
                 RecentSessionSource::File { path, .. } => path.clone(),
-                RecentSessionSource::Stream { .. } => panic!("expected file source"),
+                RecentSessionSource::Stream { .. } | RecentSessionSource::Plugin { .. } => {
+                    panic!("expected file source")
+                }
             })
             .collect::<Vec<_>>();
 
@@ -780,7 +780,10 @@ mod tests {
     use std::path::PathBuf;
 
     use serde_json::json;
-    use stypes::{FileFormat, ProcessTransportConfig, TCPTransportConfig, Transport};
+    use stypes::{
+        FileFormat, ProcessOutputMode, ProcessRestartPolicy, ProcessTransportConfig,
+        TCPTransportConfig, TcpMode, Transport,
+    };
 
     use super::*;
 
@@ -1120,6 +1123,7 @@ mod tests {
         let sources = vec![RecentSessionSource::Stream {
             transport: Transport::TCP(TCPTransportConfig {
                 bind_addr: String::from("127.0.0.1:5000"),
+                mode: TcpMode::Connect,
             }),
         }];
         let history = LegacyHistory {
@@ -1155,6 +1159,10 @@ mod tests {
                 cwd: PathBuf::from("/home/user/"),
                 command: String::from("ls"),
                 shell: None,
+                envs: Vec::new(),
+                output: ProcessOutputMode::default(),
+                pty: false,
+                restart: ProcessRestartPolicy::default(),
             }),
         }];
         let relation_ids = HashSet::from([String::from("definition-id")]);
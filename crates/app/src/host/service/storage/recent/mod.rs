@@ -57,6 +57,8 @@ pub enum RecentSessionOpenRequest {
         stream: StreamNames,
         parser: ParserNames,
     },
+    /// Reopen a byte-source plugin by opening its setup flow with the preselected parser.
+    OpenPluginSourceSetup { parser: ParserNames },
 }
 
 /// Loads and normalizes recent sessions.
@@ -199,7 +201,7 @@ fn open_recent_session_clean(
                 .into_iter()
                 .map(|source| match source {
                     RecentSessionSource::File { path, .. } => Some(path),
-                    RecentSessionSource::Stream { .. } => None,
+                    RecentSessionSource::Stream { .. } | RecentSessionSource::Plugin { .. } => None,
                 })
                 .collect::<Option<Vec<_>>>()
                 .ok_or_else(|| {
@@ -222,6 +224,12 @@ fn open_recent_session_clean(
                 Transport::TCP(_) => StreamNames::Tcp,
                 Transport::UDP(_) => StreamNames::Udp,
                 Transport::Serial(_) => StreamNames::Serial,
+                Transport::UnixSocket(_) => StreamNames::UnixSocket,
+                Transport::Replay(_) => StreamNames::Replay,
+                Transport::Mqtt(_) => StreamNames::Mqtt,
+                Transport::WebSocket(_) => StreamNames::WebSocket,
+                Transport::HttpStream(_) => StreamNames::HttpStream,
+                Transport::SocketCan(_) => StreamNames::SocketCan,
             };
             Ok(RecentSessionOpenRequest::OpenStreamSetup { stream, parser })
         }
@@ -230,6 +238,9 @@ fn open_recent_session_clean(
                 "Clean open is not supported for multiple stream sources.".into(),
             )))
         }
+        RecentSessionSource::Plugin { .. } => {
+            Ok(RecentSessionOpenRequest::OpenPluginSourceSetup { parser })
+        }
     }
 }
 
@@ -316,7 +327,9 @@ mod tests {
     };
 
     use processor::search::filter::SearchFilter;
-    use stypes::{ObserveOptions, ObserveOrigin, ParserType, TCPTransportConfig, Transport};
+    use stypes::{
+        ObserveOptions, ObserveOrigin, ParserType, TCPTransportConfig, TcpMode, Transport,
+    };
     use tempfile::{TempDir, tempdir};
 
     use crate::{
@@ -477,9 +490,13 @@ mod tests {
         snapshot_from_observe_options(ObserveOptions {
             origin: ObserveOrigin::Stream(
                 String::new(),
-                Transport::TCP(TCPTransportConfig { bind_addr }),
+                Transport::TCP(TCPTransportConfig {
+                    bind_addr,
+                    mode: TcpMode::Connect,
+                }),
             ),
             parser: ParserType::Text(()),
+            recording: None,
         })
     }
 
@@ -959,11 +976,13 @@ mod tests {
                 RecentSessionSource::Stream {
                     transport: Transport::TCP(TCPTransportConfig {
                         bind_addr: String::from("127.0.0.1:5000"),
+                        mode: TcpMode::Connect,
                     }),
                 },
                 RecentSessionSource::Stream {
                     transport: Transport::TCP(TCPTransportConfig {
                         bind_addr: String::from("127.0.0.1:5001"),
+                        mode: TcpMode::Connect,
                     }),
                 },
             ],
@@ -1008,6 +1027,7 @@ mod tests {
                 ),
             ]),
             parser: ParserType::Text(()),
+            recording: None,
         });
 
         let request = resolve_open_request(OpenRecentSessionParam {
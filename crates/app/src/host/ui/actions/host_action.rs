@@ -6,4 +6,6 @@ use uuid::Uuid;
 pub enum HostAction {
     /// Request the host to close a specific session.
     CloseSession(Uuid),
+    /// Request the host to activate a session and focus a row of its main logs table.
+    FocusSessionRow { session_id: Uuid, row: u64 },
 }
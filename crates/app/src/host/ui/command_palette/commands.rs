@@ -133,6 +133,41 @@ const COMMANDS: &[CommandDefinition] = &[
             parser: ParserNames::Text,
         },
     },
+    CommandDefinition {
+        title: "Unix Socket with Plain Text",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::UnixSocket,
+            parser: ParserNames::Text,
+        },
+    },
+    CommandDefinition {
+        title: "MQTT with Plain Text",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::Mqtt,
+            parser: ParserNames::Text,
+        },
+    },
+    CommandDefinition {
+        title: "WebSocket with Plain Text",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::WebSocket,
+            parser: ParserNames::Text,
+        },
+    },
+    CommandDefinition {
+        title: "HTTP Stream with Plain Text",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::HttpStream,
+            parser: ParserNames::Text,
+        },
+    },
+    CommandDefinition {
+        title: "SocketCAN with Plain Text",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::SocketCan,
+            parser: ParserNames::Text,
+        },
+    },
     CommandDefinition {
         title: "TCP with DLT",
         action: CommandAction::ConnectionSetup {
@@ -154,6 +189,34 @@ const COMMANDS: &[CommandDefinition] = &[
             parser: ParserNames::Dlt,
         },
     },
+    CommandDefinition {
+        title: "Unix Socket with DLT",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::UnixSocket,
+            parser: ParserNames::Dlt,
+        },
+    },
+    CommandDefinition {
+        title: "Replay Trace with DLT",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::Replay,
+            parser: ParserNames::Dlt,
+        },
+    },
+    CommandDefinition {
+        title: "MQTT with DLT",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::Mqtt,
+            parser: ParserNames::Dlt,
+        },
+    },
+    CommandDefinition {
+        title: "WebSocket with DLT",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::WebSocket,
+            parser: ParserNames::Dlt,
+        },
+    },
     CommandDefinition {
         title: "TCP with SomeIP",
         action: CommandAction::ConnectionSetup {
@@ -175,6 +238,20 @@ const COMMANDS: &[CommandDefinition] = &[
             parser: ParserNames::SomeIP,
         },
     },
+    CommandDefinition {
+        title: "Replay Trace with SomeIP",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::Replay,
+            parser: ParserNames::SomeIP,
+        },
+    },
+    CommandDefinition {
+        title: "MQTT with SomeIP",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::Mqtt,
+            parser: ParserNames::SomeIP,
+        },
+    },
     CommandDefinition {
         title: "Terminal with Plugin",
         action: CommandAction::ConnectionSetup {
@@ -203,6 +280,46 @@ const COMMANDS: &[CommandDefinition] = &[
             parser: ParserNames::Plugins,
         },
     },
+    CommandDefinition {
+        title: "MQTT with Plugin",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::Mqtt,
+            parser: ParserNames::Plugins,
+        },
+    },
+    CommandDefinition {
+        title: "WebSocket with Plugin",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::WebSocket,
+            parser: ParserNames::Plugins,
+        },
+    },
+    CommandDefinition {
+        title: "HTTP Stream with Plugin",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::HttpStream,
+            parser: ParserNames::Plugins,
+        },
+    },
+    CommandDefinition {
+        title: "SocketCAN with Plugin",
+        action: CommandAction::ConnectionSetup {
+            stream: StreamNames::SocketCan,
+            parser: ParserNames::Plugins,
+        },
+    },
+    CommandDefinition {
+        title: "Byte-Source Plugin with DLT",
+        action: CommandAction::PluginSourceSetup {
+            parser: ParserNames::Dlt,
+        },
+    },
+    CommandDefinition {
+        title: "Byte-Source Plugin with Plain Text",
+        action: CommandAction::PluginSourceSetup {
+            parser: ParserNames::Text,
+        },
+    },
 ];
 
 /// Host-level action launched from a command-palette result.
@@ -230,6 +347,9 @@ pub enum CommandAction {
         stream: StreamNames,
         parser: ParserNames,
     },
+    PluginSourceSetup {
+        parser: ParserNames,
+    },
 }
 
 /// Active-tab scope used to select commands when the palette opens.
@@ -259,7 +379,8 @@ impl CommandAction {
             | Self::ToggleRightPanel
             | Self::ToggleBottomPanel
             | Self::ToggleSdeBar
-            | Self::ConnectionSetup { .. } => CommandScope::Global,
+            | Self::ConnectionSetup { .. }
+            | Self::PluginSourceSetup { .. } => CommandScope::Global,
         }
     }
 }
@@ -411,6 +532,9 @@ pub fn execute_action(
             cmd_tx,
             HostCommand::ConnectionSessionSetup { stream, parser },
         ),
+        CommandAction::PluginSourceSetup { parser } => {
+            actions.try_send_command(cmd_tx, HostCommand::PluginSourceSessionSetup { parser })
+        }
     }
 }
 
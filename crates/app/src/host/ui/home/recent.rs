@@ -438,7 +438,7 @@ fn matches_recent_session_query(
 mod tests {
     use std::path::PathBuf;
 
-    use stypes::{FileFormat, ParserType, TCPTransportConfig, Transport};
+    use stypes::{FileFormat, ParserType, TCPTransportConfig, TcpMode, Transport};
 
     use super::{RecentSessionsUi, SelectionDirection, matches_recent_session_query};
     use crate::{
@@ -467,6 +467,7 @@ mod tests {
             vec![RecentSessionSource::Stream {
                 transport: Transport::TCP(TCPTransportConfig {
                     bind_addr: bind_addr.to_owned(),
+                    mode: TcpMode::Connect,
                 }),
             }],
             ParserType::Text(()),
@@ -158,6 +158,17 @@ impl MainMenuBar {
                     tabs.open_plugin_manager();
                     ui.close();
                 }
+
+                ui.separator();
+
+                if ui.button("Open Byte-Source Plugin").clicked() {
+                    actions.try_send_command(
+                        &self.cmd_tx,
+                        HostCommand::PluginSourceSessionSetup {
+                            parser: ParserNames::Text,
+                        },
+                    );
+                }
             });
 
             if let HostTab::Session(session) = tabs.active_mut() {
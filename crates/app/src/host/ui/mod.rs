@@ -158,6 +158,7 @@ impl Host {
                     HostCommand::StartSession(Box::new(StartSessionParam {
                         parser: ParserConfig::Text,
                         source: ByteSourceConfig::Stream(StreamConfig::Process(config)),
+                        recording: None,
                         session_setup_id: None,
                     }))
                 }
@@ -444,6 +445,9 @@ impl Host {
                         &mut self.ui_actions,
                     );
                 }
+                HostAction::FocusSessionRow { session_id, row } => {
+                    self.tabs.focus_session_row(session_id, row);
+                }
             }
         }
 
@@ -195,7 +195,15 @@ fn source_badge_icon(session: &RecentSessionSnapshot) -> &'static str {
             stypes::Transport::TCP(_) => icons::regular::PLUGS_CONNECTED,
             stypes::Transport::UDP(_) => icons::regular::BROADCAST,
             stypes::Transport::Serial(_) => icons::regular::USB,
+            stypes::Transport::UnixSocket(_) => icons::regular::PLUGS_CONNECTED,
+            stypes::Transport::Replay(_) => icons::regular::PLAY,
+            stypes::Transport::Mqtt(_) => icons::regular::CLOUD,
+            stypes::Transport::WebSocket(_) | stypes::Transport::HttpStream(_) => {
+                icons::regular::GLOBE
+            }
+            stypes::Transport::SocketCan(_) => icons::regular::CPU,
         },
+        Some(RecentSessionSource::Plugin { .. }) => icons::regular::PLUG,
         None => icons::regular::FILE,
     }
 }
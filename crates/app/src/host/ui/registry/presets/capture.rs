@@ -4,10 +4,10 @@ use uuid::Uuid;
 
 use crate::{host::ui::registry::filters::FilterRegistry, session::ui::SessionShared};
 
-use super::{PresetFilterEntry, PresetRegistry, PresetSearchValueEntry};
+use super::{PresetFilterEntry, PresetQuery, PresetRegistry, PresetSearchValueEntry};
 
 impl PresetRegistry {
-    /// Captures the current session-applied filters and charts as a preset.
+    /// Captures the current session-applied filters, charts and query as a preset.
     pub fn add_preset_from_session(
         &mut self,
         shared: &SessionShared,
@@ -33,8 +33,17 @@ impl PresetRegistry {
                 })
             })
             .collect();
-
-        self.add_preset(shared.get_info().title.clone(), filters, search_values)
+        let query = shared.filters.query.as_ref().map(|query| PresetQuery {
+            text: query.text().to_owned(),
+            enabled: query.enabled(),
+        });
+
+        self.add_preset_with_query(
+            shared.get_info().title.clone(),
+            filters,
+            search_values,
+            query,
+        )
     }
 }
 
@@ -172,5 +181,28 @@ mod tests {
             ),
         ];
         assert_eq!(preset.search_values, expected_search_values);
+        assert_eq!(preset.query, None);
+    }
+
+    #[test]
+    fn captures_session_query() {
+        let mut shared = new_shared();
+        let filters_registry = FilterRegistry::default();
+        let mut preset_registry = PresetRegistry::default();
+        shared
+            .filters
+            .set_query_text("error AND NOT heartbeat".to_owned());
+        shared.filters.set_query_enabled(false);
+
+        let preset_id = preset_registry.add_preset_from_session(&shared, &filters_registry);
+        let preset = preset_registry.get(&preset_id).unwrap();
+
+        assert_eq!(
+            preset.query,
+            Some(PresetQuery {
+                text: "error AND NOT heartbeat".to_owned(),
+                enabled: false,
+            })
+        );
     }
 }
@@ -4,7 +4,7 @@ use std::borrow::Cow;
 
 use uuid::Uuid;
 
-use super::{Preset, PresetFilterEntry, PresetSearchValueEntry};
+use super::{Preset, PresetFilterEntry, PresetQuery, PresetSearchValueEntry};
 
 /// Host-level registry for named preset snapshots captured from session filters and charts.
 #[derive(Debug, Default, Clone)]
@@ -53,6 +53,7 @@ impl PresetRegistry {
                 name,
                 filters: _,
                 search_values: _,
+                query: _,
             } = preset;
             Some(*id) != skip_id && name == base_name
         }) {
@@ -68,6 +69,7 @@ impl PresetRegistry {
                     name,
                     filters: _,
                     search_values: _,
+                    query: _,
                 } = preset;
                 Some(*id) != skip_id && name == &candidate
             }) {
@@ -88,6 +90,17 @@ impl PresetRegistry {
         name: impl Into<String>,
         filters: Vec<PresetFilterEntry>,
         search_values: Vec<PresetSearchValueEntry>,
+    ) -> Uuid {
+        self.add_preset_with_query(name, filters, search_values, None)
+    }
+
+    /// Adds a new preset including the filter query and returns its generated id.
+    pub fn add_preset_with_query(
+        &mut self,
+        name: impl Into<String>,
+        filters: Vec<PresetFilterEntry>,
+        search_values: Vec<PresetSearchValueEntry>,
+        query: Option<PresetQuery>,
     ) -> Uuid {
         let name = name.into();
         let name = self.unique_preset_name(&name, None).into_owned();
@@ -96,6 +109,7 @@ impl PresetRegistry {
             name,
             filters,
             search_values,
+            query,
         };
         let id = preset.id;
         self.presets.push(preset);
@@ -149,6 +163,7 @@ impl PresetRegistry {
             name,
             filters: stored_filters,
             search_values: stored_search_values,
+            query: _,
         } = preset;
         if name == next_name.as_ref()
             && stored_filters == &filters
@@ -5,4 +5,4 @@ mod catalog;
 mod model;
 
 pub use catalog::{PresetRegistry, PresetUpdateOutcome};
-pub use model::{Preset, PresetFilterEntry, PresetSearchValueEntry};
+pub use model::{Preset, PresetFilterEntry, PresetQuery, PresetSearchValueEntry};
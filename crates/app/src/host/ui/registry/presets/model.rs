@@ -17,6 +17,17 @@ pub struct Preset {
     pub filters: Vec<PresetFilterEntry>,
     /// Stored chart/search-value rows.
     pub search_values: Vec<PresetSearchValueEntry>,
+    /// Stored boolean filter query, if the session had one.
+    pub query: Option<PresetQuery>,
+}
+
+/// Stored preset snapshot of the boolean filter query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetQuery {
+    /// Query text in the syntax of [`processor::search::query::FilterQuery`].
+    pub text: String,
+    /// Whether the query was enabled when captured.
+    pub enabled: bool,
 }
 
 /// Stored preset snapshot for one filter row.
@@ -115,6 +126,7 @@ impl Preset {
             name,
             filters,
             search_values,
+            query: None,
         }
     }
 }
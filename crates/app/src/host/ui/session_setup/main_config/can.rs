@@ -0,0 +1,84 @@
+use egui::{Align, TextEdit, Ui};
+
+use crate::{
+    common::ui::visibility_tracker::VisibilityTracker,
+    host::{
+        common::ui_utls::show_validation_message,
+        ui::session_setup::{start_session_on_enter, state::sources::SocketCanConfig},
+    },
+};
+
+use super::RenderOutcome;
+
+pub fn render_connection(
+    config: &mut SocketCanConfig,
+    input_visibility: &mut VisibilityTracker,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    let mut outcome = RenderOutcome::None;
+
+    ui.vertical(|ui| {
+        ui.label("Interface:");
+
+        let interface_res = TextEdit::singleline(&mut config.interface)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .hint_text("can0")
+            .show(ui)
+            .response;
+
+        if input_visibility.is_newly_visible(ui) {
+            interface_res.request_focus();
+        }
+
+        if interface_res.changed() {
+            config.validate();
+        }
+
+        start_session_on_enter(&interface_res, || config.is_valid(), &mut outcome);
+
+        show_validation_message(ui, config.get_interface_err());
+    });
+
+    ui.vertical(|ui| {
+        ui.label("Filters:");
+
+        let filters_res = TextEdit::multiline(&mut config.filters)
+            .desired_width(300.)
+            .desired_rows(2)
+            .hint_text("123:7FF\n18DAF110:1FFFFFFF")
+            .show(ui)
+            .response
+            .on_hover_text(
+                "Filters as ID:MASK separated by commas or new lines, applied by the kernel. \
+                IDs with 8 hex digits match extended frames. All frames are received if empty",
+            );
+
+        if filters_res.changed() {
+            config.validate();
+        }
+
+        show_validation_message(ui, config.get_filters_err());
+    });
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Timestamps:");
+        for timestamps in SocketCanConfig::TIMESTAMPS {
+            ui.radio_value(&mut config.timestamps, timestamps, timestamps.to_string());
+        }
+    })
+    .response
+    .on_hover_text(
+        "Hardware timestamps must be supported and enabled by the driver. \
+        Software timestamps are used for frames without them",
+    );
+
+    ui.add_space(4.);
+    ui.checkbox(&mut config.fd_frames, "CAN FD")
+        .on_hover_text("Receive and send CAN FD frames in addition to classic frames");
+    ui.checkbox(&mut config.error_frames, "Error frames")
+        .on_hover_text("Receive the error frames of the interface, like bus-off");
+
+    outcome
+}
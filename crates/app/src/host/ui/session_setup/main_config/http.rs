@@ -0,0 +1,138 @@
+use egui::{Align, TextEdit, Ui};
+use stypes::{HttpStreamFormat, WebSocketMessageMode};
+
+use crate::{
+    common::ui::visibility_tracker::VisibilityTracker,
+    host::{
+        common::ui_utls::show_validation_message,
+        ui::session_setup::{
+            start_session_on_enter,
+            state::sources::{HttpStreamConfig, WebSocketConfig, http::HttpRequestConfig},
+        },
+    },
+};
+
+use super::RenderOutcome;
+
+pub fn render_websocket_connection(
+    config: &mut WebSocketConfig,
+    input_visibility: &mut VisibilityTracker,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    let outcome = render_request(
+        &mut config.request,
+        "ws://localhost:8080/logs",
+        input_visibility,
+        ui,
+    );
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Messages:");
+        ui.radio_value(&mut config.messages, WebSocketMessageMode::Lines, "Lines")
+            .on_hover_text("Show each message as one line of text");
+        ui.radio_value(&mut config.messages, WebSocketMessageMode::Raw, "Raw")
+            .on_hover_text("Pass the payloads unchanged, e.g. for binary DLT messages");
+    });
+
+    outcome
+}
+
+pub fn render_http_stream_connection(
+    config: &mut HttpStreamConfig,
+    input_visibility: &mut VisibilityTracker,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    let outcome = render_request(
+        &mut config.request,
+        "https://gateway/logs/stream",
+        input_visibility,
+        ui,
+    );
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Body:");
+        ui.radio_value(&mut config.format, HttpStreamFormat::Raw, "Raw")
+            .on_hover_text("Pass the chunks of the response unchanged");
+        ui.radio_value(
+            &mut config.format,
+            HttpStreamFormat::ServerSentEvents,
+            "Server-sent events",
+        )
+        .on_hover_text("Show the data of each event as one line");
+    });
+
+    outcome
+}
+
+fn render_request(
+    request: &mut HttpRequestConfig,
+    url_hint: &str,
+    input_visibility: &mut VisibilityTracker,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    let mut outcome = RenderOutcome::None;
+
+    ui.vertical(|ui| {
+        ui.label("URL:");
+
+        let url_res = TextEdit::singleline(&mut request.url)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .hint_text(url_hint)
+            .show(ui)
+            .response;
+
+        if input_visibility.is_newly_visible(ui) {
+            url_res.request_focus();
+        }
+
+        if url_res.changed() {
+            request.validate();
+        }
+
+        start_session_on_enter(&url_res, || request.is_valid(), &mut outcome);
+
+        show_validation_message(ui, request.get_url_err());
+    });
+
+    ui.vertical(|ui| {
+        ui.label("Headers:");
+
+        let headers_res = TextEdit::multiline(&mut request.headers)
+            .desired_width(300.)
+            .desired_rows(2)
+            .hint_text("X-Api-Key: value")
+            .show(ui)
+            .response
+            .on_hover_text("One 'Name: value' pair per line");
+
+        if headers_res.changed() {
+            request.validate();
+        }
+
+        show_validation_message(ui, request.get_headers_err());
+    });
+
+    ui.vertical(|ui| {
+        ui.label("Bearer Token:");
+
+        let token_res = TextEdit::singleline(&mut request.bearer_token)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .password(true)
+            .hint_text("Optional")
+            .show(ui)
+            .response
+            .on_hover_text("Tokens aren't saved with recent sessions");
+
+        start_session_on_enter(&token_res, || request.is_valid(), &mut outcome);
+    });
+
+    ui.add_space(4.);
+    ui.checkbox(&mut request.reconnect, "Reconnect automatically")
+        .on_hover_text("Connect again once the connection is closed or lost");
+
+    outcome
+}
@@ -13,7 +13,7 @@ use crate::{
         ui::{
             UiActions,
             session_setup::{
-                RenderOutcome, SessionSetup, start_session_on_enter,
+                RenderOutcome, SessionSetup, side_config, start_session_on_enter,
                 state::{
                     SessionSetupState,
                     parsers::ParserConfig,
@@ -26,13 +26,19 @@ use crate::{
     },
 };
 
+pub mod can;
 mod dlt;
+pub mod http;
+pub mod mqtt;
 pub mod process;
 mod recent;
+mod recording;
+pub mod replay;
 mod serial;
 mod someip;
 mod tcp;
 pub mod udp;
+pub mod unix_socket;
 
 impl SessionSetup {
     pub(super) fn render_main_config(
@@ -42,7 +48,12 @@ impl SessionSetup {
         actions: &mut UiActions,
         ui: &mut Ui,
     ) -> RenderOutcome {
-        let SessionSetupState { id, source, parser } = &mut self.state;
+        let SessionSetupState {
+            id,
+            source,
+            parser,
+            recording,
+        } = &mut self.state;
 
         match source {
             ByteSourceConfig::File(file) => render_files(slice::from_ref(file), parser, ui),
@@ -67,6 +78,15 @@ impl SessionSetup {
                         output = render_stream_connection(stream, input_visibility, actions, ui);
                     });
 
+                    // Replayed traces are recordings already.
+                    if !matches!(stream, StreamConfig::Replay(..)) {
+                        get_frame(ui).show(ui, |ui| {
+                            ui.heading("Recording");
+                            ui.add_space(10.);
+                            recording::render_recording(recording, actions, ui);
+                        });
+                    }
+
                     get_frame(ui).show(ui, |ui| {
                         ui.heading("Recent");
                         ui.add_space(10.);
@@ -92,6 +112,23 @@ impl SessionSetup {
 
                 output
             }
+            ByteSourceConfig::Plugin(config) => {
+                ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
+                    general_group_frame(ui)
+                        .inner_margin(Margin::symmetric(10, 4))
+                        .outer_margin(Margin::symmetric(10, 4))
+                        .show(ui, |ui| {
+                            ui.heading("Byte-Source Plugin");
+                            ui.add_space(10.);
+                            // Scope plugin widget IDs to this setup tab.
+                            ui.push_id(*id, |ui| {
+                                side_config::plugins::render_content(config, plugins, actions, ui)
+                            });
+                        });
+                });
+
+                RenderOutcome::None
+            }
         }
     }
 }
@@ -124,6 +161,20 @@ fn render_stream_connection(
         StreamConfig::Tcp(config) => tcp::render_connection(config, input_visibility, ui),
         StreamConfig::Udp(config) => udp::render_connection(config, input_visibility, ui),
         StreamConfig::Serial(config) => serial::render_connection(config, input_visibility, ui),
+        StreamConfig::UnixSocket(config) => {
+            unix_socket::render_connection(config, input_visibility, ui)
+        }
+        StreamConfig::Replay(config) => replay::render_connection(config, actions, ui),
+        StreamConfig::Mqtt(config) => {
+            mqtt::render_connection(config, input_visibility, actions, ui)
+        }
+        StreamConfig::WebSocket(config) => {
+            http::render_websocket_connection(config, input_visibility, ui)
+        }
+        StreamConfig::HttpStream(config) => {
+            http::render_http_stream_connection(config, input_visibility, ui)
+        }
+        StreamConfig::SocketCan(config) => can::render_connection(config, input_visibility, ui),
     }
 }
 
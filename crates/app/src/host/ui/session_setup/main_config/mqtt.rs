@@ -0,0 +1,258 @@
+use std::path::PathBuf;
+
+use egui::{Align, Button, ComboBox, Label, RichText, TextEdit, TextStyle, Ui, Widget, vec2};
+
+use crate::{
+    common::ui::visibility_tracker::VisibilityTracker,
+    host::{
+        common::ui_utls::{show_validation_message, truncate_path_to_width},
+        ui::{
+            UiActions,
+            actions::{FileDialogFilter, FileDialogOptions},
+            session_setup::{start_session_on_enter, state::sources::MqttConfig},
+        },
+    },
+};
+
+use super::RenderOutcome;
+
+const CA_DIALOG_ID: &str = "mqtt_ca_file";
+const CERT_DIALOG_ID: &str = "mqtt_client_cert";
+const KEY_DIALOG_ID: &str = "mqtt_client_key";
+
+pub fn render_connection(
+    config: &mut MqttConfig,
+    input_visibility: &mut VisibilityTracker,
+    actions: &mut UiActions,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    let mut outcome = RenderOutcome::None;
+
+    if let Some((id, paths)) =
+        actions
+            .file_dialog
+            .take_output_many(&[CA_DIALOG_ID, CERT_DIALOG_ID, KEY_DIALOG_ID])
+        && let Some(path) = paths.into_iter().next()
+    {
+        match id {
+            CA_DIALOG_ID => config.ca_file = path,
+            CERT_DIALOG_ID => config.client_cert = path,
+            _ => config.client_key = path,
+        }
+        config.validate();
+    }
+
+    ui.vertical(|ui| {
+        ui.label("Broker URL:");
+
+        let url_res = TextEdit::singleline(&mut config.broker_url)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .hint_text("mqtt://localhost:1883")
+            .show(ui)
+            .response;
+
+        if input_visibility.is_newly_visible(ui) {
+            url_res.request_focus();
+        }
+
+        if url_res.changed() {
+            config.validate();
+        }
+
+        start_session_on_enter(&url_res, || config.is_valid(), &mut outcome);
+
+        show_validation_message(ui, config.get_broker_err());
+    });
+
+    ui.vertical(|ui| {
+        ui.label("Client ID:");
+
+        let id_res = TextEdit::singleline(&mut config.client_id)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .show(ui)
+            .response
+            .on_hover_text("Must be unique among the clients connected to the broker");
+
+        if id_res.changed() {
+            config.validate();
+        }
+
+        start_session_on_enter(&id_res, || config.is_valid(), &mut outcome);
+
+        show_validation_message(ui, config.get_client_id_err());
+    });
+
+    ui.vertical(|ui| {
+        ui.label("Topics:");
+
+        let topics_res = TextEdit::multiline(&mut config.topics)
+            .desired_width(300.)
+            .desired_rows(3)
+            .hint_text("fleet/+/logs\nfleet/diag/#")
+            .show(ui)
+            .response
+            .on_hover_text(
+                "Topic filters separated by commas or new lines. \
+                Wildcards + and # are supported",
+            );
+
+        if topics_res.changed() {
+            config.validate();
+        }
+
+        show_validation_message(ui, config.get_topics_err());
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("QoS:");
+        ComboBox::from_id_salt("mqtt_qos")
+            .selected_text(config.qos.to_string())
+            .show_ui(ui, |ui| {
+                for qos in MqttConfig::QOS {
+                    ui.selectable_value(&mut config.qos, qos, qos.to_string());
+                }
+            });
+    });
+
+    ui.add_space(4.);
+    ui.checkbox(&mut config.split_topics, "Separate source per topic")
+        .on_hover_text(
+            "Show the messages of each topic as their own source. \
+            Otherwise all messages are merged into one source",
+        );
+
+    ui.add_space(4.);
+    ui.vertical(|ui| {
+        ui.label("SDE Publish Topic:");
+
+        let sde_res = TextEdit::singleline(&mut config.sde_topic)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .hint_text("Optional")
+            .show(ui)
+            .response
+            .on_hover_text("Data sent from the SDE bar is published to this topic");
+
+        if sde_res.changed() {
+            config.validate();
+        }
+
+        show_validation_message(ui, config.get_sde_topic_err());
+    });
+
+    ui.collapsing("TLS Certificates", |ui| {
+        ui.label(
+            RichText::new(
+                "Used with mqtts:// brokers. System certificates are used if no CA is set",
+            )
+            .small(),
+        );
+        ui.add_space(4.);
+
+        let mut changed = false;
+        changed |= render_cert_file(
+            ui,
+            "CA Certificate:",
+            &mut config.ca_file,
+            CA_DIALOG_ID,
+            actions,
+        );
+        changed |= render_cert_file(
+            ui,
+            "Client Certificate:",
+            &mut config.client_cert,
+            CERT_DIALOG_ID,
+            actions,
+        );
+        changed |= render_cert_file(
+            ui,
+            "Client Key:",
+            &mut config.client_key,
+            KEY_DIALOG_ID,
+            actions,
+        );
+        if changed {
+            config.validate();
+        }
+
+        show_validation_message(ui, config.get_tls_err());
+    });
+
+    outcome
+}
+
+/// Renders a certificate file row with browse and clear buttons.
+/// Returns `true` if the path has been cleared.
+fn render_cert_file(
+    ui: &mut Ui,
+    label: &str,
+    path: &mut PathBuf,
+    dialog_id: &str,
+    actions: &mut UiActions,
+) -> bool {
+    let mut cleared = false;
+    let current_path = path.clone();
+
+    egui::Sides::new().shrink_left().truncate().show(
+        ui,
+        |ui| {
+            ui.label(label);
+            let path_txt =
+                truncate_path_to_width(ui, &current_path, ui.available_width(), TextStyle::Body);
+            let response = Label::new(path_txt.text)
+                .truncate()
+                .show_tooltip_when_elided(false)
+                .selectable(true)
+                .ui(ui);
+            if path_txt.truncated {
+                response.on_hover_ui(|ui| {
+                    ui.set_max_width(ui.spacing().tooltip_width);
+                    ui.label(current_path.to_string_lossy());
+                });
+            }
+        },
+        |ui| {
+            if !current_path.as_os_str().is_empty() {
+                let clear_txt = RichText::new(egui_phosphor::regular::X)
+                    .size(16.)
+                    .text_style(egui::TextStyle::Button);
+                let clear_btn = Button::new(clear_txt)
+                    .min_size(vec2(12., 20.))
+                    .ui(ui)
+                    .on_hover_text("Clear");
+                if clear_btn.clicked() {
+                    path.clear();
+                    cleared = true;
+                }
+            }
+
+            let open_txt = RichText::new(egui_phosphor::regular::FOLDER_OPEN)
+                .size(16.)
+                .text_style(egui::TextStyle::Button);
+            let open_btn = Button::new(open_txt)
+                .min_size(vec2(12., 20.))
+                .ui(ui)
+                .on_hover_text("Browse");
+
+            if open_btn.clicked() {
+                let mut options = FileDialogOptions::new()
+                    .title(label.trim_end_matches(':'))
+                    .filters(vec![
+                        FileDialogFilter::new(
+                            "PEM files (*.pem, *.crt, *.key)",
+                            vec!["pem".to_owned(), "crt".to_owned(), "key".to_owned()],
+                        ),
+                        FileDialogFilter::new("All files (*)", vec!["*".to_owned()]),
+                    ]);
+                if let Some(parent) = current_path.parent() {
+                    options = options.directory(parent);
+                }
+                actions.file_dialog.pick_file(dialog_id, options);
+            }
+        },
+    );
+
+    cleared
+}
@@ -1,10 +1,16 @@
-use egui::{Align, Button, Label, Layout, Popup, RectAlign, RichText, TextStyle, Ui, Widget, vec2};
+use egui::{
+    Align, Button, ComboBox, Label, Layout, Popup, RectAlign, RichText, TextEdit, TextStyle, Ui,
+    Widget, vec2,
+};
+use stypes::{ProcessOutputMode, ProcessRestartPolicy};
 
 use crate::common::ui::visibility_tracker::VisibilityTracker;
 
 use super::RenderOutcome;
 use crate::host::{
-    common::ui_utls::{sized_singleline_text_edit, truncate_path_to_width},
+    common::ui_utls::{
+        show_validation_message, sized_singleline_text_edit, truncate_path_to_width,
+    },
     ui::{
         UiActions,
         actions::FileDialogOptions,
@@ -36,9 +42,61 @@ pub fn render_connection(
         |ui| working_dir(ui, config, actions, true),
     );
 
+    ui.add_space(10.);
+
+    options(config, ui);
+
     outcome
 }
 
+/// Renders the options for delivering the output, restarting and the environment of
+/// the command.
+pub fn options(config: &mut ProcessConfig, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label("Output:");
+        ui.radio_value(&mut config.output, ProcessOutputMode::Lines, "Lines")
+            .on_hover_text("Lines of stdout and stderr, for text parsing");
+        ui.radio_value(&mut config.output, ProcessOutputMode::Raw, "Raw bytes")
+            .on_hover_text(
+                "Unchanged bytes of stdout, for binary formats like DLT. \
+                Stderr is written to the logs only",
+            );
+    });
+
+    if cfg!(unix) {
+        ui.checkbox(&mut config.pty, "Run in terminal")
+            .on_hover_text("Attach the command to a pseudo-terminal for tools flushing their output on terminals only");
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Restart:");
+        ComboBox::from_id_salt("process_restart_policy")
+            .selected_text(config.restart.to_string())
+            .show_ui(ui, |ui| {
+                for policy in [
+                    ProcessRestartPolicy::Never,
+                    ProcessRestartPolicy::OnFailure,
+                    ProcessRestartPolicy::Always,
+                ] {
+                    ui.selectable_value(&mut config.restart, policy, policy.to_string());
+                }
+            });
+    });
+
+    ui.add_space(4.);
+    ui.label("Environment Variables:");
+    let envs_res = TextEdit::multiline(&mut config.envs)
+        .desired_rows(2)
+        .desired_width(f32::INFINITY)
+        .hint_text("NAME=value")
+        .show(ui)
+        .response;
+    if envs_res.changed() {
+        config.validate();
+    }
+    show_validation_message(ui, config.envs_err_msg());
+}
+
 /// Renders the process command input together with the shell picker.
 ///
 /// - `config` stores the editable command text, selected shell, and validation state.
@@ -0,0 +1,78 @@
+use egui::{Button, DragValue, Label, RichText, TextStyle, Ui, Widget, vec2};
+
+use crate::host::{
+    common::ui_utls::truncate_path_to_width,
+    ui::{UiActions, actions::FileDialogOptions, session_setup::state::recording::RecordingConfig},
+};
+
+const DIR_DIALOG_ID: &str = "stream_recording_dir";
+
+pub fn render_recording(config: &mut RecordingConfig, actions: &mut UiActions, ui: &mut Ui) {
+    if let Some(paths) = actions.file_dialog.take_output(DIR_DIALOG_ID)
+        && let Some(dir) = paths.into_iter().next()
+    {
+        config.dir = dir;
+    }
+
+    ui.checkbox(&mut config.enabled, "Record raw stream")
+        .on_hover_text(
+            "Write the received bytes to files (pcapng for network streams, \
+            dlt for DLT streams), which can be opened as files later",
+        );
+
+    ui.add_enabled_ui(config.enabled, |ui| {
+        ui.add_space(4.);
+        let current_dir = config.dir.clone();
+        egui::Sides::new().shrink_left().truncate().show(
+            ui,
+            |ui| {
+                ui.label("Folder:");
+                let path_txt =
+                    truncate_path_to_width(ui, &current_dir, ui.available_width(), TextStyle::Body);
+                let response = Label::new(path_txt.text)
+                    .truncate()
+                    .show_tooltip_when_elided(false)
+                    .selectable(true)
+                    .ui(ui);
+                if path_txt.truncated {
+                    response.on_hover_ui(|ui| {
+                        ui.set_max_width(ui.spacing().tooltip_width);
+                        ui.label(current_dir.to_string_lossy());
+                    });
+                }
+            },
+            |ui| {
+                let open_txt = RichText::new(egui_phosphor::regular::FOLDER_OPEN)
+                    .size(16.)
+                    .text_style(egui::TextStyle::Button);
+                let open_btn = Button::new(open_txt)
+                    .min_size(vec2(12., 20.))
+                    .ui(ui)
+                    .on_hover_text("Browse");
+
+                if open_btn.clicked() {
+                    actions.file_dialog.pick_folder(
+                        DIR_DIALOG_ID,
+                        FileDialogOptions::new()
+                            .title("Select Recording Folder")
+                            .directory(config.dir.clone()),
+                    );
+                }
+            },
+        );
+
+        ui.add_space(4.);
+        ui.horizontal(|ui| {
+            ui.label("New file every");
+            DragValue::new(&mut config.max_file_size_mb)
+                .suffix(" MB")
+                .ui(ui)
+                .on_hover_text("Zero disables rotation by size");
+            ui.label("or");
+            DragValue::new(&mut config.max_file_minutes)
+                .suffix(" min")
+                .ui(ui)
+                .on_hover_text("Zero disables rotation by time");
+        });
+    });
+}
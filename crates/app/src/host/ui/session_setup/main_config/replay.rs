@@ -0,0 +1,98 @@
+use egui::{Button, ComboBox, Label, RichText, TextStyle, Ui, Widget, vec2};
+
+use crate::host::{
+    common::ui_utls::{show_validation_message, truncate_path_to_width},
+    ui::{
+        UiActions,
+        actions::{FileDialogFilter, FileDialogOptions},
+        session_setup::state::sources::ReplayConfig,
+    },
+};
+
+use super::RenderOutcome;
+
+const TRACE_DIALOG_ID: &str = "replay_trace_file";
+
+pub fn render_connection(
+    config: &mut ReplayConfig,
+    actions: &mut UiActions,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    if let Some(paths) = actions.file_dialog.take_output(TRACE_DIALOG_ID)
+        && let Some(path) = paths.into_iter().next()
+    {
+        config.set_path(path);
+    }
+
+    let current_path = config.path.clone();
+    egui::Sides::new().shrink_left().truncate().show(
+        ui,
+        |ui| {
+            ui.label("Trace File:");
+            let path_txt =
+                truncate_path_to_width(ui, &current_path, ui.available_width(), TextStyle::Body);
+            let response = Label::new(path_txt.text)
+                .truncate()
+                .show_tooltip_when_elided(false)
+                .selectable(true)
+                .ui(ui);
+            if path_txt.truncated {
+                response.on_hover_ui(|ui| {
+                    ui.set_max_width(ui.spacing().tooltip_width);
+                    ui.label(current_path.to_string_lossy());
+                });
+            }
+        },
+        |ui| {
+            let open_txt = RichText::new(egui_phosphor::regular::FOLDER_OPEN)
+                .size(16.)
+                .text_style(egui::TextStyle::Button);
+            let open_btn = Button::new(open_txt)
+                .min_size(vec2(12., 20.))
+                .ui(ui)
+                .on_hover_text("Browse");
+
+            if open_btn.clicked() {
+                let mut options = FileDialogOptions::new()
+                    .title("Select Trace to Replay")
+                    .filters(vec![
+                        FileDialogFilter::new(
+                            "Traces (*.dlt, *.pcapng, *.pcap)",
+                            vec!["dlt".to_owned(), "pcapng".to_owned(), "pcap".to_owned()],
+                        ),
+                        FileDialogFilter::new("All files (*)", vec!["*".to_owned()]),
+                    ]);
+                if let Some(parent) = config.path.parent() {
+                    options = options.directory(parent);
+                }
+                actions.file_dialog.pick_file(TRACE_DIALOG_ID, options);
+            }
+        },
+    );
+    show_validation_message(ui, config.get_err_msg());
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Format:");
+        for format in ReplayConfig::FORMATS {
+            ui.radio_value(&mut config.format, format.value, format.name);
+        }
+    })
+    .response
+    .on_hover_text("DLT files must contain storage headers");
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Speed:");
+        ComboBox::from_id_salt("replay_speed")
+            .selected_text(config.speed.name)
+            .show_ui(ui, |ui| {
+                for speed in ReplayConfig::SPEED {
+                    let name = speed.name;
+                    ui.selectable_value(&mut config.speed, speed, name);
+                }
+            });
+    });
+
+    RenderOutcome::None
+}
@@ -135,8 +135,39 @@ pub fn render_connection(
                     }
                 });
         });
+
+        labeled_field(ui, "Read mode", |ui| {
+            ComboBox::from_id_salt("read mode")
+                .selected_text(config.read_mode.name)
+                .show_ui(ui, |ui| {
+                    for mode in SerialConfig::READ_MODE {
+                        ui.selectable_value(&mut config.read_mode, mode.to_owned(), mode.name);
+                    }
+                });
+        });
+
+        labeled_field(ui, "Reconnect", |ui| {
+            let reconnect_res = ComboBox::from_id_salt("reconnect")
+                .selected_text(config.reconnect.name)
+                .show_ui(ui, |ui| {
+                    for reconnect in SerialConfig::RECONNECT {
+                        ui.selectable_value(
+                            &mut config.reconnect,
+                            reconnect.to_owned(),
+                            reconnect.name,
+                        );
+                    }
+                });
+            if reconnect_res.inner.is_some() {
+                config.validate();
+            }
+        });
     });
 
+    if let Some(err) = config.reconnect_validation_err() {
+        ui.label(RichText::new(err).small().color(ui.visuals().warn_fg_color));
+    }
+
     ui.add_space(10.);
 
     ui.allocate_ui_with_layout(
@@ -1,4 +1,5 @@
 use egui::Ui;
+use stypes::TcpMode;
 
 use crate::{
     common::ui::visibility_tracker::VisibilityTracker,
@@ -30,5 +31,19 @@ pub fn render_connection(
     input_visibility: &mut VisibilityTracker,
     ui: &mut Ui,
 ) -> RenderOutcome {
-    render_socket_address(config, input_visibility, ui)
+    let outcome = render_socket_address(config, input_visibility, ui);
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Mode:");
+        ui.radio_value(&mut config.mode, TcpMode::Connect, "Connect to server")
+            .on_hover_text("Connect to a TCP server listening on the socket address");
+        ui.radio_value(&mut config.mode, TcpMode::Listen, "Listen for clients")
+            .on_hover_text(
+                "Accept incoming TCP clients on the socket address. \
+                Each client is shown as its own source",
+            );
+    });
+
+    outcome
 }
@@ -39,6 +39,8 @@ pub fn render_connection(
         render_multicasts(config, ui);
     }
 
+    render_senders(config, ui);
+
     ui.allocate_ui_with_layout(
         vec2(ui.available_width(), 30.),
         Layout::right_to_left(Align::Center),
@@ -110,6 +112,26 @@ pub fn render_multicasts(config: &mut UdpConfig, ui: &mut Ui) {
     }
 }
 
+/// Renders the allowed senders and whether each of them is observed as a separate source.
+pub fn render_senders(config: &mut UdpConfig, ui: &mut Ui) {
+    ui.separator();
+
+    let senders_res = label_input_field(
+        ui,
+        "Allowed Senders",
+        &mut config.allowed_senders,
+        "All senders (e.g. 192.168.0.10, 192.168.0.11:3490)",
+        config.get_senders_err(),
+        ui.available_width().min(400.0),
+    );
+    if senders_res.changed() {
+        config.validate();
+    }
+
+    ui.checkbox(&mut config.split_senders, "Separate source per sender")
+        .on_hover_text("Observe the datagrams of each sender as their own source");
+}
+
 fn label_input_field(
     ui: &mut Ui,
     label: &str,
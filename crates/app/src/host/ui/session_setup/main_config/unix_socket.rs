@@ -0,0 +1,57 @@
+use egui::{Align, TextEdit, Ui};
+use stypes::UnixSocketMode;
+
+use crate::{
+    common::ui::visibility_tracker::VisibilityTracker,
+    host::{
+        common::ui_utls::show_validation_message,
+        ui::session_setup::{start_session_on_enter, state::sources::UnixSocketConfig},
+    },
+};
+
+use super::RenderOutcome;
+
+pub fn render_connection(
+    config: &mut UnixSocketConfig,
+    input_visibility: &mut VisibilityTracker,
+    ui: &mut Ui,
+) -> RenderOutcome {
+    let mut outcome = RenderOutcome::None;
+
+    ui.vertical(|ui| {
+        ui.label("Socket Path:");
+
+        let path_res = TextEdit::singleline(&mut config.path)
+            .vertical_align(Align::Center)
+            .desired_width(300.)
+            .hint_text("/run/app/log.sock")
+            .show(ui)
+            .response;
+
+        if input_visibility.is_newly_visible(ui) {
+            path_res.request_focus();
+        }
+
+        if path_res.changed() {
+            config.validate();
+        }
+
+        start_session_on_enter(&path_res, || config.is_valid(), &mut outcome);
+
+        show_validation_message(ui, config.get_err_msg());
+    });
+
+    ui.add_space(4.);
+    ui.horizontal(|ui| {
+        ui.label("Mode:");
+        ui.radio_value(&mut config.mode, UnixSocketMode::Stream, "Stream")
+            .on_hover_text("Connect to a stream socket listening on the path");
+        ui.radio_value(&mut config.mode, UnixSocketMode::Datagram, "Datagram")
+            .on_hover_text(
+                "Create a datagram socket on the path and receive the datagrams sent to it. \
+                The path must not exist yet",
+            );
+    });
+
+    outcome
+}
@@ -62,6 +62,7 @@ impl SessionSetup {
             ByteSourceConfig::File(info) => info.name.as_str().into(),
             ByteSourceConfig::Concat(files) => format!("Concating {} files", files.len()).into(),
             ByteSourceConfig::Stream(..) => "Stream".into(),
+            ByteSourceConfig::Plugin(..) => "Plugin Source".into(),
         }
     }
 
@@ -96,6 +97,7 @@ impl SessionSetup {
         CentralPanel::default().show_inside(ui, |ui| {
             ui.centered_and_justified(|ui| {
                 let outcome = self.render_main_config(recent_sessions, plugins, actions, ui);
+                self.state.sync_storage_header();
                 match outcome {
                     RenderOutcome::CollectDltStatistics => {
                         if self.state.is_valid() {
@@ -207,6 +209,10 @@ impl SessionSetup {
 
                 ui.label("Stream From:");
             }
+            ByteSourceConfig::Plugin(..) => {
+                ui.label(RichText::new("Byte-Source Plugin").strong());
+                ui.label("Read From:");
+            }
         }
     }
 
@@ -224,6 +230,8 @@ impl SessionSetup {
                     .filter(|p| p.is_compatible_stream(stream))
                     .collect()
             }
+            // Plugins may deliver any kind of data.
+            ByteSourceConfig::Plugin(..) => all::<ParserNames>().collect(),
         }
     }
 }
@@ -10,7 +10,7 @@ use crate::host::{
 use super::SessionSetupState;
 
 mod dlt;
-mod plugins;
+pub mod plugins;
 mod shared;
 mod someip;
 mod text;
@@ -16,13 +16,14 @@ use crate::{
     host::ui::{
         UiActions,
         actions::{FileDialogFilter, FileDialogOptions},
-        session_setup::state::parsers::PluginParserConfig,
     },
 };
 
-/// Renders one plugin config schema field and writes edits into plugin parser settings.
-pub fn render_field(
-    config: &mut PluginParserConfig,
+use super::PluginSetupConfig;
+
+/// Renders one plugin config schema field and writes edits into the plugin settings.
+pub fn render_field<C: PluginSetupConfig>(
+    config: &mut C,
     schema: &PluginConfigSchemaItem,
     actions: &mut UiActions,
     ui: &mut Ui,
@@ -49,11 +50,11 @@ pub fn render_field(
         }
         (PluginConfigSchemaType::Directories, PluginConfigValue::Directories(paths)) => {
             render_field_title(schema, ui);
-            render_directories(schema, paths, actions, ui);
+            render_directories(C::KIND, schema, paths, actions, ui);
         }
         (PluginConfigSchemaType::Files(extensions), PluginConfigValue::Files(paths)) => {
             render_field_title(schema, ui);
-            render_files(schema, paths, extensions, actions, ui);
+            render_files(C::KIND, schema, paths, extensions, actions, ui);
         }
         (PluginConfigSchemaType::Dropdown((options, _)), PluginConfigValue::Dropdown(value)) => {
             render_field_title(schema, ui);
@@ -75,12 +76,13 @@ pub fn render_field(
 }
 
 fn render_directories(
+    kind: &str,
     schema: &PluginConfigSchemaItem,
     paths: &mut Vec<PathBuf>,
     actions: &mut UiActions,
     ui: &mut Ui,
 ) {
-    let dialog_id = format!("plugin_config_directory:{}", schema.id);
+    let dialog_id = format!("plugin_config_directory:{kind}:{}", schema.id);
 
     if let Some(selected_paths) = actions.file_dialog.take_output(&dialog_id)
         && let Some(path) = selected_paths.into_iter().next()
@@ -122,13 +124,14 @@ fn render_directories(
 }
 
 fn render_files(
+    kind: &str,
     schema: &PluginConfigSchemaItem,
     paths: &mut Vec<PathBuf>,
     extensions: &[String],
     actions: &mut UiActions,
     ui: &mut Ui,
 ) {
-    let dialog_id = format!("plugin_config_file:{}", schema.id);
+    let dialog_id = format!("plugin_config_file:{kind}:{}", schema.id);
 
     if let Some(selected_paths) = actions.file_dialog.take_output(&dialog_id) {
         for path in selected_paths {
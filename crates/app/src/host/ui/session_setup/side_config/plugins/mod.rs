@@ -1,23 +1,106 @@
-//! Plugin parser side-panel rendering.
+//! Plugin side-panel rendering.
 //!
-//! The panel lists installed parser plugins and renders schema-driven config fields.
+//! The panel lists installed plugins of one type and renders schema-driven config fields.
+//! It's used for parser plugins and byte-source plugins.
 
 use std::path::Path;
 
 use egui::{ComboBox, RichText, ScrollArea, Ui};
-use stypes::{PluginEntity, PluginType};
+use stypes::{PluginConfigSchemaItem, PluginConfigValue, PluginEntity, PluginType};
 
 use crate::host::ui::{
     UiActions,
-    session_setup::state::parsers::PluginParserConfig,
+    session_setup::state::{parsers::PluginParserConfig, sources::PluginSourceConfig},
     state::plugin::{PluginsData, PluginsState},
 };
 
 mod fields;
 
-/// Renders the plugin parser selector and selected plugin configuration fields.
-pub fn render_content(
-    config: &mut PluginParserConfig,
+/// Setup state of a plugin selection with its schema-driven configuration.
+pub trait PluginSetupConfig {
+    /// The type of the plugins which can be selected.
+    const PLUGIN_TYPE: PluginType;
+    /// Name of the plugin type in user-facing text and widget IDs.
+    const KIND: &'static str;
+
+    fn selected_plugin_path(&self) -> Option<&Path>;
+    fn selected_plugin_dir(&self) -> Option<&Path>;
+    fn resolved_plugin<'a>(&self, data: &'a PluginsData) -> Option<&'a PluginEntity>;
+    fn select_plugin(&mut self, plugin: &PluginEntity);
+    fn clear_selection(&mut self);
+    fn config_value_mut(
+        &mut self,
+        schema: &PluginConfigSchemaItem,
+    ) -> Option<&mut PluginConfigValue>;
+}
+
+impl PluginSetupConfig for PluginParserConfig {
+    const PLUGIN_TYPE: PluginType = PluginType::Parser;
+    const KIND: &'static str = "parser";
+
+    fn selected_plugin_path(&self) -> Option<&Path> {
+        PluginParserConfig::selected_plugin_path(self)
+    }
+
+    fn selected_plugin_dir(&self) -> Option<&Path> {
+        PluginParserConfig::selected_plugin_dir(self)
+    }
+
+    fn resolved_plugin<'a>(&self, data: &'a PluginsData) -> Option<&'a PluginEntity> {
+        PluginParserConfig::resolved_plugin(self, data)
+    }
+
+    fn select_plugin(&mut self, plugin: &PluginEntity) {
+        PluginParserConfig::select_plugin(self, plugin);
+    }
+
+    fn clear_selection(&mut self) {
+        PluginParserConfig::clear_selection(self);
+    }
+
+    fn config_value_mut(
+        &mut self,
+        schema: &PluginConfigSchemaItem,
+    ) -> Option<&mut PluginConfigValue> {
+        PluginParserConfig::config_value_mut(self, schema)
+    }
+}
+
+impl PluginSetupConfig for PluginSourceConfig {
+    const PLUGIN_TYPE: PluginType = PluginType::ByteSource;
+    const KIND: &'static str = "byte-source";
+
+    fn selected_plugin_path(&self) -> Option<&Path> {
+        PluginSourceConfig::selected_plugin_path(self)
+    }
+
+    fn selected_plugin_dir(&self) -> Option<&Path> {
+        PluginSourceConfig::selected_plugin_dir(self)
+    }
+
+    fn resolved_plugin<'a>(&self, data: &'a PluginsData) -> Option<&'a PluginEntity> {
+        PluginSourceConfig::resolved_plugin(self, data)
+    }
+
+    fn select_plugin(&mut self, plugin: &PluginEntity) {
+        PluginSourceConfig::select_plugin(self, plugin);
+    }
+
+    fn clear_selection(&mut self) {
+        PluginSourceConfig::clear_selection(self);
+    }
+
+    fn config_value_mut(
+        &mut self,
+        schema: &PluginConfigSchemaItem,
+    ) -> Option<&mut PluginConfigValue> {
+        PluginSourceConfig::config_value_mut(self, schema)
+    }
+}
+
+/// Renders the plugin selector and selected plugin configuration fields.
+pub fn render_content<C: PluginSetupConfig>(
+    config: &mut C,
     plugins: &PluginsState,
     actions: &mut UiActions,
     ui: &mut Ui,
@@ -33,8 +116,8 @@ pub fn render_content(
     }
 }
 
-fn render_available(
-    config: &mut PluginParserConfig,
+fn render_available<C: PluginSetupConfig>(
+    config: &mut C,
     data: &PluginsData,
     actions: &mut UiActions,
     ui: &mut Ui,
@@ -42,18 +125,20 @@ fn render_available(
     if !data
         .installed
         .iter()
-        .any(|plugin| plugin.plugin_type == PluginType::Parser)
+        .any(|plugin| plugin.plugin_type == C::PLUGIN_TYPE)
     {
-        ui.label(RichText::new("No parser plugins are installed.").weak());
+        let msg = format!("No {} plugins are installed.", C::KIND);
+        ui.label(RichText::new(msg).weak());
         return;
     }
 
     render_plugin_selector(config, data, ui);
 
     let Some(plugin) = config.resolved_plugin(data) else {
-        if config.selected_settings().is_some() {
+        if config.selected_plugin_path().is_some() {
             ui.add_space(12.0);
-            ui.label(RichText::new("Selected parser plugin is not installed.").weak());
+            let msg = format!("Selected {} plugin is not installed.", C::KIND);
+            ui.label(RichText::new(msg).weak());
         }
         return;
     };
@@ -61,20 +146,20 @@ fn render_available(
     render_config_fields(config, plugin, actions, ui);
 }
 
-fn render_plugin_selector(config: &mut PluginParserConfig, data: &PluginsData, ui: &mut Ui) {
+fn render_plugin_selector<C: PluginSetupConfig>(config: &mut C, data: &PluginsData, ui: &mut Ui) {
     let original_dir = config.selected_plugin_dir().map(Path::to_path_buf);
     let mut selected_dir = original_dir.clone();
 
-    ComboBox::from_id_salt("plugin_parser_selector")
+    ComboBox::from_id_salt(("plugin_selector", C::KIND))
         .selected_text(selected_text(config, data))
         .width(ui.available_width())
         .show_ui(ui, |ui| {
-            ui.selectable_value(&mut selected_dir, None, "Select parser plugin");
+            ui.selectable_value(&mut selected_dir, None, select_hint::<C>());
 
             for plugin in data
                 .installed
                 .iter()
-                .filter(|plugin| plugin.plugin_type == PluginType::Parser)
+                .filter(|plugin| plugin.plugin_type == C::PLUGIN_TYPE)
             {
                 ui.selectable_value(
                     &mut selected_dir,
@@ -95,8 +180,8 @@ fn render_plugin_selector(config: &mut PluginParserConfig, data: &PluginsData, u
     }
 }
 
-fn render_config_fields(
-    config: &mut PluginParserConfig,
+fn render_config_fields<C: PluginSetupConfig>(
+    config: &mut C,
     plugin: &PluginEntity,
     actions: &mut UiActions,
     ui: &mut Ui,
@@ -110,7 +195,7 @@ fn render_config_fields(
     }
 
     ScrollArea::vertical()
-        .id_salt("plugin_config_scroll")
+        .id_salt(("plugin_config_scroll", C::KIND))
         .auto_shrink([false, true])
         .show(ui, |ui| {
             for (idx, schema) in plugin.info.config_schemas.iter().enumerate() {
@@ -123,7 +208,7 @@ fn render_config_fields(
         });
 }
 
-fn selected_text(config: &PluginParserConfig, data: &PluginsData) -> String {
+fn selected_text<C: PluginSetupConfig>(config: &C, data: &PluginsData) -> String {
     if let Some(plugin) = config.resolved_plugin(data) {
         return plugin_title(plugin);
     }
@@ -131,7 +216,11 @@ fn selected_text(config: &PluginParserConfig, data: &PluginsData) -> String {
     config
         .selected_plugin_path()
         .map(|path| path.display().to_string())
-        .unwrap_or_else(|| "Select parser plugin".to_owned())
+        .unwrap_or_else(|| select_hint::<C>())
+}
+
+fn select_hint<C: PluginSetupConfig>() -> String {
+    format!("Select {} plugin", C::KIND)
 }
 
 fn plugin_title(plugin: &PluginEntity) -> String {
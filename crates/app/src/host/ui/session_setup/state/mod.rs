@@ -2,7 +2,7 @@ use enum_iterator::all;
 use tokio::sync::mpsc::Sender;
 use uuid::Uuid;
 
-use stypes::FileFormat;
+use stypes::{FileFormat, ProcessOutputMode, SerialReadMode};
 
 use crate::host::{
     command::{DltStatisticsParam, HostCommand, SomeipStatisticsParam, StartSessionParam},
@@ -11,15 +11,21 @@ use crate::host::{
         UiActions,
         session_setup::state::{
             parsers::{PluginParserConfig, someip::SomeIpParserConfig},
-            sources::{ProcessConfig, SerialConfig, StreamConfig, TcpConfig, UdpConfig},
+            sources::{
+                HttpStreamConfig, MqttConfig, ProcessConfig, ReplayConfig, SerialConfig,
+                SocketCanConfig, StreamConfig, TcpConfig, UdpConfig, UnixSocketConfig,
+                WebSocketConfig,
+            },
         },
         state::plugin::PluginsState,
     },
 };
 use parsers::{DltParserConfig, ParserConfig};
+use recording::RecordingConfig;
 use sources::ByteSourceConfig;
 
 pub mod parsers;
+pub mod recording;
 pub mod sources;
 
 #[derive(Debug)]
@@ -27,11 +33,17 @@ pub struct SessionSetupState {
     pub id: Uuid,
     pub source: ByteSourceConfig,
     pub parser: ParserConfig,
+    pub recording: RecordingConfig,
 }
 
 impl SessionSetupState {
     pub fn new(id: Uuid, source: ByteSourceConfig, parser: ParserConfig) -> Self {
-        Self { id, source, parser }
+        Self {
+            id,
+            source,
+            parser,
+            recording: RecordingConfig::new(),
+        }
     }
 
     pub fn update_parser(&mut self, parser: ParserNames) {
@@ -50,7 +62,7 @@ impl SessionSetupState {
                         Some(files.iter().map(|f| f.path.clone()).collect()),
                     )))
                 }
-                ByteSourceConfig::Stream(..) => {
+                ByteSourceConfig::Stream(..) | ByteSourceConfig::Plugin(..) => {
                     ParserConfig::Dlt(Box::new(DltParserConfig::new(false, None)))
                 }
             },
@@ -63,13 +75,55 @@ impl SessionSetupState {
                 ByteSourceConfig::Concat(files) => ParserConfig::SomeIP(Box::new(
                     SomeIpParserConfig::new(Some(files.iter().map(|f| f.path.clone()).collect())),
                 )),
-                ByteSourceConfig::Stream(..) => {
+                ByteSourceConfig::Stream(..) | ByteSourceConfig::Plugin(..) => {
                     ParserConfig::SomeIP(Box::new(SomeIpParserConfig::new(None)))
                 }
             },
             ParserNames::Text => ParserConfig::Text,
             ParserNames::Plugins => ParserConfig::Plugins(Box::new(PluginParserConfig::new())),
         };
+
+        self.sync_raw_output();
+        self.sync_storage_header();
+    }
+
+    /// Switches commands and serial ports to deliver their raw output if the parser supports
+    /// binary data only.
+    fn sync_raw_output(&mut self) {
+        if ParserNames::from(&self.parser).support_text_files() {
+            return;
+        }
+
+        match &mut self.source {
+            ByteSourceConfig::Stream(StreamConfig::Process(config)) => {
+                config.output = ProcessOutputMode::Raw;
+            }
+            ByteSourceConfig::Stream(StreamConfig::Serial(config)) => {
+                config.read_mode = SerialConfig::READ_MODE
+                    .iter()
+                    .find(|mode| mode.value == SerialReadMode::Raw)
+                    .cloned()
+                    .unwrap_or_else(|| config.read_mode.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns an error if the parser can't parse the output of the source as configured.
+    fn raw_output_error(&self) -> Option<&'static str> {
+        if ParserNames::from(&self.parser).support_text_files() {
+            return None;
+        }
+
+        match &self.source {
+            ByteSourceConfig::Stream(StreamConfig::Process(config)) => (config.output
+                == ProcessOutputMode::Lines)
+                .then_some("Selected parser needs the raw bytes output of the command"),
+            ByteSourceConfig::Stream(StreamConfig::Serial(config)) => (config.read_mode.value
+                == SerialReadMode::Lines)
+                .then_some("Selected parser needs the raw bytes read mode of the serial port"),
+            _ => None,
+        }
     }
 
     fn with_dlt_storage_header(format: FileFormat) -> bool {
@@ -88,6 +142,22 @@ impl SessionSetupState {
             StreamNames::Serial => {
                 ByteSourceConfig::Stream(StreamConfig::Serial(SerialConfig::new()))
             }
+            StreamNames::UnixSocket => {
+                ByteSourceConfig::Stream(StreamConfig::UnixSocket(UnixSocketConfig::new()))
+            }
+            StreamNames::Replay => {
+                ByteSourceConfig::Stream(StreamConfig::Replay(ReplayConfig::new()))
+            }
+            StreamNames::Mqtt => ByteSourceConfig::Stream(StreamConfig::Mqtt(MqttConfig::new())),
+            StreamNames::WebSocket => {
+                ByteSourceConfig::Stream(StreamConfig::WebSocket(WebSocketConfig::new()))
+            }
+            StreamNames::HttpStream => {
+                ByteSourceConfig::Stream(StreamConfig::HttpStream(HttpStreamConfig::new()))
+            }
+            StreamNames::SocketCan => {
+                ByteSourceConfig::Stream(StreamConfig::SocketCan(SocketCanConfig::new()))
+            }
         };
 
         // Check if current parser is compatible with the new source
@@ -98,26 +168,42 @@ impl SessionSetupState {
                 .unwrap_or(ParserNames::Text);
             self.update_parser(new_parser);
         }
+
+        self.sync_raw_output();
+        self.sync_storage_header();
+    }
+
+    /// Matches the storage header setting of the DLT parser with the format of replayed
+    /// traces, since only DLT files contain storage headers.
+    pub fn sync_storage_header(&mut self) {
+        if let (ByteSourceConfig::Stream(StreamConfig::Replay(replay)), ParserConfig::Dlt(dlt)) =
+            (&self.source, &mut self.parser)
+        {
+            dlt.with_storage_header = Self::with_dlt_storage_header(replay.format);
+        }
     }
 
     /// Clears plugin-backed setup state after installed plugin data changes.
     pub fn sync_plugins(&mut self, _plugins: &PluginsState) {
-        let ParserConfig::Plugins(config) = &mut self.parser else {
-            return;
-        };
+        if let ByteSourceConfig::Plugin(config) = &mut self.source {
+            config.clear_selection();
+        }
 
-        config.clear_selection();
+        if let ParserConfig::Plugins(config) = &mut self.parser {
+            config.clear_selection();
+        }
     }
 
     pub fn validatio_errors(&self) -> Vec<&str> {
         let mut errs = self.source.validation_errors();
         errs.extend(self.parser.validation_errors());
+        errs.extend(self.raw_output_error());
 
         errs
     }
 
     pub fn is_valid(&self) -> bool {
-        self.source.is_valid() && self.parser.is_valid()
+        self.source.is_valid() && self.parser.is_valid() && self.raw_output_error().is_none()
     }
 
     pub fn collect_dlt_statistics(
@@ -162,6 +248,17 @@ impl SessionSetupState {
         }
     }
 
+    /// Returns the recording options if recording is enabled for a live stream source.
+    fn recording_options(&self) -> Option<stypes::StreamRecording> {
+        match &self.source {
+            ByteSourceConfig::Stream(StreamConfig::Replay(..)) => None,
+            ByteSourceConfig::Stream(..) => self.recording.options(),
+            ByteSourceConfig::File(..)
+            | ByteSourceConfig::Concat(..)
+            | ByteSourceConfig::Plugin(..) => None,
+        }
+    }
+
     pub fn start_session(
         &self,
         cmd_tx: &Sender<crate::host::command::HostCommand>,
@@ -172,6 +269,7 @@ impl SessionSetupState {
         let param = StartSessionParam {
             parser: self.parser.clone(),
             source: self.source.clone(),
+            recording: self.recording_options(),
             session_setup_id: Some(self.id),
         };
 
@@ -213,6 +311,30 @@ mod tests {
         assert!(!config.with_storage_header);
     }
 
+    #[test]
+    fn storage_header_follows_replayed_trace_format() {
+        let mut state = SessionSetupState::new(
+            Uuid::new_v4(),
+            ByteSourceConfig::Stream(StreamConfig::Tcp(TcpConfig::new())),
+            ParserConfig::Dlt(Box::new(DltParserConfig::new(false, None))),
+        );
+
+        state.update_stream(StreamNames::Replay);
+        let ParserConfig::Dlt(config) = &state.parser else {
+            panic!("expected dlt parser config");
+        };
+        assert!(config.with_storage_header);
+
+        if let ByteSourceConfig::Stream(StreamConfig::Replay(replay)) = &mut state.source {
+            replay.format = FileFormat::PcapNG;
+        }
+        state.sync_storage_header();
+        let ParserConfig::Dlt(config) = &state.parser else {
+            panic!("expected dlt parser config");
+        };
+        assert!(!config.with_storage_header);
+    }
+
     #[test]
     fn update_parser_keeps_storage_header_for_binary_dlt_files() {
         let mut state = SessionSetupState::new(
@@ -261,6 +383,45 @@ mod tests {
         assert_ne!(ParserNames::from(&state.parser), ParserNames::Text);
     }
 
+    #[test]
+    fn binary_parser_needs_raw_process_output() {
+        let mut state = SessionSetupState::new(
+            Uuid::new_v4(),
+            ByteSourceConfig::Stream(StreamConfig::Process(ProcessConfig::new())),
+            ParserConfig::Text,
+        );
+
+        state.update_parser(ParserNames::Dlt);
+
+        let ByteSourceConfig::Stream(StreamConfig::Process(config)) = &mut state.source else {
+            panic!("expected process source config");
+        };
+        assert_eq!(config.output, ProcessOutputMode::Raw);
+
+        config.output = ProcessOutputMode::Lines;
+        assert!(state.raw_output_error().is_some());
+    }
+
+    #[test]
+    fn binary_parser_needs_raw_serial_read_mode() {
+        let mut state = SessionSetupState::new(
+            Uuid::new_v4(),
+            ByteSourceConfig::Stream(StreamConfig::Serial(SerialConfig::new())),
+            ParserConfig::Text,
+        );
+        assert!(state.raw_output_error().is_none());
+
+        state.update_parser(ParserNames::Dlt);
+
+        let ByteSourceConfig::Stream(StreamConfig::Serial(config)) = &mut state.source else {
+            panic!("expected serial source config");
+        };
+        assert_eq!(config.read_mode.value, SerialReadMode::Raw);
+
+        config.read_mode = SerialConfig::READ_MODE[0].to_owned();
+        assert!(state.raw_output_error().is_some());
+    }
+
     #[test]
     fn update_stream_keeps_parser() {
         let plugin_config = PluginParserConfig::new();
@@ -56,7 +56,9 @@ impl DltParserConfig {
                     .map(|(_, _, path)| path.to_owned())
                     .collect_vec(),
             ),
-            ObserveOrigin::Stream(..) => None,
+            ObserveOrigin::Stream(..) | ObserveOrigin::DirWatch(..) | ObserveOrigin::Plugin(..) => {
+                None
+            }
         };
 
         Self {
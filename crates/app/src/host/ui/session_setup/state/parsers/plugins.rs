@@ -120,7 +120,8 @@ impl PluginParserConfig {
     }
 }
 
-fn default_config_items(schemas: &[PluginConfigSchemaItem]) -> Vec<PluginConfigItem> {
+/// Creates the config items of a plugin with the default values of its schemas.
+pub fn default_config_items(schemas: &[PluginConfigSchemaItem]) -> Vec<PluginConfigItem> {
     schemas
         .iter()
         .map(|schema| PluginConfigItem {
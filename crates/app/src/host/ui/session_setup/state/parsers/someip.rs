@@ -32,7 +32,9 @@ impl SomeIpParserConfig {
                     .map(|(_, _, path)| path.to_owned())
                     .collect_vec(),
             ),
-            ObserveOrigin::Stream(..) => None,
+            ObserveOrigin::Stream(..) | ObserveOrigin::DirWatch(..) | ObserveOrigin::Plugin(..) => {
+                None
+            }
         };
 
         let fibex_files = settings
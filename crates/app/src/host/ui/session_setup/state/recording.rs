@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+/// Settings to record the raw bytes of streams to files while they are observed.
+#[derive(Debug, Clone)]
+pub struct RecordingConfig {
+    pub enabled: bool,
+    pub dir: PathBuf,
+    /// Maximum size of one recording file in megabytes, where zero means unlimited.
+    pub max_file_size_mb: u64,
+    /// Maximum duration covered by one recording file in minutes, where zero means unlimited.
+    pub max_file_minutes: u64,
+}
+
+impl RecordingConfig {
+    pub fn new() -> Self {
+        let dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("chipmunk_recordings");
+
+        Self {
+            enabled: false,
+            dir,
+            max_file_size_mb: 0,
+            max_file_minutes: 0,
+        }
+    }
+
+    /// Returns the recording options of observing if recording is enabled.
+    pub fn options(&self) -> Option<stypes::StreamRecording> {
+        let Self {
+            enabled,
+            dir,
+            max_file_size_mb,
+            max_file_minutes,
+        } = self;
+
+        enabled.then(|| stypes::StreamRecording {
+            dir: dir.to_owned(),
+            max_file_size: (*max_file_size_mb > 0).then(|| max_file_size_mb * 1024 * 1024),
+            max_file_duration: (*max_file_minutes > 0).then(|| max_file_minutes * 60),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_options() {
+        let mut config = RecordingConfig::new();
+        assert!(config.options().is_none());
+
+        config.enabled = true;
+        let options = config.options().unwrap();
+        assert_eq!(options.max_file_size, None);
+        assert_eq!(options.max_file_duration, None);
+
+        config.max_file_size_mb = 2;
+        config.max_file_minutes = 5;
+        let options = config.options().unwrap();
+        assert_eq!(options.max_file_size, Some(2 * 1024 * 1024));
+        assert_eq!(options.max_file_duration, Some(300));
+    }
+}
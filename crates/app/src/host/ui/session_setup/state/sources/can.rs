@@ -0,0 +1,184 @@
+use stypes::{CanFilter, CanTimestampSource};
+
+/// Maximum length of network interface names on Linux.
+const MAX_INTERFACE_LEN: usize = 15;
+
+#[derive(Debug, Clone)]
+pub struct SocketCanConfig {
+    pub interface: String,
+    interface_err_msg: Option<&'static str>,
+    /// Filters in the syntax of `candump` (`ID:MASK`) separated by commas or new lines.
+    pub filters: String,
+    filters_err_msg: Option<&'static str>,
+    pub fd_frames: bool,
+    pub error_frames: bool,
+    pub timestamps: CanTimestampSource,
+}
+
+impl SocketCanConfig {
+    pub const TIMESTAMPS: [CanTimestampSource; 2] =
+        [CanTimestampSource::Software, CanTimestampSource::Hardware];
+
+    pub fn new() -> Self {
+        let mut config = Self {
+            interface: String::from("can0"),
+            interface_err_msg: None,
+            filters: String::new(),
+            filters_err_msg: None,
+            fd_frames: false,
+            error_frames: false,
+            timestamps: CanTimestampSource::default(),
+        };
+        config.validate();
+
+        config
+    }
+
+    pub fn validate(&mut self) {
+        let interface = self.interface.trim();
+        self.interface_err_msg = if interface.is_empty() {
+            Some("Interface is required")
+        } else if interface.len() > MAX_INTERFACE_LEN
+            || interface.contains(|c: char| c.is_whitespace() || c == '/')
+        {
+            Some("Invalid interface name")
+        } else {
+            None
+        };
+
+        self.filters_err_msg = parse_filters(&self.filters).is_none().then_some(
+            "Filters must be given as ID:MASK with 3 (standard) or 8 (extended) hex digits",
+        );
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validation_errors().is_empty()
+    }
+
+    pub fn validation_errors(&self) -> Vec<&str> {
+        [self.interface_err_msg, self.filters_err_msg]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn get_interface_err(&self) -> Option<&str> {
+        self.interface_err_msg
+    }
+
+    pub fn get_filters_err(&self) -> Option<&str> {
+        self.filters_err_msg
+    }
+}
+
+/// Parses the filters in the syntax of `candump` separated by commas or new lines.
+/// IDs with 8 hex digits match extended frames. Returns `None` if any filter is invalid.
+fn parse_filters(filters: &str) -> Option<Vec<CanFilter>> {
+    filters
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+        .map(|filter| {
+            let (id, mask) = filter.split_once(':')?;
+            let (id, mask) = (id.trim(), mask.trim());
+            let extended = match id.len() {
+                3 => false,
+                8 => true,
+                _ => return None,
+            };
+            let max = if extended { 0x1FFF_FFFF } else { 0x7FF };
+            let id = u32::from_str_radix(id, 16).ok().filter(|id| *id <= max)?;
+            let mask = u32::from_str_radix(mask, 16).ok()?;
+            Some(CanFilter { id, mask, extended })
+        })
+        .collect()
+}
+
+impl From<SocketCanConfig> for stypes::SocketCanTransportConfig {
+    fn from(config: SocketCanConfig) -> Self {
+        Self {
+            interface: config.interface.trim().to_owned(),
+            filters: parse_filters(&config.filters).unwrap_or_default(),
+            fd_frames: config.fd_frames,
+            error_frames: config.error_frames,
+            timestamps: config.timestamps,
+        }
+    }
+}
+
+impl From<&stypes::SocketCanTransportConfig> for SocketCanConfig {
+    fn from(c: &stypes::SocketCanTransportConfig) -> Self {
+        let mut config = Self {
+            interface: c.interface.to_owned(),
+            interface_err_msg: None,
+            filters: c
+                .filters
+                .iter()
+                .map(CanFilter::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            filters_err_msg: None,
+            fd_frames: c.fd_frames,
+            error_frames: c.error_frames,
+            timestamps: c.timestamps,
+        };
+
+        config.validate();
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_candump_filters() {
+        let filters = parse_filters("123:7FF, 1F334455:1FFFFF00\n\n").unwrap();
+        assert_eq!(
+            filters,
+            vec![
+                CanFilter {
+                    id: 0x123,
+                    mask: 0x7FF,
+                    extended: false,
+                },
+                CanFilter {
+                    id: 0x1F33_4455,
+                    mask: 0x1FFF_FF00,
+                    extended: true,
+                },
+            ]
+        );
+
+        assert!(parse_filters("").unwrap().is_empty());
+        assert!(parse_filters("123").is_none());
+        assert!(parse_filters("12:7FF").is_none());
+        assert!(parse_filters("800:7FF").is_none());
+        assert!(parse_filters("123:XYZ").is_none());
+    }
+
+    #[test]
+    fn transport_round_trip() {
+        let mut config = SocketCanConfig::new();
+        assert!(config.is_valid());
+
+        config.interface = String::from(" vcan0 ");
+        config.filters = String::from("0A0:7F0, 18DAF110:1FFFFFFF");
+        config.error_frames = true;
+        let transport = stypes::SocketCanTransportConfig::from(config);
+        assert_eq!(transport.interface, "vcan0");
+        assert_eq!(transport.filters.len(), 2);
+
+        let restored = SocketCanConfig::from(&transport);
+        assert_eq!(restored.filters, "0A0:7F0\n18DAF110:1FFFFFFF");
+        assert!(restored.error_frames);
+        assert!(restored.is_valid());
+
+        let mut config = SocketCanConfig::new();
+        config.interface = String::from("can 0");
+        config.validate();
+        assert!(config.get_interface_err().is_some());
+    }
+}
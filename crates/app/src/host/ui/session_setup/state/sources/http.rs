@@ -0,0 +1,296 @@
+use stypes::{HttpHeader, HttpStreamFormat, WebSocketMessageMode};
+
+/// Request settings shared by WebSocket connections and HTTP streams.
+#[derive(Debug, Clone)]
+pub struct HttpRequestConfig {
+    pub url: String,
+    url_err_msg: Option<&'static str>,
+    /// Headers with one `Name: value` pair per line.
+    pub headers: String,
+    headers_err_msg: Option<&'static str>,
+    /// Token sent as `Authorization: Bearer` header. Not used if empty.
+    pub bearer_token: String,
+    pub reconnect: bool,
+    /// URL schemes accepted by the source.
+    schemes: &'static [&'static str],
+}
+
+impl HttpRequestConfig {
+    fn new(url: &str, schemes: &'static [&'static str]) -> Self {
+        let mut config = Self {
+            url: url.to_owned(),
+            url_err_msg: None,
+            headers: String::new(),
+            headers_err_msg: None,
+            bearer_token: String::new(),
+            reconnect: false,
+            schemes,
+        };
+        config.validate();
+
+        config
+    }
+
+    fn from_transport(
+        url: &str,
+        headers: &[HttpHeader],
+        bearer_token: Option<&str>,
+        reconnect: bool,
+        schemes: &'static [&'static str],
+    ) -> Self {
+        let mut config = Self::new(url, schemes);
+        config.headers = headers
+            .iter()
+            .map(|header| format!("{}: {}", header.name, header.value))
+            .collect::<Vec<_>>()
+            .join("\n");
+        config.bearer_token = bearer_token.unwrap_or_default().to_owned();
+        config.reconnect = reconnect;
+        config.validate();
+
+        config
+    }
+
+    pub fn validate(&mut self) {
+        let url = self.url.trim();
+        self.url_err_msg = match url.split_once("://") {
+            _ if url.is_empty() => Some("URL is required"),
+            Some((scheme, rest)) if self.schemes.contains(&scheme) => {
+                rest.is_empty().then_some("URL host is required")
+            }
+            _ if self.schemes.contains(&"wss") => Some("URL must start with ws:// or wss://"),
+            _ => Some("URL must start with http:// or https://"),
+        };
+
+        self.headers_err_msg = parse_headers(&self.headers)
+            .is_none()
+            .then_some("Headers must be given as 'Name: value', one per line");
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.url_err_msg.is_none() && self.headers_err_msg.is_none()
+    }
+
+    pub fn get_url_err(&self) -> Option<&str> {
+        self.url_err_msg
+    }
+
+    pub fn get_headers_err(&self) -> Option<&str> {
+        self.headers_err_msg
+    }
+
+    fn validation_errors(&self) -> Vec<&str> {
+        [self.url_err_msg, self.headers_err_msg]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn url(&self) -> String {
+        self.url.trim().to_owned()
+    }
+
+    fn transport_headers(&self) -> Vec<HttpHeader> {
+        parse_headers(&self.headers).unwrap_or_default()
+    }
+
+    fn transport_token(&self) -> Option<String> {
+        let token = self.bearer_token.trim();
+        (!token.is_empty()).then(|| token.to_owned())
+    }
+}
+
+/// Parses headers given as `Name: value` pairs, one per line.
+/// Returns `None` if any line isn't a valid pair.
+fn parse_headers(headers: &str) -> Option<Vec<HttpHeader>> {
+    headers
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, value) = line.split_once(':')?;
+            let name = name.trim();
+            let is_token = !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte));
+            is_token.then(|| HttpHeader {
+                name: name.to_owned(),
+                value: value.trim().to_owned(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct WebSocketConfig {
+    pub request: HttpRequestConfig,
+    pub messages: WebSocketMessageMode,
+}
+
+impl WebSocketConfig {
+    const SCHEMES: &[&str] = &["ws", "wss"];
+
+    pub fn new() -> Self {
+        Self {
+            request: HttpRequestConfig::new("ws://", Self::SCHEMES),
+            messages: WebSocketMessageMode::default(),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.request.is_valid()
+    }
+
+    pub fn validation_errors(&self) -> Vec<&str> {
+        self.request.validation_errors()
+    }
+}
+
+impl From<WebSocketConfig> for stypes::WebSocketTransportConfig {
+    fn from(config: WebSocketConfig) -> Self {
+        Self {
+            url: config.request.url(),
+            headers: config.request.transport_headers(),
+            bearer_token: config.request.transport_token(),
+            messages: config.messages,
+            reconnect: config.request.reconnect,
+        }
+    }
+}
+
+impl From<&stypes::WebSocketTransportConfig> for WebSocketConfig {
+    fn from(c: &stypes::WebSocketTransportConfig) -> Self {
+        Self {
+            request: HttpRequestConfig::from_transport(
+                &c.url,
+                &c.headers,
+                c.bearer_token.as_deref(),
+                c.reconnect,
+                Self::SCHEMES,
+            ),
+            messages: c.messages,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpStreamConfig {
+    pub request: HttpRequestConfig,
+    pub format: HttpStreamFormat,
+}
+
+impl HttpStreamConfig {
+    const SCHEMES: &[&str] = &["http", "https"];
+
+    pub fn new() -> Self {
+        Self {
+            request: HttpRequestConfig::new("http://", Self::SCHEMES),
+            format: HttpStreamFormat::default(),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.request.is_valid()
+    }
+
+    pub fn validation_errors(&self) -> Vec<&str> {
+        self.request.validation_errors()
+    }
+}
+
+impl From<HttpStreamConfig> for stypes::HttpStreamTransportConfig {
+    fn from(config: HttpStreamConfig) -> Self {
+        Self {
+            url: config.request.url(),
+            headers: config.request.transport_headers(),
+            bearer_token: config.request.transport_token(),
+            format: config.format,
+            reconnect: config.request.reconnect,
+        }
+    }
+}
+
+impl From<&stypes::HttpStreamTransportConfig> for HttpStreamConfig {
+    fn from(c: &stypes::HttpStreamTransportConfig) -> Self {
+        Self {
+            request: HttpRequestConfig::from_transport(
+                &c.url,
+                &c.headers,
+                c.bearer_token.as_deref(),
+                c.reconnect,
+                Self::SCHEMES,
+            ),
+            format: c.format,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_header_lines() {
+        let headers = parse_headers("X-Gateway: ecu-1\n\n  Accept : text/plain ").unwrap();
+        assert_eq!(
+            headers,
+            vec![
+                HttpHeader {
+                    name: String::from("X-Gateway"),
+                    value: String::from("ecu-1"),
+                },
+                HttpHeader {
+                    name: String::from("Accept"),
+                    value: String::from("text/plain"),
+                },
+            ]
+        );
+
+        assert!(parse_headers("").unwrap().is_empty());
+        assert!(parse_headers("missing colon").is_none());
+        assert!(parse_headers("Bad Name: value").is_none());
+    }
+
+    #[test]
+    fn validate_urls() {
+        let mut config = WebSocketConfig::new();
+        assert!(!config.is_valid());
+
+        config.request.url = String::from("wss://gateway/logs");
+        config.request.validate();
+        assert!(config.is_valid());
+
+        config.request.url = String::from("http://gateway/logs");
+        config.request.validate();
+        assert_eq!(
+            config.request.get_url_err(),
+            Some("URL must start with ws:// or wss://")
+        );
+
+        let mut config = HttpStreamConfig::new();
+        config.request.url = String::from("https://gateway/logs");
+        config.request.headers = String::from("no header");
+        config.request.validate();
+        assert!(config.request.get_url_err().is_none());
+        assert!(config.request.get_headers_err().is_some());
+    }
+
+    #[test]
+    fn transport_round_trip() {
+        let mut config = HttpStreamConfig::new();
+        config.request.url = String::from(" https://gateway/logs ");
+        config.request.headers = String::from("X-Gateway: ecu-1");
+        config.request.bearer_token = String::from(" ");
+        config.format = HttpStreamFormat::ServerSentEvents;
+        let transport = stypes::HttpStreamTransportConfig::from(config);
+        assert_eq!(transport.url, "https://gateway/logs");
+        assert_eq!(transport.headers.len(), 1);
+        assert!(transport.bearer_token.is_none());
+
+        let restored = HttpStreamConfig::from(&transport);
+        assert_eq!(restored.request.headers, "X-Gateway: ecu-1");
+        assert_eq!(restored.format, HttpStreamFormat::ServerSentEvents);
+        assert!(restored.is_valid());
+    }
+}
@@ -1,22 +1,36 @@
+pub mod can;
 pub mod file;
+pub mod http;
+pub mod mqtt;
+pub mod plugin;
 pub mod process;
+pub mod replay;
 pub mod serial;
 pub mod tcp;
 pub mod udp;
+pub mod unix_socket;
 
+pub use can::SocketCanConfig;
 pub use file::SourceFileInfo;
+pub use http::{HttpStreamConfig, WebSocketConfig};
+pub use mqtt::MqttConfig;
+pub use plugin::PluginSourceConfig;
 pub use process::ProcessConfig;
+pub use replay::ReplayConfig;
 pub use serial::{BaudRate, NamedValue, SerialConfig};
 use std::path::PathBuf;
 use stypes::{FileFormat, Transport};
 pub use tcp::TcpConfig;
 pub use udp::{MulticastItem, UdpConfig};
+pub use unix_socket::UnixSocketConfig;
 
 #[derive(Debug, Clone)]
 pub enum ByteSourceConfig {
     File(SourceFileInfo),
     Concat(Vec<SourceFileInfo>),
     Stream(StreamConfig),
+    /// Byte-source plugin setup state.
+    Plugin(PluginSourceConfig),
 }
 
 impl ByteSourceConfig {
@@ -29,6 +43,7 @@ impl ByteSourceConfig {
             ByteSourceConfig::File(file) => file.is_valid(),
             ByteSourceConfig::Concat(files) => files.iter().all(|f| f.is_valid()),
             ByteSourceConfig::Stream(stream) => stream.is_valid(),
+            ByteSourceConfig::Plugin(plugin) => plugin.is_valid(),
         }
     }
 
@@ -39,6 +54,7 @@ impl ByteSourceConfig {
                 files.iter().flat_map(|f| f.validation_errors()).collect()
             }
             ByteSourceConfig::Stream(stream) => stream.validation_errors(),
+            ByteSourceConfig::Plugin(plugin) => plugin.validation_errors().to_vec(),
         }
     }
 
@@ -52,6 +68,12 @@ impl ByteSourceConfig {
             Transport::TCP(config) => StreamConfig::Tcp(config.into()),
             Transport::UDP(config) => StreamConfig::Udp(config.into()),
             Transport::Serial(config) => StreamConfig::Serial(config.into()),
+            Transport::UnixSocket(config) => StreamConfig::UnixSocket(config.into()),
+            Transport::Replay(config) => StreamConfig::Replay(config.into()),
+            Transport::Mqtt(config) => StreamConfig::Mqtt(config.into()),
+            Transport::WebSocket(config) => StreamConfig::WebSocket(config.into()),
+            Transport::HttpStream(config) => StreamConfig::HttpStream(config.into()),
+            Transport::SocketCan(config) => StreamConfig::SocketCan(config.into()),
         };
         Self::Stream(config)
     }
@@ -63,6 +85,12 @@ pub enum StreamConfig {
     Tcp(TcpConfig),
     Udp(UdpConfig),
     Serial(SerialConfig),
+    UnixSocket(UnixSocketConfig),
+    Replay(ReplayConfig),
+    Mqtt(MqttConfig),
+    WebSocket(WebSocketConfig),
+    HttpStream(HttpStreamConfig),
+    SocketCan(SocketCanConfig),
 }
 
 impl StreamConfig {
@@ -72,6 +100,12 @@ impl StreamConfig {
             StreamConfig::Tcp(config) => config.is_valid(),
             StreamConfig::Udp(config) => config.is_valid(),
             StreamConfig::Serial(config) => config.is_valid(),
+            StreamConfig::UnixSocket(config) => config.is_valid(),
+            StreamConfig::Replay(config) => config.is_valid(),
+            StreamConfig::Mqtt(config) => config.is_valid(),
+            StreamConfig::WebSocket(config) => config.is_valid(),
+            StreamConfig::HttpStream(config) => config.is_valid(),
+            StreamConfig::SocketCan(config) => config.is_valid(),
         }
     }
 
@@ -81,6 +115,12 @@ impl StreamConfig {
             StreamConfig::Tcp(config) => config.validation_errors(),
             StreamConfig::Udp(config) => config.validation_errors(),
             StreamConfig::Serial(config) => config.validation_errors(),
+            StreamConfig::UnixSocket(config) => config.validation_errors(),
+            StreamConfig::Replay(config) => config.validation_errors(),
+            StreamConfig::Mqtt(config) => config.validation_errors(),
+            StreamConfig::WebSocket(config) => config.validation_errors(),
+            StreamConfig::HttpStream(config) => config.validation_errors(),
+            StreamConfig::SocketCan(config) => config.validation_errors(),
         }
     }
 }
@@ -0,0 +1,294 @@
+use std::path::{Path, PathBuf};
+
+use stypes::MqttQos;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    pub broker_url: String,
+    broker_err_msg: Option<&'static str>,
+    pub client_id: String,
+    client_id_err_msg: Option<&'static str>,
+    /// Topic filters separated by commas or new lines.
+    pub topics: String,
+    topics_err_msg: Option<&'static str>,
+    pub qos: MqttQos,
+    /// PEM file of the certificate authority. System certificates are used if empty.
+    pub ca_file: PathBuf,
+    /// PEM file of the client certificate. Client authentication is disabled if empty.
+    pub client_cert: PathBuf,
+    /// PEM file of the private key of the client certificate.
+    pub client_key: PathBuf,
+    tls_err_msg: Option<&'static str>,
+    pub split_topics: bool,
+    /// Topic data sent through SDE is published to. Publishing is disabled if empty.
+    pub sde_topic: String,
+    sde_topic_err_msg: Option<&'static str>,
+}
+
+impl MqttConfig {
+    pub const QOS: [MqttQos; 3] = [
+        MqttQos::AtMostOnce,
+        MqttQos::AtLeastOnce,
+        MqttQos::ExactlyOnce,
+    ];
+
+    pub fn new() -> Self {
+        let mut config = Self {
+            broker_url: String::from("mqtt://localhost:1883"),
+            broker_err_msg: None,
+            client_id: default_client_id(),
+            client_id_err_msg: None,
+            topics: String::new(),
+            topics_err_msg: None,
+            qos: MqttQos::default(),
+            ca_file: PathBuf::new(),
+            client_cert: PathBuf::new(),
+            client_key: PathBuf::new(),
+            tls_err_msg: None,
+            split_topics: false,
+            sde_topic: String::new(),
+            sde_topic_err_msg: None,
+        };
+        config.validate();
+
+        config
+    }
+
+    pub fn validate(&mut self) {
+        let broker_url = self.broker_url.trim();
+        self.broker_err_msg = if broker_url.is_empty() {
+            Some("Broker URL is required")
+        } else if uses_tls(broker_url).is_none() {
+            Some("Broker URL must start with mqtt:// or mqtts://")
+        } else {
+            None
+        };
+
+        self.client_id_err_msg = self
+            .client_id
+            .trim()
+            .is_empty()
+            .then_some("Client ID is required");
+
+        self.topics_err_msg = match parse_topics(&self.topics) {
+            Some(topics) if topics.is_empty() => Some("At least one topic is required"),
+            Some(_) => None,
+            None => Some("Wildcards must fill a whole topic level and # must be the last level"),
+        };
+
+        let has_client_auth =
+            !self.client_cert.as_os_str().is_empty() || !self.client_key.as_os_str().is_empty();
+        self.tls_err_msg = if !self.has_certificates() {
+            None
+        } else if uses_tls(broker_url) == Some(false) {
+            Some("Certificates are used with mqtts:// brokers only")
+        } else if self.ca_file.as_os_str().is_empty() {
+            Some("CA certificate is required with client certificates")
+        } else if !self.ca_file.is_file() {
+            Some("CA certificate doesn't exist")
+        } else if has_client_auth && (!self.client_cert.is_file() || !self.client_key.is_file()) {
+            Some("Client certificate and key are both required")
+        } else {
+            None
+        };
+
+        let sde_topic = self.sde_topic.trim();
+        self.sde_topic_err_msg = sde_topic
+            .contains(['+', '#'])
+            .then_some("Messages can't be published to topics with wildcards");
+    }
+
+    /// Returns whether local certificates are configured.
+    pub fn has_certificates(&self) -> bool {
+        [&self.ca_file, &self.client_cert, &self.client_key]
+            .iter()
+            .any(|path| !path.as_os_str().is_empty())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validation_errors().is_empty()
+    }
+
+    pub fn validation_errors(&self) -> Vec<&str> {
+        [
+            self.broker_err_msg,
+            self.client_id_err_msg,
+            self.topics_err_msg,
+            self.tls_err_msg,
+            self.sde_topic_err_msg,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    pub fn get_broker_err(&self) -> Option<&str> {
+        self.broker_err_msg
+    }
+
+    pub fn get_client_id_err(&self) -> Option<&str> {
+        self.client_id_err_msg
+    }
+
+    pub fn get_topics_err(&self) -> Option<&str> {
+        self.topics_err_msg
+    }
+
+    pub fn get_tls_err(&self) -> Option<&str> {
+        self.tls_err_msg
+    }
+
+    pub fn get_sde_topic_err(&self) -> Option<&str> {
+        self.sde_topic_err_msg
+    }
+}
+
+/// Client IDs must be unique per broker, therefore each setup gets its own one.
+fn default_client_id() -> String {
+    let id = Uuid::new_v4().simple().to_string();
+    format!("chipmunk-{}", &id[..8])
+}
+
+/// Returns whether the broker is connected over TLS, or `None` if the scheme isn't supported.
+fn uses_tls(broker_url: &str) -> Option<bool> {
+    match broker_url.split_once("://") {
+        Some(("mqtt" | "tcp", _)) | None => Some(false),
+        Some(("mqtts" | "ssl", _)) => Some(true),
+        Some(_) => None,
+    }
+}
+
+/// Parses the topic filters separated by commas or new lines.
+/// Returns `None` if any of them uses wildcards in an invalid way.
+fn parse_topics(topics: &str) -> Option<Vec<String>> {
+    topics
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|topic| !topic.is_empty())
+        .map(|topic| is_valid_filter(topic).then(|| topic.to_owned()))
+        .collect()
+}
+
+fn is_valid_filter(topic: &str) -> bool {
+    let levels: Vec<_> = topic.split('/').collect();
+    levels.iter().enumerate().all(|(idx, level)| match *level {
+        "+" => true,
+        "#" => idx == levels.len() - 1,
+        level => !level.contains(['+', '#']),
+    })
+}
+
+fn optional_path(path: &Path) -> Option<PathBuf> {
+    (!path.as_os_str().is_empty()).then(|| path.to_path_buf())
+}
+
+impl From<MqttConfig> for stypes::MqttTransportConfig {
+    fn from(config: MqttConfig) -> Self {
+        let tls = optional_path(&config.ca_file).map(|ca_file| stypes::MqttTlsConfig {
+            ca_file,
+            client_auth: optional_path(&config.client_cert).zip(optional_path(&config.client_key)),
+        });
+        let sde_topic = config.sde_topic.trim();
+
+        Self {
+            broker_url: config.broker_url.trim().to_owned(),
+            client_id: config.client_id.trim().to_owned(),
+            topics: parse_topics(&config.topics).unwrap_or_default(),
+            qos: config.qos,
+            tls,
+            split_topics: config.split_topics,
+            sde_topic: (!sde_topic.is_empty()).then(|| sde_topic.to_owned()),
+        }
+    }
+}
+
+impl From<&stypes::MqttTransportConfig> for MqttConfig {
+    fn from(c: &stypes::MqttTransportConfig) -> Self {
+        let (ca_file, (client_cert, client_key)) = match &c.tls {
+            Some(tls) => (
+                tls.ca_file.to_owned(),
+                tls.client_auth.to_owned().unwrap_or_default(),
+            ),
+            None => Default::default(),
+        };
+        let mut config = Self {
+            broker_url: c.broker_url.to_owned(),
+            broker_err_msg: None,
+            client_id: c.client_id.to_owned(),
+            client_id_err_msg: None,
+            topics: c.topics.join("\n"),
+            topics_err_msg: None,
+            qos: c.qos,
+            ca_file,
+            client_cert,
+            client_key,
+            tls_err_msg: None,
+            split_topics: c.split_topics,
+            sde_topic: c.sde_topic.to_owned().unwrap_or_default(),
+            sde_topic_err_msg: None,
+        };
+
+        config.validate();
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_topic_filters() {
+        let topics = parse_topics("fleet/+/logs, fleet/diag/#\n\nsensors").unwrap();
+        assert_eq!(topics, vec!["fleet/+/logs", "fleet/diag/#", "sensors"]);
+
+        assert!(parse_topics("").unwrap().is_empty());
+        assert!(parse_topics("fleet/#/logs").is_none());
+        assert!(parse_topics("fleet/ecu+").is_none());
+    }
+
+    #[test]
+    fn validate_config() {
+        let mut config = MqttConfig::new();
+        assert_eq!(
+            config.get_topics_err(),
+            Some("At least one topic is required")
+        );
+
+        config.topics = String::from("fleet/#");
+        config.validate();
+        assert!(config.is_valid());
+
+        config.broker_url = String::from("http://localhost");
+        config.sde_topic = String::from("fleet/+/cmd");
+        config.validate();
+        assert!(config.get_broker_err().is_some());
+        assert!(config.get_sde_topic_err().is_some());
+
+        config.broker_url = String::from("mqtt://localhost");
+        config.sde_topic = String::from("fleet/cmd");
+        config.ca_file = PathBuf::from("/certs/ca.pem");
+        config.validate();
+        assert_eq!(
+            config.get_tls_err(),
+            Some("Certificates are used with mqtts:// brokers only")
+        );
+    }
+
+    #[test]
+    fn transport_round_trip() {
+        let mut config = MqttConfig::new();
+        config.topics = String::from("a/b, c/#");
+        config.sde_topic = String::from(" cmd ");
+        let transport = stypes::MqttTransportConfig::from(config);
+        assert_eq!(transport.topics, vec!["a/b", "c/#"]);
+        assert_eq!(transport.sde_topic.as_deref(), Some("cmd"));
+        assert!(transport.tls.is_none());
+
+        let restored = MqttConfig::from(&transport);
+        assert_eq!(restored.topics, "a/b\nc/#");
+        assert!(restored.is_valid());
+    }
+}
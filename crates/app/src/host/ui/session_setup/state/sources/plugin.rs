@@ -0,0 +1,204 @@
+//! Byte-source plugin setup state.
+//!
+//! This module keeps byte-source plugin setup state in the same shape used by the
+//! runtime and recent-session persistence.
+
+use std::path::Path;
+
+use stypes::{
+    PluginByteSourceGeneralSettings, PluginByteSourceSettings, PluginConfigSchemaItem,
+    PluginConfigValue, PluginEntity, PluginType,
+};
+
+use crate::host::ui::{
+    session_setup::state::parsers::plugins::default_config_items, state::plugin::PluginsData,
+};
+
+const NO_PLUGIN_SELECTED: &str = "No byte-source plugin selected.";
+const INVALID_PLUGIN_KIND: &str = "Selected plugin is not a byte-source plugin.";
+
+/// Session setup state for byte-source plugins.
+#[derive(Debug, Clone)]
+pub struct PluginSourceConfig {
+    settings: Option<PluginByteSourceSettings>,
+    validation_errors: Vec<&'static str>,
+}
+
+impl PluginSourceConfig {
+    /// Creates an empty plugin source config.
+    pub fn new() -> Self {
+        Self {
+            settings: None,
+            validation_errors: vec![NO_PLUGIN_SELECTED],
+        }
+    }
+
+    /// Restores plugin source config from runtime/persisted settings.
+    pub fn from_settings(settings: PluginByteSourceSettings) -> Self {
+        Self {
+            settings: Some(settings),
+            validation_errors: Vec::new(),
+        }
+    }
+
+    /// Returns the selected byte-source plugin settings, if one has been selected.
+    pub fn selected_settings(&self) -> Option<&PluginByteSourceSettings> {
+        self.settings.as_ref()
+    }
+
+    /// Returns the selected plugin WASM path, if one has been selected.
+    pub fn selected_plugin_path(&self) -> Option<&Path> {
+        self.selected_settings()
+            .map(|settings| settings.plugin_path.as_path())
+    }
+
+    /// Returns the selected plugin directory derived from the stored WASM path.
+    pub fn selected_plugin_dir(&self) -> Option<&Path> {
+        self.selected_plugin_path()
+            .and_then(Path::parent)
+            .filter(|path| !path.as_os_str().is_empty())
+    }
+
+    /// Resolves stored settings to the current plugin entity, when available.
+    pub fn resolved_plugin<'a>(&self, data: &'a PluginsData) -> Option<&'a PluginEntity> {
+        let dir_path = self.selected_plugin_dir()?;
+        data.installed.iter().find(|plugin| {
+            plugin.plugin_type == PluginType::ByteSource && plugin.dir_path == dir_path
+        })
+    }
+
+    /// Selects a byte-source plugin and rebuilds plugin config values from schema defaults.
+    pub fn select_plugin(&mut self, plugin: &PluginEntity) {
+        if plugin.plugin_type != PluginType::ByteSource {
+            self.settings = None;
+            self.validation_errors = vec![INVALID_PLUGIN_KIND];
+            return;
+        }
+
+        self.settings = Some(PluginByteSourceSettings {
+            plugin_path: plugin.info.wasm_file_path.clone(),
+            general_settings: PluginByteSourceGeneralSettings::default(),
+            plugin_configs: default_config_items(&plugin.info.config_schemas),
+        });
+        self.validation_errors.clear();
+    }
+
+    /// Returns the mutable config value for a schema item.
+    pub fn config_value_mut(
+        &mut self,
+        schema: &PluginConfigSchemaItem,
+    ) -> Option<&mut PluginConfigValue> {
+        self.settings
+            .as_mut()?
+            .plugin_configs
+            .iter_mut()
+            .find(|item| item.id == schema.id)
+            .map(|item| &mut item.value)
+    }
+
+    /// Clears the selected plugin and cached plugin config values.
+    pub fn clear_selection(&mut self) {
+        self.settings = None;
+        self.validation_errors = vec![NO_PLUGIN_SELECTED];
+    }
+
+    /// Returns whether the cached validation state allows starting a plugin source session.
+    pub fn is_valid(&self) -> bool {
+        self.validation_errors.is_empty()
+    }
+
+    /// Returns cached validation errors suitable for UI display.
+    pub fn validation_errors(&self) -> &[&'static str] {
+        &self.validation_errors
+    }
+
+    /// Builds backend byte-source settings when the cached validation state is valid.
+    pub fn source_settings(&self) -> Result<PluginByteSourceSettings, &'static str> {
+        if !self.is_valid() {
+            return Err("Plugin source configuration is invalid.");
+        }
+
+        self.settings.clone().ok_or(NO_PLUGIN_SELECTED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use stypes::{
+        ParserRenderOptions, PluginConfigSchemaType, PluginInfo, PluginMetadata, RenderOptions,
+        SemanticVersion,
+    };
+
+    use super::*;
+
+    fn plugin(dir_path: &str, wasm_path: &str, plugin_type: PluginType) -> PluginEntity {
+        let render_options = match plugin_type {
+            PluginType::Parser => RenderOptions::Parser(Box::new(ParserRenderOptions {
+                columns_options: None,
+            })),
+            PluginType::ByteSource => RenderOptions::ByteSource,
+        };
+        PluginEntity {
+            dir_path: PathBuf::from(dir_path),
+            plugin_type,
+            info: PluginInfo {
+                wasm_file_path: PathBuf::from(wasm_path),
+                api_version: SemanticVersion::V0_1_0,
+                plugin_version: SemanticVersion::V0_1_0,
+                config_schemas: vec![PluginConfigSchemaItem {
+                    id: "port".to_owned(),
+                    title: "Port".to_owned(),
+                    description: None,
+                    input_type: PluginConfigSchemaType::Integer(5000),
+                }],
+                render_options,
+            },
+            metadata: PluginMetadata {
+                title: "Test plugin".to_owned(),
+                description: None,
+            },
+            readme_path: None,
+        }
+    }
+
+    #[test]
+    fn selecting_source_plugin_creates_valid_settings() {
+        let plugin = plugin(
+            "/plugins/source",
+            "/plugins/source/source.wasm",
+            PluginType::ByteSource,
+        );
+        let mut config = PluginSourceConfig::new();
+        assert!(!config.is_valid());
+
+        config.select_plugin(&plugin);
+
+        assert!(config.is_valid());
+        let settings = config.source_settings().unwrap();
+        assert_eq!(
+            settings.plugin_path,
+            PathBuf::from("/plugins/source/source.wasm")
+        );
+        assert!(matches!(
+            settings.plugin_configs[0].value,
+            PluginConfigValue::Integer(5000)
+        ));
+    }
+
+    #[test]
+    fn parser_plugin_cannot_validate_as_source_plugin() {
+        let plugin = plugin(
+            "/plugins/parser",
+            "/plugins/parser/parser.wasm",
+            PluginType::Parser,
+        );
+        let mut config = PluginSourceConfig::new();
+
+        config.select_plugin(&plugin);
+
+        assert!(!config.is_valid());
+        assert!(config.source_settings().is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::{ops::Not, path::PathBuf};
 
-use stypes::ShellProfile;
+use stypes::{ProcessOutputMode, ProcessRestartPolicy, ShellProfile};
 
 #[derive(Debug, Clone)]
 pub struct ProcessConfig {
@@ -8,8 +8,14 @@ pub struct ProcessConfig {
     pub command: String,
     pub shell: Option<ShellProfile>,
     pub available_shells: Vec<ShellProfile>,
+    /// Environment variables as `NAME=value` lines.
+    pub envs: String,
+    pub output: ProcessOutputMode,
+    pub pty: bool,
+    pub restart: ProcessRestartPolicy,
     command_error_msg: Option<&'static str>,
     cwd_error_msg: Option<&'static str>,
+    envs_error_msg: Option<&'static str>,
 }
 
 impl ProcessConfig {
@@ -21,8 +27,13 @@ impl ProcessConfig {
             command: String::new(),
             shell: None,
             available_shells,
+            envs: String::new(),
+            output: ProcessOutputMode::default(),
+            pty: false,
+            restart: ProcessRestartPolicy::default(),
             command_error_msg: None,
             cwd_error_msg: None,
+            envs_error_msg: None,
         };
 
         config.validate();
@@ -35,10 +46,15 @@ impl ProcessConfig {
             command: _,
             shell: _,
             available_shells: _,
+            envs: _,
+            output: _,
+            pty: _,
+            restart: _,
             command_error_msg,
             cwd_error_msg,
+            envs_error_msg,
         } = self;
-        command_error_msg.is_none() && cwd_error_msg.is_none()
+        command_error_msg.is_none() && cwd_error_msg.is_none() && envs_error_msg.is_none()
     }
 
     pub fn validate(&mut self) {
@@ -47,8 +63,13 @@ impl ProcessConfig {
             command,
             shell: _,
             available_shells: _,
+            envs,
+            output: _,
+            pty: _,
+            restart: _,
             command_error_msg,
             cwd_error_msg,
+            envs_error_msg,
         } = self;
         *command_error_msg = command
             .is_empty()
@@ -56,7 +77,14 @@ impl ProcessConfig {
         *cwd_error_msg = cwd
             .exists()
             .not()
-            .then_some("Working directory doesn't exist")
+            .then_some("Working directory doesn't exist");
+        *envs_error_msg = parse_envs(envs)
+            .is_none()
+            .then_some("Environment variables must be given as NAME=value lines");
+    }
+
+    pub fn envs_err_msg(&self) -> Option<&str> {
+        self.envs_error_msg
     }
 
     pub fn validation_errors(&self) -> Vec<&str> {
@@ -65,36 +93,68 @@ impl ProcessConfig {
             command: _,
             shell: _,
             available_shells: _,
+            envs: _,
+            output: _,
+            pty: _,
+            restart: _,
             command_error_msg,
             cwd_error_msg,
+            envs_error_msg,
         } = self;
 
-        [command_error_msg, cwd_error_msg]
+        [command_error_msg, cwd_error_msg, envs_error_msg]
             .into_iter()
             .filter_map(|err| *err)
             .collect()
     }
 }
 
+/// Parses environment variables given as `NAME=value` lines, skipping empty lines.
+/// Returns `None` if any line doesn't define a variable.
+fn parse_envs(envs: &str) -> Option<Vec<(String, String)>> {
+    envs.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| (name.to_owned(), value.to_owned()))
+        })
+        .collect()
+}
+
 impl From<ProcessConfig> for stypes::ProcessTransportConfig {
     fn from(config: ProcessConfig) -> Self {
         Self {
+            envs: parse_envs(&config.envs).unwrap_or_default(),
             cwd: config.cwd,
             command: config.command,
             shell: config.shell,
+            output: config.output,
+            pty: config.pty,
+            restart: config.restart,
         }
     }
 }
 
 impl From<&stypes::ProcessTransportConfig> for ProcessConfig {
     fn from(config: &stypes::ProcessTransportConfig) -> Self {
+        let envs = config
+            .envs
+            .iter()
+            .map(|(name, value)| format!("{name}={value}\n"))
+            .collect();
         let mut c = Self {
             cwd: config.cwd.to_owned(),
             command: config.command.to_owned(),
             shell: config.shell.to_owned(),
             available_shells: shell_tools::get_available_shells().to_vec(),
+            envs,
+            output: config.output,
+            pty: config.pty,
+            restart: config.restart,
             command_error_msg: None,
             cwd_error_msg: None,
+            envs_error_msg: None,
         };
 
         c.validate();
@@ -102,3 +162,25 @@ impl From<&stypes::ProcessTransportConfig> for ProcessConfig {
         c
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_environment_variables() {
+        let envs = parse_envs("A=1\n\n B = two=2 \nEMPTY=").unwrap();
+        assert_eq!(
+            envs,
+            vec![
+                (String::from("A"), String::from("1")),
+                (String::from("B"), String::from(" two=2 ")),
+                (String::from("EMPTY"), String::new()),
+            ]
+        );
+
+        assert!(parse_envs("").unwrap().is_empty());
+        assert!(parse_envs("NO_VALUE").is_none());
+        assert!(parse_envs("=value").is_none());
+    }
+}
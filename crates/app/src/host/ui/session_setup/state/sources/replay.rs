@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use stypes::{FileFormat, ReplaySpeed};
+
+use super::NamedValue;
+
+#[derive(Debug, Clone)]
+pub struct ReplayConfig {
+    pub path: PathBuf,
+    pub format: FileFormat,
+    pub speed: NamedValue<ReplaySpeed>,
+    err_msg: Option<&'static str>,
+}
+
+impl ReplayConfig {
+    pub const SPEED: [NamedValue<ReplaySpeed>; 6] = [
+        NamedValue::new(ReplaySpeed::Scaled(0.5), "0.5x"),
+        NamedValue::new(ReplaySpeed::Scaled(1.0), "1x (Original)"),
+        NamedValue::new(ReplaySpeed::Scaled(2.0), "2x"),
+        NamedValue::new(ReplaySpeed::Scaled(5.0), "5x"),
+        NamedValue::new(ReplaySpeed::Scaled(10.0), "10x"),
+        NamedValue::new(ReplaySpeed::Unlimited, "As fast as possible"),
+    ];
+
+    /// Formats of the traces which can be replayed.
+    pub const FORMATS: [NamedValue<FileFormat>; 3] = [
+        NamedValue::new(FileFormat::Binary, "DLT"),
+        NamedValue::new(FileFormat::PcapNG, "PcapNG"),
+        NamedValue::new(FileFormat::PcapLegacy, "Pcap"),
+    ];
+
+    pub fn new() -> Self {
+        let mut config = Self {
+            path: PathBuf::new(),
+            format: FileFormat::Binary,
+            speed: Self::speed_named(ReplaySpeed::default()),
+            err_msg: None,
+        };
+        config.validate();
+
+        config
+    }
+
+    /// Sets the path of the trace, detecting its format from the file extension.
+    pub fn set_path(&mut self, path: PathBuf) {
+        if let Some(format) = format_from_extension(&path) {
+            self.format = format;
+        }
+        self.path = path;
+        self.validate();
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.err_msg.is_none()
+    }
+
+    pub fn get_err_msg(&self) -> Option<&str> {
+        self.err_msg
+    }
+
+    pub fn validation_errors(&self) -> Vec<&str> {
+        if let Some(msg) = self.err_msg {
+            vec![msg]
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn validate(&mut self) {
+        self.err_msg = if self.path.as_os_str().is_empty() {
+            Some("Trace file is required")
+        } else if !self.path.is_file() {
+            Some("Trace file doesn't exist")
+        } else {
+            None
+        }
+    }
+
+    fn speed_named(speed: ReplaySpeed) -> NamedValue<ReplaySpeed> {
+        Self::SPEED
+            .iter()
+            .find(|named| named.value == speed)
+            .cloned()
+            .unwrap_or(NamedValue::new(speed, "Custom"))
+    }
+}
+
+/// Detects the format of traces from their file extension.
+fn format_from_extension(path: &Path) -> Option<FileFormat> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    match extension.as_str() {
+        "dlt" => Some(FileFormat::Binary),
+        "pcapng" => Some(FileFormat::PcapNG),
+        "pcap" => Some(FileFormat::PcapLegacy),
+        _ => None,
+    }
+}
+
+impl From<ReplayConfig> for stypes::ReplayTransportConfig {
+    fn from(config: ReplayConfig) -> Self {
+        Self {
+            path: config.path,
+            format: config.format,
+            speed: config.speed.value,
+        }
+    }
+}
+
+impl From<&stypes::ReplayTransportConfig> for ReplayConfig {
+    fn from(c: &stypes::ReplayTransportConfig) -> Self {
+        let mut config = Self {
+            path: c.path.to_owned(),
+            format: c.format,
+            speed: Self::speed_named(c.speed),
+            err_msg: None,
+        };
+
+        config.validate();
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_format_from_extension() {
+        let mut config = ReplayConfig::new();
+        assert!(!config.is_valid());
+
+        config.set_path(PathBuf::from("/traces/capture.PCAPNG"));
+        assert_eq!(config.format, FileFormat::PcapNG);
+        config.set_path(PathBuf::from("/traces/capture.pcap"));
+        assert_eq!(config.format, FileFormat::PcapLegacy);
+
+        // Unknown extensions keep the selected format.
+        config.set_path(PathBuf::from("/traces/capture.bin"));
+        assert_eq!(config.format, FileFormat::PcapLegacy);
+        assert_eq!(config.get_err_msg(), Some("Trace file doesn't exist"));
+    }
+}
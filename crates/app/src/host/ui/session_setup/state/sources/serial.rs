@@ -1,6 +1,8 @@
 use std::time::{Duration, Instant};
 
 use itertools::Itertools;
+use serialport::SerialPortType;
+use stypes::{SerialReadMode, SerialReconnect, SerialUsbId};
 
 const REGULAR_RESCAN_PORTS_DURATION: Duration = Duration::from_secs(10);
 const NOPORTS_RESCAN_PORTS_DURATION: Duration = Duration::from_secs(5);
@@ -26,6 +28,14 @@ impl<T> NamedValue<T> {
     }
 }
 
+/// Defines how a serial port is found again once its device is disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectKind {
+    Disabled,
+    ByPath,
+    ByUsbDevice,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum BaudRate {
     Custom(u32),
@@ -53,6 +63,8 @@ pub struct SerialConfig {
     pub path: String,
     path_err: Option<&'static str>,
     pub available_ports: Vec<String>,
+    /// Identities of the detected ports which belong to USB devices.
+    usb_ports: Vec<(String, SerialUsbId)>,
     last_ports_scan: Instant,
 
     // --- BaudRate ---
@@ -65,6 +77,13 @@ pub struct SerialConfig {
     pub stop_bits: u8,
     pub send_data_delay: NamedValue<u8>,
     pub exclusive: NamedValue<bool>,
+    pub read_mode: NamedValue<SerialReadMode>,
+
+    // --- Reconnect ---
+    pub reconnect: NamedValue<ReconnectKind>,
+    /// Identity of the USB device paired with the path it has been resolved for.
+    usb_id: Option<(String, SerialUsbId)>,
+    reconnect_err: Option<&'static str>,
 }
 
 impl SerialConfig {
@@ -89,6 +108,17 @@ impl SerialConfig {
         NamedValue::new(false, "No"),
     ];
 
+    pub const READ_MODE: &[NamedValue<SerialReadMode>] = &[
+        NamedValue::new(SerialReadMode::Lines, "Text lines (default)"),
+        NamedValue::new(SerialReadMode::Raw, "Raw bytes"),
+    ];
+
+    pub const RECONNECT: &[NamedValue<ReconnectKind>] = &[
+        NamedValue::new(ReconnectKind::Disabled, "Disabled (default)"),
+        NamedValue::new(ReconnectKind::ByPath, "By path"),
+        NamedValue::new(ReconnectKind::ByUsbDevice, "By USB device"),
+    ];
+
     pub const DELAY: &[NamedValue<u8>] = &[
         NamedValue::new(0, "No delay (default)"),
         NamedValue::new(10, "10 ms"),
@@ -99,13 +129,14 @@ impl SerialConfig {
     ];
 
     pub fn new() -> Self {
-        let available_ports = Self::scan_ports();
+        let (available_ports, usb_ports) = Self::scan_ports();
         let available_bauds = Self::all_baud_rates();
 
         let mut config = Self {
             path: String::new(),
             path_err: None,
             available_ports,
+            usb_ports,
             last_ports_scan: Instant::now(),
 
             baud_rate: BaudRate::default(),
@@ -117,6 +148,11 @@ impl SerialConfig {
             stop_bits: Self::STOP_BITS[0],
             exclusive: Self::EXCLUSIVE[0].to_owned(),
             send_data_delay: Self::DELAY[0].to_owned(),
+            read_mode: Self::READ_MODE[0].to_owned(),
+
+            reconnect: Self::RECONNECT[0].to_owned(),
+            usb_id: None,
+            reconnect_err: None,
         };
 
         config.validate();
@@ -129,6 +165,7 @@ impl SerialConfig {
             path: _,
             path_err: _,
             available_ports: _,
+            usb_ports: _,
             last_ports_scan: _,
             baud_rate,
             available_bauds: _,
@@ -138,6 +175,10 @@ impl SerialConfig {
             stop_bits,
             send_data_delay,
             exclusive,
+            read_mode,
+            reconnect,
+            usb_id: _,
+            reconnect_err: _,
         } = self;
 
         *baud_rate = BaudRate::default();
@@ -147,6 +188,8 @@ impl SerialConfig {
         *stop_bits = Self::STOP_BITS[0];
         *exclusive = Self::EXCLUSIVE[0].to_owned();
         *send_data_delay = Self::DELAY[0].to_owned();
+        *read_mode = Self::READ_MODE[0].to_owned();
+        *reconnect = Self::RECONNECT[0].to_owned();
     }
 
     pub fn reset_connection_input(&mut self) {
@@ -159,10 +202,29 @@ impl SerialConfig {
             .path
             .is_empty()
             .then_some("Parameter 'Path' can't be empty");
+
+        if let Some((_, id)) = self.usb_ports.iter().find(|(path, _)| *path == self.path) {
+            self.usb_id = Some((self.path.clone(), id.clone()));
+        }
+        self.reconnect_err = (self.reconnect.value == ReconnectKind::ByUsbDevice
+            && self.usb_id().is_none())
+        .then_some("Reconnecting by USB device needs the port of a connected USB device");
     }
 
     pub fn is_valid(&self) -> bool {
-        self.path_err.is_none()
+        self.path_err.is_none() && self.reconnect_err.is_none()
+    }
+
+    pub fn reconnect_validation_err(&self) -> Option<&str> {
+        self.reconnect_err
+    }
+
+    /// Returns the identity of the USB device of the current path if known.
+    fn usb_id(&self) -> Option<&SerialUsbId> {
+        self.usb_id
+            .as_ref()
+            .filter(|(path, _)| *path == self.path)
+            .map(|(_, id)| id)
     }
 
     pub fn port_validation_err(&self) -> Option<&str> {
@@ -170,11 +232,10 @@ impl SerialConfig {
     }
 
     pub fn validation_errors(&self) -> Vec<&str> {
-        if let Some(path_err) = self.path_err {
-            vec![path_err]
-        } else {
-            Vec::new()
-        }
+        [self.path_err, self.reconnect_err]
+            .into_iter()
+            .flatten()
+            .collect()
     }
 
     pub fn check_update_ports(&mut self) {
@@ -185,16 +246,35 @@ impl SerialConfig {
         };
 
         if self.last_ports_scan.elapsed() > check_duration {
-            self.available_ports = Self::scan_ports();
+            (self.available_ports, self.usb_ports) = Self::scan_ports();
             self.last_ports_scan = Instant::now();
         }
     }
 
-    fn scan_ports() -> Vec<String> {
-        serialport::available_ports()
-            .map(|ports| ports.into_iter().map(|port| port.port_name).collect_vec())
+    /// Returns the paths of the available ports and the identities of the USB ones among them.
+    fn scan_ports() -> (Vec<String>, Vec<(String, SerialUsbId)>) {
+        let ports = serialport::available_ports()
             .inspect_err(|err| log::warn!("Retrieving available ports failed with error: {err}"))
-            .unwrap_or_default()
+            .unwrap_or_default();
+
+        let usb_ports = ports
+            .iter()
+            .filter_map(|port| match &port.port_type {
+                SerialPortType::UsbPort(info) => Some((
+                    port.port_name.clone(),
+                    SerialUsbId {
+                        vid: info.vid,
+                        pid: info.pid,
+                        serial_number: info.serial_number.clone(),
+                    },
+                )),
+                _ => None,
+            })
+            .collect_vec();
+
+        let paths = ports.into_iter().map(|port| port.port_name).collect_vec();
+
+        (paths, usb_ports)
     }
 
     fn all_baud_rates() -> Vec<BaudRate> {
@@ -236,6 +316,14 @@ impl SerialConfig {
 
 impl From<SerialConfig> for stypes::SerialTransportConfig {
     fn from(config: SerialConfig) -> Self {
+        let reconnect = match config.reconnect.value {
+            ReconnectKind::Disabled => SerialReconnect::Disabled,
+            ReconnectKind::ByPath => SerialReconnect::ByPath,
+            ReconnectKind::ByUsbDevice => match config.usb_id() {
+                Some(id) => SerialReconnect::ByUsbId(id.to_owned()),
+                None => SerialReconnect::ByPath,
+            },
+        };
         Self {
             path: config.path,
             baud_rate: match config.baud_rate {
@@ -248,6 +336,8 @@ impl From<SerialConfig> for stypes::SerialTransportConfig {
             stop_bits: config.stop_bits,
             send_data_delay: config.send_data_delay.value,
             exclusive: config.exclusive.value,
+            read_mode: config.read_mode.value,
+            reconnect,
         }
     }
 }
@@ -263,6 +353,8 @@ impl From<&stypes::SerialTransportConfig> for SerialConfig {
             stop_bits,
             send_data_delay,
             exclusive,
+            read_mode,
+            reconnect,
         } = config;
 
         let mut c = Self::new();
@@ -295,6 +387,27 @@ impl From<&stypes::SerialTransportConfig> for SerialConfig {
             .cloned()
             .unwrap_or_else(|| Self::EXCLUSIVE[0].to_owned());
 
+        c.read_mode = Self::READ_MODE
+            .iter()
+            .find(|m| m.value == *read_mode)
+            .cloned()
+            .unwrap_or_else(|| Self::READ_MODE[0].to_owned());
+
+        let reconnect_kind = match reconnect {
+            SerialReconnect::Disabled => ReconnectKind::Disabled,
+            SerialReconnect::ByPath => ReconnectKind::ByPath,
+            SerialReconnect::ByUsbId(id) => {
+                // Keep the identity since the device may not be connected at the moment.
+                c.usb_id = Some((path.to_owned(), id.to_owned()));
+                ReconnectKind::ByUsbDevice
+            }
+        };
+        c.reconnect = Self::RECONNECT
+            .iter()
+            .find(|r| r.value == reconnect_kind)
+            .cloned()
+            .unwrap_or_else(|| Self::RECONNECT[0].to_owned());
+
         c.validate();
 
         c
@@ -1,8 +1,11 @@
 use std::net::SocketAddr;
 
+use stypes::TcpMode;
+
 #[derive(Debug, Clone)]
 pub struct TcpConfig {
     pub bind_addr: String,
+    pub mode: TcpMode,
     err_msg: Option<&'static str>,
 }
 
@@ -10,6 +13,7 @@ impl TcpConfig {
     pub fn new() -> Self {
         let mut config = Self {
             bind_addr: String::new(),
+            mode: TcpMode::default(),
             err_msg: None,
         };
         config.validate();
@@ -48,6 +52,7 @@ impl From<TcpConfig> for stypes::TCPTransportConfig {
     fn from(config: TcpConfig) -> Self {
         Self {
             bind_addr: config.bind_addr,
+            mode: config.mode,
         }
     }
 }
@@ -56,6 +61,7 @@ impl From<&stypes::TCPTransportConfig> for TcpConfig {
     fn from(c: &stypes::TCPTransportConfig) -> Self {
         let mut config = Self {
             bind_addr: c.bind_addr.to_owned(),
+            mode: c.mode,
             err_msg: None,
         };
 
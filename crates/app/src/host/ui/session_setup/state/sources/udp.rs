@@ -5,6 +5,10 @@ pub struct UdpConfig {
     pub bind_addr: String,
     bind_err_msg: Option<&'static str>,
     pub multicasts: Vec<MulticastItem>,
+    /// Addresses of the allowed senders separated by commas or new lines.
+    pub allowed_senders: String,
+    senders_err_msg: Option<&'static str>,
+    pub split_senders: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -65,6 +69,9 @@ impl UdpConfig {
             bind_addr: String::new(),
             bind_err_msg: None,
             multicasts: Vec::new(),
+            allowed_senders: String::new(),
+            senders_err_msg: None,
+            split_senders: false,
         };
 
         config.validate();
@@ -82,10 +89,16 @@ impl UdpConfig {
         };
 
         self.multicasts.iter_mut().for_each(|item| item.validate());
+
+        self.senders_err_msg = parse_senders(&self.allowed_senders)
+            .is_none()
+            .then_some("Senders must be given as IP or socket addresses");
     }
 
     pub fn is_valid(&self) -> bool {
-        self.bind_err_msg.is_none() && self.multicasts.iter().all(|item| item.is_valid())
+        self.bind_err_msg.is_none()
+            && self.senders_err_msg.is_none()
+            && self.multicasts.iter().all(|item| item.is_valid())
     }
 
     pub fn validation_errors(&self) -> Vec<&str> {
@@ -95,6 +108,10 @@ impl UdpConfig {
             errors.push(err_msg);
         }
 
+        if let Some(err_msg) = self.senders_err_msg {
+            errors.push(err_msg);
+        }
+
         errors.extend(
             self.multicasts
                 .iter()
@@ -107,6 +124,25 @@ impl UdpConfig {
     pub fn get_bind_err(&self) -> Option<&str> {
         self.bind_err_msg
     }
+
+    pub fn get_senders_err(&self) -> Option<&str> {
+        self.senders_err_msg
+    }
+}
+
+/// Parses the addresses of senders separated by commas or new lines, where each of them is
+/// an IP address or a socket address including the port.
+/// Returns `None` if any of them is invalid.
+fn parse_senders(senders: &str) -> Option<Vec<String>> {
+    senders
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|sender| !sender.is_empty())
+        .map(|sender| {
+            (sender.parse::<IpAddr>().is_ok() || sender.parse::<SocketAddr>().is_ok())
+                .then(|| sender.to_owned())
+        })
+        .collect()
 }
 
 impl From<MulticastItem> for stypes::MulticastInfo {
@@ -127,6 +163,8 @@ impl From<UdpConfig> for stypes::UDPTransportConfig {
                 .into_iter()
                 .map(stypes::MulticastInfo::from)
                 .collect(),
+            allowed_senders: parse_senders(&config.allowed_senders).unwrap_or_default(),
+            split_senders: config.split_senders,
         }
     }
 }
@@ -146,6 +184,9 @@ impl From<&stypes::UDPTransportConfig> for UdpConfig {
                 })
                 .collect(),
             bind_err_msg: None,
+            allowed_senders: config.allowed_senders.join("\n"),
+            senders_err_msg: None,
+            split_senders: config.split_senders,
         };
 
         c.validate();
@@ -153,3 +194,20 @@ impl From<&stypes::UDPTransportConfig> for UdpConfig {
         c
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_allowed_senders() {
+        let senders = parse_senders("192.168.0.10, 192.168.0.11:3490\n\n[::1]:3490").unwrap();
+        assert_eq!(
+            senders,
+            vec!["192.168.0.10", "192.168.0.11:3490", "[::1]:3490"]
+        );
+
+        assert!(parse_senders("").unwrap().is_empty());
+        assert!(parse_senders("192.168.0.10, ecu").is_none());
+    }
+}
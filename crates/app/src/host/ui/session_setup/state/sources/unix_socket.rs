@@ -0,0 +1,70 @@
+use stypes::UnixSocketMode;
+
+#[derive(Debug, Clone)]
+pub struct UnixSocketConfig {
+    pub path: String,
+    pub mode: UnixSocketMode,
+    err_msg: Option<&'static str>,
+}
+
+impl UnixSocketConfig {
+    pub fn new() -> Self {
+        let mut config = Self {
+            path: String::new(),
+            mode: UnixSocketMode::default(),
+            err_msg: None,
+        };
+        config.validate();
+
+        config
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.err_msg.is_none()
+    }
+
+    pub fn get_err_msg(&self) -> Option<&str> {
+        self.err_msg
+    }
+
+    pub fn validation_errors(&self) -> Vec<&str> {
+        if let Some(msg) = self.err_msg {
+            vec![msg]
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn validate(&mut self) {
+        self.err_msg = if !cfg!(unix) {
+            Some("Unix sockets aren't supported on this platform")
+        } else if self.path.trim().is_empty() {
+            Some("Socket path is required")
+        } else {
+            None
+        }
+    }
+}
+
+impl From<UnixSocketConfig> for stypes::UnixSocketTransportConfig {
+    fn from(config: UnixSocketConfig) -> Self {
+        Self {
+            path: config.path,
+            mode: config.mode,
+        }
+    }
+}
+
+impl From<&stypes::UnixSocketTransportConfig> for UnixSocketConfig {
+    fn from(c: &stypes::UnixSocketTransportConfig) -> Self {
+        let mut config = Self {
+            path: c.path.to_owned(),
+            mode: c.mode,
+            err_msg: None,
+        };
+
+        config.validate();
+
+        config
+    }
+}
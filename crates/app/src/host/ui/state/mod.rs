@@ -11,7 +11,7 @@ use crate::host::ui::{
 };
 
 use self::{info::AppInfoState, modal::HostModalState};
-pub use preferences::HostPreferences;
+pub use preferences::{HostPreferences, SdeMacro};
 
 /// Shared host data used across top-level UI surfaces.
 #[derive(Debug, Default)]
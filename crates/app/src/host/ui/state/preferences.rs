@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::host::common::sources::StreamNames;
+
 /// Host UI preferences stored through eframe persistence.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -11,6 +13,8 @@ pub struct HostPreferences {
     /// Controls the session stream input bar visibility.
     #[serde(default = "stream_input_visible_default")]
     pub sde_bar_visible: bool,
+    /// Macros of the session stream input bar.
+    pub sde_macros: Vec<SdeMacro>,
 }
 
 impl Default for HostPreferences {
@@ -18,10 +22,24 @@ impl Default for HostPreferences {
         Self {
             panels_visibility: PanelsVisibility::default(),
             sde_bar_visible: stream_input_visible_default(),
+            sde_macros: Vec::new(),
         }
     }
 }
 
+/// Named input of the session stream input bar, available for the sources of its
+/// transport profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SdeMacro {
+    pub profile: StreamNames,
+    pub name: String,
+    /// Text with escape sequences or hex bytes, which may contain the placeholders
+    /// `{1}` to `{9}`.
+    pub content: String,
+    /// Whether the content is given as hex bytes.
+    pub hex: bool,
+}
+
 /// Shared visibility state for the host right panel and session auxiliary panels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PanelsVisibility {
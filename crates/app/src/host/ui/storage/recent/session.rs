@@ -10,7 +10,9 @@ use log::warn;
 use serde::{Deserialize, Serialize};
 
 use processor::search::filter::SearchFilter;
-use stypes::{FileFormat, ObserveOptions, ObserveOrigin, ParserType, Transport};
+use stypes::{
+    FileFormat, ObserveOptions, ObserveOrigin, ParserType, PluginByteSourceSettings, Transport,
+};
 use uuid::Uuid;
 
 use crate::host::common::{
@@ -73,6 +75,7 @@ pub enum RecentSessionReopenMode {
 pub enum RecentSessionSource {
     File { format: FileFormat, path: PathBuf },
     Stream { transport: Transport },
+    Plugin { settings: PluginByteSourceSettings },
 }
 
 /// Stored semantic state for reopening a session.
@@ -185,13 +188,19 @@ impl RecentSessionSnapshot {
     /// Sessions only persist one stream kind, so the first stream source defines the kind.
     pub fn stream_kind(&self) -> Option<StreamNames> {
         match self.sources.first()? {
-            RecentSessionSource::File { .. } => None,
+            RecentSessionSource::File { .. } | RecentSessionSource::Plugin { .. } => None,
             RecentSessionSource::Stream { transport } => {
                 let stream_name = match transport {
                     Transport::Process(_) => StreamNames::Process,
                     Transport::TCP(_) => StreamNames::Tcp,
                     Transport::UDP(_) => StreamNames::Udp,
                     Transport::Serial(_) => StreamNames::Serial,
+                    Transport::UnixSocket(_) => StreamNames::UnixSocket,
+                    Transport::Replay(_) => StreamNames::Replay,
+                    Transport::Mqtt(_) => StreamNames::Mqtt,
+                    Transport::WebSocket(_) => StreamNames::WebSocket,
+                    Transport::HttpStream(_) => StreamNames::HttpStream,
+                    Transport::SocketCan(_) => StreamNames::SocketCan,
                 };
 
                 Some(stream_name)
@@ -245,11 +254,23 @@ impl RecentSessionSnapshot {
                 let initial = origins.next()?;
                 (initial, origins.collect())
             }
+            RecentSessionSource::Plugin { settings } => {
+                if self.sources.len() > 1 {
+                    warn!(
+                        "Recent source snapshot contains multiple plugin sources and cannot be restored"
+                    );
+                    return None;
+                }
+
+                let origin = ObserveOrigin::Plugin(Uuid::new_v4().to_string(), settings.clone());
+                (origin, Vec::new())
+            }
         };
 
         let options = ObserveOptions {
             origin,
             parser: self.parser.clone(),
+            recording: None,
         };
 
         Some((options, additional_sources))
@@ -272,12 +293,36 @@ impl RecentSessionSource {
                 .map(|(_, format, path)| RecentSessionSource::File { format, path })
                 .collect(),
             ObserveOrigin::Stream(_, transport) => {
-                vec![RecentSessionSource::Stream { transport }]
+                vec![RecentSessionSource::Stream {
+                    transport: without_secrets(transport),
+                }]
             }
+            ObserveOrigin::Plugin(_, settings) => {
+                vec![RecentSessionSource::Plugin { settings }]
+            }
+            // Watched directories can't be restored from recent sessions yet.
+            ObserveOrigin::DirWatch(..) => Vec::new(),
         }
     }
 }
 
+/// Removes credentials from the transport, since recent sessions are stored unencrypted.
+fn without_secrets(mut transport: Transport) -> Transport {
+    match &mut transport {
+        Transport::WebSocket(config) => config.bearer_token = None,
+        Transport::HttpStream(config) => config.bearer_token = None,
+        Transport::Process(_)
+        | Transport::TCP(_)
+        | Transport::UDP(_)
+        | Transport::Serial(_)
+        | Transport::UnixSocket(_)
+        | Transport::Replay(_)
+        | Transport::Mqtt(_)
+        | Transport::SocketCan(_) => {}
+    }
+    transport
+}
+
 fn build_cache(
     sources: &[RecentSessionSource],
     parser: &ParserType,
@@ -308,11 +353,32 @@ fn build_title(sources: &[RecentSessionSource]) -> String {
                 Transport::TCP(config) => config.bind_addr.clone(),
                 Transport::UDP(config) => config.bind_addr.clone(),
                 Transport::Serial(config) => config.path.clone(),
+                Transport::UnixSocket(config) => config.path.clone(),
+                Transport::Replay(config) => config
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| config.path.display().to_string()),
+                Transport::Mqtt(config) => config.broker_url.clone(),
+                Transport::WebSocket(config) => config.url.clone(),
+                Transport::HttpStream(config) => config.url.clone(),
+                Transport::SocketCan(config) => config.interface.clone(),
             },
+            RecentSessionSource::Plugin { settings } => plugin_source_name(settings),
         })
         .join(" & ")
 }
 
+/// Plugin binaries share generic names, so the plugin directory names the source.
+fn plugin_source_name(settings: &PluginByteSourceSettings) -> String {
+    let path = &settings.plugin_path;
+    path.parent()
+        .and_then(|dir| dir.file_name())
+        .or_else(|| path.file_stem())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
 fn build_summary(sources: &[RecentSessionSource], parser: &ParserType) -> String {
     let parser_name = ParserNames::from(parser);
     format!("{} • {parser_name}", build_source_summary(sources))
@@ -327,13 +393,29 @@ fn build_source_summary(sources: &[RecentSessionSource]) -> String {
             Transport::TCP(_) => String::from("1 TCP connection"),
             Transport::UDP(_) => String::from("1 UDP connection"),
             Transport::Serial(_) => String::from("1 serial connection"),
+            Transport::UnixSocket(_) => String::from("1 Unix socket connection"),
+            Transport::Replay(_) => String::from("1 replayed trace"),
+            Transport::Mqtt(_) => String::from("1 MQTT subscription"),
+            Transport::WebSocket(_) => String::from("1 WebSocket connection"),
+            Transport::HttpStream(_) => String::from("1 HTTP stream"),
+            Transport::SocketCan(_) => String::from("1 CAN interface"),
         },
         Some(RecentSessionSource::Stream { transport }) => match transport {
             Transport::Process(_) => format!("{} terminal commands", sources.len()),
             Transport::TCP(_) => format!("{} TCP connections", sources.len()),
             Transport::UDP(_) => format!("{} UDP connections", sources.len()),
             Transport::Serial(_) => format!("{} serial connections", sources.len()),
+            Transport::UnixSocket(_) => format!("{} Unix socket connections", sources.len()),
+            Transport::Replay(_) => format!("{} replayed traces", sources.len()),
+            Transport::Mqtt(_) => format!("{} MQTT subscriptions", sources.len()),
+            Transport::WebSocket(_) => format!("{} WebSocket connections", sources.len()),
+            Transport::HttpStream(_) => format!("{} HTTP streams", sources.len()),
+            Transport::SocketCan(_) => format!("{} CAN interfaces", sources.len()),
         },
+        Some(RecentSessionSource::Plugin { .. }) if sources.len() == 1 => {
+            String::from("1 plugin source")
+        }
+        Some(RecentSessionSource::Plugin { .. }) => format!("{} plugin sources", sources.len()),
         None => String::from("No sources"),
     }
 }
@@ -380,6 +462,7 @@ fn append_source_tooltip_lines(tooltip: &mut String, sources: &[RecentSessionSou
                 }
                 Transport::TCP(config) => {
                     let _ = writeln!(tooltip, "- TCP: {}", config.bind_addr);
+                    let _ = writeln!(tooltip, "  Mode: {}", config.mode);
                 }
                 Transport::UDP(config) => {
                     let _ = writeln!(tooltip, "- UDP: {}", config.bind_addr);
@@ -405,7 +488,46 @@ fn append_source_tooltip_lines(tooltip: &mut String, sources: &[RecentSessionSou
                     let _ = writeln!(tooltip, "- Serial port: {}", config.path);
                     let _ = writeln!(tooltip, "  Baud rate: {}", config.baud_rate);
                 }
+                Transport::UnixSocket(config) => {
+                    let _ = writeln!(tooltip, "- Unix socket: {}", config.path);
+                    let _ = writeln!(tooltip, "  Mode: {}", config.mode);
+                }
+                Transport::Replay(config) => {
+                    let _ = writeln!(tooltip, "- Replayed trace: {}", config.path.display());
+                }
+                Transport::Mqtt(config) => {
+                    let _ = writeln!(tooltip, "- MQTT: {}", config.broker_url);
+                    let _ = writeln!(tooltip, "  Topics: {}", config.topics.join(", "));
+                    let _ = writeln!(tooltip, "  QoS: {}", config.qos);
+                }
+                Transport::WebSocket(config) => {
+                    let _ = writeln!(tooltip, "- WebSocket: {}", config.url);
+                    let _ = writeln!(tooltip, "  Messages: {}", config.messages);
+                }
+                Transport::HttpStream(config) => {
+                    let _ = writeln!(tooltip, "- HTTP stream: {}", config.url);
+                    let _ = writeln!(tooltip, "  Body: {}", config.format);
+                }
+                Transport::SocketCan(config) => {
+                    let _ = writeln!(tooltip, "- SocketCAN: {}", config.interface);
+                    if !config.filters.is_empty() {
+                        let filters = config.filters.iter().map(|f| f.to_string()).join(", ");
+                        let _ = writeln!(tooltip, "  Filters: {filters}");
+                    }
+                }
             },
+            RecentSessionSource::Plugin { settings } => {
+                let _ = writeln!(
+                    tooltip,
+                    "- Byte-source plugin: {}",
+                    settings.plugin_path.display()
+                );
+                let _ = writeln!(
+                    tooltip,
+                    "  Plugin configs: {}",
+                    settings.plugin_configs.len()
+                );
+            }
         }
     }
 }
@@ -479,8 +601,8 @@ mod tests {
 
     use processor::search::filter::SearchFilter;
     use stypes::{
-        DltParserSettings, ObserveOptions, ObserveOrigin, ParserType, SomeIpParserSettings,
-        TCPTransportConfig, Transport, UDPTransportConfig,
+        DltParserSettings, ObserveOptions, ObserveOrigin, ParserType, PluginByteSourceSettings,
+        SomeIpParserSettings, TCPTransportConfig, TcpMode, Transport, UDPTransportConfig,
     };
 
     use crate::{common::time::unix_timestamp_now, host::common::colors::StoredColorPair};
@@ -505,9 +627,11 @@ mod tests {
                 String::new(),
                 Transport::TCP(TCPTransportConfig {
                     bind_addr: bind_addr.to_owned(),
+                    mode: TcpMode::Connect,
                 }),
             ),
             parser: ParserType::Text(()),
+            recording: None,
         })
     }
 
@@ -527,6 +651,7 @@ mod tests {
                 ),
             ]),
             parser: ParserType::Text(()),
+            recording: None,
         });
 
         assert_eq!(snapshot.title(), "first.log & second.log");
@@ -538,11 +663,13 @@ mod tests {
             RecentSessionSource::Stream {
                 transport: Transport::TCP(TCPTransportConfig {
                     bind_addr: String::from("127.0.0.1:5000"),
+                    mode: TcpMode::Connect,
                 }),
             },
             RecentSessionSource::Stream {
                 transport: Transport::TCP(TCPTransportConfig {
                     bind_addr: String::from("127.0.0.1:5001"),
+                    mode: TcpMode::Connect,
                 }),
             },
         ];
@@ -571,12 +698,14 @@ mod tests {
                 String::new(),
                 Transport::TCP(TCPTransportConfig {
                     bind_addr: String::from("127.0.0.1:5556"),
+                    mode: TcpMode::Connect,
                 }),
             ),
             parser: ParserType::SomeIp(SomeIpParserSettings {
                 filter_config: None,
                 fibex_file_paths: Some(vec![String::from("/tmp/one.xml")]),
             }),
+            recording: None,
         });
 
         assert_eq!(
@@ -594,12 +723,16 @@ mod tests {
                     transport: Transport::UDP(UDPTransportConfig {
                         bind_addr: String::from("127.0.0.1:5000"),
                         multicast: Vec::new(),
+                        allowed_senders: Vec::new(),
+                        split_senders: false,
                     }),
                 },
                 RecentSessionSource::Stream {
                     transport: Transport::UDP(UDPTransportConfig {
                         bind_addr: String::from("127.0.0.1:5001"),
                         multicast: Vec::new(),
+                        allowed_senders: Vec::new(),
+                        split_senders: false,
                     }),
                 },
             ],
@@ -755,6 +888,7 @@ mod tests {
                 ),
             ]),
             parser: ParserType::Text(()),
+            recording: None,
         });
 
         let (restored, additional_sources) = snapshot
@@ -786,6 +920,7 @@ mod tests {
                 ),
             ]),
             parser: ParserType::Text(()),
+            recording: None,
         });
         let stream = stream_snapshot("127.0.0.1:5556");
         let multi_stream = RecentSessionSnapshot::new(
@@ -794,12 +929,15 @@ mod tests {
                 RecentSessionSource::Stream {
                     transport: Transport::TCP(TCPTransportConfig {
                         bind_addr: String::from("127.0.0.1:5000"),
+                        mode: TcpMode::Connect,
                     }),
                 },
                 RecentSessionSource::Stream {
                     transport: Transport::UDP(UDPTransportConfig {
                         bind_addr: String::from("127.0.0.1:5001"),
                         multicast: Vec::new(),
+                        allowed_senders: Vec::new(),
+                        split_senders: false,
                     }),
                 },
             ],
@@ -811,4 +949,39 @@ mod tests {
         assert!(!stream.supports_clean_open());
         assert!(!multi_stream.supports_clean_open());
     }
+
+    #[test]
+    fn plugin_source_restores_settings() {
+        let snapshot = snapshot_from_observe_options(ObserveOptions {
+            origin: ObserveOrigin::Plugin(
+                String::from("plugin-id"),
+                PluginByteSourceSettings {
+                    plugin_path: PathBuf::from("/plugins/mqtt/plugin.wasm"),
+                    general_settings: Default::default(),
+                    plugin_configs: Vec::new(),
+                },
+            ),
+            parser: ParserType::Text(()),
+            recording: None,
+        });
+
+        assert_eq!(snapshot.title(), "mqtt");
+        assert_eq!(snapshot.summary(), "1 plugin source • Plain Text");
+        assert!(snapshot.stream_kind().is_none());
+        assert!(!snapshot.supports_clean_open());
+
+        let (restored, additional_sources) = snapshot
+            .to_startup_restore_plan()
+            .expect("plugin restore plan should be rebuilt");
+
+        assert!(additional_sources.is_empty());
+        let ObserveOrigin::Plugin(id, settings) = restored.origin else {
+            panic!("plugin origin should be restored");
+        };
+        assert_ne!(id, "plugin-id");
+        assert_eq!(
+            settings.plugin_path,
+            PathBuf::from("/plugins/mqtt/plugin.wasm")
+        );
+    }
 }
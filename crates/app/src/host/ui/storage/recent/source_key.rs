@@ -7,8 +7,12 @@ use std::{path::Path, sync::Arc};
 
 use blake3::Hasher;
 use stypes::{
-    FileFormat, MulticastInfo, ProcessTransportConfig, SerialTransportConfig, ShellProfile,
-    ShellType, TCPTransportConfig, Transport, UDPTransportConfig,
+    CanFilter, FileFormat, HttpStreamFormat, HttpStreamTransportConfig, MqttTransportConfig,
+    MulticastInfo, PluginByteSourceSettings, PluginConfigItem, PluginConfigValue,
+    ProcessOutputMode, ProcessRestartPolicy, ProcessTransportConfig, ReplayTransportConfig,
+    SerialReadMode, SerialReconnect, SerialTransportConfig, SerialUsbId, ShellProfile, ShellType,
+    SocketCanTransportConfig, TCPTransportConfig, TcpMode, Transport, UDPTransportConfig,
+    UnixSocketMode, UnixSocketTransportConfig, WebSocketMessageMode, WebSocketTransportConfig,
 };
 
 use super::session::RecentSessionSource;
@@ -38,6 +42,10 @@ fn hash_source(hasher: &mut Hasher, source: &RecentSessionSource) {
             hasher.update(&[1]);
             hash_transport(hasher, transport);
         }
+        RecentSessionSource::Plugin { settings } => {
+            hasher.update(&[2]);
+            hash_plugin_source(hasher, settings);
+        }
     }
 }
 
@@ -59,6 +67,10 @@ fn hash_transport(hasher: &mut Hasher, transport: &Transport) {
             cwd,
             command,
             shell,
+            envs,
+            output,
+            pty,
+            restart,
         }) => {
             hasher.update(&[0]);
             hash_path(hasher, cwd);
@@ -83,14 +95,45 @@ fn hash_transport(hasher: &mut Hasher, transport: &Transport) {
                     hasher.update(&[0]);
                 }
             }
+
+            // Options are hashed only if they differ from the defaults, so keys of existing
+            // entries stay unchanged.
+            let default_options = envs.is_empty()
+                && *output == ProcessOutputMode::default()
+                && !pty
+                && *restart == ProcessRestartPolicy::default();
+            if !default_options {
+                hasher.update(&(envs.len() as u64).to_le_bytes());
+                for (name, value) in envs {
+                    hash_bytes(hasher, name.as_bytes());
+                    hash_bytes(hasher, value.as_bytes());
+                }
+                let output_tag = match output {
+                    ProcessOutputMode::Lines => 0,
+                    ProcessOutputMode::Raw => 1,
+                };
+                let restart_tag = match restart {
+                    ProcessRestartPolicy::Never => 0,
+                    ProcessRestartPolicy::OnFailure => 1,
+                    ProcessRestartPolicy::Always => 2,
+                };
+                hasher.update(&[output_tag, u8::from(*pty), restart_tag]);
+            }
         }
-        Transport::TCP(TCPTransportConfig { bind_addr }) => {
-            hasher.update(&[1]);
+        Transport::TCP(TCPTransportConfig { bind_addr, mode }) => {
+            // Listen mode uses its own tag so keys of existing connect entries stay unchanged.
+            let tag = match mode {
+                TcpMode::Connect => 1,
+                TcpMode::Listen => 4,
+            };
+            hasher.update(&[tag]);
             hash_bytes(hasher, bind_addr.as_bytes());
         }
         Transport::UDP(UDPTransportConfig {
             bind_addr,
             multicast,
+            allowed_senders,
+            split_senders,
         }) => {
             hasher.update(&[2]);
             hash_bytes(hasher, bind_addr.as_bytes());
@@ -111,6 +154,16 @@ fn hash_transport(hasher: &mut Hasher, transport: &Transport) {
                     }
                 }
             }
+
+            // Options are hashed only if they differ from the defaults, so keys of existing
+            // entries stay unchanged.
+            if !allowed_senders.is_empty() || *split_senders {
+                hasher.update(&(allowed_senders.len() as u64).to_le_bytes());
+                for sender in allowed_senders {
+                    hash_bytes(hasher, sender.as_bytes());
+                }
+                hasher.update(&[u8::from(*split_senders)]);
+            }
         }
         Transport::Serial(SerialTransportConfig {
             path,
@@ -121,6 +174,8 @@ fn hash_transport(hasher: &mut Hasher, transport: &Transport) {
             stop_bits,
             send_data_delay,
             exclusive,
+            read_mode,
+            reconnect,
         }) => {
             hasher.update(&[3]);
             hash_bytes(hasher, path.as_bytes());
@@ -131,6 +186,142 @@ fn hash_transport(hasher: &mut Hasher, transport: &Transport) {
             hasher.update(&[*stop_bits]);
             hasher.update(&[*send_data_delay]);
             hasher.update(&[u8::from(*exclusive)]);
+
+            // Options are hashed only if they differ from the defaults, so keys of existing
+            // entries stay unchanged.
+            if *read_mode != SerialReadMode::default() || *reconnect != SerialReconnect::default() {
+                let read_mode_tag = match read_mode {
+                    SerialReadMode::Lines => 0,
+                    SerialReadMode::Raw => 1,
+                };
+                hasher.update(&[read_mode_tag]);
+                match reconnect {
+                    SerialReconnect::Disabled => {
+                        hasher.update(&[0]);
+                    }
+                    SerialReconnect::ByPath => {
+                        hasher.update(&[1]);
+                    }
+                    SerialReconnect::ByUsbId(SerialUsbId {
+                        vid,
+                        pid,
+                        serial_number,
+                    }) => {
+                        hasher.update(&[2]);
+                        hasher.update(&vid.to_le_bytes());
+                        hasher.update(&pid.to_le_bytes());
+                        hash_bytes(
+                            hasher,
+                            serial_number.as_deref().unwrap_or_default().as_bytes(),
+                        );
+                    }
+                }
+            }
+        }
+        Transport::UnixSocket(UnixSocketTransportConfig { path, mode }) => {
+            let tag = match mode {
+                UnixSocketMode::Stream => 5,
+                UnixSocketMode::Datagram => 6,
+            };
+            hasher.update(&[tag]);
+            hash_bytes(hasher, path.as_bytes());
+        }
+        // Speed is a playback setting, replays of the same trace share their key.
+        Transport::Replay(ReplayTransportConfig { path, format, .. }) => {
+            hasher.update(&[7]);
+            hash_file_format(hasher, *format);
+            hash_path(hasher, path);
+        }
+        // Client IDs are generated per setup and TLS or SDE settings don't change the received
+        // data, so only the subscription itself identifies the source.
+        Transport::Mqtt(MqttTransportConfig {
+            broker_url,
+            topics,
+            split_topics,
+            ..
+        }) => {
+            hasher.update(&[8, u8::from(*split_topics)]);
+            hash_bytes(hasher, broker_url.as_bytes());
+            hasher.update(&(topics.len() as u64).to_le_bytes());
+            for topic in topics {
+                hash_bytes(hasher, topic.as_bytes());
+            }
+        }
+        // Headers and tokens are credentials rather than part of the source identity.
+        Transport::WebSocket(WebSocketTransportConfig { url, messages, .. }) => {
+            let mode_tag = match messages {
+                WebSocketMessageMode::Lines => 0,
+                WebSocketMessageMode::Raw => 1,
+            };
+            hasher.update(&[9, mode_tag]);
+            hash_bytes(hasher, url.as_bytes());
+        }
+        Transport::HttpStream(HttpStreamTransportConfig { url, format, .. }) => {
+            let format_tag = match format {
+                HttpStreamFormat::Raw => 0,
+                HttpStreamFormat::ServerSentEvents => 1,
+            };
+            hasher.update(&[10, format_tag]);
+            hash_bytes(hasher, url.as_bytes());
+        }
+        // Timestamps don't change which frames are received.
+        Transport::SocketCan(SocketCanTransportConfig {
+            interface,
+            filters,
+            fd_frames,
+            error_frames,
+            ..
+        }) => {
+            hasher.update(&[11, u8::from(*fd_frames), u8::from(*error_frames)]);
+            hash_bytes(hasher, interface.as_bytes());
+            hasher.update(&(filters.len() as u64).to_le_bytes());
+            for CanFilter { id, mask, extended } in filters {
+                hasher.update(&id.to_le_bytes());
+                hasher.update(&mask.to_le_bytes());
+                hasher.update(&[u8::from(*extended)]);
+            }
+        }
+    }
+}
+
+/// Feeds the plugin binary and its config values into the digest.
+fn hash_plugin_source(hasher: &mut Hasher, settings: &PluginByteSourceSettings) {
+    hash_path(hasher, &settings.plugin_path);
+    hasher.update(&(settings.plugin_configs.len() as u64).to_le_bytes());
+    for PluginConfigItem { id, value } in &settings.plugin_configs {
+        hash_bytes(hasher, id.as_bytes());
+        match value {
+            PluginConfigValue::Boolean(value) => {
+                hasher.update(&[0, u8::from(*value)]);
+            }
+            PluginConfigValue::Integer(value) => {
+                hasher.update(&[1]);
+                hasher.update(&value.to_le_bytes());
+            }
+            PluginConfigValue::Float(value) => {
+                hasher.update(&[2]);
+                hasher.update(&value.to_le_bytes());
+            }
+            PluginConfigValue::Text(value) => {
+                hasher.update(&[3]);
+                hash_bytes(hasher, value.as_bytes());
+            }
+            PluginConfigValue::Directories(paths) | PluginConfigValue::Files(paths) => {
+                let tag = if matches!(value, PluginConfigValue::Directories(_)) {
+                    4
+                } else {
+                    5
+                };
+                hasher.update(&[tag]);
+                hasher.update(&(paths.len() as u64).to_le_bytes());
+                for path in paths {
+                    hash_path(hasher, path);
+                }
+            }
+            PluginConfigValue::Dropdown(value) => {
+                hasher.update(&[6]);
+                hash_bytes(hasher, value.as_bytes());
+            }
         }
     }
 }
@@ -151,7 +342,7 @@ fn hash_path(hasher: &mut Hasher, path: &Path) {
 mod tests {
     use std::path::PathBuf;
 
-    use stypes::{TCPTransportConfig, Transport};
+    use stypes::{TCPTransportConfig, TcpMode, Transport};
 
     use super::*;
     use crate::host::ui::storage::recent::session::{
@@ -192,6 +383,7 @@ mod tests {
         let sources = vec![RecentSessionSource::Stream {
             transport: Transport::TCP(TCPTransportConfig {
                 bind_addr: String::from("127.0.0.1:5556"),
+                mode: TcpMode::Connect,
             }),
         }];
 
@@ -200,6 +392,23 @@ mod tests {
         assert!(source_key.bytes().all(|byte| byte.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn source_key_respects_tcp_mode() {
+        let source = |mode| {
+            vec![RecentSessionSource::Stream {
+                transport: Transport::TCP(TCPTransportConfig {
+                    bind_addr: String::from("127.0.0.1:5556"),
+                    mode,
+                }),
+            }]
+        };
+
+        assert_ne!(
+            from_sources(&source(TcpMode::Connect)),
+            from_sources(&source(TcpMode::Listen))
+        );
+    }
+
     fn snapshot_from_observe_options(options: stypes::ObserveOptions) -> RecentSessionSnapshot {
         RecentSessionRegistration::new(
             0,
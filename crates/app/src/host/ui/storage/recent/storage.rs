@@ -170,7 +170,9 @@ impl RecentSessionsStorage {
 mod tests {
     use std::{fs, path::PathBuf, sync::Arc};
 
-    use stypes::{ObserveOptions, ObserveOrigin, ParserType, TCPTransportConfig, Transport};
+    use stypes::{
+        ObserveOptions, ObserveOrigin, ParserType, TCPTransportConfig, TcpMode, Transport,
+    };
     use tempfile::{TempDir, tempdir};
 
     use super::{MAX_RECENT_SESSIONS, RecentSessionsStorage};
@@ -225,9 +227,11 @@ mod tests {
                 String::new(),
                 Transport::TCP(TCPTransportConfig {
                     bind_addr: bind_addr.to_owned(),
+                    mode: TcpMode::Connect,
                 }),
             ),
             parser: ParserType::Text(()),
+            recording: None,
         })
     }
 
@@ -18,6 +18,9 @@ pub enum RecentValidationError {
     /// A file source referenced by the snapshot no longer exists.
     #[error("File is no longer available:\n{}", .0.display())]
     MissingFile(PathBuf),
+    /// A byte-source plugin referenced by the snapshot no longer exists.
+    #[error("Byte-source plugin is no longer available:\n{}", .0.display())]
+    MissingSourcePlugin(PathBuf),
     /// The saved plugin path cannot be mapped to a plugin directory.
     #[error("Parser plugin path is invalid:\n{}", .0.display())]
     PluginPathInvalid(PathBuf),
@@ -71,6 +74,13 @@ pub fn validate_sources(snapshot: &RecentSessionSnapshot) -> Result<(), RecentVa
                 }
             }
             RecentSessionSource::Stream { .. } => {}
+            RecentSessionSource::Plugin { settings } => {
+                if !settings.plugin_path.exists() {
+                    return Err(RecentValidationError::MissingSourcePlugin(
+                        settings.plugin_path.clone(),
+                    ));
+                }
+            }
         }
     }
 
@@ -131,7 +141,7 @@ pub fn validate_saved_parser(
 mod tests {
     use std::path::PathBuf;
 
-    use stypes::{ObserveOptions, ParserType, PluginType, TCPTransportConfig, Transport};
+    use stypes::{ObserveOptions, ParserType, PluginType, TCPTransportConfig, TcpMode, Transport};
     use tempfile::{NamedTempFile, tempdir};
 
     use crate::{
@@ -162,9 +172,11 @@ mod tests {
                 String::new(),
                 Transport::TCP(TCPTransportConfig {
                     bind_addr: bind_addr.to_owned(),
+                    mode: TcpMode::Connect,
                 }),
             ),
             parser: ParserType::Text(()),
+            recording: None,
         })
     }
 
@@ -310,6 +310,21 @@ impl HostTabs {
         session.request_tab_close(actions);
     }
 
+    /// Activates the session tab identified by `session_id` and focuses `row` in its main
+    /// logs table. Sessions closed meanwhile are ignored.
+    pub fn focus_session_row(&mut self, session_id: Uuid, row: u64) {
+        let Some(session_tab_idx) = self.tabs.iter().position(
+            |tab| matches!(tab, HostTab::Session(session) if session.get_info().id == session_id),
+        ) else {
+            return;
+        };
+
+        self.activate_tab(session_tab_idx);
+        if let HostTab::Session(session) = &mut self.tabs[session_tab_idx] {
+            session.focus_row(row);
+        }
+    }
+
     /// Removes the setup tab identified by `setup_id`.
     pub fn close_session_setup(&mut self, setup_id: Uuid) {
         let Some(tab_idx) = self
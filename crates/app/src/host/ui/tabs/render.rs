@@ -155,6 +155,8 @@ impl HostTabs {
             ..
         } = state;
 
+        self.sync_compare_sessions();
+
         match self.active_mut() {
             HostTab::Home(home) => home.render_content(storage, actions, preferences, plugins, ui),
             HostTab::Session(session) => session.render_content(actions, registry, preferences, ui),
@@ -170,6 +172,33 @@ impl HostTabs {
     }
 }
 
+impl HostTabs {
+    /// Provides the other sessions to the active session while it shows the comparison
+    /// of sessions.
+    fn sync_compare_sessions(&mut self) {
+        let HostTab::Session(active) = self.active() else {
+            return;
+        };
+        if !active.wants_compare_sessions() {
+            return;
+        }
+        let active_id = active.get_info().id;
+        let sessions = self
+            .tabs
+            .iter()
+            .filter_map(|tab| match tab {
+                HostTab::Session(session) if session.get_info().id != active_id => {
+                    session.compare_session()
+                }
+                _ => None,
+            })
+            .collect();
+        if let HostTab::Session(active) = self.active_mut() {
+            active.set_compare_sessions(sessions);
+        }
+    }
+}
+
 fn tab_label(ui: &mut Ui, title: &str) {
     ui.add(
         Label::new(RichText::new(title).text_style(HOST_TAB_TEXT_STYLE))
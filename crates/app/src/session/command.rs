@@ -2,8 +2,11 @@ use std::{ops::RangeInclusive, path::PathBuf};
 
 use std::sync::mpsc::Sender;
 
-use processor::{grabber::LineRange, search::filter::SearchFilter};
-use session_core::state::IndexedNavigation;
+use processor::{
+    grabber::LineRange,
+    search::{filter::SearchFilter, query::FilterQuery},
+};
+use session_core::state::{DuplicatesMode, IndexedNavigation};
 use stypes::GrabbedElement;
 use uuid::Uuid;
 
@@ -11,7 +14,10 @@ use crate::host::ui::{
     session_setup::state::sources::StreamConfig,
     storage::recent::session::RecentSessionStateSnapshot,
 };
-use crate::session::{error::SessionError, types::attachment};
+use crate::session::{
+    error::SessionError,
+    types::{TimeQuery, attachment},
+};
 
 /// Represents session specific commands to be sent from UI to session service.
 ///
@@ -26,6 +32,11 @@ pub enum SessionCommand {
         operation_id: Uuid,
         filters: Vec<SearchFilter>,
     },
+    /// Apply the provided boolean query over search filters.
+    ApplySearchQuery {
+        operation_id: Uuid,
+        query: FilterQuery,
+    },
     /// Cancel current search and clear results.
     /// If search operation is still processing then a search id will be provided
     /// to abort this operation.
@@ -33,11 +44,48 @@ pub enum SessionCommand {
     /// Apply the provided search value filters.
     ApplySearchValuesFilter {
         operation_id: Uuid,
-        filters: Vec<String>,
+        filters: Vec<SearchFilter>,
     },
     /// Cancel current search values extraction and clear value results.
     /// If operation is still processing then an id will be provided to abort it.
     DropSearchValues { operation_id: Option<Uuid> },
+    /// Hide the rows matching any of the provided filters from the main logs table.
+    /// Empty filters show all rows again.
+    ApplyExclusions {
+        operation_id: Uuid,
+        filters: Vec<SearchFilter>,
+    },
+    /// Mine templates of similar messages from the session rows, counting their messages in
+    /// `buckets` intervals of the session time.
+    MineTemplates { operation_id: Uuid, buckets: u16 },
+    /// Collapse runs of consecutive duplicate rows in the main logs table, comparing rows
+    /// as defined by `mode`. `None` shows all duplicates again.
+    CollapseDuplicates {
+        operation_id: Uuid,
+        mode: Option<DuplicatesMode>,
+    },
+    /// Compare the session rows with the lines of another session file or log file by
+    /// their normalized messages, up to `other_rows` lines of the other file if given.
+    Compare {
+        operation_id: Uuid,
+        other: PathBuf,
+        other_rows: Option<u64>,
+    },
+    /// Request the path of the session file, which other sessions compare with.
+    GetSessionFile,
+    /// Expand or collapse the run of duplicates a session position belongs to.
+    ExpandDuplicates {
+        /// Original row position in the complete session stream.
+        session_position: u64,
+        expanded: bool,
+    },
+    /// Request the main-table row showing a session position while rows are hidden.
+    GetViewRow {
+        /// Original row position in the complete session stream.
+        session_position: u64,
+    },
+    /// Request the first session row at or after the given time.
+    GetRowAtTime { time: TimeQuery },
     /// Request the indexed-table row nearest to a session position.
     GetNearestIndexedRow {
         /// Original row position in the complete session stream.
@@ -118,6 +166,8 @@ pub enum SessionCommand {
         operation_id: Uuid,
         destination: PathBuf,
         target: ExportTarget,
+        /// Whether rows of duplicates collapsed in the main view are exported.
+        include_duplicates: bool,
     },
 
     /// Export rendered text logs for the selected target.
@@ -125,6 +175,8 @@ pub enum SessionCommand {
         operation_id: Uuid,
         destination: PathBuf,
         target: ExportTarget,
+        /// Whether rows of duplicates collapsed in the main view are exported.
+        include_duplicates: bool,
         options: Box<TextExportOptions>,
     },
 
@@ -142,6 +194,27 @@ pub enum SessionCommand {
         text: String,
     },
 
+    /// Send raw bytes into an SDE-capable observed source.
+    SendSdeBytes {
+        /// Operation that should receive the bytes.
+        target: Uuid,
+        bytes: Vec<u8>,
+    },
+
+    /// Send a DLT control message to the ECU behind an observed source.
+    SendSdeDltControl {
+        /// Operation that should send the message.
+        target: Uuid,
+        request: stypes::DltControlRequest,
+    },
+
+    /// Control the playback of a replayed trace.
+    ControlReplay {
+        /// Operation replaying the trace.
+        target: Uuid,
+        control: stypes::ReplayControl,
+    },
+
     /// Cancel the running operation with the given id.
     CancelOperation { id: Uuid },
     /// Gracefully terminate the session service.
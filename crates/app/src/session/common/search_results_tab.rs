@@ -70,7 +70,8 @@ fn first_file_format(origin: &ObserveOrigin) -> Option<FileFormat> {
             };
             Some(*format)
         }
-        ObserveOrigin::Stream(_, _) => None,
+        ObserveOrigin::DirWatch(_, config) => Some(config.format),
+        ObserveOrigin::Stream(_, _) | ObserveOrigin::Plugin(..) => None,
     }
 }
 
@@ -78,7 +79,9 @@ fn first_file_format(origin: &ObserveOrigin) -> Option<FileFormat> {
 mod tests {
     use std::path::PathBuf;
 
-    use stypes::{FileFormat, ObserveOrigin, TCPTransportConfig, Transport, UDPTransportConfig};
+    use stypes::{
+        FileFormat, ObserveOrigin, TCPTransportConfig, TcpMode, Transport, UDPTransportConfig,
+    };
 
     use super::*;
 
@@ -102,6 +105,7 @@ mod tests {
             String::from("stream"),
             Transport::TCP(TCPTransportConfig {
                 bind_addr: String::from("127.0.0.1:5555"),
+                mode: TcpMode::Connect,
             }),
         )
     }
@@ -174,6 +178,8 @@ mod tests {
                 Transport::UDP(UDPTransportConfig {
                     bind_addr: String::from("127.0.0.1:5555"),
                     multicast: Vec::new(),
+                    allowed_senders: Vec::new(),
+                    split_senders: false,
                 }),
             ),
         ];
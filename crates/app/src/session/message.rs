@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use regex::Regex;
 use session_core::state::NestedMatch;
@@ -18,6 +18,30 @@ pub enum SessionMessage {
     /// Update to the total count of logs (stream/file).
     LogsCount(u64),
 
+    /// Update to the count of logs hidden from the main table by exclusion filters.
+    HiddenRowsCount(u64),
+
+    /// Update to the count of logs with indexed timestamps while the timestamps of a
+    /// linked text file are indexed.
+    TimeIndexedCount(u64),
+
+    /// Main-table row showing the requested session position, or the first visible row
+    /// after it when the position is hidden.
+    ViewRow(Result<u64, SessionError>),
+
+    /// Session row at or after a requested time, or `None` when the logs carry no
+    /// timestamps.
+    RowAtTime(Result<Option<u64>, SessionError>),
+
+    /// Templates of similar messages mined from the session rows.
+    Templates(Result<stypes::LogTemplates, SessionError>),
+
+    /// Differences of the messages of another log from the session rows.
+    Comparison(Result<stypes::LogDiff, SessionError>),
+
+    /// Path of the session file.
+    SessionFile(Result<PathBuf, SessionError>),
+
     /// Result from fetching a specific log line.
     SelectedLog(Result<GrabbedElement, SessionError>),
 
@@ -79,6 +103,12 @@ pub enum SessionMessage {
         phase: OperationPhase,
     },
 
+    /// Connection state of the source of an observe operation has changed.
+    SourceStateChanged {
+        operation_id: Uuid,
+        state: stypes::SourceConnectionState,
+    },
+
     /// Source has been added to session.
     SourceAdded { observe_op: Box<ObserveOperation> },
 
@@ -103,7 +133,7 @@ pub enum SessionMessage {
         preview: Result<attachment::PreviewContent, SessionError>,
     },
 
-    /// Result of sending text into a source through SDE.
+    /// Result of sending text, bytes or control messages into a source through SDE.
     SdeSendFinished(Result<(), SessionError>),
 }
 
@@ -43,6 +43,7 @@ impl SessionService {
         operation_id: Uuid,
         destination: PathBuf,
         target: ExportTarget,
+        include_duplicates: bool,
     ) -> Result<(), SessionError> {
         let ranges = self.export_ranges(target).await?;
 
@@ -51,7 +52,10 @@ impl SessionService {
             return Ok(());
         }
 
-        if let Err(error) = self.session.export_raw(operation_id, destination, ranges) {
+        if let Err(error) =
+            self.session
+                .export_raw(operation_id, destination, ranges, include_duplicates)
+        {
             return Err(error.into());
         }
 
@@ -64,6 +68,7 @@ impl SessionService {
         operation_id: Uuid,
         destination: PathBuf,
         target: ExportTarget,
+        include_duplicates: bool,
         options: TextExportOptions,
     ) -> Result<(), SessionError> {
         let ranges = self.export_ranges(target).await?;
@@ -84,6 +89,7 @@ impl SessionService {
             operation_id,
             destination,
             ranges,
+            include_duplicates,
             columns,
             splitter,
             delimiter,
@@ -137,16 +143,26 @@ impl SessionService {
             mode,
         });
 
+        // Collapsing duplicates doesn't affect the search results, so their collapsed rows
+        // are kept.
+        const INCLUDE_DUPLICATES: bool = true;
+
         // Preserve raw bytes only when the export is a valid source for the target parser.
         // Other modes export rendered text, with DLT/SomeIP fallback formatted as table text.
         let result = match mode {
             SearchResultsTabMode::PreserveDltBinary => {
-                self.session.export_raw(operation_id, destination, ranges)
-            }
-            SearchResultsTabMode::PreserveText => {
                 self.session
-                    .export(operation_id, destination, ranges, Vec::new(), None, None)
+                    .export_raw(operation_id, destination, ranges, INCLUDE_DUPLICATES)
             }
+            SearchResultsTabMode::PreserveText => self.session.export(
+                operation_id,
+                destination,
+                ranges,
+                INCLUDE_DUPLICATES,
+                Vec::new(),
+                None,
+                None,
+            ),
             SearchResultsTabMode::Text => {
                 // In case of falling back to export table parsers as text then use best effort
                 // separator to show them similar to columns as possible.
@@ -164,6 +180,7 @@ impl SessionService {
                             operation_id,
                             destination,
                             ranges,
+                            INCLUDE_DUPLICATES,
                             columns,
                             Some(COLUMN_SEPARATOR.to_owned()),
                             Some(FALLBACK_TEXT_DELIMITER.to_owned()),
@@ -177,6 +194,7 @@ impl SessionService {
                             operation_id,
                             destination,
                             ranges,
+                            INCLUDE_DUPLICATES,
                             columns,
                             Some(COLUMN_SEPARATOR.to_owned()),
                             Some(FALLBACK_TEXT_DELIMITER.to_owned()),
@@ -186,6 +204,7 @@ impl SessionService {
                         operation_id,
                         destination,
                         ranges,
+                        INCLUDE_DUPLICATES,
                         // Plugin columns are dynamic, so ask export to re-delimit all columns.
                         Vec::new(),
                         Some(COLUMN_SEPARATOR.to_owned()),
@@ -195,6 +214,7 @@ impl SessionService {
                         operation_id,
                         destination,
                         ranges,
+                        INCLUDE_DUPLICATES,
                         Vec::new(),
                         None,
                         None,
@@ -366,6 +386,7 @@ impl SessionService {
                 operation.destination.clone(),
             ),
             parser,
+            recording: None,
         })
     }
 }
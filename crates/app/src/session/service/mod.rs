@@ -11,7 +11,7 @@ use regex::Regex;
 use tokio::{select, sync::mpsc, task};
 use uuid::Uuid;
 
-use processor::{grabber::LineRange, search::filter};
+use processor::search::filter;
 use session_core::session::Session;
 use stypes::{
     CallbackEvent, ComputationError, NativeError, NativeErrorKind, ObserveOptions, ObserveOrigin,
@@ -36,7 +36,7 @@ use crate::{
             session_setup::state::{
                 SessionSetupState,
                 parsers::ParserConfig,
-                sources::{ByteSourceConfig, StreamConfig},
+                sources::{ByteSourceConfig, PluginSourceConfig, StreamConfig},
             },
             storage::recent::session::{
                 RecentSessionRegistration, RecentSessionSource, RecentSessionStateSnapshot,
@@ -174,6 +174,7 @@ impl SessionService {
                 ObserveOptions {
                     origin,
                     parser: parser.clone(),
+                    recording: None,
                 },
             )?;
             startup_observe_ops.push(observe_op);
@@ -328,6 +329,12 @@ impl SessionService {
             } => {
                 self.session.apply_search_filters(operation_id, filters)?;
             }
+            SessionCommand::ApplySearchQuery {
+                operation_id,
+                query,
+            } => {
+                self.session.apply_search_query(operation_id, query)?;
+            }
             SessionCommand::DropSearch { operation_id } => {
                 if let Some(filter_op) = operation_id {
                     self.session.abort(Uuid::new_v4(), filter_op)?;
@@ -341,6 +348,84 @@ impl SessionService {
                 self.session
                     .apply_search_values_filters(operation_id, filters)?;
             }
+            SessionCommand::ApplyExclusions {
+                operation_id,
+                filters,
+            } => {
+                if let Some(previous) = self.tracker.exclusions.replace(operation_id) {
+                    self.session.abort(Uuid::new_v4(), previous)?;
+                }
+                self.session.apply_exclusions(operation_id, filters)?;
+            }
+            SessionCommand::MineTemplates {
+                operation_id,
+                buckets,
+            } => {
+                if let Some(previous) = self.tracker.templates.replace(operation_id) {
+                    self.session.abort(Uuid::new_v4(), previous)?;
+                }
+                self.session.mine_templates(operation_id, buckets)?;
+            }
+            SessionCommand::CollapseDuplicates { operation_id, mode } => {
+                if let Some(previous) = self.tracker.duplicates.replace(operation_id) {
+                    self.session.abort(Uuid::new_v4(), previous)?;
+                }
+                self.session.collapse_duplicates(operation_id, mode)?;
+            }
+            SessionCommand::Compare {
+                operation_id,
+                other,
+                other_rows,
+            } => {
+                if let Some(previous) = self.tracker.comparison.replace(operation_id) {
+                    self.session.abort(Uuid::new_v4(), previous)?;
+                }
+                self.session.compare(operation_id, other, other_rows)?;
+            }
+            SessionCommand::GetSessionFile => {
+                let result = self
+                    .session
+                    .state
+                    .get_session_file()
+                    .await
+                    .map_err(SessionError::NativeError);
+
+                self.senders
+                    .send_session_msg(SessionMessage::SessionFile(result))
+                    .await;
+            }
+            SessionCommand::ExpandDuplicates {
+                session_position,
+                expanded,
+            } => {
+                // The main table is refreshed by the update of the hidden rows.
+                self.session
+                    .expand_duplicates(session_position, expanded)
+                    .await?;
+            }
+            SessionCommand::GetViewRow { session_position } => {
+                let result = self
+                    .session
+                    .get_view_row(session_position)
+                    .await
+                    .map_err(SessionError::from);
+
+                self.senders
+                    .send_session_msg(SessionMessage::ViewRow(result))
+                    .await;
+            }
+            SessionCommand::GetRowAtTime { time } => {
+                let result = match self.session.get_time_bounds().await {
+                    Ok(Some(bounds)) => self.session.get_row_at_time(time.resolve(&bounds)).await,
+                    Ok(None) => Ok(None),
+                    Err(err) => Err(err),
+                }
+                .map_err(SessionError::from);
+
+                self.senders
+                    .send_session_msg(SessionMessage::RowAtTime(result))
+                    .await;
+            }
             SessionCommand::DropSearchValues { operation_id } => {
                 if let Some(values_op) = operation_id {
                     self.session.abort(Uuid::new_v4(), values_op)?;
@@ -431,11 +516,10 @@ impl SessionService {
                 }
             }
             SessionCommand::GetSelectedLog(pos) => {
-                let rng = LineRange::from(pos..=pos);
-
+                // Grab by session position, which is independent of rows hidden in the main view.
                 let selected_log = self
                     .session
-                    .grab(rng)
+                    .grab_ranges(vec![pos..=pos])
                     .await
                     .and_then(|items| {
                         items.0.into_iter().next().ok_or_else(|| {
@@ -582,6 +666,16 @@ impl SessionService {
                             StreamConfig::Tcp(tcp) => Transport::TCP(tcp.into()),
                             StreamConfig::Udp(udp) => Transport::UDP(udp.into()),
                             StreamConfig::Serial(serial) => Transport::Serial(serial.into()),
+                            StreamConfig::UnixSocket(socket) => {
+                                Transport::UnixSocket(socket.into())
+                            }
+                            StreamConfig::Replay(replay) => Transport::Replay(replay.into()),
+                            StreamConfig::Mqtt(mqtt) => Transport::Mqtt(mqtt.into()),
+                            StreamConfig::WebSocket(websocket) => {
+                                Transport::WebSocket(websocket.into())
+                            }
+                            StreamConfig::HttpStream(http) => Transport::HttpStream(http.into()),
+                            StreamConfig::SocketCan(can) => Transport::SocketCan(can.into()),
                         };
 
                         ObserveOrigin::Stream(id, transport)
@@ -590,8 +684,14 @@ impl SessionService {
                 let observe_id = Uuid::new_v4();
                 let observe_op = ObserveOperation::new(observe_id, origin.clone());
 
-                self.session
-                    .observe(observe_id, ObserveOptions { origin, parser })?;
+                self.session.observe(
+                    observe_id,
+                    ObserveOptions {
+                        origin,
+                        parser,
+                        recording: None,
+                    },
+                )?;
 
                 self.senders
                     .send_session_msg(SessionMessage::SourceAdded {
@@ -622,6 +722,18 @@ impl SessionService {
                             }),
                         ObserveOrigin::Stream(uuid, transport) => (uuid == &source_uuid)
                             .then(|| ByteSourceConfig::from_transport(transport)),
+                        // Each file of a watched directory is added with its path as alias.
+                        ObserveOrigin::DirWatch(uuid, config) => source_uuid
+                            .strip_prefix(uuid.as_str())
+                            .and_then(|alias| alias.strip_prefix(':'))
+                            .map(|path| ByteSourceConfig::from_file(path.into(), config.format)),
+                        ObserveOrigin::Plugin(uuid, settings) => {
+                            (uuid == &source_uuid).then(|| {
+                                ByteSourceConfig::Plugin(PluginSourceConfig::from_settings(
+                                    settings.clone(),
+                                ))
+                            })
+                        }
                     };
 
                     let Some(source) = source else {
@@ -644,9 +756,10 @@ impl SessionService {
                 operation_id,
                 destination,
                 target,
+                include_duplicates,
             } => {
                 if let Err(error) = self
-                    .handle_raw_export(operation_id, destination, target)
+                    .handle_raw_export(operation_id, destination, target, include_duplicates)
                     .await
                 {
                     self.send_operation_failed(operation_id).await;
@@ -657,10 +770,17 @@ impl SessionService {
                 operation_id,
                 destination,
                 target,
+                include_duplicates,
                 options,
             } => {
                 if let Err(error) = self
-                    .handle_text_export(operation_id, destination, target, *options)
+                    .handle_text_export(
+                        operation_id,
+                        destination,
+                        target,
+                        include_duplicates,
+                        *options,
+                    )
                     .await
                 {
                     self.send_operation_failed(operation_id).await;
@@ -681,16 +801,18 @@ impl SessionService {
             }
             SessionCommand::SendSdeText { target, text } => {
                 let text = format!("{text}\r\n");
-                let result = self
-                    .session
-                    .send_into_sde(target, SdeRequest::WriteText(text))
-                    .await
-                    .map(|_| ())
-                    .map_err(SessionError::from);
-
-                self.senders
-                    .send_session_msg(SessionMessage::SdeSendFinished(result))
-                    .await;
+                self.send_sde(target, SdeRequest::WriteText(text)).await;
+            }
+            SessionCommand::SendSdeBytes { target, bytes } => {
+                self.send_sde(target, SdeRequest::WriteBytes(bytes)).await;
+            }
+            SessionCommand::SendSdeDltControl { target, request } => {
+                self.send_sde(target, SdeRequest::DltControl(request)).await;
+            }
+            SessionCommand::ControlReplay { target, control } => {
+                self.session
+                    .send_into_sde(target, SdeRequest::Replay(control))
+                    .await?;
             }
             SessionCommand::CancelOperation { id } => {
                 self.session.abort(Uuid::new_v4(), id)?;
@@ -709,6 +831,21 @@ impl SessionService {
         Ok(ControlFlow::Continue(()))
     }
 
+    /// Sends the request into the source of the target operation, reporting the
+    /// result back to the SDE bar.
+    async fn send_sde(&self, target: Uuid, request: SdeRequest) {
+        let result = self
+            .session
+            .send_into_sde(target, request)
+            .await
+            .map(|_| ())
+            .map_err(SessionError::from);
+
+        self.senders
+            .send_session_msg(SessionMessage::SdeSendFinished(result))
+            .await;
+    }
+
     async fn send_operation_failed(&self, operation_id: Uuid) {
         self.senders
             .send_session_msg(SessionMessage::OperationUpdated {
@@ -830,6 +967,16 @@ impl SessionService {
                     .send_session_msg(SessionMessage::SearchResultCountUpdated { count: found })
                     .await;
             }
+            CallbackEvent::HiddenRowsUpdated { len } => {
+                self.senders
+                    .send_session_msg(SessionMessage::HiddenRowsCount(len))
+                    .await;
+            }
+            CallbackEvent::TimeIndexUpdated { len } => {
+                self.senders
+                    .send_session_msg(SessionMessage::TimeIndexedCount(len))
+                    .await;
+            }
             CallbackEvent::IndexedMapUpdated { len } => {
                 self.senders
                     .send_session_msg(SessionMessage::IndexedCountUpdated { count: len })
@@ -853,6 +1000,18 @@ impl SessionService {
                     .await;
             }
             CallbackEvent::OperationError { uuid, error } => {
+                if self.tracker.exclusions == Some(uuid) {
+                    self.tracker.exclusions = None;
+                }
+                if self.tracker.templates == Some(uuid) {
+                    self.tracker.templates = None;
+                }
+                if self.tracker.duplicates == Some(uuid) {
+                    self.tracker.duplicates = None;
+                }
+                if self.tracker.comparison == Some(uuid) {
+                    self.tracker.comparison = None;
+                }
                 if self
                     .tracker
                     .search_results_tab
@@ -889,6 +1048,30 @@ impl SessionService {
                     .await;
             }
             CallbackEvent::OperationDone(done) => {
+                if self.tracker.exclusions == Some(done.uuid) {
+                    self.tracker.exclusions = None;
+                }
+                if self.tracker.duplicates == Some(done.uuid) {
+                    self.tracker.duplicates = None;
+                }
+                if self.tracker.templates == Some(done.uuid) {
+                    self.tracker.templates = None;
+                    let templates = self.session.get_templates().await;
+                    if let Some(templates) = templates.map_err(SessionError::from).transpose() {
+                        self.senders
+                            .send_session_msg(SessionMessage::Templates(templates))
+                            .await;
+                    }
+                }
+                if self.tracker.comparison == Some(done.uuid) {
+                    self.tracker.comparison = None;
+                    let comparison = self.session.get_comparison().await;
+                    if let Some(comparison) = comparison.map_err(SessionError::from).transpose() {
+                        self.senders
+                            .send_session_msg(SessionMessage::Comparison(comparison))
+                            .await;
+                    }
+                }
                 if let Err(error) = self.finish_results_tab(done.uuid).await {
                     self.senders
                         .send_session_msg(SessionMessage::OperationUpdated {
@@ -906,6 +1089,14 @@ impl SessionService {
                     })
                     .await;
             }
+            CallbackEvent::SourceStateChanged { uuid, state } => {
+                self.senders
+                    .send_session_msg(SessionMessage::SourceStateChanged {
+                        operation_id: uuid,
+                        state,
+                    })
+                    .await;
+            }
             CallbackEvent::FileRead => {
                 self.senders
                     .send_session_msg(SessionMessage::FileReadCompleted)
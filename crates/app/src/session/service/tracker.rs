@@ -1,5 +1,7 @@
 //! Service-owned state for operations that need follow-up after backend callbacks.
 
+use uuid::Uuid;
+
 use super::export::SearchResultsTabOperation;
 
 /// Tracks operations that need service-side work after backend completion callbacks.
@@ -7,4 +9,12 @@ use super::export::SearchResultsTabOperation;
 pub struct OperationTracker {
     /// Pending generated search-results tab export, if one is in progress.
     pub search_results_tab: Option<SearchResultsTabOperation>,
+    /// Running exclusions operation, aborted when newer exclusions are applied.
+    pub exclusions: Option<Uuid>,
+    /// Running template mining operation, whose templates are fetched once it's done.
+    pub templates: Option<Uuid>,
+    /// Running duplicates comparison, aborted when duplicates are collapsed differently.
+    pub duplicates: Option<Uuid>,
+    /// Running comparison, whose differences are fetched once it's done.
+    pub comparison: Option<Uuid>,
 }
@@ -1,10 +1,14 @@
-use std::time::{Duration, Instant};
+use std::{
+    ops::RangeInclusive,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 pub mod attachment;
 
 use uuid::Uuid;
 
-use stypes::ObserveOrigin;
+use stypes::{ObserveOrigin, SourceConnectionState};
 
 /// Represents a running observe operations with its info.
 #[derive(Debug, Clone)]
@@ -19,6 +23,8 @@ pub struct ObserveOperation {
     started: Instant,
     /// Elapsed time once the operation reaches a terminal phase.
     run_duration: Option<Duration>,
+    /// Connection state reported by sources which reconnect on their own.
+    source_state: Option<SourceConnectionState>,
 }
 
 impl ObserveOperation {
@@ -29,6 +35,7 @@ impl ObserveOperation {
             origin,
             started: Instant::now(),
             run_duration: None,
+            source_state: None,
         }
     }
 
@@ -51,6 +58,14 @@ impl ObserveOperation {
         self.run_duration
     }
 
+    pub fn set_source_state(&mut self, state: SourceConnectionState) {
+        self.source_state = Some(state);
+    }
+
+    pub fn source_state(&self) -> Option<&SourceConnectionState> {
+        self.source_state.as_ref()
+    }
+
     pub fn processing(&self) -> bool {
         self.phase == OperationPhase::Processing
     }
@@ -99,3 +114,47 @@ impl OperationPhase {
         }
     }
 }
+
+/// Another open session, which a session can be compared with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareSession {
+    pub id: Uuid,
+    pub title: String,
+    /// Session file holding the rows of the session.
+    pub file: PathBuf,
+    /// Number of rows of the session, which can lag behind the lines of its session file.
+    pub rows: u64,
+}
+
+/// Time to jump to in the main logs table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeQuery {
+    /// Milliseconds since the Unix epoch.
+    At(u64),
+    /// Milliseconds since midnight, on the day of the session logs.
+    TimeOfDay(u64),
+}
+
+impl TimeQuery {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    /// Resolves the query into milliseconds since the Unix epoch using the time bounds
+    /// of the session.
+    ///
+    /// The time of day is taken on the day of the first log, or on the next day when
+    /// it's earlier than the first log, which covers logs recorded over midnight.
+    pub fn resolve(self, bounds: &RangeInclusive<u64>) -> u64 {
+        match self {
+            TimeQuery::At(timestamp) => timestamp,
+            TimeQuery::TimeOfDay(time) => {
+                let start = *bounds.start();
+                let timestamp = start - start % Self::DAY_MS + time;
+                if timestamp < start {
+                    timestamp + Self::DAY_MS
+                } else {
+                    timestamp
+                }
+            }
+        }
+    }
+}
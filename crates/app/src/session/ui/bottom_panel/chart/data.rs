@@ -96,7 +96,7 @@ impl ChartsData {
             .filters
             .filter_entries
             .iter()
-            .filter(|item| item.enabled)
+            .filter(|item| item.enabled && !item.exclude)
             .enumerate()
         {
             let Some(filter_def) = registry.get_filter(&item.id) else {
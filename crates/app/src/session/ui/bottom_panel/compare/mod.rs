@@ -0,0 +1,375 @@
+//! Bottom-panel view of the comparison of the session with another session or log file,
+//! which lists the messages new, missing, changed in number or moved in the other log and
+//! jumps to their rows in either session.
+
+use std::path::PathBuf;
+
+use egui::{
+    ComboBox, Frame, Grid, Label, Margin, Response, RichText, ScrollArea, Spinner, Ui, Widget,
+};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use crate::{
+    common::ui::buttons,
+    host::ui::{HostAction, UiActions, actions::FileDialogOptions},
+    session::{
+        command::SessionCommand,
+        types::{CompareSession, OperationPhase},
+        ui::shared::{SearchTableSync, SessionShared},
+    },
+};
+
+const COMPARE_FILE_DIALOG_ID: &str = "session_compare_file";
+
+/// Log the session is compared with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CompareTarget {
+    Session(CompareSession),
+    File(PathBuf),
+}
+
+impl CompareTarget {
+    fn path(&self) -> &PathBuf {
+        match self {
+            CompareTarget::Session(session) => &session.file,
+            CompareTarget::File(path) => path,
+        }
+    }
+
+    /// Returns the number of lines to compare of the log, which is all lines of a file.
+    fn rows(&self) -> Option<u64> {
+        match self {
+            CompareTarget::Session(session) => Some(session.rows),
+            CompareTarget::File(_) => None,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            CompareTarget::Session(session) => session.title.clone(),
+            CompareTarget::File(path) => path
+                .file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .into_owned(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CompareUI {
+    cmd_tx: Sender<SessionCommand>,
+    /// Other open sessions, updated by the host while the view is shown.
+    sessions: Vec<CompareSession>,
+    /// Log selected to compare with.
+    target: Option<CompareTarget>,
+    /// Running comparison and the log compared in it.
+    pending: Option<(Uuid, CompareTarget)>,
+    /// Differences of the last comparison and the log compared in it.
+    comparison: Option<(CompareTarget, stypes::LogDiff)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareAction {
+    JumpToRow(u64),
+    JumpToOtherRow(u64),
+}
+
+impl CompareUI {
+    pub fn new(cmd_tx: Sender<SessionCommand>) -> Self {
+        Self {
+            cmd_tx,
+            sessions: Vec::new(),
+            target: None,
+            pending: None,
+            comparison: None,
+        }
+    }
+
+    /// Sets the other open sessions, updating the selected session with its current rows
+    /// and dropping the selection of a closed session.
+    pub fn set_sessions(&mut self, sessions: Vec<CompareSession>) {
+        if let Some(CompareTarget::Session(selected)) = &self.target {
+            self.target = sessions
+                .iter()
+                .find(|session| session.id == selected.id)
+                .map(|session| CompareTarget::Session(session.clone()));
+        }
+        self.sessions = sessions;
+    }
+
+    pub fn handle_comparison(&mut self, comparison: stypes::LogDiff) {
+        if let Some((_, target)) = self.pending.take() {
+            self.comparison = Some((target, comparison));
+        }
+    }
+
+    /// Stops waiting for the differences once the comparison ended without them.
+    pub fn on_operation_updated(&mut self, operation_id: Uuid, phase: OperationPhase) {
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|(pending_id, _)| *pending_id == operation_id)
+            && matches!(
+                phase,
+                OperationPhase::Success | OperationPhase::Failed | OperationPhase::Skipped
+            )
+        {
+            self.pending = None;
+        }
+    }
+
+    pub fn render_content(
+        &mut self,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+        ui: &mut Ui,
+    ) {
+        if let Some(paths) = actions.file_dialog.take_output(COMPARE_FILE_DIALOG_ID)
+            && let Some(path) = paths.into_iter().next()
+        {
+            self.target = Some(CompareTarget::File(path));
+        }
+
+        Frame::NONE
+            .inner_margin(Margin::symmetric(4, 2))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| self.render_controls(actions, ui));
+
+                let Some((target, comparison)) = &self.comparison else {
+                    return;
+                };
+
+                let other_open = match target {
+                    CompareTarget::Session(compared) => self
+                        .sessions
+                        .iter()
+                        .any(|session| session.id == compared.id),
+                    CompareTarget::File(_) => false,
+                };
+                let action = render_entries(comparison, other_open, ui);
+                if let Some(action) = action {
+                    self.handle_action(action, shared, actions);
+                }
+            });
+    }
+
+    fn render_controls(&mut self, actions: &mut UiActions, ui: &mut Ui) {
+        let selected_text = self
+            .target
+            .as_ref()
+            .map_or_else(|| "Select a session".to_owned(), CompareTarget::label);
+        ComboBox::from_id_salt("session_compare_target")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for session in &self.sessions {
+                    ui.selectable_value(
+                        &mut self.target,
+                        Some(CompareTarget::Session(session.clone())),
+                        &session.title,
+                    );
+                }
+                if self.sessions.is_empty() {
+                    ui.weak("No other sessions with logs");
+                }
+            });
+
+        if buttons::bottom_panel("Open File…")
+            .ui(ui)
+            .on_hover_text("Compare with a text log file")
+            .clicked()
+        {
+            actions.file_dialog.pick_file(
+                COMPARE_FILE_DIALOG_ID,
+                FileDialogOptions::new().title("Compare with File"),
+            );
+        }
+
+        let comparing = self.pending.is_some();
+        if ui
+            .add_enabled(
+                !comparing && self.target.is_some(),
+                buttons::bottom_panel("Compare"),
+            )
+            .on_hover_text("Compare the messages of the logs, ignoring numbers and timestamps")
+            .clicked()
+        {
+            self.compare(actions);
+        }
+
+        if comparing {
+            ui.add(Spinner::new());
+        } else if let Some((target, comparison)) = &self.comparison {
+            ui.label(summary(comparison))
+                .on_hover_text(target.path().display().to_string());
+        }
+    }
+
+    fn compare(&mut self, actions: &mut UiActions) {
+        let Some(target) = self.target.clone() else {
+            return;
+        };
+        let operation_id = Uuid::new_v4();
+        let cmd = SessionCommand::Compare {
+            operation_id,
+            other: target.path().clone(),
+            other_rows: target.rows(),
+        };
+        if actions.try_send_command(&self.cmd_tx, cmd) {
+            self.pending = Some((operation_id, target));
+        }
+    }
+
+    fn handle_action(
+        &self,
+        action: CompareAction,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+    ) {
+        match action {
+            CompareAction::JumpToRow(row) => {
+                shared.logs.focus_main_row(row, SearchTableSync::Sync);
+            }
+            CompareAction::JumpToOtherRow(row) => {
+                if let Some((CompareTarget::Session(session), _)) = &self.comparison {
+                    actions.add_host_action(HostAction::FocusSessionRow {
+                        session_id: session.id,
+                        row,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn kind_label(kind: stypes::LogDiffKind) -> &'static str {
+    match kind {
+        stypes::LogDiffKind::Added => "New",
+        stypes::LogDiffKind::Removed => "Missing",
+        stypes::LogDiffKind::Changed => "Changed",
+        stypes::LogDiffKind::Moved => "Moved",
+    }
+}
+
+fn summary(comparison: &stypes::LogDiff) -> String {
+    let count = |kind: stypes::LogDiffKind| {
+        comparison
+            .entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .count()
+    };
+    format!(
+        "{} new, {} missing, {} changed, {} moved, {} unchanged messages ({} rows against {})",
+        count(stypes::LogDiffKind::Added),
+        count(stypes::LogDiffKind::Removed),
+        count(stypes::LogDiffKind::Changed),
+        count(stypes::LogDiffKind::Moved),
+        comparison.unchanged,
+        comparison.left_rows,
+        comparison.right_rows,
+    )
+}
+
+/// Renders the differences, with links to the rows of the other log when it's an open
+/// session.
+fn render_entries(
+    comparison: &stypes::LogDiff,
+    other_open: bool,
+    ui: &mut Ui,
+) -> Option<CompareAction> {
+    let mut action = None;
+    ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+        Grid::new("session_compare_grid")
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                for header in ["Change", "Message", "This session", "Other"] {
+                    ui.label(RichText::new(header).strong());
+                }
+                ui.end_row();
+
+                for entry in &comparison.entries {
+                    ui.label(kind_label(entry.kind));
+                    Label::new(RichText::new(&entry.template).monospace())
+                        .selectable(true)
+                        .ui(ui);
+                    if side_cell(ui, entry.left.as_ref(), true).is_some_and(|r| r.clicked()) {
+                        action = entry
+                            .left
+                            .as_ref()
+                            .map(|side| CompareAction::JumpToRow(side.first_row));
+                    }
+                    if side_cell(ui, entry.right.as_ref(), other_open).is_some_and(|r| r.clicked())
+                    {
+                        action = entry
+                            .right
+                            .as_ref()
+                            .map(|side| CompareAction::JumpToOtherRow(side.first_row));
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+    action
+}
+
+/// Shows the number of messages in the log and their first row, as a link when `linked`.
+fn side_cell(ui: &mut Ui, side: Option<&stypes::LogDiffSide>, linked: bool) -> Option<Response> {
+    let side = side?;
+    ui.horizontal(|ui| {
+        ui.label(format!("{}×", side.count));
+        let hover = format!(
+            "First at row {}, last at row {}",
+            side.first_row, side.last_row
+        );
+        if linked {
+            Some(ui.link(side.first_row.to_string()).on_hover_text(hover))
+        } else {
+            ui.label(side.first_row.to_string()).on_hover_text(hover);
+            None
+        }
+    })
+    .inner
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: stypes::LogDiffKind) -> stypes::LogDiffEntry {
+        stypes::LogDiffEntry {
+            kind,
+            template: String::new(),
+            left: None,
+            right: None,
+        }
+    }
+
+    #[test]
+    fn summary_counts_each_kind() {
+        let comparison = stypes::LogDiff {
+            entries: vec![
+                entry(stypes::LogDiffKind::Added),
+                entry(stypes::LogDiffKind::Added),
+                entry(stypes::LogDiffKind::Changed),
+            ],
+            left_rows: 10,
+            right_rows: 12,
+            unchanged: 4,
+        };
+
+        assert_eq!(
+            summary(&comparison),
+            "2 new, 0 missing, 1 changed, 0 moved, 4 unchanged messages (10 rows against 12)"
+        );
+    }
+
+    #[test]
+    fn file_target_is_labelled_by_file_name() {
+        let target = CompareTarget::File(PathBuf::from("/logs/boot.log"));
+
+        assert_eq!(target.label(), "boot.log");
+    }
+}
@@ -128,6 +128,8 @@ mod tests {
             content: format!("log {pos}"),
             pos,
             nature: 0,
+            matches: Vec::new(),
+            collapsed: None,
         }
     }
 
@@ -894,7 +894,8 @@ mod tests {
         assert!(shared.filters.is_search_value_enabled(&value_id));
         match cmd_rx.try_recv() {
             Ok(SessionCommand::ApplySearchValuesFilter { filters, .. }) => {
-                assert_eq!(filters, vec!["duration=(\\d+)".to_owned()]);
+                assert_eq!(filters.len(), 1);
+                assert_eq!(filters[0].value, "duration=(\\d+)");
             }
             other => panic!("expected ApplySearchValuesFilter command, got {other:?}"),
         }
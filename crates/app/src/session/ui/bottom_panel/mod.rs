@@ -16,10 +16,12 @@ use crate::{
     },
 };
 use chart::ChartUI;
+use compare::CompareUI;
 use details::DetailsUI;
 use library::LibraryUI;
 use presets::PresetsUI;
 use search::SearchUI;
+use templates::TemplatesUI;
 
 mod library;
 mod presets;
@@ -28,8 +30,10 @@ mod tab_types;
 pub use tab_types::BottomTabType;
 
 pub mod chart;
+mod compare;
 mod details;
 mod search;
+mod templates;
 
 #[derive(Debug)]
 pub struct BottomPanelUI {
@@ -38,6 +42,8 @@ pub struct BottomPanelUI {
     pub library: LibraryUI,
     pub presets: PresetsUI,
     pub chart: ChartUI,
+    pub templates: TemplatesUI,
+    pub compare: CompareUI,
 }
 
 const BOTTOM_TAB_TOP_GAP: f32 = 4.0;
@@ -54,7 +60,9 @@ impl BottomPanelUI {
             details: DetailsUI::new(cmd_tx.clone()),
             library: LibraryUI::new(cmd_tx.clone()),
             presets: PresetsUI::new(cmd_tx.clone(), host_cmd_tx),
-            chart: ChartUI::new(cmd_tx),
+            chart: ChartUI::new(cmd_tx.clone()),
+            templates: TemplatesUI::new(cmd_tx.clone()),
+            compare: CompareUI::new(cmd_tx),
         }
     }
 
@@ -88,6 +96,11 @@ impl BottomPanelUI {
                 self.chart
                     .render_content(shared, actions, &registry.filters, ui)
             }
+            BottomTabType::Templates => {
+                self.templates
+                    .render_content(shared, actions, &mut registry.filters, ui)
+            }
+            BottomTabType::Compare => self.compare.render_content(shared, actions, ui),
         }
     }
 
@@ -99,6 +112,8 @@ impl BottomPanelUI {
             BottomTabType::Library => 2,
             BottomTabType::Presets => 3,
             BottomTabType::Chart => 4,
+            BottomTabType::Templates => 5,
+            BottomTabType::Compare => 6,
         };
         let strip_id = ui.id().with("session_bottom_tab_strip");
         let strip = TabStrip::new(strip_id, selected_tab_index)
@@ -114,6 +129,8 @@ impl BottomPanelUI {
             BottomTabType::Library,
             BottomTabType::Presets,
             BottomTabType::Chart,
+            BottomTabType::Templates,
+            BottomTabType::Compare,
         ]
         .map(|tab| {
             TabSpec::new(
@@ -151,6 +168,8 @@ fn bottom_tab_from_index(idx: usize) -> BottomTabType {
         2 => BottomTabType::Library,
         3 => BottomTabType::Presets,
         4 => BottomTabType::Chart,
+        5 => BottomTabType::Templates,
+        6 => BottomTabType::Compare,
         _ => panic!("Bottom tab index out of range: {idx}"),
     }
 }
@@ -8,7 +8,7 @@ use crate::{
         registry::{
             HostRegistry,
             filters::{FilterDefinition, FilterRegistry, SearchValueDefinition},
-            presets::{PresetFilterEntry, PresetSearchValueEntry},
+            presets::{PresetFilterEntry, PresetQuery, PresetSearchValueEntry},
         },
     },
     session::ui::shared::{SearchSyncTarget, SessionShared},
@@ -38,10 +38,14 @@ impl PresetsUI {
         registry: &mut HostRegistry,
         preset_id: Uuid,
     ) -> PresetApplyOutcome {
-        let Some((filters, search_values)) = registry
-            .presets
-            .get(&preset_id)
-            .map(|preset| (preset.filters.clone(), preset.search_values.clone()))
+        let Some((filters, search_values, query)) =
+            registry.presets.get(&preset_id).map(|preset| {
+                (
+                    preset.filters.clone(),
+                    preset.search_values.clone(),
+                    preset.query.clone(),
+                )
+            })
         else {
             return PresetApplyOutcome::NotFound;
         };
@@ -56,6 +60,7 @@ impl PresetsUI {
             .enabled_search_value_ids()
             .copied()
             .collect::<Vec<_>>();
+        let before_query = shared.filters.effective_query().cloned();
 
         let mut changed = false;
         for entry in filters {
@@ -90,6 +95,11 @@ impl PresetsUI {
             );
         }
 
+        if let Some(PresetQuery { text, enabled }) = query {
+            changed |= shared.filters.set_query_text(text);
+            changed |= shared.filters.set_query_enabled(enabled);
+        }
+
         let after_filters = shared
             .filters
             .enabled_filter_ids()
@@ -101,8 +111,9 @@ impl PresetsUI {
             .copied()
             .collect::<Vec<_>>();
 
-        let filters_changed =
-            before_filters != after_filters && shared.filters.active_temp_search.is_none();
+        let filters_changed = (before_filters != after_filters
+            || before_query.as_ref() != shared.filters.effective_query())
+            && shared.filters.active_temp_search.is_none();
         let search_values_changed = before_search_values != after_search_values;
         let outcome = match (filters_changed, search_values_changed) {
             (false, false) if changed => PresetApplyOutcome::AppliedNoSync,
@@ -354,7 +365,7 @@ mod tests {
         assert!(matches!(
             &commands[0],
             SessionCommand::ApplySearchValuesFilter { filters, .. }
-                if filters == &["duration=(\\d+)".to_owned()]
+                if filters.len() == 1 && filters[0].value == "duration=(\\d+)"
         ));
     }
 
@@ -625,4 +636,44 @@ mod tests {
         assert_eq!(outcome, PresetApplyOutcome::NotFound);
         assert!(drain_commands(&mut cmd_rx).is_empty());
     }
+
+    #[test]
+    fn apply_preset_restores_query_and_syncs() {
+        let runtime = Runtime::new().unwrap();
+        let (presets, mut cmd_rx, _) = new_presets();
+        let mut shared = new_shared();
+        let mut actions = new_actions(&runtime);
+        let mut registry = HostRegistry::default();
+        let preset_id = registry.presets.add_preset_with_query(
+            "query",
+            vec![],
+            vec![],
+            Some(PresetQuery {
+                text: "error AND NOT heartbeat".to_owned(),
+                enabled: true,
+            }),
+        );
+
+        let outcome = presets.apply_preset(&mut shared, &mut actions, &mut registry, preset_id);
+
+        assert_eq!(
+            outcome,
+            PresetApplyOutcome::Applied(SearchSyncTarget::Filter)
+        );
+        assert_eq!(
+            shared.filters.query.as_ref().map(|query| query.text()),
+            Some("error AND NOT heartbeat")
+        );
+        let commands = drain_commands(&mut cmd_rx);
+        assert_eq!(commands.len(), 1);
+        match &commands[0] {
+            SessionCommand::ApplySearchQuery { query, .. } => {
+                assert_eq!(query.filters().len(), 2);
+            }
+            other => panic!("expected ApplySearchQuery command, got {other:?}"),
+        }
+
+        let outcome = presets.apply_preset(&mut shared, &mut actions, &mut registry, preset_id);
+        assert_eq!(outcome, PresetApplyOutcome::NoChanges);
+    }
 }
@@ -383,7 +383,7 @@ impl PresetsUI {
         true
     }
 
-    /// Captures the current session filters and charts as a named preset.
+    /// Captures the current session filters, charts and query as a named preset.
     pub fn capture_preset(&mut self, shared: &SessionShared, registry: &mut HostRegistry) -> Uuid {
         let preset_id = registry
             .presets
@@ -395,7 +395,9 @@ impl PresetsUI {
 
 fn can_create_preset_from_session(shared: &SessionShared) -> bool {
     // Capture snapshots the full session items, not just enabled ones.
-    !shared.filters.filter_entries.is_empty() || !shared.filters.search_value_entries.is_empty()
+    !shared.filters.filter_entries.is_empty()
+        || !shared.filters.search_value_entries.is_empty()
+        || shared.filters.query.is_some()
 }
 
 #[cfg(test)]
@@ -500,6 +500,7 @@ impl PresetsUI {
                                 .then_with(|| {
                                     (!left.is_ignore_case()).cmp(&(!right.is_ignore_case()))
                                 })
+                                .then_with(|| left.column().cmp(&right.column()))
                         });
 
                         if filters.is_empty() {
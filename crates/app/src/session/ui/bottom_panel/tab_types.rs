@@ -9,6 +9,8 @@ pub enum BottomTabType {
     Library,
     Presets,
     Chart,
+    Templates,
+    Compare,
 }
 
 impl BottomTabType {
@@ -19,6 +21,8 @@ impl BottomTabType {
             BottomTabType::Library => "Library",
             BottomTabType::Presets => "Presets",
             BottomTabType::Chart => "Chart",
+            BottomTabType::Templates => "Templates",
+            BottomTabType::Compare => "Compare",
         }
     }
 }
@@ -44,6 +48,8 @@ mod tests {
                 BottomTabType::Library,
                 BottomTabType::Presets,
                 BottomTabType::Chart,
+                BottomTabType::Templates,
+                BottomTabType::Compare,
             ]
         );
     }
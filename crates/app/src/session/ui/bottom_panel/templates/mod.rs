@@ -0,0 +1,313 @@
+//! Bottom-panel view of the log templates mined from the session, which groups similar
+//! messages so the most frequent kinds of messages can be searched or hidden in one click.
+
+use chrono::DateTime;
+use egui::{
+    Frame, Grid, Label, Margin, Rect, Response, RichText, ScrollArea, Sense, Spinner, Ui, Vec2,
+    Widget, pos2, vec2,
+};
+use processor::search::filter::SearchFilter;
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use crate::{
+    common::ui::buttons,
+    host::ui::{
+        UiActions,
+        registry::filters::{FilterDefinition, FilterRegistry},
+    },
+    session::{
+        command::SessionCommand,
+        types::OperationPhase,
+        ui::shared::{SearchSyncTarget, SearchTableSync, SessionShared},
+    },
+};
+
+/// Number of intervals of the session time in the activity sparklines.
+const HISTOGRAM_BUCKETS: u16 = 48;
+
+const SPARKLINE_SIZE: Vec2 = vec2(96.0, 14.0);
+
+#[derive(Debug)]
+pub struct TemplatesUI {
+    cmd_tx: Sender<SessionCommand>,
+    /// Running template mining operation, if any.
+    operation_id: Option<Uuid>,
+    templates: Option<stypes::LogTemplates>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TemplateAction {
+    JumpToRow(u64),
+    /// Applies the template as a search filter, or as an exclusion filter when `exclude`.
+    Apply {
+        index: usize,
+        exclude: bool,
+    },
+}
+
+impl TemplatesUI {
+    pub fn new(cmd_tx: Sender<SessionCommand>) -> Self {
+        Self {
+            cmd_tx,
+            operation_id: None,
+            templates: None,
+        }
+    }
+
+    pub fn handle_templates(&mut self, templates: stypes::LogTemplates) {
+        self.operation_id = None;
+        self.templates = Some(templates);
+    }
+
+    /// Stops waiting for templates once the mining operation ended without them.
+    pub fn on_operation_updated(&mut self, operation_id: Uuid, phase: OperationPhase) {
+        if self.operation_id == Some(operation_id)
+            && matches!(
+                phase,
+                OperationPhase::Success | OperationPhase::Failed | OperationPhase::Skipped
+            )
+        {
+            self.operation_id = None;
+        }
+    }
+
+    pub fn render_content(
+        &mut self,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+        registry: &mut FilterRegistry,
+        ui: &mut Ui,
+    ) {
+        Frame::NONE
+            .inner_margin(Margin::symmetric(4, 2))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let mining = self.operation_id.is_some();
+                    if ui
+                        .add_enabled(!mining, buttons::bottom_panel("Mine templates"))
+                        .on_hover_text("Group similar messages of the session into templates")
+                        .clicked()
+                    {
+                        self.mine(actions);
+                    }
+                    if mining {
+                        ui.add(Spinner::new());
+                    } else if let Some(templates) = &self.templates {
+                        ui.label(summary(templates));
+                    }
+                });
+
+                let Some(templates) = &self.templates else {
+                    return;
+                };
+
+                let action = render_templates(templates, ui);
+                if let Some(action) = action {
+                    self.handle_action(action, shared, actions, registry);
+                }
+            });
+    }
+
+    fn mine(&mut self, actions: &mut UiActions) {
+        let operation_id = Uuid::new_v4();
+        let cmd = SessionCommand::MineTemplates {
+            operation_id,
+            buckets: HISTOGRAM_BUCKETS,
+        };
+        if actions.try_send_command(&self.cmd_tx, cmd) {
+            self.operation_id = Some(operation_id);
+        }
+    }
+
+    fn handle_action(
+        &self,
+        action: TemplateAction,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+        registry: &mut FilterRegistry,
+    ) {
+        match action {
+            TemplateAction::JumpToRow(row) => {
+                shared.logs.focus_main_row(row, SearchTableSync::Sync);
+            }
+            TemplateAction::Apply { index, exclude } => {
+                let Some(template) = self
+                    .templates
+                    .as_ref()
+                    .and_then(|templates| templates.templates.get(index))
+                else {
+                    return;
+                };
+                let filter_id =
+                    registry.add_filter(FilterDefinition::new(template_filter(template)));
+                shared.apply_filter(registry, filter_id);
+                shared.set_filter_enabled(&filter_id, true);
+                shared.set_filter_exclude(&filter_id, exclude);
+                shared
+                    .sync_persistent_search(registry, SearchSyncTarget::Filter)
+                    .into_iter()
+                    .for_each(|cmd| _ = actions.try_send_command(&self.cmd_tx, cmd));
+            }
+        }
+    }
+}
+
+/// Returns the filter matching the messages of the template.
+fn template_filter(template: &stypes::LogTemplate) -> SearchFilter {
+    SearchFilter::plain(template.pattern.as_str()).regex(true)
+}
+
+fn summary(templates: &stypes::LogTemplates) -> String {
+    let mut summary = format!(
+        "{} templates of {} messages",
+        templates.templates.len(),
+        templates.rows
+    );
+    if templates.unclustered > 0 {
+        summary.push_str(&format!(
+            ", {} messages beyond the templates limit",
+            templates.unclustered
+        ));
+    }
+    summary
+}
+
+fn render_templates(templates: &stypes::LogTemplates, ui: &mut Ui) -> Option<TemplateAction> {
+    let mut action = None;
+    ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+        Grid::new("session_templates_grid")
+            .striped(true)
+            .num_columns(7)
+            .show(ui, |ui| {
+                for header in [
+                    "Count", "Share", "First", "Last", "Activity", "Template", "",
+                ] {
+                    ui.label(RichText::new(header).strong());
+                }
+                ui.end_row();
+
+                for (index, template) in templates.templates.iter().enumerate() {
+                    ui.label(template.count.to_string());
+                    ui.label(share(template.count, templates.rows));
+                    if row_link(ui, template.first_row, template.first_time).clicked() {
+                        action = Some(TemplateAction::JumpToRow(template.first_row));
+                    }
+                    if row_link(ui, template.last_row, template.last_time).clicked() {
+                        action = Some(TemplateAction::JumpToRow(template.last_row));
+                    }
+                    sparkline(ui, &template.histogram);
+                    Label::new(RichText::new(&template.template).monospace())
+                        .selectable(true)
+                        .ui(ui);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button("Filter")
+                            .on_hover_text("Search the messages of this template")
+                            .clicked()
+                        {
+                            action = Some(TemplateAction::Apply {
+                                index,
+                                exclude: false,
+                            });
+                        }
+                        if ui
+                            .small_button("Exclude")
+                            .on_hover_text("Hide the messages of this template")
+                            .clicked()
+                        {
+                            action = Some(TemplateAction::Apply {
+                                index,
+                                exclude: true,
+                            });
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+    action
+}
+
+fn share(count: u64, rows: u64) -> String {
+    if rows == 0 {
+        return String::new();
+    }
+    format!("{:.1}%", count as f64 * 100.0 / rows as f64)
+}
+
+fn row_link(ui: &mut Ui, row: u64, time: Option<u64>) -> Response {
+    let response = ui.link(row.to_string());
+    match time.and_then(|time| DateTime::from_timestamp_millis(time as i64)) {
+        Some(time) => response.on_hover_text(time.format("%Y-%m-%d %H:%M:%S%.3f").to_string()),
+        None => response.on_hover_text("Jump to row"),
+    }
+}
+
+/// Draws the number of messages over the session time as bars.
+fn sparkline(ui: &mut Ui, histogram: &[u64]) {
+    let (rect, _) = ui.allocate_exact_size(SPARKLINE_SIZE, Sense::hover());
+    let max = histogram.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return;
+    }
+    let width = rect.width() / histogram.len() as f32;
+    let color = ui.visuals().selection.bg_fill;
+    let painter = ui.painter_at(rect);
+    for (index, count) in histogram.iter().enumerate() {
+        if *count == 0 {
+            continue;
+        }
+        let height = (rect.height() * *count as f32 / max as f32).max(1.0);
+        let left = rect.left() + width * index as f32;
+        painter.rect_filled(
+            Rect::from_min_max(
+                pos2(left, rect.bottom() - height),
+                pos2(left + width, rect.bottom()),
+            ),
+            0.0,
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use crate::common::validation::{ValidationEligibility, validate_filter};
+
+    use super::*;
+
+    fn template(pattern: &str) -> stypes::LogTemplate {
+        stypes::LogTemplate {
+            template: String::new(),
+            pattern: pattern.to_owned(),
+            count: 1,
+            first_row: 0,
+            last_row: 0,
+            first_time: None,
+            last_time: None,
+            histogram: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn template_filter_is_an_eligible_regex() {
+        let template = template(r"^[\s\x04]*user[\s\x04]+[^\s\x04]+[\s\x04]*$");
+        let filter = template_filter(&template);
+
+        assert!(filter.is_regex());
+        assert!(matches!(
+            validate_filter(&filter),
+            ValidationEligibility::Eligible
+        ));
+        assert!(Regex::new(&filter.value).unwrap().is_match("user alice"));
+    }
+
+    #[test]
+    fn share_is_empty_without_rows() {
+        assert_eq!(share(1, 0), "");
+        assert_eq!(share(1, 8), "12.5%");
+    }
+}
@@ -19,6 +19,19 @@ pub struct AnsiText {
     pub text: String,
     /// Styled byte ranges into `text`.
     pub spans: Vec<AnsiSpan>,
+    /// Byte offset in the raw content for each byte of `text`.
+    pub origins: Vec<usize>,
+}
+
+impl AnsiText {
+    /// Maps a byte range in the raw content to the range of its visible bytes in `text`.
+    ///
+    /// Returns `None` if no visible byte originates from the range.
+    pub fn visible_range(&self, raw: Range<usize>) -> Option<Range<usize>> {
+        let start = self.origins.partition_point(|origin| *origin < raw.start);
+        let end = self.origins.partition_point(|origin| *origin < raw.end);
+        (start < end).then_some(start..end)
+    }
 }
 
 /// One colored range in [`AnsiText::text`].
@@ -57,8 +70,14 @@ pub fn parse_ansi_text(content: &str) -> AnsiText {
     let mut performer = AnsiPerformer::new(content.len());
 
     // ANSI parsing is byte-oriented; UTF-8 text is reconstructed by anstyle-parse.
-    for byte in content.bytes() {
+    for (offset, byte) in content.bytes().enumerate() {
+        let visible = performer.text.len();
         parser.advance(&mut performer, byte);
+        // A character is printed with its last byte, so its visible bytes originate
+        // from the bytes just before, one by one.
+        let printed = performer.text.len() - visible;
+        let first = offset + 1 - printed;
+        performer.origins.extend(first..first + printed);
     }
 
     performer.finish()
@@ -70,6 +89,8 @@ struct AnsiPerformer {
     text: String,
     /// Completed styled ranges in `text`.
     spans: Vec<AnsiSpan>,
+    /// Raw content offsets of the bytes in `text`.
+    origins: Vec<usize>,
     /// Current SGR color state.
     style: AnsiStyle,
     /// Start of the currently open styled range in `text`, if any.
@@ -82,6 +103,7 @@ impl AnsiPerformer {
         Self {
             text: String::with_capacity(capacity),
             spans: Vec::new(),
+            origins: Vec::with_capacity(capacity),
             style: AnsiStyle::default(),
             span_start: None,
         }
@@ -94,6 +116,7 @@ impl AnsiPerformer {
         AnsiText {
             text: self.text,
             spans: self.spans,
+            origins: self.origins,
         }
     }
 
@@ -432,4 +455,22 @@ mod tests {
         assert_eq!(parsed.text, "text");
         assert!(parsed.spans.is_empty());
     }
+
+    #[test]
+    fn visible_bytes_keep_their_raw_offsets() {
+        let parsed = parse_ansi_text("a\x1b[31mé\x1b[0mb");
+
+        assert_eq!(parsed.text, "aéb");
+        assert_eq!(parsed.origins, vec![0, 6, 7, 12]);
+    }
+
+    #[test]
+    fn raw_ranges_map_to_visible_ranges() {
+        let parsed = parse_ansi_text("w\x1b[31marn\x1b[0m!");
+
+        assert_eq!(parsed.visible_range(0..9), Some(0..4));
+        assert_eq!(parsed.visible_range(1..9), Some(1..4));
+        assert_eq!(parsed.visible_range(1..6), None);
+        assert_eq!(parsed.visible_range(13..14), Some(4..5));
+    }
 }
@@ -101,7 +101,7 @@ pub fn format_rows(mut rows: Vec<GrabbedElement>, schema: &dyn LogSchema) -> Str
                     let text = element.content.get(range).unwrap_or_default();
                     append_field(&mut output, text);
                 }
-                LogTableCell::Ansi(ansi_text) => append_field(&mut output, &ansi_text.text),
+                LogTableCell::Ansi { text, .. } => append_field(&mut output, &text.text),
             }
         }
     }
@@ -181,6 +181,8 @@ mod tests {
             content: content.to_owned(),
             pos,
             nature: 0,
+            matches: Vec::new(),
+            collapsed: None,
         }
     }
 
@@ -546,7 +546,9 @@ pub fn should_stick_to_bottom(shared: &SessionShared) -> bool {
                 op.phase().is_running() && shared.observe.is_file_read_completed()
             }
             ObserveOrigin::Concat(..) => false,
-            ObserveOrigin::Stream(..) => true,
+            ObserveOrigin::Stream(..) | ObserveOrigin::DirWatch(..) | ObserveOrigin::Plugin(..) => {
+                true
+            }
         })
 }
 
@@ -1,8 +1,10 @@
 //! Shared text layout for log table cells.
 //!
 //! This module combines ANSI SGR color spans with primary and nested search
-//! highlights in one egui `LayoutJob`. ANSI parsing itself stays in `ansi_text`;
-//! this module owns the log-table rendering policy and precedence rules.
+//! highlights in one egui `LayoutJob`. Primary highlights, including capture
+//! groups, are the match ranges reported by the backend for each row. ANSI
+//! parsing itself stays in `ansi_text`; this module owns the log-table
+//! rendering policy and precedence rules.
 
 use std::ops::Range;
 
@@ -11,6 +13,7 @@ use egui::{
     text::{LayoutJob, LayoutSection},
 };
 use regex::Regex;
+use stypes::{GrabbedElement, MatchRange};
 
 use crate::session::ui::{
     common::ansi_text::{AnsiSpan, AnsiText},
@@ -24,6 +27,9 @@ const LOG_TEXT_STYLE: TextStyle = TextStyle::Monospace;
 // so it sits on top of the row-level match tint instead of the plain table background.
 // A single translucent white highlight stays visible in both themes on that base.
 const FILTER_MATCH_HIGHLIGHT_BG: Color32 = Color32::from_rgba_unmultiplied_const(255, 255, 255, 60);
+// Capture groups are painted over their match, so they use a stronger tint of the match color.
+const CAPTURE_GROUP_HIGHLIGHT_BG: Color32 =
+    Color32::from_rgba_unmultiplied_const(255, 255, 255, 120);
 const NESTED_MATCH_HIGHLIGHT_BG: Color32 = Color32::from_rgba_unmultiplied_const(255, 180, 0, 140);
 
 /// Renders a monospace log-table cell with cached ANSI spans and match highlights.
@@ -36,10 +42,18 @@ pub fn render_log_cell_text(
     let response = match item.cells.get(col_idx) {
         Some(LogTableCell::Plain(range)) => {
             let content = item.element.content.get(range.clone()).unwrap_or_default();
-            render_plain_cell(ui, content, item.element.pos as u64, shared)
+            let highlights = cell_highlights(content, &item.element, range, Some, shared);
+            render_plain_cell(ui, content, &highlights)
         }
-        Some(LogTableCell::Ansi(ansi_text)) => {
-            render_ansi_cell(ui, ansi_text, item.element.pos as u64, shared)
+        Some(LogTableCell::Ansi { range, text }) => {
+            let highlights = cell_highlights(
+                &text.text,
+                &item.element,
+                range,
+                |raw| text.visible_range(raw),
+                shared,
+            );
+            render_ansi_cell(ui, text, &highlights)
         }
         None => ui.monospace(""),
     };
@@ -61,95 +75,100 @@ fn expand_cell_response(ui: &Ui, mut response: Response) -> Response {
     response.interact(sense)
 }
 
-fn render_plain_cell(
-    ui: &mut Ui,
-    content: &str,
-    main_log_pos: u64,
-    shared: &SessionShared,
-) -> Response {
-    let (match_spans, nested_spans) = cell_highlight_spans(
-        content,
-        main_log_pos,
-        shared,
-        shared.search.compiled_filters(),
-    );
-    if match_spans.is_empty() && nested_spans.is_empty() {
+fn render_plain_cell(ui: &mut Ui, content: &str, highlights: &CellHighlights) -> Response {
+    if highlights.is_empty() {
         ui.monospace(content)
     } else {
-        let mut content_job = build_match_layout_job(ui, content, &match_spans);
-        apply_nested_highlight(&mut content_job, &nested_spans);
+        let mut content_job = build_match_layout_job(ui, content, &highlights.matches);
+        apply_overlay_highlights(&mut content_job, highlights);
 
         ui.label(content_job)
     }
 }
 
-fn render_ansi_cell(
-    ui: &mut Ui,
-    ansi_text: &AnsiText,
-    main_log_pos: u64,
-    shared: &SessionShared,
-) -> Response {
-    let (match_spans, nested_spans) = cell_highlight_spans(
-        &ansi_text.text,
-        main_log_pos,
-        shared,
-        shared.search.compiled_filters(),
-    );
+fn render_ansi_cell(ui: &mut Ui, ansi_text: &AnsiText, highlights: &CellHighlights) -> Response {
     if ansi_text.spans.is_empty() {
-        if match_spans.is_empty() && nested_spans.is_empty() {
-            ui.monospace(&ansi_text.text)
-        } else {
-            let mut job = build_match_layout_job(ui, &ansi_text.text, &match_spans);
-            apply_nested_highlight(&mut job, &nested_spans);
-
-            ui.label(job)
-        }
+        render_plain_cell(ui, &ansi_text.text, highlights)
     } else {
-        let mut job = build_log_layout_job(ui, &ansi_text.text, &ansi_text.spans, &match_spans);
-        apply_nested_highlight(&mut job, &nested_spans);
+        let mut job =
+            build_log_layout_job(ui, &ansi_text.text, &ansi_text.spans, &highlights.matches);
+        apply_overlay_highlights(&mut job, highlights);
 
         ui.label(job)
     }
 }
 
+/// Highlight spans of one cell, as byte ranges in its displayed text.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CellHighlights {
+    /// Spans matched by the row's primary filters.
+    matches: Vec<Range<usize>>,
+    /// Spans of the capture groups within the primary matches.
+    groups: Vec<Range<usize>>,
+    /// Spans matched by the nested matcher.
+    nested: Vec<Range<usize>>,
+}
+
+impl CellHighlights {
+    fn is_empty(&self) -> bool {
+        self.matches.is_empty() && self.groups.is_empty() && self.nested.is_empty()
+    }
+}
+
 /// Returns the backend-reported filter indices for one main-log position, if that row matched.
 fn matched_filter_indices(shared: &SessionShared, main_log_pos: u64) -> Option<&[FilterIndex]> {
     shared.search.filter_indices(main_log_pos)
 }
 
-/// Finds all cell-local matches for the row's matched filters and merges overlap into one pass.
+/// Maps the backend-reported match ranges of the row into the cell and merges overlap
+/// into one pass.
+///
+/// `cell` is the byte range of the cell in the row content, and `to_visible` maps a range
+/// in the raw cell text to the displayed text. Matches outside of the cell are skipped.
+///
+/// # Returns
+///
+/// The first vector contains the spans of whole matches, the second the spans of their
+/// capture groups.
 fn cell_match_spans(
-    content: &str,
-    matched_filters: &[FilterIndex],
-    compiled_filters: &[Regex],
-) -> Vec<Range<usize>> {
-    let spans: Vec<Range<usize>> = matched_filters
-        .iter()
-        .filter_map(|idx| compiled_filters.get(idx.0 as usize))
-        .flat_map(|filter| filter.find_iter(content))
-        .filter_map(|mat| (mat.start() < mat.end()).then_some(mat.range()))
-        .collect();
-
-    merge_match_spans(spans)
+    matches: &[MatchRange],
+    cell: &Range<usize>,
+    to_visible: impl Fn(Range<usize>) -> Option<Range<usize>>,
+) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let mut spans = Vec::new();
+    let mut groups = Vec::new();
+    for found in matches {
+        let start = found.start.max(cell.start);
+        let end = found.end.min(cell.end);
+        if start >= end {
+            continue;
+        }
+        let Some(visible) = to_visible(start - cell.start..end - cell.start) else {
+            continue;
+        };
+        if found.group == 0 {
+            spans.push(visible);
+        } else {
+            groups.push(visible);
+        }
+    }
+
+    (merge_match_spans(spans), merge_match_spans(groups))
 }
 
-/// Resolves primary and nested highlight spans with one row-metadata lookup.
+/// Resolves primary and nested highlight spans for one cell with one row-metadata lookup.
 ///
-/// # Returns
-///
-/// The first vector contains spans from the row's matched primary filters. The second contains
-/// spans from the nested matcher when the row belongs to the primary search results.
-fn cell_highlight_spans(
+/// Primary spans come from the row's match ranges. Nested spans are found in the displayed
+/// text when the row belongs to the primary search results.
+fn cell_highlights(
     content: &str,
-    main_log_pos: u64,
+    element: &GrabbedElement,
+    cell: &Range<usize>,
+    to_visible: impl Fn(Range<usize>) -> Option<Range<usize>>,
     shared: &SessionShared,
-    compiled_filters: &[Regex],
-) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
-    let matched_filters = matched_filter_indices(shared, main_log_pos);
-    let match_spans = matched_filters
-        .map(|filters| cell_match_spans(content, filters, compiled_filters))
-        .unwrap_or_default();
-    let nested_spans = if matched_filters.is_some() {
+) -> CellHighlights {
+    let (matches, groups) = cell_match_spans(&element.matches, cell, to_visible);
+    let nested = if matched_filter_indices(shared, element.pos as u64).is_some() {
         shared
             .search
             .nested()
@@ -160,7 +179,11 @@ fn cell_highlight_spans(
         Vec::new()
     };
 
-    (match_spans, nested_spans)
+    CellHighlights {
+        matches,
+        groups,
+        nested,
+    }
 }
 
 /// Finds and coalesces adjacent nested matches in displayed cell text.
@@ -347,27 +370,33 @@ fn build_styled_layout_job(
     job
 }
 
-/// Applies nested background last while preserving every existing section style.
-fn apply_nested_highlight(job: &mut LayoutJob, nested_spans: &[Range<usize>]) {
-    if nested_spans.is_empty() {
+/// Paints capture groups over the primary matches, then nested matches over both.
+fn apply_overlay_highlights(job: &mut LayoutJob, highlights: &CellHighlights) {
+    apply_overlay_highlight(job, &highlights.groups, CAPTURE_GROUP_HIGHLIGHT_BG);
+    apply_overlay_highlight(job, &highlights.nested, NESTED_MATCH_HIGHLIGHT_BG);
+}
+
+/// Applies an overlay background while preserving every existing section style.
+fn apply_overlay_highlight(job: &mut LayoutJob, spans: &[Range<usize>], color: Color32) {
+    if spans.is_empty() {
         return;
     }
 
     let source_sections = std::mem::take(&mut job.sections);
-    let mut sections = Vec::with_capacity(source_sections.len() + nested_spans.len() * 2);
-    let mut first_nested = 0;
+    let mut sections = Vec::with_capacity(source_sections.len() + spans.len() * 2);
+    let mut first_span = 0;
 
     for source in source_sections {
-        while nested_spans
-            .get(first_nested)
+        while spans
+            .get(first_span)
             .is_some_and(|span| span.end <= source.byte_range.start)
         {
-            first_nested += 1;
+            first_span += 1;
         }
 
         let mut cursor = source.byte_range.start;
-        let mut nested_idx = first_nested;
-        while let Some(span) = nested_spans.get(nested_idx) {
+        let mut span_idx = first_span;
+        while let Some(span) = spans.get(span_idx) {
             if span.start >= source.byte_range.end {
                 break;
             }
@@ -375,17 +404,22 @@ fn apply_nested_highlight(job: &mut LayoutJob, nested_spans: &[Range<usize>]) {
             let highlight_start = span.start.max(cursor);
             let highlight_end = span.end.min(source.byte_range.end);
             if cursor < highlight_start {
-                push_layout_section(&mut sections, &source, cursor..highlight_start, false);
+                push_layout_section(&mut sections, &source, cursor..highlight_start, None);
             }
             if highlight_start < highlight_end {
-                push_layout_section(&mut sections, &source, highlight_start..highlight_end, true);
+                push_layout_section(
+                    &mut sections,
+                    &source,
+                    highlight_start..highlight_end,
+                    Some(color),
+                );
                 cursor = highlight_end;
             }
-            nested_idx += 1;
+            span_idx += 1;
         }
 
         if cursor < source.byte_range.end {
-            push_layout_section(&mut sections, &source, cursor..source.byte_range.end, false);
+            push_layout_section(&mut sections, &source, cursor..source.byte_range.end, None);
         }
     }
 
@@ -396,7 +430,7 @@ fn push_layout_section(
     sections: &mut Vec<LayoutSection>,
     source: &LayoutSection,
     byte_range: Range<usize>,
-    nested: bool,
+    overlay: Option<Color32>,
 ) {
     let leading_space = if byte_range.start == source.byte_range.start {
         source.leading_space
@@ -404,8 +438,8 @@ fn push_layout_section(
         0.0
     };
     let mut format = source.format.clone();
-    if nested {
-        format.background = NESTED_MATCH_HIGHLIGHT_BG;
+    if let Some(background) = overlay {
+        format.background = background;
     }
     let section = LayoutSection {
         leading_space,
@@ -491,14 +525,11 @@ mod tests {
     use egui::{Color32, TextFormat, text::LayoutJob};
     use processor::search::filter::{self, SearchFilter};
     use regex::Regex;
-    use stypes::{FileFormat, FilterMatch, ObserveOrigin};
+    use stypes::{FileFormat, FilterMatch, GrabbedElement, MatchRange, ObserveOrigin};
     use uuid::Uuid;
 
     use crate::{
-        host::{
-            common::parsers::ParserNames,
-            ui::registry::filters::{FilterDefinition, FilterRegistry},
-        },
+        host::common::parsers::ParserNames,
         session::{
             types::ObserveOperation,
             ui::shared::{SessionInfo, SessionShared},
@@ -506,8 +537,9 @@ mod tests {
     };
 
     use super::{
-        FILTER_MATCH_HIGHLIGHT_BG, FilterIndex, NESTED_MATCH_HIGHLIGHT_BG, apply_nested_highlight,
-        build_styled_layout_job, cell_highlight_spans, cell_match_spans, matched_filter_indices,
+        CAPTURE_GROUP_HIGHLIGHT_BG, CellHighlights, FILTER_MATCH_HIGHLIGHT_BG,
+        NESTED_MATCH_HIGHLIGHT_BG, apply_overlay_highlight, apply_overlay_highlights,
+        build_styled_layout_job, cell_highlights, cell_match_spans, matched_filter_indices,
         readable_text_color,
     };
     use crate::session::ui::common::ansi_text::{AnsiSpan, AnsiStyle, parse_ansi_text};
@@ -531,18 +563,6 @@ mod tests {
         SessionShared::new(session_info, observe_op, LogSchemaSpec::Text)
     }
 
-    fn apply_filter(
-        shared: &mut SessionShared,
-        registry: &mut FilterRegistry,
-        filter: SearchFilter,
-    ) -> Uuid {
-        let filter_def = FilterDefinition::new(filter);
-        let filter_id = filter_def.id;
-        registry.add_filter(filter_def);
-        shared.filters.apply_filter(registry, filter_id);
-        filter_id
-    }
-
     fn append_row_match(shared: &mut SessionShared, row_nr: u64, filters: Vec<u8>) {
         shared.search.set_search_operation(Uuid::new_v4());
         shared.search.append_matches(vec![FilterMatch {
@@ -551,13 +571,6 @@ mod tests {
         }]);
     }
 
-    fn compile_regexes(filters: impl IntoIterator<Item = SearchFilter>) -> Vec<Regex> {
-        filters
-            .into_iter()
-            .map(|filter| Regex::new(&filter::as_regex(&filter)).unwrap())
-            .collect()
-    }
-
     fn apply_nested_filter(shared: &mut SessionShared, filter: SearchFilter) {
         assert!(
             shared
@@ -570,13 +583,30 @@ mod tests {
         shared.search.nested_mut().set_matcher(Box::new(matcher));
     }
 
-    fn matched_cell_spans(
-        content: &str,
-        main_log_pos: u64,
-        shared: &SessionShared,
-        compiled_filters: &[Regex],
-    ) -> Vec<Range<usize>> {
-        cell_highlight_spans(content, main_log_pos, shared, compiled_filters).0
+    fn found(group: u8, range: Range<usize>) -> MatchRange {
+        MatchRange {
+            filter: 0,
+            group,
+            start: range.start,
+            end: range.end,
+        }
+    }
+
+    fn element(pos: usize, content: &str, matches: Vec<MatchRange>) -> GrabbedElement {
+        GrabbedElement {
+            source_id: 0,
+            content: content.to_owned(),
+            pos,
+            nature: 0,
+            matches,
+            collapsed: None,
+        }
+    }
+
+    /// Resolves the highlights of a plain cell spanning the whole row.
+    fn row_highlights(element: &GrabbedElement, shared: &SessionShared) -> CellHighlights {
+        let cell = 0..element.content.len();
+        cell_highlights(&element.content, element, &cell, Some, shared)
     }
 
     fn nested_cell_spans(
@@ -584,13 +614,7 @@ mod tests {
         main_log_pos: u64,
         shared: &SessionShared,
     ) -> Vec<Range<usize>> {
-        cell_highlight_spans(
-            content,
-            main_log_pos,
-            shared,
-            shared.search.compiled_filters(),
-        )
-        .1
+        row_highlights(&element(main_log_pos as usize, content, vec![]), shared).nested
     }
 
     fn nested_layout(
@@ -601,7 +625,7 @@ mod tests {
     ) -> LayoutJob {
         let mut job =
             build_styled_layout_job(content, ansi_spans, primary_spans, TextFormat::default());
-        apply_nested_highlight(&mut job, nested_spans);
+        apply_overlay_highlight(&mut job, nested_spans, NESTED_MATCH_HIGHLIGHT_BG);
         job
     }
 
@@ -614,102 +638,73 @@ mod tests {
     }
 
     #[test]
-    fn plain_text_filter_highlights_literal() {
-        let compiled = compile_regexes([SearchFilter::plain("cpu=(1.0)")]);
-
-        let spans = cell_match_spans("cpu=(1.0) status", &[FilterIndex(0)], &compiled);
-
-        assert_eq!(spans, vec![0..9]);
-    }
-
-    #[test]
-    fn regex_filter_highlights_match() {
-        let compiled = compile_regexes([SearchFilter::plain("cpu=\\d+").regex(true)]);
+    fn reported_matches_are_highlighted() {
+        let (spans, groups) = cell_match_spans(&[found(0, 0..3), found(0, 8..11)], &(0..11), Some);
 
-        let spans = cell_match_spans("cpu=42 status", &[FilterIndex(0)], &compiled);
-
-        assert_eq!(spans, vec![0..6]);
+        assert_eq!(spans, vec![0..3, 8..11]);
+        assert!(groups.is_empty());
     }
 
     #[test]
-    fn ignore_case_matches_mixed_case() {
-        let compiled = compile_regexes([SearchFilter::plain("warning").ignore_case(true)]);
-
-        let spans = cell_match_spans("pre WARNING post", &[FilterIndex(0)], &compiled);
+    fn overlapping_matches_are_merged() {
+        let (spans, _) = cell_match_spans(&[found(0, 0..3), found(0, 1..4)], &(0..6), Some);
 
-        assert_eq!(spans, vec![4..11]);
+        assert_eq!(spans, vec![0..4]);
     }
 
     #[test]
-    fn word_boundary_skips_embedded_text() {
-        let compiled = compile_regexes([SearchFilter::plain("warn").word(true)]);
+    fn capture_groups_are_reported_apart_from_matches() {
+        let matches = [found(0, 0..6), found(1, 4..6)];
 
-        let spans = cell_match_spans("prewarn warn warned", &[FilterIndex(0)], &compiled);
+        let (spans, groups) = cell_match_spans(&matches, &(0..13), Some);
 
-        assert_eq!(spans, vec![8..12]);
+        assert_eq!(spans, vec![0..6]);
+        assert_eq!(groups, vec![4..6]);
     }
 
     #[test]
-    fn all_matches_in_cell() {
-        let compiled = compile_regexes([SearchFilter::plain("err")]);
+    fn matches_are_clipped_to_the_cell() {
+        let matches = [found(0, 0..3), found(0, 4..7), found(0, 8..12)];
 
-        let spans = cell_match_spans("err and err", &[FilterIndex(0)], &compiled);
+        let (spans, _) = cell_match_spans(&matches, &(4..10), Some);
 
-        assert_eq!(spans, vec![0..3, 8..11]);
+        assert_eq!(spans, vec![0..3, 4..6]);
     }
 
     #[test]
-    fn overlapping_matches_are_merged() {
-        let compiled = compile_regexes([SearchFilter::plain("foo"), SearchFilter::plain("oob")]);
+    fn ansi_cell_matches_map_to_visible_text() {
+        let ansi_text = parse_ansi_text("w\x1b[31marn");
 
-        let spans = cell_match_spans("foobar", &[FilterIndex(0), FilterIndex(1)], &compiled);
+        let (spans, _) = cell_match_spans(&[found(0, 2..11)], &(2..11), |raw| {
+            ansi_text.visible_range(raw)
+        });
 
+        assert_eq!(ansi_text.text, "warn");
         assert_eq!(spans, vec![0..4]);
     }
 
-    #[test]
-    fn stale_filter_index_is_ignored() {
-        let compiled = compile_regexes([SearchFilter::plain("warn")]);
-
-        let spans = cell_match_spans("warn", &[FilterIndex(1)], &compiled);
-
-        assert!(spans.is_empty());
-    }
-
     #[test]
     fn bookmarked_row_has_no_cell_highlight() {
         let mut shared = new_shared();
         shared.logs.bookmarked_rows.insert(7);
-        let compiled = compile_regexes([SearchFilter::plain("warn")]);
 
         assert!(matched_filter_indices(&shared, 7).is_none());
-        assert!(matched_cell_spans("warn", 7, &shared, &compiled).is_empty());
-    }
-
-    #[test]
-    fn indexed_row_uses_main_log_pos() {
-        let mut shared = new_shared();
-        let compiled = compile_regexes([SearchFilter::plain("warn")]);
-
-        append_row_match(&mut shared, 42, vec![0]);
-
-        let spans = matched_cell_spans("warn row", 42, &shared, &compiled);
-
-        assert_eq!(spans, vec![0..4]);
-        assert!(matched_cell_spans("warn row", 3, &shared, &compiled).is_empty());
+        assert!(row_highlights(&element(7, "warn", vec![]), &shared).is_empty());
     }
 
     #[test]
-    fn filter_matches_visible_ansi_text() {
-        let mut shared = new_shared();
-        let compiled = compile_regexes([SearchFilter::plain("warn")]);
-        append_row_match(&mut shared, 42, vec![0]);
-
-        let visible = parse_ansi_text("w\x1b[31marn").text;
-        let spans = matched_cell_spans(&visible, 42, &shared, &compiled);
+    fn capture_group_background_is_painted_over_match() {
+        let highlights = CellHighlights {
+            matches: vec![0..6],
+            groups: vec![4..6],
+            nested: Vec::new(),
+        };
+        let mut job =
+            build_styled_layout_job("cpu=42", &[], &highlights.matches, TextFormat::default());
+        apply_overlay_highlights(&mut job, &highlights);
 
-        assert_eq!(visible, "warn");
-        assert_eq!(spans, vec![0..4]);
+        assert_eq!(format_at(&job, 1).background, FILTER_MATCH_HIGHLIGHT_BG);
+        assert_eq!(format_at(&job, 4).background, CAPTURE_GROUP_HIGHLIGHT_BG);
     }
 
     #[test]
@@ -938,28 +933,4 @@ mod tests {
             Color32::WHITE
         );
     }
-
-    #[test]
-    fn temp_highlight_uses_effective_backend_order() {
-        let mut shared = new_shared();
-        let mut registry = FilterRegistry::default();
-
-        apply_filter(&mut shared, &mut registry, SearchFilter::plain("first"));
-        apply_filter(&mut shared, &mut registry, SearchFilter::plain("second"));
-        let temp_search = SearchFilter::plain("temp").ignore_case(true).into();
-        shared.filters.active_temp_search = Some(temp_search);
-        let filters = shared.filters.effective_filters(&registry);
-        shared.search.refresh_compiled_filters(&filters);
-
-        append_row_match(&mut shared, 12, vec![0]);
-
-        let spans = matched_cell_spans(
-            "first temp second",
-            12,
-            &shared,
-            shared.search.compiled_filters(),
-        );
-
-        assert_eq!(spans, vec![6..10]);
-    }
 }
@@ -160,6 +160,8 @@ mod tests {
             content: content.to_owned(),
             pos: 0,
             nature: 0,
+            matches: Vec::new(),
+            collapsed: None,
         }
     }
 
@@ -177,9 +179,14 @@ mod tests {
         };
         assert_eq!(item.element.content.get(range.clone()), Some("plain"));
 
-        let LogTableCell::Ansi(ansi_text) = &item.cells[1] else {
+        let LogTableCell::Ansi {
+            range,
+            text: ansi_text,
+        } = &item.cells[1]
+        else {
             panic!("expected ANSI cell");
         };
+        assert_eq!(range, &(6..14));
         assert_eq!(ansi_text.text, "red");
         assert_eq!(ansi_text.spans.len(), 1);
         assert_eq!(ansi_text.spans[0].range, 0..3);
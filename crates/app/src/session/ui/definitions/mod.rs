@@ -21,7 +21,11 @@ pub enum LogTableCell {
     /// Plain cell text borrowed from the prepared log content.
     Plain(Range<usize>),
     /// ANSI-styled cell text with escapes stripped from the visible content.
-    Ansi(AnsiText),
+    Ansi {
+        /// Byte range of the raw cell text in the prepared log content.
+        range: Range<usize>,
+        text: AnsiText,
+    },
 }
 
 impl LogTableCell {
@@ -29,7 +33,10 @@ impl LogTableCell {
     pub fn from_range(content: &str, range: Range<usize>) -> Self {
         let cell_text = content.get(range.clone()).unwrap_or_default();
         if memchr(0x1b, cell_text.as_bytes()).is_some() {
-            Self::Ansi(parse_ansi_text(cell_text))
+            Self::Ansi {
+                text: parse_ansi_text(cell_text),
+                range,
+            }
         } else {
             Self::Plain(range)
         }
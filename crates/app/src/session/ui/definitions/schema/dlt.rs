@@ -68,6 +68,8 @@ mod tests {
             content,
             pos: 0,
             nature: 0,
+            matches: Vec::new(),
+            collapsed: None,
         }
     }
 
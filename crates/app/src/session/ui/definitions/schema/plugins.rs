@@ -111,6 +111,8 @@ mod tests {
             content: content.to_owned(),
             pos: 0,
             nature: 0,
+            matches: Vec::new(),
+            collapsed: None,
         }
     }
 
@@ -1,20 +1,26 @@
 //! Session-owned Jump to Row input overlay.
 
+use chrono::{DateTime, NaiveDateTime, NaiveTime, Timelike};
 use egui::{Align2, Event, Key, Modifiers, Order, Ui, Window, pos2, vec2};
 
-use crate::host::common::ui_utls::{
-    clicked_outside_rect, show_validation_message, sized_singleline_text_edit,
+use crate::{
+    host::common::ui_utls::{
+        clicked_outside_rect, show_validation_message, sized_singleline_text_edit,
+    },
+    session::types::TimeQuery,
 };
 
 use super::shared::{SearchTableSync, SessionShared};
 
-/// Lightweight overlay for selecting a zero-based session row.
+/// Lightweight overlay for selecting a zero-based session row or a time.
 #[derive(Debug, Default)]
 pub struct JumpToRow {
     open: bool,
     input: String,
     first_open_frame: bool,
     show_validation: bool,
+    /// Submitted time waiting to be resolved into a row by the session service.
+    time_request: Option<TimeQuery>,
 }
 
 impl JumpToRow {
@@ -25,6 +31,7 @@ impl JumpToRow {
             input,
             first_open_frame,
             show_validation,
+            time_request,
         } = self;
 
         if *open {
@@ -35,6 +42,7 @@ impl JumpToRow {
         input.clear();
         *first_open_frame = true;
         *show_validation = false;
+        *time_request = None;
     }
 
     /// Returns whether the overlay is visible.
@@ -49,6 +57,7 @@ impl JumpToRow {
             input,
             first_open_frame,
             show_validation,
+            time_request: _,
         } = self;
 
         *open = false;
@@ -57,6 +66,11 @@ impl JumpToRow {
         *show_validation = false;
     }
 
+    /// Takes the submitted time, which the session service resolves into a row.
+    pub fn take_time_request(&mut self) -> Option<TimeQuery> {
+        self.time_request.take()
+    }
+
     /// Handles overlay input and renders the open overlay.
     pub fn render(&mut self, shared: &mut SessionShared, parent_ui: &Ui) {
         if parent_ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Escape)) {
@@ -68,10 +82,18 @@ impl JumpToRow {
         if consume_enter(parent_ui) {
             self.show_validation = true;
 
-            if let RowValidation::Valid(row) = validate_row(&self.input, logs_count) {
-                shared.logs.focus_main_row(row, SearchTableSync::Sync);
-                self.close();
-                return;
+            match validate_row(&self.input, logs_count) {
+                RowValidation::Valid(row) => {
+                    shared.logs.focus_main_row(row, SearchTableSync::Sync);
+                    self.close();
+                    return;
+                }
+                RowValidation::Time(time) => {
+                    self.close();
+                    self.time_request = Some(time);
+                    return;
+                }
+                RowValidation::InvalidInput | RowValidation::OutOfBounds => {}
             }
         }
 
@@ -107,7 +129,7 @@ impl JumpToRow {
                     7,
                 )
                 .id(input_id)
-                .hint_text("Enter row number")
+                .hint_text("Enter row number or time, e.g. 14:03:10.200")
                 .lock_focus(true)
                 .show(ui)
                 .response;
@@ -148,14 +170,15 @@ enum RowValidation {
     InvalidInput,
     OutOfBounds,
     Valid(u64),
+    Time(TimeQuery),
 }
 
 impl RowValidation {
     fn message(self) -> Option<&'static str> {
         match self {
-            Self::InvalidInput => Some("Enter a valid row number."),
+            Self::InvalidInput => Some("Enter a valid row number or time."),
             Self::OutOfBounds => Some("Row is out of bounds."),
-            Self::Valid(_) => None,
+            Self::Valid(_) | Self::Time(_) => None,
         }
     }
 }
@@ -164,8 +187,32 @@ fn validate_row(input: &str, logs_count: u64) -> RowValidation {
     match input.parse::<u64>() {
         Ok(row) if row < logs_count => RowValidation::Valid(row),
         Ok(_) => RowValidation::OutOfBounds,
-        Err(_) => RowValidation::InvalidInput,
+        Err(_) => parse_time(input).map_or(RowValidation::InvalidInput, RowValidation::Time),
+    }
+}
+
+/// Parses a date and time, taken as UTC unless it has an offset, or a time of day.
+fn parse_time(input: &str) -> Option<TimeQuery> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(input) {
+        return u64::try_from(date_time.timestamp_millis())
+            .ok()
+            .map(TimeQuery::At);
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(input, format) {
+            return u64::try_from(date_time.and_utc().timestamp_millis())
+                .ok()
+                .map(TimeQuery::At);
+        }
+    }
+    for format in ["%H:%M:%S%.f", "%H:%M"] {
+        if let Ok(time) = NaiveTime::parse_from_str(input, format) {
+            let ms = u64::from(time.num_seconds_from_midnight()) * 1000
+                + u64::from(time.nanosecond() / 1_000_000);
+            return Some(TimeQuery::TimeOfDay(ms));
+        }
     }
+    None
 }
 
 fn consume_enter(ui: &Ui) -> bool {
@@ -199,7 +246,7 @@ mod tests {
     use crate::{
         host::common::parsers::ParserNames,
         session::{
-            types::ObserveOperation,
+            types::{ObserveOperation, TimeQuery},
             ui::{
                 SessionInfo,
                 definitions::schema::LogSchemaSpec,
@@ -304,6 +351,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn validation_accepts_times() {
+        for (input, expected) in [
+            ("14:03:10.200", TimeQuery::TimeOfDay(50_590_200)),
+            ("14:03:10", TimeQuery::TimeOfDay(50_590_000)),
+            ("14:03", TimeQuery::TimeOfDay(50_580_000)),
+            ("2024-05-01 14:03:10.200", TimeQuery::At(1_714_572_190_200)),
+            ("2024-05-01T14:03:10", TimeQuery::At(1_714_572_190_000)),
+            (
+                "2024-05-01T16:03:10.200+02:00",
+                TimeQuery::At(1_714_572_190_200),
+            ),
+        ] {
+            assert_eq!(
+                validate_row(input, 10),
+                RowValidation::Time(expected),
+                "input: {input:?}"
+            );
+        }
+        assert_eq!(validate_row("25:00", 10), RowValidation::InvalidInput);
+    }
+
+    #[test]
+    fn time_of_day_resolves_on_the_day_of_the_logs() {
+        const DAY_START: u64 = 1_714_521_600_000;
+        let bounds = DAY_START + 23 * 3_600_000..=DAY_START + 25 * 3_600_000;
+
+        assert_eq!(
+            TimeQuery::TimeOfDay(23 * 3_600_000 + 500).resolve(&bounds),
+            DAY_START + 23 * 3_600_000 + 500
+        );
+        assert_eq!(
+            TimeQuery::TimeOfDay(3_600_000).resolve(&bounds),
+            DAY_START + 25 * 3_600_000
+        );
+        assert_eq!(TimeQuery::At(42).resolve(&bounds), 42);
+    }
+
+    #[test]
+    fn submitted_times_are_requested_from_the_session() {
+        let mut jump = JumpToRow::default();
+        let mut shared = new_shared(10);
+        shared.logs.replace_selection_with(3);
+        jump.open();
+        jump.input = "14:03:10".to_owned();
+
+        render(
+            &mut jump,
+            &mut shared,
+            key_input(Key::Enter, Modifiers::NONE),
+        );
+
+        assert!(!jump.is_open());
+        assert_eq!(
+            jump.take_time_request(),
+            Some(TimeQuery::TimeOfDay(50_590_000))
+        );
+        assert!(jump.take_time_request().is_none());
+        assert_eq!(shared.logs.single_selected_row(), Some(3));
+    }
+
     #[test]
     fn validation_stays_hidden_until_edit_or_submit() {
         let mut jump = JumpToRow::default();
@@ -4,12 +4,15 @@ use std::{
     sync::mpsc::Receiver as StdReceiver,
 };
 
+use chrono::DateTime;
 use egui::{Sense, TextBuffer, Ui};
 use egui_table::{CellInfo, HeaderCellInfo, PrefetchInfo, TableDelegate};
 use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
 
 use processor::grabber::LineRange;
-use stypes::GrabbedElement;
+use session_core::state::DuplicatesMode;
+use stypes::{CollapsedRun, GrabbedElement};
 
 use crate::{
     host::ui::{UiActions, state::HostPreferences},
@@ -59,6 +62,12 @@ pub struct LogsTable {
     /// on that frame.
     pending_logs_rx: Option<StdReceiver<Result<Vec<GrabbedElement>, SessionError>>>,
     schema: Rc<dyn LogSchema>,
+    /// Count of hidden rows when the cached logs were grabbed. Table rows are rows of the
+    /// main view, so cached logs are stale once rows get hidden or shown.
+    hidden_count: u64,
+    /// Count of search results when the cached logs were grabbed. Grabbed logs carry the
+    /// ranges matched by the search, so visible rows are grabbed again once it changes.
+    search_result_count: u64,
 }
 
 impl LogsTable {
@@ -70,6 +79,8 @@ impl LogsTable {
             logs: LogsMapped::new(Rc::clone(&schema)),
             pending_logs_rx: None,
             schema,
+            hidden_count: 0,
+            search_result_count: 0,
         }
     }
 
@@ -85,12 +96,22 @@ impl LogsTable {
 
         self.update_row_header_width(shared, ui);
 
+        if self.hidden_count != shared.logs.hidden_count() {
+            self.hidden_count = shared.logs.hidden_count();
+            self.logs.clear();
+            self.last_visible_rows = None;
+        }
+        if self.search_result_count != shared.search.search_result_count() {
+            self.search_result_count = shared.search.search_result_count();
+            self.last_visible_rows = None;
+        }
+
         // Ensure the border of last column isn't visible.
         let columns = columns_filling_last(ui, TABLE_ID_SALT, &shared.view.log_columns);
 
         let mut table = egui_table::Table::new()
             .id_salt(TABLE_ID_SALT)
-            .num_rows(shared.logs.logs_count())
+            .num_rows(shared.logs.visible_count())
             .columns(columns)
             .num_sticky_cols(1)
             .stick_to_bottom(should_stick_to_bottom(shared));
@@ -103,11 +124,18 @@ impl LogsTable {
             preferences.panels_visibility.bottom && shared.bottom_tab == BottomTabType::Search;
 
         if let Some(focus) = shared.logs.take_main_row_focus() {
-            const OFFSET: u64 = 3;
-            table = table.scroll_to_rows(
-                focus.row.saturating_sub(OFFSET)..=focus.row.saturating_add(OFFSET),
-                None,
-            );
+            if shared.logs.hidden_count() == 0 {
+                table = table.scroll_to_rows(focus_rows(focus.row), None);
+            } else {
+                // Focused rows are session positions, which differ from the table rows
+                // while rows are hidden.
+                actions.try_send_command(
+                    &self.cmd_tx,
+                    SessionCommand::GetViewRow {
+                        session_position: focus.row,
+                    },
+                );
+            }
 
             let details_row = shared
                 .logs
@@ -224,6 +252,48 @@ impl LogsTable {
             );
             ui.close();
         }
+
+        ui.separator();
+        self.render_duplicates_menu(shared, actions, ui);
+    }
+
+    fn render_duplicates_menu(
+        &mut self,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+        ui: &mut Ui,
+    ) {
+        ui.menu_button("Collapse Duplicates", |ui| {
+            let current = shared.logs.duplicates_mode();
+            let mut mode = current;
+            ui.radio_value(&mut mode, None, "Off");
+            ui.radio_value(&mut mode, Some(DuplicatesMode::Exact), "Identical Logs")
+                .on_hover_text("Collapse consecutive logs with the same content");
+            ui.radio_value(&mut mode, Some(DuplicatesMode::Masked), "Ignoring Numbers")
+                .on_hover_text(
+                    "Collapse consecutive logs which only differ in numbers and hex values",
+                );
+            if mode != current {
+                let cmd = SessionCommand::CollapseDuplicates {
+                    operation_id: Uuid::new_v4(),
+                    mode,
+                };
+                if actions.try_send_command(&self.cmd_tx, cmd) {
+                    shared.logs.set_duplicates_mode(mode);
+                }
+                ui.close();
+            }
+
+            ui.separator();
+            ui.add_enabled(
+                current.is_some(),
+                egui::Checkbox::new(
+                    &mut shared.exports.include_duplicates,
+                    "Export Collapsed Duplicates",
+                ),
+            )
+            .on_hover_text("Include the logs of collapsed duplicates in exports");
+        });
     }
 
     /// Grows the row-header column when the largest row number gains another digit.
@@ -246,6 +316,11 @@ impl LogsTable {
         }
     }
 
+    /// Queues a scroll to the main-view row resolved for a focused session position.
+    pub fn scroll_to_view_row(&mut self, row: u64) {
+        self.pending_scroll = Some(focus_rows(row));
+    }
+
     /// Queues a vertical table scroll for the next render pass.
     pub fn scroll(&mut self, action: TableScroll, row_count: u64) {
         if let Some(target) =
@@ -256,6 +331,12 @@ impl LogsTable {
     }
 }
 
+/// Returns the rows to scroll into view to show the focused row with some context.
+fn focus_rows(row: u64) -> RangeInclusive<u64> {
+    const OFFSET: u64 = 3;
+    row.saturating_sub(OFFSET)..=row.saturating_add(OFFSET)
+}
+
 /// Applies side effects for a main row focus produced by clicks or external jump requests.
 fn sync_focused_row(
     actions: &mut UiActions,
@@ -342,7 +423,22 @@ impl<'a> LogsDelegate<'a> {
         self.actions.try_send_command(&self.table.cmd_tx, cmd);
     }
 
+    /// Returns the session position of the log shown in the table row, or `None` if the
+    /// row isn't loaded yet while rows are hidden.
+    fn row_position(&self, row_nr: u64) -> Option<u64> {
+        if self.shared.logs.hidden_count() == 0 {
+            return Some(row_nr);
+        }
+        self.table
+            .logs
+            .get_log_item(&row_nr)
+            .map(|item| item.element.pos as u64)
+    }
+
     fn render_row_header(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        let Some(position) = self.row_position(cell.row_nr) else {
+            return;
+        };
         let source_color_idx = self
             .has_multi_sources
             .then(|| {
@@ -352,12 +448,12 @@ impl<'a> LogsDelegate<'a> {
                     .map(|item| item.element.source_id as usize)
             })
             .flatten();
-        let is_bookmarked = self.shared.logs.is_bookmarked(cell.row_nr);
+        let is_bookmarked = self.shared.logs.is_bookmarked(position);
 
         let attachment_id = self
             .shared
             .attachments
-            .attachment_by_log_position(cell.row_nr as usize)
+            .attachment_by_log_position(position as usize)
             .map(|attachment| attachment.uuid);
         let attachment_info =
             attachment_id.map_or(LogAttachmentInfo::NoAttachment, |attachment_id| {
@@ -369,7 +465,7 @@ impl<'a> LogsDelegate<'a> {
         let row_number_digits = self.shared.logs.row_number_digits();
         let header = render_row_header(
             ui,
-            cell.row_nr,
+            position,
             row_number_digits,
             source_color_idx,
             is_bookmarked,
@@ -415,9 +511,9 @@ impl<'a> LogsDelegate<'a> {
                 }
             }
         } else if header.bookmark_clicked {
-            self.toggle_row_bookmark(cell.row_nr);
+            self.toggle_row_bookmark(position);
         } else if header.response.clicked() {
-            self.handle_selection_click(cell.row_nr, ui.input(|i| i.modifiers));
+            self.handle_selection_click(position, ui.input(|i| i.modifiers));
         }
     }
 
@@ -438,11 +534,14 @@ impl<'a> LogsDelegate<'a> {
                     self.table
                         .render_context_menu(self.shared, self.actions, ui)
                 });
-                if response.clicked() {
-                    self.handle_selection_click(row_nr, ui.input(|i| i.modifiers));
+                if response.clicked()
+                    && let Some(position) = self.row_position(row_nr)
+                {
+                    self.handle_selection_click(position, ui.input(|i| i.modifiers));
                 }
                 return;
             };
+            let position = item.element.pos as u64;
 
             source_changed = self.has_multi_sources
                 && self
@@ -452,10 +551,28 @@ impl<'a> LogsDelegate<'a> {
                     .contains(&item.element.pos);
 
             let col_idx = col_nr.saturating_sub(1);
-            let response = render_log_cell_text(ui, item, col_idx, self.shared);
+            let collapsed = item.element.collapsed.clone().filter(|_| col_idx == 0);
+            let (response, expand) = match collapsed {
+                Some(run) => {
+                    ui.horizontal(|ui| {
+                        let expand = render_collapsed_badge(ui, &run);
+                        (render_log_cell_text(ui, item, col_idx, self.shared), expand)
+                    })
+                    .inner
+                }
+                None => (render_log_cell_text(ui, item, col_idx, self.shared), None),
+            };
 
-            if response.clicked() {
-                self.handle_selection_click(row_nr, ui.input(|i| i.modifiers));
+            if let Some(expanded) = expand {
+                self.actions.try_send_command(
+                    &self.table.cmd_tx,
+                    SessionCommand::ExpandDuplicates {
+                        session_position: position,
+                        expanded,
+                    },
+                );
+            } else if response.clicked() {
+                self.handle_selection_click(position, ui.input(|i| i.modifiers));
             }
 
             response.context_menu(|ui| {
@@ -470,9 +587,43 @@ impl<'a> LogsDelegate<'a> {
     }
 }
 
+/// Renders the repeat count of a run of duplicates. Returns whether to expand the run
+/// once the badge is clicked.
+fn render_collapsed_badge(ui: &mut Ui, run: &CollapsedRun) -> Option<bool> {
+    let (label, action) = if run.expanded {
+        (format!("▾ ×{}", run.count), "Click to collapse them")
+    } else {
+        (format!("▸ ×{}", run.count), "Click to expand them")
+    };
+    let response = ui
+        .small_button(label)
+        .on_hover_text(collapsed_run_tooltip(run, action));
+    response.clicked().then_some(!run.expanded)
+}
+
+fn collapsed_run_tooltip(run: &CollapsedRun, action: &str) -> String {
+    let format = |time: u64| {
+        DateTime::from_timestamp_millis(time as i64)
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+    };
+    let span = run
+        .time
+        .and_then(|(first, last)| Some((format(first)?, format(last)?)));
+    match span {
+        Some((first, last)) => format!(
+            "{} repeated logs up to row {}\nfrom {first}\nto {last}\n{action}",
+            run.count, run.last_pos
+        ),
+        None => format!(
+            "{} repeated logs up to row {}\n{action}",
+            run.count, run.last_pos
+        ),
+    }
+}
+
 impl TableDelegate for LogsDelegate<'_> {
     fn prepare(&mut self, info: &PrefetchInfo) {
-        if self.shared.logs.logs_count() == 0 {
+        if self.shared.logs.visible_count() == 0 {
             return;
         }
 
@@ -516,10 +667,11 @@ impl TableDelegate for LogsDelegate<'_> {
 
         if let Ok(elements) = logs_rx.recv_timeout(grab_cmd_consts::TIMEOUT_DURATION) {
             match elements {
-                Ok(elements) => self.table.logs.append(
-                    elements.into_iter().map(|e| (e.pos as u64, e)),
-                    self.has_multi_sources,
-                ),
+                // Logs are keyed by table row, which is their position unless rows are hidden.
+                Ok(elements) => self
+                    .table
+                    .logs
+                    .append((visible_rows.start..).zip(elements), self.has_multi_sources),
                 Err(error) => {
                     common::log_table::table::handle_grab_errors(
                         error,
@@ -553,12 +705,15 @@ impl TableDelegate for LogsDelegate<'_> {
     }
 
     fn row_ui(&mut self, ui: &mut Ui, row_nr: u64) {
-        let is_selected = self.shared.logs.is_selected(row_nr);
-        common::log_table::table::apply_log_row_colors(ui, self.shared, Some(row_nr), is_selected);
+        let position = self.row_position(row_nr);
+        let is_selected = position.is_some_and(|pos| self.shared.logs.is_selected(pos));
+        common::log_table::table::apply_log_row_colors(ui, self.shared, position, is_selected);
 
         let response = ui.response().interact(Sense::click());
-        if response.clicked() {
-            self.handle_selection_click(row_nr, ui.input(|i| i.modifiers));
+        if response.clicked()
+            && let Some(position) = position
+        {
+            self.handle_selection_click(position, ui.input(|i| i.modifiers));
         }
         response.context_menu(|ui| {
             self.table
@@ -36,7 +36,7 @@ use crate::{
         communication::{UiHandle, UiReceivers},
         error::SessionError,
         message::{BookmarkUpdate, SessionMessage},
-        types::{OperationPhase, attachment::PreviewTarget},
+        types::{CompareSession, OperationPhase, attachment::PreviewTarget},
         ui::{
             sde_bar::SdeBarUi,
             shared::{SearchSyncOutcome, SearchTableSync, SessionSignal},
@@ -136,6 +136,32 @@ impl Session {
         self.shared.get_info()
     }
 
+    /// Returns this session as a target of comparisons from other sessions, once its
+    /// session file is known.
+    pub fn compare_session(&self) -> Option<CompareSession> {
+        self.shared.session_file().map(|file| CompareSession {
+            id: self.shared.get_id(),
+            title: self.shared.get_info().title.clone(),
+            file: file.clone(),
+            rows: self.shared.logs.logs_count(),
+        })
+    }
+
+    /// Returns whether the comparison view is shown and needs the other sessions.
+    pub fn wants_compare_sessions(&self) -> bool {
+        self.shared.bottom_tab == BottomTabType::Compare
+    }
+
+    /// Sets the other sessions this session can be compared with.
+    pub fn set_compare_sessions(&mut self, sessions: Vec<CompareSession>) {
+        self.bottom_panel.compare.set_sessions(sessions);
+    }
+
+    /// Selects the row and scrolls the main logs table to it.
+    pub fn focus_row(&mut self, row: u64) {
+        self.shared.logs.focus_main_row(row, SearchTableSync::Sync);
+    }
+
     /// Returns whether this session currently has stream input controls available.
     pub fn sde_available(&self) -> bool {
         self.sde_bar.is_available()
@@ -181,6 +207,9 @@ impl Session {
 
         if jump_to_row.is_open() {
             jump_to_row.render(shared, ui);
+            if let Some(time) = jump_to_row.take_time_request() {
+                actions.try_send_command(cmd_tx, SessionCommand::GetRowAtTime { time });
+            }
         }
 
         shared.exports.handle_dialogs(actions, cmd_tx);
@@ -222,7 +251,7 @@ impl Session {
                 .frame(Frame::NONE.outer_margin(Margin::same(2)))
                 .show_inside(ui, |ui| {
                     ui.push_id(shared.get_id(), |ui| {
-                        sde_bar.render_content(actions, ui);
+                        sde_bar.render_content(actions, &mut preferences.sde_macros, ui);
                     });
                 });
         }
@@ -309,6 +338,43 @@ impl Session {
                     // Keep live-follow charts attached to the growing session span.
                     self.bottom_panel.chart.on_chart_data_changes(&self.shared);
                 }
+                SessionMessage::HiddenRowsCount(count) => {
+                    self.shared.logs.set_hidden_count(count);
+                }
+                SessionMessage::TimeIndexedCount(count) => {
+                    self.shared.logs.set_time_indexed_count(count);
+                }
+                SessionMessage::ViewRow(result) => {
+                    if let Some(row) = self.ok_or_notify(result, actions) {
+                        self.logs_table.scroll_to_view_row(row);
+                    }
+                }
+                SessionMessage::RowAtTime(result) => match self.ok_or_notify(result, actions) {
+                    Some(Some(row)) => {
+                        self.shared.logs.focus_main_row(row, SearchTableSync::Sync);
+                    }
+                    Some(None) => {
+                        actions.add_notification(AppNotification::Info(
+                            "The logs of this session have no timestamps.".to_owned(),
+                        ));
+                    }
+                    None => {}
+                },
+                SessionMessage::Templates(result) => {
+                    if let Some(templates) = self.ok_or_notify(result, actions) {
+                        self.bottom_panel.templates.handle_templates(templates);
+                    }
+                }
+                SessionMessage::Comparison(result) => {
+                    if let Some(comparison) = self.ok_or_notify(result, actions) {
+                        self.bottom_panel.compare.handle_comparison(comparison);
+                    }
+                }
+                SessionMessage::SessionFile(result) => {
+                    if let Some(session_file) = self.ok_or_notify(result, actions) {
+                        self.shared.set_session_file(session_file);
+                    }
+                }
                 SessionMessage::IndexedCountUpdated { count } => {
                     self.shared.search.set_indexed_result_count(count);
                 }
@@ -432,6 +498,12 @@ impl Session {
                     operation_id,
                     phase,
                 } => {
+                    self.bottom_panel
+                        .templates
+                        .on_operation_updated(operation_id, phase);
+                    self.bottom_panel
+                        .compare
+                        .on_operation_updated(operation_id, phase);
                     if self
                         .on_operation_updated(operation_id, phase, actions, &registry.filters)
                         .is_break()
@@ -440,6 +512,12 @@ impl Session {
                     }
                     // Potential components which keep track for operations can go here.
                 }
+                SessionMessage::SourceStateChanged {
+                    operation_id,
+                    state,
+                } => {
+                    self.shared.observe.update_source_state(operation_id, state);
+                }
                 SessionMessage::FileReadCompleted => {
                     self.shared.observe.set_file_read_completed();
                     self.recent_session
@@ -564,6 +642,8 @@ impl Session {
         }
 
         if observe_started_processing {
+            actions.try_send_command(&self.cmd_tx, SessionCommand::GetSessionFile);
+
             let outcome = self
                 .recent_session
                 .on_session_file_ready(&mut self.shared, registry);
@@ -753,7 +833,7 @@ impl Session {
 
     fn scroll_main_table(&mut self, action: TableScroll) {
         self.logs_table
-            .scroll(action, self.shared.logs.logs_count());
+            .scroll(action, self.shared.logs.visible_count());
     }
 
     fn scroll_active_table(
@@ -1006,6 +1086,7 @@ mod tests {
             session_position: 70,
             search_result_index: 2,
             indexed_row_index: 4,
+            matches: Vec::new(),
         };
         session.handle_nested_result(
             request_id,
@@ -1057,6 +1138,7 @@ mod tests {
             session_position: 70,
             search_result_index: 2,
             indexed_row_index: 4,
+            matches: Vec::new(),
         };
 
         let stale_request_id = Uuid::new_v4();
@@ -252,7 +252,7 @@ mod tests {
     use uuid::Uuid;
 
     use processor::search::filter::SearchFilter;
-    use stypes::{FileFormat, ObserveOrigin, TCPTransportConfig, Transport};
+    use stypes::{FileFormat, ObserveOrigin, TCPTransportConfig, TcpMode, Transport};
 
     use crate::{
         host::{
@@ -625,7 +625,7 @@ mod tests {
 
         match cmd_rx.try_recv() {
             Ok(SessionCommand::ApplySearchValuesFilter { filters, .. }) => {
-                assert_eq!(filters, vec![search_value.value]);
+                assert_eq!(filters, vec![search_value]);
             }
             other => panic!("expected ApplySearchValuesFilter command, got {other:?}"),
         }
@@ -682,6 +682,7 @@ mod tests {
             String::from("source"),
             Transport::TCP(TCPTransportConfig {
                 bind_addr: String::from("127.0.0.1:9000"),
+                mode: TcpMode::Connect,
             }),
         ));
         let registry = FilterRegistry::default();
@@ -0,0 +1,140 @@
+//! Form of the SDE bar for sending DLT control messages to the ECU behind a source.
+
+use egui::{ComboBox, Grid, TextEdit, Ui};
+use stypes::{DltControlCommand, DltControlRequest, DltLogLevel};
+
+/// Maximum length of ECU, application and context IDs.
+const MAX_ID_LEN: usize = 4;
+
+#[derive(Debug)]
+pub struct DltControlForm {
+    ecu_id: String,
+    app_id: String,
+    context_id: String,
+    level: DltLogLevel,
+    with_descriptions: bool,
+}
+
+impl DltControlForm {
+    pub fn new() -> Self {
+        Self {
+            ecu_id: String::from("ECU1"),
+            app_id: String::new(),
+            context_id: String::new(),
+            level: DltLogLevel::Info,
+            with_descriptions: false,
+        }
+    }
+
+    /// Renders the form and returns the request of the clicked action.
+    pub fn render(&mut self, ui: &mut Ui) -> Option<DltControlRequest> {
+        let mut command = None;
+
+        Grid::new("sde_dlt_ids")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                for (label, id, hint) in [
+                    ("ECU ID:", &mut self.ecu_id, "ECU1"),
+                    ("App ID:", &mut self.app_id, "All"),
+                    ("Context ID:", &mut self.context_id, "All"),
+                ] {
+                    ui.label(label);
+                    TextEdit::singleline(id)
+                        .desired_width(80.0)
+                        .char_limit(MAX_ID_LEN)
+                        .hint_text(hint)
+                        .show(ui);
+                    ui.end_row();
+                }
+            });
+
+        let error = self.validation_error();
+        if let Some(error) = error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        ui.separator();
+
+        ui.add_enabled_ui(error.is_none(), |ui| {
+            ui.horizontal(|ui| {
+                ComboBox::from_id_salt("sde_dlt_level")
+                    .selected_text(self.level.to_string())
+                    .show_ui(ui, |ui| {
+                        for level in DltLogLevel::ALL {
+                            ui.selectable_value(&mut self.level, level, level.to_string());
+                        }
+                    });
+                if ui.button("Set Log Level").clicked() {
+                    command = Some(DltControlCommand::SetLogLevel {
+                        app_id: self.app_id.trim().to_owned(),
+                        context_id: self.context_id.trim().to_owned(),
+                        level: self.level,
+                    });
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.with_descriptions, "Descriptions");
+                if ui.button("Get Log Info").clicked() {
+                    command = Some(DltControlCommand::GetLogInfo {
+                        app_id: self.app_id.trim().to_owned(),
+                        context_id: self.context_id.trim().to_owned(),
+                        with_descriptions: self.with_descriptions,
+                    });
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Default trace status:");
+                if ui.button("On").clicked() {
+                    command = Some(DltControlCommand::SetDefaultTraceStatus(true));
+                }
+                if ui.button("Off").clicked() {
+                    command = Some(DltControlCommand::SetDefaultTraceStatus(false));
+                }
+            });
+        });
+
+        command.map(|command| DltControlRequest {
+            ecu_id: self.ecu_id.trim().to_owned(),
+            command,
+        })
+    }
+
+    fn validation_error(&self) -> Option<&'static str> {
+        let is_valid_id = |id: &str| id.trim().len() <= MAX_ID_LEN && id.is_ascii();
+        if self.ecu_id.trim().is_empty() {
+            Some("ECU ID is required")
+        } else if ![
+            self.ecu_id.as_str(),
+            self.app_id.as_str(),
+            self.context_id.as_str(),
+        ]
+        .into_iter()
+        .all(is_valid_id)
+        {
+            Some("IDs must have up to 4 ASCII characters")
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_ids() {
+        let mut form = DltControlForm::new();
+        assert_eq!(form.validation_error(), None);
+
+        form.app_id = String::from("ÄPP");
+        assert!(form.validation_error().is_some());
+
+        form.app_id = String::from("APP");
+        form.ecu_id = String::from("  ");
+        assert_eq!(form.validation_error(), Some("ECU ID is required"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Encoding of SDE input given as hex bytes or as text with escape sequences,
+//! along with macros and the history of sent inputs.
+
+use crate::{common::fixed_queue::FixedQueue, host::ui::state::SdeMacro};
+
+/// Maximum number of sent inputs kept in the history.
+const HISTORY_LIMIT: usize = 50;
+
+/// Maximum number of arguments of macros (`{1}` to `{9}`).
+const MAX_MACRO_ARGS: usize = 9;
+
+/// Format of the input in the SDE bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputMode {
+    /// Text which is sent with a line ending.
+    #[default]
+    Text,
+    /// Hex bytes which are sent as they are.
+    Hex,
+}
+
+/// Parses hex bytes like `DE AD BE EF`, `0xDE,0xAD` or `DEADBEEF`.
+pub fn parse_hex(input: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for token in input
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ':')
+        .filter(|token| !token.is_empty())
+    {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("'{token}' contains invalid hex digits"));
+        }
+        if digits.is_empty() || digits.len() % 2 != 0 {
+            return Err(format!("'{token}' isn't given as complete hex bytes"));
+        }
+        for pair in digits.as_bytes().chunks(2) {
+            let pair = std::str::from_utf8(pair).unwrap_or_default();
+            bytes.push(u8::from_str_radix(pair, 16).unwrap_or_default());
+        }
+    }
+
+    if bytes.is_empty() {
+        return Err(String::from("No bytes are given"));
+    }
+
+    Ok(bytes)
+}
+
+/// Converts the text with the escape sequences `\r`, `\n`, `\t`, `\0`, `\\` and `\xNN`
+/// into bytes.
+pub fn unescape(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        match chars.next() {
+            Some('r') => bytes.push(b'\r'),
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('0') => bytes.push(0),
+            Some('\\') => bytes.push(b'\\'),
+            Some('x') => {
+                let digits: String = chars.by_ref().take(2).collect();
+                let byte = (digits.len() == 2 && digits.chars().all(|c| c.is_ascii_hexdigit()))
+                    .then(|| u8::from_str_radix(&digits, 16).unwrap_or_default())
+                    .ok_or_else(|| format!("Invalid escape sequence '\\x{digits}'"))?;
+                bytes.push(byte);
+            }
+            Some(other) => return Err(format!("Unknown escape sequence '\\{other}'")),
+            None => return Err(String::from("Text can't end with a single '\\'")),
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Replaces the placeholders `{1}` to `{9}` in the content with the whitespace
+/// separated arguments.
+pub fn expand_placeholders(content: &str, args: &str) -> Result<String, String> {
+    let args: Vec<_> = args.split_whitespace().collect();
+    let mut expanded = content.to_owned();
+    for idx in 1..=MAX_MACRO_ARGS {
+        let placeholder = format!("{{{idx}}}");
+        if !expanded.contains(&placeholder) {
+            continue;
+        }
+        let arg = args
+            .get(idx - 1)
+            .ok_or_else(|| format!("Argument {idx} is missing in the input"))?;
+        expanded = expanded.replace(&placeholder, arg);
+    }
+
+    Ok(expanded)
+}
+
+/// Returns the bytes of the macro with the arguments given in the input.
+pub fn encode_macro(sde_macro: &SdeMacro, args: &str) -> Result<Vec<u8>, String> {
+    let content = expand_placeholders(&sde_macro.content, args)?;
+    if sde_macro.hex {
+        parse_hex(&content)
+    } else {
+        unescape(&content)
+    }
+}
+
+/// An input sent from the SDE bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub text: String,
+    pub mode: InputMode,
+}
+
+/// History of the inputs sent in a session, which can be browsed with the arrow keys.
+#[derive(Debug)]
+pub struct InputHistory {
+    entries: FixedQueue<HistoryEntry>,
+    /// Position of the shown entry while browsing, starting from the most recent one.
+    position: Option<usize>,
+}
+
+impl InputHistory {
+    pub fn new() -> Self {
+        Self {
+            entries: FixedQueue::new(HISTORY_LIMIT),
+            position: None,
+        }
+    }
+
+    /// Adds the entry unless it matches the most recent one, and stops browsing.
+    pub fn add(&mut self, entry: HistoryEntry) {
+        self.position = None;
+        if self.entries.all_items().next() != Some(&entry) {
+            self.entries.add_item(entry);
+        }
+    }
+
+    /// Returns the entries from the most recent to the oldest.
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.all_items()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_browsing(&self) -> bool {
+        self.position.is_some()
+    }
+
+    pub fn stop_browsing(&mut self) {
+        self.position = None;
+    }
+
+    /// Moves to the next older entry and returns it, staying on the oldest entry.
+    pub fn older(&mut self) -> Option<&HistoryEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let position = self
+            .position
+            .map_or(0, |pos| (pos + 1).min(self.entries.len() - 1));
+        self.position = Some(position);
+        self.entries.all_items().nth(position)
+    }
+
+    /// Moves to the next newer entry and returns it. Returns `None` once browsing
+    /// moves past the most recent entry.
+    pub fn newer(&mut self) -> Option<&HistoryEntry> {
+        let position = self.position?.checked_sub(1);
+        self.position = position;
+        self.entries.all_items().nth(position?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::host::common::sources::StreamNames;
+
+    use super::*;
+
+    fn entry(text: &str) -> HistoryEntry {
+        HistoryEntry {
+            text: text.to_owned(),
+            mode: InputMode::Text,
+        }
+    }
+
+    #[test]
+    fn parse_hex_input() {
+        assert_eq!(parse_hex("DE AD be ef"), Ok(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+        assert_eq!(parse_hex("0x01,0x02:03"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_hex(" 0A0B0C "), Ok(vec![0x0A, 0x0B, 0x0C]));
+
+        assert!(parse_hex("").is_err());
+        assert!(parse_hex("ABC").is_err());
+        assert!(parse_hex("0x").is_err());
+        assert!(parse_hex("GG").is_err());
+        assert!(parse_hex("ÄÄ").is_err());
+        assert!(parse_hex("+1").is_err());
+    }
+
+    #[test]
+    fn unescape_text() {
+        assert_eq!(unescape(r"AT\r\n"), Ok(b"AT\r\n".to_vec()));
+        assert_eq!(unescape(r"a\tb\\c\0\x7F"), Ok(b"a\tb\\c\0\x7F".to_vec()));
+        assert_eq!(unescape("ü"), Ok("ü".as_bytes().to_vec()));
+
+        assert!(unescape(r"\q").is_err());
+        assert!(unescape(r"\x7").is_err());
+        assert!(unescape(r"\xZZ").is_err());
+        assert!(unescape("end\\").is_err());
+    }
+
+    #[test]
+    fn expand_macros() {
+        let text_macro = SdeMacro {
+            profile: StreamNames::Serial,
+            name: String::from("Set"),
+            content: String::from(r"set {1}={2}\r\n"),
+            hex: false,
+        };
+        assert_eq!(
+            encode_macro(&text_macro, "level  debug"),
+            Ok(b"set level=debug\r\n".to_vec())
+        );
+        assert!(encode_macro(&text_macro, "level").is_err());
+
+        let hex_macro = SdeMacro {
+            profile: StreamNames::Tcp,
+            name: String::from("Read"),
+            content: String::from("22 {1} {1}"),
+            hex: true,
+        };
+        assert_eq!(
+            encode_macro(&hex_macro, "F1 90"),
+            Ok(vec![0x22, 0xF1, 0xF1])
+        );
+        assert_eq!(
+            expand_placeholders("no args", ""),
+            Ok(String::from("no args"))
+        );
+    }
+
+    #[test]
+    fn browse_history() {
+        let mut history = InputHistory::new();
+        assert_eq!(history.older(), None);
+
+        history.add(entry("first"));
+        history.add(entry("second"));
+        history.add(entry("second"));
+        assert_eq!(history.entries().count(), 2);
+
+        assert_eq!(history.older(), Some(&entry("second")));
+        assert_eq!(history.older(), Some(&entry("first")));
+        assert_eq!(history.older(), Some(&entry("first")));
+        assert_eq!(history.newer(), Some(&entry("second")));
+        assert_eq!(history.newer(), None);
+        assert!(!history.is_browsing());
+        assert_eq!(history.newer(), None);
+
+        history.older();
+        history.add(entry("third"));
+        assert!(!history.is_browsing());
+        assert_eq!(history.older(), Some(&entry("third")));
+    }
+}
@@ -0,0 +1,1003 @@
+//! Source Data Exchange input bar for live observed sources.
+
+use egui::{
+    Align, AtomExt, Button, Frame, Id, Key, Layout, Margin, Modifiers, Popup, PopupCloseBehavior,
+    RectAlign, RichText, ScrollArea, Stroke, TextEdit, Ui, vec2,
+};
+use egui_extras::{Size, StripBuilder};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use stypes::{ObserveOrigin, Transport};
+
+use crate::{
+    common::phosphor::icons,
+    host::{
+        common::{parsers::ParserNames, sources::StreamNames},
+        notification::AppNotification,
+        ui::{UiActions, state::SdeMacro},
+    },
+    session::{command::SessionCommand, error::SessionError, ui::shared::SessionShared},
+};
+
+use dlt_control::DltControlForm;
+use input::{HistoryEntry, InputHistory, InputMode};
+
+mod dlt_control;
+mod input;
+
+/// Width of the buttons opening the history, macros and DLT control popups.
+const TOOL_BUTTON_WIDTH: f32 = 24.0;
+
+/// Stateful UI for sending text into SDE-capable live sources.
+#[derive(Debug)]
+pub struct SdeBarUi {
+    cmd_tx: Sender<SessionCommand>,
+    targets: Vec<SdeTarget>,
+    text: String,
+    selected_target: Option<Uuid>,
+    pending: bool,
+    mode: InputMode,
+    /// Whether the pending send is the content of the input, which is cleared once sent.
+    sending_input: bool,
+    history: InputHistory,
+    /// Name for saving the current input as macro.
+    macro_name: String,
+    dlt_control: DltControlForm,
+}
+
+#[derive(Debug)]
+struct SdeTarget {
+    id: Uuid,
+    icon: &'static str,
+    label: String,
+    /// Transport profile to which the macros of the target belong.
+    profile: StreamNames,
+    /// Whether DLT control messages can be sent to the ECU behind the target.
+    dlt_control: bool,
+}
+
+impl SdeBarUi {
+    /// Creates an SDE bar with targets from the current session state.
+    pub fn new(cmd_tx: Sender<SessionCommand>, shared: &SessionShared) -> Self {
+        let mut bar = Self {
+            cmd_tx,
+            targets: Vec::new(),
+            text: String::default(),
+            selected_target: None,
+            pending: false,
+            mode: InputMode::default(),
+            sending_input: false,
+            history: InputHistory::new(),
+            macro_name: String::new(),
+            dlt_control: DltControlForm::new(),
+        };
+        bar.refresh_targets(shared);
+        bar
+    }
+
+    /// Rebuilds the cached SDE targets from current observe operations.
+    pub fn refresh_targets(&mut self, shared: &SessionShared) {
+        self.targets = collect_eligible_targets(shared);
+        if !self.pending {
+            self.update_local_state();
+        }
+    }
+
+    /// Returns whether the current session state can show stream input controls.
+    pub fn is_available(&self) -> bool {
+        self.pending || !self.targets.is_empty()
+    }
+
+    /// Renders the SDE controls and dispatches send commands.
+    pub fn render_content(
+        &mut self,
+        actions: &mut UiActions,
+        macros: &mut Vec<SdeMacro>,
+        ui: &mut Ui,
+    ) {
+        let text_id = ui.id().with("sde_text");
+        let enter_pressed = !self.pending && Self::text_enter_pressed(ui, text_id);
+        let mut send_requested = enter_pressed;
+        if !self.pending {
+            self.handle_history_keys(ui, text_id);
+        }
+
+        let dlt_control = self
+            .selected_target()
+            .is_some_and(|target| target.dlt_control);
+        let tools_count = if dlt_control { 3. } else { 2. };
+
+        Frame::new()
+            .inner_margin(Margin::same(2))
+            .corner_radius(ui.visuals().widgets.inactive.corner_radius)
+            .fill(ui.visuals().extreme_bg_color)
+            .stroke(Self::control_stroke(ui, text_id))
+            .show(ui, |ui| {
+                ui.set_max_height(20.0);
+                ui.add_enabled_ui(!self.pending, |ui| {
+                    StripBuilder::new(ui)
+                        .cell_layout(Layout::left_to_right(Align::Center))
+                        .size(Size::relative(0.1).at_least(50.0).at_most(100.0))
+                        .size(Size::exact(22.0))
+                        .size(Size::remainder().at_least(80.0))
+                        .size(Size::exact(TOOL_BUTTON_WIDTH * tools_count))
+                        .size(Size::exact(62.0))
+                        .horizontal(|mut strip| {
+                            strip.cell(|ui| self.render_target_picker(ui));
+
+                            strip.cell(|ui| self.render_mode_toggle(ui));
+
+                            strip.cell(|ui| {
+                                let hint = match self.mode {
+                                    InputMode::Text => "Text to send",
+                                    InputMode::Hex => "Hex bytes to send",
+                                };
+                                let text_res = TextEdit::singleline(&mut self.text)
+                                    .id(text_id)
+                                    .frame(Frame::NONE)
+                                    .hint_text(hint)
+                                    .desired_width(ui.available_width())
+                                    .vertical_align(Align::Center)
+                                    .show(ui)
+                                    .response;
+                                if text_res.changed() {
+                                    self.history.stop_browsing();
+                                }
+                            });
+
+                            strip.cell(|ui| {
+                                self.render_history(ui);
+                                self.render_macros(macros, actions, ui);
+                                if dlt_control {
+                                    self.render_dlt_control(actions, ui);
+                                }
+                            });
+
+                            strip.cell(|ui| {
+                                let label = if self.pending { "Sending..." } else { "Send" };
+                                let mut send_res = ui.add_enabled(
+                                    self.can_send(),
+                                    Button::new(label).min_size(ui.available_size()),
+                                );
+                                if let Some(Err(err)) = self.input_bytes() {
+                                    send_res = send_res.on_disabled_hover_text(err);
+                                }
+                                send_requested |= send_res.clicked();
+                            });
+                        });
+                });
+            });
+
+        if self.can_send() && send_requested {
+            self.send(actions);
+        }
+    }
+
+    /// Applies a send result and reports failures through app notifications.
+    pub fn handle_result(&mut self, result: Result<(), SessionError>, actions: &mut UiActions) {
+        match result {
+            Ok(()) if self.sending_input => self.text.clear(),
+            Ok(()) => {}
+            Err(error) => actions.add_notification(AppNotification::SessionError(error)),
+        }
+
+        self.pending = false;
+        self.sending_input = false;
+        self.update_local_state();
+    }
+
+    /// Updates local bar state from the cached target list.
+    ///
+    /// Keeps the selected target valid while targets exist. Clears input,
+    /// selection, and pending state when no target is available.
+    fn update_local_state(&mut self) {
+        if self.targets.is_empty() {
+            self.clear();
+            return;
+        }
+
+        let selected_available = self
+            .selected_target
+            .is_some_and(|selected| self.targets.iter().any(|target| target.id == selected));
+
+        if !selected_available {
+            self.selected_target = self.targets.first().map(|target| target.id);
+        }
+    }
+
+    fn selected_target(&self) -> Option<&SdeTarget> {
+        let selected = self.selected_target?;
+
+        self.targets.iter().find(|target| target.id == selected)
+    }
+
+    fn render_target_picker(&mut self, ui: &mut Ui) {
+        // Keep the icon box fixed so the larger glyph does not make rows taller.
+        let target_atoms = |target: &SdeTarget| {
+            (
+                RichText::new(target.icon)
+                    .size(14.0)
+                    .atom_size(vec2(14.0, 15.0)),
+                target.label.as_str().atom_shrink(true),
+            )
+        };
+
+        let selected_target = self.selected_target();
+        let hover_label = selected_target.map(|target| target.label.as_str());
+        let button = match selected_target {
+            Some(target) => Button::new(target_atoms(target)),
+            None => Button::new("Select target"),
+        }
+        .frame_when_inactive(false)
+        .truncate();
+
+        let mut button_response = ui.add_sized(ui.available_size(), button);
+        if let Some(label) = hover_label {
+            button_response = button_response.on_hover_ui(|ui| {
+                ui.set_max_width(ui.spacing().tooltip_width);
+                ui.label(label);
+            });
+        }
+
+        let mut selected_target = self.selected_target;
+        Popup::menu(&button_response)
+            .id(ui.id().with("sde_targets"))
+            .align(RectAlign::BOTTOM_START)
+            .show(|ui| {
+                for target in &self.targets {
+                    let button = Button::selectable(
+                        selected_target == Some(target.id),
+                        target_atoms(target),
+                    );
+                    if ui.add(button).clicked() {
+                        selected_target = Some(target.id);
+                        ui.close();
+                    }
+                }
+            });
+        self.selected_target = selected_target;
+    }
+
+    fn render_mode_toggle(&mut self, ui: &mut Ui) {
+        let (label, hint) = match self.mode {
+            InputMode::Text => (
+                ">>",
+                "Text input sent with a line ending. Click for hex input",
+            ),
+            InputMode::Hex => ("0x", "Hex input sent as raw bytes. Click for text input"),
+        };
+        let toggle_res = ui
+            .add(Button::new(RichText::new(label).monospace().strong()).frame_when_inactive(false))
+            .on_hover_text(hint);
+        if toggle_res.clicked() {
+            self.mode = match self.mode {
+                InputMode::Text => InputMode::Hex,
+                InputMode::Hex => InputMode::Text,
+            };
+        }
+    }
+
+    fn render_history(&mut self, ui: &mut Ui) {
+        let history_res = ui
+            .add_enabled(
+                !self.history.is_empty(),
+                Self::tool_button(icons::regular::CLOCK_COUNTER_CLOCKWISE),
+            )
+            .on_hover_text("History (Up/Down in the input)");
+
+        let mut selected = None;
+        Popup::menu(&history_res)
+            .id(ui.id().with("sde_history"))
+            .align(RectAlign::TOP_END)
+            .show(|ui| {
+                ui.set_max_width(320.0);
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for entry in self.history.entries() {
+                        let prefix = match entry.mode {
+                            InputMode::Text => ">>",
+                            InputMode::Hex => "0x",
+                        };
+                        let label = RichText::new(format!("{prefix} {}", entry.text)).monospace();
+                        if ui.add(Button::new(label).truncate()).clicked() {
+                            selected = Some(entry.clone());
+                        }
+                    }
+                });
+            });
+
+        if let Some(entry) = selected {
+            self.history.stop_browsing();
+            self.apply_history_entry(entry);
+        }
+    }
+
+    fn render_macros(&mut self, macros: &mut Vec<SdeMacro>, actions: &mut UiActions, ui: &mut Ui) {
+        let Some(profile) = self.selected_target().map(|target| target.profile) else {
+            return;
+        };
+
+        let macros_res = ui
+            .add(Self::tool_button(icons::regular::LIGHTNING))
+            .on_hover_text(format!("{profile} Macros"));
+
+        let mut send_macro = None;
+        Popup::menu(&macros_res)
+            .id(ui.id().with("sde_macros"))
+            .align(RectAlign::TOP_END)
+            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+            .show(|ui| {
+                ui.set_max_width(320.0);
+                ui.label(RichText::new(format!("{profile} Macros")).strong());
+                ui.label(
+                    RichText::new("Placeholders {1} to {9} are filled with the words of the input")
+                        .small()
+                        .weak(),
+                );
+                ui.separator();
+
+                let mut remove = None;
+                for (idx, sde_macro) in macros.iter().enumerate() {
+                    if sde_macro.profile != profile {
+                        continue;
+                    }
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button(icons::regular::TRASH)
+                            .on_hover_text("Remove macro")
+                            .clicked()
+                        {
+                            remove = Some(idx);
+                        }
+                        let send_res = ui
+                            .add_enabled(
+                                !self.pending,
+                                Button::new(sde_macro.name.as_str()).truncate(),
+                            )
+                            .on_hover_text(sde_macro.content.as_str());
+                        if send_res.clicked() {
+                            send_macro = Some(sde_macro.clone());
+                            ui.close();
+                        }
+                    });
+                }
+                if let Some(idx) = remove {
+                    macros.remove(idx);
+                }
+                if !macros.iter().any(|sde_macro| sde_macro.profile == profile) {
+                    ui.label(RichText::new("No macros saved yet").weak());
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    TextEdit::singleline(&mut self.macro_name)
+                        .desired_width(140.0)
+                        .hint_text("Macro name")
+                        .show(ui);
+                    let can_save =
+                        !self.macro_name.trim().is_empty() && !self.text.trim().is_empty();
+                    if ui
+                        .add_enabled(can_save, Button::new("Save Input"))
+                        .on_hover_text("Save the current input as macro")
+                        .clicked()
+                    {
+                        macros.push(SdeMacro {
+                            profile,
+                            name: self.macro_name.trim().to_owned(),
+                            content: self.text.clone(),
+                            hex: self.mode == InputMode::Hex,
+                        });
+                        self.macro_name.clear();
+                        self.text.clear();
+                    }
+                });
+            });
+
+        if let Some(sde_macro) = send_macro {
+            self.send_macro(&sde_macro, actions);
+        }
+    }
+
+    fn render_dlt_control(&mut self, actions: &mut UiActions, ui: &mut Ui) {
+        let dlt_res = ui
+            .add(Self::tool_button(icons::regular::SLIDERS_HORIZONTAL))
+            .on_hover_text("DLT Control Messages");
+
+        let mut request = None;
+        Popup::menu(&dlt_res)
+            .id(ui.id().with("sde_dlt_control"))
+            .align(RectAlign::TOP_END)
+            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+            .show(|ui| {
+                ui.label(RichText::new("DLT Control Messages").strong());
+                ui.add_enabled_ui(!self.pending, |ui| {
+                    request = self.dlt_control.render(ui);
+                });
+            });
+
+        if let (Some(request), Some(target)) = (request, self.selected_target) {
+            let command = SessionCommand::SendSdeDltControl { target, request };
+            self.dispatch(command, false, actions);
+        }
+    }
+
+    fn tool_button(icon: &'static str) -> Button<'static> {
+        Button::new(RichText::new(icon).size(14.0))
+            .frame_when_inactive(false)
+            .min_size(vec2(TOOL_BUTTON_WIDTH - 4.0, 0.0))
+    }
+
+    /// Browses the history with the arrow keys while the input is focused.
+    fn handle_history_keys(&mut self, ui: &mut Ui, text_id: Id) {
+        let text_focused = ui.memory(|memory| memory.focused().is_some_and(|id| id == text_id));
+        if !text_focused {
+            return;
+        }
+
+        if ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowUp)) {
+            if let Some(entry) = self.history.older().cloned() {
+                self.apply_history_entry(entry);
+            }
+        } else if self.history.is_browsing()
+            && ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowDown))
+        {
+            match self.history.newer().cloned() {
+                Some(entry) => self.apply_history_entry(entry),
+                None => self.text.clear(),
+            }
+        }
+    }
+
+    fn apply_history_entry(&mut self, entry: HistoryEntry) {
+        let HistoryEntry { text, mode } = entry;
+        self.text = text;
+        self.mode = mode;
+    }
+
+    /// Returns the bytes of hex input, or `None` for text input.
+    fn input_bytes(&self) -> Option<Result<Vec<u8>, String>> {
+        match self.mode {
+            InputMode::Text => None,
+            InputMode::Hex => Some(input::parse_hex(&self.text)),
+        }
+    }
+
+    fn can_send(&self) -> bool {
+        !self.pending
+            && self.selected_target.is_some()
+            && !self.text.is_empty()
+            && !matches!(self.input_bytes(), Some(Err(_)))
+    }
+
+    fn send(&mut self, actions: &mut UiActions) {
+        let Some(target) = self.selected_target else {
+            return;
+        };
+
+        let command = match self.input_bytes() {
+            None => SessionCommand::SendSdeText {
+                target,
+                text: self.text.clone(),
+            },
+            Some(Ok(bytes)) => SessionCommand::SendSdeBytes { target, bytes },
+            Some(Err(err)) => {
+                actions.add_notification(AppNotification::Error(err));
+                return;
+            }
+        };
+
+        if self.dispatch(command, true, actions) {
+            self.history.add(HistoryEntry {
+                text: self.text.clone(),
+                mode: self.mode,
+            });
+        }
+    }
+
+    /// Sends the macro with the arguments given in the input, which is kept after sending.
+    fn send_macro(&mut self, sde_macro: &SdeMacro, actions: &mut UiActions) {
+        let Some(target) = self.selected_target else {
+            return;
+        };
+
+        match input::encode_macro(sde_macro, &self.text) {
+            Ok(bytes) => {
+                let command = SessionCommand::SendSdeBytes { target, bytes };
+                self.dispatch(command, false, actions);
+            }
+            Err(err) => actions.add_notification(AppNotification::Error(format!(
+                "Macro '{}' can't be sent: {err}",
+                sde_macro.name
+            ))),
+        }
+    }
+
+    /// Sends the command, marking the bar as pending until its result arrives.
+    fn dispatch(
+        &mut self,
+        command: SessionCommand,
+        sending_input: bool,
+        actions: &mut UiActions,
+    ) -> bool {
+        let sent = actions.try_send_command(&self.cmd_tx, command);
+        if sent {
+            self.pending = true;
+            self.sending_input = sending_input;
+        }
+        sent
+    }
+
+    fn clear(&mut self) {
+        let Self {
+            cmd_tx: _,
+            targets,
+            text,
+            selected_target,
+            pending,
+            mode: _,
+            sending_input,
+            history,
+            macro_name: _,
+            dlt_control: _,
+        } = self;
+
+        targets.clear();
+        text.clear();
+        *selected_target = None;
+        *pending = false;
+        *sending_input = false;
+        history.stop_browsing();
+    }
+
+    fn text_enter_pressed(ui: &mut Ui, text_id: Id) -> bool {
+        let text_focused = ui.memory(|memory| memory.focused().is_some_and(|id| id == text_id));
+
+        text_focused && ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Enter))
+    }
+
+    fn control_stroke(ui: &Ui, text_id: Id) -> Stroke {
+        let noninteractive = &ui.visuals().widgets.noninteractive;
+        if ui.memory(|memory| memory.focused().is_some_and(|id| id == text_id)) {
+            Stroke::new(
+                noninteractive.bg_stroke.width,
+                ui.visuals().text_cursor.stroke.color,
+            )
+        } else {
+            noninteractive.bg_stroke
+        }
+    }
+}
+
+fn collect_eligible_targets(shared: &SessionShared) -> Vec<SdeTarget> {
+    // DLT control messages are sent to the ECU behind TCP connections of DLT sessions.
+    let dlt_control = shared.get_info().parser == ParserNames::Dlt;
+
+    shared
+        .observe
+        .operations()
+        .iter()
+        .filter(|operation| operation.processing())
+        .filter_map(|operation| match &operation.origin {
+            ObserveOrigin::Stream(_, Transport::TCP(config)) => {
+                let icon = icons::regular::PLUGS_CONNECTED;
+                let label = config.bind_addr.clone();
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::Tcp,
+                    dlt_control,
+                };
+                Some(target)
+            }
+            ObserveOrigin::Stream(_, Transport::Process(config)) => {
+                let icon = icons::regular::TERMINAL_WINDOW;
+                let label = config.command.clone();
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::Process,
+                    dlt_control: false,
+                };
+                Some(target)
+            }
+            ObserveOrigin::Stream(_, Transport::Serial(config)) => {
+                let icon = icons::regular::USB;
+                let label = config.path.clone();
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::Serial,
+                    dlt_control: false,
+                };
+                Some(target)
+            }
+            ObserveOrigin::Stream(_, Transport::UnixSocket(config)) => {
+                let icon = icons::regular::PLUGS_CONNECTED;
+                let label = config.path.clone();
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::UnixSocket,
+                    dlt_control: false,
+                };
+                Some(target)
+            }
+            ObserveOrigin::Stream(_, Transport::Mqtt(config)) => {
+                // Only subscriptions with a publish topic accept data.
+                let topic = config.sde_topic.as_ref()?;
+                let icon = icons::regular::CLOUD;
+                let label = format!("{} ({topic})", config.broker_url);
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::Mqtt,
+                    dlt_control: false,
+                };
+                Some(target)
+            }
+            ObserveOrigin::Stream(_, Transport::WebSocket(config)) => {
+                let icon = icons::regular::GLOBE;
+                let label = config.url.clone();
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::WebSocket,
+                    dlt_control: false,
+                };
+                Some(target)
+            }
+            ObserveOrigin::Stream(_, Transport::SocketCan(config)) => {
+                let icon = icons::regular::CPU;
+                // Frames are sent in the syntax of `cansend`.
+                let label = format!("{} (ID#DATA)", config.interface);
+                let target = SdeTarget {
+                    id: operation.id,
+                    icon,
+                    label,
+                    profile: StreamNames::SocketCan,
+                    dlt_control: false,
+                };
+                Some(target)
+            }
+            ObserveOrigin::File(..)
+            | ObserveOrigin::Concat(..)
+            | ObserveOrigin::DirWatch(..)
+            | ObserveOrigin::Plugin(..)
+            // Replays are controlled from their side panel instead.
+            | ObserveOrigin::Stream(
+                _,
+                Transport::UDP(..)
+                | Transport::Replay(..)
+                | Transport::HttpStream(..),
+            ) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tokio::{
+        runtime::{Builder, Runtime},
+        sync::mpsc,
+    };
+
+    use stypes::{
+        ComputationError, FileFormat, ProcessOutputMode, ProcessRestartPolicy,
+        ProcessTransportConfig, SerialReadMode, SerialReconnect, SerialTransportConfig,
+        TCPTransportConfig, TcpMode, UDPTransportConfig, UnixSocketMode, UnixSocketTransportConfig,
+    };
+
+    use super::*;
+    use crate::{
+        host::ui::UiActions,
+        session::{
+            types::{ObserveOperation, OperationPhase},
+            ui::{SessionInfo, definitions::schema::LogSchemaSpec},
+        },
+    };
+
+    fn command_sender() -> Sender<SessionCommand> {
+        let (cmd_tx, _cmd_rx) = mpsc::channel(1);
+        cmd_tx
+    }
+
+    fn ui_actions() -> (Runtime, UiActions) {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime should be created");
+        let actions = UiActions::new(runtime.handle().clone());
+
+        (runtime, actions)
+    }
+
+    fn sde_error() -> SessionError {
+        ComputationError::Sde(String::new()).into()
+    }
+
+    fn shared_with(operation: ObserveOperation) -> SessionShared {
+        shared_with_parser(operation, ParserNames::Text)
+    }
+
+    fn shared_with_parser(operation: ObserveOperation, parser: ParserNames) -> SessionShared {
+        let session_info = SessionInfo {
+            id: Uuid::new_v4(),
+            title: String::from("test"),
+            parser,
+            raw_export_supported: false,
+        };
+
+        SessionShared::new(session_info, operation, LogSchemaSpec::Text)
+    }
+
+    fn add_operation(shared: &mut SessionShared, operation: ObserveOperation) {
+        shared.add_operation(operation);
+    }
+
+    fn processing(mut operation: ObserveOperation) -> ObserveOperation {
+        operation.set_phase(OperationPhase::Processing);
+        operation
+    }
+
+    fn process_operation(command: &str) -> ObserveOperation {
+        let config = ProcessTransportConfig {
+            cwd: PathBuf::from("."),
+            command: command.to_owned(),
+            shell: None,
+            envs: Vec::new(),
+            output: ProcessOutputMode::default(),
+            pty: false,
+            restart: ProcessRestartPolicy::default(),
+        };
+        let source_id = Uuid::new_v4().to_string();
+        let transport = Transport::Process(config);
+        let origin = ObserveOrigin::Stream(source_id, transport);
+
+        ObserveOperation::new(Uuid::new_v4(), origin)
+    }
+
+    fn serial_operation(path: &str) -> ObserveOperation {
+        let config = SerialTransportConfig {
+            path: path.to_owned(),
+            baud_rate: 115_200,
+            data_bits: 8,
+            flow_control: 0,
+            parity: 0,
+            stop_bits: 1,
+            send_data_delay: 0,
+            exclusive: false,
+            read_mode: SerialReadMode::Lines,
+            reconnect: SerialReconnect::Disabled,
+        };
+        let source_id = Uuid::new_v4().to_string();
+        let transport = Transport::Serial(config);
+        let origin = ObserveOrigin::Stream(source_id, transport);
+
+        ObserveOperation::new(Uuid::new_v4(), origin)
+    }
+
+    fn tcp_operation() -> ObserveOperation {
+        let config = TCPTransportConfig {
+            bind_addr: String::from("127.0.0.1:9000"),
+            mode: TcpMode::Connect,
+        };
+        let source_id = Uuid::new_v4().to_string();
+        let transport = Transport::TCP(config);
+        let origin = ObserveOrigin::Stream(source_id, transport);
+
+        ObserveOperation::new(Uuid::new_v4(), origin)
+    }
+
+    fn udp_operation() -> ObserveOperation {
+        let config = UDPTransportConfig {
+            bind_addr: String::from("127.0.0.1:9001"),
+            multicast: Vec::new(),
+            allowed_senders: Vec::new(),
+            split_senders: false,
+        };
+        let source_id = Uuid::new_v4().to_string();
+        let transport = Transport::UDP(config);
+        let origin = ObserveOrigin::Stream(source_id, transport);
+
+        ObserveOperation::new(Uuid::new_v4(), origin)
+    }
+
+    fn unix_socket_operation(path: &str) -> ObserveOperation {
+        let config = UnixSocketTransportConfig {
+            path: path.to_owned(),
+            mode: UnixSocketMode::Stream,
+        };
+        let source_id = Uuid::new_v4().to_string();
+        let transport = Transport::UnixSocket(config);
+        let origin = ObserveOrigin::Stream(source_id, transport);
+
+        ObserveOperation::new(Uuid::new_v4(), origin)
+    }
+
+    fn file_operation() -> ObserveOperation {
+        let source_id = String::from("file");
+        let path = PathBuf::from("test.log");
+        let origin = ObserveOrigin::File(source_id, FileFormat::Text, path);
+
+        ObserveOperation::new(Uuid::new_v4(), origin)
+    }
+
+    #[test]
+    fn eligible_targets_are_processing_streams_accepting_data() {
+        let process_source = process_operation("cat");
+        let process = processing(process_source);
+        let process_id = process.id;
+        let serial_source = serial_operation("/dev/ttyUSB0");
+        let serial = processing(serial_source);
+        let serial_id = serial.id;
+        let socket_source = unix_socket_operation("/run/app/log.sock");
+        let socket = processing(socket_source);
+        let socket_id = socket.id;
+
+        let tcp_source = tcp_operation();
+        let tcp = processing(tcp_source);
+        let tcp_id = tcp.id;
+        let mut shared = shared_with(tcp);
+        let udp_source = udp_operation();
+        let udp = processing(udp_source);
+        add_operation(&mut shared, udp);
+        let file = file_operation();
+        add_operation(&mut shared, file);
+        let initializing_process = process_operation("initializing");
+        add_operation(&mut shared, initializing_process);
+        add_operation(&mut shared, process);
+        add_operation(&mut shared, serial);
+        add_operation(&mut shared, socket);
+
+        let targets = collect_eligible_targets(&shared);
+        let target_ids: Vec<_> = targets.iter().map(|target| target.id).collect();
+
+        assert_eq!(target_ids, vec![tcp_id, process_id, serial_id, socket_id]);
+        assert!(targets.iter().all(|target| !target.dlt_control));
+    }
+
+    #[test]
+    fn dlt_control_is_available_for_tcp_targets_of_dlt_sessions() {
+        let tcp_source = tcp_operation();
+        let tcp = processing(tcp_source);
+        let mut shared = shared_with_parser(tcp, ParserNames::Dlt);
+        let serial_source = serial_operation("/dev/ttyUSB0");
+        add_operation(&mut shared, processing(serial_source));
+
+        let targets = collect_eligible_targets(&shared);
+        let dlt_control: Vec<_> = targets.iter().map(|target| target.dlt_control).collect();
+
+        assert_eq!(dlt_control, vec![true, false]);
+        assert_eq!(targets[0].profile, StreamNames::Tcp);
+    }
+
+    #[test]
+    fn macro_result_keeps_input_for_next_send() {
+        let source = process_operation("cat");
+        let operation = processing(source);
+        let shared = shared_with(operation);
+        let mut bar = SdeBarUi::new(command_sender(), &shared);
+        let (_runtime, mut actions) = ui_actions();
+
+        bar.text = String::from("arg");
+        bar.pending = true;
+        bar.handle_result(Ok(()), &mut actions);
+        assert_eq!(bar.text, "arg");
+
+        bar.pending = true;
+        bar.sending_input = true;
+        bar.handle_result(Ok(()), &mut actions);
+        assert!(bar.text.is_empty());
+        assert!(!bar.pending);
+    }
+
+    #[test]
+    fn refresh_selects_first_target_and_reselects_when_it_stops() {
+        let first_source = process_operation("first");
+        let first = processing(first_source);
+        let first_id = first.id;
+        let second_source = process_operation("second");
+        let second = processing(second_source);
+        let second_id = second.id;
+
+        let mut shared = shared_with(first);
+        add_operation(&mut shared, second);
+        let mut bar = SdeBarUi::new(command_sender(), &shared);
+
+        assert!(bar.is_available());
+        assert_eq!(bar.selected_target, Some(first_id));
+
+        shared
+            .observe
+            .update_operation(first_id, OperationPhase::Success);
+        assert_eq!(bar.selected_target, Some(first_id));
+
+        bar.refresh_targets(&shared);
+        assert!(bar.is_available());
+        assert_eq!(bar.selected_target, Some(second_id));
+    }
+
+    #[test]
+    fn pending_send_keeps_bar_visible_after_targets_stop() {
+        let source = process_operation("cat");
+        let operation = processing(source);
+        let operation_id = operation.id;
+        let mut shared = shared_with(operation);
+        let mut bar = SdeBarUi::new(command_sender(), &shared);
+        bar.text = String::from("status");
+        bar.pending = true;
+
+        shared
+            .observe
+            .update_operation(operation_id, OperationPhase::Success);
+        bar.refresh_targets(&shared);
+
+        assert!(bar.is_available());
+        assert!(bar.targets.is_empty());
+        assert!(!bar.text.is_empty());
+        assert_eq!(bar.selected_target, Some(operation_id));
+        assert!(bar.pending);
+    }
+
+    #[test]
+    fn bar_clears_only_after_no_targets_and_no_pending_send() {
+        let source = process_operation("cat");
+        let operation = processing(source);
+        let operation_id = operation.id;
+        let mut shared = shared_with(operation);
+        let mut bar = SdeBarUi::new(command_sender(), &shared);
+        bar.text = String::from("status");
+        bar.pending = true;
+
+        shared
+            .observe
+            .update_operation(operation_id, OperationPhase::Success);
+        bar.refresh_targets(&shared);
+        assert!(bar.is_available());
+
+        let (_runtime, mut actions) = ui_actions();
+        bar.handle_result(Err(sde_error()), &mut actions);
+
+        assert!(!bar.is_available());
+        assert!(bar.targets.is_empty());
+        assert!(bar.text.is_empty());
+        assert_eq!(bar.selected_target, None);
+        assert!(!bar.pending);
+    }
+
+    #[test]
+    fn failure_keeps_input_when_another_target_remains_visible() {
+        let stopped_source = process_operation("stopped");
+        let stopped = processing(stopped_source);
+        let stopped_id = stopped.id;
+        let remaining_source = process_operation("remaining");
+        let remaining = processing(remaining_source);
+        let remaining_id = remaining.id;
+
+        let mut shared = shared_with(stopped);
+        add_operation(&mut shared, remaining);
+        let mut bar = SdeBarUi::new(command_sender(), &shared);
+        bar.text = String::from("status");
+        bar.selected_target = Some(stopped_id);
+        bar.pending = true;
+
+        shared
+            .observe
+            .update_operation(stopped_id, OperationPhase::Success);
+        bar.refresh_targets(&shared);
+        let (_runtime, mut actions) = ui_actions();
+        bar.handle_result(Err(sde_error()), &mut actions);
+
+        assert!(bar.is_available());
+        assert!(!bar.text.is_empty());
+        assert_eq!(bar.selected_target, Some(remaining_id));
+    }
+}
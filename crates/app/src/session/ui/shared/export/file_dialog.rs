@@ -118,6 +118,7 @@ impl ExportState {
                 operation_id,
                 destination,
                 target: pending.target,
+                include_duplicates: self.include_duplicates,
             },
         ) {
             self.clear_operation(operation_id);
@@ -153,6 +154,7 @@ impl ExportState {
                 operation_id,
                 destination,
                 target: pending.target,
+                include_duplicates: self.include_duplicates,
                 options: Box::new(pending.options),
             },
         ) {
@@ -194,7 +196,9 @@ pub fn default_raw_file_name(shared: &SessionShared) -> String {
                 CONCAT_FILE_STEM.to_owned()
             }
         }
-        ObserveOrigin::Stream(..) => FALLBACK_FILE_NAME.to_owned(),
+        ObserveOrigin::Stream(..) | ObserveOrigin::DirWatch(..) | ObserveOrigin::Plugin(..) => {
+            FALLBACK_FILE_NAME.to_owned()
+        }
     }
 }
 
@@ -214,7 +218,9 @@ pub fn default_text_file_name(shared: &SessionShared) -> String {
             .map(|file_stem| format!("{file_stem}{SUFFIX}"))
             .unwrap_or_else(|| FALLBACK_FILE_NAME.to_owned()),
         ObserveOrigin::Concat(_) => String::from("concat_export.txt"),
-        ObserveOrigin::Stream(..) => FALLBACK_FILE_NAME.to_owned(),
+        ObserveOrigin::Stream(..) | ObserveOrigin::DirWatch(..) | ObserveOrigin::Plugin(..) => {
+            FALLBACK_FILE_NAME.to_owned()
+        }
     }
 }
 
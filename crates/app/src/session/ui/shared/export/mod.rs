@@ -34,6 +34,8 @@ pub struct ExportState {
     pending_text: Option<PendingTextExport>,
     /// Placeholder text export modal state awaiting user confirmation.
     text_modal: Option<TextExportModalState>,
+    /// Whether exports include rows of duplicates collapsed in the main view.
+    pub include_duplicates: bool,
 }
 
 impl ExportState {
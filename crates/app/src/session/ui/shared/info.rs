@@ -1,5 +1,5 @@
 use session_core::state::is_raw_export_available_for;
-use stypes::{ObserveOptions, ObserveOrigin, Transport};
+use stypes::{DirWatchConfig, ObserveOptions, ObserveOrigin, PluginByteSourceSettings, Transport};
 use uuid::Uuid;
 
 use crate::{host::common::parsers::ParserNames, session::ui::shared::ObserveState};
@@ -25,7 +25,19 @@ impl SessionInfo {
                 Transport::TCP(config) => config.bind_addr.to_owned(),
                 Transport::UDP(config) => config.bind_addr.to_owned(),
                 Transport::Serial(config) => config.path.to_owned(),
+                Transport::UnixSocket(config) => config.path.to_owned(),
+                Transport::Replay(config) => config
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| config.path.to_string_lossy().into_owned()),
+                Transport::Mqtt(config) => config.broker_url.to_owned(),
+                Transport::WebSocket(config) => config.url.to_owned(),
+                Transport::HttpStream(config) => config.url.to_owned(),
+                Transport::SocketCan(config) => config.interface.to_owned(),
             },
+            ObserveOrigin::DirWatch(_, config) => dir_watch_title(config),
+            ObserveOrigin::Plugin(_, settings) => plugin_title(settings),
         };
 
         let parser = ParserNames::from(&options.parser);
@@ -51,16 +63,26 @@ impl SessionInfo {
         let count = state.sources_count();
 
         match &first_op.origin {
-            ObserveOrigin::File(..) if count == 1 => {}
-            ObserveOrigin::File(..) | ObserveOrigin::Concat(..) => self.title = concat_title(count),
+            ObserveOrigin::File(..) | ObserveOrigin::DirWatch(..) if count == 1 => {}
+            ObserveOrigin::File(..) | ObserveOrigin::Concat(..) | ObserveOrigin::DirWatch(..) => {
+                self.title = concat_title(count)
+            }
             ObserveOrigin::Stream(_, transport) => {
                 self.title = match transport {
                     Transport::Process(..) => format!("{count} Terminal Commands"),
                     Transport::TCP(..) => format!("{count} TCP Connections"),
                     Transport::UDP(..) => format!("{count} UDP Connections"),
                     Transport::Serial(..) => format!("{count} Serial Connections"),
+                    Transport::UnixSocket(..) => format!("{count} Unix Socket Connections"),
+                    Transport::Replay(..) => format!("{count} Replayed Traces"),
+                    Transport::Mqtt(..) => format!("{count} MQTT Sources"),
+                    Transport::WebSocket(..) => format!("{count} WebSocket Connections"),
+                    Transport::HttpStream(..) => format!("{count} HTTP Streams"),
+                    Transport::SocketCan(..) => format!("{count} CAN Interfaces"),
                 }
             }
+            ObserveOrigin::Plugin(..) if count == 1 => {}
+            ObserveOrigin::Plugin(..) => self.title = format!("{count} Plugin Sources"),
         }
     }
 }
@@ -68,3 +90,21 @@ impl SessionInfo {
 fn concat_title(files_count: usize) -> String {
     format!("Concating {files_count} files")
 }
+
+/// Plugin binaries share generic names, so the plugin directory names the source.
+fn plugin_title(settings: &PluginByteSourceSettings) -> String {
+    let path = &settings.plugin_path;
+    path.parent()
+        .and_then(|dir| dir.file_name())
+        .or_else(|| path.file_stem())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| String::from("Plugin Source"))
+}
+
+fn dir_watch_title(config: &DirWatchConfig) -> String {
+    config
+        .dir
+        .join(&config.pattern)
+        .to_string_lossy()
+        .to_string()
+}
@@ -1,9 +1,22 @@
+use processor::search::filter::SearchFilter;
 use rustc_hash::FxHashSet;
+use session_core::state::DuplicatesMode;
 
 #[derive(Debug)]
 pub struct LogsState {
     /// Number of logs currently known for this session.
     logs_count: u64,
+    /// Number of logs hidden from the main table by exclusion filters and collapsed
+    /// duplicates.
+    hidden_count: u64,
+    /// Number of logs with indexed timestamps, reported while the timestamps of a linked
+    /// text file are indexed.
+    time_indexed_count: Option<u64>,
+    /// Exclusion filters last sent to the session backend.
+    applied_exclusions: Vec<SearchFilter>,
+    /// How consecutive duplicate logs are compared to collapse them in the main table,
+    /// if they are collapsed.
+    duplicates_mode: Option<DuplicatesMode>,
     /// Digits needed to display the largest zero-based row number.
     row_number_digits: usize,
     /// Pending request for the main logs table to bring a row into view.
@@ -83,6 +96,10 @@ impl Default for LogsState {
     fn default() -> Self {
         Self {
             logs_count: 0,
+            hidden_count: 0,
+            time_indexed_count: None,
+            applied_exclusions: Vec::new(),
+            duplicates_mode: None,
             row_number_digits: 1,
             main_row_focus: None,
             selected_rows: FxHashSet::default(),
@@ -107,6 +124,60 @@ impl LogsState {
             .map_or(1, |digits| digits as usize + 1);
     }
 
+    /// Returns the number of logs hidden from the main table by exclusion filters and
+    /// collapsed duplicates.
+    pub fn hidden_count(&self) -> u64 {
+        self.hidden_count
+    }
+
+    /// Updates the number of logs hidden from the main table.
+    pub fn set_hidden_count(&mut self, hidden_count: u64) {
+        self.hidden_count = hidden_count;
+    }
+
+    /// Returns the number of logs, whose timestamps are still indexed, or `None` when all
+    /// known logs are indexed.
+    pub fn time_indexing_pending(&self) -> Option<u64> {
+        self.time_indexed_count
+            .map(|indexed| self.logs_count.saturating_sub(indexed))
+            .filter(|pending| *pending > 0)
+    }
+
+    /// Updates the number of logs with indexed timestamps.
+    pub fn set_time_indexed_count(&mut self, time_indexed_count: u64) {
+        self.time_indexed_count = Some(time_indexed_count);
+    }
+
+    /// Returns the number of rows shown in the main table.
+    pub fn visible_count(&self) -> u64 {
+        self.logs_count.saturating_sub(self.hidden_count)
+    }
+
+    /// Records the exclusion filters to apply and returns them if they differ from the
+    /// ones last sent to the backend.
+    pub fn sync_exclusions(&mut self, filters: Vec<SearchFilter>) -> Option<Vec<SearchFilter>> {
+        if self.applied_exclusions == filters {
+            return None;
+        }
+        self.applied_exclusions = filters.clone();
+        Some(filters)
+    }
+
+    /// Returns how consecutive duplicate logs are compared, if they are collapsed.
+    pub fn duplicates_mode(&self) -> Option<DuplicatesMode> {
+        self.duplicates_mode
+    }
+
+    /// Records how consecutive duplicate logs are collapsed and returns whether it
+    /// changed.
+    pub fn set_duplicates_mode(&mut self, mode: Option<DuplicatesMode>) -> bool {
+        if self.duplicates_mode == mode {
+            return false;
+        }
+        self.duplicates_mode = mode;
+        true
+    }
+
     /// Returns the digit count needed to display any known zero-based row number.
     pub fn row_number_digits(&self) -> usize {
         self.row_number_digits
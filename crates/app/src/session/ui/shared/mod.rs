@@ -1,6 +1,6 @@
 //! Canonical state shared by session UI components.
 
-use std::rc::Rc;
+use std::{path::PathBuf, rc::Rc};
 
 use egui::Color32;
 
@@ -69,6 +69,9 @@ pub struct SessionShared {
 
     pub schema: Rc<dyn LogSchema>,
 
+    /// Session file holding the session rows, once the backend created or linked it.
+    session_file: Option<PathBuf>,
+
     /// Monotonic change marker for recent-session state updates.
     recent_revision: u64,
 }
@@ -113,6 +116,7 @@ impl SessionShared {
             attachments: AttachmentsState::default(),
             exports: ExportState::default(),
             schema,
+            session_file: None,
             recent_revision: 0,
         }
     }
@@ -127,6 +131,15 @@ impl SessionShared {
         &self.session_info
     }
 
+    #[inline]
+    pub fn session_file(&self) -> Option<&PathBuf> {
+        self.session_file.as_ref()
+    }
+
+    pub fn set_session_file(&mut self, session_file: PathBuf) {
+        self.session_file = Some(session_file);
+    }
+
     pub fn add_operation(&mut self, observe_op: ObserveOperation) {
         self.observe.add_operation(observe_op);
         self.session_info.update_title(&self.observe);
@@ -254,10 +267,11 @@ impl SessionShared {
         let filters = self.filters.effective_filters(registry);
         if filters.is_empty() {
             let operation_id = self.search.processing_search_operation();
-            self.search.clear_compiled_filters();
             self.search.drop_search();
+            let mut commands = vec![SessionCommand::DropSearch { operation_id }];
+            commands.extend(self.sync_exclusions(registry));
             SearchSyncOutcome {
-                commands: vec![SessionCommand::DropSearch { operation_id }],
+                commands,
                 log_search_dropped: true,
             }
         } else {
@@ -268,13 +282,20 @@ impl SessionShared {
                 });
             }
             self.search.drop_search();
-            self.search.refresh_compiled_filters(&filters);
             let operation_id = Uuid::new_v4();
             self.search.set_search_operation(operation_id);
-            commands.push(SessionCommand::ApplySearchFilter {
-                operation_id,
-                filters,
-            });
+            let command = match self.filters.effective_query() {
+                Some(query) => SessionCommand::ApplySearchQuery {
+                    operation_id,
+                    query: query.clone(),
+                },
+                None => SessionCommand::ApplySearchFilter {
+                    operation_id,
+                    filters,
+                },
+            };
+            commands.push(command);
+            commands.extend(self.sync_exclusions(registry));
             SearchSyncOutcome {
                 commands,
                 log_search_dropped: true,
@@ -282,6 +303,18 @@ impl SessionShared {
         }
     }
 
+    /// Returns the command hiding rows of the enabled exclusion filters from the main logs
+    /// table, or `None` if the exclusions didn't change since they were last applied.
+    fn sync_exclusions(&mut self, registry: &FilterRegistry) -> Option<SessionCommand> {
+        let filters = self.filters.exclusion_filters(registry);
+        self.logs
+            .sync_exclusions(filters)
+            .map(|filters| SessionCommand::ApplyExclusions {
+                operation_id: Uuid::new_v4(),
+                filters,
+            })
+    }
+
     /// Synchronizes the search-values pipeline used by charts.
     ///
     /// Search values are independent from logs search results, so they are synchronized through
@@ -291,7 +324,7 @@ impl SessionShared {
             .filters
             .enabled_search_value_ids()
             .filter_map(|uuid| registry.get_search_value(uuid))
-            .map(|def| def.filter.value.clone())
+            .map(|def| def.filter.clone())
             .collect();
 
         if filters.is_empty() {
@@ -335,13 +368,18 @@ impl SessionShared {
 
     /// Returns colors for a filter index reported by the active log search.
     ///
-    /// Index `0` uses temporary-search colors while a temporary search is active. Otherwise,
-    /// indices resolve through enabled persistent filters in session order.
+    /// Index `0` uses temporary-search colors while a temporary search is active. Filters of
+    /// an active query use the same colors. Otherwise, indices resolve through enabled
+    /// persistent filters in session order.
     pub fn filter_match_colors(&self, filter_index: usize) -> Option<&ColorPair> {
         if self.filters.active_temp_search.is_some() {
             return (filter_index == 0).then_some(&TEMP_SEARCH_COLORS);
         }
 
+        if let Some(query) = self.filters.effective_query() {
+            return (filter_index < query.filters().len()).then_some(&TEMP_SEARCH_COLORS);
+        }
+
         self.filters
             .enabled_filter_at(filter_index)
             .map(|entry| &entry.colors)
@@ -378,6 +416,15 @@ impl SessionShared {
         changed
     }
 
+    /// Updates one applied filter exclusion flag and tracks recent-session dirtiness.
+    pub fn set_filter_exclude(&mut self, filter_id: &Uuid, exclude: bool) -> bool {
+        let changed = self.filters.set_filter_exclude(filter_id, exclude);
+        if changed {
+            self.bump_recent_revision();
+        }
+        changed
+    }
+
     /// Updates one applied search value enabled flag and tracks recent-session dirtiness.
     pub fn set_search_value_enabled(&mut self, value_id: &Uuid, enabled: bool) -> bool {
         let changed = self.filters.set_search_value_enabled(value_id, enabled);
@@ -611,6 +658,7 @@ mod tests {
             &ObserveOptions {
                 origin,
                 parser: ParserType::Text(()),
+                recording: None,
             },
         );
 
@@ -810,7 +858,6 @@ mod tests {
         let mut registry = FilterRegistry::default();
         add_filter(&mut shared, &mut registry, "status=ok");
         let _ = shared.sync_search(&registry, SearchSyncTarget::Filter);
-        assert_eq!(shared.search.compiled_filters().len(), 1);
         shared.filters.active_temp_search = None;
         let filter_id = shared.filters.filter_entries[0].id;
         shared.filters.unapply_filter(&mut registry, &filter_id);
@@ -827,7 +874,6 @@ mod tests {
             }
             other => panic!("expected DropSearch command, got {other:?}"),
         }
-        assert!(shared.search.compiled_filters().is_empty());
         assert!(shared.search.processing_search_operation().is_none());
     }
 
@@ -863,8 +909,6 @@ mod tests {
             other => panic!("expected second command ApplySearchFilter, got {other:?}"),
         };
 
-        assert_eq!(shared.search.compiled_filters().len(), 1);
-        assert!(shared.search.compiled_filters()[0].is_match("status=ok"));
         assert_eq!(
             shared.search.processing_search_operation(),
             Some(applied_operation_id)
@@ -888,12 +932,39 @@ mod tests {
             }
             other => panic!("expected ApplySearchFilter, got {other:?}"),
         }
-        assert_eq!(shared.search.compiled_filters().len(), 1);
-        assert!(shared.search.compiled_filters()[0].is_match("TEMP"));
     }
 
     #[test]
-    fn temp_search_dominates_filter_payload_and_cache() {
+    fn filter_sync_applies_query() {
+        let mut shared = new_shared();
+        let mut registry = FilterRegistry::default();
+        add_filter(&mut shared, &mut registry, "status=ok");
+        shared
+            .filters
+            .set_query_text("error AND NOT heartbeat".to_owned());
+
+        let commands = shared.sync_search(&registry, SearchSyncTarget::Filter);
+
+        assert_eq!(commands.len(), 1);
+        match &commands[0] {
+            SessionCommand::ApplySearchQuery { query, .. } => {
+                assert_eq!(query.to_string(), "error AND NOT heartbeat");
+            }
+            other => panic!("expected ApplySearchQuery, got {other:?}"),
+        }
+        assert_eq!(shared.filter_match_colors(1), Some(&TEMP_SEARCH_COLORS));
+        assert_eq!(shared.filter_match_colors(2), None);
+
+        shared.filters.set_query_enabled(false);
+        let commands = shared.sync_search(&registry, SearchSyncTarget::Filter);
+        assert!(matches!(
+            commands.last(),
+            Some(SessionCommand::ApplySearchFilter { filters, .. }) if filters[0].value == "status=ok"
+        ));
+    }
+
+    #[test]
+    fn temp_search_dominates_filter_payload() {
         let mut shared = new_shared();
         let mut registry = FilterRegistry::default();
         add_filter_def(&mut shared, &mut registry, SearchFilter::plain("status=ok"));
@@ -913,11 +984,6 @@ mod tests {
         };
         assert_eq!(payload_filters.len(), 1);
         assert_eq!(payload_filters[0].value, "temp");
-
-        let compiled = shared.search.compiled_filters();
-        assert_eq!(compiled.len(), 1);
-        assert!(compiled[0].is_match("TEMP"));
-        assert!(!compiled[0].is_match("TEAM"));
     }
 
     #[test]
@@ -974,14 +1040,15 @@ mod tests {
         assert_eq!(commands.len(), 1);
         match &commands[0] {
             SessionCommand::ApplySearchValuesFilter { filters, .. } => {
-                assert_eq!(filters, &["cpu=(\\d+)".to_owned()]);
+                assert_eq!(filters.len(), 1);
+                assert_eq!(filters[0].value, "cpu=(\\d+)");
             }
             other => panic!("expected ApplySearchValuesFilter, got {other:?}"),
         }
     }
 
     #[test]
-    fn filter_sync_cache_updates_after_edit() {
+    fn filter_sync_payload_updates_after_edit() {
         let mut shared = new_shared();
         let mut registry = FilterRegistry::default();
         let filter_id =
@@ -992,9 +1059,6 @@ mod tests {
             initial_commands[0],
             SessionCommand::ApplySearchFilter { .. }
         ));
-        assert_eq!(shared.search.compiled_filters().len(), 1);
-        assert!(shared.search.compiled_filters()[0].is_match("status=ok"));
-        assert!(!shared.search.compiled_filters()[0].is_match("level=warn"));
 
         let next_filter = SearchFilter::plain("level=warn");
         let outcome = registry.edit_filter_for_session(filter_id, shared.get_id(), next_filter);
@@ -1011,9 +1075,6 @@ mod tests {
 
         assert_eq!(payload_filters.len(), 1);
         assert_eq!(payload_filters[0].value, "level=warn");
-        assert_eq!(shared.search.compiled_filters().len(), 1);
-        assert!(shared.search.compiled_filters()[0].is_match("level=warn"));
-        assert!(!shared.search.compiled_filters()[0].is_match("status=ok"));
     }
 
     #[test]
@@ -1060,7 +1121,8 @@ mod tests {
                 operation_id,
                 filters,
             } => {
-                assert_eq!(filters, &vec!["cpu=(\\d+)".to_owned()]);
+                assert_eq!(filters.len(), 1);
+                assert_eq!(filters[0].value, "cpu=(\\d+)");
                 *operation_id
             }
             other => panic!("expected second command ApplySearchValuesFilter, got {other:?}"),
@@ -1079,7 +1141,6 @@ mod tests {
         add_filter(&mut shared, &mut registry, "status=ok");
         let filter_id = shared.filters.filter_entries[0].id;
         let _ = shared.sync_search(&registry, SearchSyncTarget::Filter);
-        assert_eq!(shared.search.compiled_filters().len(), 1);
         // Disabled filters stay in session state but should not reach backend sync.
         assert!(shared.filters.set_filter_enabled(&filter_id, false));
 
@@ -1087,7 +1148,6 @@ mod tests {
 
         assert_eq!(commands.len(), 1);
         assert!(matches!(commands[0], SessionCommand::DropSearch { .. }));
-        assert!(shared.search.compiled_filters().is_empty());
     }
 
     #[test]
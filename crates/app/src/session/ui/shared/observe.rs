@@ -1,5 +1,5 @@
 use egui::Color32;
-use stypes::ObserveOrigin;
+use stypes::{ObserveOrigin, SourceConnectionState};
 use uuid::Uuid;
 
 use crate::{
@@ -45,7 +45,10 @@ impl ObserveState {
         operations.push(observe_op);
 
         let new_op_count = match &operations.last().unwrap().origin {
-            ObserveOrigin::File(..) | ObserveOrigin::Stream(..) => 1,
+            ObserveOrigin::File(..)
+            | ObserveOrigin::Stream(..)
+            | ObserveOrigin::DirWatch(..)
+            | ObserveOrigin::Plugin(..) => 1,
             ObserveOrigin::Concat(items) => items.len(),
         };
         *sources_count += new_op_count;
@@ -68,6 +71,13 @@ impl ObserveState {
         }
     }
 
+    /// Updates the connection state of the source of the observe operation.
+    pub fn update_source_state(&mut self, operation_id: Uuid, state: SourceConnectionState) {
+        if let Some(observe) = self.operations.iter_mut().find(|o| o.id == operation_id) {
+            observe.set_source_state(state);
+        }
+    }
+
     /// Observe operations.
     pub fn operations(&self) -> &[ObserveOperation] {
         &self.operations
@@ -104,7 +114,7 @@ impl ObserveState {
 mod tests {
     use std::path::PathBuf;
 
-    use stypes::{FileFormat, TCPTransportConfig, Transport};
+    use stypes::{FileFormat, TCPTransportConfig, TcpMode, Transport};
 
     use super::*;
 
@@ -153,6 +163,7 @@ mod tests {
                 String::from("stream"),
                 Transport::TCP(TCPTransportConfig {
                     bind_addr: String::from("127.0.0.1:9000"),
+                    mode: TcpMode::Connect,
                 }),
             ),
         );
@@ -2,10 +2,10 @@
 //!
 //! [`FiltersState`] bridges global registry definitions with per-session UI state.
 //! It owns the ordered list of applied filters and search values, plus the temporary search from
-//! the search bar before it is pinned into the registry.
+//! the search bar before it is pinned into the registry and the boolean filter query.
 
 use egui::Color32;
-use processor::search::filter::SearchFilter;
+use processor::search::{filter::SearchFilter, query::FilterQuery};
 use uuid::Uuid;
 
 use crate::common::validation::{
@@ -62,6 +62,46 @@ impl From<SearchFilter> for TempSearch {
     }
 }
 
+/// Boolean query over search filters, written in the Filters side panel.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    text: String,
+    enabled: bool,
+    parsed: Result<FilterQuery, String>,
+}
+
+impl SearchQuery {
+    /// Creates the query state and parses its text.
+    pub fn new(text: String, enabled: bool) -> Self {
+        let parsed = text.parse();
+        Self {
+            text,
+            enabled,
+            parsed,
+        }
+    }
+
+    /// Returns the query text as written by the user.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns whether the query replaces the enabled filters in searches.
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the parsed query, or the reason why the text isn't a valid query.
+    pub const fn parsed(&self) -> Result<&FilterQuery, &String> {
+        self.parsed.as_ref()
+    }
+
+    /// Returns the query when it's enabled and valid.
+    pub fn active(&self) -> Option<&FilterQuery> {
+        self.parsed.as_ref().ok().filter(|_| self.enabled)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Session-local state for one applied filter.
 pub struct AppliedFilterState {
@@ -71,6 +111,9 @@ pub struct AppliedFilterState {
     pub enabled: bool,
     /// Foreground and background colors used for filter matches.
     pub colors: ColorPair,
+    /// Whether rows matching the filter are hidden from the main logs table instead of
+    /// being searched.
+    pub exclude: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -90,6 +133,7 @@ impl AppliedFilterState {
             id,
             enabled,
             colors,
+            exclude: false,
         }
     }
 }
@@ -111,6 +155,8 @@ pub struct FiltersState {
     pub search_value_entries: Vec<AppliedSearchValueState>,
     /// Temporary search currently applied to this session.
     pub active_temp_search: Option<TempSearch>,
+    /// Boolean filter query of this session.
+    pub query: Option<SearchQuery>,
 }
 
 impl FiltersState {
@@ -121,29 +167,71 @@ impl FiltersState {
             filter_entries: Vec::new(),
             search_value_entries: Vec::new(),
             active_temp_search: None,
+            query: None,
         }
     }
 
     /// Returns the filters currently effective for log searching.
     ///
-    /// An active temporary search is exclusive; otherwise all enabled persistent filters apply.
+    /// An active temporary search is exclusive, followed by an enabled and valid query whose
+    /// filters are returned in query order. Otherwise all enabled persistent filters apply.
     pub fn effective_filters(&self, registry: &FilterRegistry) -> Vec<SearchFilter> {
         if let Some(temp_search) = self.active_temp_search.as_ref() {
             let filter = temp_search.filter().clone();
             return vec![filter];
         }
 
+        if let Some(query) = self.effective_query() {
+            return query.filters().into_iter().cloned().collect();
+        }
+
         self.enabled_filter_ids()
             .filter_map(|id| registry.get_filter(id))
             .map(|definition| definition.filter.clone())
             .collect()
     }
 
-    /// Returns an enabled persistent filter by its session-order index.
+    /// Returns the query currently effective for log searching, unless a temporary
+    /// search is active.
+    pub fn effective_query(&self) -> Option<&FilterQuery> {
+        if self.active_temp_search.is_some() {
+            return None;
+        }
+        self.query.as_ref().and_then(SearchQuery::active)
+    }
+
+    /// Replaces the query text and reports whether it changed.
+    pub fn set_query_text(&mut self, text: String) -> bool {
+        let enabled = self.query.as_ref().is_none_or(SearchQuery::enabled);
+        if self.query.as_ref().is_some_and(|query| query.text == text) {
+            return false;
+        }
+        self.query = Some(SearchQuery::new(text, enabled));
+        true
+    }
+
+    /// Updates the enabled flag of the query and reports whether it changed.
+    pub fn set_query_enabled(&mut self, enabled: bool) -> bool {
+        let Some(query) = self.query.as_mut() else {
+            return false;
+        };
+
+        let changed = query.enabled != enabled;
+        query.enabled = enabled;
+
+        changed
+    }
+
+    /// Removes the query and reports whether there was one.
+    pub fn clear_query(&mut self) -> bool {
+        self.query.take().is_some()
+    }
+
+    /// Returns an enabled persistent search filter by its session-order index.
     pub fn enabled_filter_at(&self, filter_index: usize) -> Option<&AppliedFilterState> {
         self.filter_entries
             .iter()
-            .filter(|entry| entry.enabled)
+            .filter(|entry| entry.enabled && !entry.exclude)
             .nth(filter_index)
     }
 
@@ -214,14 +302,30 @@ impl FiltersState {
             .is_some_and(|item| item.enabled)
     }
 
-    /// Iterates enabled filters in their session order.
+    /// Iterates enabled search filters in their session order.
     pub fn enabled_filter_ids(&self) -> impl Iterator<Item = &Uuid> {
         self.filter_entries
             .iter()
-            .filter(|item| item.enabled)
+            .filter(|item| item.enabled && !item.exclude)
             .map(|item| &item.id)
     }
 
+    /// Iterates enabled exclusion filters in their session order.
+    pub fn excluded_filter_ids(&self) -> impl Iterator<Item = &Uuid> {
+        self.filter_entries
+            .iter()
+            .filter(|item| item.enabled && item.exclude)
+            .map(|item| &item.id)
+    }
+
+    /// Returns the enabled exclusion filters hiding rows from the main logs table.
+    pub fn exclusion_filters(&self, registry: &FilterRegistry) -> Vec<SearchFilter> {
+        self.excluded_filter_ids()
+            .filter_map(|id| registry.get_filter(id))
+            .map(|definition| definition.filter.clone())
+            .collect()
+    }
+
     /// Iterates enabled search values in their session order.
     pub fn enabled_search_value_ids(&self) -> impl Iterator<Item = &Uuid> {
         self.search_value_entries
@@ -295,6 +399,19 @@ impl FiltersState {
         changed
     }
 
+    /// Updates the exclusion flag for an existing filter and reports
+    /// whether it changed.
+    pub fn set_filter_exclude(&mut self, id: &Uuid, exclude: bool) -> bool {
+        let Some(item) = self.filter_entries.iter_mut().find(|item| item.id == *id) else {
+            return false;
+        };
+
+        let changed = item.exclude != exclude;
+        item.exclude = exclude;
+
+        changed
+    }
+
     /// Updates the enabled flag for an existing search value and reports
     /// whether it changed.
     pub fn set_search_value_enabled(&mut self, id: &Uuid, enabled: bool) -> bool {
@@ -576,6 +693,32 @@ mod tests {
         registry.add_search_value(def)
     }
 
+    #[test]
+    fn excluded_filters_are_left_out_of_search() {
+        let mut state = new_state();
+        let mut registry = FilterRegistry::default();
+        let error_id = add_plain_filter_definition(&mut registry, "error");
+        let heartbeat_id = add_plain_filter_definition(&mut registry, "heartbeat");
+        state.apply_filter(&mut registry, error_id);
+        state.apply_filter(&mut registry, heartbeat_id);
+
+        assert!(state.set_filter_exclude(&heartbeat_id, true));
+        assert!(!state.set_filter_exclude(&heartbeat_id, true));
+
+        assert_eq!(
+            state.effective_filters(&registry),
+            vec![SearchFilter::plain("error").ignore_case(true)]
+        );
+        assert_eq!(
+            state.exclusion_filters(&registry),
+            vec![SearchFilter::plain("heartbeat").ignore_case(true)]
+        );
+        assert_eq!(state.enabled_filter_at(1), None);
+
+        state.set_filter_enabled(&heartbeat_id, false);
+        assert!(state.exclusion_filters(&registry).is_empty());
+    }
+
     #[test]
     fn filter_apply_uses_first_free_color() {
         let mut state = new_state();
@@ -830,6 +973,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enabled_query_replaces_effective_filters() {
+        let mut state = new_state();
+        let mut registry = FilterRegistry::default();
+        let filter_id = add_plain_filter_definition(&mut registry, "first");
+        state.apply_filter(&mut registry, filter_id);
+
+        assert!(state.set_query_text("error AND NOT".to_owned()));
+        assert!(state.query.as_ref().unwrap().parsed().is_err());
+        assert!(state.effective_query().is_none());
+        assert_eq!(state.effective_filters(&registry)[0].value, "first");
+
+        assert!(state.set_query_text("error AND NOT heartbeat".to_owned()));
+        assert!(!state.set_query_text("error AND NOT heartbeat".to_owned()));
+        let effective = state.effective_filters(&registry);
+        assert_eq!(
+            effective
+                .iter()
+                .map(|filter| filter.value.as_str())
+                .collect::<Vec<_>>(),
+            ["error", "heartbeat"]
+        );
+
+        state.active_temp_search = Some(SearchFilter::plain("temp").into());
+        assert!(state.effective_query().is_none());
+        state.active_temp_search = None;
+
+        assert!(state.set_query_enabled(false));
+        assert!(state.effective_query().is_none());
+        assert_eq!(state.effective_filters(&registry)[0].value, "first");
+        assert!(!state.set_query_text("error AND NOT heartbeat".to_owned()));
+        assert!(!state.query.as_ref().unwrap().enabled());
+    }
+
     #[test]
     fn pinning_temp_search_assigns_filter_color() {
         let mut state = new_state();
@@ -5,8 +5,6 @@
 //! It is specific to log searching; chart value extraction is tracked separately in
 //! [`SearchValuesState`](super::SearchValuesState).
 
-use processor::search::filter::{self, SearchFilter};
-use regex::Regex;
 use rustc_hash::FxHashMap;
 use stypes::FilterMatch;
 use uuid::Uuid;
@@ -85,12 +83,6 @@ pub struct SearchState {
     matches_map: Option<FxHashMap<LogMainIndex, SearchMatchMetadata>>,
     /// Index assigned to the next primary match callback item.
     next_search_result_index: u64,
-    /// Compiled regex matchers for the current effective log-search filters.
-    ///
-    /// # Note:
-    ///
-    /// Must stay in the same backend filter index order as `ApplySearchFilter.filters`.
-    compiled_filters: Vec<Regex>,
 }
 
 impl SearchState {
@@ -101,7 +93,6 @@ impl SearchState {
             counts: SearchCounts::default(),
             matches_map: None,
             next_search_result_index: 0,
-            compiled_filters: Vec::new(),
         }
     }
 
@@ -116,7 +107,6 @@ impl SearchState {
             counts,
             matches_map,
             next_search_result_index,
-            compiled_filters: _,
         } = self;
 
         nested.close();
@@ -136,24 +126,6 @@ impl SearchState {
         &mut self.nested
     }
 
-    /// Rebuilds cached regex matchers from the current effective search filters.
-    pub fn refresh_compiled_filters(&mut self, filters: &[SearchFilter]) {
-        self.compiled_filters = filters
-            .iter()
-            .filter_map(|filter| Regex::new(&filter::as_regex(filter)).ok())
-            .collect();
-    }
-
-    /// Clears cached regex matchers when no effective search filters remain.
-    pub fn clear_compiled_filters(&mut self) {
-        self.compiled_filters.clear();
-    }
-
-    /// Returns compiled regex matchers in backend filter index order.
-    pub fn compiled_filters(&self) -> &[Regex] {
-        &self.compiled_filters
-    }
-
     /// Returns the current backend search match count.
     pub fn search_result_count(&self) -> u64 {
         self.counts.search_result_count
@@ -248,7 +220,6 @@ impl SearchState {
             counts,
             matches_map,
             next_search_result_index,
-            compiled_filters: _,
         } = self;
 
         counts.reset_search_counts();
@@ -314,13 +285,6 @@ mod tests {
         }
     }
 
-    fn sample_filters() -> Vec<SearchFilter> {
-        vec![
-            SearchFilter::plain("status=ok"),
-            SearchFilter::plain("warning").ignore_case(true),
-        ]
-    }
-
     #[test]
     fn zero_count_keeps_pinned() {
         let mut counts = SearchCounts {
@@ -529,27 +493,4 @@ mod tests {
             Some(OperationPhase::Initializing)
         );
     }
-
-    #[test]
-    fn refresh_compiled_filters_follows_order() {
-        let mut state = SearchState::new(Uuid::new_v4());
-
-        state.refresh_compiled_filters(&sample_filters());
-
-        let compiled = state.compiled_filters();
-        assert_eq!(compiled.len(), 2);
-        assert!(compiled[0].is_match("status=ok"));
-        assert!(!compiled[0].is_match("STATUS=OK"));
-        assert!(compiled[1].is_match("WARNING"));
-    }
-
-    #[test]
-    fn clear_compiled_filters_empties_cache() {
-        let mut state = SearchState::new(Uuid::new_v4());
-        state.refresh_compiled_filters(&sample_filters());
-
-        state.clear_compiled_filters();
-
-        assert!(state.compiled_filters().is_empty());
-    }
 }
@@ -1,5 +1,6 @@
 //! Deferred Filters sidebar actions and session state updates.
 
+use processor::search::filter::SearchFilter;
 use uuid::Uuid;
 
 use crate::{
@@ -11,7 +12,9 @@ use crate::{
             registry::filters::{FilterRegistry, RegistryEditOutcome},
         },
     },
-    session::ui::shared::{SearchSyncTarget, SessionShared, SessionSignal},
+    session::ui::shared::{
+        SearchSyncTarget, SessionShared, SessionSignal, searching::FiltersState,
+    },
 };
 
 use super::render::validate_search_value_text;
@@ -30,12 +33,16 @@ pub(super) enum FilterPanelAction {
     CancelSearchValueEdit(Uuid),
     ApplyTempSearch(SelectedSidebarItem),
     ToggleFilter(Uuid, bool),
+    /// Hides rows matching the filter from the main logs table instead of searching them.
+    ToggleFilterExclude(Uuid, bool),
     EditFilterFlags(Uuid, FilterFlags),
     RemoveFilter(Uuid),
     RemoveAllFilters,
     ReorderItem(SelectedSidebarItem, usize),
     /// Converts the filter and optionally places it at an exact insertion slot.
     MoveFilterToValue(Uuid, Option<usize>),
+    /// Limits the search value to the schema column, or to whole lines with `None`.
+    EditSearchValueColumn(Uuid, Option<usize>),
     ToggleSearchValue(Uuid, bool),
     RemoveSearchValue(Uuid),
     RemoveAllSearchValues,
@@ -43,6 +50,10 @@ pub(super) enum FilterPanelAction {
     MoveValueToFilter(Uuid, Option<usize>),
     /// Requests the parent session to capture the current filters and charts.
     CapturePreset,
+    /// Replaces the query text, removing the query when the text is blank.
+    ApplyQueryText(String),
+    ToggleQuery(bool),
+    RemoveQuery,
 }
 
 impl FiltersUi {
@@ -137,38 +148,15 @@ impl FiltersUi {
 
                 let mut next_filter = current_value.filter.clone();
                 next_filter.value = draft;
+                self.edit_search_value(value_id, next_filter, shared, actions, registry);
+            }
+            FilterPanelAction::EditSearchValueColumn(value_id, column) => {
+                let Some(current_value) = registry.get_search_value(&value_id) else {
+                    return;
+                };
 
-                match registry.edit_search_value_for_session(value_id, shared.get_id(), next_filter)
-                {
-                    RegistryEditOutcome::NotFound => {
-                        self.clear_search_value_edit_for(value_id);
-                    }
-                    RegistryEditOutcome::EditedInPlace => {
-                        self.clear_search_value_edit_for(value_id);
-                        shared.bump_recent_revision();
-                        self.dispatch_sync_commands(
-                            shared,
-                            actions,
-                            registry,
-                            SearchSyncTarget::SearchValue,
-                        );
-                    }
-                    RegistryEditOutcome::Reassigned(next_value_id) => {
-                        if shared.rebind_search_value(&value_id, next_value_id) {
-                            self.replace_selection(
-                                SelectedSidebarItem::SearchValue(value_id),
-                                SelectedSidebarItem::SearchValue(next_value_id),
-                            );
-                        }
-                        self.clear_search_value_edit_for(value_id);
-                        self.dispatch_sync_commands(
-                            shared,
-                            actions,
-                            registry,
-                            SearchSyncTarget::SearchValue,
-                        );
-                    }
-                }
+                let next_filter = current_value.filter.clone().in_column(column);
+                self.edit_search_value(value_id, next_filter, shared, actions, registry);
             }
             FilterPanelAction::CancelSearchValueEdit(value_id) => {
                 self.clear_search_value_edit_for(value_id);
@@ -217,6 +205,16 @@ impl FiltersUi {
                     );
                 }
             }
+            FilterPanelAction::ToggleFilterExclude(filter_id, exclude) => {
+                if shared.set_filter_exclude(&filter_id, exclude) {
+                    self.dispatch_sync_commands(
+                        shared,
+                        actions,
+                        registry,
+                        SearchSyncTarget::Filter,
+                    );
+                }
+            }
             FilterPanelAction::EditFilterFlags(filter_id, flags) => {
                 let Some(current_filter) = registry.get_filter(&filter_id) else {
                     return;
@@ -226,13 +224,15 @@ impl FiltersUi {
                     regex,
                     ignore_case,
                     word,
+                    column,
                 } = flags;
                 let next_filter = current_filter
                     .filter
                     .clone()
                     .regex(regex)
                     .ignore_case(ignore_case)
-                    .word(word);
+                    .word(word)
+                    .in_column(column);
 
                 match validate_filter(&next_filter) {
                     ValidationEligibility::Eligible => (),
@@ -388,6 +388,83 @@ impl FiltersUi {
             FilterPanelAction::CapturePreset => {
                 shared.signals.push(SessionSignal::CapturePreset);
             }
+            FilterPanelAction::ApplyQueryText(text) => {
+                self.query_draft = None;
+                self.update_query(shared, actions, registry, |filters| {
+                    if text.trim().is_empty() {
+                        filters.clear_query();
+                    } else {
+                        filters.set_query_text(text);
+                    }
+                });
+            }
+            FilterPanelAction::ToggleQuery(enabled) => {
+                self.update_query(shared, actions, registry, |filters| {
+                    filters.set_query_enabled(enabled);
+                });
+            }
+            FilterPanelAction::RemoveQuery => {
+                self.query_draft = None;
+                self.update_query(shared, actions, registry, |filters| {
+                    filters.clear_query();
+                });
+            }
+        }
+    }
+
+    /// Replaces the search value definition for this session and resyncs the search values.
+    fn edit_search_value(
+        &mut self,
+        value_id: Uuid,
+        next_filter: SearchFilter,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+        registry: &mut FilterRegistry,
+    ) {
+        match registry.edit_search_value_for_session(value_id, shared.get_id(), next_filter) {
+            RegistryEditOutcome::NotFound => {
+                self.clear_search_value_edit_for(value_id);
+            }
+            RegistryEditOutcome::EditedInPlace => {
+                self.clear_search_value_edit_for(value_id);
+                shared.bump_recent_revision();
+                self.dispatch_sync_commands(
+                    shared,
+                    actions,
+                    registry,
+                    SearchSyncTarget::SearchValue,
+                );
+            }
+            RegistryEditOutcome::Reassigned(next_value_id) => {
+                if shared.rebind_search_value(&value_id, next_value_id) {
+                    self.replace_selection(
+                        SelectedSidebarItem::SearchValue(value_id),
+                        SelectedSidebarItem::SearchValue(next_value_id),
+                    );
+                }
+                self.clear_search_value_edit_for(value_id);
+                self.dispatch_sync_commands(
+                    shared,
+                    actions,
+                    registry,
+                    SearchSyncTarget::SearchValue,
+                );
+            }
+        }
+    }
+
+    /// Applies the query change and resyncs the search only when the effective query changed.
+    fn update_query(
+        &self,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+        registry: &FilterRegistry,
+        update: impl FnOnce(&mut FiltersState),
+    ) {
+        let before = shared.filters.effective_query().cloned();
+        update(&mut shared.filters);
+        if before.as_ref() != shared.filters.effective_query() {
+            self.dispatch_sync_commands(shared, actions, registry, SearchSyncTarget::Filter);
         }
     }
 
@@ -491,6 +568,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn query_changes_resync_only_when_effective() {
+        let runtime = Runtime::new().expect("runtime should initialize");
+        let mut actions = UiActions::new(runtime.handle().clone());
+        let mut registry = FilterRegistry::default();
+        let mut shared = new_shared();
+        let (cmd_tx, mut cmd_rx) = mpsc::channel(4);
+        let mut filters_ui = FiltersUi::new(cmd_tx);
+
+        filters_ui.handle_action(
+            Some(FilterPanelAction::ApplyQueryText("error AND (".to_owned())),
+            &mut shared,
+            &mut actions,
+            &mut registry,
+        );
+        assert!(shared.filters.query.is_some());
+        assert!(cmd_rx.try_recv().is_err());
+
+        filters_ui.handle_action(
+            Some(FilterPanelAction::ApplyQueryText(
+                "error AND NOT ok".to_owned(),
+            )),
+            &mut shared,
+            &mut actions,
+            &mut registry,
+        );
+        match cmd_rx.try_recv() {
+            Ok(SessionCommand::ApplySearchQuery { query, .. }) => {
+                assert_eq!(query.to_string(), "error AND NOT ok");
+            }
+            other => panic!("expected ApplySearchQuery command, got {other:?}"),
+        }
+
+        filters_ui.handle_action(
+            Some(FilterPanelAction::RemoveQuery),
+            &mut shared,
+            &mut actions,
+            &mut registry,
+        );
+        assert!(shared.filters.query.is_none());
+        assert_matches!(cmd_rx.try_recv(), Ok(SessionCommand::DropSearch { .. }));
+    }
+
     #[test]
     fn invalid_copied_filter_preserves_temp_search() {
         let runtime = Runtime::new().expect("runtime should initialize");
@@ -15,6 +15,7 @@ use crate::{
 
 mod actions;
 mod drag_drop;
+mod query;
 mod render;
 
 /// Owns Filters sidebar selection and inline-edit state.
@@ -24,6 +25,8 @@ pub struct FiltersUi {
     selected_item: Option<SelectedSidebarItem>,
     filter_edit_state: Option<TextEditState>,
     search_value_edit_state: Option<TextEditState>,
+    /// Query text being edited and not applied yet.
+    query_draft: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +42,8 @@ struct FilterFlags {
     regex: bool,
     ignore_case: bool,
     word: bool,
+    /// Index of the schema column the filter is limited to.
+    column: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -55,6 +60,7 @@ impl FiltersUi {
             selected_item: None,
             filter_edit_state: None,
             search_value_edit_state: None,
+            query_draft: None,
         }
     }
 
@@ -86,6 +92,7 @@ impl FiltersUi {
         ScrollArea::vertical()
             .max_height(list_max_height)
             .show(ui, |ui| {
+                self.render_query_group(shared, ui, &mut side_action);
                 self.render_filters_group(shared, registry, dnd_enabled, ui, &mut side_action);
                 self.render_search_values_group(
                     shared,
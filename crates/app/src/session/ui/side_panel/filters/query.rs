@@ -0,0 +1,92 @@
+//! Filters sidebar editor for the boolean filter query.
+
+use egui::{Align, Button, Key, Layout, RichText, TextEdit, Ui};
+use processor::search::query::FilterQuery;
+
+use crate::{
+    common::phosphor::icons, host::common::ui_utls::show_side_panel_group,
+    session::ui::shared::SessionShared,
+};
+
+use super::FiltersUi;
+use super::actions::FilterPanelAction;
+
+const QUERY_HINT: &str = "error AND (timeout OR /retr(y|ies)/i) AND NOT heartbeat";
+const SYNTAX_HELP: &str = "Combine terms with AND, OR, NOT and parentheses. \
+    Use \"quoted text\" for spaces and /pattern/ for regex, \
+    followed by the flags i (ignore case) and w (whole word).";
+
+impl FiltersUi {
+    /// Renders the query editor and records its deferred action.
+    ///
+    /// The draft is applied on Enter and discarded on Escape, so searches only restart
+    /// for complete queries.
+    pub(super) fn render_query_group(
+        &mut self,
+        shared: &SessionShared,
+        ui: &mut Ui,
+        side_action: &mut Option<FilterPanelAction>,
+    ) {
+        let query = shared.filters.query.as_ref();
+        show_side_panel_group(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Query").heading().size(16.0));
+                ui.label(RichText::new(icons::regular::INFO).weak())
+                    .on_hover_text(SYNTAX_HELP);
+                let Some(query) = query else {
+                    return;
+                };
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    let remove_btn = ui
+                        .add(Button::new(icons::regular::TRASH).frame(false))
+                        .on_hover_text("Remove query");
+                    if remove_btn.clicked() {
+                        *side_action = Some(FilterPanelAction::RemoveQuery);
+                    }
+                    let mut enabled = query.enabled();
+                    if ui
+                        .checkbox(&mut enabled, "")
+                        .on_hover_text("Search with the query instead of the filters")
+                        .changed()
+                    {
+                        *side_action = Some(FilterPanelAction::ToggleQuery(enabled));
+                    }
+                });
+            });
+            ui.add_space(5.0);
+
+            let mut draft = self.query_draft.clone().unwrap_or_else(|| {
+                query
+                    .map(|query| query.text().to_owned())
+                    .unwrap_or_default()
+            });
+            let text_res = TextEdit::singleline(&mut draft)
+                .desired_width(f32::INFINITY)
+                .hint_text(QUERY_HINT)
+                .show(ui);
+            if text_res.response.changed() {
+                self.query_draft = Some(draft.clone());
+            }
+            if text_res.response.lost_focus() {
+                if ui.input(|i| i.key_pressed(Key::Escape)) {
+                    self.query_draft = None;
+                } else if ui.input(|i| i.key_pressed(Key::Enter)) {
+                    *side_action = Some(FilterPanelAction::ApplyQueryText(draft.clone()));
+                }
+            }
+
+            let error = if let Some(draft) = self.query_draft.as_ref() {
+                (!draft.trim().is_empty())
+                    .then(|| draft.parse::<FilterQuery>().err())
+                    .flatten()
+            } else {
+                query.and_then(|query| query.parsed().err().cloned())
+            };
+            if let Some(error) = error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            } else if self.query_draft.is_some() {
+                ui.label(RichText::new("Press Enter to apply").weak());
+            }
+        });
+    }
+}
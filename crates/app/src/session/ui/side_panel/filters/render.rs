@@ -14,23 +14,33 @@ use crate::{
         validation::{ValidationEligibility, validate_filter, validate_search_value_filter},
     },
     host::ui::registry::filters::FilterRegistry,
-    session::ui::shared::SessionShared,
+    session::ui::{definitions::schema::ColumnInfo, shared::SessionShared},
 };
 
 use super::actions::FilterPanelAction;
 use super::drag_drop::{self, SidebarDrag, SidebarDropTarget};
 use super::{FilterFlags, FiltersUi, SelectedSidebarItem, TextEditState};
 
+/// Returns the schema columns filters can be limited to.
+///
+/// Logs without column separators have a single column, which is the whole line.
+fn scope_columns(shared: &SessionShared) -> &[ColumnInfo] {
+    let columns = shared.schema.columns();
+    if columns.len() > 1 { columns } else { &[] }
+}
+
 fn validate_filter_text(flags: FilterFlags, draft: &str) -> Option<String> {
     let FilterFlags {
         regex,
         ignore_case,
         word,
+        column,
     } = flags;
     let next_filter = SearchFilter::plain(draft)
         .regex(regex)
         .ignore_case(ignore_case)
-        .word(word);
+        .word(word)
+        .in_column(column);
 
     match validate_filter(&next_filter) {
         ValidationEligibility::Eligible => None,
@@ -53,11 +63,15 @@ pub fn validate_search_value_text(current_filter: &SearchFilter, draft: &str) ->
 struct FilterRowView<'a> {
     id: Uuid,
     enabled: bool,
+    /// Whether matching rows are hidden from the main logs table.
+    exclude: bool,
     color: Color32,
     text: &'a str,
     flags: FilterFlags,
     search_value_eligibility: &'a ValidationEligibility,
     regex_enable_eligibility: &'a ValidationEligibility,
+    /// Schema columns filters can be limited to, empty for single-column logs.
+    columns: &'a [ColumnInfo],
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +81,8 @@ struct SearchValueRowView<'a> {
     color: Color32,
     text: &'a str,
     filter: SearchFilter,
+    /// Schema columns search values can be limited to, empty for single-column logs.
+    columns: &'a [ColumnInfo],
 }
 
 impl FiltersUi {
@@ -130,15 +146,18 @@ impl FiltersUi {
             let row = FilterRowView {
                 id: item.id,
                 enabled: item.enabled,
+                exclude: item.exclude,
                 color: item.colors.bg,
                 text: def.filter.value.as_str(),
                 flags: FilterFlags {
                     regex: def.filter.is_regex(),
                     ignore_case: def.filter.is_ignore_case(),
                     word: def.filter.is_word(),
+                    column: def.filter.column(),
                 },
                 search_value_eligibility: &def.search_value_eligibility,
                 regex_enable_eligibility: &def.regex_enable_eligibility,
+                columns: scope_columns(shared),
             };
 
             have_items = true;
@@ -169,6 +188,7 @@ impl FiltersUi {
                 color: item.color,
                 text: def.filter.value.as_str(),
                 filter: def.filter.clone(),
+                columns: scope_columns(shared),
             };
 
             has_items = true;
@@ -239,6 +259,17 @@ impl FiltersUi {
                     ui.close();
                 }
 
+                let exclude_label = if row.exclude {
+                    "Show Matching Rows"
+                } else {
+                    "Hide Matching Rows"
+                };
+                if ui.button(exclude_label).clicked() {
+                    *side_action =
+                        Some(FilterPanelAction::ToggleFilterExclude(row.id, !row.exclude));
+                    ui.close();
+                }
+
                 ui.separator();
 
                 if ui.button("Remove Filter").clicked() {
@@ -467,6 +498,17 @@ impl FiltersUi {
             |ui| {
                 let mut action = None;
 
+                let exclude_res = Self::render_filter_flag_button(
+                    ui,
+                    icons::regular::EYE_SLASH,
+                    row.exclude,
+                    true,
+                    "Hide Matching Rows from Logs",
+                );
+                if exclude_res.clicked() {
+                    action = Some(FilterPanelAction::ToggleFilterExclude(row.id, !row.exclude));
+                }
+
                 let mut regex_res = Self::render_filter_flag_button(
                     ui,
                     icons::regular::ASTERISK,
@@ -509,6 +551,18 @@ impl FiltersUi {
                     ));
                 }
 
+                if let Some(column) =
+                    Self::render_column_selector(ui, row.flags.column, row.columns)
+                {
+                    action = Some(FilterPanelAction::EditFilterFlags(
+                        row.id,
+                        FilterFlags {
+                            column,
+                            ..row.flags
+                        },
+                    ));
+                }
+
                 let match_case_res = Self::render_filter_flag_button(
                     ui,
                     icons::regular::TEXT_AA,
@@ -559,6 +613,12 @@ impl FiltersUi {
             |ui| {
                 let mut action = None;
 
+                if let Some(column) =
+                    Self::render_column_selector(ui, row.filter.column(), row.columns)
+                {
+                    action = Some(FilterPanelAction::EditSearchValueColumn(row.id, column));
+                }
+
                 let move_btn = ui
                     .add(buttons::side_panel_row_icon(
                         RichText::new(icons::regular::FUNNEL).size(14.0),
@@ -635,6 +695,60 @@ impl FiltersUi {
         .inner
     }
 
+    /// Renders the menu to limit matching to one schema column.
+    ///
+    /// Returns the selected column, with `None` standing for whole lines.
+    fn render_column_selector(
+        ui: &mut Ui,
+        column: Option<usize>,
+        columns: &[ColumnInfo],
+    ) -> Option<Option<usize>> {
+        if columns.is_empty() {
+            return None;
+        }
+
+        let (label, tooltip) = match column {
+            Some(index) => {
+                let header = columns
+                    .get(index)
+                    .map_or_else(|| format!("#{index}"), |info| info.header.to_string());
+                let tooltip = format!("Match in column {header} only");
+                (RichText::new(header).small(), tooltip)
+            }
+            None => (
+                RichText::new(icons::regular::COLUMNS)
+                    .size(14.0)
+                    .color(ui.visuals().weak_text_color()),
+                String::from("Match in all columns"),
+            ),
+        };
+
+        let mut selected = None;
+        ui.menu_button(label, |ui| {
+            if ui
+                .selectable_label(column.is_none(), "All Columns")
+                .clicked()
+            {
+                selected = Some(None);
+                ui.close();
+            }
+            ui.separator();
+            for (index, info) in columns.iter().enumerate() {
+                let res = ui
+                    .selectable_label(column == Some(index), info.header.as_ref())
+                    .on_hover_text(info.header_tooltip.as_ref());
+                if res.clicked() {
+                    selected = Some(Some(index));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text(tooltip);
+
+        selected.filter(|next| *next != column)
+    }
+
     fn render_text_edit_row<V, A, C>(
         ui: &mut Ui,
         id: Uuid,
@@ -745,6 +859,7 @@ mod tests {
                 regex: false,
                 ignore_case: true,
                 word: false,
+                column: None,
             },
             "",
         );
@@ -759,6 +874,7 @@ mod tests {
                     regex: false,
                     ignore_case: true,
                     word: false,
+                    column: None,
                 },
                 "cpu",
             ),
@@ -772,6 +888,7 @@ mod tests {
                 regex: true,
                 ignore_case: true,
                 word: false,
+                column: None,
             },
             "(",
         );
@@ -786,6 +903,7 @@ mod tests {
                     regex: false,
                     ignore_case: true,
                     word: false,
+                    column: None,
                 },
                 "(",
             ),
@@ -89,7 +89,11 @@ impl FilesObserveUi {
                         .map(|f| f.1)
                         .unwrap_or(stypes::FileFormat::Text)
                 }
-                ObserveOrigin::Stream(..) => return,
+                ObserveOrigin::DirWatch(..) => {
+                    ui.label("Directory is watched. In this case you cannot attach new sources");
+                    return;
+                }
+                ObserveOrigin::Stream(..) | ObserveOrigin::Plugin(..) => return,
             };
 
             ui.allocate_ui_with_layout(
@@ -183,7 +187,18 @@ impl FilesObserveUi {
                             idx += 1;
                         }
                     }
-                    ObserveOrigin::Stream(..) => {}
+                    ObserveOrigin::DirWatch(uuid, config) => {
+                        let button = if is_tailing {
+                            ButtonAction::Stop {
+                                op_id: operation.id,
+                            }
+                        } else {
+                            ButtonAction::NewSession
+                        };
+                        self.render_file(ui, &config.dir, button, idx, uuid, actions);
+                        idx += 1;
+                    }
+                    ObserveOrigin::Stream(..) | ObserveOrigin::Plugin(..) => {}
                 }
             }
         });
@@ -0,0 +1,237 @@
+use egui::{Align, Id, Layout, RichText, Ui, Widget};
+use stypes::Transport;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        phosphor::icons,
+        ui::{buttons, visibility_tracker::VisibilityTracker},
+    },
+    host::ui::{
+        UiActions,
+        session_setup::{
+            RenderOutcome, main_config,
+            state::sources::{HttpStreamConfig, StreamConfig, WebSocketConfig},
+        },
+    },
+    session::{
+        command::{AttachSource, SessionCommand},
+        types::ObserveOperation,
+        ui::shared::SessionShared,
+    },
+};
+
+/// Configuration of the web source to attach, matching the transport of the session.
+#[derive(Debug)]
+enum AttachConfig {
+    WebSocket(WebSocketConfig),
+    HttpStream(HttpStreamConfig),
+}
+
+impl AttachConfig {
+    fn is_valid(&self) -> bool {
+        match self {
+            AttachConfig::WebSocket(config) => config.is_valid(),
+            AttachConfig::HttpStream(config) => config.is_valid(),
+        }
+    }
+
+    /// Returns the stream config to attach, resetting the URL for the next source.
+    fn take_stream_config(&mut self) -> StreamConfig {
+        match self {
+            AttachConfig::WebSocket(config) => {
+                let stream_cfg = StreamConfig::WebSocket(config.to_owned());
+                *config = WebSocketConfig::new();
+                stream_cfg
+            }
+            AttachConfig::HttpStream(config) => {
+                let stream_cfg = StreamConfig::HttpStream(config.to_owned());
+                *config = HttpStreamConfig::new();
+                stream_cfg
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpObserveUi {
+    id: Id,
+    cmd_tx: mpsc::Sender<SessionCommand>,
+    config: AttachConfig,
+    // Used to focus the URL input when the attach-connection form is shown again.
+    input_visibility: VisibilityTracker,
+}
+
+impl HttpObserveUi {
+    pub fn new_websocket(id_salt: Uuid, cmd_tx: mpsc::Sender<SessionCommand>) -> Self {
+        Self::new(
+            id_salt,
+            cmd_tx,
+            AttachConfig::WebSocket(WebSocketConfig::new()),
+        )
+    }
+
+    pub fn new_http_stream(id_salt: Uuid, cmd_tx: mpsc::Sender<SessionCommand>) -> Self {
+        Self::new(
+            id_salt,
+            cmd_tx,
+            AttachConfig::HttpStream(HttpStreamConfig::new()),
+        )
+    }
+
+    fn new(id_salt: Uuid, cmd_tx: mpsc::Sender<SessionCommand>, config: AttachConfig) -> Self {
+        let id = Id::new(format!("side_http_{id_salt}"));
+        Self {
+            id,
+            cmd_tx,
+            config,
+            input_visibility: VisibilityTracker::default(),
+        }
+    }
+
+    pub fn render_content(
+        &mut self,
+        ui: &mut Ui,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+    ) {
+        let title = match self.config {
+            AttachConfig::WebSocket(..) => "WebSocket Connections",
+            AttachConfig::HttpStream(..) => "HTTP Streams",
+        };
+        super::render_group_title(ui, title);
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        self.attach_connection(ui, actions);
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        super::render_stream_ops(
+            ui,
+            shared.observe.operations(),
+            "Connected",
+            "Disconnected",
+            |ui, op, idx| {
+                self.render_connection_item(ui, op, idx, actions);
+            },
+        );
+    }
+
+    fn attach_connection(&mut self, ui: &mut Ui, actions: &mut UiActions) {
+        super::render_attach_source(ui, self.id, "New Connection", |ui| {
+            let mut outcome = match &mut self.config {
+                AttachConfig::WebSocket(config) => main_config::http::render_websocket_connection(
+                    config,
+                    &mut self.input_visibility,
+                    ui,
+                ),
+                AttachConfig::HttpStream(config) => {
+                    main_config::http::render_http_stream_connection(
+                        config,
+                        &mut self.input_visibility,
+                        ui,
+                    )
+                }
+            };
+            ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
+                if ui
+                    .add_enabled(
+                        self.config.is_valid(),
+                        buttons::side_panel_primary("Connect"),
+                    )
+                    .clicked()
+                {
+                    outcome = RenderOutcome::StartSession;
+                }
+            });
+
+            if outcome == RenderOutcome::StartSession {
+                let cmd = SessionCommand::AttachSource {
+                    source: AttachSource::Stream(Box::new(self.config.take_stream_config())),
+                };
+
+                actions.try_send_command(&self.cmd_tx, cmd);
+            }
+        });
+    }
+
+    fn render_connection_item(
+        &self,
+        ui: &mut Ui,
+        operation: &ObserveOperation,
+        idx: usize,
+        actions: &mut UiActions,
+    ) {
+        let (source_uuid, url, mode, stream_cfg) = match &operation.origin {
+            stypes::ObserveOrigin::Stream(uuid, Transport::WebSocket(config)) => (
+                uuid,
+                &config.url,
+                config.messages.to_string(),
+                StreamConfig::WebSocket(WebSocketConfig::from(config)),
+            ),
+            stypes::ObserveOrigin::Stream(uuid, Transport::HttpStream(config)) => (
+                uuid,
+                &config.url,
+                config.format.to_string(),
+                StreamConfig::HttpStream(HttpStreamConfig::from(config)),
+            ),
+            _ => return,
+        };
+
+        super::render_observe_item(
+            ui,
+            actions,
+            idx,
+            icons::regular::GLOBE,
+            |ui| {
+                ui.label(RichText::new(url).strong());
+                ui.label(format!("({mode})"));
+            },
+            |ui, actions| {
+                if operation.phase().is_running() {
+                    let stop_res = super::get_item_button(icons::regular::STOP_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Disconnect");
+                    if stop_res.clicked() {
+                        let cmd = SessionCommand::CancelOperation { id: operation.id };
+                        actions.try_send_command(&self.cmd_tx, cmd);
+                    }
+                } else {
+                    let run_res = super::get_item_button(icons::regular::PLAY_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Reconnect");
+                    if run_res.clicked() {
+                        let cmd = SessionCommand::AttachSource {
+                            source: AttachSource::Stream(Box::new(stream_cfg.clone())),
+                        };
+
+                        actions.try_send_command(&self.cmd_tx, cmd);
+                    }
+                }
+            },
+            |ui, actions| {
+                let is_running = operation.phase().is_running();
+                let label = if is_running { "Disconnect" } else { "Connect" };
+
+                if ui.button(label).clicked() {
+                    let cmd = if is_running {
+                        SessionCommand::CancelOperation { id: operation.id }
+                    } else {
+                        SessionCommand::AttachSource {
+                            source: AttachSource::Stream(Box::new(stream_cfg.clone())),
+                        }
+                    };
+
+                    actions.try_send_command(&self.cmd_tx, cmd);
+                }
+
+                ui.separator();
+                if ui.button("Reopen in New Tab").clicked() {
+                    super::open_in_new_tab(source_uuid, actions, &self.cmd_tx);
+                }
+            },
+        );
+    }
+}
@@ -18,17 +18,29 @@ use crate::{
     },
 };
 
+use can::CanObserveUi;
 use file::FilesObserveUi;
+use http::HttpObserveUi;
+use mqtt::MqttObserveUi;
+use plugin::PluginObserveUi;
 use process::ProcessObserveUi;
+use replay::ReplayObserveUi;
 use serial::SerialObserveUi;
 use tcp::TcpObserveUi;
 use udp::UdpObserveUi;
+use unix_socket::UnixSocketObserveUi;
 
+mod can;
 mod file;
+mod http;
+mod mqtt;
+mod plugin;
 mod process;
+mod replay;
 mod serial;
 mod tcp;
 mod udp;
+mod unix_socket;
 
 const SPACE_BETWEEN_GROUPS: f32 = 5.0;
 
@@ -39,6 +51,12 @@ enum ObserveSidePanel {
     Tcp(TcpObserveUi),
     Udp(UdpObserveUi),
     Serial(SerialObserveUi),
+    UnixSocket(UnixSocketObserveUi),
+    Replay(ReplayObserveUi),
+    Mqtt(MqttObserveUi),
+    Http(HttpObserveUi),
+    Can(CanObserveUi),
+    Plugin(PluginObserveUi),
 }
 
 #[derive(Debug)]
@@ -51,7 +69,7 @@ impl ObservingUi {
         let id_salt = observe_op.id;
         use ObserveSidePanel as OSP;
         let observe_ui = match &observe_op.origin {
-            ObserveOrigin::File(..) | ObserveOrigin::Concat(..) => {
+            ObserveOrigin::File(..) | ObserveOrigin::Concat(..) | ObserveOrigin::DirWatch(..) => {
                 OSP::Files(FilesObserveUi::new(id_salt, cmd_tx))
             }
             ObserveOrigin::Stream(_, transport) => match transport {
@@ -59,7 +77,20 @@ impl ObservingUi {
                 Transport::TCP(..) => OSP::Tcp(TcpObserveUi::new(id_salt, cmd_tx)),
                 Transport::UDP(..) => OSP::Udp(UdpObserveUi::new(id_salt, cmd_tx)),
                 Transport::Serial(..) => OSP::Serial(SerialObserveUi::new(id_salt, cmd_tx)),
+                Transport::UnixSocket(..) => {
+                    OSP::UnixSocket(UnixSocketObserveUi::new(id_salt, cmd_tx))
+                }
+                Transport::Replay(..) => OSP::Replay(ReplayObserveUi::new(id_salt, cmd_tx)),
+                Transport::Mqtt(..) => OSP::Mqtt(MqttObserveUi::new(id_salt, cmd_tx)),
+                Transport::WebSocket(..) => {
+                    OSP::Http(HttpObserveUi::new_websocket(id_salt, cmd_tx))
+                }
+                Transport::HttpStream(..) => {
+                    OSP::Http(HttpObserveUi::new_http_stream(id_salt, cmd_tx))
+                }
+                Transport::SocketCan(..) => OSP::Can(CanObserveUi::new(id_salt, cmd_tx)),
             },
+            ObserveOrigin::Plugin(..) => OSP::Plugin(PluginObserveUi::new(cmd_tx)),
         };
 
         Self { observe_ui }
@@ -87,6 +118,14 @@ impl ObservingUi {
                     ObserveSidePanel::Tcp(tcp) => tcp.render_content(ui, shared, actions),
                     ObserveSidePanel::Udp(udp) => udp.render_content(ui, shared, actions),
                     ObserveSidePanel::Serial(serial) => serial.render_content(ui, shared, actions),
+                    ObserveSidePanel::UnixSocket(socket) => {
+                        socket.render_content(ui, shared, actions)
+                    }
+                    ObserveSidePanel::Replay(replay) => replay.render_content(ui, shared, actions),
+                    ObserveSidePanel::Mqtt(mqtt) => mqtt.render_content(ui, shared, actions),
+                    ObserveSidePanel::Http(http) => http.render_content(ui, shared, actions),
+                    ObserveSidePanel::Can(can) => can.render_content(ui, shared, actions),
+                    ObserveSidePanel::Plugin(plugin) => plugin.render_content(ui, shared, actions),
                 }
             });
     }
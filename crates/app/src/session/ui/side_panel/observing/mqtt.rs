@@ -0,0 +1,176 @@
+use egui::{Align, Id, Layout, RichText, Ui, Widget};
+use stypes::Transport;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        phosphor::icons,
+        ui::{buttons, visibility_tracker::VisibilityTracker},
+    },
+    host::ui::{
+        UiActions,
+        session_setup::{
+            RenderOutcome, main_config,
+            state::sources::{MqttConfig, StreamConfig},
+        },
+    },
+    session::{
+        command::{AttachSource, SessionCommand},
+        types::ObserveOperation,
+        ui::shared::SessionShared,
+    },
+};
+
+#[derive(Debug)]
+pub struct MqttObserveUi {
+    id: Id,
+    cmd_tx: mpsc::Sender<SessionCommand>,
+    config: MqttConfig,
+    // Used to focus the broker input when the attach-subscription form is shown again.
+    input_visibility: VisibilityTracker,
+}
+
+impl MqttObserveUi {
+    pub fn new(id_salt: Uuid, cmd_tx: mpsc::Sender<SessionCommand>) -> Self {
+        let id = Id::new(format!("side_mqtt_{id_salt}"));
+        Self {
+            id,
+            cmd_tx,
+            config: MqttConfig::new(),
+            input_visibility: VisibilityTracker::default(),
+        }
+    }
+
+    pub fn render_content(
+        &mut self,
+        ui: &mut Ui,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+    ) {
+        super::render_group_title(ui, "MQTT Subscriptions");
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        self.attach_subscription(ui, actions);
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        super::render_stream_ops(
+            ui,
+            shared.observe.operations(),
+            "Connected",
+            "Disconnected",
+            |ui, op, idx| {
+                self.render_mqtt_item(ui, op, idx, actions);
+            },
+        );
+    }
+
+    fn attach_subscription(&mut self, ui: &mut Ui, actions: &mut UiActions) {
+        super::render_attach_source(ui, self.id, "New Subscription", |ui| {
+            let mut outcome = main_config::mqtt::render_connection(
+                &mut self.config,
+                &mut self.input_visibility,
+                actions,
+                ui,
+            );
+            ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
+                if ui
+                    .add_enabled(
+                        self.config.is_valid(),
+                        buttons::side_panel_primary("Connect"),
+                    )
+                    .clicked()
+                {
+                    outcome = RenderOutcome::StartSession;
+                }
+            });
+
+            if outcome == RenderOutcome::StartSession {
+                let cmd = SessionCommand::AttachSource {
+                    source: AttachSource::Stream(Box::new(StreamConfig::Mqtt(
+                        self.config.to_owned(),
+                    ))),
+                };
+                // Keep the broker but get a new client ID, since brokers disconnect
+                // clients reusing the ID of another connected client.
+                let broker_url = std::mem::take(&mut self.config.broker_url);
+                self.config = MqttConfig::new();
+                self.config.broker_url = broker_url;
+                self.config.validate();
+
+                actions.try_send_command(&self.cmd_tx, cmd);
+            }
+        });
+    }
+
+    fn render_mqtt_item(
+        &self,
+        ui: &mut Ui,
+        operation: &ObserveOperation,
+        idx: usize,
+        actions: &mut UiActions,
+    ) {
+        let (source_uuid, config) = match &operation.origin {
+            stypes::ObserveOrigin::Stream(uuid, Transport::Mqtt(config)) => (uuid, config),
+            _ => return,
+        };
+
+        super::render_observe_item(
+            ui,
+            actions,
+            idx,
+            icons::regular::CLOUD,
+            |ui| {
+                ui.label(RichText::new(&config.broker_url).strong());
+                ui.label(format!("({})", config.topics.join(", ")));
+            },
+            |ui, actions| {
+                if operation.phase().is_running() {
+                    let stop_res = super::get_item_button(icons::regular::STOP_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Disconnect");
+                    if stop_res.clicked() {
+                        let cmd = SessionCommand::CancelOperation { id: operation.id };
+                        actions.try_send_command(&self.cmd_tx, cmd);
+                    }
+                } else {
+                    let run_res = super::get_item_button(icons::regular::PLAY_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Reconnect");
+                    if run_res.clicked() {
+                        let to_send = MqttConfig::from(config);
+                        let cmd = SessionCommand::AttachSource {
+                            source: AttachSource::Stream(Box::new(StreamConfig::Mqtt(to_send))),
+                        };
+
+                        actions.try_send_command(&self.cmd_tx, cmd);
+                    }
+                }
+            },
+            |ui, actions| {
+                let is_running = operation.phase().is_running();
+                let label = if is_running { "Disconnect" } else { "Connect" };
+
+                if ui.button(label).clicked() {
+                    let cmd = if is_running {
+                        SessionCommand::CancelOperation { id: operation.id }
+                    } else {
+                        let stream_cfg = StreamConfig::Mqtt(MqttConfig::from(config));
+                        SessionCommand::AttachSource {
+                            source: AttachSource::Stream(Box::new(stream_cfg)),
+                        }
+                    };
+
+                    actions.try_send_command(&self.cmd_tx, cmd);
+                }
+
+                ui.separator();
+                if ui.button("Reopen in New Tab").clicked() {
+                    super::open_in_new_tab(source_uuid, actions, &self.cmd_tx);
+                }
+            },
+        );
+    }
+}
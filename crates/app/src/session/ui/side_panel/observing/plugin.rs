@@ -0,0 +1,96 @@
+use egui::{RichText, Ui, Widget};
+use tokio::sync::mpsc;
+
+use crate::{
+    common::phosphor::icons,
+    host::ui::UiActions,
+    session::{command::SessionCommand, types::ObserveOperation, ui::shared::SessionShared},
+};
+
+#[derive(Debug)]
+pub struct PluginObserveUi {
+    cmd_tx: mpsc::Sender<SessionCommand>,
+}
+
+impl PluginObserveUi {
+    pub fn new(cmd_tx: mpsc::Sender<SessionCommand>) -> Self {
+        Self { cmd_tx }
+    }
+
+    pub fn render_content(
+        &mut self,
+        ui: &mut Ui,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+    ) {
+        super::render_group_title(ui, "Plugin Sources");
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        super::render_stream_ops(
+            ui,
+            shared.observe.operations(),
+            "Reading",
+            "Finished",
+            |ui, op, idx| {
+                self.render_plugin_item(ui, op, idx, actions);
+            },
+        );
+    }
+
+    fn render_plugin_item(
+        &self,
+        ui: &mut Ui,
+        operation: &ObserveOperation,
+        idx: usize,
+        actions: &mut UiActions,
+    ) {
+        let (source_uuid, settings) = match &operation.origin {
+            stypes::ObserveOrigin::Plugin(uuid, settings) => (uuid, settings),
+            _ => return,
+        };
+        let plugin_path = &settings.plugin_path;
+
+        super::render_observe_item(
+            ui,
+            actions,
+            idx,
+            icons::regular::PLUG,
+            |ui| {
+                let name = plugin_path
+                    .parent()
+                    .and_then(|dir| dir.file_name())
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_else(|| plugin_path.to_string_lossy());
+                ui.label(RichText::new(name).strong())
+                    .on_hover_text(plugin_path.to_string_lossy());
+            },
+            |ui, actions| {
+                // Plugin sources can't be attached to running sessions, so finished
+                // sources don't offer a restart.
+                if operation.phase().is_running() {
+                    let stop_res = super::get_item_button(icons::regular::STOP_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Stop");
+                    if stop_res.clicked() {
+                        let cmd = SessionCommand::CancelOperation { id: operation.id };
+                        actions.try_send_command(&self.cmd_tx, cmd);
+                    }
+                }
+            },
+            |ui, actions| {
+                if operation.phase().is_running() {
+                    if ui.button("Stop").clicked() {
+                        let cmd = SessionCommand::CancelOperation { id: operation.id };
+                        actions.try_send_command(&self.cmd_tx, cmd);
+                    }
+                    ui.separator();
+                }
+
+                if ui.button("Reopen in New Tab").clicked() {
+                    super::open_in_new_tab(source_uuid, actions, &self.cmd_tx);
+                }
+            },
+        );
+    }
+}
@@ -101,6 +101,10 @@ impl ProcessObserveUi {
                 |ui| host_setup::working_dir(ui, config, actions, false),
             );
 
+            ui.add_space(10.);
+
+            host_setup::options(config, ui);
+
             if outcome == RenderOutcome::StartSession {
                 let config_to_send = config.clone();
                 config.command.clear();
@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+
+use egui::{Align, DragValue, Id, Layout, RichText, Ui, Widget};
+use stypes::{ReplayControl, Transport};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    common::{phosphor::icons, ui::buttons},
+    host::ui::{
+        UiActions,
+        session_setup::{
+            RenderOutcome, main_config,
+            state::sources::{ReplayConfig, StreamConfig},
+        },
+    },
+    session::{
+        command::{AttachSource, SessionCommand},
+        types::ObserveOperation,
+        ui::shared::SessionShared,
+    },
+};
+
+#[derive(Debug)]
+pub struct ReplayObserveUi {
+    id: Id,
+    cmd_tx: mpsc::Sender<SessionCommand>,
+    config: ReplayConfig,
+    /// Operations of replays which are paused currently.
+    paused: HashSet<Uuid>,
+    /// Offset in seconds from the start of the trace for seeking.
+    seek_secs: u64,
+}
+
+impl ReplayObserveUi {
+    pub fn new(id_salt: Uuid, cmd_tx: mpsc::Sender<SessionCommand>) -> Self {
+        let id = Id::new(format!("side_replay_{id_salt}"));
+        Self {
+            id,
+            cmd_tx,
+            config: ReplayConfig::new(),
+            paused: HashSet::new(),
+            seek_secs: 0,
+        }
+    }
+
+    pub fn render_content(
+        &mut self,
+        ui: &mut Ui,
+        shared: &mut SessionShared,
+        actions: &mut UiActions,
+    ) {
+        super::render_group_title(ui, "Replayed Traces");
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        self.attach_replay(ui, actions);
+
+        ui.add_space(super::SPACE_BETWEEN_GROUPS);
+
+        super::render_stream_ops(
+            ui,
+            shared.observe.operations(),
+            "Replaying",
+            "Finished",
+            |ui, op, idx| {
+                self.render_replay_item(ui, op, idx, actions);
+            },
+        );
+    }
+
+    fn attach_replay(&mut self, ui: &mut Ui, actions: &mut UiActions) {
+        super::render_attach_source(ui, self.id, "New Replay", |ui| {
+            let mut outcome = main_config::replay::render_connection(&mut self.config, actions, ui);
+            ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
+                if ui
+                    .add_enabled(self.config.is_valid(), buttons::side_panel_primary("Start"))
+                    .clicked()
+                {
+                    outcome = RenderOutcome::StartSession;
+                }
+            });
+
+            if outcome == RenderOutcome::StartSession {
+                let cmd = SessionCommand::AttachSource {
+                    source: AttachSource::Stream(Box::new(StreamConfig::Replay(
+                        self.config.to_owned(),
+                    ))),
+                };
+
+                actions.try_send_command(&self.cmd_tx, cmd);
+            }
+        });
+    }
+
+    fn render_replay_item(
+        &mut self,
+        ui: &mut Ui,
+        operation: &ObserveOperation,
+        idx: usize,
+        actions: &mut UiActions,
+    ) {
+        let (source_uuid, config) = match &operation.origin {
+            stypes::ObserveOrigin::Stream(uuid, Transport::Replay(config)) => (uuid, config),
+            _ => return,
+        };
+        let is_running = operation.phase().is_running();
+        if !is_running {
+            self.paused.remove(&operation.id);
+        }
+        let is_paused = self.paused.contains(&operation.id);
+        let cmd_tx = &self.cmd_tx;
+        let mut seek_secs = self.seek_secs;
+        // Controls are collected while rendering and sent afterwards.
+        let mut toggle_pause = false;
+        let mut control = None;
+
+        super::render_observe_item(
+            ui,
+            actions,
+            idx,
+            icons::regular::PLAY,
+            |ui| {
+                let name = config
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_else(|| config.path.to_string_lossy());
+                ui.label(RichText::new(name).strong())
+                    .on_hover_text(config.path.to_string_lossy());
+                if is_running && is_paused {
+                    ui.label(RichText::new("Paused").small().weak());
+                }
+            },
+            |ui, actions| {
+                if is_running {
+                    let stop_res = super::get_item_button(icons::regular::STOP_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Stop");
+                    if stop_res.clicked() {
+                        let cmd = SessionCommand::CancelOperation { id: operation.id };
+                        actions.try_send_command(cmd_tx, cmd);
+                    }
+
+                    let (icon, hint) = if is_paused {
+                        (icons::regular::PLAY_CIRCLE, "Resume")
+                    } else {
+                        (icons::regular::PAUSE_CIRCLE, "Pause")
+                    };
+                    if super::get_item_button(icon)
+                        .ui(ui)
+                        .on_hover_text(hint)
+                        .clicked()
+                    {
+                        toggle_pause = true;
+                    }
+                } else {
+                    let run_res = super::get_item_button(icons::regular::PLAY_CIRCLE)
+                        .ui(ui)
+                        .on_hover_text("Replay again");
+                    if run_res.clicked() {
+                        let cmd = SessionCommand::AttachSource {
+                            source: AttachSource::Stream(Box::new(StreamConfig::Replay(
+                                ReplayConfig::from(config),
+                            ))),
+                        };
+
+                        actions.try_send_command(cmd_tx, cmd);
+                    }
+                }
+            },
+            |ui, actions| {
+                if is_running {
+                    ui.menu_button("Speed", |ui| {
+                        for speed in ReplayConfig::SPEED {
+                            if ui.button(speed.name).clicked() {
+                                control = Some(ReplayControl::SetSpeed(speed.value));
+                                ui.close();
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        DragValue::new(&mut seek_secs).suffix(" s").ui(ui);
+                        if ui
+                            .button("Seek")
+                            .on_hover_text("Continue the replay at this offset from the start")
+                            .clicked()
+                        {
+                            control = Some(ReplayControl::Seek(seek_secs * 1_000));
+                            ui.close();
+                        }
+                    });
+                    if ui.button("Restart").clicked() {
+                        control = Some(ReplayControl::Seek(0));
+                        ui.close();
+                    }
+                }
+
+                ui.separator();
+                if ui.button("Reopen in New Tab").clicked() {
+                    super::open_in_new_tab(source_uuid, actions, cmd_tx);
+                }
+            },
+        );
+
+        self.seek_secs = seek_secs;
+        if toggle_pause {
+            control = if is_paused {
+                self.paused.remove(&operation.id);
+                Some(ReplayControl::Resume)
+            } else {
+                self.paused.insert(operation.id);
+                Some(ReplayControl::Pause)
+            };
+        }
+        if let Some(control) = control {
+            let cmd = SessionCommand::ControlReplay {
+                target: operation.id,
+                control,
+            };
+            actions.try_send_command(&self.cmd_tx, cmd);
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use egui::{Align, ComboBox, DragValue, Id, Layout, RichText, TextEdit, Ui, Widget, vec2};
-use stypes::Transport;
+use egui::{Align, Color32, ComboBox, DragValue, Id, Layout, RichText, TextEdit, Ui, Widget, vec2};
+use stypes::{SourceConnectionState, Transport};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -138,6 +138,11 @@ impl SerialObserveUi {
                 ui.vertical(|ui| {
                     ui.label(RichText::new(&config.path).strong());
                     ui.label(format!("{} baud", config.baud_rate));
+                    if operation.phase().is_running()
+                        && let Some(state) = operation.source_state()
+                    {
+                        render_connection_state(ui, state);
+                    }
                 });
             },
             |ui, actions| {
@@ -326,12 +331,44 @@ impl SerialObserveUi {
             SerialConfig::EXCLUSIVE,
         );
 
+        ui.label("Read Mode");
+        render_named_value_combo(
+            ui,
+            "serial_read_mode",
+            &mut self.config.read_mode,
+            SerialConfig::READ_MODE,
+        );
+
+        ui.label("Reconnect");
+        render_named_value_combo(
+            ui,
+            "serial_reconnect",
+            &mut self.config.reconnect,
+            SerialConfig::RECONNECT,
+        );
+
         self.config.validate();
+        show_validation_message(ui, self.config.reconnect_validation_err());
     }
 }
 
 const FIELD_WIDTH: f32 = 180.0;
 
+/// Renders the connection state of a serial port which reconnects on its own.
+fn render_connection_state(ui: &mut Ui, state: &SourceConnectionState) {
+    let (icon, color) = match state {
+        SourceConnectionState::Connected => (icons::regular::PLUGS_CONNECTED, Color32::GREEN),
+        SourceConnectionState::Reconnecting { .. } => {
+            (icons::regular::PLUGS, ui.visuals().warn_fg_color)
+        }
+    };
+    ui.label(
+        RichText::new(format!("{icon} {state}"))
+            .small()
+            .color(color),
+    );
+}
+
 /// Renders a combo box for selecting one of the predefined named values.
 fn render_named_value_combo<T: Copy + PartialEq>(
     ui: &mut Ui,
@@ -1,5 +1,5 @@
 use egui::{Align, Id, Layout, RichText, Ui, Widget};
-use stypes::Transport;
+use stypes::{TcpMode, Transport};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -119,6 +119,9 @@ impl TcpObserveUi {
             icons::regular::PLUGS_CONNECTED,
             |ui| {
                 ui.label(RichText::new(&config.bind_addr).strong());
+                if config.mode == TcpMode::Listen {
+                    ui.label("(listening)");
+                }
             },
             |ui, actions| {
                 if operation.phase().is_running() {
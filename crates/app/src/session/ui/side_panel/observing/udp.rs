@@ -78,6 +78,7 @@ impl UdpObserveUi {
             if !self.config.multicasts.is_empty() {
                 main_config::udp::render_multicasts(&mut self.config, ui);
             }
+            main_config::udp::render_senders(&mut self.config, ui);
 
             ui.allocate_ui_with_layout(
                 vec2(ui.available_width(), 30.),
@@ -179,6 +180,17 @@ impl UdpObserveUi {
                     super::open_in_new_tab(source_uuid, actions, &self.cmd_tx);
                 }
             },
-        );
+        )
+        .on_hover_ui(|ui| {
+            ui.set_max_width(ui.spacing().tooltip_width);
+            if config.allowed_senders.is_empty() {
+                ui.label("All senders");
+            } else {
+                ui.label(format!("Senders: {}", config.allowed_senders.join(", ")));
+            }
+            if config.split_senders {
+                ui.label("Separate source per sender");
+            }
+        });
     }
 }
@@ -2,7 +2,7 @@ use egui::{
     Align, Button, Color32, Frame, Id, Label, Layout, Margin, Popup, RichText, Sense, Ui, Vec2,
     Widget as _,
 };
-use stypes::{ObserveOrigin, Transport};
+use stypes::{ObserveOrigin, TcpMode, Transport};
 
 use crate::{common::phosphor::icons, session::ui::shared::SessionShared};
 
@@ -11,6 +11,8 @@ pub fn render_content(shared: &SessionShared, ui: &mut Ui) {
     let single_selected_row = shared.logs.single_selected_row();
     let search_count = shared.search.search_result_count();
     let total_count = shared.logs.logs_count();
+    let hidden_count = shared.logs.hidden_count();
+    let time_indexing_pending = shared.logs.time_indexing_pending();
 
     ui.horizontal_centered(|ui| {
         Label::new(status_summary_text(
@@ -32,6 +34,31 @@ pub fn render_content(shared: &SessionShared, ui: &mut Ui) {
             ui.label(format!("Total Count: {total_count}"));
         });
 
+        if hidden_count > 0 {
+            ui.separator();
+            Label::new(format!(
+                "{} {}",
+                icons::regular::EYE_SLASH,
+                hidden_rows_text(hidden_count)
+            ))
+            .ui(ui)
+            .on_hover_text(
+                "Rows matching exclusion filters and collapsed duplicates are hidden from the \
+                 logs table.",
+            );
+        }
+
+        if let Some(pending) = time_indexing_pending {
+            ui.separator();
+            Label::new(format!(
+                "{} {}",
+                icons::regular::CLOCK,
+                time_indexing_text(pending)
+            ))
+            .ui(ui)
+            .on_hover_text("Jumps to times and time ranges cover the indexed rows only.");
+        }
+
         ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
             observe_states(shared, ui);
         });
@@ -50,6 +77,22 @@ fn status_summary_text(
     format!("{selected_row} [{selected_count}] {search_count}/{total_count}")
 }
 
+fn hidden_rows_text(hidden_count: u64) -> String {
+    if hidden_count == 1 {
+        String::from("1 row hidden")
+    } else {
+        format!("{hidden_count} rows hidden")
+    }
+}
+
+fn time_indexing_text(pending: u64) -> String {
+    if pending == 1 {
+        String::from("Indexing timestamps of 1 row")
+    } else {
+        format!("Indexing timestamps of {pending} rows")
+    }
+}
+
 fn selected_row_tooltip_text(single_selected_row: Option<u64>, selected_count: usize) -> String {
     match (single_selected_row, selected_count) {
         (Some(row), 1) => row.to_string(),
@@ -90,13 +133,27 @@ fn observe_states(shared: &SessionShared, ui: &mut Ui) {
                             String::from("concatenating"),
                             format!("{} files", files.len()),
                         ),
+                        ObserveOrigin::DirWatch(_, config) => (
+                            String::from("Directory"),
+                            format!(
+                                "Watched {}",
+                                config.dir.join(&config.pattern).to_string_lossy()
+                            ),
+                        ),
                         ObserveOrigin::Stream(_, transport) => match transport {
                             Transport::Process(config) => {
                                 (String::from("Command"), config.command.to_owned())
                             }
                             Transport::TCP(config) => (
                                 format!("TCP: {}", config.bind_addr),
-                                format!("Connected to {} via TCP", config.bind_addr),
+                                match config.mode {
+                                    TcpMode::Connect => {
+                                        format!("Connected to {} via TCP", config.bind_addr)
+                                    }
+                                    TcpMode::Listen => {
+                                        format!("Listening on {} for TCP clients", config.bind_addr)
+                                    }
+                                },
                             ),
                             Transport::UDP(config) => (
                                 format!("UDP: {}", config.bind_addr),
@@ -106,7 +163,39 @@ fn observe_states(shared: &SessionShared, ui: &mut Ui) {
                                 String::from("Serial Port"),
                                 format!("Connected to {}", config.path),
                             ),
+                            Transport::UnixSocket(config) => (
+                                String::from("Unix Socket"),
+                                format!("Connected to {} ({})", config.path, config.mode),
+                            ),
+                            Transport::Replay(config) => (
+                                String::from("Replay"),
+                                format!("Replaying {}", config.path.to_string_lossy()),
+                            ),
+                            Transport::Mqtt(config) => (
+                                format!("MQTT: {}", config.broker_url),
+                                format!(
+                                    "Subscribed to {} on {}",
+                                    config.topics.join(", "),
+                                    config.broker_url
+                                ),
+                            ),
+                            Transport::WebSocket(config) => (
+                                format!("WebSocket: {}", config.url),
+                                format!("Connected to {}", config.url),
+                            ),
+                            Transport::HttpStream(config) => (
+                                String::from("HTTP Stream"),
+                                format!("Streaming {}", config.url),
+                            ),
+                            Transport::SocketCan(config) => (
+                                format!("SocketCAN: {}", config.interface),
+                                format!("Receiving frames on {}", config.interface),
+                            ),
                         },
+                        ObserveOrigin::Plugin(_, settings) => (
+                            String::from("Plugin"),
+                            format!("Read from {}", settings.plugin_path.to_string_lossy()),
+                        ),
                     };
 
                     let title = RichText::new(title).heading().size(15.0);
@@ -132,14 +221,26 @@ fn observe_states(shared: &SessionShared, ui: &mut Ui) {
     for operation in running {
         ui.add_space(5.0);
 
+        let replay_name;
         let name = match &operation.origin {
             ObserveOrigin::File(..) => "tail",
             ObserveOrigin::Concat(..) => "concat",
+            ObserveOrigin::DirWatch(..) => "watch",
+            ObserveOrigin::Plugin(..) => "plugin",
             ObserveOrigin::Stream(_, transport) => match transport {
                 Transport::Process(config) => config.command.as_str(),
                 Transport::TCP(config) => config.bind_addr.as_str(),
                 Transport::UDP(config) => config.bind_addr.as_str(),
                 Transport::Serial(config) => config.path.as_str(),
+                Transport::UnixSocket(config) => config.path.as_str(),
+                Transport::Replay(config) => {
+                    replay_name = config.path.to_string_lossy();
+                    &replay_name
+                }
+                Transport::Mqtt(config) => config.broker_url.as_str(),
+                Transport::WebSocket(config) => config.url.as_str(),
+                Transport::HttpStream(config) => config.url.as_str(),
+                Transport::SocketCan(config) => config.interface.as_str(),
             },
         };
 
@@ -157,7 +258,9 @@ fn observe_states(shared: &SessionShared, ui: &mut Ui) {
 
 #[cfg(test)]
 mod tests {
-    use super::{selected_row_tooltip_text, status_summary_text};
+    use super::{
+        hidden_rows_text, selected_row_tooltip_text, status_summary_text, time_indexing_text,
+    };
 
     #[test]
     fn status_summary_uses_dash_without_single_selection() {
@@ -170,6 +273,18 @@ mod tests {
         assert_eq!(status_summary_text(Some(3), 1, 23, 343), "3 [1] 23/343");
     }
 
+    #[test]
+    fn hidden_rows_text_counts_rows() {
+        assert_eq!(hidden_rows_text(1), "1 row hidden");
+        assert_eq!(hidden_rows_text(42), "42 rows hidden");
+    }
+
+    #[test]
+    fn time_indexing_text_counts_rows() {
+        assert_eq!(time_indexing_text(1), "Indexing timestamps of 1 row");
+        assert_eq!(time_indexing_text(42), "Indexing timestamps of 42 rows");
+    }
+
     #[test]
     fn selected_row_tooltip_text_describes_selection_state() {
         assert_eq!(selected_row_tooltip_text(None, 0), "None");
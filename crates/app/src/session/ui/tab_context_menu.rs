@@ -18,7 +18,10 @@ impl Session {
         };
 
         match &first_operation.origin {
-            ObserveOrigin::File(..) | ObserveOrigin::Concat(..) => {
+            ObserveOrigin::File(..)
+            | ObserveOrigin::Concat(..)
+            | ObserveOrigin::DirWatch(..)
+            | ObserveOrigin::Stream(_, Transport::Replay(..)) => {
                 self.render_file_tab_menu(actions, ui)
             }
             ObserveOrigin::Stream(_, Transport::Process(..)) => {
@@ -33,6 +36,17 @@ impl Session {
             ObserveOrigin::Stream(_, Transport::Serial(..)) => {
                 self.render_serial_tab_menu(actions, ui)
             }
+            ObserveOrigin::Stream(_, Transport::UnixSocket(..)) => {
+                self.render_address_tab_menu(actions, ui, unix_socket_path)
+            }
+            ObserveOrigin::Stream(_, Transport::Mqtt(..)) => {
+                self.render_address_tab_menu(actions, ui, mqtt_broker)
+            }
+            ObserveOrigin::Stream(_, Transport::WebSocket(..) | Transport::HttpStream(..)) => {
+                self.render_address_tab_menu(actions, ui, web_url)
+            }
+            // Plugin sources and CAN interfaces have no address or path which could be copied.
+            ObserveOrigin::Plugin(..) | ObserveOrigin::Stream(_, Transport::SocketCan(..)) => false,
         }
     }
 
@@ -138,7 +152,10 @@ impl Session {
             .iter()
             .filter_map(|operation| match &operation.origin {
                 ObserveOrigin::Stream(_, transport) => address_for(transport),
-                ObserveOrigin::File(..) | ObserveOrigin::Concat(..) => None,
+                ObserveOrigin::File(..)
+                | ObserveOrigin::Concat(..)
+                | ObserveOrigin::DirWatch(..)
+                | ObserveOrigin::Plugin(..) => None,
             })
             .count();
         if address_count == 0 {
@@ -216,7 +233,13 @@ impl Session {
                         append_line(&mut text, &path.to_string_lossy(), &mut line_count);
                     }
                 }
-                ObserveOrigin::Stream(..) => {}
+                ObserveOrigin::DirWatch(_, config) => {
+                    append_line(&mut text, &config.dir.to_string_lossy(), &mut line_count);
+                }
+                ObserveOrigin::Stream(_, Transport::Replay(config)) => {
+                    append_line(&mut text, &config.path.to_string_lossy(), &mut line_count);
+                }
+                ObserveOrigin::Stream(..) | ObserveOrigin::Plugin(..) => {}
             }
         }
         text
@@ -228,7 +251,12 @@ impl Session {
             .operations()
             .iter()
             .map(|operation| match &operation.origin {
-                ObserveOrigin::File(_, _, path) => {
+                ObserveOrigin::File(_, _, path)
+                | ObserveOrigin::DirWatch(_, stypes::DirWatchConfig { dir: path, .. })
+                | ObserveOrigin::Stream(
+                    _,
+                    Transport::Replay(stypes::ReplayTransportConfig { path, .. }),
+                ) => {
                     let has_file_name = path.file_name().is_some();
                     usize::from(has_file_name)
                 }
@@ -236,7 +264,7 @@ impl Session {
                     .iter()
                     .filter(|(_, _, path)| path.file_name().is_some())
                     .count(),
-                ObserveOrigin::Stream(..) => 0,
+                ObserveOrigin::Stream(..) | ObserveOrigin::Plugin(..) => 0,
             })
             .sum()
     }
@@ -246,7 +274,12 @@ impl Session {
         let mut line_count = 0;
         for operation in self.shared.observe.operations() {
             match &operation.origin {
-                ObserveOrigin::File(_, _, path) => {
+                ObserveOrigin::File(_, _, path)
+                | ObserveOrigin::DirWatch(_, stypes::DirWatchConfig { dir: path, .. })
+                | ObserveOrigin::Stream(
+                    _,
+                    Transport::Replay(stypes::ReplayTransportConfig { path, .. }),
+                ) => {
                     if let Some(name) = path.file_name() {
                         append_line(&mut text, &name.to_string_lossy(), &mut line_count);
                     }
@@ -256,7 +289,7 @@ impl Session {
                         append_line(&mut text, &name.to_string_lossy(), &mut line_count);
                     }
                 }
-                ObserveOrigin::Stream(..) => {}
+                ObserveOrigin::Stream(..) | ObserveOrigin::Plugin(..) => {}
             }
         }
         text
@@ -302,14 +335,75 @@ impl Session {
 fn tcp_address(transport: &Transport) -> Option<&str> {
     match transport {
         Transport::TCP(config) => Some(&config.bind_addr),
-        Transport::Process(..) | Transport::UDP(..) | Transport::Serial(..) => None,
+        Transport::Process(..)
+        | Transport::UDP(..)
+        | Transport::Serial(..)
+        | Transport::UnixSocket(..)
+        | Transport::Replay(..)
+        | Transport::Mqtt(..)
+        | Transport::WebSocket(..)
+        | Transport::HttpStream(..)
+        | Transport::SocketCan(..) => None,
     }
 }
 
 fn udp_address(transport: &Transport) -> Option<&str> {
     match transport {
         Transport::UDP(config) => Some(&config.bind_addr),
-        Transport::Process(..) | Transport::TCP(..) | Transport::Serial(..) => None,
+        Transport::Process(..)
+        | Transport::TCP(..)
+        | Transport::Serial(..)
+        | Transport::UnixSocket(..)
+        | Transport::Replay(..)
+        | Transport::Mqtt(..)
+        | Transport::WebSocket(..)
+        | Transport::HttpStream(..)
+        | Transport::SocketCan(..) => None,
+    }
+}
+
+fn unix_socket_path(transport: &Transport) -> Option<&str> {
+    match transport {
+        Transport::UnixSocket(config) => Some(&config.path),
+        Transport::Process(..)
+        | Transport::TCP(..)
+        | Transport::UDP(..)
+        | Transport::Serial(..)
+        | Transport::Replay(..)
+        | Transport::Mqtt(..)
+        | Transport::WebSocket(..)
+        | Transport::HttpStream(..)
+        | Transport::SocketCan(..) => None,
+    }
+}
+
+fn mqtt_broker(transport: &Transport) -> Option<&str> {
+    match transport {
+        Transport::Mqtt(config) => Some(&config.broker_url),
+        Transport::Process(..)
+        | Transport::TCP(..)
+        | Transport::UDP(..)
+        | Transport::Serial(..)
+        | Transport::UnixSocket(..)
+        | Transport::Replay(..)
+        | Transport::WebSocket(..)
+        | Transport::HttpStream(..)
+        | Transport::SocketCan(..) => None,
+    }
+}
+
+fn web_url(transport: &Transport) -> Option<&str> {
+    match transport {
+        Transport::WebSocket(config) => Some(&config.url),
+        Transport::HttpStream(config) => Some(&config.url),
+        Transport::Process(..)
+        | Transport::TCP(..)
+        | Transport::UDP(..)
+        | Transport::Serial(..)
+        | Transport::UnixSocket(..)
+        | Transport::Replay(..)
+        | Transport::Mqtt(..)
+        | Transport::SocketCan(..) => None,
     }
 }
 
@@ -85,6 +85,11 @@ pub enum InputSource {
         /// The address to bind the connection to.
         #[arg(index = 1)]
         address: String,
+        /// Listen on the address for incoming clients instead of connecting to a server.
+        /// Clients are served one after another, and the output of all of them is
+        /// written to the same output file.
+        #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
+        listen: bool,
         /// Time interval (in seconds) to print current status.
         #[arg(short, long = "update-interval", default_value_t = 5)]
         update_interval: u64,
@@ -165,8 +170,9 @@ impl Cli {
         match input {
             InputSource::Tcp {
                 address: _,
+                listen,
                 update_interval,
-                max_reconnect_count: _,
+                max_reconnect_count,
                 reconnect_interval: interval_reconnect,
                 keep_alive,
             } => {
@@ -182,6 +188,10 @@ impl Cli {
                     keep_alive.is_none_or(|v| v > 0),
                     "Keepalive time must be greater than zero when set"
                 );
+                ensure!(
+                    !*listen || max_reconnect_count.is_none(),
+                    "Reconnecting can't be used in listen mode"
+                );
             }
             InputSource::Udp {
                 address: _,
@@ -26,13 +26,15 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
 
             let fibex_metadata = session::parser::dlt::create_fibex_metadata(fibex_files);
 
-            let parser = parsers::dlt::DltParser::new(
-                None,
-                fibex_metadata.as_ref(),
-                None,
-                None,
-                with_storage_header,
-            );
+            let create_parser = || {
+                parsers::dlt::DltParser::new(
+                    None,
+                    fibex_metadata.as_ref(),
+                    None,
+                    None,
+                    with_storage_header,
+                )
+            };
 
             // Move to next part initializing the input source and starting the session.
             match cli.output_format {
@@ -40,7 +42,7 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
                     let binary_formatter = MsgBinaryFormatter::default();
 
                     start_session(
-                        parser,
+                        create_parser,
                         input,
                         binary_formatter,
                         cli.output_path,
@@ -57,8 +59,14 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
                         cli.text_args_separator,
                     );
 
-                    start_session(parser, input, text_formatter, cli.output_path, cancel_token)
-                        .await?;
+                    start_session(
+                        create_parser,
+                        input,
+                        text_formatter,
+                        cli.output_path,
+                        cancel_token,
+                    )
+                    .await?;
                 }
             };
         }
@@ -13,7 +13,7 @@ use sources::{
     binary::raw::BinaryByteSource,
     socket::{
         tcp::{
-            KeepAliveConfig, TcpSource,
+            KeepAliveConfig, TcpServer, TcpSource,
             reconnect::{ReconnectInfo, ReconnectStateMsg},
         },
         udp::UdpSource,
@@ -30,25 +30,70 @@ mod socket;
 /// Starts session with the given parser and the provided infos about input source
 /// and other session parameters.
 ///
-/// * `parser`: Parser instance to be used for parsing the bytes in the session.
+/// * `create_parser`: Creates the parser instance to be used for parsing the bytes in the
+///   session. It's called once for each client when listening for TCP connections.
 /// * `input_source`: The input source info for the session.
 /// * `msg_formatter`: The formatter and writer for messages in the session.
 /// * `output_path`: The path for the output file path.
 /// * `cancel_token`: CancellationToken.
-pub async fn start_session<P, W>(
-    parser: P,
+pub async fn start_session<F, P, W>(
+    create_parser: F,
     input_source: InputSource,
-    msg_formatter: W,
+    mut msg_formatter: W,
     output_path: PathBuf,
     cancel_token: CancellationToken,
 ) -> anyhow::Result<()>
 where
+    F: Fn() -> P,
     P: parsers::Parser,
     W: MessageFormatter,
 {
     match input_source {
         InputSource::Tcp {
             address,
+            listen: true,
+            update_interval,
+            max_reconnect_count: _,
+            reconnect_interval: _,
+            keep_alive,
+        } => {
+            // Clients are reconnecting to the listener on their own.
+            let (_state_tx, state_rx) = tokio::sync::watch::channel(ReconnectStateMsg::Connected);
+
+            let update_interval = Duration::from_secs(update_interval);
+
+            let keepalive = keep_alive.map(|keepalive_secs| {
+                let keep_duration = Duration::from_secs(keepalive_secs);
+                KeepAliveConfig::new(keep_duration, keep_duration)
+            });
+
+            let server = TcpServer::bind(&address, keepalive)
+                .await
+                .context("Initializing TCP listener failed")?;
+
+            while !cancel_token.is_cancelled() {
+                println!("Waiting for TCP clients on {address}...");
+                let (source, peer) = tokio::select! {
+                    _ = cancel_token.cancelled() => break,
+                    accepted = server.accept() => accepted.context("Accepting TCP client failed")?,
+                };
+                println!("Client {peer} connected");
+
+                socket::run_session(
+                    create_parser(),
+                    source,
+                    output_path.clone(),
+                    &mut msg_formatter,
+                    state_rx.clone(),
+                    update_interval,
+                    cancel_token.clone(),
+                )
+                .await?;
+            }
+        }
+        InputSource::Tcp {
+            address,
+            listen: false,
             update_interval,
             max_reconnect_count,
             reconnect_interval,
@@ -75,10 +120,10 @@ where
                 .context("Initializing TCP connection failed")?;
 
             socket::run_session(
-                parser,
+                create_parser(),
                 source,
                 output_path,
-                msg_formatter,
+                &mut msg_formatter,
                 state_rx,
                 update_interval,
                 cancel_token,
@@ -92,17 +137,17 @@ where
             // UDP connections inherently support auto-connecting by design.
             let (_state_tx, state_rx) = tokio::sync::watch::channel(ReconnectStateMsg::Connected);
 
-            let source = UdpSource::new(address, Vec::new())
+            let source = UdpSource::new(address, Vec::new(), &[])
                 .await
                 .context("Initializing UDP connection failed")?;
 
             let temp_interval = Duration::from_secs(update_interval);
 
             socket::run_session(
-                parser,
+                create_parser(),
                 source,
                 output_path,
-                msg_formatter,
+                &mut msg_formatter,
                 state_rx,
                 temp_interval,
                 cancel_token,
@@ -114,7 +159,14 @@ where
             let reader = BufReader::new(&file);
             let source = BinaryByteSource::new(reader);
 
-            file::run_session(parser, source, output_path, msg_formatter, cancel_token).await?;
+            file::run_session(
+                create_parser(),
+                source,
+                output_path,
+                msg_formatter,
+                cancel_token,
+            )
+            .await?;
         }
     }
     Ok(())
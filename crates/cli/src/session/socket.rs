@@ -27,7 +27,7 @@ pub async fn run_session<P, D, W>(
     parser: P,
     bytesource: D,
     output_path: PathBuf,
-    mut msg_formatter: W,
+    msg_formatter: &mut W,
     mut state_rc: watch::Receiver<ReconnectStateMsg>,
     update_interval: Duration,
     cancel_token: CancellationToken,
@@ -30,6 +30,16 @@ impl LogMessage for FormattableMessage<'_> {
         writer.write_all(&bytes)?;
         Ok(len)
     }
+
+    /// Uses the storage header time, which is the capture time for stored and
+    /// streamed messages. The ECU time of messages without one counts from the start of
+    /// the ECU, so these messages have no timestamp.
+    fn timestamp(&self) -> Option<u64> {
+        self.message.storage_header.as_ref().map(|header| {
+            u64::from(header.timestamp.seconds) * 1000
+                + u64::from(header.timestamp.microseconds) / 1000
+        })
+    }
 }
 
 #[derive(Debug, Serialize)]
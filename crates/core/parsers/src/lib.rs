@@ -124,6 +124,15 @@ pub trait LogMessage: Display + Serialize {
     /// Serializes a message directly into a Writer
     /// returns the size of the serialized message
     fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error>;
+
+    /// Returns the time of the message in milliseconds since the Unix epoch, if the
+    /// format carries one.
+    ///
+    /// Timestamps without a date (time of day only) are reported on the first day of the
+    /// epoch, so they still compare correctly within the same trace.
+    fn timestamp(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// A trait for parsers that extract at most one item per call.
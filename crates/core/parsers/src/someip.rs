@@ -342,7 +342,12 @@ impl SingleParser for SomeipParser {
             input,
             timestamp,
         )
-        .map(|(rest, message)| ParseOutput::new(rest, message.map(ParseYield::from)))
+        .map(|(rest, message)| {
+            ParseOutput::new(
+                rest,
+                message.map(|message| message.with_timestamp(timestamp).into()),
+            )
+        })
     }
 }
 
@@ -533,12 +538,25 @@ fn rpc_message_string(
 pub struct SomeipLogMessage {
     description: String,
     bytes: Vec<u8>,
+    /// Capture time of the message in milliseconds, if provided by the source.
+    #[serde(skip)]
+    timestamp: Option<u64>,
 }
 
 impl SomeipLogMessage {
     /// Creates a new log message for the given values.
     pub fn from(description: String, bytes: Vec<u8>) -> Self {
-        SomeipLogMessage { description, bytes }
+        SomeipLogMessage {
+            description,
+            bytes,
+            timestamp: None,
+        }
+    }
+
+    /// Sets the capture time of the message in milliseconds.
+    pub fn with_timestamp(mut self, timestamp: Option<u64>) -> Self {
+        self.timestamp = timestamp;
+        self
     }
 }
 
@@ -547,6 +565,10 @@ impl LogMessage for SomeipLogMessage {
         writer.write_all(&self.bytes)?;
         Ok(self.bytes.len())
     }
+
+    fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
 }
 
 impl Display for SomeipLogMessage {
@@ -686,6 +708,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_keeps_capture_timestamp() {
+        let input: &[u8] = &[
+            0xFF, 0xFF, 0x00, 0x00, // serviceId(u16), methodId(u16)
+            0x00, 0x00, 0x00, 0x08, // length(u32)
+            0xDE, 0xAD, 0xBE, 0xEF, // clientId(u16), sessionId(u16)
+            0x01, 0x01, 0x01, 0x00, // proto(u8), version(u8), messageType,(u8) returnCode(u8)
+        ];
+
+        let mut parser = SomeipParser::new(None);
+
+        let ParseOutput { message, .. } =
+            parser.parse_item(input, Some(1_700_000_000_123)).unwrap();
+
+        if let ParseYield::Message(item) = message.unwrap() {
+            assert_eq!(item.timestamp(), Some(1_700_000_000_123));
+        } else {
+            panic!("unexpected parse yield");
+        }
+    }
+
     #[test]
     fn parse_cookie_server() {
         let input: &[u8] = &[
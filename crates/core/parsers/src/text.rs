@@ -1,6 +1,11 @@
 use crate::{Error, LogMessage, ParseOutput, ParseYield, SingleParser};
+use chrono::NaiveDate;
+use regex::Regex;
 use serde::Serialize;
-use std::{fmt, io::Write};
+use std::{fmt, io::Write, sync::LazyLock};
+
+/// Number of leading bytes of a line searched for a timestamp.
+const TIMESTAMP_SCAN_LEN: usize = 64;
 
 pub struct StringTokenizer {}
 
@@ -21,6 +26,78 @@ impl LogMessage for StringMessage {
         writer.write_all(self.content.as_bytes())?;
         Ok(len)
     }
+
+    fn timestamp(&self) -> Option<u64> {
+        parse_timestamp(&self.content)
+    }
+}
+
+/// Looks for a timestamp near the start of a text line and returns it in milliseconds
+/// since the Unix epoch.
+///
+/// Dates like `2024-05-01 14:03:10.200` are recognized with `-`, `/` or `.` as date
+/// separator, `T` or a space before the time and an optional `Z` or UTC offset. Lines
+/// carrying the time of day only, like `14:03:10.200`, are reported on the first day
+/// of the epoch.
+pub fn parse_timestamp(line: &str) -> Option<u64> {
+    static DATE_TIME: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"(\d{4})[-/.](\d{2})[-/.](\d{2})[T ](\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?(?: ?(Z|[+-]\d{2}:?\d{2}))?",
+        )
+        .expect("Date time pattern is static and must compile")
+    });
+    static TIME: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"(?:^|[^\d:])(\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?")
+            .expect("Time pattern is static and must compile")
+    });
+    let mut end = line.len().min(TIMESTAMP_SCAN_LEN);
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = &line[..end];
+    if let Some(caps) = DATE_TIME.captures(head) {
+        let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+        let date = NaiveDate::from_ymd_opt(num(1)? as i32, num(2)?, num(3)?)?;
+        let time = date.and_hms_milli_opt(
+            num(4)?,
+            num(5)?,
+            num(6)?,
+            caps.get(7).map_or(0, |m| fraction_ms(m.as_str())),
+        )?;
+        let offset_ms = caps.get(8).map_or(Some(0), |m| utc_offset_ms(m.as_str()))?;
+        return u64::try_from(time.and_utc().timestamp_millis() - offset_ms).ok();
+    }
+    let caps = TIME.captures(head)?;
+    let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u64>().ok());
+    let (hours, minutes, seconds) = (num(1)?, num(2)?, num(3)?);
+    if hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    let ms = caps.get(4).map_or(0, |m| fraction_ms(m.as_str()));
+    Some((hours * 3600 + minutes * 60 + seconds) * 1000 + u64::from(ms))
+}
+
+/// Converts the digits of a fraction of a second into milliseconds.
+fn fraction_ms(digits: &str) -> u32 {
+    digits
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(3)
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+/// Converts `Z`, `+hh:mm` or `+hhmm` into an offset in milliseconds.
+fn utc_offset_ms(offset: &str) -> Option<i64> {
+    if offset == "Z" {
+        return Some(0);
+    }
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+    let hours: i64 = digits.get(..2)?.parse().ok()?;
+    let minutes: i64 = digits.get(2..4)?.parse().ok()?;
+    Some(sign * (hours * 60 + minutes) * 60_000)
 }
 
 impl SingleParser for StringTokenizer {
@@ -106,6 +183,30 @@ mod tests {
         assert!(items_iter.next().is_none());
     }
 
+    #[test]
+    fn timestamps_are_parsed_from_line_start() {
+        for (line, expected) in [
+            (
+                "2024-05-01 14:03:10.200 INFO started",
+                Some(1_714_572_190_200),
+            ),
+            ("2024-05-01T14:03:10Z started", Some(1_714_572_190_000)),
+            (
+                "[2024/05/01 16:03:10,2 +02:00] started",
+                Some(1_714_572_190_200),
+            ),
+            (
+                "05-01 14:03:10.200  1234  1234 I Tag: started",
+                Some(50_590_200),
+            ),
+            ("14:03:10 started", Some(50_590_000)),
+            ("started at 25:03:10", None),
+            ("no time here", None),
+        ] {
+            assert_eq!(parse_timestamp(line), expected, "line: {line:?}");
+        }
+    }
+
     #[test]
     fn trailing_line_without_newline() {
         let mut parser = StringTokenizer {};
@@ -0,0 +1,300 @@
+//! Includes the comparison of two logs by their messages, which finds the messages new or
+//! missing in the other log and the messages changed in frequency or order.
+//!
+//! Lines are compared after [`normalize`], so the lines of the same message are aligned
+//! regardless of their numbers, ids and timestamps. The order of messages is the order of
+//! their first appearance: the longest sequence of messages appearing in the same order in
+//! both logs stays in place, and the other messages moved.
+
+use std::{collections::HashMap, io, path::Path};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::{lines::LineReader, templates::normalize};
+
+/// One of the compared logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The log compared with the other one.
+    Left,
+    /// The other log.
+    Right,
+}
+
+impl Side {
+    fn index(self) -> usize {
+        match self {
+            Self::Left => 0,
+            Self::Right => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Occurrences {
+    count: u64,
+    first_row: u64,
+    last_row: u64,
+}
+
+impl From<Occurrences> for stypes::LogDiffSide {
+    fn from(occurrences: Occurrences) -> Self {
+        Self {
+            count: occurrences.count,
+            first_row: occurrences.first_row,
+            last_row: occurrences.last_row,
+        }
+    }
+}
+
+/// Compares the messages of two logs.
+#[derive(Debug, Default)]
+pub struct LogComparer {
+    /// Indexes of the normalized messages.
+    templates: HashMap<String, usize>,
+    /// Occurrences of each normalized message in the left and the right log.
+    occurrences: Vec<[Option<Occurrences>; 2]>,
+    /// Normalized messages in the order of their first appearance in each log.
+    order: [Vec<usize>; 2],
+    rows: [u64; 2],
+}
+
+impl LogComparer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the line at the row of the log. Blank lines are left out.
+    pub fn add(&mut self, side: Side, line: &str, row: u64) {
+        let side = side.index();
+        self.rows[side] += 1;
+        let template = normalize(line);
+        if template.is_empty() {
+            return;
+        }
+        let index = match self.templates.get(&template) {
+            Some(index) => *index,
+            None => {
+                self.templates.insert(template, self.occurrences.len());
+                self.occurrences.push([None, None]);
+                self.occurrences.len() - 1
+            }
+        };
+        match &mut self.occurrences[index][side] {
+            Some(occurrences) => {
+                occurrences.count += 1;
+                occurrences.last_row = row;
+            }
+            empty => {
+                *empty = Some(Occurrences {
+                    count: 1,
+                    first_row: row,
+                    last_row: row,
+                });
+                self.order[side].push(index);
+            }
+        }
+    }
+
+    /// Adds the lines of the file to the log, up to `rows` lines if given.
+    ///
+    /// Returns `false` if the comparison was cancelled.
+    pub fn add_file(
+        &mut self,
+        side: Side,
+        path: &Path,
+        rows: Option<u64>,
+        cancel: &CancellationToken,
+    ) -> io::Result<bool> {
+        LineReader::open(path, cancel)?.read(rows.unwrap_or(u64::MAX), |row, line| {
+            self.add(side, line, row);
+            true
+        })
+    }
+
+    /// Returns the differences of the right log from the left log: the added messages
+    /// first, followed by the removed, the changed and the moved messages.
+    pub fn into_diff(self) -> stypes::LogDiff {
+        let moved = self.moved();
+        let mut texts = vec![String::new(); self.occurrences.len()];
+        for (template, index) in self.templates {
+            texts[index] = template;
+        }
+        let mut unchanged = 0;
+        let mut entries = Vec::new();
+        for (index, (template, [left, right])) in
+            texts.into_iter().zip(self.occurrences).enumerate()
+        {
+            let kind = match (left, right) {
+                (None, _) => stypes::LogDiffKind::Added,
+                (_, None) => stypes::LogDiffKind::Removed,
+                (Some(left), Some(right)) if left.count != right.count => {
+                    stypes::LogDiffKind::Changed
+                }
+                _ if moved[index] => stypes::LogDiffKind::Moved,
+                _ => {
+                    unchanged += 1;
+                    continue;
+                }
+            };
+            entries.push(stypes::LogDiffEntry {
+                kind,
+                template,
+                left: left.map(Into::into),
+                right: right.map(Into::into),
+            });
+        }
+        entries.sort_by_key(|entry| (kind_order(entry.kind), sort_key(entry)));
+        stypes::LogDiff {
+            entries,
+            left_rows: self.rows[0],
+            right_rows: self.rows[1],
+            unchanged,
+        }
+    }
+
+    /// Returns whether each message in both logs first appears out of the order of the
+    /// other messages in both logs.
+    fn moved(&self) -> Vec<bool> {
+        let in_both = |index: &&usize| self.occurrences[**index].iter().all(Option::is_some);
+        let mut right_order = vec![0; self.occurrences.len()];
+        for (position, index) in self.order[1].iter().filter(in_both).enumerate() {
+            right_order[*index] = position;
+        }
+        let left: Vec<usize> = self.order[0].iter().filter(in_both).copied().collect();
+        let positions: Vec<usize> = left.iter().map(|index| right_order[*index]).collect();
+        let mut moved = vec![false; self.occurrences.len()];
+        for index in &left {
+            moved[*index] = true;
+        }
+        for kept in longest_increasing(&positions) {
+            moved[left[kept]] = false;
+        }
+        moved
+    }
+}
+
+fn kind_order(kind: stypes::LogDiffKind) -> u8 {
+    match kind {
+        stypes::LogDiffKind::Added => 0,
+        stypes::LogDiffKind::Removed => 1,
+        stypes::LogDiffKind::Changed => 2,
+        stypes::LogDiffKind::Moved => 3,
+    }
+}
+
+/// Orders the changed messages by their difference in number, the largest first, and
+/// the other messages by their first appearance.
+fn sort_key(entry: &stypes::LogDiffEntry) -> (i64, u64) {
+    let count = |side: &Option<stypes::LogDiffSide>| side.as_ref().map_or(0, |side| side.count);
+    let first_row = entry
+        .left
+        .as_ref()
+        .or(entry.right.as_ref())
+        .map_or(0, |side| side.first_row);
+    match entry.kind {
+        stypes::LogDiffKind::Changed => (
+            -(count(&entry.left).abs_diff(count(&entry.right)) as i64),
+            first_row,
+        ),
+        _ => (0, first_row),
+    }
+}
+
+/// Returns the indexes of a longest strictly increasing subsequence of the values.
+fn longest_increasing(values: &[usize]) -> Vec<usize> {
+    // Index of the smallest last value of the increasing subsequences of each length.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (index, value) in values.iter().enumerate() {
+        let length = tails.partition_point(|tail| values[*tail] < *value);
+        previous[index] = length.checked_sub(1).map(|length| tails[length]);
+        if length == tails.len() {
+            tails.push(index);
+        } else {
+            tails[length] = index;
+        }
+    }
+    let mut sequence = Vec::with_capacity(tails.len());
+    let mut next = tails.last().copied();
+    while let Some(index) = next {
+        sequence.push(index);
+        next = previous[index];
+    }
+    sequence.reverse();
+    sequence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogComparer, Side, longest_increasing};
+
+    fn compare(left: &[&str], right: &[&str]) -> stypes::LogDiff {
+        let mut comparer = LogComparer::new();
+        for (row, line) in left.iter().enumerate() {
+            comparer.add(Side::Left, line, row as u64);
+        }
+        for (row, line) in right.iter().enumerate() {
+            comparer.add(Side::Right, line, row as u64);
+        }
+        comparer.into_diff()
+    }
+
+    fn kinds(diff: &stypes::LogDiff) -> Vec<(stypes::LogDiffKind, &str)> {
+        diff.entries
+            .iter()
+            .map(|entry| (entry.kind, entry.template.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn numbers_and_timestamps_are_ignored() {
+        let diff = compare(
+            &["10:00:01.120 boot took 15 ms", "", "ready"],
+            &["11:30:07.009 boot took 230 ms", "ready"],
+        );
+
+        assert!(diff.entries.is_empty());
+        assert_eq!(diff.unchanged, 2);
+        assert_eq!((diff.left_rows, diff.right_rows), (3, 2));
+    }
+
+    #[test]
+    fn new_missing_and_changed_messages_are_reported() {
+        let diff = compare(
+            &["start", "retry 1", "legacy init", "done"],
+            &["start", "retry 1", "retry 2", "retry 3", "net up", "done"],
+        );
+
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                (stypes::LogDiffKind::Added, "net up"),
+                (stypes::LogDiffKind::Removed, "legacy init"),
+                (stypes::LogDiffKind::Changed, "retry <*>"),
+            ]
+        );
+        let changed = &diff.entries[2];
+        assert_eq!(changed.left.as_ref().unwrap().count, 1);
+        let right = changed.right.as_ref().unwrap();
+        assert_eq!((right.count, right.first_row, right.last_row), (3, 1, 3));
+        assert_eq!(diff.entries[0].right.as_ref().unwrap().first_row, 4);
+        assert!(diff.entries[0].left.is_none());
+        assert_eq!(diff.unchanged, 2);
+    }
+
+    #[test]
+    fn messages_out_of_order_are_moved() {
+        let diff = compare(&["a", "b", "c", "d"], &["a", "c", "d", "b"]);
+
+        assert_eq!(kinds(&diff), vec![(stypes::LogDiffKind::Moved, "b")]);
+        assert_eq!(diff.unchanged, 3);
+    }
+
+    #[test]
+    fn longest_increasing_keeps_the_longest_order() {
+        assert_eq!(longest_increasing(&[0, 3, 1, 2]), vec![0, 2, 3]);
+        assert_eq!(longest_increasing(&[2, 1, 0]), vec![2]);
+        assert!(longest_increasing(&[]).is_empty());
+    }
+}
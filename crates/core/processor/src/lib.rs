@@ -14,12 +14,15 @@ extern crate indexer_base;
 #[macro_use]
 extern crate log;
 
+pub mod compare;
 pub mod export;
 pub mod grabber;
+pub mod lines;
 pub mod map;
 pub mod processor;
 pub mod producer;
 pub mod search;
+pub mod templates;
 pub mod text_source;
 
 #[cfg(test)]
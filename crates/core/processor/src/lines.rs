@@ -0,0 +1,151 @@
+//! Includes the reading of the lines of files, which is shared by the analyses reading
+//! whole session files or logs, like the template mining and the comparison of logs.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Seek, SeekFrom},
+    path::Path,
+};
+
+use tokio_util::sync::CancellationToken;
+
+/// Lines read between checks of the cancellation.
+const CANCEL_CHECK_LINES: u64 = 10_000;
+
+/// Reads the lines of a file, without their line breaks, together with their rows.
+pub struct LineReader<'a> {
+    reader: BufReader<File>,
+    cancel: &'a CancellationToken,
+    /// Row of the next line.
+    row: u64,
+    /// Offset of the next line in the file.
+    offset: u64,
+    /// Whether a last line without a line break is skipped.
+    complete: bool,
+    buffer: Vec<u8>,
+}
+
+impl<'a> LineReader<'a> {
+    /// Opens the file to read its lines from the first one.
+    pub fn open(path: &Path, cancel: &'a CancellationToken) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(File::open(path)?),
+            cancel,
+            row: 0,
+            offset: 0,
+            complete: false,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Continues reading with the line of the given row, which starts at `offset`.
+    pub fn starting_at(mut self, row: u64, offset: u64) -> io::Result<Self> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.row = row;
+        self.offset = offset;
+        Ok(self)
+    }
+
+    /// Skips a last line without a line break, as it can still be being written.
+    pub fn complete_lines(mut self) -> Self {
+        self.complete = true;
+        self
+    }
+
+    /// Returns the row of the next line.
+    pub fn row(&self) -> u64 {
+        self.row
+    }
+
+    /// Returns the offset of the next line in the file.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Passes the lines up to the row `end` to `line` with their rows. Reading stops
+    /// early at the end of the file or once `line` returns `false`.
+    ///
+    /// Returns `false` if reading was cancelled.
+    pub fn read(&mut self, end: u64, mut line: impl FnMut(u64, &str) -> bool) -> io::Result<bool> {
+        while self.row < end {
+            if self.row.is_multiple_of(CANCEL_CHECK_LINES) && self.cancel.is_cancelled() {
+                return Ok(false);
+            }
+            self.buffer.clear();
+            let read = self.reader.read_until(b'\n', &mut self.buffer)?;
+            if read == 0 || (self.complete && self.buffer.last() != Some(&b'\n')) {
+                break;
+            }
+            let row = self.row;
+            self.row += 1;
+            self.offset += read as u64;
+            let text = String::from_utf8_lossy(&self.buffer);
+            if !line(row, text.trim_end_matches(['\r', '\n'])) {
+                break;
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tokio_util::sync::CancellationToken;
+
+    use super::LineReader;
+
+    fn read(reader: &mut LineReader, end: u64) -> Vec<(u64, String)> {
+        let mut lines = Vec::new();
+        reader
+            .read(end, |row, line| {
+                lines.push((row, line.to_owned()));
+                true
+            })
+            .unwrap();
+        lines
+    }
+
+    #[test]
+    fn lines_are_read_from_the_given_row() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"first\r\nsecond\nthird\nfourth").unwrap();
+        let cancel = CancellationToken::new();
+
+        let mut reader = LineReader::open(file.path(), &cancel).unwrap();
+        assert_eq!(
+            read(&mut reader, u64::MAX),
+            vec![
+                (0, String::from("first")),
+                (1, String::from("second")),
+                (2, String::from("third")),
+                (3, String::from("fourth")),
+            ]
+        );
+
+        let mut reader = LineReader::open(file.path(), &cancel)
+            .unwrap()
+            .starting_at(1, 7)
+            .unwrap()
+            .complete_lines();
+        assert_eq!(read(&mut reader, 2), vec![(1, String::from("second"))]);
+        assert_eq!(
+            read(&mut reader, u64::MAX),
+            vec![(2, String::from("third"))]
+        );
+        assert_eq!((reader.row(), reader.offset()), (3, 20));
+    }
+
+    #[test]
+    fn cancelled_reading_stops() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"first\n").unwrap();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let mut reader = LineReader::open(file.path(), &cancel).unwrap();
+        assert!(!reader.read(u64::MAX, |_, _| true).unwrap());
+        assert_eq!(reader.row(), 0);
+    }
+}
@@ -139,6 +139,9 @@ impl ByteSource for MockByteSource {
         let bytes = match &msg {
             stypes::SdeRequest::WriteText(text) => text.as_bytes(),
             stypes::SdeRequest::WriteBytes(bytes) => bytes,
+            stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => {
+                return Err(Error::NotSupported);
+            }
         };
         Ok(stypes::SdeResponse { bytes: bytes.len() })
     }
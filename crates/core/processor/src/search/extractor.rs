@@ -7,10 +7,23 @@ use itertools::Itertools;
 use regex::Regex;
 use std::{path::PathBuf, str::FromStr};
 
-fn get_extracted_value(index: u64, input: &str, filters: &[Regex]) -> stypes::ExtractedMatchValue {
+/// Extracts the captured values of the filters matching the line.
+///
+/// Returns `None` if no filter matches within its column.
+fn get_extracted_value(
+    index: u64,
+    input: &str,
+    filters: &[SearchFilter],
+    regexs: &[Regex],
+) -> Option<stypes::ExtractedMatchValue> {
     let mut values: Vec<(usize, Vec<String>)> = vec![];
-    for (filter_index, filter) in filters.iter().enumerate() {
-        for caps in filter.captures_iter(input) {
+    let mut matched = false;
+    for (filter_index, (filter, regex)) in filters.iter().zip(regexs).enumerate() {
+        let Some(input) = filter.scope(input) else {
+            continue;
+        };
+        for caps in regex.captures_iter(input) {
+            matched = true;
             // Element on 0 always is the whole match. Here we don't need it
             let matches: Vec<String> = caps
                 .iter()
@@ -25,8 +38,9 @@ fn get_extracted_value(index: u64, input: &str, filters: &[Regex]) -> stypes::Ex
             }
         }
     }
-    stypes::ExtractedMatchValue { index, values }
+    matched.then_some(stypes::ExtractedMatchValue { index, values })
 }
+
 pub struct MatchesExtractor {
     pub file_path: PathBuf,
     filters: Vec<SearchFilter>,
@@ -65,7 +79,7 @@ impl MatchesExtractor {
                 &regex_matcher,
                 &self.file_path,
                 UTF8(|lnum, line| {
-                    values.push(get_extracted_value(lnum - 1, line, &regexs));
+                    values.extend(get_extracted_value(lnum - 1, line, &self.filters, &regexs));
                     Ok(true)
                 }),
             )
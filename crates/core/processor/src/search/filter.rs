@@ -1,3 +1,4 @@
+use parsers::COLUMN_SEPARATOR;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -9,6 +10,9 @@ pub struct SearchFilter {
     is_regex: bool,
     ignore_case: bool,
     is_word: bool,
+    /// Index of the column the filter is limited to, or `None` to match whole lines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
 }
 
 impl SearchFilter {
@@ -22,6 +26,7 @@ impl SearchFilter {
             is_regex: false,
             ignore_case: false,
             is_word: false,
+            column: None,
         }
     }
 
@@ -45,6 +50,25 @@ impl SearchFilter {
         self.is_word
     }
 
+    /// Returns the index of the column the filter is limited to.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    /// Returns the part of the line the filter applies to.
+    ///
+    /// For column-scoped filters this is the content of the column in lines of
+    /// column-based parsers, or `None` if the line has no such column.
+    pub fn scope<'a>(&self, line: &'a str) -> Option<&'a str> {
+        match self.column {
+            Some(column) => line
+                .trim_end_matches(['\r', '\n'])
+                .split(COLUMN_SEPARATOR)
+                .nth(column),
+            None => Some(line),
+        }
+    }
+
     /// Sets case-insensitive matching for the filter builder.
     #[must_use]
     pub fn ignore_case(mut self, ignore: bool) -> Self {
@@ -65,6 +89,13 @@ impl SearchFilter {
         self.is_word = word;
         self
     }
+
+    /// Limits matching to the column with the given index.
+    #[must_use]
+    pub fn in_column(mut self, column: Option<usize>) -> Self {
+        self.column = column;
+        self
+    }
 }
 
 pub fn get_filter_error(filter: &SearchFilter) -> Option<String> {
@@ -84,19 +115,40 @@ pub fn as_regex(filter: &SearchFilter) -> String {
     format!("{ignore_case_start}{word_marker}{subject}{word_marker}{ignore_case_end}",)
 }
 
+/// Returns the text contained in every match of the filter, which is available for
+/// filters without regex syntax only.
+///
+/// Case-insensitive filters with non-ASCII text have no such text, as their matches can
+/// differ from the value in more than the case of ASCII letters.
+pub fn as_literal(filter: &SearchFilter) -> Option<String> {
+    if filter.is_regex && regex::escape(&filter.value) != filter.value {
+        return None;
+    }
+    if filter.ignore_case && !filter.value.is_ascii() {
+        return None;
+    }
+    Some(filter.value.clone())
+}
+
 pub fn as_alias(filter: &SearchFilter) -> String {
     let word_marker = if filter.is_word { "1" } else { "0" };
     let ignore_case = if filter.ignore_case { "1" } else { "0" };
     let is_regex = if filter.is_regex { "1" } else { "0" };
+    let column = filter
+        .column
+        .map(|column| format!("@{column}"))
+        .unwrap_or_default();
     format!(
-        "{}:{}{}{}",
+        "{}:{}{}{}{column}",
         filter.value, is_regex, ignore_case, word_marker
     )
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{SearchFilter, as_alias, as_regex};
+    use parsers::COLUMN_SEPARATOR;
+
+    use super::{SearchFilter, as_alias, as_literal, as_regex};
 
     #[test]
     fn as_regex_escapes_plain_text() {
@@ -105,6 +157,26 @@ mod tests {
         assert_eq!(as_regex(&filter), "cpu=\\(1\\.0\\)");
     }
 
+    #[test]
+    fn as_literal_is_available_without_regex_syntax() {
+        assert_eq!(
+            as_literal(&SearchFilter::plain("cpu=(1.0)")),
+            Some(String::from("cpu=(1.0)"))
+        );
+        assert_eq!(
+            as_literal(&SearchFilter::plain("timeout").regex(true)),
+            Some(String::from("timeout"))
+        );
+        assert_eq!(
+            as_literal(&SearchFilter::plain("time.*out").regex(true)),
+            None
+        );
+        assert_eq!(
+            as_literal(&SearchFilter::plain("grüße").ignore_case(true)),
+            None
+        );
+    }
+
     #[test]
     fn as_regex_preserves_regex_with_flags() {
         let filter = SearchFilter::plain("cpu=(\\d+)")
@@ -131,4 +203,25 @@ mod tests {
 
         assert_eq!(as_alias(&filter), "cpu=(\\d+):111");
     }
+
+    #[test]
+    fn as_alias_encodes_column() {
+        let filter = SearchFilter::plain("NAV").in_column(Some(7));
+
+        assert_eq!(as_alias(&filter), "NAV:000@7");
+    }
+
+    #[test]
+    fn scope_selects_column() {
+        let line = ["ECU1", "NAV", "payload NAV"].join(COLUMN_SEPARATOR) + "\n";
+        let filter = SearchFilter::plain("NAV");
+
+        assert_eq!(filter.scope(&line), Some(line.as_str()));
+        assert_eq!(filter.clone().in_column(Some(1)).scope(&line), Some("NAV"));
+        assert_eq!(
+            filter.clone().in_column(Some(2)).scope(&line),
+            Some("payload NAV")
+        );
+        assert_eq!(filter.in_column(Some(3)).scope(&line), None);
+    }
 }
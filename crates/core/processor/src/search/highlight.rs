@@ -0,0 +1,139 @@
+//! Includes the lookup of the byte ranges matched by search filters in lines, which lets
+//! clients highlight exactly what the search has matched instead of matching again.
+
+use std::{ops::Range, str::FromStr};
+
+use regex::Regex;
+
+use crate::search::{error::SearchError, filter, filter::SearchFilter};
+
+/// Finds the byte ranges of the matches of search filters in lines.
+///
+/// The ranges come from a second pass of a [`Regex`] over the lines being grabbed, not
+/// from the grep matcher of the search, which only reports the matching rows. Both are
+/// built from [`filter::as_regex`], so they agree on what the filters match.
+#[derive(Debug, Clone)]
+pub struct MatchFinder {
+    filters: Vec<SearchFilter>,
+    matchers: Vec<Regex>,
+}
+
+impl MatchFinder {
+    /// Creates a finder for the filters, which are identified by their position.
+    pub fn new(filters: Vec<SearchFilter>) -> Result<Self, SearchError> {
+        let matchers = filters
+            .iter()
+            .map(|filter| {
+                let regex_as_str = filter::as_regex(filter);
+                Regex::from_str(&regex_as_str).map_err(|err| {
+                    SearchError::Regex(format!("Failed to create regex for {regex_as_str}: {err}"))
+                })
+            })
+            .collect::<Result<Vec<Regex>, SearchError>>()?;
+        Ok(Self { filters, matchers })
+    }
+
+    /// Returns the matches of the given filters in the line together with the capture
+    /// groups of the matches. Filters limited to a column are matched within their column.
+    ///
+    /// Unknown filters are ignored.
+    pub fn find(&self, line: &str, filters: &[u8]) -> Vec<stypes::MatchRange> {
+        let mut ranges = Vec::new();
+        for index in filters {
+            let (Some(filter), Some(matcher)) = (
+                self.filters.get(*index as usize),
+                self.matchers.get(*index as usize),
+            ) else {
+                continue;
+            };
+            let Some(scope) = filter.scope(line) else {
+                continue;
+            };
+            // The scope is a part of the line, so its offset follows from the pointers.
+            let offset = scope.as_ptr() as usize - line.as_ptr() as usize;
+            push_matches(&mut ranges, matcher, scope, *index, |range| {
+                range.start + offset..range.end + offset
+            });
+        }
+        ranges
+    }
+}
+
+/// Appends the non-empty matches of the regex in the text and their capture groups,
+/// mapping the ranges in the text to the ranges in the line with `to_line`.
+pub(crate) fn push_matches(
+    ranges: &mut Vec<stypes::MatchRange>,
+    matcher: &Regex,
+    text: &str,
+    filter: u8,
+    to_line: impl Fn(Range<usize>) -> Range<usize>,
+) {
+    for captures in matcher.captures_iter(text) {
+        for (group, found) in captures.iter().enumerate() {
+            let (Ok(group), Some(found)) = (u8::try_from(group), found) else {
+                continue;
+            };
+            if found.is_empty() {
+                continue;
+            }
+            let range = to_line(found.range());
+            ranges.push(stypes::MatchRange {
+                filter,
+                group,
+                start: range.start,
+                end: range.end,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parsers::COLUMN_SEPARATOR;
+
+    use super::MatchFinder;
+    use crate::search::filter::SearchFilter;
+
+    fn ranges(finder: &MatchFinder, line: &str, filters: &[u8]) -> Vec<(u8, u8, usize, usize)> {
+        finder
+            .find(line, filters)
+            .into_iter()
+            .map(|range| (range.filter, range.group, range.start, range.end))
+            .collect()
+    }
+
+    #[test]
+    fn matches_of_the_given_filters_are_found() {
+        let finder = MatchFinder::new(vec![
+            SearchFilter::plain("err"),
+            SearchFilter::plain("warn").ignore_case(true),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            ranges(&finder, "err WARN err", &[0, 1]),
+            vec![(0, 0, 0, 3), (0, 0, 9, 12), (1, 0, 4, 8)]
+        );
+        assert_eq!(ranges(&finder, "err WARN err", &[1]), vec![(1, 0, 4, 8)]);
+        assert!(ranges(&finder, "err", &[2]).is_empty());
+    }
+
+    #[test]
+    fn capture_groups_are_reported_after_their_match() {
+        let finder =
+            MatchFinder::new(vec![SearchFilter::plain(r"cpu=(\d+)(x)?").regex(true)]).unwrap();
+
+        assert_eq!(
+            ranges(&finder, "load cpu=42", &[0]),
+            vec![(0, 0, 5, 11), (0, 1, 9, 11)]
+        );
+    }
+
+    #[test]
+    fn column_filters_are_matched_within_their_column() {
+        let finder = MatchFinder::new(vec![SearchFilter::plain("id").in_column(Some(1))]).unwrap();
+        let line = format!("id{COLUMN_SEPARATOR}pid");
+
+        assert_eq!(ranges(&finder, &line, &[0]), vec![(0, 0, 4, 6)]);
+    }
+}
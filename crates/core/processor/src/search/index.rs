@@ -0,0 +1,836 @@
+//! Includes the search index, which narrows the parts of a file to search down to the
+//! parts able to contain the searched text.
+//!
+//! The file is divided into blocks of complete lines. Each block keeps a bloom filter of
+//! the trigrams of its lines, so blocks missing any trigram of a searched text are skipped
+//! without reading them. The index can be persisted next to the searched file and is
+//! extended incrementally while the file grows.
+
+use crate::search::error::SearchError;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::SystemTime,
+};
+use tokio_util::sync::CancellationToken;
+
+/// Search index shared between the searchers of a file.
+pub type SharedSearchIndex = Arc<Mutex<SearchIndex>>;
+
+/// Version of the persisted index. Indexes of other versions are rebuilt.
+const VERSION: u32 = 2;
+
+/// Minimal size of a block. Blocks end at the end of a line, so they are usually a bit
+/// longer.
+const BLOCK_BYTES: u64 = 128 * 1024;
+
+/// Bits in the bloom filter of a block per distinct trigram of the block, which keeps the
+/// chance of a trigram to be found in a block without it at about 3%.
+const BLOOM_BITS_PER_TRIGRAM: usize = 8;
+
+/// Minimal number of bits in the bloom filter of a block.
+const MIN_BLOOM_BITS: usize = 1024;
+
+/// Number of bits set in the bloom filter per trigram.
+const BLOOM_HASHES: usize = 3;
+
+/// Bytes hashed at the start and at the end of the indexed part of the file to recognize
+/// changes of the file.
+const FINGERPRINT_BYTES: u64 = 4 * 1024;
+
+/// Bytes read at once while indexing.
+const READ_BYTES: usize = 64 * 1024;
+
+/// Texts shorter than a trigram can't be looked up.
+const MIN_LITERAL_LEN: usize = 3;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    /// Number of indexed bytes, which is the end of the last block.
+    bytes: u64,
+    /// Number of lines in the indexed bytes.
+    lines: u64,
+    blocks: u64,
+    /// Hash of the start of the indexed bytes.
+    head: u64,
+    /// Hash of the end of the indexed bytes.
+    tail: u64,
+}
+
+impl Header {
+    fn new() -> Self {
+        Self {
+            version: VERSION,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Block {
+    /// Offset of the first byte of the block in the file.
+    start: u64,
+    /// Row of the first line of the block.
+    line: u64,
+    /// Bloom filter of the trigrams, whose number of bits is a power of two.
+    bloom: Vec<u64>,
+}
+
+impl Block {
+    /// Creates the block with a bloom filter sized for the number of its trigrams.
+    fn new(start: u64, line: u64, trigrams: &HashSet<u32>) -> Self {
+        let bits = (trigrams.len() * BLOOM_BITS_PER_TRIGRAM)
+            .next_power_of_two()
+            .max(MIN_BLOOM_BITS);
+        let mut block = Self {
+            start,
+            line,
+            bloom: vec![0; bits / 64],
+        };
+        for trigram in trigrams {
+            for bit in block.bloom_bits(*trigram) {
+                block.bloom[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        block
+    }
+
+    /// Returns the bloom filter bits of the trigram.
+    fn bloom_bits(&self, trigram: u32) -> [usize; BLOOM_HASHES] {
+        // Finalizer of SplitMix64, spreading the trigram over all bits of the hash.
+        let mut hash = trigram as u64;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash ^= hash >> 31;
+        let (first, second) = (hash >> 32, hash | 1);
+        let mask = (self.bloom.len() * 64 - 1) as u64;
+        std::array::from_fn(|i| (first.wrapping_add(second.wrapping_mul(i as u64)) & mask) as usize)
+    }
+
+    fn contains(&self, trigram: u32) -> bool {
+        self.bloom_bits(trigram)
+            .iter()
+            .all(|bit| self.bloom[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns whether the block can contain any of the texts, given as their trigrams.
+    fn may_contain(&self, literals: &[Vec<u32>]) -> bool {
+        literals
+            .iter()
+            .any(|trigrams| trigrams.iter().all(|trigram| self.contains(*trigram)))
+    }
+}
+
+/// Returns the trigrams of the text, ignoring the case of ASCII letters.
+fn trigrams(text: &[u8]) -> Vec<u32> {
+    text.windows(3)
+        .map(|bytes| trigram(bytes[0], bytes[1], bytes[2]))
+        .collect()
+}
+
+fn trigram(first: u8, second: u8, third: u8) -> u32 {
+    u32::from_be_bytes([
+        0,
+        first.to_ascii_lowercase(),
+        second.to_ascii_lowercase(),
+        third.to_ascii_lowercase(),
+    ])
+}
+
+/// FNV-1a hash of the bytes, which is the same across runs and builds.
+pub fn fnv(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Returns the hashes of the start and of the end of the first `bytes` bytes of the file.
+fn fingerprint(file: &mut File, bytes: u64) -> io::Result<(u64, u64)> {
+    let mut hash = |from: u64| -> io::Result<u64> {
+        let mut buffer = vec![0u8; (bytes - from).min(FINGERPRINT_BYTES) as usize];
+        file.seek(SeekFrom::Start(from))?;
+        file.read_exact(&mut buffer)?;
+        Ok(fnv(&buffer))
+    };
+    Ok((hash(0)?, hash(bytes.saturating_sub(FINGERPRINT_BYTES))?))
+}
+
+/// Returns whether the part of the file of `len` bytes indexed by the header is unchanged.
+fn matches(header: &Header, file: &mut File, len: u64) -> io::Result<bool> {
+    if header.bytes == 0 {
+        return Ok(true);
+    }
+    if len < header.bytes {
+        return Ok(false);
+    }
+    Ok(fingerprint(file, header.bytes)? == (header.head, header.tail))
+}
+
+/// Returns the complete blocks of about `block_bytes` bytes between the end of the bytes
+/// indexed by the header and `limit`, extending the header by them.
+fn index_blocks(
+    header: &mut Header,
+    file: &mut File,
+    limit: u64,
+    block_bytes: u64,
+    cancel: &CancellationToken,
+) -> io::Result<Vec<Block>> {
+    file.seek(SeekFrom::Start(header.bytes))?;
+    let mut reader = file.take(limit - header.bytes);
+    let mut buffer = vec![0u8; READ_BYTES];
+    let mut blocks = Vec::new();
+    let mut trigrams = HashSet::new();
+    let mut start = header.bytes;
+    let mut pos = header.bytes;
+    let mut lines = header.lines;
+    // The last two bytes of the current line and the length of the line so far.
+    let mut window: (u8, u8, usize) = (0, 0, 0);
+    'read: while !cancel.is_cancelled() {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        for byte in &buffer[..read] {
+            pos += 1;
+            if *byte != b'\n' {
+                if window.2 >= 2 {
+                    trigrams.insert(trigram(window.0, window.1, *byte));
+                }
+                window = (window.1, *byte, window.2 + 1);
+                continue;
+            }
+            lines += 1;
+            window = (0, 0, 0);
+            if pos - start < block_bytes {
+                continue;
+            }
+            blocks.push(Block::new(start, header.lines, &trigrams));
+            trigrams.clear();
+            start = pos;
+            header.bytes = pos;
+            header.lines = lines;
+            if limit < pos + block_bytes {
+                break 'read;
+            }
+        }
+    }
+    header.blocks += blocks.len() as u64;
+    Ok(blocks)
+}
+
+/// Blocks following the indexed part of the file, which are built without locking the
+/// index.
+struct Extension {
+    /// Header of the extended index.
+    base: Header,
+    header: Header,
+    blocks: Vec<Block>,
+    /// Whether the blocks replace the blocks of the index, as the file has changed.
+    rebuilt: bool,
+}
+
+impl Extension {
+    /// Indexes the complete blocks following the part of the file indexed by `base`
+    /// within the first `bytes` bytes of the file. Returns `None` if there are none and
+    /// the indexed part is unchanged.
+    fn build(
+        base: Header,
+        block_bytes: u64,
+        file_path: &Path,
+        bytes: u64,
+        cancel: &CancellationToken,
+    ) -> io::Result<Option<Self>> {
+        let mut file = File::open(file_path)?;
+        let len = file.metadata()?.len();
+        let rebuilt = !matches(&base, &mut file, len)?;
+        let mut header = if rebuilt { Header::new() } else { base.clone() };
+        let limit = bytes.min(len);
+        let blocks = if limit >= header.bytes + block_bytes {
+            index_blocks(&mut header, &mut file, limit, block_bytes, cancel)?
+        } else {
+            Vec::new()
+        };
+        if blocks.is_empty() && !rebuilt {
+            return Ok(None);
+        }
+        if header.bytes > 0 {
+            (header.head, header.tail) = fingerprint(&mut file, header.bytes)?;
+        }
+        Ok(Some(Self {
+            base,
+            header,
+            blocks,
+            rebuilt,
+        }))
+    }
+}
+
+/// Extends the index in a background thread by the complete blocks within the first
+/// `bytes` bytes of the file, unless it's extended already. The index is locked only to
+/// take over the new blocks, so searches use the part indexed so far meanwhile.
+///
+/// Extending stops once the index is dropped.
+pub fn extend_in_background(index: &SharedSearchIndex, file_path: &Path, bytes: u64) {
+    let (base, block_bytes, cancel) = {
+        let Ok(mut index) = index.lock() else {
+            return;
+        };
+        if index.extending
+            || index.cancel.is_cancelled()
+            || bytes < index.header.bytes + index.block_bytes
+        {
+            return;
+        }
+        index.extending = true;
+        (
+            index.header.clone(),
+            index.block_bytes,
+            index.cancel.clone(),
+        )
+    };
+    let shared = Arc::downgrade(index);
+    let file_path = file_path.to_path_buf();
+    let spawned = thread::Builder::new()
+        .name(String::from("search-index"))
+        .spawn(move || {
+            let extension = Extension::build(base, block_bytes, &file_path, bytes, &cancel);
+            let Some(index) = shared.upgrade() else {
+                return;
+            };
+            let Ok(mut index) = index.lock() else {
+                return;
+            };
+            index.extending = false;
+            match extension {
+                Ok(Some(extension)) => index.apply(extension, &file_path),
+                Ok(None) => {}
+                Err(err) => log::warn!("Fail to index file {file_path:?}: {err}"),
+            }
+        });
+    if let Err(err) = spawned {
+        log::warn!("Fail to start indexing in the background: {err}");
+        if let Ok(mut index) = index.lock() {
+            index.extending = false;
+        }
+    }
+}
+
+/// Trigram index of the blocks of a file.
+///
+/// Only blocks of complete lines are indexed. Bytes after the last block are always
+/// searched and are indexed once a block of them is complete. The bloom filter of each
+/// block is sized by the number of distinct trigrams of the block, so blocks of varied
+/// text can still be told apart.
+pub struct SearchIndex {
+    header: Header,
+    blocks: Vec<Block>,
+    /// Path of the persisted index, or `None` to keep the index in memory only.
+    path: Option<PathBuf>,
+    /// Number of blocks written to the persisted index.
+    persisted: usize,
+    /// Length of the persisted index up to the end of the persisted blocks.
+    persisted_len: u64,
+    block_bytes: u64,
+    /// Whether the index is being extended in the background.
+    extending: bool,
+    /// Stops extending the index in the background once the index is dropped.
+    cancel: CancellationToken,
+}
+
+impl fmt::Debug for SearchIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("bytes", &self.header.bytes)
+            .field("lines", &self.header.lines)
+            .field("blocks", &self.blocks.len())
+            .field("path", &self.path)
+            .field("extending", &self.extending)
+            .finish()
+    }
+}
+
+impl Drop for SearchIndex {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+impl SearchIndex {
+    /// Creates an empty index, which is persisted at `path` if given.
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            header: Header::new(),
+            blocks: Vec::new(),
+            path,
+            persisted: 0,
+            persisted_len: 0,
+            block_bytes: BLOCK_BYTES,
+            extending: false,
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Opens the index persisted at `path`. A missing or broken index, or an index of
+    /// another version, results in an empty index, which is built on the next search.
+    ///
+    /// Opening marks the persisted index as used, as unused indexes are cleaned up by
+    /// the time they were last modified.
+    pub fn open(path: PathBuf) -> Self {
+        match Self::load(&path) {
+            Ok((header, blocks, len)) => {
+                if let Err(err) = OpenOptions::new()
+                    .append(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()))
+                {
+                    log::debug!(
+                        "Fail to mark search index {} as used: {err}",
+                        path.display()
+                    );
+                }
+                let mut index = Self::new(Some(path));
+                index.header = header;
+                index.persisted = blocks.len();
+                index.persisted_len = len;
+                index.blocks = blocks;
+                index
+            }
+            Err(err) => {
+                if path.exists() {
+                    log::warn!("Fail to load search index {}: {err}", path.display());
+                }
+                Self::new(Some(path))
+            }
+        }
+    }
+
+    /// Returns the header, the blocks and the length of the persisted index.
+    fn load(path: &Path) -> io::Result<(Header, Vec<Block>, u64)> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let header: Header = bincode::deserialize_from(&mut reader).map_err(io::Error::other)?;
+        if header.version != VERSION {
+            return Err(io::Error::other(format!(
+                "version {} isn't supported",
+                header.version
+            )));
+        }
+        let blocks = (0..header.blocks)
+            .map(|_| bincode::deserialize_from(&mut reader).map_err(io::Error::other))
+            .collect::<io::Result<Vec<Block>>>()?;
+        Ok((header, blocks, reader.stream_position()?))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_block_bytes(mut self, block_bytes: u64) -> Self {
+        self.block_bytes = block_bytes;
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_extending(&self) -> bool {
+        self.extending
+    }
+
+    /// Returns the number of indexed bytes.
+    pub fn indexed_bytes(&self) -> u64 {
+        self.header.bytes
+    }
+
+    /// Removes the persisted index and stops extending the index in the background.
+    pub fn remove(&mut self) -> io::Result<()> {
+        self.cancel.cancel();
+        self.persisted = 0;
+        match self.path.as_ref() {
+            Some(path) if path.exists() => fs::remove_file(path),
+            _ => Ok(()),
+        }
+    }
+
+    /// Drops all blocks if the indexed part of the file has changed since indexing.
+    pub fn verify(&mut self, file_path: &Path) -> Result<(), SearchError> {
+        let io_err = |e: io::Error| {
+            SearchError::IoOperation(format!("Could not verify index of {file_path:?}: {e}"))
+        };
+        let mut file = File::open(file_path).map_err(io_err)?;
+        let len = file.metadata().map_err(io_err)?.len();
+        if !matches(&self.header, &mut file, len).map_err(io_err)? {
+            self.apply(
+                Extension {
+                    base: self.header.clone(),
+                    header: Header::new(),
+                    blocks: Vec::new(),
+                    rebuilt: true,
+                },
+                file_path,
+            );
+        }
+        Ok(())
+    }
+
+    /// Drops all blocks if the indexed part of the file has changed since indexing, and
+    /// indexes the complete blocks within the first `bytes` bytes of the file. New blocks
+    /// are added to the persisted index.
+    ///
+    /// Indexing stops early without an error when cancelled.
+    pub fn update(
+        &mut self,
+        file_path: &Path,
+        bytes: u64,
+        cancel: &CancellationToken,
+    ) -> Result<(), SearchError> {
+        let extension = Extension::build(
+            self.header.clone(),
+            self.block_bytes,
+            file_path,
+            bytes,
+            cancel,
+        )
+        .map_err(|e| {
+            SearchError::IoOperation(format!("Could not index file {file_path:?}: {e}"))
+        })?;
+        if let Some(extension) = extension {
+            self.apply(extension, file_path);
+        }
+        Ok(())
+    }
+
+    /// Takes over the blocks of the extension and adds them to the persisted index. The
+    /// extension is dropped if the index has changed since it was built.
+    fn apply(&mut self, extension: Extension, file_path: &Path) {
+        if extension.base != self.header || self.cancel.is_cancelled() {
+            return;
+        }
+        if extension.rebuilt {
+            log::debug!("File {file_path:?} has changed, rebuilding its search index");
+            self.blocks.clear();
+            self.persisted = 0;
+        }
+        self.header = extension.header;
+        self.blocks.extend(extension.blocks);
+        if let Err(err) = self.persist() {
+            log::warn!("Fail to persist search index of {file_path:?}: {err}");
+        }
+    }
+
+    /// Writes the header and the blocks, which aren't persisted yet.
+    fn persist(&mut self) -> io::Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        if self.persisted == self.blocks.len() && self.persisted > 0 {
+            return Ok(());
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(path)?;
+        if self.persisted == 0 || file.metadata()?.len() < self.persisted_len {
+            self.persisted = 0;
+            self.persisted_len =
+                bincode::serialized_size(&self.header).map_err(io::Error::other)?;
+        }
+        file.set_len(self.persisted_len)?;
+        file.seek(SeekFrom::End(0))?;
+        let mut writer = io::BufWriter::new(&mut file);
+        for block in &self.blocks[self.persisted..] {
+            bincode::serialize_into(&mut writer, block).map_err(io::Error::other)?;
+        }
+        writer.flush()?;
+        drop(writer);
+        let len = file.stream_position()?;
+        file.seek(SeekFrom::Start(0))?;
+        bincode::serialize_into(&mut file, &self.header).map_err(io::Error::other)?;
+        self.persisted = self.blocks.len();
+        self.persisted_len = len;
+        Ok(())
+    }
+
+    /// Returns the parts of the given bytes of the file, which can contain any of the
+    /// texts, with the rows of their first lines. `line` is the row of the first line of
+    /// the given bytes.
+    ///
+    /// Texts are matched ignoring the case of ASCII letters. Texts shorter than a trigram
+    /// can be anywhere, so all bytes are returned for them.
+    pub fn candidates(
+        &self,
+        literals: &[String],
+        bytes: Range<u64>,
+        line: u64,
+    ) -> Vec<(Range<u64>, u64)> {
+        if literals
+            .iter()
+            .any(|literal| literal.len() < MIN_LITERAL_LEN)
+        {
+            return vec![(bytes, line)];
+        }
+        let literals: Vec<Vec<u32>> = literals
+            .iter()
+            .map(|literal| trigrams(literal.as_bytes()))
+            .collect();
+        let mut parts: Vec<(Range<u64>, u64)> = Vec::new();
+        let mut add = |part: Range<u64>, first: u64| {
+            if part.is_empty() {
+                return;
+            }
+            match parts.last_mut() {
+                Some((last, _)) if last.end == part.start => last.end = part.end,
+                _ => parts.push((part, first)),
+            }
+        };
+        let first = self
+            .blocks
+            .partition_point(|block| block.start <= bytes.start)
+            .saturating_sub(1);
+        for (pos, block) in self.blocks.iter().enumerate().skip(first) {
+            let end = self
+                .blocks
+                .get(pos + 1)
+                .map_or(self.header.bytes, |next| next.start);
+            if end <= bytes.start {
+                continue;
+            }
+            if block.start >= bytes.end {
+                break;
+            }
+            // Blocks searched partially before can't be skipped, as the row of the
+            // first unsearched line is known only for the start of the bytes.
+            if block.start < bytes.start {
+                add(bytes.start..end.min(bytes.end), line);
+            } else if end > bytes.end || block.may_contain(&literals) {
+                add(block.start..end.min(bytes.end), block.line);
+            }
+        }
+        if self.header.bytes < bytes.end {
+            if self.header.bytes <= bytes.start {
+                add(bytes, line);
+            } else {
+                add(self.header.bytes..bytes.end, self.header.lines);
+            }
+        }
+        parts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchIndex, extend_in_background, trigrams};
+    use std::{
+        fs::OpenOptions,
+        io::Write,
+        path::Path,
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+    use tokio_util::sync::CancellationToken;
+
+    fn lines(from: usize, to: usize) -> String {
+        (from..to)
+            .map(|i| {
+                if i % 50 == 7 {
+                    format!("{i:05} ERROR connection lost\n")
+                } else {
+                    format!("{i:05} INFO heartbeat\n")
+                }
+            })
+            .collect()
+    }
+
+    /// Log lines of a service with varied components, messages, ids and values.
+    fn service_logs(lines: usize) -> String {
+        const LEVELS: [&str; 4] = ["DEBUG", "INFO", "INFO", "WARN"];
+        const COMPONENTS: [&str; 8] = [
+            "net::http",
+            "db::pool",
+            "auth::token",
+            "cache::lru",
+            "sched::worker",
+            "api::orders",
+            "api::users",
+            "io::disk",
+        ];
+        const MESSAGES: [&str; 10] = [
+            "request completed",
+            "connection established",
+            "query executed",
+            "token refreshed",
+            "entry evicted",
+            "job scheduled",
+            "retrying after backoff",
+            "payload validated",
+            "session closed",
+            "flushing buffers",
+        ];
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        (0..lines)
+            .map(|i| {
+                let value = next();
+                let message = if i == lines / 2 {
+                    "OutOfMemoryError while allocating 512 MiB"
+                } else {
+                    MESSAGES[(value >> 16) as usize % MESSAGES.len()]
+                };
+                format!(
+                    "2024-05-01 14:{:02}:{:02}.{:03} {} [{}] {message} id={:016x} user={} latency={}ms\n",
+                    i / 60_000 % 60,
+                    i / 1000 % 60,
+                    i % 1000,
+                    LEVELS[value as usize % LEVELS.len()],
+                    COMPONENTS[(value >> 8) as usize % COMPONENTS.len()],
+                    next(),
+                    (value >> 24) % 100_000,
+                    (value >> 40) % 5000,
+                )
+            })
+            .collect()
+    }
+
+    fn update(index: &mut SearchIndex, path: &Path) {
+        let len = std::fs::metadata(path).unwrap().len();
+        index.update(path, len, &CancellationToken::new()).unwrap();
+    }
+
+    #[test]
+    fn blocks_without_the_text_are_skipped() {
+        let content = lines(0, 1000);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &content).unwrap();
+        let mut index = SearchIndex::new(None).with_block_bytes(200);
+        update(&mut index, file.path());
+        let len = content.len() as u64;
+
+        assert!(index.indexed_bytes() > 0);
+        let parts = index.candidates(&[String::from("error")], 0..len, 0);
+        let searched: u64 = parts.iter().map(|(part, _)| part.end - part.start).sum();
+        assert!(searched < len / 2);
+        for (row, line) in content.lines().enumerate() {
+            if !line.contains("ERROR") {
+                continue;
+            }
+            let start = content.lines().take(row).map(|l| l.len() as u64 + 1).sum();
+            let (part, first) = parts
+                .iter()
+                .find(|(part, _)| part.contains(&start))
+                .expect("line with the text is a candidate");
+            let skipped = &content[part.start as usize..start as usize];
+            assert_eq!(*first + skipped.matches('\n').count() as u64, row as u64);
+        }
+        assert_eq!(
+            index.candidates(&[String::from("er")], 0..len, 0),
+            vec![(0..len, 0)]
+        );
+    }
+
+    #[test]
+    fn index_is_extended_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs.txt");
+        let index_path = dir.path().join("logs.index");
+        std::fs::write(&path, lines(0, 300)).unwrap();
+        let mut index = SearchIndex::open(index_path.clone()).with_block_bytes(200);
+        update(&mut index, &path);
+        let indexed = index.indexed_bytes();
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(lines(300, 600).as_bytes())
+            .unwrap();
+        update(&mut index, &path);
+        assert!(index.indexed_bytes() > indexed);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let len = content.len() as u64;
+        let reopened = SearchIndex::open(index_path).with_block_bytes(200);
+        assert_eq!(reopened.indexed_bytes(), index.indexed_bytes());
+        assert_eq!(
+            reopened.candidates(&[String::from("ERROR")], 0..len, 0),
+            index.candidates(&[String::from("ERROR")], 0..len, 0)
+        );
+    }
+
+    #[test]
+    fn changed_file_is_indexed_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs.txt");
+        std::fs::write(&path, lines(0, 300)).unwrap();
+        let mut index = SearchIndex::open(dir.path().join("logs.index")).with_block_bytes(200);
+        update(&mut index, &path);
+
+        let content = lines(0, 300).replace("heartbeat", "WARNINGS!");
+        std::fs::write(&path, &content).unwrap();
+        update(&mut index, &path);
+
+        let len = content.len() as u64;
+        let parts = index.candidates(&[String::from("warnings")], 0..len, 0);
+        let searched: u64 = parts.iter().map(|(part, _)| part.end - part.start).sum();
+        assert_eq!(searched, len);
+    }
+
+    #[test]
+    fn blocks_of_varied_logs_are_skipped() {
+        let content = service_logs(20_000);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &content).unwrap();
+        let mut index = SearchIndex::new(None);
+        update(&mut index, file.path());
+        let len = content.len() as u64;
+
+        assert!(index.blocks.len() > 10);
+        for block in &index.blocks {
+            let set: u32 = block.bloom.iter().map(|bits| bits.count_ones()).sum();
+            assert!((set as usize) < block.bloom.len() * 64 / 2);
+        }
+        let parts = index.candidates(&[String::from("outofmemoryerror")], 0..len, 0);
+        let searched: u64 = parts.iter().map(|(part, _)| part.end - part.start).sum();
+        assert!(searched < len / 5);
+        let start = content.find("OutOfMemoryError").unwrap() as u64;
+        assert!(parts.iter().any(|(part, _)| part.contains(&start)));
+
+        let parts = index.candidates(&[String::from("request completed")], 0..len, 0);
+        assert_eq!(parts, vec![(0..len, 0)]);
+    }
+
+    #[test]
+    fn index_is_extended_in_background() {
+        let content = lines(0, 1000);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &content).unwrap();
+        let index = Arc::new(Mutex::new(SearchIndex::new(None).with_block_bytes(200)));
+        extend_in_background(&index, file.path(), content.len() as u64);
+
+        let started = Instant::now();
+        while index.lock().unwrap().is_extending() {
+            assert!(started.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+        }
+        let mut expected = SearchIndex::new(None).with_block_bytes(200);
+        update(&mut expected, file.path());
+        assert_eq!(
+            index.lock().unwrap().indexed_bytes(),
+            expected.indexed_bytes()
+        );
+    }
+
+    #[test]
+    fn trigrams_ignore_ascii_case() {
+        assert_eq!(trigrams(b"Err"), trigrams(b"eRR"));
+        assert_eq!(trigrams(b"ab").len(), 0);
+        assert_eq!(trigrams(b"abcd").len(), 2);
+    }
+}
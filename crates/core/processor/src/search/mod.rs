@@ -1,4 +1,7 @@
 pub mod error;
 pub mod extractor;
 pub mod filter;
+pub mod highlight;
+pub mod index;
+pub mod query;
 pub mod searchers;
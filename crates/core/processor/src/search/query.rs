@@ -0,0 +1,462 @@
+//! Boolean queries combining search filters with `AND`, `OR` and `NOT`.
+//!
+//! Queries are written as text, e.g. `APID=NAV AND (error OR /time(d )?out/i) AND NOT heartbeat`.
+//!
+//! * Bare words are matched as plain text.
+//! * `"quoted text"` is matched as plain text and may contain spaces, parentheses and
+//!   escaped quotes (`\"`).
+//! * `/pattern/` is matched as regex, with `\/` for slashes in the pattern.
+//! * Quoted text and patterns can be followed by the flags `i` (ignore case) and `w`
+//!   (whole word), e.g. `"Error"iw`.
+//! * `AND`, `OR` and `NOT` are case-insensitive and can be written as `&&`, `||` and `!`.
+//!   Terms without an operator in between are combined with `AND`.
+//! * `NOT` binds stronger than `AND`, which binds stronger than `OR`. Parentheses group
+//!   expressions.
+
+use crate::search::filter::{SearchFilter, get_filter_error};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// An expression tree over search filters, which decides per line whether it matches
+/// based on the filters found in it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum FilterQuery {
+    Filter(SearchFilter),
+    And(Vec<FilterQuery>),
+    Or(Vec<FilterQuery>),
+    Not(Box<FilterQuery>),
+}
+
+impl FilterQuery {
+    /// Creates a query matching lines with any of the filters, which is how filters are
+    /// combined in regular searches.
+    pub fn any(filters: Vec<SearchFilter>) -> Self {
+        Self::Or(filters.into_iter().map(Self::Filter).collect())
+    }
+
+    /// Returns the filters of the query in order of appearance.
+    ///
+    /// Filters used multiple times are returned for each use. Indexes in the returned list
+    /// match the filter indexes in the search results.
+    pub fn filters(&self) -> Vec<&SearchFilter> {
+        let mut filters = Vec::new();
+        self.collect_filters(&mut filters);
+        filters
+    }
+
+    fn collect_filters<'a>(&'a self, filters: &mut Vec<&'a SearchFilter>) {
+        match self {
+            Self::Filter(filter) => filters.push(filter),
+            Self::And(items) | Self::Or(items) => {
+                items.iter().for_each(|item| item.collect_filters(filters))
+            }
+            Self::Not(item) => item.collect_filters(filters),
+        }
+    }
+
+    /// Evaluates the query for a line, where `matches` tells for each of the query
+    /// filters (in the order of [`FilterQuery::filters()`]) whether it's found in the line.
+    pub fn evaluate(&self, matches: &[bool]) -> bool {
+        self.evaluate_next(&mut matches.iter())
+    }
+
+    /// Evaluates all sub-expressions without short-circuiting so each filter consumes
+    /// its match flag.
+    fn evaluate_next<'a>(&self, matches: &mut impl Iterator<Item = &'a bool>) -> bool {
+        match self {
+            Self::Filter(_) => matches.next().copied().unwrap_or_default(),
+            Self::And(items) => items
+                .iter()
+                .fold(true, |acc, item| item.evaluate_next(matches) && acc),
+            Self::Or(items) => items
+                .iter()
+                .fold(false, |acc, item| item.evaluate_next(matches) || acc),
+            Self::Not(item) => !item.evaluate_next(matches),
+        }
+    }
+
+    /// Returns whether the query matches lines without any of its filters, which means
+    /// all lines must be checked rather than lines containing any of the filters.
+    pub fn matches_without_filters(&self) -> bool {
+        self.evaluate(&vec![false; self.filters().len()])
+    }
+
+    /// Checks that the query has filters and all of them are valid.
+    pub fn validate(&self) -> Result<(), String> {
+        let filters = self.filters();
+        if filters.is_empty() {
+            return Err(String::from("Query doesn't contain any filters"));
+        }
+        for filter in filters {
+            if let Some(err) = get_filter_error(filter) {
+                return Err(format!("Invalid filter '{}': {err}", filter.value));
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>, parent_precedence: u8) -> fmt::Result {
+        let (precedence, items, separator) = match self {
+            Self::Filter(filter) => return fmt_filter(filter, f),
+            Self::Not(item) => {
+                f.write_str("NOT ")?;
+                return item.fmt_nested(f, PRECEDENCE_NOT);
+            }
+            Self::And(items) => (PRECEDENCE_AND, items, " AND "),
+            Self::Or(items) => (PRECEDENCE_OR, items, " OR "),
+        };
+        if items.len() == 1 {
+            return items[0].fmt_nested(f, parent_precedence);
+        }
+        let grouped = precedence < parent_precedence || items.is_empty();
+        if grouped {
+            f.write_str("(")?;
+        }
+        for (idx, item) in items.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(separator)?;
+            }
+            item.fmt_nested(f, precedence + 1)?;
+        }
+        if grouped {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+const PRECEDENCE_OR: u8 = 1;
+const PRECEDENCE_AND: u8 = 2;
+const PRECEDENCE_NOT: u8 = 3;
+
+/// Formats the query in the text syntax, which parses back into the same query.
+impl fmt::Display for FilterQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_nested(f, 0)
+    }
+}
+
+fn fmt_filter(filter: &SearchFilter, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let has_flags = filter.is_ignore_case() || filter.is_word();
+    if filter.is_regex() {
+        f.write_str("/")?;
+        let mut chars = filter.value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    f.write_str("\\")?;
+                    if let Some(escaped) = chars.next() {
+                        write!(f, "{escaped}")?;
+                    }
+                }
+                '/' => f.write_str("\\/")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        f.write_str("/")?;
+    } else if !has_flags && is_bare_word(&filter.value) {
+        return f.write_str(&filter.value);
+    } else {
+        let escaped = filter.value.replace('\\', "\\\\").replace('"', "\\\"");
+        write!(f, "\"{escaped}\"")?;
+    }
+    if filter.is_ignore_case() {
+        f.write_str("i")?;
+    }
+    if filter.is_word() {
+        f.write_str("w")?;
+    }
+    Ok(())
+}
+
+/// Returns whether the text can be written without quotes.
+fn is_bare_word(text: &str) -> bool {
+    !text.is_empty()
+        && !text.starts_with(['!', '/'])
+        && !text
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '(' | ')' | '"'))
+        && keyword(text).is_none()
+}
+
+fn keyword(word: &str) -> Option<Token> {
+    if word.eq_ignore_ascii_case("and") || word == "&&" {
+        Some(Token::And)
+    } else if word.eq_ignore_ascii_case("or") || word == "||" {
+        Some(Token::Or)
+    } else if word.eq_ignore_ascii_case("not") {
+        Some(Token::Not)
+    } else {
+        None
+    }
+}
+
+impl FromStr for FilterQuery {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            return Err(String::from("Query is empty"));
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(format!("Unexpected {token}"));
+        }
+        query.validate()?;
+        Ok(query)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Filter(SearchFilter),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("'('"),
+            Self::Close => f.write_str("')'"),
+            Self::And => f.write_str("AND"),
+            Self::Or => f.write_str("OR"),
+            Self::Not => f.write_str("NOT"),
+            Self::Filter(filter) => write!(f, "'{}'", filter.value),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            '"' | '/' => {
+                chars.next();
+                let is_regex = c == '/';
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some(escaped) if escaped == c || (!is_regex && escaped == '\\') => {
+                                value.push(escaped);
+                            }
+                            Some(escaped) => {
+                                value.push('\\');
+                                value.push(escaped);
+                            }
+                            None => return Err(format!("Missing closing {c} in query")),
+                        },
+                        Some(next) if next == c => break,
+                        Some(next) => value.push(next),
+                        None => return Err(format!("Missing closing {c} in query")),
+                    }
+                }
+                let mut filter = SearchFilter::plain(value).regex(is_regex);
+                while let Some(&flag) = chars.peek() {
+                    match flag {
+                        'i' => filter = filter.ignore_case(true),
+                        'w' => filter = filter.word(true),
+                        flag if flag.is_whitespace() || matches!(flag, '(' | ')') => break,
+                        flag => return Err(format!("Unknown flag '{flag}' after {c}")),
+                    }
+                    chars.next();
+                }
+                tokens.push(Token::Filter(filter));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '(' | ')' | '"') {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(keyword(&word).unwrap_or(Token::Filter(SearchFilter::plain(word))));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser with one function per precedence level.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<FilterQuery, String> {
+        let mut items = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            items.push(self.parse_and()?);
+        }
+        Ok(flatten(items, FilterQuery::Or))
+    }
+
+    fn parse_and(&mut self) -> Result<FilterQuery, String> {
+        let mut items = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.pos += 1,
+                // Terms without an operator in between are combined with `AND`.
+                Some(Token::Not | Token::Open | Token::Filter(_)) => {}
+                _ => break,
+            }
+            items.push(self.parse_unary()?);
+        }
+        Ok(flatten(items, FilterQuery::And))
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterQuery, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| String::from("Query ends unexpectedly"))?;
+        self.pos += 1;
+        match token {
+            Token::Not => Ok(FilterQuery::Not(Box::new(self.parse_unary()?))),
+            Token::Open => {
+                let query = self.parse_or()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(String::from("Missing closing ')' in query"));
+                }
+                self.pos += 1;
+                Ok(query)
+            }
+            Token::Filter(filter) => Ok(FilterQuery::Filter(filter)),
+            token => Err(format!("Unexpected {token}")),
+        }
+    }
+}
+
+/// Returns the single item, or combines the items with the given operator.
+fn flatten(
+    mut items: Vec<FilterQuery>,
+    combine: fn(Vec<FilterQuery>) -> FilterQuery,
+) -> FilterQuery {
+    if items.len() == 1 {
+        items.remove(0)
+    } else {
+        combine(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(value: &str) -> FilterQuery {
+        FilterQuery::Filter(SearchFilter::plain(value))
+    }
+
+    fn parse(text: &str) -> FilterQuery {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_precedence_and_grouping() {
+        assert_eq!(
+            parse("APID=NAV AND (error OR timeout) AND NOT heartbeat"),
+            FilterQuery::And(vec![
+                plain("APID=NAV"),
+                FilterQuery::Or(vec![plain("error"), plain("timeout")]),
+                FilterQuery::Not(Box::new(plain("heartbeat"))),
+            ])
+        );
+        assert_eq!(
+            parse("a || b && !c"),
+            FilterQuery::Or(vec![
+                plain("a"),
+                FilterQuery::And(vec![plain("b"), FilterQuery::Not(Box::new(plain("c")))]),
+            ])
+        );
+        assert_eq!(
+            parse("a b or c"),
+            parse("(a AND b) OR c"),
+            "adjacent terms are combined with AND"
+        );
+    }
+
+    #[test]
+    fn parse_quoted_and_regex_filters() {
+        assert_eq!(
+            parse(r#""not found"iw or /a\/b+/i and "say \"hi\"""#),
+            FilterQuery::Or(vec![
+                FilterQuery::Filter(
+                    SearchFilter::plain("not found")
+                        .ignore_case(true)
+                        .word(true)
+                ),
+                FilterQuery::And(vec![
+                    FilterQuery::Filter(SearchFilter::plain("a/b+").regex(true).ignore_case(true)),
+                    plain(r#"say "hi""#),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn reject_invalid_queries() {
+        for text in [
+            "", "  ", "a AND", "(a OR b", "a)", "OR a", "\"open", "/open", "\"a\"x", "/(/",
+        ] {
+            assert!(text.parse::<FilterQuery>().is_err(), "query: {text}");
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for text in [
+            "APID=NAV AND (error OR timeout) AND NOT heartbeat",
+            r#"NOT (a OR "b c") OR /\d+\/s/iw AND "and""#,
+            r#""x\\y\"z"i"#,
+        ] {
+            let query = parse(text);
+            assert_eq!(query.to_string(), text);
+            assert_eq!(parse(&query.to_string()), query);
+        }
+        assert_eq!(parse("a && !(b || c)").to_string(), "a AND NOT (b OR c)");
+    }
+
+    #[test]
+    fn evaluate_query() {
+        let query = parse("a AND (b OR c) AND NOT d");
+        assert_eq!(query.filters().len(), 4);
+        assert!(query.evaluate(&[true, false, true, false]));
+        assert!(!query.evaluate(&[true, true, true, true]));
+        assert!(!query.evaluate(&[false, true, true, false]));
+        assert!(!query.matches_without_filters());
+
+        assert!(parse("NOT heartbeat").matches_without_filters());
+        assert!(FilterQuery::any(vec![SearchFilter::plain("a")]).evaluate(&[true]));
+    }
+}
@@ -7,7 +7,7 @@ use anstyle_parse::{DefaultCharAccumulator, Parser, Perform};
 use memchr::memchr;
 use regex::Regex;
 
-use crate::search::{error::SearchError, filter, filter::SearchFilter};
+use crate::search::{error::SearchError, filter, filter::SearchFilter, highlight};
 
 /// Represents a utility for searching matches in a string.
 /// Primarily used for nested searches, such as filtering results from a primary search.
@@ -15,6 +15,8 @@ use crate::search::{error::SearchError, filter, filter::SearchFilter};
 pub struct LineSearcher {
     /// A compiled regular expression used for matching lines.
     re: Regex,
+    /// The search filter, used to limit matching to its column.
+    filter: SearchFilter,
 }
 
 impl LineSearcher {
@@ -34,6 +36,7 @@ impl LineSearcher {
             re: Regex::from_str(&regex_as_str).map_err(|err| {
                 SearchError::Regex(format!("Failed to create regex for {regex_as_str}: {err}"))
             })?,
+            filter: filter.clone(),
         })
     }
 
@@ -45,15 +48,43 @@ impl LineSearcher {
     ///
     /// # Returns
     ///
-    /// * `true` - If the line matches the regular expression.
+    /// * `true` - If the line, or the column of column-scoped filters, matches the regular
+    ///   expression.
     /// * `false` - Otherwise.
     pub fn is_match(&self, ln: &str) -> bool {
+        let Some(ln) = self.filter.scope(ln) else {
+            return false;
+        };
         if memchr(0x1b, ln.as_bytes()).is_some() {
             return self.re.is_match(&strip_ansi(ln));
         }
 
         self.re.is_match(ln)
     }
+
+    /// Returns the matches in the line together with their capture groups, reported as
+    /// matches of the filter `0`.
+    ///
+    /// Lines with ANSI escapes are matched by their visible text like in [`Self::is_match`],
+    /// while the ranges refer to the bytes of the line.
+    pub fn find(&self, ln: &str) -> Vec<stypes::MatchRange> {
+        let mut ranges = Vec::new();
+        let Some(scope) = self.filter.scope(ln) else {
+            return ranges;
+        };
+        let offset = scope.as_ptr() as usize - ln.as_ptr() as usize;
+        if memchr(0x1b, scope.as_bytes()).is_some() {
+            let (visible, origins) = strip_ansi_with_origins(scope);
+            highlight::push_matches(&mut ranges, &self.re, &visible, 0, |range| {
+                offset + origins[range.start]..offset + origins[range.end - 1] + 1
+            });
+        } else {
+            highlight::push_matches(&mut ranges, &self.re, scope, 0, |range| {
+                range.start + offset..range.end + offset
+            });
+        }
+        ranges
+    }
 }
 
 fn strip_ansi(content: &str) -> String {
@@ -65,6 +96,22 @@ fn strip_ansi(content: &str) -> String {
     visible.0
 }
 
+/// Returns the visible text of the content with the offset of each of its bytes in the
+/// content.
+fn strip_ansi_with_origins(content: &str) -> (String, Vec<usize>) {
+    let mut parser = Parser::<DefaultCharAccumulator>::new();
+    let mut visible = VisibleText(String::with_capacity(content.len()));
+    let mut origins = Vec::with_capacity(content.len());
+    for (pos, byte) in content.bytes().enumerate() {
+        let len = visible.0.len();
+        parser.advance(&mut visible, byte);
+        // Characters are printed once their last byte is read.
+        let added = visible.0.len() - len;
+        origins.extend((0..added).map(|byte| pos + 1 + byte - added));
+    }
+    (visible.0, origins)
+}
+
 struct VisibleText(String);
 
 impl Perform for VisibleText {
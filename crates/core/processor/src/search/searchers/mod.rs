@@ -1,15 +1,32 @@
-use crate::{grabber::GrabError, search::error::SearchError};
+use crate::{
+    grabber::GrabError,
+    search::{
+        error::SearchError,
+        index::{self, SharedSearchIndex},
+    },
+};
 use grep_regex::RegexMatcher;
 use grep_searcher::{Searcher, sinks::UTF8};
 use std::{
+    collections::VecDeque,
     fs::File,
-    io::{Read, Seek, SeekFrom},
+    io::{self, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
     ops::Range,
     path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
 };
 use text_grep::buffer::CancellableBufReader;
 use tokio_util::sync::CancellationToken;
 
+/// Size of the file parts searched in parallel. Parts end at the end of a line, so they
+/// are usually a bit longer.
+const CHUNK_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Bytes read at once while looking for the end of the line at a chunk border.
+const BORDER_SCAN_BYTES: usize = 64 * 1024;
+
 pub mod linear;
 pub mod regular;
 #[cfg(test)]
@@ -26,11 +43,151 @@ pub struct BaseSearcher<State: SearchState> {
     bytes_read: u64,
     lines_read: u64,
     search_state: State,
+    /// Size of the file parts searched in parallel.
+    chunk_bytes: u64,
+    /// Index narrowing the parts of the file to search, if available.
+    index: Option<SharedSearchIndex>,
 }
-pub trait SearchState {
+pub trait SearchState: Sized + Send {
     type SearchResultType;
     fn new(path: &Path) -> Self;
     fn get_terms(&self) -> Vec<String>;
+    /// Returns whether every line must be passed to the collect function rather than
+    /// only lines matching any of the terms.
+    fn matches_all_lines(&self) -> bool {
+        false
+    }
+    /// Returns texts, of which each line passed to the collect function contains at
+    /// least one, ignoring the case of ASCII letters. `None` if there are no such texts,
+    /// so the search index can't be used.
+    fn literals(&self) -> Option<Vec<String>> {
+        None
+    }
+    /// Creates a state with the same search setup and no results, which collects the
+    /// matches of one chunk of the file.
+    fn fork(&self) -> Self;
+    /// Takes over the results collected for a chunk of the file. The rows of the chunk
+    /// results count from the start of the chunk and are shifted by `offset`.
+    fn merge(&mut self, chunk: Self, offset: u64);
+}
+
+/// Results of searching one chunk of the file.
+struct ChunkOutput<State> {
+    state: State,
+    /// Number of lines in the chunk.
+    lines: u64,
+    /// Number of lines passed to the collect function.
+    processed: usize,
+}
+
+/// Part of the file to search.
+struct Chunk {
+    bytes: Range<u64>,
+    /// Row of the first line of the chunk, or `None` if the chunk directly follows the
+    /// previous one.
+    line: Option<u64>,
+}
+
+/// Reader counting the lines passing through it.
+struct LineCounter<R> {
+    inner: R,
+    lines: u64,
+}
+
+impl<R: Read> Read for LineCounter<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buffer)?;
+        self.lines += bytecount::count(&buffer[..read], b'\n') as u64;
+        Ok(read)
+    }
+}
+
+/// Splits the bytes of the file into chunks of about `chunk_bytes`, each ending at the
+/// end of a line.
+fn split_into_chunks(
+    file_path: &Path,
+    bytes: Range<u64>,
+    chunk_bytes: u64,
+) -> Result<Vec<Range<u64>>, SearchError> {
+    let io_err =
+        |e: io::Error| GrabError::IoOperation(format!("Could not read file {file_path:?}: {e}"));
+    let mut file = File::open(file_path).map_err(io_err)?;
+    let mut buffer = vec![0u8; BORDER_SCAN_BYTES];
+    let mut borders = vec![bytes.start];
+    let mut scan = bytes.start + chunk_bytes;
+    'chunks: while scan < bytes.end {
+        file.seek(SeekFrom::Start(scan)).map_err(io_err)?;
+        loop {
+            let len = ((bytes.end - scan) as usize).min(BORDER_SCAN_BYTES);
+            let read = file.read(&mut buffer[..len]).map_err(io_err)?;
+            if read == 0 {
+                break 'chunks;
+            }
+            if let Some(pos) = memchr::memchr(b'\n', &buffer[..read]) {
+                let border = scan + pos as u64 + 1;
+                if border < bytes.end {
+                    borders.push(border);
+                }
+                scan = border + chunk_bytes;
+                continue 'chunks;
+            }
+            scan += read as u64;
+        }
+    }
+    borders.push(bytes.end);
+    Ok(borders.windows(2).map(|pair| pair[0]..pair[1]).collect())
+}
+
+/// Searches one chunk of the file, passing the rows counted from the start of the chunk
+/// to the collect function.
+fn search_chunk<State, F>(
+    file_path: &Path,
+    bytes: Range<u64>,
+    matcher: &RegexMatcher,
+    mut state: State,
+    cancel_token: CancellationToken,
+    collect_fn: &F,
+) -> Result<ChunkOutput<State>, SearchError>
+where
+    F: Fn(u64, &str, &mut State),
+{
+    let in_file = File::open(file_path)
+        .map_err(|_| GrabError::IoOperation(format!("Could not open file {file_path:?}")))?;
+    let mut in_file_reader = CancellableBufReader::new(in_file, cancel_token);
+    in_file_reader
+        .seek(SeekFrom::Start(bytes.start))
+        .map_err(|_| {
+            GrabError::IoOperation(format!(
+                "Could not seek file {file_path:?} to {}",
+                bytes.start
+            ))
+        })?;
+    let mut reader = LineCounter {
+        inner: in_file_reader.take(bytes.end - bytes.start),
+        lines: 0,
+    };
+    let mut processed: usize = 0;
+    // grep counts lines from 1, while rows count from 0.
+    Searcher::new()
+        .search_reader(
+            matcher,
+            &mut reader,
+            UTF8(|row, line| {
+                collect_fn(row - 1, line, &mut state);
+                processed += 1;
+                Ok(true)
+            }),
+        )
+        .map_err(|e| {
+            SearchError::IoOperation(format!(
+                "Could not search in file {file_path:?}; error: {e}"
+            ))
+        })?;
+    Ok(ChunkOutput {
+        state,
+        lines: reader.lines,
+        processed,
+    })
 }
 
 impl<State: SearchState> BaseSearcher<State> {
@@ -41,12 +198,67 @@ impl<State: SearchState> BaseSearcher<State> {
             bytes_read: read_bytes,
             lines_read: rows_count,
             search_state,
+            chunk_bytes: CHUNK_BYTES,
+            index: None,
         }
     }
 
+    /// Sets the index used to skip the parts of the file without matches.
+    pub fn set_index(&mut self, index: Option<SharedSearchIndex>) {
+        self.index = index;
+    }
+
+    /// Continues searching in another file holding the rows searched so far, keeping the
+    /// collected results. The index is replaced only if the searcher uses one.
+    ///
+    /// `blank_rows` tells that the searched rows are empty lines in the new file, so the
+    /// search continues after one byte per searched row.
+    pub fn relink(&mut self, path: &Path, index: Option<SharedSearchIndex>, blank_rows: bool) {
+        self.file_path = PathBuf::from(path);
+        if blank_rows {
+            self.bytes_read = self.lines_read;
+        }
+        if self.index.is_some() {
+            self.index = index;
+        }
+    }
+
+    /// Returns the parts of the bytes to search with the rows of their first lines. Only
+    /// the part of the file indexed so far is used, while the index is extended in the
+    /// background for later searches.
+    ///
+    /// Without an index, or if the search terms can't be looked up in the index, all
+    /// bytes are returned as a single part.
+    fn candidates(&self, read_bytes: u64) -> Vec<(Range<u64>, u64)> {
+        let bytes = self.bytes_read..read_bytes;
+        let (Some(shared), Some(literals)) = (self.index.as_ref(), self.search_state.literals())
+        else {
+            return vec![(bytes, self.lines_read)];
+        };
+        let parts = {
+            let Ok(mut index) = shared.lock() else {
+                log::warn!("Search index of {:?} isn't available", self.file_path);
+                return vec![(bytes, self.lines_read)];
+            };
+            if let Err(err) = index.verify(&self.file_path) {
+                log::warn!("Fail to verify search index: {err}");
+                return vec![(bytes, self.lines_read)];
+            }
+            index.candidates(&literals, bytes, self.lines_read)
+        };
+        // Read bytes are the offset of the last byte read from the file.
+        index::extend_in_background(shared, &self.file_path, read_bytes + 1);
+        parts
+    }
+
     /// Execute a search for the given file and filters collecting the findings
     /// with the provided collect function.
     ///
+    /// Parts of the file, which can't contain matches according to the search index, are
+    /// skipped. Large parts are split into chunks at line ends, which are searched in
+    /// parallel. Each chunk collects into a forked state, and the chunk results are merged
+    /// in the order of the chunks with their absolute line numbers.
+    ///
     /// # Returns
     /// Returns a range containing the absolute line numbers of all matches found
     /// during this specific call.
@@ -55,10 +267,10 @@ impl<State: SearchState> BaseSearcher<State> {
         rows_count: u64,
         read_bytes: u64,
         cancel_token: CancellationToken,
-        mut collect_fn: F,
+        collect_fn: F,
     ) -> Result<Range<usize>, SearchError>
     where
-        F: FnMut(u64, &str, &mut State),
+        F: Fn(u64, &str, &mut State) + Sync,
     {
         if read_bytes == 0 || read_bytes == self.bytes_read {
             return Ok(0..0);
@@ -75,7 +287,11 @@ impl<State: SearchState> BaseSearcher<State> {
                 "Cannot search without filters".to_owned(),
             ));
         }
-        let combined_regex: String = format!("({})", terms.join("|"));
+        let combined_regex: String = if self.search_state.matches_all_lines() {
+            String::from("^")
+        } else {
+            format!("({})", terms.join("|"))
+        };
         let matcher = match RegexMatcher::new(&combined_regex) {
             Ok(regex) => regex,
             Err(err) => {
@@ -84,43 +300,116 @@ impl<State: SearchState> BaseSearcher<State> {
                 )));
             }
         };
-        let in_file = File::open(&self.file_path).map_err(|_| {
-            GrabError::IoOperation(format!("Could not open file {:?}", self.file_path))
-        })?;
-        let mut in_file_reader = CancellableBufReader::new(in_file, cancel_token);
-        in_file_reader
-            .seek(SeekFrom::Start(self.bytes_read))
-            .map_err(|_| {
-                GrabError::IoOperation(format!(
-                    "Could not seek file {:?} to {}",
-                    self.file_path, self.bytes_read
-                ))
-            })?;
-        let mut reader_handler = in_file_reader.take(read_bytes - self.bytes_read);
-        // Take in account: we are counting on all levels (grabbing search, grabbing stream etc)
-        // from 0 line always. But grep gives results from 1. That's why here is a point of correct:
-        // lnum - 1
+        let mut chunks: Vec<Chunk> = Vec::new();
+        for (bytes, line) in self.candidates(read_bytes) {
+            let parts = if bytes.end - bytes.start > self.chunk_bytes {
+                split_into_chunks(&self.file_path, bytes, self.chunk_bytes)?
+            } else {
+                vec![bytes]
+            };
+            chunks.extend(parts.into_iter().enumerate().map(|(pos, bytes)| Chunk {
+                bytes,
+                line: (pos == 0).then_some(line),
+            }));
+        }
+        let outputs = if let [chunk] = chunks.as_slice() {
+            vec![search_chunk(
+                &self.file_path,
+                chunk.bytes.clone(),
+                &matcher,
+                self.search_state.fork(),
+                cancel_token,
+                &collect_fn,
+            )]
+        } else {
+            self.search_chunks(&chunks, &matcher, &cancel_token, &collect_fn)
+        };
         let lines_read = self.lines_read;
+        let mut offset = lines_read;
         let mut processed: usize = 0;
-        Searcher::new()
-            .search_reader(
-                &matcher,
-                &mut reader_handler,
-                UTF8(|row, line| {
-                    // self.matching(row + lines_read - 1, line);
-                    collect_fn(row + lines_read - 1, line, &mut self.search_state);
-                    processed += 1;
-                    Ok(true)
-                }),
-            )
-            .map_err(|e| {
-                SearchError::IoOperation(format!(
-                    "Could not search in file {:?}; error: {e}",
-                    self.file_path
-                ))
-            })?;
+        for (chunk, output) in chunks.iter().zip(outputs) {
+            let output = output?;
+            offset = chunk.line.unwrap_or(offset);
+            self.search_state.merge(output.state, offset);
+            offset += output.lines;
+            processed += output.processed;
+        }
         self.lines_read = rows_count;
         self.bytes_read = read_bytes + 1;
         Ok(lines_read as usize..(lines_read as usize + processed))
     }
+
+    /// Searches the chunks on as many threads as there are available cores. The results
+    /// are returned in the order of the chunks.
+    ///
+    /// The states collecting the matches of the chunks are forked before the threads
+    /// start, so the threads share the file path and the collect function only.
+    fn search_chunks<F>(
+        &self,
+        chunks: &[Chunk],
+        matcher: &RegexMatcher,
+        cancel_token: &CancellationToken,
+        collect_fn: &F,
+    ) -> Vec<Result<ChunkOutput<State>, SearchError>>
+    where
+        F: Fn(u64, &str, &mut State) + Sync,
+    {
+        let workers = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(chunks.len());
+        let pending: Mutex<VecDeque<(usize, &Chunk, State)>> = Mutex::new(
+            chunks
+                .iter()
+                .enumerate()
+                .map(|(index, chunk)| (index, chunk, self.search_state.fork()))
+                .collect(),
+        );
+        let outputs = Mutex::new(Vec::with_capacity(chunks.len()));
+        let file_path = self.file_path.as_path();
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                let matcher = matcher.clone();
+                let (pending, outputs) = (&pending, &outputs);
+                scope.spawn(move || {
+                    loop {
+                        let Some((index, chunk, state)) = pending
+                            .lock()
+                            .ok()
+                            .and_then(|mut pending| pending.pop_front())
+                        else {
+                            break;
+                        };
+                        let output = search_chunk(
+                            file_path,
+                            chunk.bytes.clone(),
+                            &matcher,
+                            state,
+                            cancel_token.clone(),
+                            collect_fn,
+                        );
+                        if output.is_err()
+                            && let Ok(mut pending) = pending.lock()
+                        {
+                            // There's no use for the other chunks after an error.
+                            pending.clear();
+                        }
+                        if let Ok(mut outputs) = outputs.lock() {
+                            outputs.push((index, output));
+                        }
+                    }
+                });
+            }
+        });
+        let mut outputs = outputs.into_inner().unwrap_or_default();
+        outputs.sort_by_key(|(index, _)| *index);
+        if outputs.len() < chunks.len() && outputs.iter().all(|(_, output)| output.is_ok()) {
+            outputs.push((
+                chunks.len(),
+                Err(SearchError::IoOperation(String::from(
+                    "Search of some parts of the file didn't finish",
+                ))),
+            ));
+        }
+        outputs.into_iter().map(|(_, output)| output).collect()
+    }
 }
@@ -3,12 +3,12 @@
 
 use crate::{
     map::FiltersStats,
-    search::{error::SearchError, filter, filter::SearchFilter},
+    search::{error::SearchError, filter, filter::SearchFilter, query::FilterQuery},
 };
 use regex::Regex;
 use std::{
     collections::HashMap,
-    ops::Range,
+    ops::{Range, RangeInclusive},
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -38,14 +38,41 @@ impl Results {
 pub struct RegularSearchState {
     pub file_path: PathBuf,
     filters: Vec<SearchFilter>,
+    /// Boolean query over the filters, or `None` to match lines with any of them.
+    query: Option<FilterQuery>,
     matchers: Vec<Regex>,
     aliases: HashMap<usize, String>,
+    /// Sorted ranges of the rows to search, or `None` to search all rows.
+    rows: Option<Vec<RangeInclusive<u64>>>,
     results: Results,
 }
 
 pub type RegularSearchHolder = BaseSearcher<RegularSearchState>;
 
 impl RegularSearchHolder {
+    /// Sets up the search for lines matching the query. Queries combining the filters
+    /// with `OR` only are searched like the plain list of their filters.
+    pub fn setup_query(&mut self, query: FilterQuery) -> Result<(), SearchError> {
+        let filters: Vec<SearchFilter> = query.filters().into_iter().cloned().collect();
+        let is_any = match &query {
+            FilterQuery::Or(items) => items
+                .iter()
+                .all(|item| matches!(item, FilterQuery::Filter(_))),
+            FilterQuery::Filter(_) => true,
+            _ => false,
+        };
+        self.setup(filters)?;
+        self.search_state.query = (!is_any).then_some(query);
+        Ok(())
+    }
+
+    /// Limits the search to the given sorted ranges of rows. `None` searches all rows.
+    ///
+    /// The limit is reset by setting up new filters.
+    pub fn set_rows(&mut self, rows: Option<Vec<RangeInclusive<u64>>>) {
+        self.search_state.rows = rows;
+    }
+
     pub fn setup(&mut self, filters: Vec<SearchFilter>) -> Result<(), SearchError> {
         let invalid = filters
             .iter()
@@ -67,6 +94,8 @@ impl RegularSearchHolder {
             })?);
         }
         self.search_state.filters = filters;
+        self.search_state.query = None;
+        self.search_state.rows = None;
         self.search_state.matchers = matchers;
         self.search_state.aliases = aliases;
         Ok(())
@@ -79,29 +108,90 @@ impl SearchState for RegularSearchState {
         Self {
             file_path: PathBuf::from(path),
             filters: vec![],
+            query: None,
             matchers: vec![],
             aliases: HashMap::new(),
+            rows: None,
             results: Results::new(),
         }
     }
     fn get_terms(&self) -> Vec<String> {
         self.filters.iter().map(filter::as_regex).collect()
     }
+    fn matches_all_lines(&self) -> bool {
+        self.query
+            .as_ref()
+            .is_some_and(FilterQuery::matches_without_filters)
+    }
+    fn literals(&self) -> Option<Vec<String>> {
+        // Lines matching a boolean query don't need to match any of the filters.
+        if self.query.is_some() {
+            return None;
+        }
+        self.filters.iter().map(filter::as_literal).collect()
+    }
+    fn fork(&self) -> Self {
+        Self {
+            file_path: self.file_path.clone(),
+            filters: self.filters.clone(),
+            query: self.query.clone(),
+            matchers: self.matchers.clone(),
+            // Aliases and rows are applied while merging.
+            aliases: HashMap::new(),
+            rows: None,
+            results: Results::new(),
+        }
+    }
+    fn merge(&mut self, chunk: Self, offset: u64) {
+        let Some(matches) = chunk.results.indexes else {
+            return;
+        };
+        for mut filter_match in matches {
+            filter_match.index += offset;
+            if !self.includes_row(filter_match.index) {
+                continue;
+            }
+            for index in filter_match.filters.iter() {
+                if let Some(alias) = self.aliases.get(&(*index as usize))
+                    && let Some(stats) = self.results.stats.as_mut()
+                {
+                    stats.increment(alias, None);
+                }
+            }
+            if let Some(indexes) = self.results.indexes.as_mut() {
+                indexes.push(filter_match);
+            }
+        }
+    }
+}
+
+impl RegularSearchState {
+    fn includes_row(&self, row: u64) -> bool {
+        self.rows.as_ref().is_none_or(|rows| {
+            let pos = rows.partition_point(|range| *range.end() < row);
+            rows.get(pos).is_some_and(|range| range.contains(&row))
+        })
+    }
 }
 
 fn collect(row: u64, line: &str, state: &mut RegularSearchState) {
+    let matched: Vec<bool> = state
+        .matchers
+        .iter()
+        .zip(state.filters.iter())
+        .map(|(re, filter)| filter.scope(line).is_some_and(|text| re.is_match(text)))
+        .collect();
+    let is_match = match state.query.as_ref() {
+        Some(query) => query.evaluate(&matched),
+        // Lines can match the combined terms outside the columns of scoped filters.
+        None => matched.contains(&true),
+    };
+    if !is_match {
+        return;
+    }
     let mut line_indexes = stypes::FilterMatch::new(row, vec![]);
-    let mut matched_rows = vec![];
-    for (index, re) in state.matchers.iter().enumerate() {
-        if re.is_match(line) {
-            matched_rows.push(index as u8);
-            line_indexes.filters.push(index as u8);
-            if let Some(alias) = state.aliases.get(&index)
-                && let Some(stats) = state.results.stats.as_mut()
-            {
-                stats.increment(alias, None);
-            }
-        }
+    for (index, _) in matched.iter().enumerate().filter(|(_, matched)| **matched) {
+        line_indexes.filters.push(index as u8);
     }
     if let Some(indexes) = state.results.indexes.as_mut() {
         indexes.push(line_indexes);
@@ -88,3 +88,42 @@ fn matching_ignores_ansi_sequence_text() {
     assert!(searcher.is_match("status 31m warn received"));
     assert!(!searcher.is_match("status \x1b[31mwarn received"));
 }
+
+#[test]
+fn matching_limited_to_column() {
+    let line = ["ECU1", "NAV", "NAV timeout"].join(parsers::COLUMN_SEPARATOR);
+    let searcher = LineSearcher::new(&SearchFilter::plain("NAV").in_column(Some(2))).unwrap();
+    assert!(searcher.is_match(&line));
+
+    let searcher = LineSearcher::new(&SearchFilter::plain("ECU1").in_column(Some(2))).unwrap();
+    assert!(!searcher.is_match(&line));
+
+    let searcher = LineSearcher::new(&SearchFilter::plain("NAV").in_column(Some(3))).unwrap();
+    assert!(!searcher.is_match(&line));
+}
+
+#[test]
+fn found_ranges_refer_to_the_raw_line() {
+    let ranges = |searcher: &LineSearcher, line: &str| -> Vec<(u8, usize, usize)> {
+        searcher
+            .find(line)
+            .into_iter()
+            .map(|range| (range.group, range.start, range.end))
+            .collect()
+    };
+    let searcher = LineSearcher::new(&SearchFilter::plain("warn")).unwrap();
+    assert_eq!(
+        ranges(&searcher, "status w\x1b[31marn received"),
+        vec![(0, 7, 16)]
+    );
+
+    let searcher = LineSearcher::new(&SearchFilter::plain(r"cpu=(\d+)").regex(true)).unwrap();
+    assert_eq!(
+        ranges(&searcher, "cpu=1 cpu=42"),
+        vec![(0, 0, 5), (1, 4, 5), (0, 6, 12), (1, 10, 12)]
+    );
+
+    let line = ["NAV", "NAV timeout"].join(parsers::COLUMN_SEPARATOR);
+    let searcher = LineSearcher::new(&SearchFilter::plain("NAV").in_column(Some(1))).unwrap();
+    assert_eq!(ranges(&searcher, &line), vec![(0, 4, 7)]);
+}
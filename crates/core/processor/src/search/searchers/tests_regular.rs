@@ -1,11 +1,17 @@
 use crate::search::{
     filter::SearchFilter,
+    index::SearchIndex,
+    query::FilterQuery,
     searchers::{BaseSearcher, regular::RegularSearchState},
 };
-use std::io::{Error, Write};
+use parsers::COLUMN_SEPARATOR;
+use std::{
+    io::{Error, Write},
+    sync::{Arc, Mutex},
+};
 use tokio_util::sync::CancellationToken;
 
-use super::regular;
+use super::{regular, split_into_chunks};
 
 #[cfg(test)]
 const LOGS: &[&str] = &[
@@ -36,6 +42,23 @@ fn filtered(
     Ok(indexes)
 }
 
+// create tmp file with content, apply search with query
+fn queried(content: &str, query: &str) -> Result<Vec<stypes::FilterMatch>, std::io::Error> {
+    let query: FilterQuery = query.parse().map_err(Error::other)?;
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    let input_file = tmp_file.as_file_mut();
+    input_file.write_all(content.as_bytes())?;
+    let file_size = input_file.metadata()?.len();
+    let mut searcher: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    searcher
+        .setup_query(query)
+        .map_err(|e| Error::other(format!("Fail to setup search: {e}")))?;
+    let (_range, indexes, _stats) =
+        regular::search(&mut searcher, 0, file_size, CancellationToken::new())
+            .map_err(|e| Error::other(format!("Error in search: {e}")))?;
+    Ok(indexes)
+}
+
 #[test]
 fn test_ripgrep_regex_non_regex() -> Result<(), std::io::Error> {
     let filters = vec![
@@ -74,3 +97,193 @@ fn test_ripgrep_case_sensitivity() -> Result<(), std::io::Error> {
     assert_eq!(3, matches[0].index);
     Ok(())
 }
+
+#[test]
+fn test_query_and_or_not() -> Result<(), std::io::Error> {
+    let content = LOGS.join("\n");
+
+    let matches = queried(&content, "Info AND (1.3 OR 1.8)")?;
+    let rows: Vec<u64> = matches.iter().map(|m| m.index).collect();
+    assert_eq!(rows, vec![0, 5]);
+    assert_eq!(matches[0].filters, vec![0, 1]);
+    assert_eq!(matches[1].filters, vec![0, 2]);
+
+    let matches = queried(&content, "NOT Info")?;
+    let rows: Vec<u64> = matches.iter().map(|m| m.index).collect();
+    assert_eq!(rows, vec![1, 3]);
+    assert!(matches[0].filters.is_empty());
+
+    let matches = queried(&content, "/\\[(warn|err)\\]/i || (Info && !/: [a-c]/)")?;
+    let rows: Vec<u64> = matches.iter().map(|m| m.index).collect();
+    assert_eq!(rows, vec![1, 3, 4, 5]);
+    Ok(())
+}
+
+#[test]
+fn test_column_scoped_filters() -> Result<(), std::io::Error> {
+    let content = [
+        ["ECU1", "NAV", "route updated"],
+        ["ECU1", "DIAG", "NAV timeout"],
+        ["ECU2", "NAV", "NAV ready"],
+    ]
+    .map(|row| row.join(COLUMN_SEPARATOR))
+    .join("\n");
+
+    let matches = filtered(&content, vec![SearchFilter::plain("NAV")])?;
+    assert_eq!(3, matches.len());
+
+    let matches = filtered(
+        &content,
+        vec![SearchFilter::plain("NAV").in_column(Some(1))],
+    )?;
+    let rows: Vec<u64> = matches.iter().map(|m| m.index).collect();
+    assert_eq!(rows, vec![0, 2]);
+
+    let matches = filtered(
+        &content,
+        vec![
+            SearchFilter::plain("ECU2").in_column(Some(2)),
+            SearchFilter::plain("nav")
+                .ignore_case(true)
+                .in_column(Some(2)),
+        ],
+    )?;
+    let rows: Vec<u64> = matches.iter().map(|m| m.index).collect();
+    assert_eq!(rows, vec![1, 2]);
+    assert_eq!(matches[0].filters, vec![1]);
+    Ok(())
+}
+
+#[test]
+fn test_search_limited_to_rows() -> Result<(), std::io::Error> {
+    let content = LOGS.join("\n");
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    let input_file = tmp_file.as_file_mut();
+    input_file.write_all(content.as_bytes())?;
+    let file_size = input_file.metadata()?.len();
+    let mut searcher: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    searcher
+        .setup(vec![SearchFilter::plain("Info")])
+        .map_err(|e| Error::other(format!("Fail to setup search: {e}")))?;
+    searcher.set_rows(Some(vec![1..=2, 5..=u64::MAX]));
+    let (_range, matches, stats) =
+        regular::search(&mut searcher, 0, file_size, CancellationToken::new())
+            .map_err(|e| Error::other(format!("Error in search: {e}")))?;
+    let rows: Vec<u64> = matches.iter().map(|m| m.index).collect();
+    assert_eq!(rows, vec![2, 5]);
+    assert_eq!(stats.stats.values().sum::<u64>(), 2);
+    Ok(())
+}
+
+// create tmp file with content, apply search splitting the file into chunks
+fn chunked(
+    content: &str,
+    filters: Vec<SearchFilter>,
+    chunk_bytes: u64,
+) -> Result<(Vec<(u64, Vec<u8>)>, Vec<(String, u64)>), std::io::Error> {
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    let input_file = tmp_file.as_file_mut();
+    input_file.write_all(content.as_bytes())?;
+    let file_size = input_file.metadata()?.len();
+    let mut searcher: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    searcher.chunk_bytes = chunk_bytes;
+    searcher
+        .setup(filters)
+        .map_err(|e| Error::other(format!("Fail to setup search: {e}")))?;
+    let (_range, indexes, stats) =
+        regular::search(&mut searcher, 0, file_size, CancellationToken::new())
+            .map_err(|e| Error::other(format!("Error in search: {e}")))?;
+    let matches = indexes.into_iter().map(|m| (m.index, m.filters)).collect();
+    let mut stats: Vec<(String, u64)> = stats.stats.into_iter().collect();
+    stats.sort();
+    Ok((matches, stats))
+}
+
+#[test]
+fn test_chunks_end_at_line_ends() -> Result<(), std::io::Error> {
+    let content = LOGS.join("\n");
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    tmp_file.as_file_mut().write_all(content.as_bytes())?;
+    let len = content.len() as u64;
+
+    let chunks = split_into_chunks(tmp_file.path(), 0..len, 20)
+        .map_err(|e| Error::other(format!("Fail to split: {e}")))?;
+
+    assert_eq!(chunks.first().map(|chunk| chunk.start), Some(0));
+    assert_eq!(chunks.last().map(|chunk| chunk.end), Some(len));
+    for pair in chunks.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start);
+        assert_eq!(content.as_bytes()[pair[0].end as usize - 1], b'\n');
+    }
+    assert!(chunks.len() > 1);
+    Ok(())
+}
+
+#[test]
+fn test_chunked_search_matches_whole_file_search() -> Result<(), std::io::Error> {
+    let content = (0..500)
+        .map(|i| LOGS[i % LOGS.len()].replace("(1.", &format!("({i}.")))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let filters = || {
+        vec![
+            SearchFilter::plain("Warn"),
+            SearchFilter::plain(r"\(4\d\.").regex(true),
+            SearchFilter::plain("f$").regex(true),
+        ]
+    };
+
+    let whole = chunked(&content, filters(), u64::MAX)?;
+    for chunk_bytes in [1, 64, 1000] {
+        let chunks = chunked(&content, filters(), chunk_bytes)?;
+        assert_eq!(whole.0, chunks.0, "chunk bytes: {chunk_bytes}");
+        assert_eq!(whole.1, chunks.1, "chunk bytes: {chunk_bytes}");
+    }
+    assert!(!whole.0.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_indexed_search_matches_search_without_index() -> Result<(), std::io::Error> {
+    let line = |i: usize| LOGS[i % LOGS.len()].replace("(1.", &format!("({i}."));
+    let first = (0..400).map(line).collect::<Vec<String>>().join("\n") + "\n";
+    let second = (400..800).map(line).collect::<Vec<String>>().join("\n") + "\n";
+    let filters = || vec![SearchFilter::plain("(42"), SearchFilter::plain("(777")];
+    let search = |searcher: &mut BaseSearcher<RegularSearchState>, rows: u64, bytes: u64| {
+        regular::search(searcher, rows, bytes, CancellationToken::new())
+            .map(|(_, indexes, stats)| {
+                let matches: Vec<(u64, Vec<u8>)> =
+                    indexes.into_iter().map(|m| (m.index, m.filters)).collect();
+                let mut stats: Vec<(String, u64)> = stats.stats.into_iter().collect();
+                stats.sort();
+                (matches, stats)
+            })
+            .map_err(|e| Error::other(format!("Error in search: {e}")))
+    };
+
+    let mut tmp_file = tempfile::NamedTempFile::new()?;
+    tmp_file.as_file_mut().write_all(first.as_bytes())?;
+    let mut plain: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    let mut indexed: BaseSearcher<RegularSearchState> = BaseSearcher::new(tmp_file.path(), 0, 0);
+    indexed.set_index(Some(Arc::new(Mutex::new(
+        SearchIndex::new(None).with_block_bytes(300),
+    ))));
+    for searcher in [&mut plain, &mut indexed] {
+        searcher
+            .setup(filters())
+            .map_err(|e| Error::other(format!("Fail to setup search: {e}")))?;
+    }
+    // Read bytes are the offset of the last byte, which is the line break here.
+    let bytes = first.len() as u64 - 1;
+    assert_eq!(
+        search(&mut plain, 400, bytes)?,
+        search(&mut indexed, 400, bytes)?
+    );
+
+    tmp_file.as_file_mut().write_all(second.as_bytes())?;
+    let bytes = (first.len() + second.len()) as u64 - 1;
+    let appended = search(&mut plain, 800, bytes)?;
+    assert_eq!(appended, search(&mut indexed, 800, bytes)?);
+    assert!(appended.0.iter().any(|(row, _)| *row >= 400));
+    Ok(())
+}
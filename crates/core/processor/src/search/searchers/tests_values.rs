@@ -1,7 +1,11 @@
-use crate::search::searchers::{
-    self, BaseSearcher,
-    values::{ValueSearchMatch, ValueSearchOutput, ValueSearchState},
+use crate::search::{
+    filter::SearchFilter,
+    searchers::{
+        self, BaseSearcher,
+        values::{ValueSearchMatch, ValueSearchOutput, ValueSearchState},
+    },
 };
+use parsers::COLUMN_SEPARATOR;
 use std::{
     collections::HashMap,
     io::{Error, Write},
@@ -13,7 +17,7 @@ use super::values::ValueSearchHolder;
 // create tmp file with content, apply search
 fn extracted(
     content: &str,
-    filters: Vec<String>,
+    filters: Vec<SearchFilter>,
 ) -> Result<HashMap<u8, Vec<ValueSearchMatch>>, std::io::Error> {
     let mut tmp_file = tempfile::NamedTempFile::new()?;
     let input_file = tmp_file.as_file_mut();
@@ -41,7 +45,7 @@ fn test_value_search() -> Result<(), std::io::Error> {
             format!("{n} test log entry; some content")
         });
     }
-    let terms = extracted(&rows.join("\n"), vec![String::from("CPU=(\\d{2,})")])?;
+    let terms = extracted(&rows.join("\n"), vec![SearchFilter::plain("CPU=(\\d{2,})")])?;
     let mut control: f64 = 0f64;
     assert_eq!(1, terms.len());
     terms.iter().for_each(|(_term, values)| {
@@ -74,8 +78,8 @@ fn test_value_search2() -> Result<(), std::io::Error> {
     let terms = extracted(
         &rows.join("\n"),
         vec![
-            String::from("CPU=(\\d{1,})%"),
-            String::from("TEMP=(\\d{1,})C"),
+            SearchFilter::plain("CPU=(\\d{1,})%"),
+            SearchFilter::plain("TEMP=(\\d{1,})C"),
         ],
     )?;
     let mut control_0: f64 = 0f64;
@@ -95,3 +99,18 @@ fn test_value_search2() -> Result<(), std::io::Error> {
     assert_eq!(control_1, sum_1);
     Ok(())
 }
+
+#[test]
+fn test_value_search_in_column() -> Result<(), std::io::Error> {
+    let content = [["CPU=10", "CPU=20"], ["CPU=30", "MEM=40"]]
+        .map(|row| row.join(COLUMN_SEPARATOR))
+        .join("\n");
+    let terms = extracted(
+        &content,
+        vec![SearchFilter::plain("CPU=(\\d+)").in_column(Some(1))],
+    )?;
+    let values = terms.get(&0).expect("values of the first term");
+    assert_eq!(values.len(), 1);
+    assert_eq!((values[0].line, values[0].value), (0, 20.0));
+    Ok(())
+}
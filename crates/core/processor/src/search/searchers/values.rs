@@ -1,6 +1,6 @@
 //! Search related to extract numeric values form logs for charts.
 
-use crate::search::error::SearchError;
+use crate::search::{error::SearchError, filter::SearchFilter};
 use regex::Regex;
 use std::{
     collections::HashMap,
@@ -75,7 +75,9 @@ fn is_valid(filter: &str) -> bool {
 #[derive(Debug)]
 pub struct ValueSearchState {
     pub file_path: PathBuf,
-    terms: Vec<String>,
+    /// Search terms. Their values are always matched as case-insensitive regular expressions,
+    /// limited to the filter column if any.
+    terms: Vec<SearchFilter>,
     matchers: Vec<Regex>,
     results: SearchResults,
     errors: HashMap<u64, Vec<(u8, String)>>,
@@ -84,18 +86,18 @@ pub struct ValueSearchState {
 pub type ValueSearchHolder = BaseSearcher<ValueSearchState>;
 
 impl ValueSearchHolder {
-    pub fn setup(&mut self, terms: Vec<String>) -> Result<(), SearchError> {
+    pub fn setup(&mut self, terms: Vec<SearchFilter>) -> Result<(), SearchError> {
         let invalid = terms
             .iter()
-            .filter(|f| !is_valid(f))
-            .cloned()
+            .filter(|f| !is_valid(&f.value))
+            .map(|f| f.value.clone())
             .collect::<Vec<String>>()
             .join("; ");
         if !invalid.is_empty() {
             Err(SearchError::Input(format!("Invalid filters: {invalid}")))?;
         }
         let mut matchers = vec![];
-        for filter in terms.iter() {
+        for filter in terms.iter().map(|f| &f.value) {
             matchers.push(Regex::from_str(&as_regex(filter)).map_err(|err| {
                 SearchError::Regex(format!("Failed to create regex for {filter}: {err}"))
             })?);
@@ -118,13 +120,42 @@ impl SearchState for ValueSearchState {
         }
     }
     fn get_terms(&self) -> Vec<String> {
-        self.terms.iter().map(|f| as_regex(f)).collect()
+        self.terms.iter().map(|f| as_regex(&f.value)).collect()
+    }
+    fn fork(&self) -> Self {
+        Self {
+            file_path: self.file_path.clone(),
+            terms: self.terms.clone(),
+            matchers: self.matchers.clone(),
+            results: SearchResults::new(),
+            errors: HashMap::new(),
+        }
+    }
+    fn merge(&mut self, chunk: Self, offset: u64) {
+        if let (Some(indexes), Some(chunk_indexes)) =
+            (self.results.indexes.as_mut(), chunk.results.indexes)
+        {
+            for (term_index, matches) in chunk_indexes {
+                indexes
+                    .entry(term_index)
+                    .or_default()
+                    .extend(matches.into_iter().map(|val_match| {
+                        ValueSearchMatch::new(val_match.line + offset, val_match.value)
+                    }));
+            }
+        }
+        self.errors.extend(
+            chunk
+                .errors
+                .into_iter()
+                .map(|(row, errors)| (row + offset, errors)),
+        );
     }
 }
 
 fn collect(row: u64, line: &str, state: &mut ValueSearchState) {
-    for (term_index, re) in state.matchers.iter().enumerate() {
-        if let Some(caps) = re.captures(line)
+    for (term_index, (re, term)) in state.matchers.iter().zip(state.terms.iter()).enumerate() {
+        if let Some(caps) = term.scope(line).and_then(|text| re.captures(text))
             && let Some(value) = caps.get(1)
         {
             let value_str = value.as_str().to_owned();
@@ -0,0 +1,452 @@
+//! Includes the mining of log templates, which groups similar lines into templates with
+//! variable slots, so the volume of a session can be summarized by its kinds of messages.
+//!
+//! The miner follows the Drain algorithm: lines are routed through a prefix tree of fixed
+//! depth by their number of tokens and their leading tokens. In the reached leaf, a line
+//! joins the most similar template, whose tokens differing from the line become variables.
+//! Tokens containing digits are variables from the start, since they are mostly ids,
+//! counters or times.
+
+use std::{collections::HashMap, io, ops::RangeInclusive, path::Path, sync::LazyLock};
+
+use parsers::COLUMN_SEPARATOR;
+use tokio_util::sync::CancellationToken;
+
+use crate::lines::LineReader;
+
+/// Text shown for the variable slots of templates.
+pub const VARIABLE: &str = "<*>";
+
+/// Pattern of the gaps between tokens, which are whitespaces and column separators.
+static GAP_PATTERN: LazyLock<String> =
+    LazyLock::new(|| format!(r"[\s{}]", regex::escape(COLUMN_SEPARATOR)));
+
+/// Pattern of the variable slots of templates.
+static VARIABLE_PATTERN: LazyLock<String> =
+    LazyLock::new(|| format!(r"[^\s{}]+", regex::escape(COLUMN_SEPARATOR)));
+
+/// Options of the template mining.
+#[derive(Debug, Clone)]
+pub struct MinerOptions {
+    /// Number of leading tokens routing lines through the prefix tree.
+    pub prefix: usize,
+    /// Minimal share of tokens a line must have in common with a template to join it.
+    pub similarity: f64,
+    /// Maximal number of children of a tree node. Further tokens are routed as variables.
+    pub max_children: usize,
+    /// Maximal number of templates. Lines which would start further templates are counted
+    /// as unclustered.
+    pub max_templates: usize,
+}
+
+impl Default for MinerOptions {
+    fn default() -> Self {
+        Self {
+            prefix: 1,
+            similarity: 0.4,
+            max_children: 100,
+            max_templates: 10_000,
+        }
+    }
+}
+
+/// Splits a time range into buckets counting the lines of each template.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    time: RangeInclusive<u64>,
+    buckets: u16,
+}
+
+impl Histogram {
+    /// Creates a histogram of the time, in milliseconds since the Unix epoch.
+    pub fn new(time: RangeInclusive<u64>, buckets: u16) -> Self {
+        Self { time, buckets }
+    }
+
+    fn bucket(&self, timestamp: u64) -> Option<usize> {
+        if self.buckets == 0 || !self.time.contains(&timestamp) {
+            return None;
+        }
+        let span = u128::from(self.time.end() - self.time.start()) + 1;
+        let offset = u128::from(timestamp - self.time.start());
+        Some((offset * u128::from(self.buckets) / span) as usize)
+    }
+}
+
+/// Splits the line into tokens separated by whitespaces and column separators.
+fn tokenize(line: &str) -> Vec<Token> {
+    line.split(|c: char| c.is_whitespace() || COLUMN_SEPARATOR.contains(c))
+        .filter(|token| !token.is_empty())
+        .map(Token::new)
+        .collect()
+}
+
+/// Returns the line with its tokens containing digits replaced by [`VARIABLE`] and the
+/// gaps between tokens replaced by single spaces, so the lines of the same message are
+/// equal regardless of their numbers, ids and timestamps.
+pub fn normalize(line: &str) -> String {
+    tokenize(line)
+        .iter()
+        .map(Token::key)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    Variable,
+}
+
+impl Token {
+    fn new(token: &str) -> Self {
+        if token.bytes().any(|byte| byte.is_ascii_digit()) {
+            Self::Variable
+        } else {
+            Self::Text(token.to_owned())
+        }
+    }
+
+    /// Returns the key routing the token through the prefix tree.
+    fn key(&self) -> &str {
+        match self {
+            Self::Text(text) => text,
+            Self::Variable => VARIABLE,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Template {
+    tokens: Vec<Token>,
+    count: u64,
+    first_row: u64,
+    last_row: u64,
+    first_time: Option<u64>,
+    last_time: Option<u64>,
+    histogram: Vec<u64>,
+}
+
+impl Template {
+    fn new(tokens: Vec<Token>, row: u64, buckets: usize) -> Self {
+        Self {
+            tokens,
+            count: 0,
+            first_row: row,
+            last_row: row,
+            first_time: None,
+            last_time: None,
+            histogram: vec![0; buckets],
+        }
+    }
+
+    /// Returns the share of the tokens in common with the line, where variables have all
+    /// tokens in common.
+    fn similarity(&self, tokens: &[Token]) -> f64 {
+        if tokens.is_empty() {
+            return 1.0;
+        }
+        let common = self
+            .tokens
+            .iter()
+            .zip(tokens)
+            .filter(|(own, token)| matches!(own, Token::Variable) || own == token)
+            .count();
+        common as f64 / tokens.len() as f64
+    }
+
+    /// Turns the tokens differing from the line into variables.
+    fn merge(&mut self, tokens: Vec<Token>) {
+        for (own, token) in self.tokens.iter_mut().zip(tokens) {
+            if *own != token {
+                *own = Token::Variable;
+            }
+        }
+    }
+
+    fn add(&mut self, row: u64, timestamp: Option<u64>, histogram: Option<&Histogram>) {
+        self.count += 1;
+        self.first_row = self.first_row.min(row);
+        self.last_row = self.last_row.max(row);
+        let Some(timestamp) = timestamp else {
+            return;
+        };
+        self.first_time = Some(
+            self.first_time
+                .map_or(timestamp, |time| time.min(timestamp)),
+        );
+        self.last_time = Some(self.last_time.map_or(timestamp, |time| time.max(timestamp)));
+        if let Some(bucket) = histogram.and_then(|histogram| histogram.bucket(timestamp))
+            && let Some(count) = self.histogram.get_mut(bucket)
+        {
+            *count += 1;
+        }
+    }
+
+    fn text(&self) -> String {
+        self.tokens
+            .iter()
+            .map(Token::key)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns a regular expression matching the lines of the template.
+    fn pattern(&self) -> String {
+        let gap = &*GAP_PATTERN;
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|token| match token {
+                Token::Text(text) => regex::escape(text),
+                Token::Variable => VARIABLE_PATTERN.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(&format!("{gap}+"));
+        format!("^{gap}*{tokens}{gap}*$")
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    templates: Vec<usize>,
+}
+
+/// Mines the templates of lines.
+#[derive(Debug)]
+pub struct TemplateMiner {
+    options: MinerOptions,
+    histogram: Option<Histogram>,
+    /// Prefix trees by the number of tokens of their lines.
+    roots: HashMap<usize, Node>,
+    templates: Vec<Template>,
+    rows: u64,
+    unclustered: u64,
+}
+
+impl TemplateMiner {
+    /// Creates a miner counting the lines of templates in the histogram, if given.
+    pub fn new(options: MinerOptions, histogram: Option<Histogram>) -> Self {
+        Self {
+            options,
+            histogram,
+            roots: HashMap::new(),
+            templates: Vec::new(),
+            rows: 0,
+            unclustered: 0,
+        }
+    }
+
+    /// Adds the line at the row to its template, starting a new template if no template
+    /// is similar enough.
+    pub fn add(&mut self, line: &str, row: u64, timestamp: Option<u64>) {
+        self.rows += 1;
+        let tokens = tokenize(line);
+        let mut node = self.roots.entry(tokens.len()).or_default();
+        for token in tokens.iter().take(self.options.prefix) {
+            let key = if node.children.contains_key(token.key())
+                || node.children.len() < self.options.max_children
+            {
+                token.key()
+            } else {
+                VARIABLE
+            };
+            node = node.children.entry(key.to_owned()).or_default();
+        }
+        let similar = node
+            .templates
+            .iter()
+            .map(|index| (*index, self.templates[*index].similarity(&tokens)))
+            .filter(|(_, similarity)| *similarity >= self.options.similarity)
+            .fold(None, |best: Option<(usize, f64)>, candidate| match best {
+                Some(best) if best.1 >= candidate.1 => Some(best),
+                _ => Some(candidate),
+            });
+        let index = match similar {
+            Some((index, _)) => {
+                self.templates[index].merge(tokens);
+                index
+            }
+            None if self.templates.len() < self.options.max_templates => {
+                let buckets = self
+                    .histogram
+                    .as_ref()
+                    .map_or(0, |histogram| histogram.buckets as usize);
+                self.templates.push(Template::new(tokens, row, buckets));
+                node.templates.push(self.templates.len() - 1);
+                self.templates.len() - 1
+            }
+            None => {
+                self.unclustered += 1;
+                return;
+            }
+        };
+        self.templates[index].add(row, timestamp, self.histogram.as_ref());
+    }
+
+    /// Adds the first `rows` lines of the file, which are the rows of a session file.
+    ///
+    /// Returns `false` if the mining was cancelled.
+    pub fn add_file(
+        &mut self,
+        path: &Path,
+        rows: u64,
+        mut timestamp: impl FnMut(u64) -> Option<u64>,
+        cancel: &CancellationToken,
+    ) -> io::Result<bool> {
+        LineReader::open(path, cancel)?.read(rows, |row, line| {
+            self.add(line, row, timestamp(row));
+            true
+        })
+    }
+
+    /// Returns the mined templates, the most frequent first.
+    pub fn into_templates(self) -> stypes::LogTemplates {
+        let mut templates: Vec<stypes::LogTemplate> = self
+            .templates
+            .into_iter()
+            .map(|template| stypes::LogTemplate {
+                template: template.text(),
+                pattern: template.pattern(),
+                count: template.count,
+                first_row: template.first_row,
+                last_row: template.last_row,
+                first_time: template.first_time,
+                last_time: template.last_time,
+                histogram: template.histogram,
+            })
+            .collect();
+        templates.sort_by(|a, b| b.count.cmp(&a.count).then(a.first_row.cmp(&b.first_row)));
+        stypes::LogTemplates {
+            templates,
+            time: self
+                .histogram
+                .map(|histogram| (*histogram.time.start(), *histogram.time.end())),
+            rows: self.rows,
+            unclustered: self.unclustered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use regex::Regex;
+    use tokio_util::sync::CancellationToken;
+
+    use super::{Histogram, MinerOptions, TemplateMiner};
+
+    fn mine(lines: &[&str]) -> stypes::LogTemplates {
+        let mut miner = TemplateMiner::new(MinerOptions::default(), None);
+        for (row, line) in lines.iter().enumerate() {
+            miner.add(line, row as u64, None);
+        }
+        miner.into_templates()
+    }
+
+    fn texts(templates: &stypes::LogTemplates) -> Vec<(&str, u64)> {
+        templates
+            .templates
+            .iter()
+            .map(|template| (template.template.as_str(), template.count))
+            .collect()
+    }
+
+    #[test]
+    fn similar_lines_share_a_template() {
+        let templates = mine(&[
+            "user alice logged in",
+            "disk full",
+            "user bob logged in",
+            "user carol logged in",
+            "disk full",
+        ]);
+
+        assert_eq!(
+            texts(&templates),
+            vec![("user <*> logged in", 3), ("disk full", 2)]
+        );
+        assert_eq!(templates.templates[0].first_row, 0);
+        assert_eq!(templates.templates[0].last_row, 3);
+        assert_eq!(templates.rows, 5);
+    }
+
+    #[test]
+    fn tokens_with_digits_are_variables() {
+        let templates = mine(&["took 15 ms", "took 230 ms", "took long"]);
+
+        assert_eq!(
+            texts(&templates),
+            vec![("took <*> ms", 2), ("took long", 1)]
+        );
+    }
+
+    #[test]
+    fn patterns_match_the_lines_of_their_template() {
+        let templates = mine(&["open file a.txt (ro)", "open file b.txt (rw)"]);
+        let pattern = Regex::new(&templates.templates[0].pattern).unwrap();
+
+        assert_eq!(texts(&templates), vec![("open file <*> <*>", 2)]);
+        assert!(pattern.is_match("open file a.txt (ro)"));
+        assert!(pattern.is_match("  open\tfile c.txt x\u{4}"));
+        assert!(!pattern.is_match("open file a.txt"));
+        assert!(!pattern.is_match("reopen file a.txt (ro)"));
+    }
+
+    #[test]
+    fn occurrences_are_counted_over_time() {
+        let mut miner =
+            TemplateMiner::new(MinerOptions::default(), Some(Histogram::new(0..=99, 4)));
+        miner.add("tick", 0, Some(10));
+        miner.add("tick", 1, None);
+        miner.add("tick", 2, Some(30));
+        miner.add("tick", 3, Some(90));
+
+        let templates = miner.into_templates();
+        let template = &templates.templates[0];
+        assert_eq!(template.count, 4);
+        assert_eq!(template.first_time, Some(10));
+        assert_eq!(template.last_time, Some(90));
+        assert_eq!(template.histogram, vec![1, 1, 0, 1]);
+        assert_eq!(templates.time, Some((0, 99)));
+    }
+
+    #[test]
+    fn lines_beyond_the_templates_limit_are_unclustered() {
+        let options = MinerOptions {
+            max_templates: 1,
+            ..MinerOptions::default()
+        };
+        let mut miner = TemplateMiner::new(options, None);
+        miner.add("first kind", 0, None);
+        miner.add("second", 1, None);
+        miner.add("first kind", 2, None);
+
+        let templates = miner.into_templates();
+        assert_eq!(texts(&templates), vec![("first kind", 2)]);
+        assert_eq!(templates.unclustered, 1);
+    }
+
+    #[test]
+    fn file_rows_are_mined_with_their_timestamps() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "start\r\nstep 1\nstep 2\nnot mined\n").unwrap();
+        let mut miner = TemplateMiner::new(MinerOptions::default(), None);
+
+        let done = miner
+            .add_file(
+                file.path(),
+                3,
+                |row| Some(row * 10),
+                &CancellationToken::new(),
+            )
+            .unwrap();
+
+        let templates = miner.into_templates();
+        assert!(done);
+        assert_eq!(texts(&templates), vec![("step <*>", 2), ("start", 1)]);
+        assert_eq!(templates.templates[0].last_time, Some(20));
+    }
+}
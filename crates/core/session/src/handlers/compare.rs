@@ -0,0 +1,43 @@
+//! Includes the implementation of comparing the session with another log by the
+//! normalized messages of their rows.
+
+use std::path::PathBuf;
+
+use crate::{
+    handlers::read_blocking,
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+};
+use log::debug;
+use processor::compare::{LogComparer, Side};
+
+/// Compares the session rows with the lines of the other file, which is the session file
+/// of another session or a text log, and keeps the differences in the session state.
+/// The lines of the other file are read up to `other_rows` if given, which bounds a
+/// session file to the rows of its session.
+///
+/// Returns the number of differing messages.
+pub async fn execute_compare(
+    operation_api: &OperationAPI,
+    other: PathBuf,
+    other_rows: Option<u64>,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    debug!("RUST: Compare operation is requested");
+    let filename = state.get_session_file().await?;
+    let (rows, _read_bytes) = state.get_stream_len().await?;
+    let cancel = operation_api.cancellation_token();
+    let compared = read_blocking("comparison", move || {
+        let mut comparer = LogComparer::new();
+        let done = comparer.add_file(Side::Left, &filename, Some(rows), &cancel)?
+            && comparer.add_file(Side::Right, &other, other_rows, &cancel)?;
+        Ok(done.then(|| comparer.into_diff()))
+    })
+    .await?;
+    let Some(comparison) = compared else {
+        return Ok(None);
+    };
+    let count = comparison.entries.len() as u64;
+    state.set_comparison(Some(comparison)).await?;
+    Ok(Some(count))
+}
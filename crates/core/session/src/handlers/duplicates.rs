@@ -0,0 +1,35 @@
+//! Includes the implementation of collapsing runs of consecutive duplicate rows in the
+//! main view.
+
+use crate::{
+    handlers::read_blocking,
+    operations::{OperationAPI, OperationResult},
+    state::{Duplicates, DuplicatesMode, SessionStateAPI},
+};
+use log::debug;
+
+/// Finds the runs of consecutive duplicate rows compared as defined by `mode` and collapses
+/// them in the main view. `None` shows all duplicates again.
+///
+/// Returns the number of collapsed rows.
+pub async fn execute_collapse(
+    operation_api: &OperationAPI,
+    mode: Option<DuplicatesMode>,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    debug!("RUST: Collapse duplicates operation is requested");
+    let Some(mode) = mode else {
+        return state.set_duplicates(None).await.map(Some);
+    };
+    let filename = state.get_session_file().await?;
+    let (rows, _read_bytes) = state.get_stream_len().await?;
+    let cancel = operation_api.cancellation_token();
+    let duplicates = read_blocking("duplicates comparison", move || {
+        Duplicates::from_file(mode, &filename, rows, &cancel)
+    })
+    .await?;
+    let Some(duplicates) = duplicates else {
+        return Ok(None);
+    };
+    state.set_duplicates(Some(duplicates)).await.map(Some)
+}
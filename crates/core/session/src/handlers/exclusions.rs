@@ -0,0 +1,67 @@
+//! Includes the implementation of applying exclusion filters, which hide the matching rows
+//! from the main view without changing the session file.
+
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+};
+use log::debug;
+use processor::search::{
+    filter::SearchFilter,
+    searchers::{self, regular::RegularSearchHolder},
+};
+use tokio::task;
+
+/// Searches the session for rows matching any of the exclusion filters and hides them from
+/// the main view. Empty filters show all rows again.
+///
+/// Returns the number of hidden rows.
+pub async fn execute_exclusions(
+    operation_api: &OperationAPI,
+    filters: Vec<SearchFilter>,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    debug!("RUST: Exclusions operation is requested");
+    if filters.is_empty() {
+        state
+            .set_exclusions(operation_api.id(), None, Vec::new())
+            .await?;
+        return Ok(Some(0));
+    }
+    let filename = state.get_session_file().await?;
+    let (rows, read_bytes) = state.get_stream_len().await?;
+    let mut holder = RegularSearchHolder::new(&filename, 0, 0);
+    holder.setup(filters).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to setup exclusion filters: {e}")),
+    })?;
+    let cancel = operation_api.cancellation_token();
+    let (holder, results) = task::spawn_blocking(move || {
+        let results = searchers::regular::search(&mut holder, rows, read_bytes, cancel);
+        (holder, results)
+    })
+    .await
+    .map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to run exclusions search: {e}")),
+    })?;
+    if operation_api.cancellation_token().is_cancelled() {
+        return Ok(None);
+    }
+    let (_processed, matches, _stats) = results.map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to execute exclusions search. Error: {e}")),
+    })?;
+    let rows: Vec<u64> = matches
+        .into_iter()
+        .map(|filter_match| filter_match.index)
+        .collect();
+    let hidden = rows.len() as u64;
+    state
+        .set_exclusions(operation_api.id(), Some(holder), rows)
+        .await?;
+    Ok(Some(hidden))
+}
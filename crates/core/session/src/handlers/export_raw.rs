@@ -1,6 +1,7 @@
 //! Module for handling exporting part or full content of files in raw format.
 
 use crate::{operations::OperationResult, state::SessionStateAPI};
+use file_tools::DecompressedReader;
 use indexer_base::config::IndexSection;
 use log::debug;
 use parsers::{
@@ -21,10 +22,7 @@ use sources::{
         raw::BinaryByteSource,
     },
 };
-use std::{
-    fs::File,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 use tokio_util::sync::CancellationToken;
 
 /// Export part of the full content of the session source files in raw format.
@@ -33,6 +31,7 @@ pub async fn execute_export(
     state: SessionStateAPI,
     out_path: PathBuf,
     ranges: Vec<std::ops::RangeInclusive<u64>>,
+    include_duplicates: bool,
 ) -> OperationResult<bool> {
     debug!("RUST: ExportRaw operation is requested");
     let observed = state.get_executed_holder().await?;
@@ -45,6 +44,9 @@ pub async fn execute_export(
             )),
         });
     }
+    // Rows hidden from the main view aren't exported, except for collapsed duplicates
+    // if they are included.
+    let ranges = state.get_visible_ranges(ranges, include_duplicates).await?;
     let mut indexes = ranges
         .iter()
         .map(IndexSection::from)
@@ -84,7 +86,7 @@ async fn assing_source(
     read_to_end: bool,
     cancel: &CancellationToken,
 ) -> Result<Option<usize>, stypes::NativeError> {
-    let reader = File::open(src).map_err(|e| stypes::NativeError {
+    let reader = DecompressedReader::open(src).map_err(|e| stypes::NativeError {
         severity: stypes::Severity::ERROR,
         kind: stypes::NativeErrorKind::Io,
         message: Some(format!("Fail open file {}: {}", src.to_string_lossy(), e)),
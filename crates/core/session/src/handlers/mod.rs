@@ -1,6 +1,33 @@
+pub mod compare;
+pub mod duplicates;
+pub mod exclusions;
 pub mod export_raw;
 pub mod extract;
 pub mod observe;
 mod observing;
 pub mod search;
 pub mod search_values;
+pub mod templates;
+
+use std::io;
+use tokio::task;
+
+/// Reads files for an analysis of the session in a blocking task. The analysis is named
+/// by `what` in the errors.
+pub(crate) async fn read_blocking<T: Send + 'static>(
+    what: &str,
+    read: impl FnOnce() -> io::Result<T> + Send + 'static,
+) -> Result<T, stypes::NativeError> {
+    task::spawn_blocking(read)
+        .await
+        .map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::ComputationFailed,
+            message: Some(format!("Fail to run {what}: {e}")),
+        })?
+        .map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!("Fail to read files for {what}: {e}")),
+        })
+}
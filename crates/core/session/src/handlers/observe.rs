@@ -78,9 +78,18 @@ pub async fn start_observing(
                 uuid,
                 transport,
                 &options.parser,
+                options.recording.as_ref(),
                 rx_sde,
             )
             .await
         }
+        stypes::ObserveOrigin::DirWatch(uuid, config) => {
+            observing::dir_watch::watch_dir(operation_api, state, uuid, config, &options.parser)
+                .await
+        }
+        stypes::ObserveOrigin::Plugin(uuid, settings) => {
+            observing::plugin::observe_plugin(operation_api, state, uuid, settings, &options.parser)
+                .await
+        }
     }
 }
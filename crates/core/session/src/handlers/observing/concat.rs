@@ -2,11 +2,12 @@ use crate::{
     operations::{OperationAPI, OperationResult},
     state::SessionStateAPI,
 };
+use file_tools::DecompressedReader;
 use sources::binary::{
     pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
     raw::BinaryByteSource,
 };
-use std::{fs::File, path::PathBuf};
+use std::path::PathBuf;
 
 #[allow(clippy::type_complexity)]
 pub async fn concat_files(
@@ -30,7 +31,7 @@ pub async fn concat_files(
                 uuid,
             )),
         })?;
-        let input_file = File::open(filename).map_err(|e| stypes::NativeError {
+        let input_file = DecompressedReader::open(filename).map_err(|e| stypes::NativeError {
             severity: stypes::Severity::ERROR,
             kind: stypes::NativeErrorKind::Io,
             message: Some(format!(
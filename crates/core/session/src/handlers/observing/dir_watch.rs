@@ -0,0 +1,428 @@
+//! Observes a directory, appending the files matching a glob pattern to the session as
+//! they appear. Files are read one after another, where the newest file is tailed until
+//! the next file shows up in the directory.
+
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+    tail::{self, TailOptions},
+};
+use glob::Pattern;
+use sources::binary::{
+    pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+    raw::BinaryByteSource,
+};
+use std::{
+    collections::{HashSet, VecDeque},
+    fs::{self, File},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tokio::{
+    join, select,
+    sync::mpsc::channel,
+    time::{Duration, interval},
+};
+use tokio_util::sync::CancellationToken;
+
+/// Interval of scanning the directory for new files.
+const SCAN_INTERVAL_MS: u64 = 1000;
+
+pub async fn watch_dir(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    uuid: &str,
+    config: &stypes::DirWatchConfig,
+    parser: &stypes::ParserType,
+) -> OperationResult<()> {
+    let pattern = Pattern::new(&config.pattern).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Configuration,
+        message: Some(format!("Invalid file pattern {}: {e}", config.pattern)),
+    })?;
+    let cancel = operation_api.cancellation_token();
+    let mut known = HashSet::new();
+    let mut pending = VecDeque::new();
+
+    state.set_session_file(None).await?;
+    operation_api.processing();
+
+    while !cancel.is_cancelled() {
+        pending.extend(scan_new(config, &pattern, &mut known)?);
+        let Some(path) = pending.pop_front() else {
+            let new_files = wait_new_files(config, &pattern, &mut known, &cancel).await?;
+            pending.extend(new_files);
+            continue;
+        };
+        let alias = source_alias(uuid, &path);
+        if !pending.is_empty() || super::file::is_compressed(&path)? {
+            // Newer files exist already, therefore the file is complete and is read once.
+            super::concat::concat_files(
+                operation_api.clone(),
+                state.clone(),
+                &[(alias, config.format, path)],
+                parser,
+            )
+            .await?;
+        } else {
+            let new_files = follow_file(
+                &operation_api,
+                &state,
+                &alias,
+                &path,
+                config,
+                &pattern,
+                &mut known,
+                parser,
+            )
+            .await?;
+            pending.extend(new_files);
+        }
+    }
+
+    Ok(Some(()))
+}
+
+/// Returns the alias of the source of a file within the watched directory.
+fn source_alias(uuid: &str, path: &Path) -> String {
+    format!("{uuid}:{}", path.display())
+}
+
+/// Reads and tails the file until newer files appear in the directory, returning them.
+#[allow(clippy::too_many_arguments)]
+async fn follow_file(
+    operation_api: &OperationAPI,
+    state: &SessionStateAPI,
+    alias: &str,
+    path: &Path,
+    config: &stypes::DirWatchConfig,
+    pattern: &Pattern,
+    known: &mut HashSet<PathBuf>,
+    parser: &stypes::ParserType,
+) -> Result<Vec<PathBuf>, stypes::NativeError> {
+    let cancel = operation_api.cancellation_token();
+    let header_ready = match config.format {
+        stypes::FileFormat::PcapLegacy => {
+            wait_header(path, config, pattern, known, &cancel, |file| {
+                PcapLegacyByteSource::new(file).map(drop)
+            })
+            .await?
+        }
+        stypes::FileFormat::PcapNG => {
+            wait_header(path, config, pattern, known, &cancel, |file| {
+                PcapngByteSource::new(file).map(drop)
+            })
+            .await?
+        }
+        stypes::FileFormat::Binary | stypes::FileFormat::Text => true,
+    };
+    if !header_ready {
+        return Ok(Vec::new());
+    }
+    let source_id = state.add_source(alias).await?;
+    let stop_tracking = cancel.child_token();
+    let (tx_tail, rx_tail) = channel(1);
+    let tx_final = tx_tail.clone();
+
+    let watching = async move {
+        let (tracked, new_files) = join!(
+            tail::track(
+                path,
+                TailOptions::no_rotation(),
+                tx_tail,
+                stop_tracking.clone()
+            ),
+            async {
+                let new_files = select! {
+                    new_files = wait_new_files(config, pattern, known, &cancel) => new_files,
+                    // Reading the file has been ended already.
+                    _ = tx_final.closed() => Ok(Vec::new()),
+                };
+                stop_tracking.cancel();
+                new_files
+            }
+        );
+        if let Err(err) = tracked {
+            log::warn!("Stop tailing file {}: {err}", path.display());
+        }
+        // Make sure the data written before the switch to the next file are read too.
+        // Dropping the sender afterwards ends reading the file.
+        let _ = tx_final.send(Ok(())).await;
+        new_files
+    };
+
+    let (listening, new_files) = match config.format {
+        stypes::FileFormat::Binary | stypes::FileFormat::Text => join!(
+            super::run_source(
+                operation_api.clone(),
+                state.clone(),
+                BinaryByteSource::new(input_file(path)?),
+                source_id,
+                parser,
+                None,
+                Some(rx_tail)
+            ),
+            watching
+        ),
+        stypes::FileFormat::PcapLegacy => join!(
+            super::run_source(
+                operation_api.clone(),
+                state.clone(),
+                PcapLegacyByteSource::new(input_file(path)?)?,
+                source_id,
+                parser,
+                None,
+                Some(rx_tail)
+            ),
+            watching
+        ),
+        stypes::FileFormat::PcapNG => join!(
+            super::run_source(
+                operation_api.clone(),
+                state.clone(),
+                PcapngByteSource::new(input_file(path)?)?,
+                source_id,
+                parser,
+                None,
+                Some(rx_tail)
+            ),
+            watching
+        ),
+    };
+    listening?;
+    new_files
+}
+
+/// Waits until the header of a pcap file has been written completely, trying to read it
+/// with `read_header` on each scan of the directory. Returns `false` on cancellation, and if
+/// the header still can't be read once newer files appear, since the file is complete then.
+async fn wait_header<E: std::fmt::Display>(
+    path: &Path,
+    config: &stypes::DirWatchConfig,
+    pattern: &Pattern,
+    known: &HashSet<PathBuf>,
+    cancel: &CancellationToken,
+    read_header: impl Fn(File) -> Result<(), E>,
+) -> Result<bool, stypes::NativeError> {
+    let mut scan_interval = interval(Duration::from_millis(SCAN_INTERVAL_MS));
+    loop {
+        // Newer files are checked before reading, so the file is complete if they exist.
+        let newer = !scan_new(config, pattern, &mut known.clone())?.is_empty();
+        let err = match read_header(input_file(path)?) {
+            Ok(()) => return Ok(true),
+            Err(err) => err,
+        };
+        if newer {
+            log::warn!("Skip file {} with invalid header: {err}", path.display());
+            return Ok(false);
+        }
+        log::debug!("Wait for complete header of {}: {err}", path.display());
+        select! {
+            _ = cancel.cancelled() => return Ok(false),
+            _ = scan_interval.tick() => {}
+        }
+    }
+}
+
+fn input_file(path: &Path) -> Result<File, stypes::NativeError> {
+    File::open(path).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!("Fail open file {}: {e}", path.to_string_lossy())),
+    })
+}
+
+/// Scans the directory periodically until new files appear, returning them. An empty list is
+/// returned on cancellation.
+async fn wait_new_files(
+    config: &stypes::DirWatchConfig,
+    pattern: &Pattern,
+    known: &mut HashSet<PathBuf>,
+    cancel: &CancellationToken,
+) -> Result<Vec<PathBuf>, stypes::NativeError> {
+    let mut scan_interval = interval(Duration::from_millis(SCAN_INTERVAL_MS));
+    loop {
+        select! {
+            _ = cancel.cancelled() => return Ok(Vec::new()),
+            _ = scan_interval.tick() => {}
+        }
+        let new_files = scan_new(config, pattern, known)?;
+        if !new_files.is_empty() {
+            return Ok(new_files);
+        }
+    }
+}
+
+/// Lists the files matching the pattern which haven't been seen before, in the configured
+/// order, and marks them as known.
+fn scan_new(
+    config: &stypes::DirWatchConfig,
+    pattern: &Pattern,
+    known: &mut HashSet<PathBuf>,
+) -> Result<Vec<PathBuf>, stypes::NativeError> {
+    let io_error = |e: std::io::Error| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!(
+            "Fail to read directory {}: {e}",
+            config.dir.to_string_lossy()
+        )),
+    };
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&config.dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if known.contains(&path)
+            || !path.is_file()
+            || !path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| pattern.matches(name))
+        {
+            continue;
+        }
+        let modified = fs::metadata(&path)
+            .and_then(|md| md.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        files.push((modified, path));
+    }
+
+    match config.order {
+        stypes::DirWatchOrder::Name => files.sort_by(|(_, a), (_, b)| a.cmp(b)),
+        stypes::DirWatchOrder::Modified => files.sort(),
+    }
+    let files: Vec<PathBuf> = files.into_iter().map(|(_, path)| path).collect();
+    known.extend(files.iter().cloned());
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dir: &Path, order: stypes::DirWatchOrder) -> stypes::DirWatchConfig {
+        stypes::DirWatchConfig {
+            dir: dir.to_path_buf(),
+            pattern: String::from("*.dlt"),
+            format: stypes::FileFormat::Binary,
+            order,
+        }
+    }
+
+    #[test]
+    fn scan_matching_files_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("b.dlt"), b"").unwrap();
+        fs::write(dir.path().join("a.dlt"), b"").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"").unwrap();
+        fs::create_dir(dir.path().join("nested.dlt")).unwrap();
+
+        let config = config(dir.path(), stypes::DirWatchOrder::Name);
+        let pattern = Pattern::new(&config.pattern).unwrap();
+        let mut known = HashSet::new();
+
+        let files = scan_new(&config, &pattern, &mut known).unwrap();
+        assert_eq!(
+            files,
+            vec![dir.path().join("a.dlt"), dir.path().join("b.dlt")]
+        );
+
+        fs::write(dir.path().join("0.dlt"), b"").unwrap();
+        let files = scan_new(&config, &pattern, &mut known).unwrap();
+        assert_eq!(files, vec![dir.path().join("0.dlt")]);
+        assert!(scan_new(&config, &pattern, &mut known).unwrap().is_empty());
+    }
+
+    #[test]
+    fn scan_ordered_by_modification() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        for (name, age_secs) in [("a.dlt", 10), ("b.dlt", 30), ("c.dlt", 20)] {
+            let file = File::create(dir.path().join(name)).unwrap();
+            file.set_modified(now - Duration::from_secs(age_secs))
+                .unwrap();
+        }
+
+        let config = config(dir.path(), stypes::DirWatchOrder::Modified);
+        let pattern = Pattern::new(&config.pattern).unwrap();
+        let files = scan_new(&config, &pattern, &mut HashSet::new()).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("b.dlt"),
+                dir.path().join("c.dlt"),
+                dir.path().join("a.dlt")
+            ]
+        );
+    }
+
+    /// Reads a header of four bytes, failing on incomplete headers.
+    fn read_test_header(mut file: File) -> Result<(), std::io::Error> {
+        use std::io::Read;
+
+        file.read_exact(&mut [0u8; 4])
+    }
+
+    #[tokio::test]
+    async fn wait_until_header_is_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.dlt");
+        fs::write(&path, b"he").unwrap();
+
+        let config = config(dir.path(), stypes::DirWatchOrder::Name);
+        let pattern = Pattern::new(&config.pattern).unwrap();
+        let mut known = HashSet::new();
+        scan_new(&config, &pattern, &mut known).unwrap();
+
+        let waiting = tokio::spawn({
+            let path = path.clone();
+            async move {
+                wait_header(
+                    &path,
+                    &config,
+                    &pattern,
+                    &known,
+                    &CancellationToken::new(),
+                    read_test_header,
+                )
+                .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        fs::write(&path, b"header").unwrap();
+
+        assert!(waiting.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn skip_incomplete_header_once_newer_files_exist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.dlt");
+        fs::write(&path, b"").unwrap();
+
+        let config = config(dir.path(), stypes::DirWatchOrder::Name);
+        let pattern = Pattern::new(&config.pattern).unwrap();
+        let mut known = HashSet::new();
+        scan_new(&config, &pattern, &mut known).unwrap();
+        fs::write(dir.path().join("b.dlt"), b"header").unwrap();
+
+        let ready = wait_header(
+            &path,
+            &config,
+            &pattern,
+            &known,
+            &CancellationToken::new(),
+            read_test_header,
+        )
+        .await
+        .unwrap();
+
+        assert!(!ready);
+        // The newer file is left for the next scan.
+        assert_eq!(
+            scan_new(&config, &pattern, &mut known).unwrap(),
+            vec![dir.path().join("b.dlt")]
+        );
+    }
+}
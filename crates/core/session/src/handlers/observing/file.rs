@@ -1,14 +1,18 @@
 use crate::{
     operations::{OperationAPI, OperationResult},
-    state::SessionStateAPI,
-    tail,
+    state::{SessionFile, SessionStateAPI},
+    tail::{self, LinkedFile, RotatingFile, Rotation, TailOptions},
 };
 use sources::binary::{
     pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
     raw::BinaryByteSource,
 };
-use std::{fs::File, path::Path};
-use tokio::{join, select, sync::mpsc::channel};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+use tokio::{join, select, sync::mpsc::channel, task};
 
 pub async fn observe_file(
     operation_api: OperationAPI,
@@ -18,13 +22,30 @@ pub async fn observe_file(
     filename: &Path,
     parser: &stypes::ParserType,
 ) -> OperationResult<()> {
+    if is_compressed(filename)? {
+        // Compressed files can neither be tailed nor linked as session file, therefore
+        // their decompressed content is read once like in concatenated files.
+        return super::concat::concat_files(
+            operation_api,
+            state,
+            &[(uuid.to_owned(), *file_format, filename.to_path_buf())],
+            parser,
+        )
+        .await;
+    }
     let source_id = state.add_source(uuid).await?;
     let (tx_tail, mut rx_tail) = channel(1);
     match file_format {
         stypes::FileFormat::Binary => {
-            let source = BinaryByteSource::new(input_file(filename)?);
+            let options = TailOptions::default();
+            let source = BinaryByteSource::new(rotating_file(filename, options)?);
             let (_, listening) = join!(
-                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                tail::track(
+                    filename,
+                    options,
+                    tx_tail,
+                    operation_api.cancellation_token()
+                ),
                 super::run_source(
                     operation_api,
                     state,
@@ -39,8 +60,15 @@ pub async fn observe_file(
         }
         stypes::FileFormat::PcapLegacy => {
             let source = PcapLegacyByteSource::new(input_file(filename)?)?;
+            // Pcap files start with a header, which can't be continued by the content of
+            // another file. Therefore tailing ends once the file is rotated.
             let (_, listening) = join!(
-                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                tail::track(
+                    filename,
+                    TailOptions::no_rotation(),
+                    tx_tail,
+                    operation_api.cancellation_token()
+                ),
                 super::run_source(
                     operation_api,
                     state,
@@ -55,8 +83,15 @@ pub async fn observe_file(
         }
         stypes::FileFormat::PcapNG => {
             let source = PcapngByteSource::new(input_file(filename)?)?;
+            // Pcap files start with a header, which can't be continued by the content of
+            // another file. Therefore tailing ends once the file is rotated.
             let (_, listening) = join!(
-                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                tail::track(
+                    filename,
+                    TailOptions::no_rotation(),
+                    tx_tail,
+                    operation_api.cancellation_token()
+                ),
                 super::run_source(
                     operation_api,
                     state,
@@ -73,9 +108,15 @@ pub async fn observe_file(
             // We need to count for cases where parsers other than text parser
             // (like plugins) are expected to have text files sources.
             if !matches!(parser, stypes::ParserType::Text(())) {
-                let source = BinaryByteSource::new(input_file(filename)?);
+                let options = TailOptions::default();
+                let source = BinaryByteSource::new(rotating_file(filename, options)?);
                 let (_, listening) = join!(
-                    tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                    tail::track(
+                        filename,
+                        options,
+                        tx_tail,
+                        operation_api.cancellation_token()
+                    ),
                     super::run_source(
                         operation_api,
                         state,
@@ -89,6 +130,15 @@ pub async fn observe_file(
                 return listening;
             }
 
+            let mut linked = LinkedFile::open(filename).map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Io,
+                message: Some(format!(
+                    "Fail open file {}: {}",
+                    filename.to_string_lossy(),
+                    e
+                )),
+            })?;
             state.set_session_file(Some(filename.to_path_buf())).await?;
             // Grab main file content
             state.update_session(source_id).await?;
@@ -99,37 +149,135 @@ pub async fn observe_file(
             let cancel = operation_api.cancellation_token();
             let (result, tracker) = join!(
                 async {
-                    let result = select! {
-                        res = async move {
+                    let rotation = select! {
+                        res = async {
                             while let Some(update) = rx_tail.recv().await {
                                 update.map_err(|err| stypes::NativeError {
                                     severity: stypes::Severity::ERROR,
                                     kind: stypes::NativeErrorKind::Interrupted,
                                     message: Some(err.to_string()),
                                 })?;
+                                let (rows, bytes) = state.get_stream_len().await?;
+                                let read = if rows == 0 { 0 } else { bytes + 1 };
+                                let rotation = linked.rotation(read).map_err(|e| {
+                                    stypes::NativeError {
+                                        severity: stypes::Severity::ERROR,
+                                        kind: stypes::NativeErrorKind::Io,
+                                        message: Some(format!(
+                                            "Fail to check file {}: {}",
+                                            filename.to_string_lossy(),
+                                            e
+                                        )),
+                                    }
+                                })?;
+                                if let Some(rotation) = rotation {
+                                    return Ok(Some((rotation, rows)));
+                                }
                                 state.update_session(source_id).await?;
                             }
-                            Ok(())
+                            Ok::<_, stypes::NativeError>(None)
                         } => res,
-                        _ = cancel.cancelled() => Ok(())
+                        _ = cancel.cancelled() => Ok(None)
+                    }?;
+                    let Some((rotation, rows)) = rotation else {
+                        return Ok(None);
                     };
-                    result
+                    // The linked session file can't follow the rotated file, so the session
+                    // continues in a generated file with the new content of the file.
+                    unlink(&state, filename, rotation, rows, source_id).await?;
+                    let source =
+                        BinaryByteSource::new(rotating_file(filename, TailOptions::default())?);
+                    super::run_source(
+                        operation_api,
+                        state.clone(),
+                        source,
+                        source_id,
+                        parser,
+                        None,
+                        Some(rx_tail),
+                    )
+                    .await
                 },
-                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                tail::track(filename, TailOptions::default(), tx_tail, cancel.clone()),
             );
-            result
-                .and_then(|_| {
-                    tracker.map_err(|e| stypes::NativeError {
-                        severity: stypes::Severity::ERROR,
-                        kind: stypes::NativeErrorKind::Interrupted,
-                        message: Some(format!("Tailing error: {e}")),
-                    })
-                })
-                .map(|_| None)
+            result.and_then(|res| {
+                tracker.map_err(|e| stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::Interrupted,
+                    message: Some(format!("Tailing error: {e}")),
+                })?;
+                Ok(res)
+            })
         }
     }
 }
 
+/// Copies the rows of the linked session file, which has been rotated or truncated, into a
+/// generated session file and switches the session to it.
+///
+/// A truncated file has lost the read rows, which are replaced by empty lines to keep the
+/// rows of the session.
+async fn unlink(
+    state: &SessionStateAPI,
+    filename: &Path,
+    rotation: Rotation,
+    rows: u64,
+    source_id: u16,
+) -> Result<(), stypes::NativeError> {
+    let generated = SessionFile::generated_filename()?;
+    let blank_rows = matches!(rotation, Rotation::Truncated);
+    if blank_rows {
+        log::warn!(
+            "File {} has been truncated; content of {rows} rows read before isn't available",
+            filename.display()
+        );
+    } else {
+        log::info!("File {} has been rotated", filename.display());
+    }
+    let target = generated.clone();
+    task::spawn_blocking(move || -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(&target)?);
+        match rotation {
+            Rotation::Replaced(mut replaced) => {
+                let copied = io::copy(&mut replaced, &mut writer)?;
+                let mut last = [0u8; 1];
+                if copied > 0 {
+                    replaced.seek(SeekFrom::End(-1))?;
+                    replaced.read_exact(&mut last)?;
+                    // New rows must not continue the last line of the rotated file.
+                    if last[0] != b'\n' {
+                        writer.write_all(b"\n")?;
+                    }
+                }
+            }
+            Rotation::Truncated => {
+                for _ in 0..rows {
+                    writer.write_all(b"\n")?;
+                }
+            }
+        }
+        writer.flush()
+    })
+    .await
+    .map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!("Fail to copy rotated file: {e}")),
+    })?
+    .map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!(
+            "Fail to copy rotated file into {}: {}",
+            generated.to_string_lossy(),
+            e
+        )),
+    })?;
+    state
+        .unlink_session_file(generated, blank_rows, source_id)
+        .await
+}
+
 fn input_file(filename: &Path) -> Result<File, stypes::NativeError> {
     File::open(filename).map_err(|e| stypes::NativeError {
         severity: stypes::Severity::ERROR,
@@ -141,3 +289,32 @@ fn input_file(filename: &Path) -> Result<File, stypes::NativeError> {
         )),
     })
 }
+
+fn rotating_file(
+    filename: &Path,
+    options: TailOptions,
+) -> Result<RotatingFile, stypes::NativeError> {
+    RotatingFile::open(filename, options).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!(
+            "Fail open file {}: {}",
+            filename.to_string_lossy(),
+            e
+        )),
+    })
+}
+
+pub(super) fn is_compressed(filename: &Path) -> Result<bool, stypes::NativeError> {
+    file_tools::Compression::detect(filename)
+        .map(|compression| compression.is_some())
+        .map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to read file {}: {}",
+                filename.to_string_lossy(),
+                e
+            )),
+        })
+}
@@ -9,7 +9,7 @@ use crate::state::SessionStateAPI;
 /// It accumulates updates and sends them when `flush()` is called.
 ///
 /// It uses two internal stores to manage data:
-/// - `buffer` accumulates textual log entries.
+/// - `buffer` accumulates textual log entries and `timestamps` the times of their rows.
 /// - `attachments` stores associated `Attachment` objects. These are sent
 ///   only after the text buffer is flushed to ensure synchronization.
 pub struct LogsWriter {
@@ -20,6 +20,9 @@ pub struct LogsWriter {
     /// the buffered data is stored as a `String`.
     text_buffer: String,
 
+    /// Timestamps of the rows in the text buffer, one for each line.
+    timestamps: Vec<Option<u64>>,
+
     /// Buffer for received attachments. These are queued and sent only after
     /// the buffered log messages have been flushed, preserving the logical
     /// order between messages and attachments.
@@ -36,6 +39,7 @@ impl LogsWriter {
             state,
             id,
             text_buffer: String::new(),
+            timestamps: Vec::new(),
             attachments: Vec::new(),
         }
     }
@@ -52,7 +56,10 @@ impl LogsWriter {
             // iterations).
             let msgs = String::from(&self.text_buffer);
             self.text_buffer.clear();
-            self.state.write_session_file(self.id, msgs).await?;
+            let timestamps = self.timestamps.drain(..).collect();
+            self.state
+                .write_session_file(self.id, msgs, timestamps)
+                .await?;
         }
         if !self.attachments.is_empty() {
             // Draining into a new vector preserves the capacity of the internal buffer.
@@ -61,21 +68,33 @@ impl LogsWriter {
         }
         Ok(())
     }
+
+    fn append_message<T: LogMessage>(&mut self, msg: &T) {
+        use std::fmt::Write;
+        let start = self.text_buffer.len();
+        // Writing to string never fails.
+        _ = writeln!(&mut self.text_buffer, "{msg}");
+        // Messages spanning multiple lines produce a row for each of them.
+        let rows = self.text_buffer.as_bytes()[start..]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count();
+        let timestamp = msg.timestamp();
+        self.timestamps.extend(std::iter::repeat_n(timestamp, rows));
+    }
 }
 
 impl<T: LogMessage> LogRecordsCollector<T> for LogsWriter {
     fn append(&mut self, log_record: parsers::ParseYield<T>) {
-        use std::fmt::Write;
         match log_record {
             parsers::ParseYield::Message(msg) => {
-                // Writing to string never fails.
-                _ = writeln!(&mut self.text_buffer, "{msg}");
+                self.append_message(&msg);
             }
             parsers::ParseYield::Attachment(attachment) => {
                 self.attachments.push(attachment);
             }
             parsers::ParseYield::MessageAndAttachment((msg, attachment)) => {
-                _ = writeln!(&mut self.text_buffer, "{msg}");
+                self.append_message(&msg);
                 self.attachments.push(attachment);
             }
         }
@@ -25,8 +25,10 @@ use tokio::{
 };
 
 pub mod concat;
+pub mod dir_watch;
 pub mod file;
 mod logs_writer;
+pub mod plugin;
 pub mod stream;
 
 pub const FLUSH_TIMEOUT_IN_MS: u128 = 500;
@@ -0,0 +1,34 @@
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+};
+use plugins_host::PluginsByteSource;
+use sources::binary::raw::BinaryByteSource;
+
+/// Observes the bytes delivered by a byte-source plugin until the plugin has no more data
+/// or the operation is cancelled.
+pub async fn observe_plugin(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    uuid: &str,
+    settings: &stypes::PluginByteSourceSettings,
+    parser: &stypes::ParserType,
+) -> OperationResult<()> {
+    let source_id = state.add_source(uuid).await?;
+    let plugin_source = PluginsByteSource::initialize(
+        &settings.plugin_path,
+        &settings.general_settings,
+        settings.plugin_configs.clone(),
+    )
+    .await?;
+    super::run_source(
+        operation_api,
+        state,
+        BinaryByteSource::new(plugin_source),
+        source_id,
+        parser,
+        None,
+        None,
+    )
+    .await
+}
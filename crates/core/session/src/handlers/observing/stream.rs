@@ -3,26 +3,110 @@ use crate::{
     operations::{OperationAPI, OperationResult},
     state::SessionStateAPI,
 };
+use futures::{StreamExt, stream::FuturesUnordered};
 use sources::{
+    TransportProtocol,
     command::process::ProcessSource,
+    http::{stream::HttpStreamSource, websocket::WebSocketSource},
+    mqtt::{MqttSource, MqttTopicSource},
+    recording::{Recorder, RecordingFormat, RecordingSource},
+    replay::ReplaySource,
+    sde::SdeSender,
     serial::serialport::SerialSource,
-    socket::{tcp::TcpSource, udp::UdpSource},
+    socket::{
+        tcp::{TcpServer, TcpSource},
+        udp::{UdpSenderSource, UdpSource},
+    },
 };
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt::Display,
+    hash::Hash,
+    net::SocketAddr,
+};
+use tokio::{
+    join, select,
+    sync::{
+        mpsc::{self, error::TrySendError, unbounded_channel},
+        watch,
+    },
+};
+
+#[cfg(target_os = "linux")]
+use sources::can::SocketCanSource;
+#[cfg(unix)]
+use sources::socket::unix::UnixSocketSource;
 
 use super::SdeReceiver;
 
+/// Count of datagrams queued for the source of each UDP sender.
+const UDP_SENDER_QUEUE_LEN: usize = 1024;
+
+/// Count of messages queued for the source of each MQTT topic.
+const MQTT_TOPIC_QUEUE_LEN: usize = 1024;
+
 pub async fn observe_stream(
     operation_api: OperationAPI,
     state: SessionStateAPI,
     uuid: &str,
     transport: &stypes::Transport,
     parser: &stypes::ParserType,
+    recording: Option<&stypes::StreamRecording>,
     rx_sde: Option<SdeReceiver>,
 ) -> OperationResult<()> {
     let source_id = state.add_source(uuid).await?;
+    let recorder = |peer| stream_recorder(recording, transport, parser, peer);
     match transport {
         stypes::Transport::UDP(settings) => {
-            let udp_source = UdpSource::new(&settings.bind_addr, settings.multicast.clone())
+            let udp_source = UdpSource::new(
+                &settings.bind_addr,
+                settings.multicast.clone(),
+                &settings.allowed_senders,
+            )
+            .await
+            .map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Interrupted,
+                message: Some(format!("{e}")),
+            })?;
+            if settings.split_senders {
+                return split_udp_senders(
+                    operation_api,
+                    state,
+                    uuid,
+                    source_id,
+                    udp_source,
+                    parser,
+                    &recorder,
+                )
+                .await;
+            }
+            observing::run_source(
+                operation_api,
+                state,
+                RecordingSource::new(udp_source, recorder(None)),
+                source_id,
+                parser,
+                rx_sde,
+                None,
+            )
+            .await
+        }
+        stypes::Transport::TCP(settings) if settings.mode == stypes::TcpMode::Listen => {
+            listen_tcp(
+                operation_api,
+                state,
+                uuid,
+                source_id,
+                &settings.bind_addr,
+                parser,
+                &recorder,
+                rx_sde,
+            )
+            .await
+        }
+        stypes::Transport::TCP(settings) => {
+            let tcp_source = TcpSource::new(&settings.bind_addr, None, None)
                 .await
                 .map_err(|e| stypes::NativeError {
                     severity: stypes::Severity::ERROR,
@@ -32,7 +116,7 @@ pub async fn observe_stream(
             observing::run_source(
                 operation_api,
                 state,
-                udp_source,
+                RecordingSource::new(tcp_source, recorder(None)),
                 source_id,
                 parser,
                 rx_sde,
@@ -40,18 +124,41 @@ pub async fn observe_stream(
             )
             .await
         }
-        stypes::Transport::TCP(settings) => {
-            let tcp_source = TcpSource::new(&settings.bind_addr, None, None)
-                .await
-                .map_err(|e| stypes::NativeError {
+        stypes::Transport::Serial(settings) => {
+            let (tx_state, rx_state) = watch::channel(stypes::SourceConnectionState::Connected);
+            let serial_source =
+                SerialSource::new(settings, Some(tx_state)).map_err(|e| stypes::NativeError {
                     severity: stypes::Severity::ERROR,
                     kind: stypes::NativeErrorKind::Interrupted,
                     message: Some(format!("{e}")),
                 })?;
+            let (listening, _) = join!(
+                observing::run_source(
+                    operation_api.clone(),
+                    state,
+                    RecordingSource::new(serial_source, recorder(None)),
+                    source_id,
+                    parser,
+                    rx_sde,
+                    None,
+                ),
+                forward_source_state(&operation_api, rx_state)
+            );
+            listening
+        }
+        stypes::Transport::Process(settings) => {
+            let process_source =
+                ProcessSource::new(settings.clone())
+                    .await
+                    .map_err(|e| stypes::NativeError {
+                        severity: stypes::Severity::ERROR,
+                        kind: stypes::NativeErrorKind::Interrupted,
+                        message: Some(format!("{e}")),
+                    })?;
             observing::run_source(
                 operation_api,
                 state,
-                tcp_source,
+                RecordingSource::new(process_source, recorder(None)),
                 source_id,
                 parser,
                 rx_sde,
@@ -59,8 +166,13 @@ pub async fn observe_stream(
             )
             .await
         }
-        stypes::Transport::Serial(settings) => {
-            let serial_source = SerialSource::new(settings).map_err(|e| stypes::NativeError {
+        #[cfg(unix)]
+        stypes::Transport::UnixSocket(settings) => {
+            let unix_source = match settings.mode {
+                stypes::UnixSocketMode::Stream => UnixSocketSource::connect(&settings.path).await,
+                stypes::UnixSocketMode::Datagram => UnixSocketSource::bind_datagram(&settings.path),
+            }
+            .map_err(|e| stypes::NativeError {
                 severity: stypes::Severity::ERROR,
                 kind: stypes::NativeErrorKind::Interrupted,
                 message: Some(format!("{e}")),
@@ -68,7 +180,7 @@ pub async fn observe_stream(
             observing::run_source(
                 operation_api,
                 state,
-                serial_source,
+                RecordingSource::new(unix_source, recorder(None)),
                 source_id,
                 parser,
                 rx_sde,
@@ -76,14 +188,96 @@ pub async fn observe_stream(
             )
             .await
         }
-        stypes::Transport::Process(settings) => {
-            let process_source = ProcessSource::new(
-                settings.command.clone(),
-                settings.cwd.clone(),
-                settings.shell.clone(),
+        #[cfg(not(unix))]
+        stypes::Transport::UnixSocket(_) => Err(stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Configuration,
+            message: Some(String::from(
+                "Unix domain sockets aren't supported on this platform",
+            )),
+        }),
+        stypes::Transport::Mqtt(settings) => {
+            let mqtt_source =
+                MqttSource::connect(settings)
+                    .await
+                    .map_err(|e| stypes::NativeError {
+                        severity: stypes::Severity::ERROR,
+                        kind: stypes::NativeErrorKind::Interrupted,
+                        message: Some(format!("{e}")),
+                    })?;
+            if settings.split_topics {
+                return split_mqtt_topics(
+                    operation_api,
+                    state,
+                    uuid,
+                    source_id,
+                    mqtt_source,
+                    parser,
+                    recording,
+                    rx_sde,
+                )
+                .await;
+            }
+            observing::run_source(
+                operation_api,
+                state,
+                RecordingSource::new(mqtt_source, recorder(None)),
+                source_id,
+                parser,
+                rx_sde,
+                None,
             )
             .await
-            .map_err(|e| stypes::NativeError {
+        }
+        stypes::Transport::WebSocket(settings) => {
+            let (tx_state, rx_state) = watch::channel(stypes::SourceConnectionState::Connected);
+            let websocket_source = WebSocketSource::connect(settings, Some(tx_state))
+                .await
+                .map_err(|e| stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::Interrupted,
+                    message: Some(format!("{e}")),
+                })?;
+            let (listening, _) = join!(
+                observing::run_source(
+                    operation_api.clone(),
+                    state,
+                    RecordingSource::new(websocket_source, recorder(None)),
+                    source_id,
+                    parser,
+                    rx_sde,
+                    None,
+                ),
+                forward_source_state(&operation_api, rx_state)
+            );
+            listening
+        }
+        stypes::Transport::HttpStream(settings) => {
+            let (tx_state, rx_state) = watch::channel(stypes::SourceConnectionState::Connected);
+            let http_source = HttpStreamSource::connect(settings, Some(tx_state))
+                .await
+                .map_err(|e| stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::Interrupted,
+                    message: Some(format!("{e}")),
+                })?;
+            let (listening, _) = join!(
+                observing::run_source(
+                    operation_api.clone(),
+                    state,
+                    RecordingSource::new(http_source, recorder(None)),
+                    source_id,
+                    parser,
+                    rx_sde,
+                    None,
+                ),
+                forward_source_state(&operation_api, rx_state)
+            );
+            listening
+        }
+        #[cfg(target_os = "linux")]
+        stypes::Transport::SocketCan(settings) => {
+            let can_source = SocketCanSource::open(settings).map_err(|e| stypes::NativeError {
                 severity: stypes::Severity::ERROR,
                 kind: stypes::NativeErrorKind::Interrupted,
                 message: Some(format!("{e}")),
@@ -91,7 +285,7 @@ pub async fn observe_stream(
             observing::run_source(
                 operation_api,
                 state,
-                process_source,
+                RecordingSource::new(can_source, recorder(None)),
                 source_id,
                 parser,
                 rx_sde,
@@ -99,5 +293,458 @@ pub async fn observe_stream(
             )
             .await
         }
+        #[cfg(not(target_os = "linux"))]
+        stypes::Transport::SocketCan(_) => Err(stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Configuration,
+            message: Some(String::from("SocketCAN is supported on Linux only")),
+        }),
+        stypes::Transport::Replay(settings) => {
+            // Traces are recorded already, therefore replays aren't recorded again.
+            let replay_source = ReplaySource::new(settings).map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Configuration,
+                message: Some(format!("{e}")),
+            })?;
+            observing::run_source(
+                operation_api,
+                state,
+                replay_source,
+                source_id,
+                parser,
+                rx_sde,
+                None,
+            )
+            .await
+        }
+    }
+}
+
+/// Listens for incoming TCP clients and observes each accepted connection as its own source.
+///
+/// The first client is assigned to the source of the observe operation itself, while each
+/// following client gets a new source ID. Clients may disconnect at any time and the listener
+/// keeps accepting new ones until the operation is cancelled.
+///
+/// Data sent through SDE is delivered to the most recently connected client.
+async fn listen_tcp(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    uuid: &str,
+    source_id: u16,
+    bind_addr: &str,
+    parser: &stypes::ParserType,
+    recorder: &impl Fn(Option<SocketAddr>) -> Option<Recorder>,
+    mut rx_sde: Option<SdeReceiver>,
+) -> OperationResult<()> {
+    let server = TcpServer::bind(bind_addr, None)
+        .await
+        .map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Interrupted,
+            message: Some(format!("Fail to listen on {bind_addr}: {e}")),
+        })?;
+    state.set_session_file(None).await?;
+    operation_api.processing();
+    let cancel = operation_api.cancellation_token();
+    let mut initial_source_id = Some(source_id);
+    let mut clients = FuturesUnordered::new();
+    let mut sde_senders: Vec<SdeSender> = Vec::new();
+    loop {
+        select! {
+            accepted = server.accept() => {
+                let (tcp_source, peer) = match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        log::warn!("Fail to accept TCP client on {bind_addr}: {err}");
+                        continue;
+                    }
+                };
+                let source_id = match initial_source_id.take() {
+                    Some(source_id) => source_id,
+                    None => state.add_source(&format!("{uuid}@{peer}")).await?,
+                };
+                let (tx_sde, rx_client_sde) = unbounded_channel();
+                sde_senders.push(tx_sde);
+                clients.push(observing::run_source(
+                    operation_api.clone(),
+                    state.clone(),
+                    RecordingSource::new(tcp_source, recorder(Some(peer))),
+                    source_id,
+                    parser,
+                    Some(rx_client_sde),
+                    None,
+                ));
+            }
+            Some(result) = clients.next() => {
+                result?;
+            }
+            Some((msg, tx_response)) = async {
+                match rx_sde.as_mut() {
+                    Some(rx_sde) => rx_sde.recv().await,
+                    None => None,
+                }
+            } => {
+                sde_senders.retain(|tx| !tx.is_closed());
+                let undelivered = match sde_senders.last() {
+                    Some(tx) => tx.send((msg, tx_response)).err().map(|err| err.0.1),
+                    None => Some(tx_response),
+                };
+                if let Some(tx_response) = undelivered
+                    && tx_response
+                        .send(Err(String::from("No TCP client is connected")))
+                        .is_err()
+                {
+                    log::warn!("Fail to send back message from source");
+                }
+            }
+            _ = cancel.cancelled() => break,
+        }
+    }
+    while let Some(result) = clients.next().await {
+        result?;
+    }
+    Ok(None)
+}
+
+/// Observes the datagrams of each sender of a UDP socket as their own source.
+///
+/// The first sender is assigned to the source of the observe operation itself, while each
+/// following sender gets a new source ID, which is registered with the address of the sender.
+/// Datagrams are received until the operation is cancelled. Datagrams of a sender are dropped
+/// while the queue of its source is full.
+async fn split_udp_senders(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    uuid: &str,
+    source_id: u16,
+    mut udp_source: UdpSource,
+    parser: &stypes::ParserType,
+    recorder: &impl Fn(Option<SocketAddr>) -> Option<Recorder>,
+) -> OperationResult<()> {
+    state.set_session_file(None).await?;
+    operation_api.processing();
+    let cancel = operation_api.cancellation_token();
+    let mut senders = SplitSources::new(source_id, UDP_SENDER_QUEUE_LEN);
+    loop {
+        select! {
+            received = udp_source.recv_datagram() => {
+                let (datagram, sender) = received.map_err(|e| stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::Interrupted,
+                    message: Some(format!("{e}")),
+                })?;
+                senders
+                    .queue(&state, uuid, sender, datagram.to_vec(), |source_id, rx_datagrams| {
+                        observing::run_source(
+                            operation_api.clone(),
+                            state.clone(),
+                            RecordingSource::new(
+                                UdpSenderSource::new(sender, rx_datagrams),
+                                recorder(Some(sender)),
+                            ),
+                            source_id,
+                            parser,
+                            None,
+                            None,
+                        )
+                    })
+                    .await?;
+            }
+            Some(result) = senders.next_finished() => {
+                result?;
+            }
+            _ = cancel.cancelled() => break,
+        }
+    }
+    senders.close().await
+}
+
+/// Observes the messages of each topic of an MQTT subscription as their own source.
+///
+/// The first topic is assigned to the source of the observe operation itself, while each
+/// following topic gets a new source ID, which is registered with the name of the topic.
+/// Messages are received until the operation is cancelled. Messages of a topic are dropped
+/// while the queue of its source is full.
+#[allow(clippy::too_many_arguments)]
+async fn split_mqtt_topics(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    uuid: &str,
+    source_id: u16,
+    mut mqtt_source: MqttSource,
+    parser: &stypes::ParserType,
+    recording: Option<&stypes::StreamRecording>,
+    mut rx_sde: Option<SdeReceiver>,
+) -> OperationResult<()> {
+    state.set_session_file(None).await?;
+    operation_api.processing();
+    let cancel = operation_api.cancellation_token();
+    let mut topics = SplitSources::new(source_id, MQTT_TOPIC_QUEUE_LEN);
+    loop {
+        select! {
+            received = mqtt_source.recv_message() => {
+                let (topic, payload) = received.map_err(|e| stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::Interrupted,
+                    message: Some(format!("{e}")),
+                })?;
+                topics
+                    .queue(&state, uuid, topic.clone(), payload, |source_id, rx_payloads| {
+                        let recorder = recording.map(|recording| {
+                            Recorder::new(
+                                recording.clone(),
+                                parsed_recording_format(parser).unwrap_or(RAW_RECORDING_FORMAT),
+                                // Topics differing only in their special characters
+                                // have equal labels, which the source id sets apart.
+                                &format!("mqtt-{source_id}-{topic}"),
+                            )
+                        });
+                        observing::run_source(
+                            operation_api.clone(),
+                            state.clone(),
+                            RecordingSource::new(
+                                MqttTopicSource::new(topic.clone(), rx_payloads),
+                                recorder,
+                            ),
+                            source_id,
+                            parser,
+                            None,
+                            None,
+                        )
+                    })
+                    .await?;
+            }
+            Some(result) = topics.next_finished() => {
+                result?;
+            }
+            Some((msg, tx_response)) = async {
+                match rx_sde.as_mut() {
+                    Some(rx_sde) => rx_sde.recv().await,
+                    None => None,
+                }
+            } => {
+                let response = match msg {
+                    stypes::SdeRequest::WriteText(text) => mqtt_source.publish(text.into_bytes()),
+                    stypes::SdeRequest::WriteBytes(bytes) => mqtt_source.publish(bytes),
+                    stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => Err(sources::Error::NotSupported),
+                };
+                let response = response
+                    .map(|bytes| stypes::SdeResponse { bytes })
+                    .map_err(|err| err.to_string());
+                if tx_response.send(response).is_err() {
+                    log::warn!("Fail to send back message from source");
+                }
+            }
+            _ = cancel.cancelled() => break,
+        }
+    }
+    topics.close().await
+}
+
+/// Queue of the items of one part of a split stream with the count of items dropped since
+/// the queue was full.
+struct SplitQueue<T> {
+    tx_items: mpsc::Sender<T>,
+    /// Alias of the source of the part.
+    alias: String,
+    dropped: u64,
+}
+
+/// Sources of the parts of a stream observed separately, like the senders of a UDP socket.
+///
+/// The items of each part are queued for the source of the part, while the sources run in
+/// the loop receiving the items. Therefore items are dropped if the queue of their part is
+/// full rather than waiting for the source to read them.
+struct SplitSources<K, T, F> {
+    /// Source of the observe operation, which is assigned to the first part.
+    initial_source_id: Option<u16>,
+    queue_len: usize,
+    queues: HashMap<K, SplitQueue<T>>,
+    sources: FuturesUnordered<F>,
+}
+
+impl<K, T, F> SplitSources<K, T, F>
+where
+    K: Eq + Hash + Display,
+    F: Future<Output = OperationResult<()>>,
+{
+    fn new(source_id: u16, queue_len: usize) -> Self {
+        Self {
+            initial_source_id: Some(source_id),
+            queue_len,
+            queues: HashMap::new(),
+            sources: FuturesUnordered::new(),
+        }
+    }
+
+    /// Queues the item of the part `key`. The first item of a part starts its source with
+    /// `run_source`, which gets the source ID and the queue of the part.
+    async fn queue(
+        &mut self,
+        state: &SessionStateAPI,
+        uuid: &str,
+        key: K,
+        item: T,
+        run_source: impl FnOnce(u16, mpsc::Receiver<T>) -> F,
+    ) -> Result<(), stypes::NativeError> {
+        let queue = match self.queues.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let alias = format!("{uuid}@{}", entry.key());
+                let source_id = match self.initial_source_id.take() {
+                    Some(source_id) => source_id,
+                    None => state.add_source(&alias).await?,
+                };
+                let (tx_items, rx_items) = mpsc::channel(self.queue_len);
+                self.sources.push(run_source(source_id, rx_items));
+                entry.insert(SplitQueue {
+                    tx_items,
+                    alias,
+                    dropped: 0,
+                })
+            }
+        };
+        match queue.tx_items.try_send(item) {
+            Ok(()) => queue.dropped = 0,
+            Err(TrySendError::Full(_)) => {
+                queue.dropped += 1;
+                // Logging is thinned out while the queue stays full.
+                if queue.dropped.is_power_of_two() {
+                    log::warn!(
+                        "Queue of source {} is full; dropped {} items",
+                        queue.alias,
+                        queue.dropped
+                    );
+                }
+            }
+            Err(TrySendError::Closed(_)) => log::warn!("Source {} is closed", queue.alias),
+        }
+        Ok(())
+    }
+
+    /// Waits for the next source to end, returning `None` if no source is running.
+    async fn next_finished(&mut self) -> Option<OperationResult<()>> {
+        self.sources.next().await
+    }
+
+    /// Closes the queues, which ends the sources once they read the queued items, and waits
+    /// for the sources.
+    async fn close(mut self) -> OperationResult<()> {
+        self.queues.clear();
+        while let Some(result) = self.sources.next().await {
+            result?;
+        }
+        Ok(None)
+    }
+}
+
+/// Creates the recorder of the raw bytes of a stream source if recording is requested.
+///
+/// `peer` is the address of the remote side of network sources if known, which is used in
+/// recordings of network traffic and distinguishes the recordings of multiple peers.
+fn stream_recorder(
+    recording: Option<&stypes::StreamRecording>,
+    transport: &stypes::Transport,
+    parser: &stypes::ParserType,
+    peer: Option<SocketAddr>,
+) -> Option<Recorder> {
+    let recording = recording?;
+    if matches!(transport, stypes::Transport::Replay(_)) {
+        return None;
+    }
+    let unknown_addr = SocketAddr::from(([0, 0, 0, 0], 0));
+    let parse_addr = |addr: &str| addr.parse::<SocketAddr>().unwrap_or(unknown_addr);
+    let network = match transport {
+        stypes::Transport::UDP(settings) => Some((
+            TransportProtocol::UDP,
+            peer.unwrap_or(unknown_addr),
+            parse_addr(&settings.bind_addr),
+            "udp",
+        )),
+        stypes::Transport::TCP(settings) => match settings.mode {
+            stypes::TcpMode::Connect => Some((
+                TransportProtocol::TCP,
+                parse_addr(&settings.bind_addr),
+                unknown_addr,
+                "tcp",
+            )),
+            stypes::TcpMode::Listen => Some((
+                TransportProtocol::TCP,
+                peer.unwrap_or(unknown_addr),
+                parse_addr(&settings.bind_addr),
+                "tcp",
+            )),
+        },
+        _ => None,
+    };
+    let format = match (parsed_recording_format(parser), network.clone()) {
+        (Some(format), _) => format,
+        (None, Some((protocol, source, destination, _))) => RecordingFormat::PcapNg {
+            protocol,
+            source,
+            destination,
+        },
+        (None, None) => RAW_RECORDING_FORMAT,
+    };
+    let label = match (network, transport) {
+        (Some((.., label)), _) => label,
+        (None, stypes::Transport::Serial(_)) => "serial",
+        (None, stypes::Transport::Process(_)) => "process",
+        (None, stypes::Transport::Mqtt(_)) => "mqtt",
+        (None, stypes::Transport::WebSocket(_)) => "websocket",
+        (None, stypes::Transport::HttpStream(_)) => "http",
+        (None, stypes::Transport::SocketCan(_)) => "can",
+        (None, stypes::Transport::UnixSocket(_)) => "unix-socket",
+        // Network sources are labelled above and replays aren't recorded.
+        (
+            None,
+            stypes::Transport::TCP(_) | stypes::Transport::UDP(_) | stypes::Transport::Replay(_),
+        ) => return None,
+    };
+    let label = match peer {
+        Some(peer) => format!("{label}-{peer}"),
+        None => label.to_owned(),
+    };
+
+    Some(Recorder::new(recording.clone(), format, &label))
+}
+
+/// Format of recordings which can't be opened with a parser of their own.
+const RAW_RECORDING_FORMAT: RecordingFormat = RecordingFormat::Raw {
+    file_format: stypes::FileFormat::Binary,
+    extension: "bin",
+};
+
+/// Returns the format of recordings which can be reopened as files with the given parser.
+fn parsed_recording_format(parser: &stypes::ParserType) -> Option<RecordingFormat> {
+    match parser {
+        stypes::ParserType::Dlt(settings) if !settings.with_storage_header => {
+            Some(RecordingFormat::Dlt)
+        }
+        stypes::ParserType::Dlt(_) => Some(RecordingFormat::Raw {
+            file_format: stypes::FileFormat::Binary,
+            extension: "dlt",
+        }),
+        stypes::ParserType::Text(()) => Some(RecordingFormat::Raw {
+            file_format: stypes::FileFormat::Text,
+            extension: "txt",
+        }),
+        stypes::ParserType::SomeIp(_) | stypes::ParserType::Plugin(_) => None,
+    }
+}
+
+/// Forwards the changes of the connection state of a source to the client until the source
+/// is dropped.
+async fn forward_source_state(
+    operation_api: &OperationAPI,
+    mut rx_state: watch::Receiver<stypes::SourceConnectionState>,
+) {
+    while rx_state.changed().await.is_ok() {
+        let state = rx_state.borrow_and_update().clone();
+        operation_api.emit(stypes::CallbackEvent::SourceStateChanged {
+            uuid: operation_api.id(),
+            state,
+        });
     }
 }
@@ -9,10 +9,12 @@ use log::debug;
 use processor::{
     map::FiltersStats,
     search::{
-        filter::SearchFilter,
+        highlight::MatchFinder,
+        query::FilterQuery,
         searchers::{self, regular::RegularSearchHolder},
     },
 };
+use std::ops::RangeInclusive;
 use tokio::{
     select,
     sync::mpsc::{Receiver, Sender, channel},
@@ -66,35 +68,67 @@ async fn wait_until_search_dropped(state: &SessionStateAPI) -> Result<(), stypes
     })
 }
 
+/// Resolves the time into the ranges of rows to search.
+///
+/// While the time reaches past the latest row, the last range stays open, so rows
+/// appended to the session later are searched as well.
+async fn rows_in_time(
+    state: &SessionStateAPI,
+    time: RangeInclusive<u64>,
+    rows: u64,
+) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+    let reaches_end = state
+        .get_time_bounds()
+        .await?
+        .is_some_and(|bounds| bounds.end() <= time.end());
+    let mut ranges = state.get_time_rows(time).await?;
+    if reaches_end
+        && let Some(last) = ranges.last_mut()
+        && *last.end() + 1 == rows
+    {
+        *last = *last.start()..=u64::MAX;
+    }
+    Ok(ranges)
+}
+
 pub async fn execute_search(
     operation_api: &OperationAPI,
-    filters: Vec<SearchFilter>,
+    query: FilterQuery,
+    time: Option<RangeInclusive<u64>>,
     state: SessionStateAPI,
 ) -> OperationResult<u64> {
     debug!("RUST: Search operation is requested");
     wait_until_search_dropped(&state).await?;
     let (rows, read_bytes) = state.get_stream_len().await?;
+    let search_rows = match time {
+        Some(time) => Some(rows_in_time(&state, time, rows).await?),
+        None => None,
+    };
     let mut holder = state.get_search_holder(operation_api.id()).await?;
-    if let Err(err) = holder
-        .setup(filters.clone())
-        .map_err(|e| stypes::NativeError {
-            severity: stypes::Severity::ERROR,
-            kind: stypes::NativeErrorKind::OperationSearch,
-            message: Some(format!("Fail to setup search terms: {e}")),
-        })
-    {
+    let filters: Vec<_> = query.filters().into_iter().cloned().collect();
+    let is_empty = filters.is_empty();
+    if let Err(err) = holder.setup_query(query).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::OperationSearch,
+        message: Some(format!("Fail to setup search terms: {e}")),
+    }) {
         state
             .set_search_holder(Some(holder), operation_api.id())
             .await?;
         return Err(err);
     }
-    if filters.is_empty() {
+    holder.set_rows(search_rows);
+    if is_empty {
         debug!("RUST: Search are dropped. Filters are empty");
         state
             .set_search_holder(Some(holder), operation_api.id())
             .await?;
         Ok(Some(0))
     } else {
+        // The filters are already validated by the holder, so the finder is expected. It's
+        // installed with the matches, as the matches of the previous search can still be
+        // grabbed until then.
+        let finder = MatchFinder::new(filters).ok();
         let (tx_result, mut rx_result): SearchResultChannel = channel(1);
         let cancel = operation_api.cancellation_token();
         let cancel_search = operation_api.cancellation_token();
@@ -139,7 +173,7 @@ pub async fn execute_search(
                         }
                         Err(_) => {
                             if !cancel.is_cancelled() {
-                                state.set_matches(None, None).await.map_err(|err| (None, err))?;
+                                state.set_matches(None, None, None).await.map_err(|err| (None, err))?;
                             }
                         },
                     };
@@ -163,7 +197,9 @@ pub async fn execute_search(
                         .await?;
                     // stats - isn't big object, it's small hashmap and clone operation here will not decrease performance.
                     // even this happens just once per search
-                    state.set_matches(Some(matches), Some(stats)).await?;
+                    state
+                        .set_matches(Some(matches), Some(stats), finder)
+                        .await?;
                     Ok(Some(found as u64))
                 }
                 Err((holder, err)) => {
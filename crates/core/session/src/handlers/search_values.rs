@@ -6,9 +6,12 @@ use crate::{
     state::SessionStateAPI,
 };
 use log::debug;
-use processor::search::searchers::{
-    self,
-    values::{ValueSearchHolder, ValueSearchMatch, ValueSearchOutput},
+use processor::search::{
+    filter::SearchFilter,
+    searchers::{
+        self,
+        values::{ValueSearchHolder, ValueSearchMatch, ValueSearchOutput},
+    },
 };
 use std::collections::HashMap;
 use tokio::{
@@ -62,7 +65,7 @@ async fn wait_until_search_values_dropped(
 
 pub async fn execute_value_search(
     operation_api: &OperationAPI,
-    filters: Vec<String>,
+    filters: Vec<SearchFilter>,
     state: SessionStateAPI,
 ) -> OperationResult<()> {
     debug!("RUST: Search values operation is requested");
@@ -0,0 +1,41 @@
+//! Includes the implementation of mining log templates, which groups the session rows into
+//! templates of similar messages.
+
+use crate::{
+    handlers::read_blocking,
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+};
+use log::debug;
+use processor::templates::{Histogram, MinerOptions, TemplateMiner};
+
+/// Mines the templates of the session rows and keeps them in the session state. The
+/// messages of each template are counted in `buckets` intervals of the session time.
+///
+/// Returns the number of mined templates.
+pub async fn execute_templates(
+    operation_api: &OperationAPI,
+    buckets: u16,
+    state: SessionStateAPI,
+) -> OperationResult<u64> {
+    debug!("RUST: Template mining operation is requested");
+    let filename = state.get_session_file().await?;
+    let (rows, _read_bytes) = state.get_stream_len().await?;
+    let time_index = state.get_time_index().await?;
+    let histogram = time_index
+        .bounds()
+        .map(|time| Histogram::new(time, buckets));
+    let cancel = operation_api.cancellation_token();
+    let mined = read_blocking("template mining", move || {
+        let mut miner = TemplateMiner::new(MinerOptions::default(), histogram);
+        let done = miner.add_file(&filename, rows, time_index.lookup(), &cancel)?;
+        Ok(done.then(|| miner.into_templates()))
+    })
+    .await?;
+    let Some(templates) = mined else {
+        return Ok(None);
+    };
+    let count = templates.templates.len() as u64;
+    state.set_templates(Some(templates)).await?;
+    Ok(Some(count))
+}
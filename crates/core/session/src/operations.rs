@@ -1,10 +1,14 @@
 //! Operations definitions and their main functionalities, additionally it includes
 //! Management of listening to incoming sessions then invoking them.
 
-use crate::{handlers, state::SessionStateAPI, tracker::OperationTrackerAPI};
+use crate::{
+    handlers,
+    state::{DuplicatesMode, SessionStateAPI},
+    tracker::OperationTrackerAPI,
+};
 use log::{debug, error, warn};
 use merging::merger::FileMergeOptions;
-use processor::search::filter::SearchFilter;
+use processor::search::{filter::SearchFilter, query::FilterQuery};
 use serde::Serialize;
 use sources::sde::{SdeReceiver, SdeSender};
 use std::{ops::RangeInclusive, path::PathBuf};
@@ -34,10 +38,35 @@ impl Operation {
 pub enum OperationKind {
     Observe(stypes::ObserveOptions),
     Search {
-        filters: Vec<SearchFilter>,
+        query: FilterQuery,
+        /// Limits the search to the rows with timestamps within this time, in
+        /// milliseconds since the Unix epoch.
+        time: Option<RangeInclusive<u64>>,
     },
     SearchValues {
-        filters: Vec<String>,
+        filters: Vec<SearchFilter>,
+    },
+    /// Hides the rows matching any of the filters from the main view. Empty filters show
+    /// all rows again.
+    ApplyExclusions {
+        filters: Vec<SearchFilter>,
+    },
+    /// Mines templates of similar messages from the session rows and counts their messages
+    /// in `buckets` intervals of the session time.
+    MineTemplates {
+        buckets: u16,
+    },
+    /// Compares the session rows with the lines of another session file or log file by
+    /// their normalized messages. The lines of the other file are read up to `other_rows`
+    /// if given, like the rows of another session.
+    Compare {
+        other: PathBuf,
+        other_rows: Option<u64>,
+    },
+    /// Collapses runs of consecutive duplicate rows in the main view, comparing rows as
+    /// defined by `mode`. `None` shows all duplicates again.
+    CollapseDuplicates {
+        mode: Option<DuplicatesMode>,
     },
     /// Export operation containing parameters for exporting data.
     ///
@@ -45,6 +74,7 @@ pub enum OperationKind {
     ///
     /// * `out_path` - The file system path where the exported data will be saved.
     /// * `ranges` - A vector of inclusive ranges specifying the segments of data to export.
+    /// * `include_duplicates` - Whether rows of collapsed duplicates are exported.
     /// * `columns` - A vector of column indices indicating which columns to include in the export.
     /// * `spliter` - An optional string used as the record separator in session file to split log message to columns.
     /// * `delimiter` - An optional string used as the field delimiter within each record in output file.
@@ -58,6 +88,8 @@ pub enum OperationKind {
         out_path: PathBuf,
         /// The ranges of data to be exported, each defined as an inclusive range.
         ranges: Vec<std::ops::RangeInclusive<u64>>,
+        /// Whether rows of collapsed duplicates are exported.
+        include_duplicates: bool,
         /// The indices of the columns to include in the export.
         columns: Vec<usize>,
         /// An optional string used as the record separator in session file to split log message to columns. Defaults can be applied if `None`.
@@ -68,6 +100,7 @@ pub enum OperationKind {
     ExportRaw {
         out_path: PathBuf,
         ranges: Vec<std::ops::RangeInclusive<u64>>,
+        include_duplicates: bool,
     },
     Extract {
         filters: Vec<SearchFilter>,
@@ -101,6 +134,10 @@ impl std::fmt::Display for OperationKind {
                 OperationKind::Observe(_) => "Observing",
                 OperationKind::Search { .. } => "Searching",
                 OperationKind::SearchValues { .. } => "Searching values",
+                OperationKind::ApplyExclusions { .. } => "Applying exclusions",
+                OperationKind::MineTemplates { .. } => "Mining templates",
+                OperationKind::Compare { .. } => "Comparing",
+                OperationKind::CollapseDuplicates { .. } => "Collapsing duplicates",
                 OperationKind::Export { .. } => "Exporting",
                 OperationKind::ExportRaw { .. } => "Exporting as Raw",
                 OperationKind::Extract { .. } => "Extracting",
@@ -269,9 +306,9 @@ impl OperationAPI {
                     )
                     .await;
                 }
-                OperationKind::Search { filters } => {
+                OperationKind::Search { query, time } => {
                     api.finish(
-                        handlers::search::execute_search(&api, filters, state)
+                        handlers::search::execute_search(&api, query, time, state)
                             .await
                             .map(|v| v.map(stypes::ResultU64)),
                         operation_str,
@@ -285,9 +322,46 @@ impl OperationAPI {
                     )
                     .await;
                 }
+                OperationKind::ApplyExclusions { filters } => {
+                    api.finish(
+                        handlers::exclusions::execute_exclusions(&api, filters, state)
+                            .await
+                            .map(|v| v.map(stypes::ResultU64)),
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::MineTemplates { buckets } => {
+                    api.finish(
+                        handlers::templates::execute_templates(&api, buckets, state)
+                            .await
+                            .map(|v| v.map(stypes::ResultU64)),
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::Compare { other, other_rows } => {
+                    api.finish(
+                        handlers::compare::execute_compare(&api, other, other_rows, state)
+                            .await
+                            .map(|v| v.map(stypes::ResultU64)),
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::CollapseDuplicates { mode } => {
+                    api.finish(
+                        handlers::duplicates::execute_collapse(&api, mode, state)
+                            .await
+                            .map(|v| v.map(stypes::ResultU64)),
+                        operation_str,
+                    )
+                    .await;
+                }
                 OperationKind::Export {
                     out_path,
                     ranges,
+                    include_duplicates,
                     columns,
                     spliter,
                     delimiter,
@@ -297,6 +371,7 @@ impl OperationAPI {
                             .export_session(
                                 out_path,
                                 ranges,
+                                include_duplicates,
                                 columns,
                                 spliter,
                                 delimiter,
@@ -309,13 +384,18 @@ impl OperationAPI {
                     )
                     .await;
                 }
-                OperationKind::ExportRaw { out_path, ranges } => {
+                OperationKind::ExportRaw {
+                    out_path,
+                    ranges,
+                    include_duplicates,
+                } => {
                     api.finish(
                         handlers::export_raw::execute_export(
                             &api.cancellation_token(),
                             state,
                             out_path,
                             ranges,
+                            include_duplicates,
                         )
                         .await
                         .map(|v| v.map(stypes::ResultBool)),
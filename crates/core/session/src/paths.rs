@@ -6,6 +6,7 @@ use std::path::PathBuf;
 const CHIPMUNK_HOME: &str = ".chipmunk";
 const CHIPMUNK_TMP: &str = "tmp";
 const CHIPMUNK_DOWNLOADS: &str = "downloads";
+const CHIPMUNK_SEARCH_INDEXES: &str = "search_indexes";
 
 pub fn get_home_dir() -> Result<PathBuf, stypes::NativeError> {
     if let Some(home) = dirs::home_dir() {
@@ -37,6 +38,24 @@ pub fn get_streams_dir() -> Result<PathBuf, stypes::NativeError> {
     Ok(streams)
 }
 
+/// Gets the directory of the search indexes of linked files within Chipmunk home
+/// directory creating it if it doesn't exist.
+pub fn get_search_indexes_dir() -> Result<PathBuf, stypes::NativeError> {
+    let indexes = get_home_dir()?.join(CHIPMUNK_SEARCH_INDEXES);
+    if !indexes.exists() {
+        std::fs::create_dir_all(&indexes).map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to create search indexes folder {}: {}",
+                indexes.to_string_lossy(),
+                e
+            )),
+        })?;
+    }
+    Ok(indexes)
+}
+
 /// Gets downloads directory path within Chipmunk home directory.
 pub fn get_chipmunk_downloads_dir() -> Result<PathBuf, stypes::NativeError> {
     let downloads = get_home_dir()?.join(CHIPMUNK_DOWNLOADS);
@@ -5,13 +5,16 @@ use crate::{
     operations,
     operations::Operation,
     state,
-    state::{IndexedNavigation, NestedMatch, SessionStateAPI},
+    state::{DuplicatesMode, IndexedNavigation, NestedMatch, SessionStateAPI},
     tracker,
     tracker::OperationTrackerAPI,
 };
 use futures::Future;
 use log::{debug, error, warn};
-use processor::{grabber::LineRange, search::filter::SearchFilter};
+use processor::{
+    grabber::LineRange,
+    search::{filter::SearchFilter, query::FilterQuery},
+};
 use std::{ops::RangeInclusive, path::PathBuf};
 use tokio::{
     join,
@@ -156,6 +159,7 @@ impl Session {
         self.state.clone()
     }
 
+    /// Grabs the rows of the main view, which skips the rows hidden by exclusion filters.
     pub async fn grab(
         &self,
         range: LineRange,
@@ -196,6 +200,18 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Finds the main-view row showing a session position, or the first visible row after
+    /// it if the row is hidden by exclusion filters.
+    pub async fn get_view_row(
+        &self,
+        session_position: u64,
+    ) -> Result<u64, stypes::ComputationError> {
+        self.state
+            .get_view_row(session_position)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub async fn add_bookmark(&self, row: u64) -> Result<(), stypes::ComputationError> {
         self.state
             .add_bookmark(row)
@@ -254,6 +270,47 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Returns the earliest and the latest timestamp of the session rows in milliseconds
+    /// since the Unix epoch, or `None` if the logs carry no timestamps.
+    pub async fn get_time_bounds(
+        &self,
+    ) -> Result<Option<RangeInclusive<u64>>, stypes::ComputationError> {
+        self.state
+            .get_time_bounds()
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Grabs the rows with timestamps within the given time, in milliseconds since the
+    /// Unix epoch. Rows without a timestamp of their own belong to the row before them.
+    pub async fn grab_time_range(
+        &self,
+        time: RangeInclusive<u64>,
+    ) -> Result<stypes::GrabbedElementList, stypes::ComputationError> {
+        let ranges = self
+            .state
+            .get_time_rows(time)
+            .await
+            .map_err(stypes::ComputationError::NativeError)?;
+        if ranges.is_empty() {
+            return Ok(stypes::GrabbedElementList(Vec::new()));
+        }
+        self.grab_ranges(ranges).await
+    }
+
+    /// Finds the session position of the first row at or after the timestamp, in
+    /// milliseconds since the Unix epoch. Timestamps after the end of the session resolve
+    /// to the last row with a timestamp.
+    pub async fn get_row_at_time(
+        &self,
+        timestamp: u64,
+    ) -> Result<Option<u64>, stypes::ComputationError> {
+        self.state
+            .get_row_at_time(timestamp)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub fn abort(&self, operation_id: Uuid, target: Uuid) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
@@ -402,6 +459,7 @@ impl Session {
         operation_id: Uuid,
         out_path: PathBuf,
         ranges: Vec<RangeInclusive<u64>>,
+        include_duplicates: bool,
         columns: Vec<usize>,
         spliter: Option<String>,
         delimiter: Option<String>,
@@ -412,6 +470,7 @@ impl Session {
                 operations::OperationKind::Export {
                     out_path,
                     ranges,
+                    include_duplicates,
                     columns,
                     spliter,
                     delimiter,
@@ -425,11 +484,16 @@ impl Session {
         operation_id: Uuid,
         out_path: PathBuf,
         ranges: Vec<RangeInclusive<u64>>,
+        include_duplicates: bool,
     ) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
                 operation_id,
-                operations::OperationKind::ExportRaw { out_path, ranges },
+                operations::OperationKind::ExportRaw {
+                    out_path,
+                    ranges,
+                    include_duplicates,
+                },
             ))
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
@@ -449,7 +513,43 @@ impl Session {
         self.tx_operations
             .send(Operation::new(
                 operation_id,
-                operations::OperationKind::Search { filters },
+                operations::OperationKind::Search {
+                    query: FilterQuery::any(filters),
+                    time: None,
+                },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Searches for lines matching the boolean query over search filters.
+    pub fn apply_search_query(
+        &self,
+        operation_id: Uuid,
+        query: FilterQuery,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::Search { query, time: None },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Searches for lines matching the query among the rows with timestamps within the
+    /// given time, in milliseconds since the Unix epoch.
+    pub fn apply_search_query_in_time(
+        &self,
+        operation_id: Uuid,
+        query: FilterQuery,
+        time: RangeInclusive<u64>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::Search {
+                    query,
+                    time: Some(time),
+                },
             ))
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
@@ -457,7 +557,7 @@ impl Session {
     pub fn apply_search_values_filters(
         &self,
         operation_id: Uuid,
-        filters: Vec<String>,
+        filters: Vec<SearchFilter>,
     ) -> Result<(), stypes::ComputationError> {
         self.tx_operations
             .send(Operation::new(
@@ -467,6 +567,100 @@ impl Session {
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
 
+    /// Hides the rows matching any of the filters from the main view. Empty filters show
+    /// all rows again.
+    pub fn apply_exclusions(
+        &self,
+        operation_id: Uuid,
+        filters: Vec<SearchFilter>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::ApplyExclusions { filters },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Mines templates of similar messages from the session rows, counting their messages
+    /// in `buckets` intervals of the session time. The result is read with
+    /// [`Session::get_templates`] once the operation is done.
+    pub fn mine_templates(
+        &self,
+        operation_id: Uuid,
+        buckets: u16,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::MineTemplates { buckets },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Returns the templates of the last template mining, or `None` if not mined yet.
+    pub async fn get_templates(
+        &self,
+    ) -> Result<Option<stypes::LogTemplates>, stypes::ComputationError> {
+        self.state
+            .get_templates()
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Compares the session rows with the lines of another session file or log file, up
+    /// to `other_rows` lines of the other file if given.
+    pub fn compare(
+        &self,
+        operation_id: Uuid,
+        other: PathBuf,
+        other_rows: Option<u64>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::Compare { other, other_rows },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Returns the differences of the last comparison, or `None` if not compared yet.
+    pub async fn get_comparison(
+        &self,
+    ) -> Result<Option<stypes::LogDiff>, stypes::ComputationError> {
+        self.state
+            .get_comparison()
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Collapses runs of consecutive duplicate rows in the main view. `None` shows all
+    /// duplicates again.
+    pub fn collapse_duplicates(
+        &self,
+        operation_id: Uuid,
+        mode: Option<DuplicatesMode>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::CollapseDuplicates { mode },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Expands or collapses the run of duplicates the session position belongs to.
+    pub async fn expand_duplicates(
+        &self,
+        position: u64,
+        expanded: bool,
+    ) -> Result<bool, stypes::ComputationError> {
+        self.state
+            .expand_duplicates(position, expanded)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub async fn drop_search(&self) -> Result<bool, stypes::ComputationError> {
         self.state
             .drop_search()
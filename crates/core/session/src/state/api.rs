@@ -1,7 +1,7 @@
 use super::values::graph::CandlePoint;
 use crate::{
     state::{
-        NestedMatch, indexes::IndexedNavigation, observed::Observed,
+        Duplicates, NestedMatch, TimeIndex, indexes::IndexedNavigation, observed::Observed,
         session_file::SessionFileOrigin, values::ValuesError,
     },
     tracker::OperationTrackerAPI,
@@ -13,6 +13,7 @@ use processor::{
     map::{FiltersStats, ScaledDistribution},
     search::{
         filter::SearchFilter,
+        highlight::MatchFinder,
         searchers::{
             regular::RegularSearchHolder,
             values::{ValueSearchHolder, ValueSearchMatch},
@@ -37,16 +38,29 @@ pub enum Api {
         ),
     ),
     GetSessionFile(oneshot::Sender<Result<PathBuf, stypes::NativeError>>),
+    /// Appends the text of messages to the session file with the timestamps of the rows
+    /// they produce.
     WriteSessionFile(
         (
             u16,
             String,
+            Vec<Option<u64>>,
             oneshot::Sender<Result<(), stypes::NativeError>>,
         ),
     ),
     FlushSessionFile(oneshot::Sender<Result<(), stypes::NativeError>>),
     GetSessionFileOrigin(oneshot::Sender<Result<Option<SessionFileOrigin>, stypes::NativeError>>),
     UpdateSession((u16, oneshot::Sender<Result<bool, stypes::NativeError>>)),
+    /// Switches the linked session file to the generated file with the given path, holding
+    /// the rows of the linked file or empty lines instead of them, as rows of the source.
+    UnlinkSessionFile(
+        (
+            PathBuf,
+            bool,
+            u16,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
     AddSource((String, oneshot::Sender<u16>)),
     GetSource((String, oneshot::Sender<Option<u16>>)),
     GetSourcesDefinitions(oneshot::Sender<Vec<stypes::SourceDefinition>>),
@@ -75,6 +89,8 @@ pub enum Api {
         out_path: PathBuf,
         /// The ranges of data to be exported, each defined as an inclusive range.
         ranges: Vec<std::ops::RangeInclusive<u64>>,
+        /// Whether rows of collapsed duplicates are exported.
+        include_duplicates: bool,
         /// The indices of the columns to include in the export.
         columns: Vec<usize>,
         /// An optional string used as the record separator in session file to split log message to columns. Defaults can be applied if `None`.
@@ -117,6 +133,62 @@ pub enum Api {
         /// Receives the matching indexed row, or `None` when the indexed map is empty.
         tx_response: oneshot::Sender<Option<u64>>,
     },
+    /// Finds the main-view row showing a session position.
+    GetViewRow {
+        /// Original row position in the complete session stream.
+        session_position: u64,
+        /// Receives the main-view row, or the first visible row after a hidden one.
+        tx_response: oneshot::Sender<u64>,
+    },
+    /// Removes the rows hidden from the main view from ranges of session positions. Rows
+    /// of collapsed duplicates are kept if `include_duplicates` is set.
+    GetVisibleRanges {
+        ranges: Vec<RangeInclusive<u64>>,
+        include_duplicates: bool,
+        tx_response: oneshot::Sender<Vec<RangeInclusive<u64>>>,
+    },
+    /// Replaces the rows hidden from the main view and the searcher of the exclusion
+    /// filters, which keeps hiding matching rows as the session grows. Results of searchers
+    /// set by other exclusions operations than `operation` are dropped afterwards.
+    SetExclusions {
+        operation: Uuid,
+        holder: Option<RegularSearchHolder>,
+        rows: Vec<u64>,
+        tx_response: oneshot::Sender<Result<(), stypes::NativeError>>,
+    },
+    /// Gets the earliest and the latest timestamp of the session rows.
+    GetTimeBounds(oneshot::Sender<Option<RangeInclusive<u64>>>),
+    /// Gets the ranges of session positions with timestamps within the given time.
+    GetTimeRows {
+        time: RangeInclusive<u64>,
+        tx_response: oneshot::Sender<Vec<RangeInclusive<u64>>>,
+    },
+    /// Gets the first session position at or after the given timestamp.
+    GetRowAtTime {
+        timestamp: u64,
+        tx_response: oneshot::Sender<Option<u64>>,
+    },
+    /// Gets a snapshot of the time index to look up the timestamps of many rows.
+    GetTimeIndex(oneshot::Sender<TimeIndex>),
+    /// Replaces the templates mined from the session rows.
+    SetTemplates((Option<stypes::LogTemplates>, oneshot::Sender<()>)),
+    /// Gets the templates mined from the session rows, if mined.
+    GetTemplates(oneshot::Sender<Option<stypes::LogTemplates>>),
+    /// Replaces the differences from the log the session was compared with.
+    SetComparison((Option<stypes::LogDiff>, oneshot::Sender<()>)),
+    /// Gets the differences from the log the session was compared with, if compared.
+    GetComparison(oneshot::Sender<Option<stypes::LogDiff>>),
+    /// Replaces the runs of consecutive duplicate rows collapsed in the main view and
+    /// compares the rows added since they were found in the background. Responds with the
+    /// number of collapsed rows.
+    SetDuplicates((Option<Duplicates>, oneshot::Sender<u64>)),
+    /// Expands or collapses the run of duplicates a session position belongs to.
+    ExpandDuplicates {
+        position: u64,
+        expanded: bool,
+        /// Receives whether the run changed.
+        tx_response: oneshot::Sender<bool>,
+    },
     AddBookmark((u64, oneshot::Sender<()>)),
     SetBookmarks((Vec<u64>, oneshot::Sender<()>)),
     RemoveBookmark((u64, oneshot::Sender<()>)),
@@ -164,6 +236,7 @@ pub enum Api {
         (
             Option<Vec<stypes::FilterMatch>>,
             Option<FiltersStats>,
+            Option<MatchFinder>,
             oneshot::Sender<()>,
         ),
     ),
@@ -211,6 +284,7 @@ impl Display for Api {
                 Self::FlushSessionFile(_) => "FlushSessionFile",
                 Self::GetSessionFileOrigin(_) => "GetSessionFileOrigin",
                 Self::UpdateSession(_) => "UpdateSession",
+                Self::UnlinkSessionFile(_) => "UnlinkSessionFile",
                 Self::AddSource(_) => "AddSource",
                 Self::GetSource(_) => "GetSource",
                 Self::GetSourcesDefinitions(_) => "GetSourcesDefinitions",
@@ -231,6 +305,19 @@ impl Display for Api {
                 Self::GetIndexedMapLen(_) => "GetIndexedMapLen",
                 Self::GetNearestIndexedRow { .. } => "GetNearestIndexedRow",
                 Self::GetIndexedNeighbor { .. } => "GetIndexedNeighbor",
+                Self::GetViewRow { .. } => "GetViewRow",
+                Self::GetVisibleRanges { .. } => "GetVisibleRanges",
+                Self::SetExclusions { .. } => "SetExclusions",
+                Self::GetTimeBounds(_) => "GetTimeBounds",
+                Self::GetTimeRows { .. } => "GetTimeRows",
+                Self::GetRowAtTime { .. } => "GetRowAtTime",
+                Self::GetTimeIndex(_) => "GetTimeIndex",
+                Self::SetTemplates(_) => "SetTemplates",
+                Self::GetTemplates(_) => "GetTemplates",
+                Self::SetComparison(_) => "SetComparison",
+                Self::GetComparison(_) => "GetComparison",
+                Self::SetDuplicates(_) => "SetDuplicates",
+                Self::ExpandDuplicates { .. } => "ExpandDuplicates",
                 Self::AddBookmark(_) => "AddBookmark",
                 Self::SetBookmarks(_) => "SetBookmarks",
                 Self::RemoveBookmark(_) => "RemoveBookmark",
@@ -434,9 +521,10 @@ impl SessionStateAPI {
         &self,
         source_id: u16,
         msg: String,
+        timestamps: Vec<Option<u64>>,
     ) -> Result<(), stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::WriteSessionFile((source_id, msg, tx)), rx)
+        self.exec_operation(Api::WriteSessionFile((source_id, msg, timestamps, tx)), rx)
             .await?
     }
 
@@ -459,6 +547,20 @@ impl SessionStateAPI {
             .await?
     }
 
+    pub async fn unlink_session_file(
+        &self,
+        filename: PathBuf,
+        blank_rows: bool,
+        source_id: u16,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::UnlinkSessionFile((filename, blank_rows, source_id, tx)),
+            rx,
+        )
+        .await?
+    }
+
     pub async fn add_source(&self, uuid: &str) -> Result<u16, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
         self.exec_operation(Api::AddSource((uuid.to_owned(), tx)), rx)
@@ -506,6 +608,7 @@ impl SessionStateAPI {
     ///
     /// * `out_path` - A `PathBuf` representing the path to the output file where data will be exported.
     /// * `ranges` - A `Vec<RangeInclusive<u64>>` specifying the ranges of data to export.
+    /// * `include_duplicates` - Whether rows of collapsed duplicates are exported.
     /// * `columns` - A `Vec<usize>` containing the column number to be exported.
     /// * `spliter` - A `String` used as the record separator in session file to split log message to columns.
     /// * `delimiter` - A `String` used as the field delimiter within each record in output file.
@@ -521,6 +624,7 @@ impl SessionStateAPI {
         &self,
         out_path: PathBuf,
         ranges: Vec<std::ops::RangeInclusive<u64>>,
+        include_duplicates: bool,
         columns: Vec<usize>,
         spliter: Option<String>,
         delimiter: Option<String>,
@@ -531,6 +635,7 @@ impl SessionStateAPI {
             Api::ExportSession {
                 out_path,
                 ranges,
+                include_duplicates,
                 columns,
                 spliter,
                 delimiter,
@@ -566,18 +671,191 @@ impl SessionStateAPI {
             .await?
     }
 
+    /// Finds the main-view row showing a session position, or the first visible row after
+    /// it if the row is hidden.
+    pub async fn get_view_row(&self, session_position: u64) -> Result<u64, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::GetViewRow {
+                session_position,
+                tx_response: tx,
+            },
+            rx,
+        )
+        .await
+    }
+
+    /// Removes the rows hidden from the main view from the ranges of session positions.
+    /// Rows of collapsed duplicates are kept if `include_duplicates` is set.
+    pub async fn get_visible_ranges(
+        &self,
+        ranges: Vec<RangeInclusive<u64>>,
+        include_duplicates: bool,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::GetVisibleRanges {
+                ranges,
+                include_duplicates,
+                tx_response: tx,
+            },
+            rx,
+        )
+        .await
+    }
+
+    /// Hides the rows from the main view and keeps the holder to search the exclusion
+    /// filters in new rows. `None` drops the searcher. `operation` is the exclusions
+    /// operation which has set up the holder.
+    pub async fn set_exclusions(
+        &self,
+        operation: Uuid,
+        holder: Option<RegularSearchHolder>,
+        rows: Vec<u64>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::SetExclusions {
+                operation,
+                holder,
+                rows,
+                tx_response: tx,
+            },
+            rx,
+        )
+        .await?
+    }
+
+    /// Returns the earliest and the latest timestamp of the session rows in milliseconds
+    /// since the Unix epoch, or `None` if no row has a timestamp.
+    pub async fn get_time_bounds(
+        &self,
+    ) -> Result<Option<RangeInclusive<u64>>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimeBounds(tx), rx).await
+    }
+
+    /// Returns the ranges of session positions with timestamps within the given time.
+    pub async fn get_time_rows(
+        &self,
+        time: RangeInclusive<u64>,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::GetTimeRows {
+                time,
+                tx_response: tx,
+            },
+            rx,
+        )
+        .await
+    }
+
+    /// Returns the first session position at or after the given timestamp.
+    pub async fn get_row_at_time(
+        &self,
+        timestamp: u64,
+    ) -> Result<Option<u64>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::GetRowAtTime {
+                timestamp,
+                tx_response: tx,
+            },
+            rx,
+        )
+        .await
+    }
+
+    /// Returns a snapshot of the time index of the session rows, which shares its data
+    /// with the index of the session until rows are appended.
+    pub async fn get_time_index(&self) -> Result<TimeIndex, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimeIndex(tx), rx).await
+    }
+
+    /// Replaces the templates mined from the session rows. `None` drops them.
+    pub async fn set_templates(
+        &self,
+        templates: Option<stypes::LogTemplates>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetTemplates((templates, tx)), rx)
+            .await
+    }
+
+    /// Returns the templates mined from the session rows, or `None` if not mined yet.
+    pub async fn get_templates(&self) -> Result<Option<stypes::LogTemplates>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTemplates(tx), rx).await
+    }
+
+    /// Replaces the differences from the log the session was compared with. `None` drops
+    /// them.
+    pub async fn set_comparison(
+        &self,
+        comparison: Option<stypes::LogDiff>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetComparison((comparison, tx)), rx)
+            .await
+    }
+
+    /// Returns the differences from the log the session was compared with, or `None` if
+    /// not compared yet.
+    pub async fn get_comparison(&self) -> Result<Option<stypes::LogDiff>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetComparison(tx), rx).await
+    }
+
+    /// Replaces the runs of consecutive duplicate rows collapsed in the main view. `None`
+    /// shows all duplicates again.
+    ///
+    /// Returns the number of collapsed rows.
+    pub async fn set_duplicates(
+        &self,
+        duplicates: Option<Duplicates>,
+    ) -> Result<u64, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetDuplicates((duplicates, tx)), rx)
+            .await
+    }
+
+    /// Expands or collapses the run of duplicates the session position belongs to.
+    /// Returns whether the run changed.
+    pub async fn expand_duplicates(
+        &self,
+        position: u64,
+        expanded: bool,
+    ) -> Result<bool, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(
+            Api::ExpandDuplicates {
+                position,
+                expanded,
+                tx_response: tx,
+            },
+            rx,
+        )
+        .await
+    }
+
     pub async fn drop_search(&self) -> Result<bool, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
         self.exec_operation(Api::DropSearch(tx), rx).await
     }
 
+    /// Sets the matches of the search together with the finder of the match ranges
+    /// attached to grabbed rows matched by the search, so highlights never mix the
+    /// matches of one search with the filters of another.
     pub async fn set_matches(
         &self,
         matches: Option<Vec<stypes::FilterMatch>>,
         stats: Option<FiltersStats>,
+        finder: Option<MatchFinder>,
     ) -> Result<(), stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::SetMatches((matches, stats, tx)), rx)
+        self.exec_operation(Api::SetMatches((matches, stats, finder, tx)), rx)
             .await
     }
 
@@ -5,12 +5,27 @@ use std::ops::RangeInclusive;
 use log::error;
 use tokio::sync::mpsc::UnboundedSender;
 
-use super::{IndexedNavigation, frame::Frame, map::Map, nature::Nature};
+use super::{
+    IndexedNavigation,
+    duplicates::{ComparedRows, Duplicates},
+    frame::Frame,
+    hidden::HiddenRows,
+    map::Map,
+    nature::Nature,
+};
 
 /// The controller of search results view.
+///
+/// It also owns the rows hidden from the main view. Rows excluded by exclusion filters are
+/// left out of the search results view as well, while rows of collapsed duplicates are
+/// hidden from the main view only.
 #[derive(Debug)]
 pub struct Controller {
     map: Map,
+    /// Rows hidden from the main view, which are the excluded and the collapsed rows.
+    hidden: HiddenRows,
+    /// Runs of consecutive duplicate rows, if collapsing duplicates is enabled.
+    duplicates: Option<Duplicates>,
     tx_callback_events: Option<UnboundedSender<stypes::CallbackEvent>>,
 }
 
@@ -18,12 +33,27 @@ impl Controller {
     pub(crate) fn new(tx_callback_events: Option<UnboundedSender<stypes::CallbackEvent>>) -> Self {
         Self {
             map: Map::new(),
+            hidden: HiddenRows::new(),
+            duplicates: None,
             tx_callback_events,
         }
     }
 
+    /// Inserts the rows into the map, keeping excluded rows out of the indexed projection.
+    fn insert(&mut self, rows: impl IntoIterator<Item = u64>, nature: Nature) {
+        if self.hidden.excluded().is_empty() {
+            self.map.insert(rows, nature);
+            return;
+        }
+        let (hidden, visible): (Vec<u64>, Vec<u64>) = rows
+            .into_iter()
+            .partition(|row| self.hidden.is_excluded(*row));
+        self.map.insert(visible, nature);
+        self.map.insert_hidden(hidden, nature);
+    }
+
     pub(crate) fn add_bookmark(&mut self, row: u64) {
-        self.map.insert([row], Nature::BOOKMARK);
+        self.insert([row], Nature::BOOKMARK);
         self.notify();
     }
 
@@ -33,12 +63,21 @@ impl Controller {
     }
 
     pub(crate) fn set_bookmarks(&mut self, rows: Vec<u64>) {
-        self.map.insert(rows, Nature::BOOKMARK);
+        self.insert(rows, Nature::BOOKMARK);
         self.notify();
     }
 
     pub(crate) fn set_stream_len(&mut self, len: u64) {
         self.map.set_stream_len(len);
+        if len == 0 {
+            if let Some(duplicates) = self.duplicates.as_mut() {
+                *duplicates = Duplicates::new(duplicates.mode());
+            }
+            if !self.hidden.is_empty() {
+                self.hidden.clear();
+                self.notify_hidden();
+            }
+        }
         self.notify();
     }
 
@@ -49,7 +88,7 @@ impl Controller {
 
     pub(crate) fn set_search_results(&mut self, matches: &[stypes::FilterMatch]) {
         self.map.clean(Nature::SEARCH);
-        self.map.insert(
+        self.insert(
             matches.iter().map(|filter_match| filter_match.index),
             Nature::SEARCH,
         );
@@ -57,13 +96,147 @@ impl Controller {
     }
 
     pub(crate) fn append_search_results(&mut self, matches: &[stypes::FilterMatch]) {
-        self.map.insert(
+        self.insert(
             matches.iter().map(|filter_match| filter_match.index),
             Nature::SEARCH,
         );
         self.notify();
     }
 
+    /// Returns the rows hidden from the main view.
+    pub(crate) fn hidden(&self) -> &HiddenRows {
+        &self.hidden
+    }
+
+    /// Replaces the rows hidden by exclusion filters.
+    pub(crate) fn set_hidden_rows(&mut self, rows: Vec<u64>) {
+        self.map.show_all();
+        self.hidden.set_excluded(rows);
+        if !self.hidden.excluded().is_empty() {
+            self.map.hide(self.hidden.excluded());
+        }
+        self.refresh_collapsed();
+        self.notify();
+    }
+
+    /// Hides additional rows by exclusion filters, e.g. matches in newly added logs.
+    pub(crate) fn append_hidden_rows(&mut self, rows: Vec<u64>) {
+        if rows.is_empty() {
+            return;
+        }
+        self.map.hide(&rows);
+        self.hidden.append_excluded(rows.iter().copied());
+        // Excluded rows aren't collapsed, and excluded heads pass their runs on to the
+        // next rows, so the collapsed rows of the runs of the excluded rows change.
+        if let Some(duplicates) = self.duplicates.as_ref() {
+            let mut runs: Vec<&RangeInclusive<u64>> =
+                rows.iter().filter_map(|row| duplicates.run(*row)).collect();
+            runs.dedup();
+            for run in runs {
+                let hidden = &self.hidden;
+                let collapsed: Vec<u64> = duplicates
+                    .collapsed_in(run, |row| hidden.is_excluded(row))
+                    .collect();
+                self.hidden.replace_collapsed(run, collapsed);
+            }
+        }
+        self.notify_hidden();
+        self.notify();
+    }
+
+    /// Returns the runs of consecutive duplicate rows, if collapsing duplicates is enabled.
+    pub(crate) fn duplicates(&self) -> Option<&Duplicates> {
+        self.duplicates.as_ref()
+    }
+
+    /// Returns the run of duplicates shown by the row, which is the first row of the run
+    /// not excluded, with the number of its rows not excluded.
+    pub(crate) fn shown_run(&self, row: u64) -> Option<(&RangeInclusive<u64>, u64)> {
+        let run = self.duplicates.as_ref()?.run(row)?;
+        let head = Duplicates::head(run, |position| self.hidden.is_excluded(position));
+        (head == Some(row)).then(|| {
+            let len = run.end() - run.start() + 1;
+            (run, len - self.hidden.excluded_within(run))
+        })
+    }
+
+    /// Replaces the runs of consecutive duplicate rows. `None` shows all duplicates again.
+    pub(crate) fn set_duplicates(&mut self, duplicates: Option<Duplicates>) {
+        self.duplicates = duplicates;
+        self.refresh_collapsed();
+    }
+
+    /// Continues the runs of duplicates with the next compared rows of the session and
+    /// collapses the rows repeating the row before them. Rows compared for other runs are
+    /// dropped.
+    pub(crate) fn append_duplicates(&mut self, compared: ComparedRows) {
+        let Some(duplicates) = self.duplicates.as_mut() else {
+            return;
+        };
+        let Some(appended) = duplicates.append(compared) else {
+            return;
+        };
+        let hidden = &self.hidden;
+        let excluded = |row| hidden.is_excluded(row);
+        // Rows after the head of their run are collapsed unless excluded.
+        let collapsed: Vec<u64> = appended
+            .into_iter()
+            .filter(|row| {
+                !excluded(*row)
+                    && duplicates
+                        .run(*row)
+                        .and_then(|run| Duplicates::head(run, excluded))
+                        .is_some_and(|head| head < *row)
+            })
+            .collect();
+        if !collapsed.is_empty() {
+            self.hidden.append_collapsed(collapsed);
+            self.notify_hidden();
+        }
+    }
+
+    /// Expands or collapses the run of duplicates the row belongs to. Returns whether the
+    /// run changed.
+    pub(crate) fn expand_duplicates(&mut self, row: u64, expanded: bool) -> bool {
+        let changed = self
+            .duplicates
+            .as_mut()
+            .is_some_and(|duplicates| duplicates.set_expanded(row, expanded));
+        if changed {
+            self.refresh_collapsed();
+        }
+        changed
+    }
+
+    /// Removes the hidden rows from ranges of session positions. Rows of collapsed
+    /// duplicates are kept if `include_duplicates` is set.
+    pub(crate) fn visible_ranges(
+        &self,
+        ranges: &[RangeInclusive<u64>],
+        include_duplicates: bool,
+    ) -> Vec<RangeInclusive<u64>> {
+        if self.hidden.is_empty() {
+            ranges.to_vec()
+        } else {
+            self.hidden.visible_ranges(ranges, include_duplicates)
+        }
+    }
+
+    /// Rebuilds the rows of collapsed duplicates, which depend on the excluded rows.
+    fn refresh_collapsed(&mut self) {
+        let hidden = &self.hidden;
+        let rows = self
+            .duplicates
+            .as_ref()
+            .map_or_else(Vec::new, |duplicates| {
+                duplicates
+                    .collapsed_rows(|row| hidden.is_excluded(row))
+                    .collect()
+            });
+        self.hidden.set_collapsed(rows);
+        self.notify_hidden();
+    }
+
     pub(crate) fn indexed_neighbor(
         &mut self,
         anchor: Option<u64>,
@@ -104,6 +277,17 @@ impl Controller {
         self.map.len()
     }
 
+    fn notify_hidden(&self) {
+        if let Some(tx) = self.tx_callback_events.as_ref() {
+            let event = stypes::CallbackEvent::HiddenRowsUpdated {
+                len: self.hidden.len() as u64,
+            };
+            if let Err(err) = tx.send(event) {
+                error!("Fail to send hidden rows notification: {err:?}");
+            }
+        }
+    }
+
     fn notify(&self) {
         if let Some(tx) = self.tx_callback_events.as_ref() {
             let event = stypes::CallbackEvent::IndexedMapUpdated {
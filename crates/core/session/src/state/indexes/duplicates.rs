@@ -0,0 +1,385 @@
+//! Includes the detection of runs of consecutive duplicate rows, which are collapsed into
+//! their first row in the main view.
+
+use std::{collections::HashSet, io, ops::RangeInclusive, path::Path};
+
+use processor::{lines::LineReader, templates::normalize};
+use tokio_util::sync::CancellationToken;
+
+/// Rows compared at once while comparing the rows of a file.
+const COMPARE_CHUNK_ROWS: u64 = 10_000;
+
+/// How rows are compared to find duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatesMode {
+    /// Rows with the same content are duplicates.
+    Exact,
+    /// Rows with the same content after [`normalize`] are duplicates, so counters, ids
+    /// and timestamps don't break runs.
+    Masked,
+}
+
+/// Comparison of each row with the row before it, which continues after the compared
+/// rows. It's detached from [`Duplicates`] to compare new rows in the background.
+#[derive(Debug, Clone)]
+pub struct RowComparer {
+    mode: DuplicatesMode,
+    /// Number of compared rows.
+    len: u64,
+    /// Compared content of the last row.
+    last: Option<String>,
+}
+
+impl RowComparer {
+    /// Compares the next lines with the lines before them.
+    pub fn compare<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>) -> ComparedRows {
+        let row = self.len;
+        let repeated = lines
+            .into_iter()
+            .filter_map(|line| {
+                let row = self.len;
+                self.repeats(line).then_some(row)
+            })
+            .collect();
+        ComparedRows {
+            row,
+            repeated,
+            comparer: self.clone(),
+            done: true,
+        }
+    }
+
+    /// Compares the lines of the file following the compared rows, up to the row `end`.
+    /// Reading starts at the row `from.0` at the byte offset `from.1`, which must not be
+    /// after the compared rows. The rows are passed to `chunk` in chunks until it returns
+    /// `false`, and the last chunk is marked as done.
+    ///
+    /// Only complete lines are compared, as the grabber counts only them as rows. Returns
+    /// `false` if the comparison was cancelled.
+    pub fn compare_file(
+        mut self,
+        path: &Path,
+        from: (u64, u64),
+        end: u64,
+        cancel: &CancellationToken,
+        mut chunk: impl FnMut(ComparedRows) -> bool,
+    ) -> io::Result<bool> {
+        let mut reader = LineReader::open(path, cancel)?
+            .starting_at(from.0, from.1)?
+            .complete_lines();
+        let mut read = reader.read(self.len, |_, _| true)?;
+        loop {
+            let lines_end = end.min(self.len + COMPARE_CHUNK_ROWS);
+            let mut lines = Vec::new();
+            // A file without the compared rows ends the comparison with no rows.
+            if read && reader.row() == self.len {
+                read = reader.read(lines_end, |_, line| {
+                    lines.push(line.to_owned());
+                    true
+                })?;
+            }
+            let mut compared = self.compare(lines.iter().map(String::as_str));
+            compared.done = !read || self.len < lines_end || lines_end == end;
+            let done = compared.done;
+            if !chunk(compared) || done {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Compares the next line with the line before it and returns whether it repeats it.
+    fn repeats(&mut self, line: &str) -> bool {
+        self.len += 1;
+        let content = match self.mode {
+            DuplicatesMode::Exact => line.to_owned(),
+            DuplicatesMode::Masked => normalize(line),
+        };
+        if self.last.as_ref() == Some(&content) {
+            return true;
+        }
+        self.last = Some(content);
+        false
+    }
+}
+
+/// Rows compared by a [`RowComparer`], which continue the runs of [`Duplicates`].
+#[derive(Debug)]
+pub struct ComparedRows {
+    /// First compared row.
+    row: u64,
+    /// Rows repeating the row before them.
+    repeated: Vec<u64>,
+    /// Comparison after the compared rows.
+    comparer: RowComparer,
+    /// Whether these are the last rows compared by the comparison.
+    pub done: bool,
+}
+
+/// Runs of consecutive duplicate rows of the session.
+///
+/// Rows are compared in order as the session grows, so a run keeps growing while new rows
+/// repeat its content. Runs are collapsed unless expanded.
+///
+/// Rows hidden by exclusion filters are part of the runs, but a collapsed run is shown by
+/// its first row not excluded, so excluding the first row of a run doesn't hide the run.
+#[derive(Debug)]
+pub struct Duplicates {
+    comparer: RowComparer,
+    /// Sorted runs of at least two rows.
+    runs: Vec<RangeInclusive<u64>>,
+    /// First rows of the expanded runs.
+    expanded: HashSet<u64>,
+}
+
+impl Duplicates {
+    pub fn new(mode: DuplicatesMode) -> Self {
+        Self {
+            comparer: RowComparer {
+                mode,
+                len: 0,
+                last: None,
+            },
+            runs: Vec::new(),
+            expanded: HashSet::new(),
+        }
+    }
+
+    /// Compares the first `rows` lines of the file, which are the rows of a session file.
+    ///
+    /// Returns `None` if the comparison was cancelled.
+    pub fn from_file(
+        mode: DuplicatesMode,
+        path: &Path,
+        rows: u64,
+        cancel: &CancellationToken,
+    ) -> io::Result<Option<Self>> {
+        let mut duplicates = Self::new(mode);
+        let done = duplicates
+            .comparer()
+            .compare_file(path, (0, 0), rows, cancel, |compared| {
+                duplicates.append(compared);
+                true
+            })?;
+        Ok(done.then_some(duplicates))
+    }
+
+    pub fn mode(&self) -> DuplicatesMode {
+        self.comparer.mode
+    }
+
+    /// Returns the number of compared rows.
+    pub fn len(&self) -> u64 {
+        self.comparer.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.comparer.len == 0
+    }
+
+    /// Returns the comparison of the rows following the compared rows.
+    pub fn comparer(&self) -> RowComparer {
+        self.comparer.clone()
+    }
+
+    /// Continues the runs with the compared rows and returns the rows added to runs, which
+    /// aren't expanded. Rows not following the compared rows in the same mode were
+    /// compared for other runs, so `None` is returned without changing the runs.
+    pub fn append(&mut self, compared: ComparedRows) -> Option<Vec<u64>> {
+        if compared.row != self.comparer.len || compared.comparer.mode != self.comparer.mode {
+            return None;
+        }
+        self.comparer = compared.comparer;
+        let mut collapsed = Vec::new();
+        for row in compared.repeated {
+            let run_start = match self.runs.last_mut() {
+                Some(run) if *run.end() + 1 == row => {
+                    *run = *run.start()..=row;
+                    *run.start()
+                }
+                _ => {
+                    self.runs.push(row - 1..=row);
+                    row - 1
+                }
+            };
+            if !self.expanded.contains(&run_start) {
+                collapsed.push(row);
+            }
+        }
+        Some(collapsed)
+    }
+
+    /// Returns the run the row belongs to.
+    pub fn run(&self, row: u64) -> Option<&RangeInclusive<u64>> {
+        let index = self.runs.partition_point(|run| *run.end() < row);
+        self.runs.get(index).filter(|run| run.contains(&row))
+    }
+
+    /// Returns whether the run starting at the row is expanded.
+    pub fn is_expanded(&self, run_start: u64) -> bool {
+        self.expanded.contains(&run_start)
+    }
+
+    /// Expands or collapses the run the row belongs to. Returns whether the run changed.
+    pub fn set_expanded(&mut self, row: u64, expanded: bool) -> bool {
+        let Some(run_start) = self.run(row).map(|run| *run.start()) else {
+            return false;
+        };
+        if expanded {
+            self.expanded.insert(run_start)
+        } else {
+            self.expanded.remove(&run_start)
+        }
+    }
+
+    /// Returns the row showing the run, which is the first row of the run not excluded.
+    pub fn head(run: &RangeInclusive<u64>, excluded: impl Fn(u64) -> bool) -> Option<u64> {
+        run.clone().find(|row| !excluded(*row))
+    }
+
+    /// Returns the sorted rows hidden by the run while it's collapsed, which are the rows
+    /// after its head. Excluded rows are left out, as they are hidden anyway.
+    pub fn collapsed_in(
+        &self,
+        run: &RangeInclusive<u64>,
+        excluded: impl Fn(u64) -> bool,
+    ) -> impl Iterator<Item = u64> {
+        let expanded = self.expanded.contains(run.start());
+        run.clone()
+            .filter(move |row| !expanded && !excluded(*row))
+            .skip(1)
+    }
+
+    /// Returns the sorted rows hidden by collapsed runs, which are all rows of the runs
+    /// but their heads and the excluded rows.
+    pub fn collapsed_rows<'a>(
+        &'a self,
+        excluded: impl Fn(u64) -> bool + Copy + 'a,
+    ) -> impl Iterator<Item = u64> + 'a {
+        self.runs
+            .iter()
+            .flat_map(move |run| self.collapsed_in(run, excluded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tokio_util::sync::CancellationToken;
+
+    use super::{Duplicates, DuplicatesMode};
+
+    fn append<'a>(
+        duplicates: &mut Duplicates,
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<u64> {
+        let compared = duplicates.comparer().compare(lines);
+        duplicates.append(compared).unwrap()
+    }
+
+    #[test]
+    fn consecutive_equal_rows_form_runs() {
+        let mut duplicates = Duplicates::new(DuplicatesMode::Exact);
+        let collapsed = append(&mut duplicates, ["a", "b", "b", "b", "a", "c", "c"]);
+
+        assert_eq!(collapsed, vec![2, 3, 6]);
+        assert_eq!(duplicates.run(3), Some(&(1..=3)));
+        assert_eq!(duplicates.run(4), None);
+        assert_eq!(
+            duplicates.collapsed_rows(|_| false).collect::<Vec<_>>(),
+            vec![2, 3, 6]
+        );
+    }
+
+    #[test]
+    fn runs_continue_across_appends() {
+        let mut duplicates = Duplicates::new(DuplicatesMode::Exact);
+        append(&mut duplicates, ["a", "b"]);
+
+        assert_eq!(append(&mut duplicates, ["b", "b"]), vec![2, 3]);
+        assert_eq!(duplicates.run(1), Some(&(1..=3)));
+        assert_eq!(duplicates.len(), 4);
+    }
+
+    #[test]
+    fn expanded_runs_show_their_rows() {
+        let mut duplicates = Duplicates::new(DuplicatesMode::Exact);
+        append(&mut duplicates, ["a", "a", "b", "b"]);
+
+        assert!(duplicates.set_expanded(1, true));
+        assert!(!duplicates.set_expanded(1, true));
+        assert!(duplicates.is_expanded(0));
+        assert_eq!(
+            duplicates.collapsed_rows(|_| false).collect::<Vec<_>>(),
+            vec![3]
+        );
+        // Rows added to an expanded run stay visible.
+        assert_eq!(append(&mut duplicates, ["b", "a", "a"]), vec![4, 6]);
+        assert!(duplicates.set_expanded(3, true));
+        assert_eq!(
+            duplicates.collapsed_rows(|_| false).collect::<Vec<_>>(),
+            vec![6]
+        );
+    }
+
+    #[test]
+    fn masked_rows_ignore_numbers_and_hex_values() {
+        let mut duplicates = Duplicates::new(DuplicatesMode::Masked);
+        let collapsed = append(
+            &mut duplicates,
+            [
+                "retry 1 of handle 0x1f after 12ms",
+                "retry 2 of handle 0x2a after 340ms",
+                "retry 3 of handle 0x2a after 1s",
+                "retry handle 0x2a",
+            ],
+        );
+
+        assert_eq!(collapsed, vec![1, 2]);
+    }
+
+    #[test]
+    fn collapsed_runs_are_shown_by_their_first_row_not_excluded() {
+        let mut duplicates = Duplicates::new(DuplicatesMode::Exact);
+        append(&mut duplicates, ["a", "b", "b", "b", "b", "c"]);
+        let excluded = |row: u64| row == 1 || row == 3;
+
+        assert_eq!(Duplicates::head(&(1..=4), excluded), Some(2));
+        assert_eq!(
+            duplicates.collapsed_rows(excluded).collect::<Vec<_>>(),
+            vec![4]
+        );
+        assert_eq!(Duplicates::head(&(1..=2), |_| true), None);
+    }
+
+    #[test]
+    fn new_rows_of_the_file_continue_the_runs() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"a\nb\nb\nc\nc\nc").unwrap();
+        let cancel = CancellationToken::new();
+        let mut duplicates = Duplicates::from_file(DuplicatesMode::Exact, file.path(), 3, &cancel)
+            .unwrap()
+            .unwrap();
+
+        let mut chunks = Vec::new();
+        // Reading starts at the second row, before the compared rows.
+        let read = duplicates
+            .comparer()
+            .compare_file(file.path(), (1, 2), u64::MAX, &cancel, |compared| {
+                chunks.push(compared);
+                true
+            })
+            .unwrap();
+        assert!(read);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].done);
+        let compared = chunks.pop().unwrap();
+        let outdated = duplicates.comparer().compare(["c"]);
+
+        // The last line isn't complete yet.
+        assert_eq!(duplicates.append(compared), Some(vec![4]));
+        assert_eq!(duplicates.len(), 5);
+        assert_eq!(duplicates.run(3), Some(&(3..=4)));
+        assert_eq!(duplicates.append(outdated), None);
+    }
+}
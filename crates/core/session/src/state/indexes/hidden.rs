@@ -0,0 +1,307 @@
+//! Includes the definitions of rows hidden from the main view by exclusion filters and
+//! collapsed duplicates.
+
+use std::{iter, ops::RangeInclusive};
+
+/// Sorted positions of rows without duplicates.
+#[derive(Debug, Default)]
+struct SortedRows {
+    rows: Vec<u64>,
+}
+
+impl SortedRows {
+    fn set(&mut self, mut rows: Vec<u64>) {
+        rows.sort_unstable();
+        rows.dedup();
+        self.rows = rows;
+    }
+
+    fn append(&mut self, rows: impl IntoIterator<Item = u64>) {
+        let len = self.rows.len();
+        self.rows.extend(rows);
+        let appended_in_order = self.rows[len.saturating_sub(1)..]
+            .windows(2)
+            .all(|pair| pair[0] < pair[1]);
+        if !appended_in_order {
+            self.rows.sort_unstable();
+            self.rows.dedup();
+        }
+    }
+
+    /// Returns the number of rows before the position.
+    fn before(&self, position: u64) -> usize {
+        self.rows.partition_point(|row| *row < position)
+    }
+
+    fn contains(&self, position: u64) -> bool {
+        self.rows.binary_search(&position).is_ok()
+    }
+
+    /// Returns the rows at and after the position.
+    fn from(&self, position: u64) -> &[u64] {
+        &self.rows[self.before(position)..]
+    }
+}
+
+/// Merges two sorted disjoint slices of rows.
+fn merge<'a>(left: &'a [u64], right: &'a [u64]) -> impl Iterator<Item = u64> + 'a {
+    let mut left = left.iter().copied().peekable();
+    let mut right = right.iter().copied().peekable();
+    iter::from_fn(move || match (left.peek(), right.peek()) {
+        (Some(l), Some(r)) if r < l => right.next(),
+        (Some(_), _) => left.next(),
+        (None, _) => right.next(),
+    })
+}
+
+/// Session rows hidden from the main view.
+///
+/// The main view lists all session rows except the hidden ones, so its rows (view rows) are
+/// mapped to session positions with binary searches over the sorted hidden positions. The
+/// session file itself is never changed.
+///
+/// Rows hidden by exclusion filters and rows of collapsed duplicates are kept apart and
+/// merged on lookup, so each of them grows in order on its own. Excluded rows are never
+/// collapsed, so both are disjoint.
+#[derive(Debug, Default)]
+pub struct HiddenRows {
+    excluded: SortedRows,
+    collapsed: SortedRows,
+}
+
+impl HiddenRows {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the rows hidden by exclusion filters.
+    pub fn set_excluded(&mut self, rows: Vec<u64>) {
+        self.excluded.set(rows);
+    }
+
+    /// Hides additional rows by exclusion filters.
+    pub fn append_excluded(&mut self, rows: impl IntoIterator<Item = u64>) {
+        self.excluded.append(rows);
+    }
+
+    /// Replaces the rows of collapsed duplicates.
+    pub fn set_collapsed(&mut self, rows: Vec<u64>) {
+        self.collapsed.set(rows);
+    }
+
+    /// Hides additional rows of collapsed duplicates.
+    pub fn append_collapsed(&mut self, rows: impl IntoIterator<Item = u64>) {
+        self.collapsed.append(rows);
+    }
+
+    /// Replaces the rows of collapsed duplicates within the range by the sorted rows.
+    pub fn replace_collapsed(
+        &mut self,
+        range: &RangeInclusive<u64>,
+        rows: impl IntoIterator<Item = u64>,
+    ) {
+        let from = self.collapsed.before(*range.start());
+        let to = self.collapsed.before(range.end() + 1);
+        self.collapsed.rows.splice(from..to, rows);
+    }
+
+    /// Returns the sorted positions of the rows hidden by exclusion filters.
+    pub fn excluded(&self) -> &[u64] {
+        &self.excluded.rows
+    }
+
+    /// Returns the sorted positions of the rows of collapsed duplicates.
+    pub fn collapsed(&self) -> &[u64] {
+        &self.collapsed.rows
+    }
+
+    pub fn clear(&mut self) {
+        self.excluded.rows.clear();
+        self.collapsed.rows.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.excluded.rows.len() + self.collapsed.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the row with the session position is hidden by exclusion filters.
+    pub fn is_excluded(&self, position: u64) -> bool {
+        self.excluded.contains(position)
+    }
+
+    /// Returns the number of rows within the range hidden by exclusion filters.
+    pub fn excluded_within(&self, range: &RangeInclusive<u64>) -> u64 {
+        (self.excluded.before(range.end() + 1) - self.excluded.before(*range.start())) as u64
+    }
+
+    /// Returns the number of hidden rows before the session position.
+    fn before(&self, position: u64) -> u64 {
+        (self.excluded.before(position) + self.collapsed.before(position)) as u64
+    }
+
+    /// Returns the sorted hidden rows at and after the session position. Rows of
+    /// collapsed duplicates are left out if `keep_collapsed` is set.
+    fn from(&self, position: u64, keep_collapsed: bool) -> impl Iterator<Item = u64> + '_ {
+        let collapsed = if keep_collapsed {
+            &[]
+        } else {
+            self.collapsed.from(position)
+        };
+        merge(self.excluded.from(position), collapsed)
+    }
+
+    /// Returns the session position of the row shown in the view row.
+    pub fn position(&self, row: u64) -> u64 {
+        // `p + 1 - before(p + 1)` is the count of visible rows up to the position `p`, so
+        // the requested row is shown at the first position where it exceeds `row`.
+        let (mut low, mut high) = (row, row + self.len() as u64);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if mid + 1 - self.before(mid + 1) > row {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        low
+    }
+
+    /// Returns the view row of the session position, or of the first visible row after it
+    /// if the row is hidden.
+    pub fn nearest_view_row(&self, position: u64) -> u64 {
+        position - self.before(position)
+    }
+
+    /// Maps the range of view rows to the ranges of session positions of the shown rows.
+    pub fn view_ranges(&self, range: &RangeInclusive<u64>) -> Vec<RangeInclusive<u64>> {
+        let mut ranges = Vec::new();
+        if range.is_empty() {
+            return ranges;
+        }
+        let mut remaining = range.end() - range.start() + 1;
+        let mut position = self.position(*range.start());
+        let mut hidden = self.from(position, false).peekable();
+        while remaining > 0 {
+            let len = hidden
+                .peek()
+                .map_or(remaining, |hidden| (hidden - position).min(remaining));
+            ranges.push(position..=position + len - 1);
+            remaining -= len;
+            position += len;
+            // Skip the following block of hidden rows.
+            while hidden.next_if_eq(&position).is_some() {
+                position += 1;
+            }
+        }
+        ranges
+    }
+
+    /// Removes the hidden rows from the ranges of session positions. Rows of collapsed
+    /// duplicates are kept if `keep_collapsed` is set.
+    pub fn visible_ranges(
+        &self,
+        ranges: &[RangeInclusive<u64>],
+        keep_collapsed: bool,
+    ) -> Vec<RangeInclusive<u64>> {
+        let mut visible = Vec::with_capacity(ranges.len());
+        for range in ranges.iter().filter(|range| !range.is_empty()) {
+            let mut start = *range.start();
+            for hidden in self
+                .from(start, keep_collapsed)
+                .take_while(|row| row <= range.end())
+            {
+                if start < hidden {
+                    visible.push(start..=hidden - 1);
+                }
+                start = hidden + 1;
+            }
+            if start <= *range.end() {
+                visible.push(start..=*range.end());
+            }
+        }
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HiddenRows;
+
+    /// Hides the rows 0, 3, 4 and 8, which are split into excluded and collapsed rows.
+    fn hidden() -> HiddenRows {
+        let mut hidden = HiddenRows::new();
+        hidden.set_excluded(vec![4, 0]);
+        hidden.set_collapsed(vec![3, 8]);
+        hidden
+    }
+
+    #[test]
+    fn maps_view_rows_to_positions() {
+        let hidden = hidden();
+
+        let positions: Vec<u64> = (0..6).map(|row| hidden.position(row)).collect();
+        assert_eq!(positions, vec![1, 2, 5, 6, 7, 9]);
+        for (row, position) in positions.into_iter().enumerate() {
+            assert_eq!(hidden.nearest_view_row(position), row as u64);
+        }
+        // The hidden row is mapped to the next visible row.
+        assert_eq!(hidden.nearest_view_row(3), 2);
+        assert_eq!(hidden.position(2), 5);
+        assert_eq!(
+            (hidden.excluded(), hidden.collapsed()),
+            (&[0, 4][..], &[3, 8][..])
+        );
+    }
+
+    #[test]
+    fn view_ranges_skip_hidden_blocks() {
+        let hidden = hidden();
+
+        assert_eq!(hidden.view_ranges(&(0..=5)), vec![1..=2, 5..=7, 9..=9]);
+        assert_eq!(hidden.view_ranges(&(1..=2)), vec![2..=2, 5..=5]);
+        assert_eq!(HiddenRows::new().view_ranges(&(2..=4)), vec![2..=4]);
+    }
+
+    #[test]
+    fn visible_ranges_remove_hidden_rows() {
+        let hidden = hidden();
+
+        assert_eq!(
+            hidden.visible_ranges(&[0..=5, 7..=10], false),
+            vec![1..=2, 5..=5, 7..=7, 9..=10]
+        );
+        assert_eq!(hidden.visible_ranges(&[3..=4], false), Vec::new());
+        assert_eq!(
+            hidden.visible_ranges(&[0..=5, 7..=10], true),
+            vec![1..=3, 5..=5, 7..=10]
+        );
+    }
+
+    #[test]
+    fn append_keeps_rows_sorted() {
+        let mut hidden = HiddenRows::new();
+        hidden.set_excluded(vec![2, 5]);
+        hidden.append_excluded([7, 9]);
+        hidden.append_excluded([1, 5]);
+        hidden.append_collapsed([3, 4]);
+
+        assert_eq!(hidden.len(), 7);
+        assert_eq!(hidden.position(0), 0);
+        assert_eq!(hidden.position(1), 6);
+        assert_eq!(hidden.excluded_within(&(2..=7)), 3);
+    }
+
+    #[test]
+    fn collapsed_rows_are_replaced_within_the_range() {
+        let mut hidden = hidden();
+        hidden.replace_collapsed(&(2..=6), [5, 6]);
+
+        assert_eq!(hidden.excluded(), &[0, 4]);
+        assert_eq!(hidden.collapsed(), &[5, 6, 8]);
+        assert_eq!(hidden.position(1), 2);
+    }
+}
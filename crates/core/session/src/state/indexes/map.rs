@@ -47,6 +47,9 @@ const RANGES_LIMIT: usize = 10000; // based on bench test < 100K
 pub struct Map {
     indexes: FxHashMap<u64, Nature>,
     keys: Keys,
+    /// Natures of rows hidden from the main view. They are kept out of the indexed
+    /// projection and restored once the rows are shown again.
+    hidden: FxHashMap<u64, Nature>,
 }
 
 impl Map {
@@ -54,6 +57,7 @@ impl Map {
         Self {
             indexes: FxHashMap::default(),
             keys: Keys::new(),
+            hidden: FxHashMap::default(),
         }
     }
 
@@ -127,6 +131,33 @@ impl Map {
         });
     }
 
+    /// Inserts rows hidden from the main view, which stay out of the indexed projection.
+    pub fn insert_hidden(&mut self, positions: impl IntoIterator<Item = u64>, nature: Nature) {
+        positions.into_iter().for_each(|position| {
+            self.hidden.entry(position).or_default().include(nature);
+        });
+    }
+
+    /// Moves the rows out of the indexed projection while keeping their natures.
+    pub fn hide(&mut self, positions: &[u64]) {
+        let mut to_be_removed = Vec::new();
+        positions.iter().for_each(|position| {
+            if let Some(nature) = self.indexes.get(position) {
+                self.hidden.insert(*position, *nature);
+                to_be_removed.push(*position);
+            }
+        });
+        self.indexes_remove(&mut to_be_removed);
+    }
+
+    /// Returns all hidden rows to the indexed projection.
+    pub fn show_all(&mut self) {
+        let hidden = std::mem::take(&mut self.hidden);
+        hidden.into_iter().for_each(|(position, nature)| {
+            self.insert([position], nature);
+        });
+    }
+
     pub fn remove(&mut self, positions: &[u64], nature: Nature) {
         let mut to_be_removed = Vec::new();
         positions.iter().for_each(|position| {
@@ -135,6 +166,11 @@ impl Map {
                 if index.is_empty() {
                     to_be_removed.push(*position);
                 }
+            } else if let Some(index) = self.hidden.get_mut(position) {
+                index.exclude(nature);
+                if index.is_empty() {
+                    self.hidden.remove(position);
+                }
             }
         });
         self.indexes_remove(&mut to_be_removed);
@@ -142,7 +178,12 @@ impl Map {
 
     pub fn naturalize(&self, elements: &mut [stypes::GrabbedElement]) {
         elements.iter_mut().for_each(|element| {
-            if let Some(nature) = self.indexes.get(&(element.pos as u64)) {
+            let position = element.pos as u64;
+            if let Some(nature) = self
+                .indexes
+                .get(&position)
+                .or_else(|| self.hidden.get(&position))
+            {
                 element.set_nature(nature.bits());
             }
             // Elements outside the indexed map retain nature 0.
@@ -194,6 +235,10 @@ impl Map {
             }
         });
         self.indexes_remove(&mut to_be_removed);
+        self.hidden.retain(|_, index| {
+            index.exclude(nature);
+            !index.is_empty()
+        });
     }
 
     pub fn len(&self) -> usize {
@@ -209,6 +254,7 @@ impl Map {
         if len == 0 {
             self.indexes.clear();
             self.keys.clear();
+            self.hidden.clear();
         }
     }
 
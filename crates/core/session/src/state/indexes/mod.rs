@@ -1,5 +1,7 @@
 pub mod controller;
+pub mod duplicates;
 pub mod frame;
+pub mod hidden;
 pub mod keys;
 pub mod map;
 pub mod nature;
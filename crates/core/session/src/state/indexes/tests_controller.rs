@@ -1,6 +1,11 @@
 use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
 
-use super::{IndexedNavigation, controller::Controller, nature::Nature};
+use super::{
+    IndexedNavigation,
+    controller::Controller,
+    duplicates::{Duplicates, DuplicatesMode},
+    nature::Nature,
+};
 
 fn filter_matches(rows: impl IntoIterator<Item = u64>) -> Vec<stypes::FilterMatch> {
     rows.into_iter()
@@ -29,6 +34,18 @@ fn indexed_rows(controller: &mut Controller) -> Vec<(u64, Nature)> {
         .indexes
 }
 
+fn hidden_rows(controller: &Controller) -> Vec<u64> {
+    let hidden = controller.hidden();
+    let mut rows = [hidden.excluded(), hidden.collapsed()].concat();
+    rows.sort_unstable();
+    rows
+}
+
+fn append_duplicates<'a>(controller: &mut Controller, lines: impl IntoIterator<Item = &'a str>) {
+    let compared = controller.duplicates().unwrap().comparer().compare(lines);
+    controller.append_duplicates(compared);
+}
+
 fn next_len(rx: &mut UnboundedReceiver<stypes::CallbackEvent>) -> u64 {
     match rx.try_recv().unwrap() {
         stypes::CallbackEvent::IndexedMapUpdated { len } => len,
@@ -254,3 +271,121 @@ fn indexed_neighbor_wraps_over_search_and_bookmark_union() {
         Some(9)
     );
 }
+
+#[test]
+fn hidden_rows_are_left_out_of_the_indexed_projection() {
+    let mut controller = Controller::default();
+    controller.set_search_results(&filter_matches([2, 5, 9]));
+    controller.set_bookmarks(vec![5, 7]);
+
+    controller.set_hidden_rows(vec![5, 9]);
+
+    assert_eq!(
+        indexed_rows(&mut controller),
+        vec![(2, Nature::SEARCH), (7, Nature::BOOKMARK)]
+    );
+
+    controller.add_bookmark(9);
+    controller.append_search_results(&filter_matches([12]));
+    assert_eq!(
+        indexed_rows(&mut controller),
+        vec![
+            (2, Nature::SEARCH),
+            (7, Nature::BOOKMARK),
+            (12, Nature::SEARCH),
+        ]
+    );
+
+    controller.set_hidden_rows(vec![]);
+    assert_eq!(
+        indexed_rows(&mut controller),
+        vec![
+            (2, Nature::SEARCH),
+            (5, overlap()),
+            (7, Nature::BOOKMARK),
+            (9, overlap()),
+            (12, Nature::SEARCH),
+        ]
+    );
+}
+
+#[test]
+fn hidden_rows_keep_their_nature_and_notify_len() {
+    let (tx, mut rx) = unbounded_channel();
+    let mut controller = Controller::new(Some(tx));
+    controller.set_bookmarks(vec![3]);
+    assert_eq!(next_len(&mut rx), 1);
+
+    controller.append_hidden_rows(vec![3, 4]);
+
+    match rx.try_recv().unwrap() {
+        stypes::CallbackEvent::HiddenRowsUpdated { len } => assert_eq!(len, 2),
+        event => panic!("unexpected callback: {event:?}"),
+    }
+    assert_eq!(next_len(&mut rx), 0);
+    let mut elements = vec![stypes::GrabbedElement {
+        source_id: 0,
+        content: String::new(),
+        pos: 3,
+        nature: 0,
+        matches: Vec::new(),
+        collapsed: None,
+    }];
+    controller.naturalize(&mut elements);
+    assert_eq!(elements[0].nature, Nature::BOOKMARK.bits());
+}
+
+#[test]
+fn collapsed_duplicates_are_hidden_from_the_main_view_only() {
+    let mut controller = Controller::default();
+    controller.set_search_results(&filter_matches([1, 2]));
+    controller.set_hidden_rows(vec![5]);
+    controller.set_duplicates(Some(Duplicates::new(DuplicatesMode::Exact)));
+    append_duplicates(&mut controller, ["a", "b", "b", "b", "c", "d"]);
+
+    assert_eq!(hidden_rows(&controller), vec![2, 3, 5]);
+    assert_eq!(controller.hidden().excluded(), &[5]);
+    assert_eq!(
+        indexed_rows(&mut controller),
+        vec![(1, Nature::SEARCH), (2, Nature::SEARCH)]
+    );
+    assert_eq!(
+        controller.visible_ranges(&[0..=5], false),
+        vec![0..=1, 4..=4]
+    );
+    assert_eq!(controller.visible_ranges(&[0..=5], true), vec![0..=4]);
+
+    assert!(controller.expand_duplicates(3, true));
+    assert_eq!(hidden_rows(&controller), vec![5]);
+    assert!(controller.expand_duplicates(1, false));
+    assert_eq!(hidden_rows(&controller), vec![2, 3, 5]);
+
+    controller.set_duplicates(None);
+    assert_eq!(hidden_rows(&controller), vec![5]);
+}
+
+#[test]
+fn excluded_head_passes_its_run_on_to_the_next_row() {
+    let mut controller = Controller::default();
+    controller.set_duplicates(Some(Duplicates::new(DuplicatesMode::Exact)));
+    append_duplicates(&mut controller, ["a", "b", "b", "b", "b", "c"]);
+    assert_eq!(hidden_rows(&controller), vec![2, 3, 4]);
+
+    controller.append_hidden_rows(vec![1, 3]);
+    assert_eq!(hidden_rows(&controller), vec![1, 3, 4]);
+    assert_eq!(controller.hidden().collapsed(), &[4]);
+    assert_eq!(controller.shown_run(2), Some((&(1..=4), 2)));
+    assert_eq!(controller.shown_run(1), None);
+    assert_eq!(
+        controller.visible_ranges(&[0..=5], false),
+        vec![0..=0, 2..=2, 5..=5]
+    );
+
+    controller.set_hidden_rows(vec![1]);
+    assert_eq!(hidden_rows(&controller), vec![1, 3, 4]);
+    append_duplicates(&mut controller, ["c", "c"]);
+    assert_eq!(hidden_rows(&controller), vec![1, 3, 4, 6, 7]);
+
+    controller.set_hidden_rows(Vec::new());
+    assert_eq!(hidden_rows(&controller), vec![2, 3, 4, 6, 7]);
+}
@@ -14,6 +14,8 @@ fn element(pos: usize) -> stypes::GrabbedElement {
         content: String::new(),
         pos,
         nature: 0,
+        matches: Vec::new(),
+        collapsed: None,
     }
 }
 
@@ -5,6 +5,7 @@ use processor::{
     map::SearchMap,
     search::{
         filter::SearchFilter,
+        highlight::MatchFinder,
         searchers::{
             linear::LineSearcher,
             regular::RegularSearchHolder,
@@ -19,9 +20,12 @@ use std::{
     ops::RangeInclusive,
     path::PathBuf,
 };
-use tokio::sync::{
-    mpsc::{self, UnboundedReceiver, UnboundedSender},
-    oneshot,
+use tokio::{
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
+    task,
 };
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
@@ -34,20 +38,30 @@ mod searchers;
 mod session_file;
 mod source_ids;
 #[cfg(test)]
+mod tests_matches;
+#[cfg(test)]
 mod tests_nested;
+mod time_index;
 pub(crate) mod values;
 
 pub use api::{Api, SessionStateAPI};
 pub use attachments::{Attachments, AttachmentsError};
+use indexes::duplicates::ComparedRows;
 pub use indexes::{
-    IndexedNavigation, controller::Controller as Indexes, frame::Frame, map::Map, nature::Nature,
+    IndexedNavigation,
+    controller::Controller as Indexes,
+    duplicates::{Duplicates, DuplicatesMode},
+    frame::Frame,
+    map::Map,
+    nature::Nature,
 };
 use observed::Observed;
-use searchers::{SearchRequest, SearchResponse};
+use searchers::{RelinkedFile, SearchRequest, SearchResponse};
 use stypes::{FilterMatch, GrabbedElement};
 
 pub use observed::is_raw_export_available_for;
-pub use session_file::{SessionFile, SessionFileOrigin, SessionFileState};
+pub use session_file::{SessionFile, SessionFileOrigin, SessionFileState, TextTimestamps};
+pub use time_index::TimeIndex;
 pub use values::{Values, ValuesError};
 
 /// Coordinates of a nested match across the session and its indexed projections.
@@ -59,6 +73,8 @@ pub struct NestedMatch {
     pub search_result_index: u64,
     /// Displayed row position in the search-and-bookmark indexed table.
     pub indexed_row_index: u64,
+    /// Byte ranges of the nested filter matches in the row.
+    pub matches: Vec<stypes::MatchRange>,
 }
 
 /// Status of session state.
@@ -77,41 +93,158 @@ pub struct SessionState {
     /// Collection of executed observe operations.
     pub observed: Observed,
     pub search_map: SearchMap,
+    /// Finds the match ranges of the active search filters in grabbed rows.
+    match_finder: Option<MatchFinder>,
     pub indexes: Indexes,
+    /// Exclusions operation, whose searcher hides new matching rows. Results of searchers of
+    /// other operations are outdated.
+    exclusions_operation: Option<Uuid>,
     pub values: Values,
     pub attachments: Attachments,
+    /// Templates mined from the session rows by the last template mining.
+    templates: Option<stypes::LogTemplates>,
+    /// Differences from the log the session was last compared with.
+    comparison: Option<stypes::LogDiff>,
     pub cancelling_operations: HashMap<Uuid, bool>,
     pub status: Status,
     searcher_tx: mpsc::Sender<SearchRequest>,
+    /// Receives the timestamps parsed from the rows of the linked text file.
+    tx_text_timestamps: UnboundedSender<TextTimestamps>,
+    /// Whether a task compares the new rows for duplicates.
+    comparing_duplicates: bool,
+    /// Receives the rows compared for duplicates in the background.
+    tx_duplicates: UnboundedSender<ComparedRows>,
 }
 
 impl SessionState {
     fn new(
         tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
         searcher_tx: mpsc::Sender<SearchRequest>,
+        tx_text_timestamps: UnboundedSender<TextTimestamps>,
+        tx_duplicates: UnboundedSender<ComparedRows>,
     ) -> Self {
         Self {
             session_file: SessionFile::new(),
             observed: Observed::new(),
             search_map: SearchMap::new(),
+            match_finder: None,
             attachments: Attachments::new(),
+            templates: None,
+            comparison: None,
             indexes: Indexes::new(Some(tx_callback_events.clone())),
+            exclusions_operation: None,
             values: Values::new(Some(tx_callback_events)),
             status: Status::Open,
             cancelling_operations: HashMap::new(),
             searcher_tx,
+            tx_text_timestamps,
+            comparing_duplicates: false,
+            tx_duplicates,
         }
     }
 
+    /// Grabs the rows of the main view, skipping the rows hidden by exclusion filters and
+    /// collapsed duplicates.
     fn handle_grab(
         &mut self,
         range: &LineRange,
     ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
-        let mut elements = self.session_file.grab(range)?;
+        let hidden = self.indexes.hidden();
+        let mut elements = if hidden.is_empty() {
+            self.session_file.grab(range)?
+        } else {
+            let mut elements: Vec<GrabbedElement> = vec![];
+            for range in hidden.view_ranges(&range.range) {
+                let mut session_elements = self.session_file.grab(&LineRange::from(range))?;
+                elements.append(&mut session_elements);
+            }
+            elements
+        };
         self.indexes.naturalize(&mut elements);
+        self.attach_matches(&mut elements);
+        self.attach_collapsed(&mut elements);
         Ok(elements)
     }
 
+    /// Attaches the runs of duplicates to the elements starting them.
+    fn attach_collapsed(&self, elements: &mut [GrabbedElement]) {
+        let Some(duplicates) = self.indexes.duplicates() else {
+            return;
+        };
+        let time_index = &self.session_file.time_index;
+        for element in elements.iter_mut() {
+            let pos = element.pos as u64;
+            let Some((run, count)) = self.indexes.shown_run(pos) else {
+                continue;
+            };
+            element.collapsed = Some(stypes::CollapsedRun {
+                count,
+                last_pos: *run.end() as usize,
+                time: time_index
+                    .timestamp(*run.start())
+                    .zip(time_index.timestamp(*run.end())),
+                expanded: duplicates.is_expanded(*run.start()),
+            });
+        }
+    }
+
+    /// Starts comparing the session rows added since the last comparison for duplicates in
+    /// a blocking task, if collapsing duplicates is enabled and the task isn't running
+    /// already. The compared rows are sent to `tx_duplicates` in chunks and appended by
+    /// [`SessionState::handle_compared_duplicates`].
+    fn index_duplicates(&mut self) {
+        let Some(duplicates) = self.indexes.duplicates() else {
+            return;
+        };
+        let rows = duplicates.len()..self.session_file.len();
+        if self.comparing_duplicates || rows.is_empty() {
+            return;
+        }
+        let (Ok(filename), Some(from)) = (
+            self.session_file.filename(),
+            self.session_file.slot_start(rows.start),
+        ) else {
+            return;
+        };
+        self.comparing_duplicates = true;
+        let comparer = duplicates.comparer();
+        let tx_duplicates = self.tx_duplicates.clone();
+        task::spawn_blocking(move || {
+            let unchanged = comparer.clone().compare([]);
+            let compared = comparer.compare_file(
+                &filename,
+                from,
+                rows.end,
+                &CancellationToken::new(),
+                |compared| tx_duplicates.send(compared).is_ok(),
+            );
+            if let Err(err) = compared {
+                log::warn!("Fail to compare rows of {filename:?} for duplicates: {err}");
+                // Lets the next update of the session compare the rows again.
+                let _ = tx_duplicates.send(unchanged);
+            }
+        });
+    }
+
+    /// Appends the rows compared for duplicates in the background and continues with the
+    /// rows added while they were compared.
+    fn handle_compared_duplicates(&mut self, compared: ComparedRows) {
+        let done = compared.done;
+        self.indexes.append_duplicates(compared);
+        if done {
+            self.comparing_duplicates = false;
+            self.index_duplicates();
+        }
+    }
+
+    /// Replaces the runs of duplicates and returns the number of collapsed rows. Rows
+    /// added since the runs were compared are compared in the background.
+    fn handle_set_duplicates(&mut self, duplicates: Option<Duplicates>) -> u64 {
+        self.indexes.set_duplicates(duplicates);
+        self.index_duplicates();
+        self.indexes.hidden().collapsed().len() as u64
+    }
+
     fn handle_grab_indexed(
         &mut self,
         mut range: RangeInclusive<u64>,
@@ -123,9 +256,25 @@ impl SessionState {
             elements.append(&mut session_elements);
         }
         frame.set_elements_nature(&mut elements)?;
+        self.attach_matches(&mut elements);
         Ok(elements)
     }
 
+    /// Attaches the byte ranges matched by the active search to the elements matched by it.
+    fn attach_matches(&self, elements: &mut [GrabbedElement]) {
+        let Some(finder) = self.match_finder.as_ref() else {
+            return;
+        };
+        let matches = &self.search_map.matches;
+        for element in elements.iter_mut() {
+            let pos = element.pos as u64;
+            if let Ok(found) = matches.binary_search_by_key(&pos, |filter_match| filter_match.index)
+            {
+                element.matches = finder.find(&element.content, &matches[found].filters);
+            }
+        }
+    }
+
     /// Transforms search match data into ranges of line numbers in the original session file.
     ///
     /// This function is used to retrieve the line number ranges in the session file based on the
@@ -177,6 +326,7 @@ impl SessionState {
             elements.append(&mut session_elements);
         }
         self.indexes.naturalize(&mut elements);
+        self.attach_matches(&mut elements);
         Ok(elements)
     }
 
@@ -294,12 +444,16 @@ impl SessionState {
                     };
                     return Err(error);
                 }
-                if searcher.is_match(&line.content) {
+                // Excluded rows aren't listed in the indexed table.
+                if !self.indexes.hidden().is_excluded(session_position)
+                    && searcher.is_match(&line.content)
+                {
                     let indexed_row_index = self.indexes.indexed_row_index(session_position)?;
                     let nested_match = NestedMatch {
                         session_position,
                         search_result_index: search_result_index as u64,
                         indexed_row_index,
+                        matches: searcher.find(&line.content),
                     };
                     return Ok(Some(nested_match));
                 }
@@ -320,6 +474,7 @@ impl SessionState {
             elements.append(&mut session_elements);
         }
         self.indexes.naturalize(&mut elements);
+        self.attach_matches(&mut elements);
         Ok(elements)
     }
 
@@ -329,10 +484,15 @@ impl SessionState {
         state_cancellation_token: CancellationToken,
         tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
         msg: String,
+        timestamps: Vec<Option<u64>>,
     ) -> Result<(), stypes::NativeError> {
         if matches!(
-            self.session_file
-                .write(source_id, state_cancellation_token.clone(), msg)?,
+            self.session_file.write(
+                source_id,
+                state_cancellation_token.clone(),
+                msg,
+                timestamps
+            )?,
             SessionFileState::Changed
         ) {
             self.update_searchers(state_cancellation_token, tx_callback_events)
@@ -364,10 +524,12 @@ impl SessionState {
         state_cancellation_token: CancellationToken,
         tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
     ) -> Result<bool, stypes::NativeError> {
-        if let SessionFileState::Changed = self
+        let changes = self
             .session_file
-            .update(source_id, state_cancellation_token.clone())?
-        {
+            .update(source_id, state_cancellation_token.clone())?;
+        self.session_file
+            .index_text_timestamps(self.tx_text_timestamps.clone());
+        if let SessionFileState::Changed = changes {
             self.update_searchers(state_cancellation_token, tx_callback_events)
                 .await?;
             Ok(true)
@@ -376,6 +538,62 @@ impl SessionState {
         }
     }
 
+    /// Appends the timestamps parsed from the rows of the linked text file, reporting the
+    /// number of indexed rows, and continues with the rows added while they were parsed.
+    fn handle_text_timestamps(
+        &mut self,
+        timestamps: TextTimestamps,
+        tx_callback_events: &UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        let done = timestamps.done;
+        match self.session_file.append_text_timestamps(timestamps) {
+            Ok(true) => tx_callback_events.send(stypes::CallbackEvent::TimeIndexUpdated {
+                len: self.session_file.time_index.len(),
+            })?,
+            Ok(false) => {}
+            Err(err) => error!("Fail to index timestamps: {err:?}"),
+        }
+        if done {
+            self.session_file
+                .index_text_timestamps(self.tx_text_timestamps.clone());
+        }
+        Ok(())
+    }
+
+    /// Switches the linked session file to the generated one, moving the searchers and the
+    /// attachments to it.
+    async fn handle_unlink_session_file(
+        &mut self,
+        filename: PathBuf,
+        blank_rows: bool,
+        source_id: u16,
+        state_cancellation_token: CancellationToken,
+        tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        let changes = self.session_file.unlink(
+            filename.clone(),
+            blank_rows,
+            source_id,
+            state_cancellation_token.clone(),
+        )?;
+        self.attachments.set_dest_path(filename.clone());
+        self.searcher_tx
+            .send(SearchRequest::RelinkSessionFile(RelinkedFile {
+                filename,
+                index: self.session_file.search_index.clone(),
+                blank_rows,
+            }))
+            .await
+            .map_err(|_| {
+                stypes::NativeError::channel("Failed to send relink request to searchers")
+            })?;
+        if let SessionFileState::Changed = changes {
+            self.update_searchers(state_cancellation_token, tx_callback_events)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn update_searchers(
         &mut self,
         state_cancellation_token: CancellationToken,
@@ -385,6 +603,7 @@ impl SessionState {
         let bytes = self.session_file.read_bytes();
         self.search_map.set_stream_len(rows);
         self.indexes.set_stream_len(rows);
+        self.index_duplicates();
         tx_callback_events.send(stypes::CallbackEvent::StreamUpdated(rows))?;
         self.searcher_tx
             .send(SearchRequest::SearchRegular {
@@ -400,12 +619,22 @@ impl SessionState {
             .send(SearchRequest::SearchValue {
                 rows,
                 bytes,
-                cancel: state_cancellation_token,
+                cancel: state_cancellation_token.clone(),
             })
             .await
             .map_err(|_| {
                 stypes::NativeError::channel("Failed to send search values request to searchers")
             })?;
+        self.searcher_tx
+            .send(SearchRequest::SearchExclusions {
+                rows,
+                bytes,
+                cancel: state_cancellation_token,
+            })
+            .await
+            .map_err(|_| {
+                stypes::NativeError::channel("Failed to send exclusions request to searchers")
+            })?;
         Ok(())
     }
 
@@ -415,7 +644,9 @@ impl SessionState {
     /// # Arguments
     ///
     /// * `out_path` - A `PathBuf` representing the path to the output file where data will be exported.
-    /// * `ranges` - A `Vec<RangeInclusive<u64>>` specifying the ranges of data to export.
+    /// * `ranges` - A `Vec<RangeInclusive<u64>>` specifying the ranges of data to export. Rows
+    ///   hidden from the main view are skipped.
+    /// * `include_duplicates` - Whether rows of collapsed duplicates are exported anyway.
     /// * `columns` - A `Vec<usize>` containing the column number to be exported.
     /// * `spliter` - A `String` used as the record separator in session file to split log message to columns.
     /// * `delimiter` - A `String` used as the field delimiter within each record in output file.
@@ -432,6 +663,7 @@ impl SessionState {
         &mut self,
         out_path: PathBuf,
         ranges: Vec<std::ops::RangeInclusive<u64>>,
+        include_duplicates: bool,
         columns: Vec<usize>,
         spliter: Option<String>,
         delimiter: Option<String>,
@@ -447,6 +679,7 @@ impl SessionState {
                     e
                 )),
             })?);
+        let ranges = self.indexes.visible_ranges(&ranges, include_duplicates);
         for (i, range) in ranges.iter().enumerate() {
             let modifier =
                 if let (Some(spliter), Some(delimiter)) = (spliter.as_ref(), delimiter.as_ref()) {
@@ -490,6 +723,40 @@ impl SessionState {
         Ok(true)
     }
 
+    /// Hides the rows from the main view and hands the searcher of the exclusion filters over
+    /// to the searchers. The searcher catches up with rows added since it was created right
+    /// away, and keeps hiding matching rows on session updates afterwards.
+    async fn handle_set_exclusions(
+        &mut self,
+        operation: Uuid,
+        holder: Option<RegularSearchHolder>,
+        rows: Vec<u64>,
+        state_cancellation_token: CancellationToken,
+    ) -> Result<(), stypes::NativeError> {
+        let catch_up = holder.is_some();
+        self.exclusions_operation = catch_up.then_some(operation);
+        self.indexes.set_hidden_rows(rows);
+        self.searcher_tx
+            .send(SearchRequest::SetExclusionsHolder { operation, holder })
+            .await
+            .map_err(|_| {
+                stypes::NativeError::channel("Failed to send exclusions holder to searchers")
+            })?;
+        if catch_up {
+            self.searcher_tx
+                .send(SearchRequest::SearchExclusions {
+                    rows: self.session_file.len(),
+                    bytes: self.session_file.read_bytes(),
+                    cancel: state_cancellation_token,
+                })
+                .await
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to send exclusions request to searchers")
+                })?;
+        }
+        Ok(())
+    }
+
     async fn handle_get_search_holder(
         &mut self,
     ) -> Result<RegularSearchHolder, stypes::NativeError> {
@@ -498,6 +765,7 @@ impl SessionState {
         self.searcher_tx
             .send(SearchRequest::GetSearchHolder {
                 filename,
+                index: self.session_file.search_index.clone(),
                 sender: holder_rx,
             })
             .await
@@ -570,7 +838,14 @@ pub async fn run(
     tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
 ) -> Result<(), stypes::NativeError> {
     let (search_req_tx, mut search_res_rx) = searchers::spawn();
-    let mut state = SessionState::new(tx_callback_events.clone(), search_req_tx);
+    let (tx_text_timestamps, mut rx_text_timestamps) = mpsc::unbounded_channel();
+    let (tx_duplicates, mut rx_duplicates) = mpsc::unbounded_channel();
+    let mut state = SessionState::new(
+        tx_callback_events.clone(),
+        search_req_tx,
+        tx_text_timestamps,
+        tx_duplicates,
+    );
     let state_cancellation_token = CancellationToken::new();
     debug!("task is started");
     loop {
@@ -600,6 +875,12 @@ pub async fn run(
 
                 }
             }
+            Some(timestamps) = rx_text_timestamps.recv() => {
+                state.handle_text_timestamps(timestamps, &tx_callback_events)?;
+            }
+            Some(compared) = rx_duplicates.recv() => {
+                state.handle_compared_duplicates(compared);
+            }
             else => {
                 break;
             }
@@ -638,7 +919,7 @@ async fn handle_api_msg(
                     stypes::NativeError::channel("Failed to respond to Api::GetSessionFile")
                 })?;
         }
-        Api::WriteSessionFile((source_id, msg, tx_response)) => {
+        Api::WriteSessionFile((source_id, msg, timestamps, tx_response)) => {
             tx_response
                 .send(
                     state
@@ -647,6 +928,7 @@ async fn handle_api_msg(
                             state_cancellation_token.clone(),
                             tx_callback_events.clone(),
                             msg,
+                            timestamps,
                         )
                         .await,
                 )
@@ -684,6 +966,20 @@ async fn handle_api_msg(
                 stypes::NativeError::channel("Failed to respond to Api::UpdateSession")
             })?;
         }
+        Api::UnlinkSessionFile((filename, blank_rows, source_id, tx_response)) => {
+            let res = state
+                .handle_unlink_session_file(
+                    filename,
+                    blank_rows,
+                    source_id,
+                    state_cancellation_token.clone(),
+                    tx_callback_events.clone(),
+                )
+                .await;
+            tx_response.send(res).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::UnlinkSessionFile")
+            })?;
+        }
         Api::AddSource((uuid, tx_response)) => {
             tx_response
                 .send(state.session_file.sources.add_source(uuid))
@@ -722,6 +1018,7 @@ async fn handle_api_msg(
         Api::ExportSession {
             out_path,
             ranges,
+            include_duplicates,
             columns,
             spliter,
             delimiter,
@@ -729,7 +1026,15 @@ async fn handle_api_msg(
             tx_response,
         } => {
             let res = state
-                .handle_export_session(out_path, ranges, columns, spliter, delimiter, cancel)
+                .handle_export_session(
+                    out_path,
+                    ranges,
+                    include_duplicates,
+                    columns,
+                    spliter,
+                    delimiter,
+                    cancel,
+                )
                 .await;
             tx_response.send(res).map_err(|_| {
                 stypes::NativeError::channel("Failed to respond to Api::ExportSession")
@@ -772,6 +1077,114 @@ async fn handle_api_msg(
                     stypes::NativeError::channel("Failed to respond to Api::GetIndexedNeighbor")
                 })?;
         }
+        Api::GetViewRow {
+            session_position,
+            tx_response,
+        } => {
+            let view_row = state.indexes.hidden().nearest_view_row(session_position);
+            tx_response.send(view_row).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetViewRow")
+            })?;
+        }
+        Api::GetVisibleRanges {
+            ranges,
+            include_duplicates,
+            tx_response,
+        } => {
+            tx_response
+                .send(state.indexes.visible_ranges(&ranges, include_duplicates))
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetVisibleRanges")
+                })?;
+        }
+        Api::SetExclusions {
+            operation,
+            holder,
+            rows,
+            tx_response,
+        } => {
+            let res = state
+                .handle_set_exclusions(operation, holder, rows, state_cancellation_token.clone())
+                .await;
+            tx_response.send(res).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::SetExclusions")
+            })?;
+        }
+        Api::GetTimeBounds(tx_response) => {
+            tx_response
+                .send(state.session_file.time_index.bounds())
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetTimeBounds")
+                })?;
+        }
+        Api::GetTimeRows { time, tx_response } => {
+            let rows = state
+                .session_file
+                .time_index
+                .rows(&time, state.session_file.len());
+            tx_response.send(rows).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetTimeRows")
+            })?;
+        }
+        Api::GetRowAtTime {
+            timestamp,
+            tx_response,
+        } => {
+            let row = state
+                .session_file
+                .time_index
+                .row_at(timestamp, state.session_file.len());
+            tx_response.send(row).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetRowAtTime")
+            })?;
+        }
+        Api::GetTimeIndex(tx_response) => {
+            tx_response
+                .send(state.session_file.time_index.clone())
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetTimeIndex")
+                })?;
+        }
+        Api::SetTemplates((templates, tx_response)) => {
+            state.templates = templates;
+            tx_response.send(()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::SetTemplates")
+            })?;
+        }
+        Api::GetTemplates(tx_response) => {
+            tx_response.send(state.templates.clone()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetTemplates")
+            })?;
+        }
+        Api::SetComparison((comparison, tx_response)) => {
+            state.comparison = comparison;
+            tx_response.send(()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::SetComparison")
+            })?;
+        }
+        Api::GetComparison(tx_response) => {
+            tx_response.send(state.comparison.clone()).map_err(|_| {
+                stypes::NativeError::channel("Failed to respond to Api::GetComparison")
+            })?;
+        }
+        Api::SetDuplicates((duplicates, tx_response)) => {
+            tx_response
+                .send(state.handle_set_duplicates(duplicates))
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::SetDuplicates")
+                })?;
+        }
+        Api::ExpandDuplicates {
+            position,
+            expanded,
+            tx_response,
+        } => {
+            tx_response
+                .send(state.indexes.expand_duplicates(position, expanded))
+                .map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::ExpandDuplicates")
+                })?;
+        }
         Api::AddBookmark((row, tx_response)) => {
             state.indexes.add_bookmark(row);
             tx_response.send(()).map_err(|_| {
@@ -874,6 +1287,7 @@ async fn handle_api_msg(
 
             if result {
                 state.search_map.set(None, None);
+                state.match_finder = None;
                 state.indexes.drop_search();
             }
             tx_callback_events.send(stypes::CallbackEvent::no_search_results())?;
@@ -882,13 +1296,14 @@ async fn handle_api_msg(
                 stypes::NativeError::channel("Failed to respond to Api::DropSearch")
             })?;
         }
-        Api::SetMatches((matches, stats, tx_response)) => {
+        Api::SetMatches((matches, stats, finder, tx_response)) => {
             let update: Option<stypes::FilterMatchList> =
                 matches.as_ref().map(|matches| matches.into());
             if let Some(matches) = matches.as_ref() {
                 state.indexes.set_search_results(matches);
             }
             state.search_map.set(matches, stats);
+            state.match_finder = finder;
             tx_callback_events.send(stypes::CallbackEvent::SearchMapUpdated(update))?;
             tx_callback_events.send(stypes::CallbackEvent::search_results(
                 state.search_map.len() as u64,
@@ -1016,6 +1431,22 @@ async fn handle_searchers(
             }
             Err(err) => error!("Fail to update search values: {err}"),
         },
+        SearchResponse::SearchExclusionsResult(operation, _)
+            if state.exclusions_operation != Some(operation) =>
+        {
+            debug!("Drop outdated results of exclusions operation {operation}");
+        }
+        SearchResponse::SearchExclusionsResult(_, res) => match res {
+            Ok((_processed, matches, _stats)) => {
+                state.indexes.append_hidden_rows(
+                    matches
+                        .into_iter()
+                        .map(|filter_match| filter_match.index)
+                        .collect(),
+                );
+            }
+            Err(err) => error!("Fail to update rows hidden by exclusions: {err}"),
+        },
     }
 
     Ok(HanldeOutpt::None)
@@ -47,7 +47,12 @@ pub fn is_raw_export_available_for(options: &[stypes::ObserveOptions]) -> bool {
     options.iter().all(|opt| {
         !matches!(
             (&opt.origin, &opt.parser),
-            (stypes::ObserveOrigin::Stream(..), _) | (_, stypes::ParserType::Plugin(..))
+            (
+                stypes::ObserveOrigin::Stream(..)
+                    | stypes::ObserveOrigin::DirWatch(..)
+                    | stypes::ObserveOrigin::Plugin(..),
+                _
+            ) | (_, stypes::ParserType::Plugin(..))
         )
     })
 }
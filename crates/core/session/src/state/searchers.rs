@@ -3,6 +3,7 @@ use std::fmt::Debug;
 
 use tokio::sync::mpsc::{self};
 use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
 use processor::search::searchers::{
     self, BaseSearcher, SearchState,
@@ -38,6 +39,9 @@ async fn run(
     let mut searchers = Searchers {
         regular: SearcherState::NotInited,
         values: SearcherState::NotInited,
+        exclusions: SearcherState::NotInited,
+        exclusions_operation: None,
+        relinked: None,
     };
     while let Some(request) = request_rx.recv().await {
         match request {
@@ -72,7 +76,29 @@ async fn run(
 
                 log_if_err(res);
             }
-            SearchRequest::GetSearchHolder { filename, sender } => {
+            SearchRequest::SearchExclusions {
+                rows,
+                bytes,
+                cancel,
+            } => {
+                let Some(operation) = searchers.exclusions_operation else {
+                    continue;
+                };
+                let Some(res) = tokio::task::block_in_place(|| {
+                    searchers.exclusions.search(rows, bytes, cancel)
+                }) else {
+                    continue;
+                };
+                let res = response_tx
+                    .send(SearchResponse::SearchExclusionsResult(operation, res))
+                    .await;
+                log_if_err(res);
+            }
+            SearchRequest::GetSearchHolder {
+                filename,
+                index,
+                sender,
+            } => {
                 let holder = {
                     match searchers.regular {
                         SearcherState::Available(_) => {
@@ -96,7 +122,9 @@ async fn run(
                         }
                         SearcherState::NotInited => {
                             searchers.regular.set_in_use();
-                            Ok(RegularSearchHolder::new(&filename, 0, 0))
+                            let mut holder = RegularSearchHolder::new(&filename, 0, 0);
+                            holder.set_index(index);
+                            Ok(holder)
                         }
                     }
                 };
@@ -141,6 +169,7 @@ async fn run(
                 let result = if searchers.regular.is_in_use() {
                     if let Some(holder) = holder {
                         searchers.regular.set_searcher(holder);
+                        searchers.regular.relink(searchers.relinked.as_ref());
                     } else {
                         searchers.regular.set_not_inited();
                     }
@@ -160,6 +189,7 @@ async fn run(
                 let result = if searchers.values.is_in_use() {
                     if let Some(holder) = holder {
                         searchers.values.set_searcher(holder);
+                        searchers.values.relink(searchers.relinked.as_ref());
                     } else {
                         searchers.values.set_not_inited();
                     }
@@ -172,6 +202,15 @@ async fn run(
                 let res = tx_response.send(result);
                 log_if_err(res);
             }
+            SearchRequest::SetExclusionsHolder { operation, holder } => {
+                searchers.exclusions_operation = holder.is_some().then_some(operation);
+                if let Some(holder) = holder {
+                    searchers.exclusions.set_searcher(holder);
+                    searchers.exclusions.relink(searchers.relinked.as_ref());
+                } else {
+                    searchers.exclusions.set_not_inited();
+                }
+            }
             SearchRequest::DropSearch { tx_result } => {
                 let result = if searchers.regular.is_in_use() {
                     false
@@ -182,6 +221,13 @@ async fn run(
                 let res = tx_result.send(result);
                 log_if_err(res);
             }
+            SearchRequest::RelinkSessionFile(relinked) => {
+                // Holders in use are relinked once they are given back.
+                searchers.regular.relink(Some(&relinked));
+                searchers.values.relink(Some(&relinked));
+                searchers.exclusions.relink(Some(&relinked));
+                searchers.relinked = Some(relinked);
+            }
             SearchRequest::DropSearchValue { tx_result } => {
                 let result = if searchers.values.is_in_use() {
                     false
@@ -217,6 +263,19 @@ impl<State: SearchState> SearcherState<State> {
     pub fn set_searcher(&mut self, seacher: BaseSearcher<State>) {
         *self = SearcherState::<_>::Available(seacher);
     }
+
+    /// Switches an available searcher still searching the linked file to the generated one.
+    pub fn relink(&mut self, relinked: Option<&RelinkedFile>) {
+        if let (Self::Available(searcher), Some(relinked)) = (self, relinked)
+            && searcher.file_path != relinked.filename
+        {
+            searcher.relink(
+                &relinked.filename,
+                relinked.index.clone(),
+                relinked.blank_rows,
+            );
+        }
+    }
 }
 
 impl SearcherState<ValueSearchState> {
@@ -260,4 +319,10 @@ impl SearcherState<RegularSearchState> {
 pub struct Searchers {
     pub regular: SearcherState<RegularSearchState>,
     pub values: SearcherState<ValueSearchState>,
+    /// Searcher of the exclusion filters hiding rows from the main view.
+    pub exclusions: SearcherState<RegularSearchState>,
+    /// Exclusions operation which has set up the searcher of the exclusion filters.
+    pub exclusions_operation: Option<Uuid>,
+    /// Generated file replacing the linked session file, once it has been replaced.
+    pub relinked: Option<RelinkedFile>,
 }
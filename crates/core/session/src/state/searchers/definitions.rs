@@ -2,10 +2,14 @@ use std::path::PathBuf;
 
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
-use processor::search::searchers::{
-    regular::{self, RegularSearchHolder},
-    values::{OperationResults, ValueSearchHolder},
+use processor::search::{
+    index::SharedSearchIndex,
+    searchers::{
+        regular::{self, RegularSearchHolder},
+        values::{OperationResults, ValueSearchHolder},
+    },
 };
 
 #[derive(Debug)]
@@ -20,8 +24,16 @@ pub enum SearchRequest {
         bytes: u64,
         cancel: CancellationToken,
     },
+    /// Searches the new rows for matches of the exclusion filters.
+    SearchExclusions {
+        rows: u64,
+        bytes: u64,
+        cancel: CancellationToken,
+    },
     GetSearchHolder {
         filename: PathBuf,
+        /// Index of the session file, which is set to newly created holders.
+        index: Option<SharedSearchIndex>,
         sender: oneshot::Sender<Result<RegularSearchHolder, stypes::NativeError>>,
     },
     GetSearchValueHolder {
@@ -36,16 +48,37 @@ pub enum SearchRequest {
         holder: Option<ValueSearchHolder>,
         tx_response: oneshot::Sender<Result<(), stypes::NativeError>>,
     },
+    /// Replaces the searcher of the exclusion filters set up by the exclusions operation
+    /// `operation`, or drops it if `None`.
+    SetExclusionsHolder {
+        operation: Uuid,
+        holder: Option<RegularSearchHolder>,
+    },
     DropSearch {
         tx_result: oneshot::Sender<bool>,
     },
+    /// Switches the searchers to the generated file, which replaced the linked session file.
+    RelinkSessionFile(RelinkedFile),
     DropSearchValue {
         tx_result: oneshot::Sender<bool>,
     },
 }
 
+/// Generated session file replacing the linked one, see [`BaseSearcher::relink`].
+///
+/// [`BaseSearcher::relink`]: processor::search::searchers::BaseSearcher::relink
+#[derive(Debug, Clone)]
+pub struct RelinkedFile {
+    pub filename: PathBuf,
+    pub index: Option<SharedSearchIndex>,
+    /// Whether the rows of the linked file are written as empty lines.
+    pub blank_rows: bool,
+}
+
 #[derive(Debug)]
 pub enum SearchResponse {
     SearchRegularResult(regular::SearchResults),
     SearchValueResult(OperationResults),
+    /// Results of the searcher of the exclusion filters set up by the given operation.
+    SearchExclusionsResult(Uuid, regular::SearchResults),
 }
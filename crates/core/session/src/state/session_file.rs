@@ -2,25 +2,35 @@
 //! the file where the parsed logs in their text format will be stored temporally
 //! during the session.
 
-use super::source_ids::SourceIDs;
+use super::{source_ids::SourceIDs, time_index::TimeIndex};
 use crate::paths;
 use log::debug;
 use processor::{
     grabber::{Grabber, LineRange},
+    lines::LineReader,
+    search::index::{self, SearchIndex, SharedSearchIndex},
     text_source::TextFileSource,
 };
 use std::{
     fs::File,
-    io::{BufWriter, Write},
-    path::PathBuf,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
     time::Instant,
 };
 use stypes::GrabbedElement;
+use tokio::{sync::mpsc::UnboundedSender, task};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 pub const FLUSH_DATA_IN_MS: u128 = 500;
+/// Number of rows, whose timestamps are reported at once while indexing the timestamps
+/// of linked text files.
+const TIME_INDEX_CHUNK_ROWS: usize = 50_000;
 pub const SESSION_FILE_EXTENSION: &str = "session";
+pub const SEARCH_INDEX_EXTENSION: &str = "index";
+pub const TIME_INDEX_EXTENSION: &str = "time";
 
 #[derive(Debug)]
 pub enum SessionFileState {
@@ -49,6 +59,18 @@ impl SessionFileOrigin {
     }
 }
 
+/// Timestamps parsed from the rows of a linked text file.
+#[derive(Debug)]
+pub struct TextTimestamps {
+    /// Row of the first timestamp.
+    pub row: u64,
+    /// Byte offset of the end of the rows.
+    pub offset: u64,
+    pub timestamps: Vec<Option<u64>>,
+    /// Whether these are the last timestamps parsed by the task.
+    pub done: bool,
+}
+
 #[derive(Debug)]
 pub struct SessionFile {
     pub grabber: Option<Box<Grabber>>,
@@ -56,6 +78,15 @@ pub struct SessionFile {
     pub writer: Option<BufWriter<File>>,
     pub last_message_timestamp: Instant,
     pub sources: SourceIDs,
+    /// Timestamps of the rows, reported by parsers for generated files or parsed from
+    /// the lines of linked text files.
+    pub time_index: TimeIndex,
+    /// Byte offset of the end of the linked file rows, whose timestamps are indexed.
+    text_offset: u64,
+    /// Cancels the task parsing the timestamps of the linked file rows while it runs.
+    text_timestamps_task: Option<CancellationToken>,
+    /// Index narrowing the searches of the session file, shared with the searchers.
+    pub search_index: Option<SharedSearchIndex>,
 }
 
 impl SessionFile {
@@ -66,6 +97,10 @@ impl SessionFile {
             writer: None,
             last_message_timestamp: Instant::now(),
             sources: SourceIDs::new(),
+            time_index: TimeIndex::default(),
+            text_offset: 0,
+            text_timestamps_task: None,
+            search_index: None,
         }
     }
 
@@ -75,8 +110,7 @@ impl SessionFile {
                 self.filename = Some(SessionFileOrigin::Linked(filename.clone()));
                 filename
             } else {
-                let streams = paths::get_streams_dir()?;
-                let filename = streams.join(format!("{}.{SESSION_FILE_EXTENSION}", Uuid::new_v4()));
+                let filename = Self::generated_filename()?;
                 debug!("Session file setup: {}", filename.to_string_lossy());
                 self.writer = Some(BufWriter::new(File::create(&filename).map_err(|e| {
                     stypes::NativeError {
@@ -92,6 +126,10 @@ impl SessionFile {
                 self.filename = Some(SessionFileOrigin::Generated(filename.clone()));
                 filename
             };
+            let search_index = search_index_path(self.filename.as_ref())
+                .map_or_else(|| SearchIndex::new(None), SearchIndex::open);
+            self.search_index = Some(Arc::new(Mutex::new(search_index)));
+            self.time_index = TimeIndex::new(time_index_path(self.filename.as_ref()));
             Ok(Grabber::lazy(TextFileSource::new(&filename))
                 .map(|g| self.grabber = Some(Box::new(g)))?)
         } else {
@@ -99,6 +137,97 @@ impl SessionFile {
         }
     }
 
+    /// Returns a new path for a generated session file.
+    pub fn generated_filename() -> Result<PathBuf, stypes::NativeError> {
+        Ok(paths::get_streams_dir()?.join(format!("{}.{SESSION_FILE_EXTENSION}", Uuid::new_v4())))
+    }
+
+    /// Switches a linked session to the given generated file, which already holds the
+    /// rows of the linked file and may continue with rows of the source `source_id` not
+    /// read yet. New content is written to the generated file afterwards.
+    ///
+    /// `blank_rows` tells that the rows read so far are written as empty lines, because
+    /// their content is lost, so their byte offsets aren't kept.
+    pub fn unlink(
+        &mut self,
+        filename: PathBuf,
+        blank_rows: bool,
+        source_id: u16,
+        state_cancellation_token: CancellationToken,
+    ) -> Result<SessionFileState, stypes::NativeError> {
+        if !self
+            .filename
+            .as_ref()
+            .is_some_and(SessionFileOrigin::is_linked)
+        {
+            return Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Grabber,
+                message: Some(String::from("Session file isn't linked")),
+            });
+        }
+        if let Some(cancel) = self.text_timestamps_task.take() {
+            cancel.cancel();
+        }
+        let prev = self.len();
+        let writer = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&filename)
+            .map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Io,
+                message: Some(format!(
+                    "Fail to create session writer for {}: {}",
+                    filename.to_string_lossy(),
+                    e
+                )),
+            })?;
+        let mut grabber = Grabber::lazy(TextFileSource::new(&filename))?;
+        if !blank_rows && let Some(metadata) = self.grabber.as_ref().and_then(|g| g.get_metadata())
+        {
+            // The rows read so far have been copied with the same byte offsets.
+            grabber.inject_metadata(metadata.clone())?;
+        }
+        if let Some(range) = grabber.update_from_file(Some(state_cancellation_token))? {
+            let start = (*range.start()).max(prev);
+            if start <= *range.end() {
+                self.sources.add_range(start..=*range.end(), source_id);
+            }
+        }
+        self.grabber = Some(Box::new(grabber));
+        let current = self.len();
+        // Rows of the generated file follow the rows of the linked file, so the rows of the
+        // linked file, which aren't indexed yet, are indexed here. Blank rows are one byte
+        // long.
+        let offset = if blank_rows {
+            self.time_index.len()
+        } else {
+            self.text_offset
+        };
+        let time_index = &mut self.time_index;
+        let mut appended = Ok(());
+        parse_text_timestamps(
+            &filename,
+            time_index.len()..current,
+            offset,
+            &CancellationToken::new(),
+            |timestamps| {
+                appended = time_index.append(timestamps.timestamps);
+                appended.is_ok()
+            },
+        )?;
+        appended?;
+        self.writer = Some(BufWriter::new(writer));
+        let search_index = SearchIndex::open(filename.with_extension(SEARCH_INDEX_EXTENSION));
+        self.search_index = Some(Arc::new(Mutex::new(search_index)));
+        self.filename = Some(SessionFileOrigin::Generated(filename));
+        Ok(if prev != current {
+            SessionFileState::Changed
+        } else {
+            SessionFileState::NoChanges
+        })
+    }
+
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> u64 {
         self.grabber
@@ -123,6 +252,7 @@ impl SessionFile {
         source_id: u16,
         state_cancellation_token: CancellationToken,
         msg: String,
+        timestamps: Vec<Option<u64>>,
     ) -> Result<SessionFileState, stypes::NativeError> {
         if !self.sources.is_source_same(source_id) {
             self.flush(state_cancellation_token.clone(), false)?;
@@ -135,6 +265,7 @@ impl SessionFile {
             )),
         })?;
         writer.write_all(msg.as_bytes())?;
+        self.time_index.append(timestamps)?;
 
         self.sources.source_update(source_id);
         if self.last_message_timestamp.elapsed().as_millis() > FLUSH_DATA_IN_MS {
@@ -191,6 +322,71 @@ impl SessionFile {
         })
     }
 
+    /// Starts parsing the timestamps of the linked text file rows, which aren't indexed
+    /// yet, in a blocking task unless the task is running already. The timestamps are
+    /// sent to `tx_timestamps` in chunks and appended by [`SessionFile::append_text_timestamps`].
+    pub fn index_text_timestamps(&mut self, tx_timestamps: UnboundedSender<TextTimestamps>) {
+        let Some(SessionFileOrigin::Linked(filename)) = self.filename.clone() else {
+            return;
+        };
+        let rows = self.time_index.len()..self.len();
+        if self.text_timestamps_task.is_some() || rows.is_empty() {
+            return;
+        }
+        let cancel = CancellationToken::new();
+        self.text_timestamps_task = Some(cancel.clone());
+        let offset = self.text_offset;
+        task::spawn_blocking(move || {
+            let (row, tx) = (rows.start, tx_timestamps.clone());
+            let parsed = parse_text_timestamps(&filename, rows, offset, &cancel, |timestamps| {
+                tx.send(timestamps).is_ok()
+            });
+            if let Err(err) = parsed {
+                log::warn!("Fail to parse timestamps of {filename:?}: {err}");
+                // Lets the next update of the session file parse the rows again.
+                let _ = tx_timestamps.send(TextTimestamps {
+                    row,
+                    offset,
+                    timestamps: Vec::new(),
+                    done: true,
+                });
+            }
+        });
+    }
+
+    /// Appends the timestamps parsed from the linked text file and returns whether they
+    /// are appended. Timestamps not following the indexed rows are outdated and dropped.
+    pub fn append_text_timestamps(
+        &mut self,
+        timestamps: TextTimestamps,
+    ) -> Result<bool, stypes::NativeError> {
+        if timestamps.done {
+            self.text_timestamps_task = None;
+        }
+        if !self
+            .filename
+            .as_ref()
+            .is_some_and(SessionFileOrigin::is_linked)
+            || timestamps.row != self.time_index.len()
+            || timestamps.timestamps.is_empty()
+        {
+            return Ok(false);
+        }
+        self.time_index.append(timestamps.timestamps)?;
+        self.text_offset = timestamps.offset;
+        Ok(true)
+    }
+
+    /// Returns the first row and the byte offset of the slot of the grabber holding the
+    /// row, where reading the file continues towards the row.
+    pub fn slot_start(&self, row: u64) -> Option<(u64, u64)> {
+        let slots = &self.grabber.as_ref()?.get_metadata()?.slots;
+        let index = slots.partition_point(|slot| slot.lines.end() < row);
+        slots
+            .get(index)
+            .map(|slot| (slot.lines.start(), slot.bytes.start()))
+    }
+
     pub fn grab(&self, range: &LineRange) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
         let grabber = &mut (self.grabber.as_ref().ok_or(stypes::NativeError {
             severity: stypes::Severity::ERROR,
@@ -216,6 +412,8 @@ impl SessionFile {
                     content,
                     pos,
                     nature: 0,
+                    matches: Vec::new(),
+                    collapsed: None,
                 }
             })
             .collect())
@@ -275,6 +473,12 @@ impl SessionFile {
 
     /// Cleans up temporary generated files and attachments for non-linked sessions.
     pub fn cleanup(&mut self) -> Result<(), stypes::NativeError> {
+        if let Some(cancel) = self.text_timestamps_task.take() {
+            cancel.cancel();
+        }
+        if let Err(err) = self.time_index.remove() {
+            log::warn!("Fail to remove time index of {:?}: {err}", self.filename);
+        }
         let Some(SessionFileOrigin::Generated(filename)) = &self.filename else {
             return Ok(());
         };
@@ -283,6 +487,13 @@ impl SessionFile {
         self.writer.take();
         self.grabber.take();
 
+        if let Some(index) = self.search_index.take()
+            && let Ok(mut index) = index.lock()
+            && let Err(err) = index.remove()
+        {
+            log::warn!("Fail to remove search index of {filename:?}: {err}");
+        }
+
         debug!("cleaning up files: {filename:?}");
         if filename.exists() {
             std::fs::remove_file(filename).map_err(|e| stypes::NativeError {
@@ -325,6 +536,78 @@ impl SessionFile {
     }
 }
 
+/// Returns the path of the persisted search index of the session file.
+///
+/// Indexes of generated files are kept next to them and removed with them. Indexes of
+/// linked files are kept in the home directory, so they are reused when the file is
+/// opened again.
+fn search_index_path(origin: Option<&SessionFileOrigin>) -> Option<PathBuf> {
+    match origin? {
+        SessionFileOrigin::Generated(filename) => {
+            Some(filename.with_extension(SEARCH_INDEX_EXTENSION))
+        }
+        SessionFileOrigin::Linked(filename) => {
+            let dir = paths::get_search_indexes_dir()
+                .inspect_err(|err| log::warn!("Search index isn't persisted: {err:?}"))
+                .ok()?;
+            let path = std::fs::canonicalize(filename).unwrap_or_else(|_| filename.clone());
+            // The name has to be the same across runs and builds to find the index again.
+            Some(dir.join(format!(
+                "{:016x}.{SEARCH_INDEX_EXTENSION}",
+                index::fnv(path.as_os_str().as_encoded_bytes())
+            )))
+        }
+    }
+}
+
+/// Returns the path of the persisted time index of the session file, which is kept next
+/// to the generated file of the session even when the session is linked to a file.
+fn time_index_path(origin: Option<&SessionFileOrigin>) -> Option<PathBuf> {
+    match origin? {
+        SessionFileOrigin::Generated(filename) => Some(filename.clone()),
+        SessionFileOrigin::Linked(_) => SessionFile::generated_filename()
+            .inspect_err(|err| log::warn!("Time index isn't persisted: {err:?}"))
+            .ok(),
+    }
+    .map(|filename| filename.with_extension(TIME_INDEX_EXTENSION))
+}
+
+/// Parses the timestamps of the `rows` of the text file, whose first row starts at the
+/// byte `offset`. Timestamps are passed to `chunk` in chunks, until it returns `false`,
+/// and the last chunk is marked as done. Cancelling ends the parsing with the last chunk.
+///
+/// Only complete lines are parsed, as the grabber counts only them as rows.
+fn parse_text_timestamps(
+    filename: &Path,
+    rows: Range<u64>,
+    offset: u64,
+    cancel: &CancellationToken,
+    mut chunk: impl FnMut(TextTimestamps) -> bool,
+) -> io::Result<()> {
+    let mut reader = LineReader::open(filename, cancel)?
+        .starting_at(rows.start, offset)?
+        .complete_lines();
+    loop {
+        let row = reader.row();
+        let end = rows.end.min(row + TIME_INDEX_CHUNK_ROWS as u64);
+        let mut timestamps = Vec::new();
+        let read = reader.read(end, |_, line| {
+            timestamps.push(parsers::text::parse_timestamp(line));
+            true
+        })?;
+        let done = !read || reader.row() < end || end == rows.end;
+        let parsed = TextTimestamps {
+            row,
+            offset: reader.offset(),
+            timestamps,
+            done,
+        };
+        if !chunk(parsed) || done {
+            return Ok(());
+        }
+    }
+}
+
 impl Default for SessionFile {
     fn default() -> Self {
         Self::new()
@@ -334,10 +617,20 @@ impl Default for SessionFile {
 #[cfg(test)]
 mod tests {
     use std::{
-        fs::{self, File},
-        io::BufWriter,
+        fs::{self, File, OpenOptions},
+        io::{BufWriter, Write},
     };
 
+    use processor::search::index::SearchIndex;
+    use std::sync::{Arc, Mutex};
+
+    use processor::{
+        grabber::{Grabber, LineRange},
+        text_source::TextFileSource,
+    };
+    use tokio::sync::mpsc::unbounded_channel;
+    use tokio_util::sync::CancellationToken;
+
     use super::{SessionFile, SessionFileOrigin};
 
     #[test]
@@ -345,16 +638,21 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let session_path = temp_dir.path().join("generated.session");
         let attachments_dir = temp_dir.path().join("generated");
+        let index_path = temp_dir.path().join("generated.index");
         fs::create_dir(&attachments_dir).unwrap();
+        fs::write(&index_path, "index").unwrap();
         fs::write(attachments_dir.join("attachment.txt"), "content").unwrap();
 
         let mut session_file = SessionFile::new();
         session_file.filename = Some(SessionFileOrigin::Generated(session_path.clone()));
         session_file.writer = Some(BufWriter::new(File::create(&session_path).unwrap()));
+        session_file.search_index =
+            Some(Arc::new(Mutex::new(SearchIndex::open(index_path.clone()))));
 
         session_file.cleanup().unwrap();
 
         assert!(!session_path.exists());
+        assert!(!index_path.exists());
         assert!(!attachments_dir.exists());
     }
 
@@ -371,4 +669,87 @@ mod tests {
 
         assert!(session_path.exists());
     }
+
+    #[test]
+    fn unlink_continues_in_generated_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let linked_path = temp_dir.path().join("app.log");
+        let generated_path = temp_dir.path().join("generated.session");
+        fs::write(&linked_path, "first\nsecond\n").unwrap();
+
+        let mut session_file = SessionFile::new();
+        session_file.filename = Some(SessionFileOrigin::Linked(linked_path.clone()));
+        session_file.grabber = Some(Box::new(
+            Grabber::lazy(TextFileSource::new(&linked_path)).unwrap(),
+        ));
+        session_file.update(0, CancellationToken::new()).unwrap();
+        assert_eq!(session_file.len(), 2);
+
+        // Truncated linked file, whose rows are replaced by empty lines.
+        fs::write(&generated_path, "\n\n").unwrap();
+        session_file
+            .unlink(generated_path.clone(), true, 0, CancellationToken::new())
+            .unwrap();
+        assert!(matches!(
+            &session_file.filename,
+            Some(SessionFileOrigin::Generated(path)) if path == &generated_path
+        ));
+        assert_eq!(session_file.len(), 2);
+
+        session_file
+            .write(0, CancellationToken::new(), "third\n".into(), vec![None])
+            .unwrap();
+        session_file.flush(CancellationToken::new(), true).unwrap();
+        assert_eq!(session_file.len(), 3);
+        let rows = session_file.grab(&LineRange::from(2..=2)).unwrap();
+        assert_eq!(rows[0].content, "third");
+        assert_eq!(session_file.time_index.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn linked_file_timestamps_are_indexed_in_background() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let linked_path = temp_dir.path().join("app.log");
+        fs::write(
+            &linked_path,
+            "2024-05-01 14:03:10.200 first\ncontinued\n2024-05-01 14:03:11.000 second\n",
+        )
+        .unwrap();
+        let mut session_file = SessionFile::new();
+        session_file.filename = Some(SessionFileOrigin::Linked(linked_path.clone()));
+        session_file.grabber = Some(Box::new(
+            Grabber::lazy(TextFileSource::new(&linked_path)).unwrap(),
+        ));
+        let (tx_timestamps, mut rx_timestamps) = unbounded_channel();
+
+        session_file.update(0, CancellationToken::new()).unwrap();
+        assert!(session_file.time_index.is_empty());
+        session_file.index_text_timestamps(tx_timestamps.clone());
+        let timestamps = rx_timestamps.recv().await.unwrap();
+        assert!(timestamps.done);
+        assert!(session_file.append_text_timestamps(timestamps).unwrap());
+        assert_eq!(session_file.time_index.len(), 3);
+        assert_eq!(
+            session_file.time_index.timestamp(1),
+            Some(1_714_572_190_200)
+        );
+
+        // Rows added later are parsed from the end of the indexed rows.
+        OpenOptions::new()
+            .append(true)
+            .open(&linked_path)
+            .unwrap()
+            .write_all(b"2024-05-01 14:03:12.000 third\n")
+            .unwrap();
+        session_file.update(0, CancellationToken::new()).unwrap();
+        session_file.index_text_timestamps(tx_timestamps);
+        let timestamps = rx_timestamps.recv().await.unwrap();
+        assert_eq!(timestamps.row, 3);
+        assert!(session_file.append_text_timestamps(timestamps).unwrap());
+        assert_eq!(session_file.time_index.len(), 4);
+        assert_eq!(
+            session_file.time_index.timestamp(3),
+            Some(1_714_572_192_000)
+        );
+    }
 }
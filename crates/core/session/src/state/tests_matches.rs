@@ -0,0 +1,120 @@
+//! Match range contract tests for grabbed rows.
+
+use std::io::Write;
+
+use processor::{
+    grabber::LineRange,
+    search::{filter::SearchFilter, highlight::MatchFinder},
+};
+use tempfile::NamedTempFile;
+use tokio::sync::{
+    mpsc::{channel, unbounded_channel},
+    oneshot,
+};
+use tokio_util::sync::CancellationToken;
+
+use super::{Api, SessionState, handle_api_msg};
+
+fn state_with(file: &mut NamedTempFile, lines: &[&str]) -> SessionState {
+    for line in lines {
+        writeln!(file, "{line}").unwrap();
+    }
+    file.flush().unwrap();
+
+    let (callback_tx, _callback_rx) = unbounded_channel();
+    let (search_tx, _search_rx) = channel(1);
+    let (timestamps_tx, _timestamps_rx) = unbounded_channel();
+    let (duplicates_tx, _duplicates_rx) = unbounded_channel();
+    let mut state = SessionState::new(callback_tx, search_tx, timestamps_tx, duplicates_tx);
+    state
+        .session_file
+        .init(Some(file.path().to_path_buf()))
+        .unwrap();
+    state
+        .session_file
+        .update(0, CancellationToken::new())
+        .unwrap();
+    state
+}
+
+fn ranges(elements: &[stypes::GrabbedElement]) -> Vec<Vec<(u8, u8, usize, usize)>> {
+    elements
+        .iter()
+        .map(|element| {
+            element
+                .matches
+                .iter()
+                .map(|range| (range.filter, range.group, range.start, range.end))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn grabbed_search_rows_carry_their_match_ranges() {
+    let mut file = NamedTempFile::new().unwrap();
+    let mut state = state_with(&mut file, &["error one", "fine", "warn id=7 error"]);
+    let filters = vec![
+        SearchFilter::plain("error"),
+        SearchFilter::plain(r"id=(\d+)").regex(true),
+    ];
+    state.match_finder = Some(MatchFinder::new(filters).unwrap());
+    let matches = vec![
+        stypes::FilterMatch::new(0, vec![0]),
+        stypes::FilterMatch::new(2, vec![0, 1]),
+    ];
+    state.indexes.set_search_results(&matches);
+    state.search_map.set(Some(matches), None);
+
+    let search = state.handle_grab_search(LineRange::from(0..=1)).unwrap();
+    assert_eq!(
+        ranges(&search),
+        vec![
+            vec![(0, 0, 0, 5)],
+            vec![(0, 0, 10, 15), (1, 0, 5, 9), (1, 1, 8, 9)],
+        ]
+    );
+
+    let rows = state.handle_grab(&LineRange::from(0..=2)).unwrap();
+    assert!(rows[1].matches.is_empty());
+    assert_eq!(ranges(&rows)[2], ranges(&search)[1]);
+}
+
+#[test]
+fn rows_have_no_match_ranges_without_search() {
+    let mut file = NamedTempFile::new().unwrap();
+    let mut state = state_with(&mut file, &["error one"]);
+    let matches = vec![stypes::FilterMatch::new(0, vec![0])];
+    state.search_map.set(Some(matches), None);
+
+    let rows = state.handle_grab(&LineRange::from(0..=0)).unwrap();
+    assert!(rows[0].matches.is_empty());
+}
+
+#[tokio::test]
+async fn finder_is_swapped_with_the_matches() {
+    let mut file = NamedTempFile::new().unwrap();
+    let mut state = state_with(&mut file, &["error one", "warn two"]);
+    state.match_finder = Some(MatchFinder::new(vec![SearchFilter::plain("error")]).unwrap());
+    let matches = vec![stypes::FilterMatch::new(0, vec![0])];
+    state.indexes.set_search_results(&matches);
+    state.search_map.set(Some(matches), None);
+
+    let finder =
+        MatchFinder::new(vec![SearchFilter::plain("zzz"), SearchFilter::plain("two")]).unwrap();
+    let matches = vec![stypes::FilterMatch::new(1, vec![1])];
+    let (tx_callback_events, _rx_callback_events) = unbounded_channel();
+    let (tx_response, rx_response) = oneshot::channel();
+    handle_api_msg(
+        Api::SetMatches((Some(matches), None, Some(finder), tx_response)),
+        &mut state,
+        &tx_callback_events,
+        &CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+    rx_response.await.unwrap();
+
+    let rows = state.handle_grab(&LineRange::from(0..=1)).unwrap();
+    assert_eq!(ranges(&rows), vec![vec![], vec![(1, 0, 5, 8)]]);
+}
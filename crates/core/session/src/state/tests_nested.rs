@@ -24,7 +24,9 @@ impl Fixture {
 
         let (callback_tx, _callback_rx) = unbounded_channel();
         let (search_tx, _search_rx) = channel(1);
-        let mut state = SessionState::new(callback_tx, search_tx);
+        let (timestamps_tx, _timestamps_rx) = unbounded_channel();
+        let (duplicates_tx, _duplicates_rx) = unbounded_channel();
+        let mut state = SessionState::new(callback_tx, search_tx, timestamps_tx, duplicates_tx);
         let session_path = file.path().to_path_buf();
         state.session_file.init(Some(session_path)).unwrap();
         state
@@ -54,6 +56,15 @@ impl Fixture {
     }
 }
 
+fn found(start: usize, end: usize) -> Vec<stypes::MatchRange> {
+    vec![stypes::MatchRange {
+        filter: 0,
+        group: 0,
+        start,
+        end,
+    }]
+}
+
 fn filter_matches(rows: &[u64]) -> Vec<stypes::FilterMatch> {
     rows.iter()
         .map(|row| stypes::FilterMatch::new(*row, vec![]))
@@ -71,6 +82,7 @@ fn unanchored_navigation_starts_at_requested_boundary() {
             session_position: 0,
             search_result_index: 0,
             indexed_row_index: 0,
+            matches: found(6, 12),
         })
     );
     assert_eq!(
@@ -79,6 +91,7 @@ fn unanchored_navigation_starts_at_requested_boundary() {
             session_position: 2,
             search_result_index: 2,
             indexed_row_index: 2,
+            matches: found(5, 11),
         })
     );
 }
@@ -172,6 +185,7 @@ fn result_reports_exact_primary_and_indexed_coordinates() {
             session_position: 7,
             search_result_index: 3,
             indexed_row_index: 5,
+            matches: found(0, 5),
         })
     );
     assert_eq!(
@@ -180,6 +194,7 @@ fn result_reports_exact_primary_and_indexed_coordinates() {
             session_position: 3,
             search_result_index: 1,
             indexed_row_index: 2,
+            matches: found(0, 7),
         })
     );
 }
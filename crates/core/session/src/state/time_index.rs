@@ -0,0 +1,477 @@
+//! Includes the time index of the session, which keeps the timestamps of the session file
+//! rows to resolve times into rows.
+//!
+//! Only the earliest and the latest timestamp of each block of rows are kept in memory.
+//! The timestamps of the rows are persisted next to the session file as the rows, where
+//! the timestamp changes, and are read for the blocks visited by a lookup only.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::{Range, RangeInclusive},
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Marks rows before the first row with a timestamp.
+const NO_TIMESTAMP: u64 = u64::MAX;
+
+/// Number of rows summarized by one block.
+const BLOCK_LEN: u64 = 1024;
+
+/// Size of a persisted change.
+const CHANGE_BYTES: usize = 16;
+
+/// Row whose timestamp differs from the timestamp of the row before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Change {
+    row: u64,
+    timestamp: u64,
+}
+
+impl Change {
+    fn to_bytes(self) -> [u8; CHANGE_BYTES] {
+        let mut bytes = [0; CHANGE_BYTES];
+        bytes[..8].copy_from_slice(&self.row.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut row = [0; 8];
+        let mut timestamp = [0; 8];
+        row.copy_from_slice(&bytes[..8]);
+        timestamp.copy_from_slice(&bytes[8..CHANGE_BYTES]);
+        Self {
+            row: u64::from_le_bytes(row),
+            timestamp: u64::from_le_bytes(timestamp),
+        }
+    }
+}
+
+/// The earliest and the latest timestamp of a block of rows, with the position of its
+/// first change.
+#[derive(Debug, Clone)]
+struct Block {
+    min: u64,
+    max: u64,
+    /// Timestamp of the first row unless the row is a change.
+    start: u64,
+    /// Number of changes before the block.
+    changes: u64,
+}
+
+impl Block {
+    fn new(start: Option<u64>, changes: u64) -> Self {
+        Self {
+            min: u64::MAX,
+            max: 0,
+            start: start.unwrap_or(NO_TIMESTAMP),
+            changes,
+        }
+    }
+
+    fn add(&mut self, timestamp: u64) {
+        self.min = self.min.min(timestamp);
+        self.max = self.max.max(timestamp);
+    }
+
+    fn overlaps(&self, time: &RangeInclusive<u64>) -> bool {
+        self.min <= self.max && self.min <= *time.end() && self.max >= *time.start()
+    }
+}
+
+/// Storage of the changes.
+#[derive(Debug, Clone)]
+enum Changes {
+    /// Changes kept in memory, for indexes without a file.
+    Memory(Arc<Vec<Change>>),
+    /// Changes persisted in the file.
+    File(PathBuf),
+}
+
+impl Changes {
+    fn write(&mut self, changes: &[Change]) -> io::Result<()> {
+        match self {
+            Self::Memory(stored) => Arc::make_mut(stored).extend_from_slice(changes),
+            Self::File(path) => {
+                if changes.is_empty() {
+                    return Ok(());
+                }
+                let bytes: Vec<u8> = changes.iter().flat_map(|c| c.to_bytes()).collect();
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?
+                    .write_all(&bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the changes of the blocks visited by a lookup, opening the file once.
+struct Reader<'a> {
+    changes: &'a Changes,
+    file: Option<File>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(changes: &'a Changes) -> Self {
+        Self {
+            changes,
+            file: None,
+        }
+    }
+
+    fn read(&mut self, range: Range<u64>) -> io::Result<Vec<Change>> {
+        let path = match self.changes {
+            Changes::Memory(stored) => {
+                return Ok(stored[range.start as usize..range.end as usize].to_vec());
+            }
+            Changes::File(path) => path,
+        };
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => self.file.insert(File::open(path)?),
+        };
+        let mut bytes = vec![0; (range.end - range.start) as usize * CHANGE_BYTES];
+        file.seek(SeekFrom::Start(range.start * CHANGE_BYTES as u64))?;
+        file.read_exact(&mut bytes)?;
+        Ok(bytes
+            .chunks_exact(CHANGE_BYTES)
+            .map(Change::from_bytes)
+            .collect())
+    }
+}
+
+/// Timestamps of the session file rows in milliseconds since the Unix epoch.
+///
+/// Rows without a timestamp of their own, like the continuation lines of a multiline
+/// message, inherit the timestamp of the row before them. Rows are summarized in blocks
+/// by their earliest and latest timestamps, so lookups skip the blocks outside of the
+/// requested time without reading their rows. Blocks, which can't be read, are logged and
+/// have no rows with timestamps.
+///
+/// Clones are snapshots, which share the blocks and the stored changes with the index
+/// until the index is appended to, so taking a snapshot for a long lookup is cheap.
+#[derive(Debug, Clone)]
+pub struct TimeIndex {
+    blocks: Arc<Vec<Block>>,
+    rows: u64,
+    /// Number of stored changes.
+    changes_len: u64,
+    last: Option<u64>,
+    changes: Changes,
+}
+
+impl Default for TimeIndex {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl TimeIndex {
+    /// Creates an empty index, whose timestamps are persisted at `path` if given, or kept
+    /// in memory otherwise.
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            blocks: Arc::new(Vec::new()),
+            rows: 0,
+            changes_len: 0,
+            last: None,
+            changes: path.map_or_else(|| Changes::Memory(Arc::default()), Changes::File),
+        }
+    }
+
+    /// Appends the timestamps of the next rows of the session file.
+    pub fn append(&mut self, timestamps: impl IntoIterator<Item = Option<u64>>) -> io::Result<()> {
+        let mut changes = Vec::new();
+        let blocks = Arc::make_mut(&mut self.blocks);
+        for timestamp in timestamps {
+            if self.rows.is_multiple_of(BLOCK_LEN) {
+                blocks.push(Block::new(
+                    self.last,
+                    self.changes_len + changes.len() as u64,
+                ));
+            }
+            let timestamp = timestamp.or(self.last);
+            if let Some(timestamp) = timestamp {
+                if let Some(block) = blocks.last_mut() {
+                    block.add(timestamp);
+                }
+                if self.last != Some(timestamp) {
+                    changes.push(Change {
+                        row: self.rows,
+                        timestamp,
+                    });
+                }
+            }
+            self.last = timestamp;
+            self.rows += 1;
+        }
+        self.changes_len += changes.len() as u64;
+        self.changes.write(&changes)
+    }
+
+    /// Returns the number of indexed rows.
+    pub fn len(&self) -> u64 {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Returns the timestamp of the row, or `None` for rows before the first row with a
+    /// timestamp and rows out of the index.
+    pub fn timestamp(&self, row: u64) -> Option<u64> {
+        self.lookup()(row)
+    }
+
+    /// Returns a lookup of the timestamps of rows like [`TimeIndex::timestamp`], which
+    /// reads the timestamps of a block once while rows of the block are looked up.
+    pub fn lookup(&self) -> impl FnMut(u64) -> Option<u64> + '_ {
+        let mut reader = Reader::new(&self.changes);
+        let mut block: Option<(usize, Vec<u64>)> = None;
+        move |row| {
+            if row >= self.rows {
+                return None;
+            }
+            let index = (row / BLOCK_LEN) as usize;
+            let (_, stamps) = match block.take() {
+                Some(cached) if cached.0 == index => block.insert(cached),
+                _ => block.insert((index, self.stamps(&mut reader, index))),
+            };
+            stamps
+                .get((row % BLOCK_LEN) as usize)
+                .copied()
+                .filter(|stamp| *stamp != NO_TIMESTAMP)
+        }
+    }
+
+    /// Returns the earliest and the latest timestamp of the session.
+    pub fn bounds(&self) -> Option<RangeInclusive<u64>> {
+        self.blocks
+            .iter()
+            .filter(|block| block.min <= block.max)
+            .fold(None, |bounds: Option<RangeInclusive<u64>>, block| {
+                Some(match bounds {
+                    Some(bounds) => {
+                        (*bounds.start()).min(block.min)..=(*bounds.end()).max(block.max)
+                    }
+                    None => block.min..=block.max,
+                })
+            })
+    }
+
+    /// Returns the ranges of rows with timestamps within the given time, considering the
+    /// first `rows` rows only.
+    pub fn rows(&self, time: &RangeInclusive<u64>, rows: u64) -> Vec<RangeInclusive<u64>> {
+        let rows = rows.min(self.rows);
+        let mut reader = Reader::new(&self.changes);
+        let mut ranges: Vec<RangeInclusive<u64>> = Vec::new();
+        for (index, block) in self.blocks.iter().enumerate() {
+            let from = index as u64 * BLOCK_LEN;
+            if from >= rows {
+                break;
+            }
+            if !block.overlaps(time) {
+                continue;
+            }
+            let stamps = self.stamps(&mut reader, index);
+            for (row, stamp) in (from..rows).zip(&stamps) {
+                if !time.contains(stamp) {
+                    continue;
+                }
+                match ranges.last_mut() {
+                    Some(range) if *range.end() + 1 == row => *range = *range.start()..=row,
+                    _ => ranges.push(row..=row),
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Returns the first row at or after the given time, considering the first `rows`
+    /// rows only. Times after the end of the session resolve to the last row with a
+    /// timestamp.
+    pub fn row_at(&self, timestamp: u64, rows: u64) -> Option<u64> {
+        let rows = rows.min(self.rows);
+        let mut reader = Reader::new(&self.changes);
+        for (index, block) in self.blocks.iter().enumerate() {
+            let from = index as u64 * BLOCK_LEN;
+            if from >= rows {
+                break;
+            }
+            if block.min > block.max || block.max < timestamp {
+                continue;
+            }
+            let stamps = self.stamps(&mut reader, index);
+            let visible = &stamps[..stamps.len().min((rows - from) as usize)];
+            if let Some(offset) = visible
+                .iter()
+                .position(|stamp| *stamp != NO_TIMESTAMP && *stamp >= timestamp)
+            {
+                return Some(from + offset as u64);
+            }
+        }
+        // Rows after the first row with a timestamp inherit one, so the last row has a
+        // timestamp unless none of the rows has.
+        rows.checked_sub(1)
+            .filter(|row| self.timestamp(*row).is_some())
+    }
+
+    /// Removes the persisted timestamps.
+    pub fn remove(&mut self) -> io::Result<()> {
+        match &self.changes {
+            Changes::File(path) if path.exists() => fs::remove_file(path),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the timestamps of the rows of the block.
+    fn stamps(&self, reader: &mut Reader<'_>, index: usize) -> Vec<u64> {
+        let Some(block) = self.blocks.get(index) else {
+            return Vec::new();
+        };
+        let from = index as u64 * BLOCK_LEN;
+        let to = (from + BLOCK_LEN).min(self.rows);
+        let end = self
+            .blocks
+            .get(index + 1)
+            .map_or(self.changes_len, |next| next.changes);
+        let changes = match reader.read(block.changes..end) {
+            Ok(changes) => changes,
+            Err(err) => {
+                log::warn!("Fail to read timestamps of rows {from}..{to}: {err}");
+                return vec![NO_TIMESTAMP; (to - from) as usize];
+            }
+        };
+        let mut changes = changes.into_iter().peekable();
+        let mut stamp = block.start;
+        (from..to)
+            .map(|row| {
+                if let Some(change) = changes.next_if(|change| change.row == row) {
+                    stamp = change.timestamp;
+                }
+                stamp
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BLOCK_LEN, CHANGE_BYTES, TimeIndex};
+
+    #[test]
+    fn rows_without_timestamps_inherit_the_previous_one() {
+        let mut index = TimeIndex::default();
+        index
+            .append([None, Some(100), None, Some(300), None])
+            .unwrap();
+
+        assert_eq!(index.len(), 5);
+        assert_eq!(index.bounds(), Some(100..=300));
+        assert_eq!(index.rows(&(100..=200), 5), vec![1..=2]);
+        assert_eq!(index.rows(&(300..=300), 5), vec![3..=4]);
+        assert_eq!(index.rows(&(300..=300), 4), vec![3..=3]);
+    }
+
+    #[test]
+    fn snapshots_keep_their_rows() {
+        let mut index = TimeIndex::default();
+        index.append([Some(10), Some(20)]).unwrap();
+        let snapshot = index.clone();
+        index.append([Some(30)]).unwrap();
+
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.bounds(), Some(10..=20));
+        assert_eq!(snapshot.timestamp(2), None);
+        assert_eq!(index.timestamp(2), Some(30));
+    }
+
+    #[test]
+    fn timestamp_of_rows_before_the_first_timestamp_is_none() {
+        let mut index = TimeIndex::default();
+        index.append([None, Some(100), None]).unwrap();
+
+        assert_eq!(index.timestamp(0), None);
+        assert_eq!(index.timestamp(1), Some(100));
+        assert_eq!(index.timestamp(2), Some(100));
+        assert_eq!(index.timestamp(3), None);
+    }
+
+    #[test]
+    fn rows_are_merged_into_ranges_across_blocks() {
+        let mut index = TimeIndex::default();
+        index.append((0..BLOCK_LEN * 3).map(Some)).unwrap();
+        let from = BLOCK_LEN - 10;
+        let to = BLOCK_LEN * 2 + 10;
+
+        assert_eq!(index.rows(&(from..=to), index.len()), vec![from..=to]);
+    }
+
+    #[test]
+    fn unordered_timestamps_produce_separate_ranges() {
+        let mut index = TimeIndex::default();
+        index
+            .append([Some(10), Some(20), Some(5), Some(15), Some(30)])
+            .unwrap();
+
+        assert_eq!(index.rows(&(10..=20), 5), vec![0..=1, 3..=3]);
+        assert_eq!(index.bounds(), Some(5..=30));
+    }
+
+    #[test]
+    fn row_at_finds_the_first_row_reaching_the_time() {
+        let mut index = TimeIndex::default();
+        assert_eq!(index.row_at(0, 0), None);
+
+        index
+            .append([None, Some(10), Some(20), None, Some(40)])
+            .unwrap();
+
+        assert_eq!(index.row_at(0, 5), Some(1));
+        assert_eq!(index.row_at(15, 5), Some(2));
+        assert_eq!(index.row_at(40, 5), Some(4));
+        assert_eq!(index.row_at(50, 5), Some(4));
+        assert_eq!(index.row_at(50, 3), Some(2));
+    }
+
+    #[test]
+    fn persisted_timestamps_are_read_per_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.time");
+        let mut index = TimeIndex::new(Some(path.clone()));
+        // Every tenth row has a timestamp of its own.
+        let timestamps: Vec<Option<u64>> = (0..BLOCK_LEN * 3)
+            .map(|row| (row % 10 == 0).then_some(row * 100))
+            .collect();
+        index.append(timestamps[..100].iter().copied()).unwrap();
+        index.append(timestamps[100..].iter().copied()).unwrap();
+
+        let changes = (BLOCK_LEN * 3).div_ceil(10);
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            changes * CHANGE_BYTES as u64
+        );
+        assert_eq!(
+            index.timestamp(BLOCK_LEN + 5),
+            Some((BLOCK_LEN / 10) * 1000)
+        );
+        assert_eq!(index.rows(&(2000..=2999), index.len()), vec![20..=29]);
+        assert_eq!(index.row_at(BLOCK_LEN * 150, index.len()), Some(1540));
+        assert_eq!(
+            index.row_at(u64::MAX - 1, index.len()),
+            Some(index.len() - 1)
+        );
+
+        index.remove().unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -1,15 +1,29 @@
 //! Include functionalities for tailing files to send notifications once new
 //! data are written to the file.
+//!
+//! Tracking is aware of log rotations: Replacing the file (e.g. `logrotate` renaming it and
+//! creating a new one) or truncating it (e.g. `copytruncate`) are reported as updates as well,
+//! while [`RotatingFile`] continues reading with the new content of the file.
 
+mod rotating;
+
+pub use rotating::{LinkedFile, RotatingFile, Rotation};
+
+use rotating::{FINGERPRINT_LEN, FileId};
 use std::path::Path;
 use thiserror::Error as ThisError;
 use tokio::{
-    sync::mpsc::Sender,
-    time::{Duration, timeout},
+    select,
+    sync::mpsc::{Sender, error::TrySendError},
+    time::{Duration, Instant, Interval, MissedTickBehavior, interval_at},
 };
 use tokio_util::sync::CancellationToken;
 
+/// Interval of checking the file when changes notifications aren't available.
 const TRACKING_INTERVAL_MS: u64 = 1000;
+/// Interval of checking the file as fallback for missed changes notifications.
+#[cfg(target_os = "linux")]
+const FALLBACK_TRACKING_INTERVAL_MS: u64 = 5000;
 
 #[derive(ThisError, Debug)]
 pub enum Error {
@@ -19,42 +33,330 @@ pub enum Error {
     Channel(String),
 }
 
+/// Defines how rotated and truncated files are handled while tailing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TailOptions {
+    /// Keep following the file after it has been rotated or truncated. Tracking ends with
+    /// an error once the file is rotated or truncated otherwise.
+    pub follow_rotation: bool,
+    /// Read the data written to the rotated file before switching to the new file.
+    pub include_rotated_tail: bool,
+}
+
+impl Default for TailOptions {
+    fn default() -> Self {
+        Self {
+            follow_rotation: true,
+            include_rotated_tail: true,
+        }
+    }
+}
+
+impl TailOptions {
+    /// Options for sources which can't continue reading from a replaced or truncated file.
+    pub fn no_rotation() -> Self {
+        Self {
+            follow_rotation: false,
+            include_rotated_tail: false,
+        }
+    }
+}
+
+/// Tracks the file at the given path, sending an update signal once the file grows, is
+/// rotated or truncated.
 pub async fn track(
     path: &Path,
+    options: TailOptions,
     tx_update: Sender<Result<(), Error>>,
     shutdown: CancellationToken,
 ) -> Result<(), Error> {
-    use tokio::fs;
-    let mut size = fs::metadata(path)
+    let mut state = FileState::read(path)
         .await
-        .map(|md| md.len())
-        .map_err(|e| Error::Io(e.to_string()))?;
+        .map_err(|e| Error::Io(e.to_string()))?
+        .ok_or_else(|| Error::Io(format!("File {} doesn't exist", path.display())))?;
+    let mut triggers = Triggers::new(path);
     loop {
-        match timeout(
-            Duration::from_millis(TRACKING_INTERVAL_MS),
-            shutdown.cancelled(),
-        )
-        .await
-        {
-            Ok(_) => break,
-            Err(_) => {
-                let updated = fs::metadata(path)
-                    .await
-                    .map(|md| md.len())
-                    .map_err(|e| Error::Io(e.to_string()))?;
-                if updated != size {
-                    let truncated = size > updated;
-                    if truncated {
-                        log::info!("File has been truncated. Path: {}", path.display());
-                        return Err(Error::Io(String::from("File has been truncated")));
-                    }
-                    size = updated;
-                    if let Err(err) = tx_update.send(Ok(())).await {
-                        return Err(Error::Channel(format!("Fail to send update signal: {err}")));
-                    }
-                }
+        select! {
+            _ = shutdown.cancelled() => break,
+            _ = triggers.next() => {}
+        }
+        let Some(updated) = FileState::read(path)
+            .await
+            .map_err(|e| Error::Io(e.to_string()))?
+        else {
+            // The file has been renamed while rotating and the new one isn't created yet.
+            if !options.follow_rotation {
+                log::info!("File has been removed. Path: {}", path.display());
+                return Err(Error::Io(String::from("File has been removed")));
             }
+            continue;
         };
+        if updated == state {
+            continue;
+        }
+        if updated.id != state.id {
+            log::info!("File has been rotated. Path: {}", path.display());
+            if !options.follow_rotation {
+                return Err(Error::Io(String::from("File has been rotated")));
+            }
+        } else if updated.size < state.size || !updated.head.starts_with(&state.head) {
+            log::info!("File has been truncated. Path: {}", path.display());
+            if !options.follow_rotation {
+                return Err(Error::Io(String::from("File has been truncated")));
+            }
+        }
+        state = updated;
+        // An update which is still pending covers this one too.
+        if let Err(TrySendError::Closed(_)) = tx_update.try_send(Ok(())) {
+            return Err(Error::Channel(String::from(
+                "Fail to send update signal: channel is closed",
+            )));
+        }
     }
     Ok(())
 }
+
+/// Identity, size and first bytes of the tracked file at the moment of the last check.
+#[derive(Debug, PartialEq, Eq)]
+struct FileState {
+    id: FileId,
+    size: u64,
+    /// Beginning of the file, which changes once the file is truncated and written again.
+    head: Vec<u8>,
+}
+
+impl FileState {
+    /// Reads the state of the file, returning `None` if the file doesn't exist.
+    async fn read(path: &Path) -> std::io::Result<Option<Self>> {
+        use tokio::io::AsyncReadExt;
+
+        let not_found = |err: &std::io::Error| err.kind() == std::io::ErrorKind::NotFound;
+        let md = match tokio::fs::metadata(path).await {
+            Ok(md) => md,
+            Err(err) if not_found(&err) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let mut head = Vec::with_capacity(FINGERPRINT_LEN);
+        match tokio::fs::File::open(path).await {
+            Ok(file) => {
+                file.take(FINGERPRINT_LEN as u64)
+                    .read_to_end(&mut head)
+                    .await?;
+            }
+            Err(err) if not_found(&err) => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        Ok(Some(Self {
+            id: FileId::from_metadata(&md),
+            size: md.len(),
+            head,
+        }))
+    }
+}
+
+/// Delivers the moments at which the tracked file must be checked for changes.
+///
+/// On Linux the parent directory of the file is watched with inotify, while polling with a
+/// long interval covers file systems without notifications support. Other platforms poll
+/// the file with [`TRACKING_INTERVAL_MS`].
+struct Triggers {
+    interval: Interval,
+    #[cfg(target_os = "linux")]
+    watcher: Option<(
+        notify::RecommendedWatcher,
+        tokio::sync::mpsc::UnboundedReceiver<()>,
+    )>,
+}
+
+impl Triggers {
+    #[cfg(target_os = "linux")]
+    fn new(path: &Path) -> Self {
+        let watcher = watch(path)
+            .inspect_err(|err| {
+                log::warn!(
+                    "Fail to watch changes of {}; falling back to polling: {err}",
+                    path.display()
+                )
+            })
+            .ok();
+        let interval_ms = if watcher.is_some() {
+            FALLBACK_TRACKING_INTERVAL_MS
+        } else {
+            TRACKING_INTERVAL_MS
+        };
+        Self {
+            interval: polling(interval_ms),
+            watcher,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn new(_path: &Path) -> Self {
+        Self {
+            interval: polling(TRACKING_INTERVAL_MS),
+        }
+    }
+
+    async fn next(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let Some((_, rx_events)) = self.watcher.as_mut() {
+            select! {
+                _ = self.interval.tick() => {}
+                Some(_) = rx_events.recv() => {}
+            }
+            return;
+        }
+        self.interval.tick().await;
+    }
+}
+
+fn polling(interval_ms: u64) -> Interval {
+    let period = Duration::from_millis(interval_ms);
+    let mut interval = interval_at(Instant::now() + period, period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    interval
+}
+
+/// Watches the parent directory of the file, since rotating replaces the file itself,
+/// forwarding the events related to the file name.
+#[cfg(target_os = "linux")]
+fn watch(
+    path: &Path,
+) -> notify::Result<(
+    notify::RecommendedWatcher,
+    tokio::sync::mpsc::UnboundedReceiver<()>,
+)> {
+    use notify::{RecursiveMode, Watcher};
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .ok_or_else(|| notify::Error::path_not_found().add_path(path.to_path_buf()))?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => std::path::PathBuf::from("."),
+    };
+
+    let (tx_events, rx_events) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) => {
+                if event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == Some(file_name.as_os_str()))
+                {
+                    let _ = tx_events.send(());
+                }
+            }
+            Err(err) => log::warn!("Error while watching tailed file: {err}"),
+        })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    Ok((watcher, rx_events))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+    use tokio::{sync::mpsc::channel, time::timeout};
+
+    use super::*;
+
+    const UPDATE_TIMEOUT: Duration = Duration::from_millis(TRACKING_INTERVAL_MS * 10);
+
+    /// Lets the spawned tracker read the initial state of the file before it's changed.
+    async fn tracker_started() {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    fn append(path: &Path, content: &[u8]) {
+        fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(content)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn track_growth_and_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\n").unwrap();
+
+        let (tx_update, mut rx_update) = channel(1);
+        let shutdown = CancellationToken::new();
+        let tracker = tokio::spawn({
+            let path = path.clone();
+            let shutdown = shutdown.clone();
+            async move { track(&path, TailOptions::default(), tx_update, shutdown).await }
+        });
+        tracker_started().await;
+
+        append(&path, b"second\n");
+        let update = timeout(UPDATE_TIMEOUT, rx_update.recv()).await.unwrap();
+        assert!(matches!(update, Some(Ok(()))));
+
+        fs::rename(&path, dir.path().join("app.log.1")).unwrap();
+        fs::write(&path, b"rotated\n").unwrap();
+        let update = timeout(UPDATE_TIMEOUT, rx_update.recv()).await.unwrap();
+        assert!(matches!(update, Some(Ok(()))));
+
+        shutdown.cancel();
+        assert!(tracker.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn truncation_fails_without_following() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\nsecond\n").unwrap();
+
+        let (tx_update, _rx_update) = channel(1);
+        let tracker = tokio::spawn({
+            let path = path.clone();
+            async move {
+                track(
+                    &path,
+                    TailOptions::no_rotation(),
+                    tx_update,
+                    CancellationToken::new(),
+                )
+                .await
+            }
+        });
+        tracker_started().await;
+
+        fs::write(&path, b"").unwrap();
+        let result = timeout(UPDATE_TIMEOUT, tracker).await.unwrap().unwrap();
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn rewritten_head_counts_as_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\n").unwrap();
+
+        let (tx_update, _rx_update) = channel(1);
+        let tracker = tokio::spawn({
+            let path = path.clone();
+            async move {
+                track(
+                    &path,
+                    TailOptions::no_rotation(),
+                    tx_update,
+                    CancellationToken::new(),
+                )
+                .await
+            }
+        });
+        tracker_started().await;
+
+        // Truncated and written past the previous size before the next check.
+        fs::write(&path, b"second line\n").unwrap();
+        let result = timeout(UPDATE_TIMEOUT, tracker).await.unwrap().unwrap();
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+}
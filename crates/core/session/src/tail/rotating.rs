@@ -0,0 +1,387 @@
+use std::{
+    fs::{self, File, Metadata},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use super::TailOptions;
+
+/// Number of bytes at the beginning of a file compared to notice it has been truncated and
+/// written again, which its size alone can't tell (e.g. `copytruncate` followed by writes
+/// within one check).
+pub(crate) const FINGERPRINT_LEN: usize = 64;
+
+/// Reads up to [`FINGERPRINT_LEN`] bytes from the beginning of the reader.
+pub(crate) fn read_head(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(FINGERPRINT_LEN);
+    reader.take(FINGERPRINT_LEN as u64).read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Reads the beginning of the file at the given path without moving any other reader.
+fn read_head_at(path: &Path) -> io::Result<Vec<u8>> {
+    read_head(&mut File::open(path)?)
+}
+
+/// Identifies a file independently of its path, to detect replacing the file while rotating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileId {
+    #[cfg(unix)]
+    dev: u64,
+    #[cfg(unix)]
+    ino: u64,
+    #[cfg(not(unix))]
+    created: Option<std::time::SystemTime>,
+}
+
+impl FileId {
+    #[cfg(unix)]
+    pub(crate) fn from_metadata(md: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            dev: md.dev(),
+            ino: md.ino(),
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn from_metadata(md: &Metadata) -> Self {
+        Self {
+            created: md.created().ok(),
+        }
+    }
+}
+
+/// Reader of a tailed file which keeps reading once the file is rotated or truncated.
+///
+/// Reaching the end of the file, the reader checks the file at the original path:
+/// * Replaced file: Continues reading the new file from its beginning.
+/// * Truncated file: Continues reading from the beginning of the file. Besides shrinking,
+///   truncation is noticed by the beginning of the file differing from the read one.
+///
+/// The remaining content of a rotated file is read before switching to the new file if
+/// [`TailOptions::include_rotated_tail`] is set. Otherwise the reader switches as soon as
+/// the rotation is noticed.
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    id: FileId,
+    /// Position of the reader within the current file.
+    pos: u64,
+    /// First bytes read from the current file.
+    head: Vec<u8>,
+    /// Whether the end of the current file has been reached by the last read.
+    at_end: bool,
+    options: TailOptions,
+}
+
+impl RotatingFile {
+    pub fn open(path: impl Into<PathBuf>, options: TailOptions) -> io::Result<Self> {
+        let path = path.into();
+        let file = File::open(&path)?;
+        let id = FileId::from_metadata(&file.metadata()?);
+        Ok(Self {
+            path,
+            file,
+            id,
+            pos: 0,
+            head: Vec::with_capacity(FINGERPRINT_LEN),
+            at_end: false,
+            options,
+        })
+    }
+
+    /// Switches to the new content of the file if it has been rotated or truncated,
+    /// returning `true` if the reader has been switched.
+    fn follow(&mut self) -> io::Result<bool> {
+        // The file has been renamed and the new one isn't created yet.
+        let Some(md) = self.path_metadata()? else {
+            return Ok(false);
+        };
+        if FileId::from_metadata(&md) != self.id {
+            let file = File::open(&self.path)?;
+            self.id = FileId::from_metadata(&file.metadata()?);
+            self.file = file;
+            self.pos = 0;
+            self.head.clear();
+            log::debug!("Continue reading rotated file {}", self.path.display());
+            return Ok(true);
+        }
+        self.follow_truncation(&md)
+    }
+
+    /// Returns the metadata of the file at the path, or `None` if there is no file.
+    fn path_metadata(&self) -> io::Result<Option<Metadata>> {
+        match fs::metadata(&self.path) {
+            Ok(md) => Ok(Some(md)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Continues reading from the beginning of the current file if it has been truncated,
+    /// returning `true` if the reader has been switched.
+    fn follow_truncation(&mut self, md: &Metadata) -> io::Result<bool> {
+        if md.len() < self.pos || !self.same_head()? {
+            self.file.seek(SeekFrom::Start(0))?;
+            self.pos = 0;
+            self.head.clear();
+            log::debug!("Continue reading truncated file {}", self.path.display());
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Checks whether the file still starts with the bytes read from it.
+    fn same_head(&self) -> io::Result<bool> {
+        if self.head.is_empty() {
+            return Ok(true);
+        }
+        Ok(read_head_at(&self.path)?.starts_with(&self.head))
+    }
+
+    fn read_current(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.file.read(buf)?;
+        // Reads are sequential, so the head is collected while reading the beginning.
+        if self.pos < FINGERPRINT_LEN as u64 {
+            let len = (FINGERPRINT_LEN - self.pos as usize).min(read);
+            self.head.extend_from_slice(&buf[..len]);
+        }
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl Read for RotatingFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.options.follow_rotation {
+            return self.read_current(buf);
+        }
+        if !self.options.include_rotated_tail {
+            self.follow()?;
+        } else if self.at_end
+            && let Some(md) = self.path_metadata()?
+            && FileId::from_metadata(&md) == self.id
+        {
+            // Content written after reaching the end may follow a truncation, which must be
+            // noticed before reading on at the previous position. Rotated files are read
+            // to their end first.
+            self.follow_truncation(&md)?;
+        }
+        let mut read = self.read_current(buf)?;
+        if read == 0 && !buf.is_empty() && self.follow()? {
+            read = self.read_current(buf)?;
+        }
+        self.at_end = read == 0 && !buf.is_empty();
+        Ok(read)
+    }
+}
+
+/// Change of a file read in place, after which it can't be read on.
+#[derive(Debug)]
+pub enum Rotation {
+    /// The file has been replaced by a new one. Holds the replaced file, which keeps the
+    /// content read so far.
+    Replaced(File),
+    /// The file has been truncated, losing the content read so far.
+    Truncated,
+}
+
+/// File read in place by its path, like a linked session file, which notices the file
+/// being rotated or truncated.
+#[derive(Debug)]
+pub struct LinkedFile {
+    path: PathBuf,
+    /// Handle keeping the content of the file accessible once it's replaced.
+    file: File,
+    id: FileId,
+    /// First bytes of the file at the last check.
+    head: Vec<u8>,
+}
+
+impl LinkedFile {
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let mut file = File::open(&path)?;
+        let id = FileId::from_metadata(&file.metadata()?);
+        let head = read_head(&mut file)?;
+        Ok(Self {
+            path,
+            file,
+            id,
+            head,
+        })
+    }
+
+    /// Checks the file at its path once `read` bytes of it have been read. Returns `None`
+    /// while the file only grows, and while it's missing since the rotation isn't done yet.
+    pub fn rotation(&mut self, read: u64) -> io::Result<Option<Rotation>> {
+        let md = match fs::metadata(&self.path) {
+            Ok(md) => md,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        if FileId::from_metadata(&md) != self.id {
+            self.file.seek(SeekFrom::Start(0))?;
+            return Ok(Some(Rotation::Replaced(self.file.try_clone()?)));
+        }
+        if md.len() < read {
+            return Ok(Some(Rotation::Truncated));
+        }
+        let head = read_head_at(&self.path)?;
+        if !head.starts_with(&self.head) {
+            return Ok(Some(Rotation::Truncated));
+        }
+        // The beginning of a short file grows with it.
+        self.head = head;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::OpenOptions, io::Write, path::Path};
+
+    use super::*;
+
+    fn read_available(reader: &mut RotatingFile) -> Vec<u8> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).unwrap();
+        content
+    }
+
+    fn append(path: &Path, content: &[u8]) {
+        OpenOptions::new()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(content)
+            .unwrap();
+    }
+
+    #[test]
+    fn continue_after_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let rotated = dir.path().join("app.log.1");
+        fs::write(&path, b"first\n").unwrap();
+
+        let mut reader = RotatingFile::open(&path, TailOptions::default()).unwrap();
+        assert_eq!(read_available(&mut reader), b"first\n");
+
+        append(&path, b"second\n");
+        fs::rename(&path, &rotated).unwrap();
+        fs::write(&path, b"third\n").unwrap();
+
+        assert_eq!(read_available(&mut reader), b"second\nthird\n");
+    }
+
+    #[test]
+    fn skip_rotated_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let rotated = dir.path().join("app.log.1");
+        fs::write(&path, b"first\n").unwrap();
+
+        let options = TailOptions {
+            follow_rotation: true,
+            include_rotated_tail: false,
+        };
+        let mut reader = RotatingFile::open(&path, options).unwrap();
+        assert_eq!(read_available(&mut reader), b"first\n");
+
+        append(&path, b"second\n");
+        fs::rename(&path, &rotated).unwrap();
+        fs::write(&path, b"third\n").unwrap();
+
+        assert_eq!(read_available(&mut reader), b"third\n");
+    }
+
+    #[test]
+    fn continue_after_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first line\n").unwrap();
+
+        let mut reader = RotatingFile::open(&path, TailOptions::default()).unwrap();
+        assert_eq!(read_available(&mut reader), b"first line\n");
+
+        // Copy-truncate rotation.
+        fs::write(&path, b"").unwrap();
+        append(&path, b"new\n");
+
+        assert_eq!(read_available(&mut reader), b"new\n");
+    }
+
+    #[test]
+    fn continue_after_truncation_and_longer_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\n").unwrap();
+
+        let mut reader = RotatingFile::open(&path, TailOptions::default()).unwrap();
+        assert_eq!(read_available(&mut reader), b"first\n");
+
+        // Copy-truncate rotation followed by writes past the read position before the
+        // reader checks the file again.
+        fs::write(&path, b"").unwrap();
+        append(&path, b"second line\n");
+
+        assert_eq!(read_available(&mut reader), b"second line\n");
+    }
+
+    #[test]
+    fn linked_file_detects_replacement() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\n").unwrap();
+
+        let mut linked = LinkedFile::open(&path).unwrap();
+        append(&path, b"second\n");
+        assert!(linked.rotation(13).unwrap().is_none());
+
+        fs::rename(&path, dir.path().join("app.log.1")).unwrap();
+        assert!(linked.rotation(13).unwrap().is_none());
+
+        fs::write(&path, b"third\n").unwrap();
+        let Some(Rotation::Replaced(mut replaced)) = linked.rotation(13).unwrap() else {
+            panic!("replaced file expected");
+        };
+        let mut content = Vec::new();
+        replaced.read_to_end(&mut content).unwrap();
+        assert_eq!(content, b"first\nsecond\n");
+    }
+
+    #[test]
+    fn linked_file_detects_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\n").unwrap();
+
+        let mut linked = LinkedFile::open(&path).unwrap();
+        fs::write(&path, b"").unwrap();
+        append(&path, b"second line\n");
+
+        assert!(matches!(
+            linked.rotation(6).unwrap(),
+            Some(Rotation::Truncated)
+        ));
+    }
+
+    #[test]
+    fn no_rotation_keeps_current_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, b"first\n").unwrap();
+
+        let mut reader = RotatingFile::open(&path, TailOptions::no_rotation()).unwrap();
+        assert_eq!(read_available(&mut reader), b"first\n");
+
+        fs::rename(&path, dir.path().join("app.log.1")).unwrap();
+        fs::write(&path, b"second\n").unwrap();
+
+        assert!(read_available(&mut reader).is_empty());
+    }
+}
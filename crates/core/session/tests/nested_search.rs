@@ -40,7 +40,7 @@ async fn public_api_returns_named_nested_coordinates() {
         .collect();
     session
         .state
-        .set_matches(Some(matches), None)
+        .set_matches(Some(matches), None, None)
         .await
         .unwrap();
     session.set_bookmarks(vec![2, 4]).await.unwrap();
@@ -57,6 +57,12 @@ async fn public_api_returns_named_nested_coordinates() {
             session_position: 5,
             search_result_index: 2,
             indexed_row_index: 4,
+            matches: vec![stypes::MatchRange {
+                filter: 0,
+                group: 0,
+                start: 6,
+                end: 12,
+            }],
         })
     );
 
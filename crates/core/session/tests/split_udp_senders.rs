@@ -0,0 +1,74 @@
+//! Observing UDP senders as separate sources while they flood the socket.
+
+use std::{net::UdpSocket, time::Duration};
+
+use session::session::Session;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+/// More datagrams than the queue of one sender takes.
+const DATAGRAMS: usize = 4096;
+
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cancel_while_sender_floods_the_queue() {
+    let bind_addr = {
+        let probe = UdpSocket::bind("127.0.0.1:0").unwrap();
+        probe.local_addr().unwrap()
+    };
+    let (session, mut events) = Session::new(Uuid::new_v4()).await.unwrap();
+    let operation_id = Uuid::new_v4();
+    session
+        .observe(
+            operation_id,
+            stypes::ObserveOptions {
+                origin: stypes::ObserveOrigin::Stream(
+                    Uuid::new_v4().to_string(),
+                    stypes::Transport::UDP(stypes::UDPTransportConfig {
+                        bind_addr: bind_addr.to_string(),
+                        multicast: Vec::new(),
+                        allowed_senders: Vec::new(),
+                        split_senders: true,
+                    }),
+                ),
+                parser: stypes::ParserType::Text(()),
+                recording: None,
+            },
+        )
+        .unwrap();
+    timeout(OPERATION_TIMEOUT, async {
+        while let Some(event) = events.recv().await {
+            if matches!(event, stypes::CallbackEvent::OperationProcessing(id) if id == operation_id)
+            {
+                break;
+            }
+        }
+    })
+    .await
+    .expect("Observing should start");
+
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    for n in 0..DATAGRAMS {
+        sender
+            .send_to(format!("datagram {n}\n").as_bytes(), bind_addr)
+            .unwrap();
+    }
+    session.abort(Uuid::new_v4(), operation_id).unwrap();
+
+    timeout(OPERATION_TIMEOUT, async {
+        while let Some(event) = events.recv().await {
+            match event {
+                stypes::CallbackEvent::OperationDone(done) if done.uuid == operation_id => break,
+                stypes::CallbackEvent::OperationError { error, .. } => {
+                    panic!("Received operation error: {error:#?}")
+                }
+                _ => {}
+            }
+        }
+    })
+    .await
+    .expect("Observing should end once it's cancelled");
+
+    session.stop(Uuid::new_v4()).await.unwrap();
+}
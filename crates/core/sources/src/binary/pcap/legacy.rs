@@ -27,7 +27,7 @@ impl<R: Read> PcapLegacyByteSource<R> {
     }
 }
 
-impl<R: Read + Send + Sync> ByteSource for PcapLegacyByteSource<R> {
+impl<R: Read + Send> ByteSource for PcapLegacyByteSource<R> {
     async fn load(
         &mut self,
         filter: Option<&SourceFilter>,
@@ -27,7 +27,7 @@ impl<R: Read> PcapngByteSource<R> {
     }
 }
 
-impl<R: Read + Send + Sync> ByteSource for PcapngByteSource<R> {
+impl<R: Read + Send> ByteSource for PcapngByteSource<R> {
     async fn load(
         &mut self,
         filter: Option<&SourceFilter>,
@@ -0,0 +1,273 @@
+//! CAN frames in the layout of the SocketCAN structs `can_frame` and `canfd_frame`, along
+//! with their textual representation used by the `can-utils` tools.
+
+use std::{fmt::Write as _, time::Duration};
+
+/// Size of classic CAN frames (`struct can_frame`).
+pub const CAN_MTU: usize = 16;
+/// Size of CAN FD frames (`struct canfd_frame`).
+pub const CANFD_MTU: usize = 72;
+
+/// Offset of the payload in both frame structs.
+const DATA_OFFSET: usize = 8;
+
+const CAN_MAX_DLEN: usize = 8;
+const CANFD_MAX_DLEN: usize = 64;
+
+/// Payload lengths which can be encoded in the DLC of CAN FD frames.
+const CANFD_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+pub const CAN_EFF_FLAG: u32 = 0x8000_0000;
+pub const CAN_RTR_FLAG: u32 = 0x4000_0000;
+pub const CAN_ERR_FLAG: u32 = 0x2000_0000;
+pub const CAN_SFF_MASK: u32 = 0x0000_07FF;
+pub const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+pub const CAN_ERR_MASK: u32 = 0x1FFF_FFFF;
+
+/// Error classes encoded in the ID of error frames, as defined in `linux/can/error.h`.
+const ERROR_CLASSES: [(u32, &str); 9] = [
+    (0x001, "TX timeout"),
+    (0x002, "lost arbitration"),
+    (0x004, "controller problem"),
+    (0x008, "protocol violation"),
+    (0x010, "transceiver status"),
+    (0x020, "no ACK"),
+    (0x040, "bus-off"),
+    (0x080, "bus error"),
+    (0x100, "controller restarted"),
+];
+
+/// A classic or CAN FD frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanFrame {
+    /// The ID including the EFF, RTR and ERR flags.
+    pub can_id: u32,
+    pub data: Vec<u8>,
+    /// The flags of CAN FD frames (e.g. bit rate switch), or `None` for classic frames.
+    pub fd_flags: Option<u8>,
+}
+
+impl CanFrame {
+    /// Reads the frame from the bytes received from a raw CAN socket. Returns `None` if the
+    /// size doesn't match any of the frame structs.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (max_len, fd_flags) = match bytes.len() {
+            CAN_MTU => (CAN_MAX_DLEN, None),
+            CANFD_MTU => (CANFD_MAX_DLEN, Some(bytes[5])),
+            _ => return None,
+        };
+        let can_id = u32::from_ne_bytes(bytes[..4].try_into().ok()?);
+        let len = usize::from(bytes[4]).min(max_len);
+
+        Some(Self {
+            can_id,
+            data: bytes[DATA_OFFSET..DATA_OFFSET + len].to_vec(),
+            fd_flags,
+        })
+    }
+
+    /// Returns the bytes of the frame struct to be written to a raw CAN socket.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mtu = if self.fd_flags.is_some() {
+            CANFD_MTU
+        } else {
+            CAN_MTU
+        };
+        let mut bytes = vec![0u8; mtu];
+        bytes[..4].copy_from_slice(&self.can_id.to_ne_bytes());
+        bytes[4] = self.data.len() as u8;
+        bytes[5] = self.fd_flags.unwrap_or_default();
+        bytes[DATA_OFFSET..DATA_OFFSET + self.data.len()].copy_from_slice(&self.data);
+        bytes
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.can_id & CAN_ERR_FLAG != 0
+    }
+
+    /// Parses the frame in the syntax of `cansend`:
+    ///
+    /// * `<id>#<data>` for classic frames, e.g. `123#DEADBEEF` or `1F334455#11.22.33`.
+    /// * `<id>#R` for remote transmission requests.
+    /// * `<id>##<flags><data>` for CAN FD frames, e.g. `123##1AABBCC`.
+    ///
+    /// IDs with 3 hex digits are standard IDs, IDs with 8 hex digits are extended IDs.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (id, rest) = text
+            .split_once('#')
+            .ok_or_else(|| format!("Frame '{text}' must be given as ID#DATA"))?;
+        let id_value = u32::from_str_radix(id, 16).map_err(|_| format!("Invalid CAN ID '{id}'"))?;
+        let can_id = match id.len() {
+            3 if id_value <= CAN_SFF_MASK => id_value,
+            8 if id_value <= CAN_EFF_MASK => id_value | CAN_EFF_FLAG,
+            _ => {
+                return Err(format!(
+                    "CAN ID '{id}' must have 3 (standard) or 8 (extended) hex digits"
+                ));
+            }
+        };
+
+        if let Some(fd) = rest.strip_prefix('#') {
+            let mut chars = fd.chars();
+            let flags = chars
+                .next()
+                .and_then(|flags| flags.to_digit(16))
+                .ok_or_else(|| String::from("CAN FD frames need the flags after '##'"))?;
+            let data = parse_data(chars.as_str())?;
+            if !CANFD_LENGTHS.contains(&data.len()) {
+                return Err(format!(
+                    "CAN FD frames can't have {} data bytes. Valid lengths are {CANFD_LENGTHS:?}",
+                    data.len()
+                ));
+            }
+            return Ok(Self {
+                can_id,
+                data,
+                fd_flags: Some(flags as u8),
+            });
+        }
+
+        if rest.eq_ignore_ascii_case("r") {
+            return Ok(Self {
+                can_id: can_id | CAN_RTR_FLAG,
+                data: Vec::new(),
+                fd_flags: None,
+            });
+        }
+
+        let data = parse_data(rest)?;
+        if data.len() > CAN_MAX_DLEN {
+            return Err(format!(
+                "Classic CAN frames can't have more than {CAN_MAX_DLEN} data bytes"
+            ));
+        }
+        Ok(Self {
+            can_id,
+            data,
+            fd_flags: None,
+        })
+    }
+
+    /// Writes the frame as line in the log format of `candump`, appending the error classes
+    /// to error frames.
+    pub fn write_log_line(&self, timestamp: Duration, interface: &str, line: &mut String) {
+        let _ = write!(
+            line,
+            "({}.{:06}) {interface} ",
+            timestamp.as_secs(),
+            timestamp.subsec_micros()
+        );
+
+        if self.is_error() {
+            let _ = write!(line, "{:08X}", self.can_id & (CAN_ERR_FLAG | CAN_EFF_MASK));
+        } else if self.can_id & CAN_EFF_FLAG != 0 {
+            let _ = write!(line, "{:08X}", self.can_id & CAN_EFF_MASK);
+        } else {
+            let _ = write!(line, "{:03X}", self.can_id & CAN_SFF_MASK);
+        }
+
+        match self.fd_flags {
+            Some(flags) => {
+                let _ = write!(line, "##{:X}", flags & 0xF);
+            }
+            None if self.can_id & CAN_RTR_FLAG != 0 => line.push_str("#R"),
+            None => line.push('#'),
+        }
+        for byte in &self.data {
+            let _ = write!(line, "{byte:02X}");
+        }
+
+        if self.is_error() {
+            let classes: Vec<_> = ERROR_CLASSES
+                .iter()
+                .filter(|(class, _)| self.can_id & class != 0)
+                .map(|(_, name)| *name)
+                .collect();
+            let _ = write!(line, " ERROR {}", classes.join(", "));
+        }
+        line.push('\n');
+    }
+}
+
+/// Parses hex encoded data, which may be separated into bytes with dots.
+fn parse_data(data: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = data.bytes().filter(|byte| *byte != b'.').collect();
+    if digits.len() % 2 != 0 {
+        return Err(format!("Data '{data}' must consist of complete hex bytes"));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("Invalid hex data '{data}'"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_line(frame: &CanFrame) -> String {
+        let mut line = String::new();
+        frame.write_log_line(Duration::new(1700000000, 123_456_789), "vcan0", &mut line);
+        line
+    }
+
+    #[test]
+    fn parse_and_format_frames() {
+        let cases = [
+            ("123#DEADBEEF", "(1700000000.123456) vcan0 123#DEADBEEF\n"),
+            (
+                "1F334455#11.22.33",
+                "(1700000000.123456) vcan0 1F334455#112233\n",
+            ),
+            ("7FF#", "(1700000000.123456) vcan0 7FF#\n"),
+            ("123#R", "(1700000000.123456) vcan0 123#R\n"),
+            ("123##1AABBCC", "(1700000000.123456) vcan0 123##1AABBCC\n"),
+        ];
+        for (input, expected) in cases {
+            let frame = CanFrame::parse(input).unwrap();
+            assert_eq!(log_line(&frame), expected, "input: {input}");
+        }
+
+        assert!(CanFrame::parse("12#00").is_err());
+        assert!(CanFrame::parse("800#00").is_err());
+        assert!(CanFrame::parse("123#001").is_err());
+        assert!(CanFrame::parse("123#000000000000000000").is_err());
+        assert!(CanFrame::parse("123##1000000000000000000").is_err());
+        assert!(CanFrame::parse("123").is_err());
+    }
+
+    #[test]
+    fn frame_bytes_round_trip() {
+        let classic = CanFrame::parse("1F334455#1122").unwrap();
+        let bytes = classic.to_bytes();
+        assert_eq!(bytes.len(), CAN_MTU);
+        assert_eq!(CanFrame::from_bytes(&bytes), Some(classic));
+
+        let fd = CanFrame::parse("123##1000102030405060708090A0B").unwrap();
+        let bytes = fd.to_bytes();
+        assert_eq!(bytes.len(), CANFD_MTU);
+        assert_eq!(CanFrame::from_bytes(&bytes), Some(fd));
+
+        assert_eq!(CanFrame::from_bytes(&[0; 10]), None);
+    }
+
+    #[test]
+    fn format_error_frames() {
+        let frame = CanFrame {
+            can_id: CAN_ERR_FLAG | 0x040 | 0x004,
+            data: vec![0; 8],
+            fd_flags: None,
+        };
+        assert_eq!(
+            log_line(&frame),
+            "(1700000000.123456) vcan0 20000044#0000000000000000 \
+            ERROR controller problem, bus-off\n"
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Source receiving the frames of Linux SocketCAN interfaces through raw CAN sockets.
+
+use std::{
+    ffi::CString,
+    io, mem,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    ptr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bufread::DeqBuffer;
+use stypes::{CanFilter, CanTimestampSource, SocketCanTransportConfig};
+use thiserror::Error;
+use tokio::io::unix::AsyncFd;
+
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+
+use frame::{CAN_EFF_FLAG, CAN_EFF_MASK, CAN_ERR_MASK, CAN_SFF_MASK, CANFD_MTU, CanFrame};
+
+pub mod frame;
+
+/// Maximum capacity for the buffer of the CAN source.
+const MAX_BUFF_SIZE: usize = 1024 * 1024;
+
+/// Maximum length of a frame formatted as log line.
+const MAX_LINE_LEN: usize = 256;
+
+/// Size of the buffer for the control messages carrying the timestamps.
+const CONTROL_BUFF_LEN: usize = 128;
+
+#[derive(Error, Debug)]
+pub enum CanSourceError {
+    #[error("CAN interface '{0}' doesn't exist")]
+    Interface(String),
+    #[error("Setting up CAN socket failed: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Source receiving the frames of a SocketCAN interface, delivering each frame as line in
+/// the log format of `candump`.
+///
+/// Data sent through SDE is transmitted as frames given in the syntax of `cansend`
+/// (`ID#DATA`), one frame per line.
+pub struct SocketCanSource {
+    buffer: DeqBuffer,
+    socket: AsyncFd<OwnedFd>,
+    interface: String,
+    fd_frames: bool,
+    timestamps: CanTimestampSource,
+    line: String,
+}
+
+impl SocketCanSource {
+    /// Opens a raw CAN socket bound to the interface, applying the filters of the
+    /// configuration in the kernel.
+    pub fn open(config: &SocketCanTransportConfig) -> Result<Self, CanSourceError> {
+        let interface = config.interface.trim();
+        let name =
+            CString::new(interface).map_err(|_| CanSourceError::Interface(interface.into()))?;
+        // SAFETY: The name is a valid nul-terminated string.
+        let if_index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if if_index == 0 {
+            return Err(CanSourceError::Interface(interface.into()));
+        }
+
+        // SAFETY: Creating a socket has no preconditions. The returned descriptor is owned
+        // by `OwnedFd` directly after checking it.
+        let socket = unsafe {
+            let fd = libc::socket(
+                libc::PF_CAN,
+                libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                libc::CAN_RAW,
+            );
+            if fd < 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            OwnedFd::from_raw_fd(fd)
+        };
+
+        if config.fd_frames {
+            let enable: libc::c_int = 1;
+            set_option(
+                &socket,
+                libc::SOL_CAN_RAW,
+                libc::CAN_RAW_FD_FRAMES,
+                &[enable],
+            )?;
+        }
+        // The kernel receives all frames by default.
+        if !config.filters.is_empty() {
+            let filters: Vec<_> = config.filters.iter().map(kernel_filter).collect();
+            set_option(&socket, libc::SOL_CAN_RAW, libc::CAN_RAW_FILTER, &filters)?;
+        }
+        if config.error_frames {
+            set_option(
+                &socket,
+                libc::SOL_CAN_RAW,
+                libc::CAN_RAW_ERR_FILTER,
+                &[CAN_ERR_MASK],
+            )?;
+        }
+        let timestamping = match config.timestamps {
+            CanTimestampSource::Software => {
+                libc::SOF_TIMESTAMPING_RX_SOFTWARE | libc::SOF_TIMESTAMPING_SOFTWARE
+            }
+            CanTimestampSource::Hardware => {
+                libc::SOF_TIMESTAMPING_RX_HARDWARE
+                    | libc::SOF_TIMESTAMPING_RAW_HARDWARE
+                    | libc::SOF_TIMESTAMPING_RX_SOFTWARE
+                    | libc::SOF_TIMESTAMPING_SOFTWARE
+            }
+        };
+        set_option(
+            &socket,
+            libc::SOL_SOCKET,
+            libc::SO_TIMESTAMPING,
+            &[timestamping as libc::c_int],
+        )?;
+
+        // SAFETY: All-zero is a valid address, the family and index are set afterwards.
+        let mut addr: libc::sockaddr_can = unsafe { mem::zeroed() };
+        addr.can_family = libc::AF_CAN as libc::sa_family_t;
+        addr.can_ifindex = if_index as libc::c_int;
+        // SAFETY: The address is valid for the given size.
+        let res = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                ptr::from_ref(&addr).cast(),
+                mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        Ok(Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            socket: AsyncFd::new(socket)?,
+            interface: interface.to_owned(),
+            fd_frames: config.fd_frames,
+            timestamps: config.timestamps,
+            line: String::with_capacity(MAX_LINE_LEN),
+        })
+    }
+
+    /// Transmits the frame, waiting until the socket can accept it.
+    async fn send(&self, frame: &CanFrame) -> Result<(), SourceError> {
+        if frame.fd_flags.is_some() && !self.fd_frames {
+            return Err(SourceError::Setup(String::from(
+                "CAN FD frames can be sent only if CAN FD is enabled for the source",
+            )));
+        }
+        let bytes = frame.to_bytes();
+        loop {
+            let mut guard = self.socket.writable().await.map_err(SourceError::Io)?;
+            let written = guard.try_io(|socket| {
+                // SAFETY: The bytes are valid for their length.
+                let written =
+                    unsafe { libc::write(socket.as_raw_fd(), bytes.as_ptr().cast(), bytes.len()) };
+                if written < 0 {
+                    Err(io::Error::last_os_error())
+                } else {
+                    Ok(written as usize)
+                }
+            });
+            match written {
+                Ok(Ok(written)) if written == bytes.len() => return Ok(()),
+                Ok(Ok(_)) => {
+                    return Err(SourceError::Unrecoverable(String::from(
+                        "CAN frame was written partially",
+                    )));
+                }
+                Ok(Err(err)) => return Err(SourceError::Io(err)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+/// Converts the filter to the kernel struct, matching the frame format of the filter only.
+fn kernel_filter(filter: &CanFilter) -> libc::can_filter {
+    let (can_id, can_mask) = if filter.extended {
+        (
+            (filter.id & CAN_EFF_MASK) | CAN_EFF_FLAG,
+            (filter.mask & CAN_EFF_MASK) | CAN_EFF_FLAG,
+        )
+    } else {
+        (
+            filter.id & CAN_SFF_MASK,
+            (filter.mask & CAN_SFF_MASK) | CAN_EFF_FLAG,
+        )
+    };
+    libc::can_filter { can_id, can_mask }
+}
+
+fn set_option<T>(
+    socket: &OwnedFd,
+    level: libc::c_int,
+    name: libc::c_int,
+    values: &[T],
+) -> io::Result<()> {
+    // SAFETY: The values are valid for the given size.
+    let res = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            name,
+            values.as_ptr().cast(),
+            mem::size_of_val(values) as libc::socklen_t,
+        )
+    };
+    if res < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Receives the next frame along with its timestamp from the non-blocking socket.
+fn receive_frame(
+    socket: &OwnedFd,
+    timestamps: CanTimestampSource,
+) -> io::Result<(Option<CanFrame>, Duration)> {
+    let mut frame = [0u8; CANFD_MTU];
+    let mut iov = libc::iovec {
+        iov_base: frame.as_mut_ptr().cast(),
+        iov_len: frame.len(),
+    };
+    // Use u64 for the alignment of the control messages.
+    let mut control = [0u64; CONTROL_BUFF_LEN / 8];
+    // SAFETY: All-zero is a valid message header, buffers are set afterwards.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+
+    // SAFETY: The message header points to buffers which are valid for their lengths.
+    let received = unsafe { libc::recvmsg(socket.as_raw_fd(), &mut msg, 0) };
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: The control messages have been written by the kernel into the buffer.
+    let timestamp = unsafe { frame_timestamp(&msg, timestamps) }.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    });
+
+    Ok((CanFrame::from_bytes(&frame[..received as usize]), timestamp))
+}
+
+/// Reads the timestamp of the received frame from the control messages.
+///
+/// # Safety
+///
+/// The control buffer of the message must contain the control messages of a `recvmsg` call.
+unsafe fn frame_timestamp(msg: &libc::msghdr, timestamps: CanTimestampSource) -> Option<Duration> {
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(msg) };
+    while !cmsg.is_null() {
+        let header = unsafe { &*cmsg };
+        if header.cmsg_level == libc::SOL_SOCKET && header.cmsg_type == libc::SO_TIMESTAMPING {
+            // The kernel delivers the software, a deprecated and the raw hardware timestamp.
+            let stamps =
+                unsafe { ptr::read_unaligned(libc::CMSG_DATA(cmsg).cast::<[libc::timespec; 3]>()) };
+            let candidates = match timestamps {
+                CanTimestampSource::Software => [stamps[0], stamps[0]],
+                CanTimestampSource::Hardware => [stamps[2], stamps[0]],
+            };
+            return candidates
+                .into_iter()
+                .find(|stamp| stamp.tv_sec != 0 || stamp.tv_nsec != 0)
+                .map(|stamp| Duration::new(stamp.tv_sec as u64, stamp.tv_nsec as u32));
+        }
+        cmsg = unsafe { libc::CMSG_NXTHDR(msg, cmsg) };
+    }
+
+    None
+}
+
+impl ByteSource for SocketCanSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        if self.buffer.write_available() < MAX_LINE_LEN {
+            self.buffer.flush();
+        }
+        if self.buffer.write_available() < MAX_LINE_LEN {
+            // Wait for the parser to consume the buffer before receiving more frames.
+            return Ok(Some(ReloadInfo::new(0, self.len(), 0, None)));
+        }
+
+        let timestamps = self.timestamps;
+        // Implementation is cancel-safe because waiting for the socket to be readable is
+        // cancel-safe and frames are received without awaiting.
+        let (frame, timestamp) = loop {
+            let mut guard = self.socket.readable().await.map_err(SourceError::Io)?;
+            match guard.try_io(|socket| receive_frame(socket.get_ref(), timestamps)) {
+                Ok(Ok((Some(frame), timestamp))) => break (frame, timestamp),
+                Ok(Ok((None, _))) => {
+                    warn!("Received CAN frame with unknown size on {}", self.interface);
+                }
+                Ok(Err(err)) => return Err(SourceError::Io(err)),
+                Err(_would_block) => {}
+            }
+        };
+
+        self.line.clear();
+        frame.write_log_line(timestamp, &self.interface, &mut self.line);
+        let loaded = self.buffer.write_from(self.line.as_bytes());
+
+        Ok(Some(ReloadInfo::new(
+            loaded,
+            self.len(),
+            0,
+            Some(timestamp.as_millis() as u64),
+        )))
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.read_available()
+    }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        let text = match request {
+            stypes::SdeRequest::WriteText(text) => text,
+            stypes::SdeRequest::WriteBytes(_)
+            | stypes::SdeRequest::Replay(_)
+            | stypes::SdeRequest::DltControl(_) => {
+                return Err(SourceError::NotSupported);
+            }
+        };
+        // Parse all frames first, so nothing is sent if any of them is invalid.
+        let frames = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(CanFrame::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(SourceError::Setup)?;
+
+        let mut bytes = 0;
+        for frame in &frames {
+            self.send(frame).await?;
+            bytes += frame.data.len();
+        }
+        Ok(stypes::SdeResponse { bytes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernel_filters_match_frame_format() {
+        let standard = kernel_filter(&CanFilter {
+            id: 0x123,
+            mask: 0x7FF,
+            extended: false,
+        });
+        assert_eq!(standard.can_id, 0x123);
+        assert_eq!(standard.can_mask, 0x7FF | CAN_EFF_FLAG);
+
+        let extended = kernel_filter(&CanFilter {
+            id: 0x1F33_4455,
+            mask: 0xFFFF_FF00,
+            extended: true,
+        });
+        assert_eq!(extended.can_id, 0x1F33_4455 | CAN_EFF_FLAG);
+        assert_eq!(extended.can_mask, 0x1FFF_FF00 | CAN_EFF_FLAG);
+    }
+
+    #[test]
+    fn unknown_interface() {
+        let config = SocketCanTransportConfig {
+            interface: String::from("chipmunk_no_can"),
+            filters: Vec::new(),
+            fd_frames: false,
+            error_frames: false,
+            timestamps: CanTimestampSource::Software,
+        };
+        assert!(matches!(
+            SocketCanSource::open(&config),
+            Err(CanSourceError::Interface(_))
+        ));
+    }
+}
@@ -1,15 +1,33 @@
 use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
 use bufread::DeqBuffer;
-use std::{ffi::OsString, path::PathBuf, process::Stdio};
-use stypes::ShellProfile;
+use std::{
+    ffi::OsString,
+    path::Path,
+    process::{ExitStatus, Stdio},
+};
+use stypes::{ProcessOutputMode, ProcessRestartPolicy, ProcessTransportConfig, ShellProfile};
 use thiserror::Error;
 use tokio::{
-    io::AsyncWriteExt,
-    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    process::{Child, ChildStderr, Command},
     select,
+    time::{Duration, Instant, sleep_until},
 };
 use tokio_stream::StreamExt;
-use tokio_util::codec::{self, FramedRead, LinesCodec};
+use tokio_util::codec::{FramedRead, LinesCodec, LinesCodecError};
+
+/// Capacity of the buffer while delivering lines of the output.
+const LINES_BUFF_SIZE: usize = 8192;
+/// Capacity of the buffer while delivering the raw bytes of the output, which must be able
+/// to hold the biggest messages of binary formats.
+const RAW_BUFF_SIZE: usize = 1024 * 1024;
+/// Maximum count of bytes read from stdout at once in raw mode.
+const RAW_READ_SIZE: usize = 64 * 1024;
+/// Delay before the first restart of the command.
+const RESTART_BACKOFF_MIN: Duration = Duration::from_millis(500);
+/// Maximum delay before restarting the command. Commands running for longer than this
+/// are considered healthy, resetting the delay to [`RESTART_BACKOFF_MIN`].
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
 
 #[derive(Error, Debug)]
 pub enum ProcessError {
@@ -19,112 +37,340 @@ pub enum ProcessError {
     Unrecoverable(String),
 }
 
-pub struct ProcessSource {
-    process: Child,
-    buffer: DeqBuffer,
-    stdout: FramedRead<ChildStdout, LinesCodec>,
-    stderr: FramedRead<ChildStderr, LinesCodec>,
-    stdin: ChildStdin,
+type Reader = Box<dyn AsyncRead + Send + Unpin>;
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Stdout of the running command.
+enum Stdout {
+    Lines(FramedRead<Reader, LinesCodec>),
+    Raw { reader: Reader, tmp_buffer: Vec<u8> },
 }
 
-impl Drop for ProcessSource {
-    fn drop(&mut self) {
-        let is_process_alive = self.process.try_wait().is_ok_and(|state| state.is_none());
-        if is_process_alive {
-            let _ = self.process.start_kill().inspect_err(|err| {
-                warn!("Fail to kill child process: {err}");
-            });
+impl Stdout {
+    fn new(reader: Reader, output: ProcessOutputMode) -> Self {
+        match output {
+            ProcessOutputMode::Lines => Self::Lines(FramedRead::new(reader, LinesCodec::default())),
+            ProcessOutputMode::Raw => Self::Raw {
+                reader,
+                tmp_buffer: vec![0u8; RAW_READ_SIZE],
+            },
+        }
+    }
+
+    /// Reads the next line or chunk of bytes into the buffer, returning the count of the
+    /// stored bytes or `None` once stdout is closed.
+    async fn read_into(&mut self, buffer: &mut DeqBuffer) -> Result<Option<usize>, SourceError> {
+        match self {
+            Stdout::Lines(lines) => match lines.next().await {
+                Some(Ok(line)) => Ok(Some(write_line(buffer, &line))),
+                Some(Err(err)) => Err(SourceError::Unrecoverable(format!("{err}"))),
+                None => Ok(None),
+            },
+            Stdout::Raw { reader, tmp_buffer } => {
+                let len = reader.read(tmp_buffer).await.map_err(|err| {
+                    SourceError::Unrecoverable(format!("Reading process output failed: {err}"))
+                })?;
+                if len == 0 {
+                    return Ok(None);
+                }
+                let added = buffer.write_from(&tmp_buffer[..len]);
+                if added < len {
+                    return Err(SourceError::Unrecoverable(format!(
+                        "Internal buffer maximum capcity reached.\
+                        Read from process: {len}, Copied to buffer: {added}"
+                    )));
+                }
+                Ok(Some(len))
+            }
         }
     }
 }
 
-impl ProcessSource {
-    #[cfg(windows)]
-    fn spawn(
-        command: &str,
-        cwd: PathBuf,
-        shell: Option<ShellProfile>,
-    ) -> Result<Child, ProcessError> {
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-
-        let (bin, cmd_arg) = shell
-            .map(|sh| (sh.path.as_os_str().to_os_string(), sh.shell.command_arg()))
-            // Check if built-in PowerShell is installed in use it as default shell.
-            .or_else(|| {
-                shell_tools::get_win_powershell().map(|p| {
-                    (
-                        p.as_os_str().to_os_string(),
-                        stypes::ShellType::Pwsh.command_arg(),
-                    )
-                })
-            })
-            .unwrap_or_else(|| (OsString::from("cmd"), "/C"));
+/// Checks if the buffer can take the next chunk of raw bytes, flushing it if needed.
+fn can_load_raw(buffer: &mut DeqBuffer) -> bool {
+    if buffer.write_available() < RAW_READ_SIZE {
+        buffer.flush();
+    }
+    buffer.write_available() >= RAW_READ_SIZE
+}
 
-        Command::new(bin)
-            .arg(cmd_arg)
-            .arg(command)
-            .current_dir(OsString::from(cwd))
-            .creation_flags(CREATE_NO_WINDOW)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .stdin(Stdio::piped())
-            .kill_on_drop(true)
-            .spawn()
-            .map_err(|e| ProcessError::Setup(format!("{e}")))
-    }
-
-    #[cfg(not(windows))]
-    fn spawn(
-        command: &str,
-        cwd: PathBuf,
-        shell: Option<ShellProfile>,
-    ) -> Result<Child, ProcessError> {
-        let (bin, cmd_arg) = shell
-            .map(|sh| (sh.path.as_os_str().to_os_string(), sh.shell.command_arg()))
-            .unwrap_or_else(|| (OsString::from("sh"), "-c"));
-        Command::new(bin)
-            .arg(cmd_arg)
-            .arg(command)
-            .current_dir(OsString::from(cwd))
+fn write_line(buffer: &mut DeqBuffer, line: &str) -> usize {
+    buffer.write_from(line.as_bytes());
+    buffer.write_from(b"\n");
+    line.len() + 1
+}
+
+/// Command started by the source together with the handles of its input and output.
+struct Running {
+    process: Child,
+    stdout: Stdout,
+    /// Lines of stderr. Missing once stderr is closed or if stderr is attached to the
+    /// pseudo-terminal together with stdout.
+    stderr: Option<FramedRead<ChildStderr, LinesCodec>>,
+    stdin: Writer,
+    started: Instant,
+}
+
+impl Running {
+    fn spawn(config: &ProcessTransportConfig) -> Result<Self, ProcessError> {
+        let mut command = shell_command(&config.command, &config.cwd, config.shell.as_ref());
+        command.envs(config.envs.iter().map(|(name, value)| (name, value)));
+        command.kill_on_drop(true);
+        if config.pty {
+            Self::spawn_pty(command, config.output)
+        } else {
+            Self::spawn_piped(command, config.output)
+        }
+    }
+
+    fn spawn_piped(mut command: Command, output: ProcessOutputMode) -> Result<Self, ProcessError> {
+        let mut process = command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .stdin(Stdio::piped())
-            .kill_on_drop(true)
             .spawn()
-            .map_err(|e| ProcessError::Setup(format!("{e}")))
-    }
-
-    pub async fn new(
-        command: String,
-        cwd: PathBuf,
-        shell: Option<ShellProfile>,
-    ) -> Result<Self, ProcessError> {
-        let mut process = ProcessSource::spawn(&command, cwd, shell)?;
-        let stdout = codec::FramedRead::new(
-            process
-                .stdout
-                .take()
-                .ok_or_else(|| ProcessError::Setup(String::from("Fail to get stdout handle")))?,
-            LinesCodec::default(),
-        );
-        let stderr = codec::FramedRead::new(
-            process
-                .stderr
-                .take()
-                .ok_or_else(|| ProcessError::Setup(String::from("Fail to get stderr handle")))?,
-            LinesCodec::default(),
-        );
+            .map_err(|e| ProcessError::Setup(format!("{e}")))?;
+        let stdout = process
+            .stdout
+            .take()
+            .ok_or_else(|| ProcessError::Setup(String::from("Fail to get stdout handle")))?;
+        let stderr = process
+            .stderr
+            .take()
+            .ok_or_else(|| ProcessError::Setup(String::from("Fail to get stderr handle")))?;
         let stdin = process
             .stdin
             .take()
             .ok_or_else(|| ProcessError::Setup(String::from("Fail to get stdin handle")))?;
         Ok(Self {
             process,
-            buffer: DeqBuffer::new(8192),
-            stdout,
-            stderr,
-            stdin,
+            stdout: Stdout::new(Box::new(stdout), output),
+            stderr: Some(FramedRead::new(stderr, LinesCodec::default())),
+            stdin: Box::new(stdin),
+            started: Instant::now(),
+        })
+    }
+
+    #[cfg(unix)]
+    fn spawn_pty(mut command: Command, output: ProcessOutputMode) -> Result<Self, ProcessError> {
+        use nix::{
+            pty::{Winsize, openpty},
+            sys::termios::{SetArg, Termios, cfmakeraw, tcgetattr, tcsetattr},
+        };
+
+        let pty_err = |e: nix::Error| ProcessError::Setup(format!("Pseudo-terminal error: {e}"));
+        let io_err = |e: std::io::Error| ProcessError::Setup(format!("Pseudo-terminal error: {e}"));
+
+        // Wide terminal to avoid tools shortening their lines to the terminal width.
+        let size = Winsize {
+            ws_row: 50,
+            ws_col: 500,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        let pty = openpty(&size, None::<&Termios>).map_err(pty_err)?;
+        if output == ProcessOutputMode::Raw {
+            // Terminal processing like converting line feeds would corrupt binary output.
+            let mut termios = tcgetattr(&pty.slave).map_err(pty_err)?;
+            cfmakeraw(&mut termios);
+            tcsetattr(&pty.slave, SetArg::TCSANOW, &termios).map_err(pty_err)?;
+        }
+
+        let stdout = pty.slave.try_clone().map_err(io_err)?;
+        let stderr = pty.slave.try_clone().map_err(io_err)?;
+        let process = command
+            .stdin(Stdio::from(pty.slave))
+            .stdout(Stdio::from(stdout))
+            .stderr(Stdio::from(stderr))
+            .spawn()
+            .map_err(|e| ProcessError::Setup(format!("{e}")))?;
+        // Close the terminal handles of the parent, so reading ends once the command exits.
+        drop(command);
+
+        let writer = std::fs::File::from(pty.master.try_clone().map_err(io_err)?);
+        let reader = std::fs::File::from(pty.master);
+        Ok(Self {
+            process,
+            stdout: Stdout::new(
+                Box::new(PtyReader(tokio::fs::File::from_std(reader))),
+                output,
+            ),
+            stderr: None,
+            stdin: Box::new(tokio::fs::File::from_std(writer)),
+            started: Instant::now(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn spawn_pty(_command: Command, _output: ProcessOutputMode) -> Result<Self, ProcessError> {
+        Err(ProcessError::Setup(String::from(
+            "Running commands in a pseudo-terminal is supported on Unix only",
+        )))
+    }
+
+    /// Reads the next output of the command into the buffer, returning the count of the
+    /// stored bytes or `None` once stdout is closed.
+    ///
+    /// Lines of stderr are delivered together with stdout in lines mode. In raw mode they
+    /// are logged only, since they would corrupt the binary output.
+    async fn read_into(&mut self, buffer: &mut DeqBuffer) -> Result<Option<usize>, SourceError> {
+        // Implementation is cancel-safe since reading lines and bytes is cancel-safe and no
+        // data are gathered between the await calls.
+        loop {
+            select! {
+                line = next_line(&mut self.stderr) => match line {
+                    Some(Ok(line)) => match self.stdout {
+                        Stdout::Lines(_) => return Ok(Some(write_line(buffer, &line))),
+                        Stdout::Raw { .. } => debug!("Process stderr: {line}"),
+                    },
+                    Some(Err(err)) => return Err(SourceError::Unrecoverable(format!("{err}"))),
+                    None => self.stderr = None,
+                },
+                read = self.stdout.read_into(buffer) => return read,
+            }
+        }
+    }
+}
+
+/// Returns the next line of stderr, staying pending if stderr isn't available.
+async fn next_line(
+    stderr: &mut Option<FramedRead<ChildStderr, LinesCodec>>,
+) -> Option<Result<String, LinesCodecError>> {
+    match stderr {
+        Some(stderr) => stderr.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Master side of a pseudo-terminal, reaching its end once the command exits.
+#[cfg(unix)]
+struct PtyReader(tokio::fs::File);
+
+#[cfg(unix)]
+impl AsyncRead for PtyReader {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match std::pin::Pin::new(&mut self.0).poll_read(cx, buf) {
+            // Reading fails with EIO once all handles of the terminal are closed on Linux.
+            std::task::Poll::Ready(Err(err))
+                if err.raw_os_error() == Some(nix::errno::Errno::EIO as i32) =>
+            {
+                std::task::Poll::Ready(Ok(()))
+            }
+            poll => poll,
+        }
+    }
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str, cwd: &Path, shell: Option<&ShellProfile>) -> Command {
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let (bin, cmd_arg) = shell
+        .map(|sh| (sh.path.as_os_str().to_os_string(), sh.shell.command_arg()))
+        // Check if built-in PowerShell is installed in use it as default shell.
+        .or_else(|| {
+            shell_tools::get_win_powershell().map(|p| {
+                (
+                    p.as_os_str().to_os_string(),
+                    stypes::ShellType::Pwsh.command_arg(),
+                )
+            })
         })
+        .unwrap_or_else(|| (OsString::from("cmd"), "/C"));
+
+    let mut cmd = Command::new(bin);
+    cmd.arg(cmd_arg)
+        .arg(command)
+        .current_dir(cwd)
+        .creation_flags(CREATE_NO_WINDOW);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str, cwd: &Path, shell: Option<&ShellProfile>) -> Command {
+    let (bin, cmd_arg) = shell
+        .map(|sh| (sh.path.as_os_str().to_os_string(), sh.shell.command_arg()))
+        .unwrap_or_else(|| (OsString::from("sh"), "-c"));
+    let mut cmd = Command::new(bin);
+    cmd.arg(cmd_arg).arg(command).current_dir(cwd);
+    cmd
+}
+
+/// Source delivering the output of a command.
+///
+/// The output is delivered either as lines of stdout and stderr or as the unchanged bytes
+/// of stdout, based on [`ProcessOutputMode`]. The command is started again once it exits
+/// based on [`ProcessRestartPolicy`], delaying each consecutive restart with a growing
+/// backoff.
+pub struct ProcessSource {
+    config: ProcessTransportConfig,
+    buffer: DeqBuffer,
+    /// The running command, missing while waiting to restart it.
+    running: Option<Running>,
+    /// Moment of starting the command again once it has exited.
+    restart_at: Option<Instant>,
+    /// Delay of the next restart.
+    backoff: Duration,
+}
+
+impl Drop for ProcessSource {
+    fn drop(&mut self) {
+        let Some(running) = self.running.as_mut() else {
+            return;
+        };
+        let is_process_alive = running
+            .process
+            .try_wait()
+            .is_ok_and(|state| state.is_none());
+        if is_process_alive {
+            let _ = running.process.start_kill().inspect_err(|err| {
+                warn!("Fail to kill child process: {err}");
+            });
+        }
+    }
+}
+
+impl ProcessSource {
+    pub async fn new(config: ProcessTransportConfig) -> Result<Self, ProcessError> {
+        let running = Running::spawn(&config)?;
+        let buff_size = match config.output {
+            ProcessOutputMode::Lines => LINES_BUFF_SIZE,
+            ProcessOutputMode::Raw => RAW_BUFF_SIZE,
+        };
+        Ok(Self {
+            config,
+            buffer: DeqBuffer::new(buff_size),
+            running: Some(running),
+            restart_at: None,
+            backoff: RESTART_BACKOFF_MIN,
+        })
+    }
+
+    /// Schedules starting the command again after the exit with the given status, returning
+    /// `false` if the command mustn't be restarted.
+    fn schedule_restart(&mut self, status: ExitStatus, uptime: Duration) -> bool {
+        let restart = match self.config.restart {
+            ProcessRestartPolicy::Never => false,
+            ProcessRestartPolicy::OnFailure => !status.success(),
+            ProcessRestartPolicy::Always => true,
+        };
+        if !restart {
+            return false;
+        }
+        if uptime >= RESTART_BACKOFF_MAX {
+            self.backoff = RESTART_BACKOFF_MIN;
+        }
+        info!(
+            "Command exited with {status}. Restarting in {} ms",
+            self.backoff.as_millis()
+        );
+        self.restart_at = Some(Instant::now() + self.backoff);
+        self.backoff = (self.backoff * 2).min(RESTART_BACKOFF_MAX);
+        true
     }
 }
 
@@ -133,40 +379,39 @@ impl ByteSource for ProcessSource {
         &mut self,
         _filter: Option<&SourceFilter>,
     ) -> Result<Option<ReloadInfo>, SourceError> {
-        let mut closing = false;
-        let mut output;
-        // Implementation is cancel-safe here because there is no data gathered between to await
-        // calls. The only multiple await calls here, are actually while closing where there is
-        // no data to lose anyway.
+        // Implementation is cancel-safe: Reading the output is cancel-safe, waiting for the
+        // exit of the command can be repeated and the moment of restart is kept in the state.
         loop {
-            if !closing {
-                output = select! {
-                    res = self.stdout.next() => res,
-                    res = self.stderr.next() => {
-                        if res.is_none() {
-                            closing = true;
-                        }
-                        res
-                    },
+            let Some(running) = self.running.as_mut() else {
+                let Some(restart_at) = self.restart_at else {
+                    return Ok(None);
                 };
-                if !closing {
-                    break;
-                }
-            } else {
-                output = self.stdout.next().await;
-                break;
+                sleep_until(restart_at).await;
+                let running = Running::spawn(&self.config).map_err(|err| {
+                    SourceError::Unrecoverable(format!("Fail to restart command: {err}"))
+                })?;
+                self.running = Some(running);
+                self.restart_at = None;
+                continue;
+            };
+
+            if matches!(running.stdout, Stdout::Raw { .. }) && !can_load_raw(&mut self.buffer) {
+                // Buffer is full and the parser needs to consume the available bytes first.
+                let available_bytes = self.buffer.read_available();
+                return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+            }
+            if let Some(stored) = running.read_into(&mut self.buffer).await? {
+                let available_bytes = self.buffer.read_available();
+                return Ok(Some(ReloadInfo::new(stored, available_bytes, 0, None)));
+            }
+
+            // Stdout is closed.
+            let status = running.process.wait().await.map_err(SourceError::Io)?;
+            let uptime = running.started.elapsed();
+            self.running = None;
+            if !self.schedule_restart(status, uptime) {
+                return Ok(None);
             }
-        }
-        if let Some(Ok(line)) = output {
-            let stored = line.len() + 1;
-            self.buffer.write_from(line.as_bytes());
-            self.buffer.write_from(b"\n");
-            let available_bytes = self.buffer.read_available();
-            Ok(Some(ReloadInfo::new(stored, available_bytes, 0, None)))
-        } else if let Some(Err(err)) = output {
-            Err(SourceError::Unrecoverable(format!("{err}")))
-        } else {
-            Ok(None)
         }
     }
 
@@ -190,11 +435,24 @@ impl ByteSource for ProcessSource {
         &mut self,
         request: stypes::SdeRequest,
     ) -> Result<stypes::SdeResponse, SourceError> {
+        let Some(running) = self.running.as_mut() else {
+            return Err(SourceError::Setup(String::from(
+                "Command isn't running at the moment",
+            )));
+        };
         let bytes = match request {
             stypes::SdeRequest::WriteText(ref str) => str.as_bytes(),
             stypes::SdeRequest::WriteBytes(ref bytes) => bytes,
+            stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => {
+                return Err(SourceError::NotSupported);
+            }
         };
-        self.stdin.write_all(bytes).await.map_err(SourceError::Io)?;
+        running
+            .stdin
+            .write_all(bytes)
+            .await
+            .map_err(SourceError::Io)?;
+        running.stdin.flush().await.map_err(SourceError::Io)?;
         Ok(stypes::SdeResponse { bytes: bytes.len() })
     }
 }
@@ -203,17 +461,42 @@ impl ByteSource for ProcessSource {
 mod tests {
     use super::*;
     use crate::tests::general_source_reload_test;
+    use std::env;
+
+    fn config(command: &str) -> ProcessTransportConfig {
+        ProcessTransportConfig {
+            cwd: env::current_dir().unwrap(),
+            command: command.to_string(),
+            shell: None,
+            envs: Vec::new(),
+            output: ProcessOutputMode::Lines,
+            pty: false,
+            restart: ProcessRestartPolicy::Never,
+        }
+    }
+
+    fn list_command() -> &'static str {
+        if cfg!(windows) { "help" } else { "ls -lsa" }
+    }
+
+    /// Reads the whole output of the source until it ends.
+    async fn read_all(source: &mut ProcessSource) -> Vec<u8> {
+        let mut content = Vec::new();
+        while source
+            .load(None)
+            .await
+            .expect("Reload data from process source failed")
+            .is_some()
+        {
+            content.extend_from_slice(source.current_slice());
+            source.consume(source.current_slice().len());
+        }
+        content
+    }
 
     #[tokio::test]
     async fn test_process() -> Result<(), ProcessError> {
-        use std::env;
-        let mut command = "";
-        if cfg!(windows) {
-            command = "help";
-        } else if cfg!(unix) {
-            command = "ls -lsa";
-        }
-        match ProcessSource::new(command.to_string(), env::current_dir().unwrap(), None).await {
+        match ProcessSource::new(config(list_command())).await {
             Ok(mut process_source) => {
                 while process_source
                     .load(None)
@@ -225,7 +508,9 @@ mod tests {
                     process_source.consume(process_source.current_slice().len());
                 }
                 // By some reasons during test sometimes process stay alive and as result
-                let _ = process_source.process.kill().await;
+                if let Some(running) = process_source.running.as_mut() {
+                    let _ = running.process.kill().await;
+                }
                 Ok(())
             }
             Err(err) => Err(err),
@@ -234,18 +519,64 @@ mod tests {
 
     #[tokio::test]
     async fn test_source_reload() {
-        use std::env;
-        let mut command = "";
-        if cfg!(windows) {
-            command = "help";
-        } else if cfg!(unix) {
-            command = "ls -lsa";
-        }
-        let mut process_source =
-            ProcessSource::new(command.to_string(), env::current_dir().unwrap(), None)
-                .await
-                .unwrap();
+        let mut process_source = ProcessSource::new(config(list_command())).await.unwrap();
 
         general_source_reload_test(&mut process_source).await;
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn raw_output_is_unchanged() {
+        let mut config = config(r"printf 'a\000\001\r\nb\377'; echo err >&2");
+        config.output = ProcessOutputMode::Raw;
+        let mut source = ProcessSource::new(config).await.unwrap();
+
+        assert_eq!(read_all(&mut source).await, b"a\x00\x01\r\nb\xff");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn environment_variables() {
+        let mut config = config("echo \"$CHIPMUNK_TEST_VAR\"");
+        config.envs = vec![(String::from("CHIPMUNK_TEST_VAR"), String::from("value"))];
+        let mut source = ProcessSource::new(config).await.unwrap();
+
+        assert_eq!(read_all(&mut source).await, b"value\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pty_output() {
+        let mut config = config("test -t 1 && echo tty");
+        config.pty = true;
+        let mut source = ProcessSource::new(config).await.unwrap();
+
+        assert_eq!(read_all(&mut source).await, b"tty\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn restart_on_failure() {
+        let mut config = config("echo run; exit 1");
+        config.restart = ProcessRestartPolicy::OnFailure;
+        let mut source = ProcessSource::new(config).await.unwrap();
+
+        for _ in 0..3 {
+            source.load(None).await.unwrap().unwrap();
+            assert_eq!(source.current_slice(), b"run\n");
+            source.consume(source.current_slice().len());
+        }
+        assert_eq!(source.backoff, RESTART_BACKOFF_MIN * 4);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn no_restart_on_success() {
+        let mut config = config("echo run");
+        config.restart = ProcessRestartPolicy::OnFailure;
+        let mut source = ProcessSource::new(config).await.unwrap();
+
+        assert_eq!(read_all(&mut source).await, b"run\n");
+        assert!(source.restart_at.is_none());
+    }
 }
@@ -0,0 +1,236 @@
+//! Sources receiving data from web servers, either as the messages of WebSocket connections
+//! or as the body of long-lived HTTP responses.
+
+use std::{future::Future, time::Duration};
+
+use bufread::DeqBuffer;
+use bytes::Bytes;
+use stypes::{HttpHeader, SourceConnectionState};
+use thiserror::Error;
+use tokio::{
+    sync::watch,
+    task::{JoinHandle, yield_now},
+};
+
+use crate::Error as SourceError;
+
+pub mod stream;
+pub mod websocket;
+
+/// Maximum capacity for the buffer of web byte-sources.
+const MAX_BUFF_SIZE: usize = 1024 * 1024;
+
+/// Maximum time to wait for the server to accept the connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The time interval between the attempts to reconnect to the server.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Error, Debug)]
+pub enum HttpSourceError {
+    #[error("Invalid URL: {0}")]
+    Url(String),
+    #[error("Invalid header '{0}': {1}")]
+    Header(String, String),
+    #[error("Connection to server failed: {0}")]
+    Connection(String),
+    #[error("Server responded with status {0}")]
+    Status(String),
+    #[error("Server didn't respond within {} seconds", CONNECT_TIMEOUT.as_secs())]
+    Timeout,
+}
+
+/// Returns the headers of the request, including the authorization with the bearer token.
+fn request_headers<'a>(
+    headers: &'a [HttpHeader],
+    bearer_token: Option<&str>,
+) -> impl Iterator<Item = (String, String)> + 'a {
+    let authorization = bearer_token
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(|token| (String::from("Authorization"), format!("Bearer {token}")));
+
+    headers
+        .iter()
+        .map(|header| {
+            (
+                header.name.trim().to_owned(),
+                header.value.trim().to_owned(),
+            )
+        })
+        .chain(authorization)
+}
+
+/// Buffer of web byte-sources, which keeps the part of the received data that didn't fit
+/// into it until the parser consumed enough bytes.
+struct PayloadBuffer {
+    buffer: DeqBuffer,
+    pending: Bytes,
+}
+
+impl PayloadBuffer {
+    fn new() -> Self {
+        Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            pending: Bytes::new(),
+        }
+    }
+
+    /// Checks if there is data which didn't fit into the buffer yet.
+    fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Adds the data to the buffer, returning the count of the bytes which fit into it.
+    fn push(&mut self, data: Bytes) -> usize {
+        debug_assert!(!self.has_pending(), "Pending data must be written first");
+        self.pending = data;
+        self.write_pending()
+    }
+
+    /// Writes as much of the pending data into the buffer as possible, flushing it when
+    /// necessary, and returns the count of written bytes.
+    fn write_pending(&mut self) -> usize {
+        if self.buffer.write_available() < self.pending.len() {
+            self.buffer.flush();
+        }
+        let written = self.buffer.write_from(&self.pending);
+        let _ = self.pending.split_to(written);
+        written
+    }
+}
+
+/// Struct to manage reconnecting to web servers once the connection is lost.
+///
+/// Reconnecting doesn't give up, since servers and gateways may be restarted at any time.
+/// It ends once the connection is established again or the source is dropped.
+struct Reconnecter<T> {
+    /// Channel to send the state of the connection.
+    state_sender: Option<watch::Sender<SourceConnectionState>>,
+    /// Handle of spawned reconnecting task.
+    task_handle: Option<JoinHandle<T>>,
+}
+
+impl<T: Send + 'static> Reconnecter<T> {
+    fn new(state_sender: Option<watch::Sender<SourceConnectionState>>) -> Self {
+        Self {
+            state_sender,
+            task_handle: None,
+        }
+    }
+
+    /// Spawns a task calling `connect` until it succeeds.
+    ///
+    /// # Panics:
+    ///
+    /// This function panics if there is an already spawned task which hadn't been consumed yet.
+    fn spawn_reconnect<F, Fut>(&mut self, url: String, connect: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, HttpSourceError>> + Send,
+    {
+        assert!(
+            self.task_handle.is_none(),
+            "There must be no spawned reconnect task when spawn reconnect is called"
+        );
+        let handle = tokio::spawn(reconnect(url, connect, self.state_sender.clone()));
+
+        self.task_handle = Some(handle);
+    }
+
+    /// Waits for the spawned reconnect task if there is one, returning the new connection.
+    ///
+    /// This function is cancel-safe, since awaiting the task can be repeated.
+    async fn reconnected(&mut self) -> Result<Option<T>, SourceError> {
+        let Some(handle) = self.task_handle.as_mut() else {
+            return Ok(None);
+        };
+        let connection = handle.await.map_err(|err| {
+            SourceError::Unrecoverable(format!("Reconnect to server task panicked. Error: {err}"))
+        })?;
+        self.task_handle = None;
+
+        Ok(Some(connection))
+    }
+}
+
+impl<T> Drop for Reconnecter<T> {
+    fn drop(&mut self) {
+        if let Some(task) = self.task_handle.take() {
+            task.abort();
+        }
+    }
+}
+
+async fn reconnect<T, F, Fut>(
+    url: String,
+    connect: F,
+    state_sender: Option<watch::Sender<SourceConnectionState>>,
+) -> T
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, HttpSourceError>>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        if let Some(sender) = &state_sender {
+            sender.send_replace(SourceConnectionState::Reconnecting { attempts });
+        }
+        log::info!("Reconnecting to {url}. Attempt: {attempts}");
+
+        match connect().await {
+            Ok(connection) => {
+                if let Some(sender) = &state_sender {
+                    sender.send_replace(SourceConnectionState::Connected);
+                    // Make sure the state is forwarded before data is received again.
+                    yield_now().await;
+                }
+                return connection;
+            }
+            Err(err) => {
+                log::debug!("Got following error while trying to reconnect: {err}");
+            }
+        }
+
+        tokio::time::sleep(RECONNECT_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_with_bearer_token() {
+        let headers = vec![HttpHeader {
+            name: String::from(" X-Gateway "),
+            value: String::from("ecu-1 "),
+        }];
+        let all: Vec<_> = request_headers(&headers, Some(" secret ")).collect();
+        assert_eq!(
+            all,
+            vec![
+                (String::from("X-Gateway"), String::from("ecu-1")),
+                (String::from("Authorization"), String::from("Bearer secret")),
+            ]
+        );
+
+        assert_eq!(request_headers(&headers, Some("  ")).count(), 1);
+        assert_eq!(request_headers(&[], None).count(), 0);
+    }
+
+    #[test]
+    fn payload_buffer_keeps_overflow() {
+        let mut buffer = PayloadBuffer::new();
+        let data = Bytes::from(vec![b'a'; MAX_BUFF_SIZE + 10]);
+        assert_eq!(buffer.push(data), MAX_BUFF_SIZE);
+        assert!(buffer.has_pending());
+        assert_eq!(buffer.write_pending(), 0);
+
+        buffer.buffer.read_done(100);
+        assert_eq!(buffer.write_pending(), 10);
+        assert!(!buffer.has_pending());
+        assert_eq!(buffer.buffer.read_available(), MAX_BUFF_SIZE - 90);
+    }
+}
@@ -0,0 +1,265 @@
+use bytes::Bytes;
+use reqwest::{
+    Client, Response, Url,
+    header::{ACCEPT, HeaderName, HeaderValue},
+};
+use stypes::{HttpStreamFormat, HttpStreamTransportConfig, SourceConnectionState};
+use tokio::{sync::watch, time::timeout};
+
+use super::{CONNECT_TIMEOUT, HttpSourceError, PayloadBuffer, Reconnecter, request_headers};
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+
+/// Source receiving the body of a long-lived HTTP response, like chunked log streams or
+/// server-sent events.
+pub struct HttpStreamSource {
+    buffer: PayloadBuffer,
+    client: Client,
+    response: Response,
+    config: HttpStreamTransportConfig,
+    /// Decoder of the events if the body consists of server-sent events.
+    events: Option<EventStreamDecoder>,
+    reconnecter: Option<Reconnecter<Response>>,
+}
+
+impl HttpStreamSource {
+    /// Sends the request, waiting until the server responded with a success status.
+    ///
+    /// The request is sent again once the response ended or the connection is lost if
+    /// reconnecting is enabled, sending the state of the connection over `state_sender`.
+    pub async fn connect(
+        config: &HttpStreamTransportConfig,
+        state_sender: Option<watch::Sender<SourceConnectionState>>,
+    ) -> Result<Self, HttpSourceError> {
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|err| HttpSourceError::Connection(err.to_string()))?;
+        let response = request(&client, config).await?;
+        let events =
+            (config.format == HttpStreamFormat::ServerSentEvents).then(EventStreamDecoder::default);
+        let reconnecter = config.reconnect.then(|| Reconnecter::new(state_sender));
+
+        Ok(Self {
+            buffer: PayloadBuffer::new(),
+            client,
+            response,
+            config: config.clone(),
+            events,
+            reconnecter,
+        })
+    }
+}
+
+/// Sends the `GET` request with the configured headers.
+async fn request(
+    client: &Client,
+    config: &HttpStreamTransportConfig,
+) -> Result<Response, HttpSourceError> {
+    let url = Url::parse(config.url.trim()).map_err(|err| HttpSourceError::Url(err.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(HttpSourceError::Url(format!(
+            "Unsupported scheme '{}'. Use http or https",
+            url.scheme()
+        )));
+    }
+
+    let mut request = client.get(url);
+    if config.format == HttpStreamFormat::ServerSentEvents {
+        request = request.header(ACCEPT, "text/event-stream");
+    }
+    for (name, value) in request_headers(&config.headers, config.bearer_token.as_deref()) {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|err| HttpSourceError::Header(name.clone(), err.to_string()))?;
+        let header_value = HeaderValue::from_str(&value)
+            .map_err(|err| HttpSourceError::Header(name.clone(), err.to_string()))?;
+        request = request.header(header_name, header_value);
+    }
+
+    let response = timeout(CONNECT_TIMEOUT, request.send())
+        .await
+        .map_err(|_| HttpSourceError::Timeout)?
+        .map_err(|err| HttpSourceError::Connection(err.to_string()))?;
+    if !response.status().is_success() {
+        return Err(HttpSourceError::Status(response.status().to_string()));
+    }
+
+    Ok(response)
+}
+
+impl ByteSource for HttpStreamSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        if self.buffer.has_pending() {
+            let loaded = self.buffer.write_pending();
+            return Ok(Some(ReloadInfo::new(loaded, self.len(), 0, None)));
+        }
+
+        // Implementation is cancel-safe here because awaiting the reconnect task can be
+        // repeated and there is one await call on the body of the response only.
+        loop {
+            if let Some(reconnecter) = self.reconnecter.as_mut()
+                && let Some(response) = reconnecter.reconnected().await?
+            {
+                self.response = response;
+                // Events of the previous response can't be completed anymore.
+                if let Some(events) = self.events.as_mut() {
+                    *events = EventStreamDecoder::default();
+                }
+            }
+
+            let ended = match self.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    let data = match self.events.as_mut() {
+                        Some(events) => events.decode(&chunk),
+                        None => chunk,
+                    };
+                    if data.is_empty() {
+                        continue;
+                    }
+                    trace!("---> Received {} bytes", data.len());
+                    let loaded = self.buffer.push(data);
+                    return Ok(Some(ReloadInfo::new(loaded, self.len(), 0, None)));
+                }
+                Ok(None) => None,
+                Err(err) => Some(SourceError::Unrecoverable(format!(
+                    "Reading HTTP response failed: {err}"
+                ))),
+            };
+
+            let Some(reconnecter) = self.reconnecter.as_mut() else {
+                return match ended {
+                    Some(err) => Err(err),
+                    None => Ok(None),
+                };
+            };
+            warn!("HTTP stream from {} ended. Reconnecting", self.config.url);
+            let client = self.client.clone();
+            let config = self.config.clone();
+            reconnecter.spawn_reconnect(config.url.clone(), move || {
+                let client = client.clone();
+                let config = config.clone();
+                async move { request(&client, &config).await }
+            });
+        }
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.buffer.read_available()
+    }
+}
+
+/// Decoder of server-sent events, delivering the data of each event as one line.
+///
+/// Events can be split across the chunks of the response, therefore incomplete lines and
+/// events are kept until the following chunks complete them. Fields other than `data` are
+/// ignored.
+#[derive(Debug, Default)]
+struct EventStreamDecoder {
+    /// Incomplete line at the end of the last chunk.
+    line: Vec<u8>,
+    /// Data of the current event, each data field terminated with a line break.
+    data: Vec<u8>,
+}
+
+impl EventStreamDecoder {
+    /// Decodes the chunk, returning the data of the events completed by it.
+    fn decode(&mut self, chunk: &[u8]) -> Bytes {
+        let mut decoded = Vec::new();
+        let mut rest = chunk;
+        while let Some(end) = rest.iter().position(|byte| *byte == b'\n') {
+            self.line.extend_from_slice(&rest[..end]);
+            rest = &rest[end + 1..];
+            let line = std::mem::take(&mut self.line);
+            self.decode_line(line.strip_suffix(b"\r").unwrap_or(&line), &mut decoded);
+        }
+        self.line.extend_from_slice(rest);
+
+        Bytes::from(decoded)
+    }
+
+    fn decode_line(&mut self, line: &[u8], decoded: &mut Vec<u8>) {
+        // Empty lines dispatch the event.
+        if line.is_empty() {
+            decoded.append(&mut self.data);
+            return;
+        }
+        let (field, value) = match line.iter().position(|byte| *byte == b':') {
+            // Lines starting with a colon are comments.
+            Some(0) => return,
+            Some(colon) => {
+                let value = &line[colon + 1..];
+                (&line[..colon], value.strip_prefix(b" ").unwrap_or(value))
+            }
+            None => (line, &[][..]),
+        };
+        if field == b"data" {
+            self.data.extend_from_slice(value);
+            self.data.push(b'\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+    use super::*;
+
+    #[test]
+    fn decode_events() {
+        let mut decoder = EventStreamDecoder::default();
+        assert_eq!(
+            decoder.decode(b": keep-alive\n\nevent: log\ndata: first\n\n"),
+            Bytes::from_static(b"first\n")
+        );
+        assert_eq!(
+            decoder.decode(b"data: multi\r\ndata:line\r\n\r\nid: 3\nda"),
+            Bytes::from_static(b"multi\nline\n")
+        );
+        assert!(decoder.decode(b"ta: split").is_empty());
+        assert_eq!(decoder.decode(b"\n\n"), Bytes::from_static(b"split\n"));
+        // Events without data aren't delivered.
+        assert!(decoder.decode(b"event: ping\n\n").is_empty());
+    }
+
+    #[tokio::test]
+    async fn receive_chunked_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\n\
+                    Content-Type: text/event-stream\r\n\
+                    Transfer-Encoding: chunked\r\n\r\n\
+                    10\r\ndata: first\n\ndat\r\n\
+                    b\r\na: second\n\n\r\n\
+                    0\r\n\r\n",
+                )
+                .await
+                .unwrap();
+        });
+
+        let config = HttpStreamTransportConfig {
+            url: format!("http://{addr}/logs"),
+            headers: Vec::new(),
+            bearer_token: Some(String::from("secret")),
+            format: HttpStreamFormat::ServerSentEvents,
+            reconnect: false,
+        };
+        let mut source = HttpStreamSource::connect(&config, None).await.unwrap();
+        while source.load(None).await.unwrap().is_some() {}
+        assert_eq!(source.current_slice(), b"first\nsecond\n");
+    }
+}
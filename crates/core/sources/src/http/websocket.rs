@@ -0,0 +1,274 @@
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use stypes::{SourceConnectionState, WebSocketMessageMode, WebSocketTransportConfig};
+use tokio::{net::TcpStream, sync::watch, time::timeout};
+use tokio_tungstenite::{
+    MaybeTlsStream, WebSocketStream, connect_async,
+    tungstenite::{
+        self, Message,
+        client::IntoClientRequest,
+        http::{HeaderName, HeaderValue},
+    },
+};
+
+use super::{CONNECT_TIMEOUT, HttpSourceError, PayloadBuffer, Reconnecter, request_headers};
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Source receiving the messages of a WebSocket server.
+///
+/// Data sent through SDE is sent back to the server, text as text messages and bytes as
+/// binary messages.
+pub struct WebSocketSource {
+    buffer: PayloadBuffer,
+    socket: Socket,
+    config: WebSocketTransportConfig,
+    reconnecter: Option<Reconnecter<Socket>>,
+}
+
+impl WebSocketSource {
+    /// Connects to the server, waiting until the opening handshake is completed.
+    ///
+    /// The connection is reopened once it's lost if reconnecting is enabled, sending the state
+    /// of the connection over `state_sender`.
+    pub async fn connect(
+        config: &WebSocketTransportConfig,
+        state_sender: Option<watch::Sender<SourceConnectionState>>,
+    ) -> Result<Self, HttpSourceError> {
+        let socket = connect(config).await?;
+        let reconnecter = config.reconnect.then(|| Reconnecter::new(state_sender));
+
+        Ok(Self {
+            buffer: PayloadBuffer::new(),
+            socket,
+            config: config.clone(),
+            reconnecter,
+        })
+    }
+
+    /// Returns the payload of the message to deliver, or `None` for control messages.
+    fn payload(&self, message: Message) -> Option<Bytes> {
+        let payload: Bytes = match message {
+            Message::Text(text) => text.into(),
+            Message::Binary(bytes) => bytes,
+            Message::Ping(_) | Message::Pong(_) | Message::Close(_) | Message::Frame(_) => {
+                return None;
+            }
+        };
+
+        match self.config.messages {
+            WebSocketMessageMode::Raw => Some(payload),
+            WebSocketMessageMode::Lines if payload.ends_with(b"\n") => Some(payload),
+            WebSocketMessageMode::Lines => {
+                let mut line = Vec::with_capacity(payload.len() + 1);
+                line.extend_from_slice(&payload);
+                line.push(b'\n');
+                Some(Bytes::from(line))
+            }
+        }
+    }
+}
+
+/// Opens the WebSocket connection with the configured headers.
+async fn connect(config: &WebSocketTransportConfig) -> Result<Socket, HttpSourceError> {
+    let mut request = config
+        .url
+        .trim()
+        .into_client_request()
+        .map_err(|err| HttpSourceError::Url(err.to_string()))?;
+    for (name, value) in request_headers(&config.headers, config.bearer_token.as_deref()) {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|err| HttpSourceError::Header(name.clone(), err.to_string()))?;
+        let header_value = HeaderValue::from_str(&value)
+            .map_err(|err| HttpSourceError::Header(name.clone(), err.to_string()))?;
+        request.headers_mut().append(header_name, header_value);
+    }
+
+    let (socket, _) = timeout(CONNECT_TIMEOUT, connect_async(request))
+        .await
+        .map_err(|_| HttpSourceError::Timeout)?
+        .map_err(|err| match err {
+            tungstenite::Error::Http(response) => {
+                HttpSourceError::Status(response.status().to_string())
+            }
+            err => HttpSourceError::Connection(err.to_string()),
+        })?;
+
+    Ok(socket)
+}
+
+impl ByteSource for WebSocketSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        if self.buffer.has_pending() {
+            let loaded = self.buffer.write_pending();
+            return Ok(Some(ReloadInfo::new(loaded, self.len(), 0, None)));
+        }
+
+        // Implementation is cancel-safe here because awaiting the reconnect task can be
+        // repeated and receiving the next message from the socket is cancel-safe.
+        loop {
+            if let Some(reconnecter) = self.reconnecter.as_mut()
+                && let Some(socket) = reconnecter.reconnected().await?
+            {
+                self.socket = socket;
+            }
+
+            let disconnected = match self.socket.next().await {
+                Some(Ok(message)) => {
+                    let Some(payload) = self.payload(message) else {
+                        continue;
+                    };
+                    trace!("---> Received {} bytes", payload.len());
+                    let loaded = self.buffer.push(payload);
+                    return Ok(Some(ReloadInfo::new(loaded, self.len(), 0, None)));
+                }
+                Some(Err(err)) => Some(SourceError::Unrecoverable(format!(
+                    "WebSocket connection failed: {err}"
+                ))),
+                // Server closed the connection.
+                None => None,
+            };
+
+            let Some(reconnecter) = self.reconnecter.as_mut() else {
+                return match disconnected {
+                    Some(err) => Err(err),
+                    None => Ok(None),
+                };
+            };
+            warn!(
+                "WebSocket connection to {} is closed. Reconnecting",
+                self.config.url
+            );
+            let config = self.config.clone();
+            reconnecter.spawn_reconnect(config.url.clone(), move || {
+                let config = config.clone();
+                async move { connect(&config).await }
+            });
+        }
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.buffer.read_available()
+    }
+
+    async fn cancel(&mut self) -> Result<(), SourceError> {
+        // Closing is polite only, the connection is dropped anyway.
+        if let Err(err) = self.socket.close(None).await {
+            debug!("Closing WebSocket connection failed: {err}");
+        }
+        Ok(())
+    }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        if self
+            .reconnecter
+            .as_ref()
+            .is_some_and(|reconnecter| reconnecter.task_handle.is_some())
+        {
+            return Err(SourceError::Setup(String::from(
+                "Connection is lost. Reconnecting to server",
+            )));
+        }
+        let (message, bytes) = match request {
+            stypes::SdeRequest::WriteText(text) => {
+                let len = text.len();
+                (Message::text(text), len)
+            }
+            stypes::SdeRequest::WriteBytes(bytes) => {
+                let len = bytes.len();
+                (Message::binary(bytes), len)
+            }
+            stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => {
+                return Err(SourceError::NotSupported);
+            }
+        };
+        self.socket.send(message).await.map_err(|err| {
+            SourceError::Unrecoverable(format!("Fail to send WebSocket message: {err}"))
+        })?;
+        Ok(stypes::SdeResponse { bytes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+
+    fn config(url: String, messages: WebSocketMessageMode) -> WebSocketTransportConfig {
+        WebSocketTransportConfig {
+            url,
+            headers: Vec::new(),
+            bearer_token: None,
+            messages,
+            reconnect: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn receive_lines_and_send_sde() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = accept_async(stream).await.unwrap();
+            socket.send(Message::text("first")).await.unwrap();
+            socket
+                .send(Message::binary(b"second\n".to_vec()))
+                .await
+                .unwrap();
+            let received = socket.next().await.unwrap().unwrap();
+            socket.close(None).await.unwrap();
+            received
+        });
+
+        let config = config(format!("ws://{addr}"), WebSocketMessageMode::Lines);
+        let mut source = WebSocketSource::connect(&config, None).await.unwrap();
+        while source.len() < "first\nsecond\n".len() {
+            source.load(None).await.unwrap().unwrap();
+        }
+        assert_eq!(source.current_slice(), b"first\nsecond\n");
+
+        let response = source
+            .income(stypes::SdeRequest::WriteText(String::from("cmd")))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 3);
+        assert!(source.load(None).await.unwrap().is_none());
+
+        assert_eq!(server.await.unwrap(), Message::text("cmd"));
+    }
+
+    #[tokio::test]
+    async fn raw_messages_are_unchanged() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = accept_async(stream).await.unwrap();
+            socket.send(Message::binary(vec![1, 2, 3])).await.unwrap();
+            socket.close(None).await.unwrap();
+        });
+
+        let config = config(format!("ws://{addr}"), WebSocketMessageMode::Raw);
+        let mut source = WebSocketSource::connect(&config, None).await.unwrap();
+        source.load(None).await.unwrap().unwrap();
+        assert_eq!(source.current_slice(), &[1, 2, 3]);
+    }
+}
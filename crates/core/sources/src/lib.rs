@@ -10,7 +10,13 @@ extern crate log;
 mod tests;
 
 pub mod binary;
+#[cfg(target_os = "linux")]
+pub mod can;
 pub mod command;
+pub mod http;
+pub mod mqtt;
+pub mod recording;
+pub mod replay;
 pub mod sde;
 pub mod serial;
 pub mod socket;
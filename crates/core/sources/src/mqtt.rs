@@ -0,0 +1,502 @@
+use std::{collections::VecDeque, fs, path::Path, time::Duration};
+
+use bufread::DeqBuffer;
+use bytes::Bytes;
+use rumqttc::{
+    AsyncClient, ConnectionError, Event, EventLoop, MqttOptions, Packet, Publish, QoS,
+    SubscribeFilter, SubscribeReasonCode, TlsConfiguration,
+};
+use thiserror::Error;
+use tokio::{sync::mpsc, time::timeout};
+
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+
+/// Maximum size of the payload of one MQTT message.
+const MAX_PAYLOAD_SIZE: usize = 1024 * 1024;
+
+/// Maximum capacity for the buffer of MQTT byte-sources.
+const MAX_BUFF_SIZE: usize = 4 * MAX_PAYLOAD_SIZE;
+
+/// Capacity of the queue of requests to the broker, like subscriptions and publishes.
+const REQUESTS_CAPACITY: usize = 64;
+
+/// Maximum time to wait for the broker to accept the connection and the subscriptions.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+/// Delay before the first attempt to reconnect once the connection to the broker is lost,
+/// which is doubled with each failed attempt up to [`MAX_RECONNECT_DELAY`].
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Error, Debug)]
+pub enum MqttSourceError {
+    #[error("Invalid broker URL: {0}")]
+    Url(String),
+    #[error("Config: {0}")]
+    Config(String),
+    #[error("Fail to read certificate {0}: {1}")]
+    Certificate(String, std::io::Error),
+    #[error("Connection to broker failed: {0}")]
+    Connection(String),
+    #[error("Broker rejected subscription to {0}")]
+    SubscriptionRejected(String),
+    #[error("Broker didn't respond within {} seconds", CONNECT_TIMEOUT.as_secs())]
+    Timeout,
+}
+
+/// Address of an MQTT broker parsed from its URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BrokerAddr {
+    host: String,
+    port: u16,
+    tls: bool,
+}
+
+impl BrokerAddr {
+    /// Parses URLs like `mqtt://host:1883` or `mqtts://host`. The port defaults to 1883 for
+    /// plain connections and to 8883 for TLS connections.
+    fn parse(url: &str) -> Result<Self, MqttSourceError> {
+        let url = url.trim();
+        let (tls, rest) = match url.split_once("://") {
+            Some(("mqtt" | "tcp", rest)) => (false, rest),
+            Some(("mqtts" | "ssl", rest)) => (true, rest),
+            Some((scheme, _)) => {
+                return Err(MqttSourceError::Url(format!(
+                    "Unsupported scheme '{scheme}'. Use mqtt or mqtts"
+                )));
+            }
+            None => (false, url),
+        };
+        let rest = rest.trim_end_matches('/');
+        let default_port = if tls { 8883 } else { 1883 };
+
+        // IPv6 addresses are enclosed in brackets to separate them from the port.
+        let (host, port) = match rest.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, port) = bracketed
+                    .split_once(']')
+                    .ok_or_else(|| MqttSourceError::Url(format!("Missing ']' in {url}")))?;
+                (host, port.strip_prefix(':'))
+            }
+            None => match rest.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (rest, None),
+            },
+        };
+        if host.is_empty() {
+            return Err(MqttSourceError::Url(format!("Missing host in {url}")));
+        }
+        let port = match port {
+            Some(port) => port
+                .parse()
+                .map_err(|err| MqttSourceError::Url(format!("Invalid port '{port}': {err}")))?,
+            None => default_port,
+        };
+
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            tls,
+        })
+    }
+}
+
+/// Source subscribing to the topics of an MQTT broker, delivering the payload of each
+/// received message.
+///
+/// Lost connections are reestablished with an increasing delay between the attempts, while
+/// a broker refusing the connection or failing TLS ends the source.
+///
+/// Data sent through SDE is published to the configured SDE topic.
+pub struct MqttSource {
+    buffer: DeqBuffer,
+    client: AsyncClient,
+    eventloop: EventLoop,
+    filters: Vec<SubscribeFilter>,
+    qos: QoS,
+    sde_topic: Option<String>,
+    /// Messages received while waiting for the broker to acknowledge the subscriptions.
+    pending: VecDeque<Publish>,
+    /// Delay before the next attempt to reconnect to the broker.
+    reconnect_delay: Duration,
+}
+
+impl MqttSource {
+    /// Connects to the broker and subscribes to the configured topics, waiting until the
+    /// broker has accepted both.
+    pub async fn connect(config: &stypes::MqttTransportConfig) -> Result<Self, MqttSourceError> {
+        let addr = BrokerAddr::parse(&config.broker_url)?;
+        let topics: Vec<_> = config
+            .topics
+            .iter()
+            .map(|topic| topic.trim())
+            .filter(|topic| !topic.is_empty())
+            .collect();
+        if topics.is_empty() {
+            return Err(MqttSourceError::Config(String::from(
+                "At least one topic is required",
+            )));
+        }
+        if config.client_id.trim().is_empty() {
+            return Err(MqttSourceError::Config(String::from(
+                "Client ID is required",
+            )));
+        }
+
+        let mut options = MqttOptions::new(config.client_id.trim(), addr.host, addr.port);
+        options.set_keep_alive(KEEP_ALIVE);
+        options.set_max_packet_size(MAX_PAYLOAD_SIZE, MAX_PAYLOAD_SIZE);
+        if addr.tls {
+            let tls = match &config.tls {
+                Some(tls) => tls_configuration(tls)?,
+                None => TlsConfiguration::default(),
+            };
+            options.set_transport(rumqttc::Transport::tls_with_config(tls));
+        }
+
+        let qos = qos(config.qos);
+        let filters: Vec<_> = topics
+            .into_iter()
+            .map(|topic| SubscribeFilter::new(topic.to_owned(), qos))
+            .collect();
+        let (client, eventloop) = AsyncClient::new(options, REQUESTS_CAPACITY);
+        let mut source = Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            client,
+            eventloop,
+            filters,
+            qos,
+            sde_topic: config.sde_topic.clone().filter(|topic| !topic.is_empty()),
+            pending: VecDeque::new(),
+            reconnect_delay: RECONNECT_DELAY,
+        };
+        timeout(CONNECT_TIMEOUT, source.subscribe())
+            .await
+            .map_err(|_| MqttSourceError::Timeout)??;
+
+        Ok(source)
+    }
+
+    /// Subscribes to the topics, waiting for the acknowledgment of the broker.
+    async fn subscribe(&mut self) -> Result<(), MqttSourceError> {
+        self.client
+            .subscribe_many(self.filters.clone())
+            .await
+            .map_err(|err| MqttSourceError::Connection(err.to_string()))?;
+        loop {
+            match self.eventloop.poll().await {
+                Ok(Event::Incoming(Packet::SubAck(ack))) => {
+                    let rejected = ack
+                        .return_codes
+                        .iter()
+                        .zip(&self.filters)
+                        .filter(|(code, _)| matches!(code, SubscribeReasonCode::Failure))
+                        .map(|(_, filter)| filter.path.as_str())
+                        .collect::<Vec<_>>();
+                    if !rejected.is_empty() {
+                        return Err(MqttSourceError::SubscriptionRejected(rejected.join(", ")));
+                    }
+                    return Ok(());
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => self.pending.push_back(publish),
+                Ok(_) => {}
+                Err(err) => return Err(MqttSourceError::Connection(connection_error(err))),
+            }
+        }
+    }
+
+    /// Waits for the next message on the subscribed topics, returning its topic and payload.
+    ///
+    /// The message isn't added to the internal buffer of the source, which makes it possible
+    /// to dispatch the messages of each topic to their own [`MqttTopicSource`].
+    pub async fn recv_message(&mut self) -> Result<(String, Bytes), SourceError> {
+        if let Some(publish) = self.pending.pop_front() {
+            return Ok((publish.topic, publish.payload));
+        }
+        loop {
+            match self.eventloop.poll().await {
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    return Ok((publish.topic, publish.payload));
+                }
+                Ok(Event::Incoming(Packet::ConnAck(ack))) => {
+                    self.reconnect_delay = RECONNECT_DELAY;
+                    // Subscriptions are dropped by the broker if the session isn't resumed.
+                    if !ack.session_present {
+                        self.client
+                            .try_subscribe_many(self.filters.clone())
+                            .map_err(|err| SourceError::Unrecoverable(err.to_string()))?;
+                    }
+                }
+                Ok(_) => {}
+                // Polling the event loop again reconnects to the broker.
+                Err(err) if is_transient(&err) => {
+                    warn!(
+                        "Connection to broker lost: {}. Reconnecting in {} seconds",
+                        connection_error(err),
+                        self.reconnect_delay.as_secs()
+                    );
+                    tokio::time::sleep(self.reconnect_delay).await;
+                    self.reconnect_delay = (self.reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+                }
+                Err(err) => {
+                    return Err(SourceError::Unrecoverable(format!(
+                        "Connection to broker failed: {}",
+                        connection_error(err)
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Publishes the bytes to the SDE topic. The message is sent once the source is polled
+    /// for new messages again.
+    pub fn publish(&self, bytes: Vec<u8>) -> Result<usize, SourceError> {
+        let Some(topic) = &self.sde_topic else {
+            return Err(SourceError::Setup(String::from(
+                "No topic is configured to publish data to",
+            )));
+        };
+        let len = bytes.len();
+        self.client
+            .try_publish(topic, self.qos, false, bytes)
+            .map_err(|err| SourceError::Setup(format!("Fail to publish to {topic}: {err}")))?;
+        Ok(len)
+    }
+}
+
+impl ByteSource for MqttSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        if !has_capacity(&mut self.buffer) {
+            let available_bytes = self.len();
+            return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+        }
+
+        let (topic, payload) = self.recv_message().await?;
+        let len = payload.len();
+        trace!("---> Received {len} bytes on topic {topic}");
+        write_payload(&mut self.buffer, &payload)?;
+
+        let available_bytes = self.buffer.read_available();
+
+        Ok(Some(ReloadInfo::new(len, available_bytes, 0, None)))
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.read_available()
+    }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        let bytes = match request {
+            stypes::SdeRequest::WriteText(text) => self.publish(text.into_bytes())?,
+            stypes::SdeRequest::WriteBytes(bytes) => self.publish(bytes)?,
+            stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => {
+                return Err(SourceError::NotSupported);
+            }
+        };
+        Ok(stypes::SdeResponse { bytes })
+    }
+}
+
+/// Source delivering the messages of one topic, which are received by an [`MqttSource`]
+/// shared with other topics and dispatched over a channel.
+pub struct MqttTopicSource {
+    buffer: DeqBuffer,
+    rx_payloads: mpsc::Receiver<Bytes>,
+    topic: String,
+}
+
+impl MqttTopicSource {
+    pub fn new(topic: String, rx_payloads: mpsc::Receiver<Bytes>) -> Self {
+        Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            rx_payloads,
+            topic,
+        }
+    }
+
+    /// The topic of the messages.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+}
+
+impl ByteSource for MqttTopicSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        if !has_capacity(&mut self.buffer) {
+            let available_bytes = self.len();
+            return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+        }
+
+        // Dispatching ends once the connection is closed.
+        let Some(payload) = self.rx_payloads.recv().await else {
+            return Ok(None);
+        };
+        let len = payload.len();
+        write_payload(&mut self.buffer, &payload)?;
+
+        let available_bytes = self.buffer.read_available();
+
+        Ok(Some(ReloadInfo::new(len, available_bytes, 0, None)))
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.read_available()
+    }
+}
+
+/// Checks if the buffer can take the largest possible payload, flushing it when necessary.
+fn has_capacity(buffer: &mut DeqBuffer) -> bool {
+    buffer.write_available() >= MAX_PAYLOAD_SIZE
+        || (buffer.flush() > 0 && buffer.write_available() >= MAX_PAYLOAD_SIZE)
+}
+
+fn write_payload(buffer: &mut DeqBuffer, payload: &[u8]) -> Result<(), SourceError> {
+    let added = buffer.write_from(payload);
+    if added < payload.len() {
+        return Err(SourceError::Unrecoverable(format!(
+            "Internal buffer maximum capcity reached.\
+            Received payload: {}, Copied to buffer: {added}",
+            payload.len()
+        )));
+    }
+    Ok(())
+}
+
+fn qos(qos: stypes::MqttQos) -> QoS {
+    match qos {
+        stypes::MqttQos::AtMostOnce => QoS::AtMostOnce,
+        stypes::MqttQos::AtLeastOnce => QoS::AtLeastOnce,
+        stypes::MqttQos::ExactlyOnce => QoS::ExactlyOnce,
+    }
+}
+
+fn tls_configuration(tls: &stypes::MqttTlsConfig) -> Result<TlsConfiguration, MqttSourceError> {
+    let read = |path: &Path| {
+        fs::read(path).map_err(|err| MqttSourceError::Certificate(path.display().to_string(), err))
+    };
+    let client_auth = match &tls.client_auth {
+        Some((cert, key)) => Some((read(cert)?, read(key)?)),
+        None => None,
+    };
+
+    Ok(TlsConfiguration::Simple {
+        ca: read(&tls.ca_file)?,
+        alpn: None,
+        client_auth,
+    })
+}
+
+/// Checks whether reconnecting to the broker can resolve the connection error.
+fn is_transient(err: &ConnectionError) -> bool {
+    !matches!(
+        err,
+        ConnectionError::ConnectionRefused(_)
+            | ConnectionError::Tls(_)
+            | ConnectionError::RequestsDone
+    )
+}
+
+fn connection_error(err: ConnectionError) -> String {
+    match err {
+        ConnectionError::ConnectionRefused(code) => format!("Connection refused: {code:?}"),
+        err => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broker_url_defaults_port_by_scheme() {
+        assert_eq!(
+            BrokerAddr::parse("mqtt://localhost").unwrap(),
+            BrokerAddr {
+                host: String::from("localhost"),
+                port: 1883,
+                tls: false,
+            }
+        );
+        assert_eq!(
+            BrokerAddr::parse("mqtts://broker.local/").unwrap(),
+            BrokerAddr {
+                host: String::from("broker.local"),
+                port: 8883,
+                tls: true,
+            }
+        );
+    }
+
+    #[test]
+    fn broker_url_with_port_and_ipv6() {
+        assert_eq!(
+            BrokerAddr::parse("127.0.0.1:1884").unwrap(),
+            BrokerAddr {
+                host: String::from("127.0.0.1"),
+                port: 1884,
+                tls: false,
+            }
+        );
+        assert_eq!(
+            BrokerAddr::parse("mqtt://[::1]:1885").unwrap(),
+            BrokerAddr {
+                host: String::from("::1"),
+                port: 1885,
+                tls: false,
+            }
+        );
+    }
+
+    #[test]
+    fn broker_url_rejects_invalid() {
+        assert!(BrokerAddr::parse("http://localhost").is_err());
+        assert!(BrokerAddr::parse("mqtt://:1883").is_err());
+        assert!(BrokerAddr::parse("mqtt://localhost:port").is_err());
+    }
+
+    #[tokio::test]
+    async fn topic_source_delivers_dispatched_payloads() {
+        let (tx_payloads, rx_payloads) = mpsc::channel(4);
+        let mut source = MqttTopicSource::new(String::from("logs/ecu"), rx_payloads);
+        for payload in ["one", "two"] {
+            tx_payloads.send(Bytes::from(payload)).await.unwrap();
+        }
+        drop(tx_payloads);
+
+        for payload in ["one", "two"] {
+            source.load(None).await.unwrap();
+            assert_eq!(source.current_slice(), payload.as_bytes());
+            source.consume(payload.len());
+        }
+        assert!(source.load(None).await.unwrap().is_none());
+        assert_eq!(source.topic(), "logs/ecu");
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Pattern at the start of each DLT storage header.
+const STORAGE_HEADER_PATTERN: &[u8; 4] = b"DLT\x01";
+/// Length of the DLT standard header without its optional fields.
+const STANDARD_HEADER_LEN: usize = 4;
+/// Flag of the header type indicating that the standard header contains the ECU ID.
+const WITH_ECU_ID: u8 = 0x04;
+
+/// Splits a stream of DLT messages without storage headers into messages, prefixing each of
+/// them with a storage header.
+#[derive(Debug, Default)]
+pub(super) struct DltFramer {
+    /// Bytes of the incomplete message received so far.
+    pending: Vec<u8>,
+}
+
+impl DltFramer {
+    /// Writes the complete messages of the received bytes, keeping the remaining bytes until
+    /// their message is completed. Returns the count of written bytes.
+    pub fn write<W: Write>(
+        &mut self,
+        writer: &mut W,
+        bytes: &[u8],
+        time: SystemTime,
+    ) -> io::Result<u64> {
+        self.pending.extend_from_slice(bytes);
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        let mut written = 0;
+        let mut offset = 0;
+        while let Some(msg_len) = message_len(&self.pending[offset..]) {
+            if msg_len < STANDARD_HEADER_LEN {
+                // Invalid length: Skip one byte to find the next message.
+                offset += 1;
+                continue;
+            }
+            let Some(msg) = self.pending.get(offset..offset + msg_len) else {
+                break;
+            };
+            let ecu_id: &[u8] = match msg.first() {
+                Some(header_type) if header_type & WITH_ECU_ID != 0 && msg_len >= 8 => &msg[4..8],
+                _ => &[0; 4],
+            };
+            writer.write_all(STORAGE_HEADER_PATTERN)?;
+            writer.write_all(&(since_epoch.as_secs() as u32).to_le_bytes())?;
+            writer.write_all(&since_epoch.subsec_micros().to_le_bytes())?;
+            writer.write_all(ecu_id)?;
+            writer.write_all(msg)?;
+
+            written += (16 + msg_len) as u64;
+            offset += msg_len;
+        }
+        self.pending.drain(..offset);
+
+        Ok(written)
+    }
+}
+
+/// Reads the length of the message from its standard header if available.
+fn message_len(bytes: &[u8]) -> Option<usize> {
+    let len = bytes.get(2..4)?;
+    Some(u16::from_be_bytes([len[0], len[1]]) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_storage_headers() {
+        // Standard headers only with and without ECU ID.
+        let with_ecu = [0x25, 0x00, 0x00, 0x0A, b'E', b'C', b'U', b'1', 0xAA, 0xBB];
+        let without_ecu = [0x21, 0x01, 0x00, 0x05, 0xCC];
+        let time = UNIX_EPOCH + std::time::Duration::from_micros(2_000_003);
+
+        let mut framer = DltFramer::default();
+        let mut output = Vec::new();
+        let mut stream = with_ecu.to_vec();
+        stream.extend_from_slice(&without_ecu);
+
+        // Split in the middle of the second message.
+        let written = framer.write(&mut output, &stream[..12], time).unwrap();
+        assert_eq!(written, 26);
+        let written = framer.write(&mut output, &stream[12..], time).unwrap();
+        assert_eq!(written, 21);
+
+        let mut expected = Vec::new();
+        for (msg, ecu) in [(&with_ecu[..], *b"ECU1"), (&without_ecu[..], [0; 4])] {
+            expected.extend_from_slice(b"DLT\x01");
+            expected.extend_from_slice(&2u32.to_le_bytes());
+            expected.extend_from_slice(&3u32.to_le_bytes());
+            expected.extend_from_slice(&ecu);
+            expected.extend_from_slice(msg);
+        }
+        assert_eq!(output, expected);
+    }
+}
@@ -0,0 +1,366 @@
+//! Recording of the raw bytes delivered by stream sources.
+//!
+//! Stream sources don't keep the bytes they deliver, which makes it impossible to parse them
+//! again later (e.g. with an updated FIBEX). [`RecordingSource`] wraps any [`ByteSource`] and
+//! writes the newly loaded bytes to files in a format that can be opened as file source later.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    net::SocketAddr,
+    path::PathBuf,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter, TransportProtocol};
+
+mod dlt;
+mod pcapng;
+
+use dlt::DltFramer;
+use pcapng::PcapNgWriter;
+
+/// Defines how the raw bytes are written to the recording files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// Bytes are written unchanged to files with the given extension.
+    Raw {
+        file_format: stypes::FileFormat,
+        extension: &'static str,
+    },
+    /// Bytes are DLT messages without storage headers, which are written with a storage
+    /// header each to `.dlt` files.
+    Dlt,
+    /// Bytes are payloads of the given network connection, which are written as ethernet
+    /// frames with timestamps to `.pcapng` files.
+    PcapNg {
+        protocol: TransportProtocol,
+        source: SocketAddr,
+        destination: SocketAddr,
+    },
+}
+
+impl RecordingFormat {
+    /// Format of the recording files once they are opened as file sources.
+    pub fn file_format(&self) -> stypes::FileFormat {
+        match self {
+            RecordingFormat::Raw { file_format, .. } => *file_format,
+            RecordingFormat::Dlt => stypes::FileFormat::Binary,
+            RecordingFormat::PcapNg { .. } => stypes::FileFormat::PcapNG,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            RecordingFormat::Raw { extension, .. } => extension,
+            RecordingFormat::Dlt => "dlt",
+            RecordingFormat::PcapNg { .. } => "pcapng",
+        }
+    }
+}
+
+/// Writer of the current recording file.
+enum FileWriter {
+    Raw(BufWriter<File>),
+    Dlt {
+        writer: BufWriter<File>,
+        framer: DltFramer,
+    },
+    PcapNg(PcapNgWriter<BufWriter<File>>),
+}
+
+impl FileWriter {
+    /// Writes the bytes to the file, returning the count of bytes written to it.
+    fn write(&mut self, bytes: &[u8], time: SystemTime) -> io::Result<u64> {
+        match self {
+            FileWriter::Raw(writer) => {
+                writer.write_all(bytes)?;
+                Ok(bytes.len() as u64)
+            }
+            FileWriter::Dlt { writer, framer } => framer.write(writer, bytes, time),
+            FileWriter::PcapNg(writer) => writer.write_payload(bytes, time),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            FileWriter::Raw(writer) | FileWriter::Dlt { writer, .. } => writer.flush(),
+            FileWriter::PcapNg(writer) => writer.flush(),
+        }
+    }
+}
+
+/// Writes raw bytes to recording files, continuing in a new file once the current one
+/// reaches the limits of the configuration.
+pub struct Recorder {
+    config: stypes::StreamRecording,
+    format: RecordingFormat,
+    /// Prefix of the names of the recording files.
+    name: String,
+    writer: Option<FileWriter>,
+    /// Paths of the recording files created so far.
+    files: Vec<PathBuf>,
+    file_size: u64,
+    file_started: Instant,
+}
+
+impl Recorder {
+    /// Creates a recorder writing files named after `label` and the current time. Files are
+    /// created once the first bytes are recorded.
+    pub fn new(config: stypes::StreamRecording, format: RecordingFormat, label: &str) -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let label: String = label
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        Self {
+            config,
+            format,
+            name: format!("{label}_{started}"),
+            writer: None,
+            files: Vec::new(),
+            file_size: 0,
+            file_started: Instant::now(),
+        }
+    }
+
+    /// Paths of the recording files created so far.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn format(&self) -> &RecordingFormat {
+        &self.format
+    }
+
+    /// Writes the bytes to the current recording file.
+    pub fn record(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        if self.writer.is_none() || self.limits_reached() {
+            self.open_next()?;
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        self.file_size += writer.write(bytes, SystemTime::now())?;
+        // Flush directly so recordings can be opened while they are written.
+        writer.flush()
+    }
+
+    fn limits_reached(&self) -> bool {
+        let size_reached = self
+            .config
+            .max_file_size
+            .is_some_and(|max| self.file_size >= max);
+        let duration_reached = self
+            .config
+            .max_file_duration
+            .is_some_and(|max| self.file_started.elapsed().as_secs() >= max);
+        size_reached || duration_reached
+    }
+
+    /// Finishes the current recording file and creates the next one, refusing to overwrite
+    /// an existing file.
+    fn open_next(&mut self) -> io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+        fs::create_dir_all(&self.config.dir)?;
+        let path = self.config.dir.join(format!(
+            "{}_{:03}.{}",
+            self.name,
+            self.files.len(),
+            self.format.extension()
+        ));
+        let file = BufWriter::new(File::create_new(&path)?);
+        let (writer, header_size) = match &self.format {
+            RecordingFormat::Raw { .. } => (FileWriter::Raw(file), 0),
+            RecordingFormat::Dlt => (
+                FileWriter::Dlt {
+                    writer: file,
+                    framer: DltFramer::default(),
+                },
+                0,
+            ),
+            RecordingFormat::PcapNg {
+                protocol,
+                source,
+                destination,
+            } => {
+                let writer = PcapNgWriter::new(file, protocol.clone(), *source, *destination)?;
+                let header_size = writer.written();
+                (FileWriter::PcapNg(writer), header_size)
+            }
+        };
+        debug!("Recording raw stream into {}", path.display());
+        self.writer = Some(writer);
+        self.files.push(path);
+        self.file_size = header_size;
+        self.file_started = Instant::now();
+        Ok(())
+    }
+}
+
+/// Byte source recording the bytes loaded by the wrapped source.
+///
+/// Recording errors don't interrupt the source. They are logged and recording is stopped.
+pub struct RecordingSource<S: ByteSource> {
+    inner: S,
+    recorder: Option<Recorder>,
+}
+
+impl<S: ByteSource> RecordingSource<S> {
+    /// Wraps the source, where the loaded bytes are recorded only if a recorder is provided.
+    pub fn new(inner: S, recorder: Option<Recorder>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<S: ByteSource> ByteSource for RecordingSource<S> {
+    fn consume(&mut self, offset: usize) {
+        self.inner.consume(offset);
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.inner.current_slice()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    async fn load(
+        &mut self,
+        filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        let info = self.inner.load(filter).await?;
+        if let (Some(recorder), Some(info)) = (self.recorder.as_mut(), info.as_ref()) {
+            // Newly loaded bytes are appended to the end of the available ones.
+            let slice = self.inner.current_slice();
+            let loaded = info.newly_loaded_bytes.min(slice.len());
+            if let Err(err) = recorder.record(&slice[slice.len() - loaded..]) {
+                warn!("Recording raw stream failed and is stopped: {err}");
+                self.recorder = None;
+            }
+        }
+        Ok(info)
+    }
+
+    async fn cancel(&mut self) -> Result<(), SourceError> {
+        self.inner.cancel().await
+    }
+
+    async fn income(
+        &mut self,
+        msg: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        self.inner.income(msg).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn config(dir: &Path, max_file_size: Option<u64>) -> stypes::StreamRecording {
+        stypes::StreamRecording {
+            dir: dir.to_owned(),
+            max_file_size,
+            max_file_duration: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chipmunk_recording_{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn record_raw_with_rotation() {
+        let dir = temp_dir("raw");
+        let format = RecordingFormat::Raw {
+            file_format: stypes::FileFormat::Text,
+            extension: "txt",
+        };
+        let mut recorder = Recorder::new(config(&dir, Some(6)), format, "serial /dev/tty0");
+
+        recorder.record(b"one\n").unwrap();
+        recorder.record(b"two\n").unwrap();
+        recorder.record(b"three\n").unwrap();
+
+        let files = recorder.files().to_vec();
+        assert_eq!(files.len(), 2);
+        assert!(
+            files[0]
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("serial--dev-tty0_")
+        );
+        assert_eq!(fs::read(&files[0]).unwrap(), b"one\ntwo\n");
+        assert_eq!(fs::read(&files[1]).unwrap(), b"three\n");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn existing_recording_is_not_overwritten() {
+        let dir = temp_dir("existing");
+        let format = RecordingFormat::Raw {
+            file_format: stypes::FileFormat::Text,
+            extension: "txt",
+        };
+        let mut first = Recorder::new(config(&dir, None), format.clone(), "mqtt");
+        let mut second = Recorder::new(config(&dir, None), format, "mqtt");
+        second.name = first.name.clone();
+
+        first.record(b"first\n").unwrap();
+        let err = second.record(b"second\n").unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read(&first.files()[0]).unwrap(), b"first\n");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn recording_source_records_loaded_bytes() {
+        use crate::binary::raw::BinaryByteSource;
+
+        let dir = temp_dir("source");
+        let input = b"first line\nsecond line\n";
+        let format = RecordingFormat::Raw {
+            file_format: stypes::FileFormat::Text,
+            extension: "txt",
+        };
+        let recorder = Recorder::new(config(&dir, None), format, "process");
+        let mut source = RecordingSource::new(
+            BinaryByteSource::new(std::io::Cursor::new(input)),
+            Some(recorder),
+        );
+
+        while let Some(info) = source.load(None).await.unwrap() {
+            if info.newly_loaded_bytes == 0 {
+                break;
+            }
+            source.consume(info.available_bytes / 2);
+        }
+
+        let files = source.recorder.as_ref().unwrap().files().to_vec();
+        assert_eq!(fs::read(&files[0]).unwrap(), input);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    io::{self, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use etherparse::PacketBuilder;
+
+use crate::TransportProtocol;
+
+/// Block type of the section header block.
+const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+/// Block type of the interface description block.
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+/// Block type of the enhanced packet block.
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const LINKTYPE_ETHERNET: u16 = 1;
+/// Maximum count of payload bytes in one frame, leaving enough space for the headers
+/// within the maximum size of IP packets.
+const MAX_FRAME_PAYLOAD: usize = 65_000;
+/// Addresses of the ethernet headers, since they aren't known for the received payloads.
+const SOURCE_MAC: [u8; 6] = [0x02, 0, 0, 0, 0, 0x01];
+const DESTINATION_MAC: [u8; 6] = [0x02, 0, 0, 0, 0, 0x02];
+const TTL: u8 = 64;
+const TCP_WINDOW: u16 = u16::MAX;
+
+/// Writes payloads of one network connection as ethernet frames to a pcapng file.
+pub(super) struct PcapNgWriter<W: Write> {
+    writer: W,
+    protocol: TransportProtocol,
+    source: SocketAddr,
+    destination: SocketAddr,
+    /// Sequence number of the next TCP segment.
+    tcp_seq: u32,
+    written: u64,
+}
+
+impl<W: Write> PcapNgWriter<W> {
+    /// Creates the writer, writing the header blocks of the file.
+    pub fn new(
+        writer: W,
+        protocol: TransportProtocol,
+        source: SocketAddr,
+        destination: SocketAddr,
+    ) -> io::Result<Self> {
+        let mut pcap_writer = Self {
+            writer,
+            protocol,
+            source,
+            destination,
+            tcp_seq: 0,
+            written: 0,
+        };
+
+        let mut section_header = Vec::with_capacity(16);
+        section_header.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        // Version 1.0
+        section_header.extend_from_slice(&1u16.to_le_bytes());
+        section_header.extend_from_slice(&0u16.to_le_bytes());
+        // Section length is unknown.
+        section_header.extend_from_slice(&(-1i64).to_le_bytes());
+        pcap_writer.write_block(SECTION_HEADER_BLOCK, &section_header)?;
+
+        let mut interface = Vec::with_capacity(8);
+        interface.extend_from_slice(&LINKTYPE_ETHERNET.to_le_bytes());
+        interface.extend_from_slice(&0u16.to_le_bytes());
+        // No limit for the snapshot length.
+        interface.extend_from_slice(&0u32.to_le_bytes());
+        pcap_writer.write_block(INTERFACE_DESCRIPTION_BLOCK, &interface)?;
+
+        Ok(pcap_writer)
+    }
+
+    /// Count of bytes written to the file so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Writes the payload as frames received at the given time, returning the count of
+    /// written bytes.
+    pub fn write_payload(&mut self, payload: &[u8], time: SystemTime) -> io::Result<u64> {
+        let written_before = self.written;
+        // Timestamps are in microseconds, which is the default resolution of interfaces.
+        let micros = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64;
+        for chunk in payload.chunks(MAX_FRAME_PAYLOAD) {
+            let frame = self.build_frame(chunk)?;
+            let mut packet = Vec::with_capacity(20 + frame.len() + 3);
+            // Interface ID
+            packet.extend_from_slice(&0u32.to_le_bytes());
+            packet.extend_from_slice(&((micros >> 32) as u32).to_le_bytes());
+            packet.extend_from_slice(&(micros as u32).to_le_bytes());
+            // Captured and original length.
+            packet.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            packet.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            packet.extend_from_slice(&frame);
+            self.write_block(ENHANCED_PACKET_BLOCK, &packet)?;
+        }
+
+        Ok(self.written - written_before)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn build_frame(&mut self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        let builder = PacketBuilder::ethernet2(SOURCE_MAC, DESTINATION_MAC);
+        let builder = match (self.source.ip(), self.destination.ip()) {
+            (IpAddr::V4(source), IpAddr::V4(destination)) => {
+                builder.ipv4(source.octets(), destination.octets(), TTL)
+            }
+            (source, destination) => {
+                builder.ipv6(to_ipv6(source).octets(), to_ipv6(destination).octets(), TTL)
+            }
+        };
+
+        let mut frame = Vec::new();
+        let result = match self.protocol {
+            TransportProtocol::TCP => {
+                let builder = builder.tcp(
+                    self.source.port(),
+                    self.destination.port(),
+                    self.tcp_seq,
+                    TCP_WINDOW,
+                );
+                self.tcp_seq = self.tcp_seq.wrapping_add(payload.len() as u32);
+                frame.reserve(builder.size(payload.len()));
+                builder.write(&mut frame, payload)
+            }
+            TransportProtocol::UDP | TransportProtocol::Unknown => {
+                let builder = builder.udp(self.source.port(), self.destination.port());
+                frame.reserve(builder.size(payload.len()));
+                builder.write(&mut frame, payload)
+            }
+        };
+        result.map_err(|err| io::Error::other(format!("Building frame failed: {err}")))?;
+
+        Ok(frame)
+    }
+
+    /// Writes a block with the given body, padding the body to 32 bits.
+    fn write_block(&mut self, block_type: u32, body: &[u8]) -> io::Result<()> {
+        let padding = (4 - body.len() % 4) % 4;
+        let total_len = (12 + body.len() + padding) as u32;
+        self.writer.write_all(&block_type.to_le_bytes())?;
+        self.writer.write_all(&total_len.to_le_bytes())?;
+        self.writer.write_all(body)?;
+        self.writer.write_all(&[0; 3][..padding])?;
+        self.writer.write_all(&total_len.to_le_bytes())?;
+        self.written += total_len as u64;
+
+        Ok(())
+    }
+}
+
+fn to_ipv6(addr: IpAddr) -> Ipv6Addr {
+    match addr {
+        IpAddr::V4(addr) if addr == Ipv4Addr::UNSPECIFIED => Ipv6Addr::UNSPECIFIED,
+        IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+        IpAddr::V6(addr) => addr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ByteSource, binary::pcap::ng::PcapngByteSource};
+
+    #[tokio::test]
+    async fn reopen_written_frames() {
+        let source: SocketAddr = "192.168.0.10:3490".parse().unwrap();
+        let destination: SocketAddr = "192.168.0.1:3490".parse().unwrap();
+        let mut writer =
+            PcapNgWriter::new(Vec::new(), TransportProtocol::UDP, source, destination).unwrap();
+        writer.write_payload(b"first", SystemTime::now()).unwrap();
+        writer
+            .write_payload(b"second payload", SystemTime::now())
+            .unwrap();
+        let written = writer.written();
+        let output = writer.writer;
+        assert_eq!(written, output.len() as u64);
+
+        let mut reopened = PcapngByteSource::new(std::io::Cursor::new(output)).unwrap();
+        reopened.load(None).await.unwrap();
+        assert_eq!(reopened.current_slice(), b"first");
+        reopened.consume(5);
+        reopened.load(None).await.unwrap();
+        assert_eq!(reopened.current_slice(), b"second payload");
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::{ErrorKind, Read};
+
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+
+/// Pattern at the start of each DLT storage header.
+const STORAGE_HEADER_PATTERN: &[u8; 4] = b"DLT\x01";
+const STORAGE_HEADER_LEN: usize = 16;
+/// Length of the DLT standard header without its optional fields.
+const STANDARD_HEADER_LEN: usize = 4;
+
+/// Byte source loading one DLT message with its storage header at a time, delivering the
+/// time of the storage header as timestamp of the loaded bytes.
+pub(super) struct DltRecordSource<R: Read> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: Read> DltRecordSource<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Reads exactly the given count of bytes, returning `false` if the input ends before.
+    fn read_exact_or_eof(&mut self, buf: &mut [u8]) -> Result<bool, SourceError> {
+        match self.reader.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(err) => Err(SourceError::Io(err)),
+        }
+    }
+}
+
+impl<R: Read + Send> ByteSource for DltRecordSource<R> {
+    fn consume(&mut self, offset: usize) {
+        self.buffer.drain(..offset.min(self.buffer.len()));
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        let mut headers = [0; STORAGE_HEADER_LEN + STANDARD_HEADER_LEN];
+        if !self.read_exact_or_eof(&mut headers)? {
+            return Ok(None);
+        }
+        if &headers[..4] != STORAGE_HEADER_PATTERN {
+            return Err(SourceError::Unrecoverable(String::from(
+                "Missing DLT storage header. Only DLT files with storage headers can be replayed",
+            )));
+        }
+        let secs = u32::from_le_bytes([headers[4], headers[5], headers[6], headers[7]]);
+        let micros = u32::from_le_bytes([headers[8], headers[9], headers[10], headers[11]]);
+        let msg_len = u16::from_be_bytes([headers[18], headers[19]]) as usize;
+        if msg_len < STANDARD_HEADER_LEN {
+            return Err(SourceError::Unrecoverable(format!(
+                "Invalid length of DLT message: {msg_len}"
+            )));
+        }
+
+        let mut rest = vec![0; msg_len - STANDARD_HEADER_LEN];
+        if !self.read_exact_or_eof(&mut rest)? {
+            // Incomplete message at the end of the file.
+            return Ok(None);
+        }
+        self.buffer.extend_from_slice(&headers);
+        self.buffer.extend_from_slice(&rest);
+
+        let ts = secs as u64 * 1_000 + micros as u64 / 1_000;
+        Ok(Some(ReloadInfo::new(
+            headers.len() + rest.len(),
+            self.buffer.len(),
+            0,
+            Some(ts),
+        )))
+    }
+}
@@ -0,0 +1,360 @@
+//! Replay of recorded traces as streams.
+//!
+//! [`ReplaySource`] delivers the records of pcap, pcapng and DLT files according to their
+//! timestamps, which lets stream sessions be reproduced without the original hardware.
+
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use tokio::time::{Instant, sleep_until};
+
+use crate::{
+    ByteSource, Error as SourceError, ReloadInfo, SourceFilter,
+    binary::pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+};
+
+mod dlt;
+
+use dlt::DltRecordSource;
+
+/// Reader of the records of a trace file.
+enum RecordReader {
+    Pcap(PcapLegacyByteSource<BufReader<File>>),
+    PcapNg(PcapngByteSource<BufReader<File>>),
+    Dlt(DltRecordSource<BufReader<File>>),
+}
+
+impl RecordReader {
+    fn open(path: &Path, format: stypes::FileFormat) -> Result<Self, SourceError> {
+        let open_file = || {
+            File::open(path).map(BufReader::new).map_err(|err| {
+                SourceError::Setup(format!("Opening trace {} failed: {err}", path.display()))
+            })
+        };
+        match format {
+            stypes::FileFormat::PcapLegacy => {
+                Ok(Self::Pcap(PcapLegacyByteSource::new(open_file()?)?))
+            }
+            stypes::FileFormat::PcapNG => Ok(Self::PcapNg(PcapngByteSource::new(open_file()?)?)),
+            stypes::FileFormat::Binary => Ok(Self::Dlt(DltRecordSource::new(open_file()?))),
+            stypes::FileFormat::Text => Err(SourceError::Setup(String::from(
+                "Replaying text files isn't supported",
+            ))),
+        }
+    }
+
+    fn consume(&mut self, offset: usize) {
+        match self {
+            RecordReader::Pcap(source) => source.consume(offset),
+            RecordReader::PcapNg(source) => source.consume(offset),
+            RecordReader::Dlt(source) => source.consume(offset),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            RecordReader::Pcap(source) => source.len(),
+            RecordReader::PcapNg(source) => source.len(),
+            RecordReader::Dlt(source) => source.len(),
+        }
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        match self {
+            RecordReader::Pcap(source) => source.current_slice(),
+            RecordReader::PcapNg(source) => source.current_slice(),
+            RecordReader::Dlt(source) => source.current_slice(),
+        }
+    }
+
+    async fn load(
+        &mut self,
+        filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        match self {
+            RecordReader::Pcap(source) => source.load(filter).await,
+            RecordReader::PcapNg(source) => source.load(filter).await,
+            RecordReader::Dlt(source) => source.load(filter).await,
+        }
+    }
+}
+
+/// Byte source replaying a trace file according to the timestamps of its records.
+///
+/// The replay is controlled with [`stypes::SdeRequest::Replay`] requests.
+pub struct ReplaySource {
+    path: PathBuf,
+    format: stypes::FileFormat,
+    reader: RecordReader,
+    speed: stypes::ReplaySpeed,
+    paused: bool,
+    /// Count of bytes of the reader which are delivered already.
+    delivered: usize,
+    /// Loaded record waiting for its time to be delivered.
+    pending: Option<ReloadInfo>,
+    /// Timestamp of the first record in milliseconds, to which seeking is relative.
+    first_ts: Option<u64>,
+    /// Instant at which the record with the given timestamp is delivered. Timing of the
+    /// following records is calculated relative to it.
+    clock: Option<(Instant, u64)>,
+    /// Offset in milliseconds from the first record to continue the replay at.
+    seek_to: Option<u64>,
+}
+
+impl ReplaySource {
+    pub fn new(config: &stypes::ReplayTransportConfig) -> Result<Self, SourceError> {
+        let reader = RecordReader::open(&config.path, config.format)?;
+        Ok(Self {
+            path: config.path.clone(),
+            format: config.format,
+            reader,
+            speed: config.speed,
+            paused: false,
+            delivered: 0,
+            pending: None,
+            first_ts: None,
+            clock: None,
+            seek_to: None,
+        })
+    }
+
+    /// Opens the trace again and skips the records before the given offset.
+    async fn seek(
+        &mut self,
+        offset: u64,
+        filter: Option<&SourceFilter>,
+    ) -> Result<(), SourceError> {
+        self.reader = RecordReader::open(&self.path, self.format)?;
+        self.delivered = 0;
+        self.pending = None;
+        self.clock = None;
+
+        while let Some(info) = self.reader.load(filter).await? {
+            let ts = info.last_known_ts.unwrap_or_default();
+            let first_ts = *self.first_ts.get_or_insert(ts);
+            if ts.saturating_sub(first_ts) >= offset {
+                self.pending = Some(info);
+                break;
+            }
+            let skipped = self.reader.len();
+            self.reader.consume(skipped);
+        }
+
+        Ok(())
+    }
+
+    fn apply(&mut self, control: stypes::ReplayControl) {
+        match control {
+            stypes::ReplayControl::Pause => self.paused = true,
+            stypes::ReplayControl::Resume => self.paused = false,
+            stypes::ReplayControl::SetSpeed(speed) => self.speed = speed,
+            stypes::ReplayControl::Seek(offset) => self.seek_to = Some(offset),
+        }
+        // Timing continues from the next delivered record.
+        self.clock = None;
+    }
+}
+
+impl ByteSource for ReplaySource {
+    fn consume(&mut self, offset: usize) {
+        let offset = offset.min(self.delivered);
+        self.reader.consume(offset);
+        self.delivered -= offset;
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        // Bytes of the pending record are hidden until it's delivered.
+        let slice = self.reader.current_slice();
+        &slice[..self.delivered.min(slice.len())]
+    }
+
+    fn len(&self) -> usize {
+        self.delivered
+    }
+
+    async fn load(
+        &mut self,
+        filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        if self.paused {
+            // Requests to resume the replay cancel this future.
+            std::future::pending::<()>().await;
+        }
+
+        // State is kept in fields until the record is delivered to keep loading cancel safe.
+        if let Some(offset) = self.seek_to {
+            self.seek(offset, filter).await?;
+            self.seek_to = None;
+        }
+
+        if self.pending.is_none() {
+            let Some(info) = self.reader.load(filter).await? else {
+                return Ok(None);
+            };
+            self.pending = Some(info);
+        }
+
+        if let (Some(ts), stypes::ReplaySpeed::Scaled(factor)) = (
+            self.pending.as_ref().and_then(|info| info.last_known_ts),
+            self.speed,
+        ) {
+            self.first_ts.get_or_insert(ts);
+            let (started, base_ts) = *self.clock.get_or_insert((Instant::now(), ts));
+            let delay = ts.saturating_sub(base_ts) as f64 / factor.max(f64::EPSILON);
+            sleep_until(started + Duration::from_secs_f64(delay / 1_000.)).await;
+        }
+
+        let Some(info) = self.pending.take() else {
+            return Ok(None);
+        };
+        self.delivered = info.available_bytes;
+
+        Ok(Some(info))
+    }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        match request {
+            stypes::SdeRequest::Replay(control) => {
+                self.apply(control);
+                Ok(stypes::SdeResponse { bytes: 0 })
+            }
+            stypes::SdeRequest::WriteText(_)
+            | stypes::SdeRequest::WriteBytes(_)
+            | stypes::SdeRequest::DltControl(_) => Err(SourceError::NotSupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use super::*;
+
+    /// Writes DLT messages with storage headers at the given times in milliseconds, where
+    /// the payload of each message is its index.
+    fn write_dlt_trace(name: &str, times: &[u64]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("chipmunk_replay_{name}.dlt"));
+        let mut file = File::create(&path).unwrap();
+        for (idx, time) in times.iter().enumerate() {
+            file.write_all(b"DLT\x01").unwrap();
+            file.write_all(&((time / 1_000) as u32).to_le_bytes())
+                .unwrap();
+            file.write_all(&((time % 1_000 * 1_000) as u32).to_le_bytes())
+                .unwrap();
+            file.write_all(b"ECU1").unwrap();
+            file.write_all(&[0x21, idx as u8, 0x00, 0x05, idx as u8])
+                .unwrap();
+        }
+        path
+    }
+
+    fn config(path: &Path, speed: stypes::ReplaySpeed) -> stypes::ReplayTransportConfig {
+        stypes::ReplayTransportConfig {
+            path: path.to_owned(),
+            format: stypes::FileFormat::Binary,
+            speed,
+        }
+    }
+
+    /// Loads the next record, returning its index and consuming it.
+    async fn next_record(source: &mut ReplaySource) -> Option<u8> {
+        source.load(None).await.unwrap()?;
+        let idx = *source.current_slice().last().unwrap();
+        assert_eq!(source.len(), 21);
+        source.consume(source.len());
+        Some(idx)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replay_with_original_timing() {
+        let path = write_dlt_trace("timing", &[10_000, 10_500, 12_000]);
+        let mut source = ReplaySource::new(&config(&path, stypes::ReplaySpeed::default())).unwrap();
+
+        let started = Instant::now();
+        assert_eq!(next_record(&mut source).await, Some(0));
+        assert_eq!(next_record(&mut source).await, Some(1));
+        assert_eq!(started.elapsed(), Duration::from_millis(500));
+        assert_eq!(next_record(&mut source).await, Some(2));
+        assert_eq!(started.elapsed(), Duration::from_millis(2_000));
+        assert_eq!(next_record(&mut source).await, None);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replay_scaled_and_unlimited() {
+        let path = write_dlt_trace("speed", &[0, 1_000, 3_000]);
+        let mut source =
+            ReplaySource::new(&config(&path, stypes::ReplaySpeed::Scaled(2.0))).unwrap();
+
+        let started = Instant::now();
+        assert_eq!(next_record(&mut source).await, Some(0));
+        assert_eq!(next_record(&mut source).await, Some(1));
+        assert_eq!(started.elapsed(), Duration::from_millis(500));
+
+        source
+            .income(stypes::SdeRequest::Replay(stypes::ReplayControl::SetSpeed(
+                stypes::ReplaySpeed::Unlimited,
+            )))
+            .await
+            .unwrap();
+        assert_eq!(next_record(&mut source).await, Some(2));
+        assert_eq!(started.elapsed(), Duration::from_millis(500));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pause_and_seek() {
+        let path = write_dlt_trace("seek", &[0, 1_000, 2_000, 3_000]);
+        let mut source = ReplaySource::new(&config(&path, stypes::ReplaySpeed::Unlimited)).unwrap();
+        assert_eq!(next_record(&mut source).await, Some(0));
+
+        source
+            .income(stypes::SdeRequest::Replay(stypes::ReplayControl::Pause))
+            .await
+            .unwrap();
+        let paused = tokio::time::timeout(Duration::from_secs(60), source.load(None)).await;
+        assert!(paused.is_err());
+
+        source
+            .income(stypes::SdeRequest::Replay(stypes::ReplayControl::Resume))
+            .await
+            .unwrap();
+        source
+            .income(stypes::SdeRequest::Replay(stypes::ReplayControl::Seek(
+                2_000,
+            )))
+            .await
+            .unwrap();
+        assert_eq!(next_record(&mut source).await, Some(2));
+
+        // Seeking backwards delivers the records again.
+        source
+            .income(stypes::SdeRequest::Replay(stypes::ReplayControl::Seek(0)))
+            .await
+            .unwrap();
+        assert_eq!(next_record(&mut source).await, Some(0));
+        assert_eq!(next_record(&mut source).await, Some(1));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let config = stypes::ReplayTransportConfig {
+            path: PathBuf::from("trace.txt"),
+            format: stypes::FileFormat::Text,
+            speed: stypes::ReplaySpeed::default(),
+        };
+        assert!(ReplaySource::new(&config).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+//! Encoding of DLT control messages as requested with [`stypes::SdeRequest::DltControl`].
+//!
+//! Messages are encoded without storage header, as they are sent to DLT daemons over
+//! network connections.
+
+use stypes::{DltControlCommand, DltControlRequest};
+
+/// Header type with extended header, ECU ID and protocol version 1. Payloads are
+/// encoded in little endian.
+const HEADER_TYPE: u8 = 0x01 | 0x04 | (1 << 5);
+/// Message info of non-verbose control requests.
+const MESSAGE_INFO: u8 = (3 << 1) | (1 << 4);
+/// Length of the standard header with ECU ID and the extended header.
+const HEADERS_LEN: usize = 8 + 10;
+
+/// Application and context IDs of the sender of control messages.
+const SENDER_APP_ID: &[u8; 4] = b"CHIP";
+const SENDER_CONTEXT_ID: &[u8; 4] = b"CTRL";
+
+/// Communication interface the daemon uses to apply the request.
+const COM_INTERFACE: &[u8; 4] = b"remo";
+
+const SERVICE_SET_LOG_LEVEL: u32 = 0x01;
+const SERVICE_GET_LOG_INFO: u32 = 0x03;
+const SERVICE_SET_DEFAULT_TRACE_STATUS: u32 = 0x12;
+
+/// Options of `get_log_info` requests to include log levels and trace status, and
+/// additionally the descriptions of applications and contexts.
+const LOG_INFO_WITHOUT_DESCRIPTIONS: u8 = 6;
+const LOG_INFO_WITH_DESCRIPTIONS: u8 = 7;
+
+/// Encodes the control request as DLT message with the given message counter.
+///
+/// Returns an error if any of the IDs isn't valid.
+pub fn encode_control_message(request: &DltControlRequest, counter: u8) -> Result<Vec<u8>, String> {
+    let mut payload = Vec::with_capacity(17);
+    match &request.command {
+        DltControlCommand::SetLogLevel {
+            app_id,
+            context_id,
+            level,
+        } => {
+            payload.extend_from_slice(&SERVICE_SET_LOG_LEVEL.to_le_bytes());
+            payload.extend_from_slice(&encode_id(app_id, "Application")?);
+            payload.extend_from_slice(&encode_id(context_id, "Context")?);
+            payload.push(*level as u8);
+        }
+        DltControlCommand::GetLogInfo {
+            app_id,
+            context_id,
+            with_descriptions,
+        } => {
+            payload.extend_from_slice(&SERVICE_GET_LOG_INFO.to_le_bytes());
+            payload.push(if *with_descriptions {
+                LOG_INFO_WITH_DESCRIPTIONS
+            } else {
+                LOG_INFO_WITHOUT_DESCRIPTIONS
+            });
+            payload.extend_from_slice(&encode_id(app_id, "Application")?);
+            payload.extend_from_slice(&encode_id(context_id, "Context")?);
+        }
+        DltControlCommand::SetDefaultTraceStatus(enabled) => {
+            payload.extend_from_slice(&SERVICE_SET_DEFAULT_TRACE_STATUS.to_le_bytes());
+            payload.push(u8::from(*enabled));
+        }
+    }
+    payload.extend_from_slice(COM_INTERFACE);
+
+    let ecu_id = encode_id(&request.ecu_id, "ECU")?;
+    let len = (HEADERS_LEN + payload.len()) as u16;
+
+    let mut message = Vec::with_capacity(HEADERS_LEN + payload.len());
+    message.push(HEADER_TYPE);
+    message.push(counter);
+    message.extend_from_slice(&len.to_be_bytes());
+    message.extend_from_slice(&ecu_id);
+    message.push(MESSAGE_INFO);
+    // Control messages are sent without verbose arguments.
+    message.push(0);
+    message.extend_from_slice(SENDER_APP_ID);
+    message.extend_from_slice(SENDER_CONTEXT_ID);
+    message.extend_from_slice(&payload);
+
+    Ok(message)
+}
+
+/// Encodes the ID with up to 4 ASCII characters, padded with zeros.
+fn encode_id(id: &str, kind: &str) -> Result<[u8; 4], String> {
+    let id = id.trim();
+    if id.len() > 4 || !id.is_ascii() {
+        return Err(format!(
+            "{kind} ID '{id}' must have up to 4 ASCII characters"
+        ));
+    }
+    let mut encoded = [0u8; 4];
+    encoded[..id.len()].copy_from_slice(id.as_bytes());
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use stypes::DltLogLevel;
+
+    use super::*;
+
+    fn request(command: DltControlCommand) -> DltControlRequest {
+        DltControlRequest {
+            ecu_id: String::from("ECU1"),
+            command,
+        }
+    }
+
+    #[test]
+    fn encode_set_log_level() {
+        let message = encode_control_message(
+            &request(DltControlCommand::SetLogLevel {
+                app_id: String::from("APP"),
+                context_id: String::from("CTX1"),
+                level: DltLogLevel::Debug,
+            }),
+            7,
+        )
+        .unwrap();
+
+        let mut expected = vec![0x25, 7, 0, 35];
+        expected.extend_from_slice(b"ECU1");
+        expected.extend_from_slice(&[0x16, 0]);
+        expected.extend_from_slice(b"CHIPCTRL");
+        expected.extend_from_slice(&[0x01, 0, 0, 0]);
+        expected.extend_from_slice(b"APP\0CTX1");
+        expected.push(5);
+        expected.extend_from_slice(b"remo");
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn encode_get_log_info_and_trace_status() {
+        let message = encode_control_message(
+            &request(DltControlCommand::GetLogInfo {
+                app_id: String::new(),
+                context_id: String::new(),
+                with_descriptions: true,
+            }),
+            0,
+        )
+        .unwrap();
+        assert_eq!(message.len(), 35);
+        assert_eq!(&message[18..], b"\x03\0\0\0\x07\0\0\0\0\0\0\0\0remo");
+
+        let message =
+            encode_control_message(&request(DltControlCommand::SetDefaultTraceStatus(true)), 0)
+                .unwrap();
+        assert_eq!(u16::from_be_bytes([message[2], message[3]]), 27);
+        assert_eq!(&message[18..], b"\x12\0\0\0\x01remo");
+    }
+
+    #[test]
+    fn reject_invalid_ids() {
+        let invalid = DltControlRequest {
+            ecu_id: String::from("ECU12"),
+            command: DltControlCommand::SetDefaultTraceStatus(false),
+        };
+        assert!(encode_control_message(&invalid, 0).is_err());
+
+        let invalid = request(DltControlCommand::SetLogLevel {
+            app_id: String::from("ÄPP"),
+            context_id: String::new(),
+            level: DltLogLevel::Off,
+        });
+        assert!(encode_control_message(&invalid, 0).is_err());
+    }
+}
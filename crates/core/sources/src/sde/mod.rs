@@ -1,3 +1,5 @@
+pub mod dlt;
+
 use tokio::sync::{
     mpsc::{UnboundedReceiver, UnboundedSender},
     oneshot,
@@ -1 +1,2 @@
+pub mod reconnect;
 pub mod serialport;
@@ -0,0 +1,145 @@
+use std::time::Duration;
+use stypes::{
+    SerialReadMode, SerialReconnect, SerialTransportConfig, SerialUsbId, SourceConnectionState,
+};
+use tokio::{sync::watch, task::JoinHandle};
+use tokio_serial::{SerialPortType, SerialStream};
+
+use crate::Error as SourceError;
+
+/// The time interval between the attempts to reopen the serial port.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Struct to manage reopening the serial port once its device is disconnected.
+///
+/// Reconnecting doesn't give up, since replugging a device can take any time. It ends once
+/// the port is reopened or the source is dropped.
+pub struct SerialReconnecter {
+    config: SerialTransportConfig,
+    /// Channel to send the state of the connection.
+    state_sender: Option<watch::Sender<SourceConnectionState>>,
+    /// Handle of spawned reconnecting task.
+    pub task_handle: Option<JoinHandle<SerialStream>>,
+}
+
+impl SerialReconnecter {
+    pub fn new(
+        config: SerialTransportConfig,
+        state_sender: Option<watch::Sender<SourceConnectionState>>,
+    ) -> Self {
+        Self {
+            config,
+            state_sender,
+            task_handle: None,
+        }
+    }
+
+    pub fn read_mode(&self) -> SerialReadMode {
+        self.config.read_mode
+    }
+
+    /// Spawns a reconnect task setting its handle to the field [`Self::task_handle`]
+    ///
+    /// # Panics:
+    ///
+    /// This function panics if there is an already spawned task which hadn't been consumed yet.
+    pub fn spawn_reconnect(&mut self) {
+        assert!(
+            self.task_handle.is_none(),
+            "There must be no spawned reconnect task when spawn reconnect is called"
+        );
+        let handle = tokio::spawn(reconnect(self.config.clone(), self.state_sender.clone()));
+
+        self.task_handle = Some(handle);
+    }
+}
+
+impl Drop for SerialReconnecter {
+    fn drop(&mut self) {
+        if let Some(task) = self.task_handle.take() {
+            task.abort();
+        }
+    }
+}
+
+async fn reconnect(
+    config: SerialTransportConfig,
+    state_sender: Option<watch::Sender<SourceConnectionState>>,
+) -> SerialStream {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        if let Some(sender) = &state_sender {
+            sender.send_replace(SourceConnectionState::Reconnecting { attempts });
+        }
+        log::info!("Reconnecting to serial port. Attempt: {attempts}");
+
+        match find_port(&config).and_then(|path| super::serialport::open_port(&config, &path)) {
+            Ok(port) => {
+                if let Some(sender) = &state_sender {
+                    sender.send_replace(SourceConnectionState::Connected);
+                }
+                return port;
+            }
+            Err(err) => {
+                log::debug!("Got following error while trying to reconnect: {err}");
+            }
+        }
+
+        tokio::time::sleep(RECONNECT_INTERVAL).await;
+    }
+}
+
+/// Returns the path of the port to reopen.
+fn find_port(config: &SerialTransportConfig) -> Result<String, SourceError> {
+    let id = match &config.reconnect {
+        SerialReconnect::Disabled | SerialReconnect::ByPath => return Ok(config.path.clone()),
+        SerialReconnect::ByUsbId(id) => id,
+    };
+    let ports = tokio_serial::available_ports()
+        .map_err(|err| SourceError::Setup(format!("Fail to list serial ports: {err}")))?;
+    ports
+        .into_iter()
+        .find_map(|port| match port.port_type {
+            SerialPortType::UsbPort(info)
+                if usb_id_matches(id, info.vid, info.pid, info.serial_number.as_deref()) =>
+            {
+                Some(port.port_name)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| SourceError::Setup(format!("USB serial device {id} isn't connected")))
+}
+
+/// Checks if the USB device matches the identity. The serial number is compared only if the
+/// identity defines it.
+fn usb_id_matches(id: &SerialUsbId, vid: u16, pid: u16, serial_number: Option<&str>) -> bool {
+    id.vid == vid
+        && id.pid == pid
+        && id
+            .serial_number
+            .as_deref()
+            .is_none_or(|expected| serial_number == Some(expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_usb_identity() {
+        let mut id = SerialUsbId {
+            vid: 0x0403,
+            pid: 0x6001,
+            serial_number: None,
+        };
+        assert!(usb_id_matches(&id, 0x0403, 0x6001, Some("A1")));
+        assert!(usb_id_matches(&id, 0x0403, 0x6001, None));
+        assert!(!usb_id_matches(&id, 0x0403, 0x6015, Some("A1")));
+
+        id.serial_number = Some(String::from("A1"));
+        assert!(usb_id_matches(&id, 0x0403, 0x6001, Some("A1")));
+        assert!(!usb_id_matches(&id, 0x0403, 0x6001, Some("B2")));
+        assert!(!usb_id_matches(&id, 0x0403, 0x6001, None));
+    }
+}
@@ -1,14 +1,26 @@
 use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
 use bufread::DeqBuffer;
-use bytes::{BufMut, BytesMut};
-use futures::{
-    SinkExt,
-    stream::{SplitSink, SplitStream, StreamExt},
+use bytes::BytesMut;
+use std::io;
+use stypes::{SerialReadMode, SerialReconnect, SourceConnectionState};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt, ReadHalf, WriteHalf},
+    sync::watch,
+    time::{Duration, sleep},
 };
-use std::{io, str};
-use tokio::time::{Duration, sleep};
 use tokio_serial::{DataBits, FlowControl, Parity, SerialPortBuilderExt, SerialStream, StopBits};
-use tokio_util::codec::{Decoder, Encoder, Framed};
+use tokio_stream::StreamExt;
+use tokio_util::codec::{Decoder, FramedRead};
+
+use super::reconnect::SerialReconnecter;
+
+/// Capacity of the buffer while delivering lines.
+const LINES_BUFF_SIZE: usize = 8192;
+/// Capacity of the buffer while delivering the received bytes unchanged, which must be able
+/// to hold the biggest messages of binary formats.
+const RAW_BUFF_SIZE: usize = 1024 * 1024;
+/// Maximum count of bytes read from the port at once in raw mode.
+const RAW_READ_SIZE: usize = 4096;
 
 struct LineCodec;
 
@@ -18,25 +30,16 @@ impl Decoder for LineCodec {
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         match &src.iter().position(|b| *b == b'\n') {
-            Some(n) => match str::from_utf8(&src.split_to(n + 1)) {
-                Ok(s) => Ok(Some(s.to_string())),
-                Err(err) => Err(io::Error::other(format!("Failed to format string: {err}"))),
-            },
+            // Invalid characters are replaced since failing here can't be told apart from
+            // losing the connection to the device.
+            Some(n) => Ok(Some(
+                String::from_utf8_lossy(&src.split_to(n + 1)).into_owned(),
+            )),
             None => Ok(None),
         }
     }
 }
 
-impl Encoder<Vec<u8>> for LineCodec {
-    type Error = io::Error;
-
-    fn encode(&mut self, item: Vec<u8>, dst: &mut BytesMut) -> Result<(), Self::Error> {
-        dst.reserve(item.len());
-        dst.put(&item as &[u8]);
-        Ok(())
-    }
-}
-
 fn data_bits(data_bits: &u8) -> DataBits {
     match data_bits {
         5 => DataBits::Five,
@@ -73,12 +76,102 @@ fn stop_bits(stop_bits: &u8) -> StopBits {
     }
 }
 
+/// Opens the serial port on the given path with the settings of the configuration.
+pub(super) fn open_port(
+    config: &stypes::SerialTransportConfig,
+    path: &str,
+) -> Result<SerialStream, SourceError> {
+    match tokio_serial::new(path, config.baud_rate)
+        .data_bits(data_bits(&config.data_bits))
+        .flow_control(flow_control(&config.flow_control))
+        .parity(parity(&config.parity))
+        .stop_bits(stop_bits(&config.stop_bits))
+        .open_native_async()
+    {
+        // We get warning on windows because `port` doesn't need to be mutated there
+        #[cfg_attr(windows, allow(unused_mut))]
+        Ok(mut port) => {
+            #[cfg(unix)]
+            if let Err(err) = port.set_exclusive(config.exclusive) {
+                return Err(SourceError::Setup(format!(
+                    "Unable to set serial port {} exclusive to {}: {}",
+                    path, config.exclusive, err
+                )));
+            }
+            Ok(port)
+        }
+        Err(err) => Err(SourceError::Setup(format!(
+            "Failed to open serial port {}: {}",
+            path, err
+        ))),
+    }
+}
+
+/// Reading side of the serial port.
+enum PortReader {
+    Lines(FramedRead<ReadHalf<SerialStream>, LineCodec>),
+    Raw {
+        reader: ReadHalf<SerialStream>,
+        tmp_buffer: Vec<u8>,
+    },
+}
+
+impl PortReader {
+    /// Reads the next line or the next received bytes into the buffer, returning the count
+    /// of stored bytes or `None` once the port is closed.
+    async fn read_into(&mut self, buffer: &mut DeqBuffer) -> Result<Option<usize>, SourceError> {
+        match self {
+            PortReader::Lines(lines) => match lines.next().await {
+                Some(Ok(received)) => {
+                    buffer.write_from(received.as_bytes());
+                    Ok(Some(received.len()))
+                }
+                Some(Err(err)) => Err(SourceError::Setup(format!("Failed to read stream: {err}"))),
+                None => Ok(None),
+            },
+            PortReader::Raw { reader, tmp_buffer } => {
+                let len = reader
+                    .read(tmp_buffer)
+                    .await
+                    .map_err(|err| SourceError::Setup(format!("Failed to read stream: {err}")))?;
+                if len == 0 {
+                    return Ok(None);
+                }
+                let added = buffer.write_from(&tmp_buffer[..len]);
+                if added < len {
+                    return Err(SourceError::Unrecoverable(format!(
+                        "Internal buffer maximum capcity reached.\
+                        Read from serial port: {len}, Copied to buffer: {added}"
+                    )));
+                }
+                Ok(Some(len))
+            }
+        }
+    }
+}
+
+fn split_port(
+    port: SerialStream,
+    read_mode: SerialReadMode,
+) -> (PortReader, WriteHalf<SerialStream>) {
+    let (reader, writer) = tokio::io::split(port);
+    let reader = match read_mode {
+        SerialReadMode::Lines => PortReader::Lines(FramedRead::new(reader, LineCodec)),
+        SerialReadMode::Raw => PortReader::Raw {
+            reader,
+            tmp_buffer: vec![0u8; RAW_READ_SIZE],
+        },
+    };
+    (reader, writer)
+}
+
 pub struct SerialSource {
-    write_stream: SplitSink<Framed<SerialStream, LineCodec>, Vec<u8>>,
-    read_stream: SplitStream<Framed<SerialStream, LineCodec>>,
+    reader: PortReader,
+    writer: WriteHalf<SerialStream>,
     buffer: DeqBuffer,
-    amount: usize,
+    path: String,
     send_data_delay: u8,
+    reconnecter: Option<SerialReconnecter>,
 }
 
 // Do we need to do some actions of destructor?
@@ -89,39 +182,74 @@ pub struct SerialSource {
 // }
 
 impl SerialSource {
-    pub fn new(config: &stypes::SerialTransportConfig) -> Result<Self, SourceError> {
-        match tokio_serial::new(config.path.as_str(), config.baud_rate)
-            .data_bits(data_bits(&config.data_bits))
-            .flow_control(flow_control(&config.flow_control))
-            .parity(parity(&config.parity))
-            .stop_bits(stop_bits(&config.stop_bits))
-            .open_native_async()
+    /// Opens the serial port of the configuration.
+    ///
+    /// The port is reopened once the device is disconnected if reconnecting is enabled in
+    /// the configuration, where the changes of the connection state are sent to
+    /// `state_sender` if provided.
+    pub fn new(
+        config: &stypes::SerialTransportConfig,
+        state_sender: Option<watch::Sender<SourceConnectionState>>,
+    ) -> Result<Self, SourceError> {
+        let port = open_port(config, &config.path)?;
+        let (reader, writer) = split_port(port, config.read_mode);
+        let buff_size = match config.read_mode {
+            SerialReadMode::Lines => LINES_BUFF_SIZE,
+            SerialReadMode::Raw => RAW_BUFF_SIZE,
+        };
+        let reconnecter = match config.reconnect {
+            SerialReconnect::Disabled => None,
+            SerialReconnect::ByPath | SerialReconnect::ByUsbId(_) => {
+                Some(SerialReconnecter::new(config.clone(), state_sender))
+            }
+        };
+        Ok(Self {
+            reader,
+            writer,
+            buffer: DeqBuffer::new(buff_size),
+            path: config.path.clone(),
+            send_data_delay: config.send_data_delay,
+            reconnecter,
+        })
+    }
+
+    /// Checks if the buffer can take the next received bytes in raw mode, flushing it
+    /// if needed.
+    fn can_load(&mut self) -> bool {
+        if !matches!(self.reader, PortReader::Raw { .. }) {
+            return true;
+        }
+        if self.buffer.write_available() < RAW_READ_SIZE {
+            self.buffer.flush();
+        }
+        self.buffer.write_available() >= RAW_READ_SIZE
+    }
+
+    async fn write(&mut self, bytes: &[u8]) -> Result<(), SourceError> {
+        if self
+            .reconnecter
+            .as_ref()
+            .is_some_and(|rec| rec.task_handle.is_some())
         {
-            // We get warning on windows because `port` doesn't need to be mutated there
-            #[cfg_attr(windows, allow(unused_mut))]
-            Ok(mut port) => {
-                #[cfg(unix)]
-                if let Err(err) = port.set_exclusive(config.exclusive) {
-                    return Err(SourceError::Setup(format!(
-                        "Unable to set serial port {} exclusive to {}: {}",
-                        config.path, config.exclusive, err
-                    )));
-                }
-                let stream = LineCodec.framed(port);
-                let (write_stream, read_stream) = stream.split();
-                Ok(Self {
-                    write_stream,
-                    read_stream,
-                    buffer: DeqBuffer::new(8192),
-                    amount: 0,
-                    send_data_delay: config.send_data_delay,
-                })
+            return Err(SourceError::Setup(String::from(
+                "Serial port is disconnected at the moment",
+            )));
+        }
+        if self.send_data_delay == 0 {
+            self.writer
+                .write_all(bytes)
+                .await
+                .map_err(SourceError::Io)?;
+        } else {
+            for byte in bytes {
+                self.writer
+                    .write_all(&[*byte])
+                    .await
+                    .map_err(SourceError::Io)?;
+                sleep(Duration::from_millis(self.send_data_delay as u64)).await;
             }
-            Err(err) => Err(SourceError::Setup(format!(
-                "Failed to open serial port {}: {}",
-                config.path, err
-            ))),
         }
+        self.writer.flush().await.map_err(SourceError::Io)
     }
 }
 
@@ -130,29 +258,47 @@ impl ByteSource for SerialSource {
         &mut self,
         _filter: Option<&SourceFilter>,
     ) -> Result<Option<ReloadInfo>, SourceError> {
-        // Implementation is cancel-safe here because there is one await call on a stream only.
-        match self.read_stream.next().await {
-            Some(result) => match result {
-                Ok(received) => {
-                    self.amount = received.len();
-                    if self.amount == 0 {
-                        return Ok(None);
-                    }
-                    self.buffer.write_from(received.as_bytes());
+        if !self.can_load() {
+            // Buffer is full and the parser needs to consume the available bytes first.
+            let available_bytes = self.buffer.read_available();
+            return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+        }
+
+        // Implementation is cancel-safe here because awaiting the reconnect task can be
+        // repeated and there is one await call on the port only.
+        loop {
+            if let Some(reconnecter) = self.reconnecter.as_mut()
+                && let Some(handle) = reconnecter.task_handle.as_mut()
+            {
+                let port = handle.await.map_err(|err| {
+                    SourceError::Unrecoverable(format!(
+                        "Reconnect to serial port task panicked. Error: {err}"
+                    ))
+                })?;
+                reconnecter.task_handle = None;
+                (self.reader, self.writer) = split_port(port, reconnecter.read_mode());
+            }
+
+            let disconnected = match self.reader.read_into(&mut self.buffer).await {
+                Ok(Some(amount)) => {
+                    let available_bytes = self.buffer.read_available();
+                    return Ok(Some(ReloadInfo::new(amount, available_bytes, 0, None)));
                 }
-                Err(err) => {
-                    return Err(SourceError::Setup(format!("Failed to read stream: {err}")));
+                Ok(None) => {
+                    SourceError::Setup(String::from("Error awaiting future in reading (RX) stream"))
                 }
-            },
-            None => {
-                return Err(SourceError::Setup(
-                    "Error awaiting future in reading (RX) stream".to_string(),
-                ));
-            }
-        }
+                Err(err) => err,
+            };
 
-        let available_bytes = self.buffer.read_available();
-        Ok(Some(ReloadInfo::new(self.amount, available_bytes, 0, None)))
+            let Some(reconnecter) = self.reconnecter.as_mut() else {
+                return Err(disconnected);
+            };
+            warn!(
+                "Serial port {} is disconnected: {disconnected}. Reconnecting",
+                self.path
+            );
+            reconnecter.spawn_reconnect();
+        }
     }
 
     fn current_slice(&self) -> &[u8] {
@@ -175,44 +321,15 @@ impl ByteSource for SerialSource {
         &mut self,
         request: stypes::SdeRequest,
     ) -> Result<stypes::SdeResponse, SourceError> {
-        Ok(match request {
-            stypes::SdeRequest::WriteText(mut str) => {
-                let len = str.len();
-                if self.send_data_delay == 0 {
-                    self.write_stream
-                        .send(str.as_bytes().to_vec())
-                        .await
-                        .map_err(SourceError::Io)?;
-                } else {
-                    while !str.is_empty() {
-                        self.write_stream
-                            .send(str.drain(0..1).collect::<String>().as_bytes().to_vec())
-                            .await
-                            .map_err(SourceError::Io)?;
-                        sleep(Duration::from_millis(self.send_data_delay as u64)).await;
-                    }
-                }
-                stypes::SdeResponse { bytes: len }
+        let bytes = match request {
+            stypes::SdeRequest::WriteText(ref str) => str.as_bytes(),
+            stypes::SdeRequest::WriteBytes(ref bytes) => bytes,
+            stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => {
+                return Err(SourceError::NotSupported);
             }
-            stypes::SdeRequest::WriteBytes(mut bytes) => {
-                let len = bytes.len();
-                if self.send_data_delay == 0 {
-                    self.write_stream
-                        .send(bytes)
-                        .await
-                        .map_err(SourceError::Io)?;
-                } else {
-                    while !bytes.is_empty() {
-                        self.write_stream
-                            .send(bytes.drain(0..1).collect::<Vec<u8>>())
-                            .await
-                            .map_err(SourceError::Io)?;
-                        sleep(Duration::from_millis(self.send_data_delay as u64)).await;
-                    }
-                }
-                stypes::SdeResponse { bytes: len }
-            }
-        })
+        };
+        self.write(bytes).await?;
+        Ok(stypes::SdeResponse { bytes: bytes.len() })
     }
 }
 
@@ -2,6 +2,8 @@ use bufread::DeqBuffer;
 
 pub mod tcp;
 pub mod udp;
+#[cfg(unix)]
+pub mod unix;
 
 /// Maximum packet size for the internal temp buffer of socket byte-sources.
 const MAX_DATAGRAM_SIZE: usize = 65_507;
@@ -1,10 +1,13 @@
 use std::{net::SocketAddr, time::Duration};
 
-use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter, sde::dlt};
 use bufread::DeqBuffer;
 use reconnect::{ReconnectInfo, ReconnectResult, TcpReconnecter};
 use socket2::{SockRef, TcpKeepalive};
-use tokio::net::TcpStream;
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+};
 
 use super::{BuffCapacityState, MAX_BUFF_SIZE, MAX_DATAGRAM_SIZE, handle_buff_capacity};
 
@@ -31,6 +34,8 @@ pub struct TcpSource {
     socket: TcpStream,
     tmp_buffer: Vec<u8>,
     reconnecter: Option<TcpReconnecter>,
+    /// Counter of the DLT control messages sent to the remote side.
+    dlt_counter: u8,
 }
 
 impl TcpSource {
@@ -48,6 +53,28 @@ impl TcpSource {
             socket,
             tmp_buffer: vec![0u8; MAX_DATAGRAM_SIZE],
             reconnecter,
+            dlt_counter: 0,
+        })
+    }
+
+    /// Creates a source reading from an already established connection, such as
+    /// one accepted by [`TcpServer`].
+    ///
+    /// Reconnecting isn't supported on such sources since the remote side is
+    /// responsible for establishing the connection.
+    pub fn from_stream(
+        socket: TcpStream,
+        keepalive: Option<&KeepAliveConfig>,
+    ) -> Result<Self, std::io::Error> {
+        if let Some(keepalive_config) = keepalive {
+            Self::set_keepalive(&socket, keepalive_config)?;
+        }
+        Ok(Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            socket,
+            tmp_buffer: vec![0u8; MAX_DATAGRAM_SIZE],
+            reconnecter: None,
+            dlt_counter: 0,
         })
     }
 
@@ -57,15 +84,81 @@ impl TcpSource {
     ) -> std::io::Result<TcpStream> {
         let socket = TcpStream::connect(binding_address).await?;
         if let Some(keepalive_config) = keep_alive {
-            let socket_ref = SockRef::from(&socket);
-            let keepalive = TcpKeepalive::new()
-                .with_time(keepalive_config.time)
-                .with_interval(keepalive_config.interval);
-            socket_ref.set_tcp_keepalive(&keepalive)?;
+            Self::set_keepalive(&socket, keepalive_config)?;
         }
 
         Ok(socket)
     }
+
+    fn set_keepalive(
+        socket: &TcpStream,
+        keepalive_config: &KeepAliveConfig,
+    ) -> std::io::Result<()> {
+        let socket_ref = SockRef::from(socket);
+        let keepalive = TcpKeepalive::new()
+            .with_time(keepalive_config.time)
+            .with_interval(keepalive_config.interval);
+        socket_ref.set_tcp_keepalive(&keepalive)
+    }
+
+    async fn write(&mut self, bytes: &[u8]) -> Result<usize, SourceError> {
+        if self
+            .reconnecter
+            .as_ref()
+            .is_some_and(|rec| rec.task_handle.is_some())
+        {
+            return Err(SourceError::Setup(String::from(
+                "Connection is lost while reconnecting",
+            )));
+        }
+        self.socket
+            .write_all(bytes)
+            .await
+            .map_err(SourceError::Io)?;
+        Ok(bytes.len())
+    }
+}
+
+/// Listens on a local address and provides a [`TcpSource`] for each incoming client.
+///
+/// The server doesn't stop on disconnected clients, and [`TcpServer::accept()`] can be
+/// called again to wait for the next client.
+pub struct TcpServer {
+    listener: TcpListener,
+    keepalive: Option<KeepAliveConfig>,
+}
+
+impl TcpServer {
+    /// Binds the server to the given address without accepting any connections yet.
+    pub async fn bind(
+        addr: &str,
+        keepalive: Option<KeepAliveConfig>,
+    ) -> Result<Self, std::io::Error> {
+        let binding_address: SocketAddr = addr.parse().map_err(std::io::Error::other)?;
+        let listener = TcpListener::bind(binding_address).await?;
+        Ok(Self {
+            listener,
+            keepalive,
+        })
+    }
+
+    /// Returns the local address the server is listening on.
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for the next client and returns a source for it with its remote address.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. No connection will be lost if it's dropped before
+    /// completion.
+    pub async fn accept(&self) -> Result<(TcpSource, SocketAddr), std::io::Error> {
+        let (socket, peer) = self.listener.accept().await?;
+        debug!("Accepted TCP client {peer}");
+        let source = TcpSource::from_stream(socket, self.keepalive.as_ref())?;
+        Ok((source, peer))
+    }
 }
 
 impl ByteSource for TcpSource {
@@ -166,6 +259,24 @@ impl ByteSource for TcpSource {
     fn len(&self) -> usize {
         self.buffer.read_available()
     }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        let bytes = match request {
+            stypes::SdeRequest::WriteText(text) => self.write(text.as_bytes()).await?,
+            stypes::SdeRequest::WriteBytes(bytes) => self.write(&bytes).await?,
+            stypes::SdeRequest::DltControl(request) => {
+                let message = dlt::encode_control_message(&request, self.dlt_counter)
+                    .map_err(SourceError::Setup)?;
+                self.dlt_counter = self.dlt_counter.wrapping_add(1);
+                self.write(&message).await?
+            }
+            stypes::SdeRequest::Replay(_) => return Err(SourceError::NotSupported),
+        };
+        Ok(stypes::SdeResponse { bytes })
+    }
 }
 
 #[cfg(test)]
@@ -176,7 +287,7 @@ mod tests {
     use reconnect::ReconnectStateMsg;
     use std::time::Duration;
     use tokio::{
-        io::AsyncWriteExt,
+        io::{AsyncReadExt, AsyncWriteExt},
         net::TcpListener,
         task::yield_now,
         time::{Instant, sleep, timeout},
@@ -580,4 +691,104 @@ mod tests {
 
         assert!(rec_res.is_ok());
     }
+
+    /// Connects to the server and sends mock messages, sleeping for the specified
+    /// duration (in milliseconds) between messages.
+    async fn connect_and_send(server: &str, sleep_mili_sec: u64) {
+        let mut stream = TcpStream::connect(server).await.unwrap();
+        for msg in MESSAGES {
+            stream
+                .write_all(msg.as_bytes())
+                .await
+                .expect("could not send on socket");
+            stream.flush().await.expect("flush message should work");
+            sleep(Duration::from_millis(sleep_mili_sec)).await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_server_reload() {
+        static SERVER: &str = "127.0.0.1:4008";
+        let server = TcpServer::bind(SERVER, None).await.unwrap();
+        let send_handle = tokio::spawn(async move {
+            connect_and_send(SERVER, 100).await;
+        });
+
+        let (mut tcp_source, _) = server.accept().await.unwrap();
+        for msg in MESSAGES {
+            tcp_source.load(None).await.expect("reload failed");
+            assert_eq!(tcp_source.current_slice(), msg.as_bytes());
+            tcp_source.consume(msg.len());
+        }
+
+        assert!(send_handle.await.is_ok());
+    }
+
+    /// Server must keep accepting new clients after previous ones disconnected, while
+    /// sources of disconnected clients must report that no more bytes are loaded.
+    #[tokio::test(start_paused = true)]
+    async fn test_server_accept_after_disconnect() {
+        static SERVER: &str = "127.0.0.1:4009";
+        let server = TcpServer::bind(SERVER, None).await.unwrap();
+        let send_handle = tokio::spawn(async move {
+            for _ in 0..2 {
+                connect_and_send(SERVER, 30).await;
+            }
+        });
+
+        for _ in 0..2 {
+            let (mut tcp_source, _) = server.accept().await.unwrap();
+            for msg in MESSAGES {
+                tcp_source.load(None).await.expect("reload failed");
+                assert_eq!(tcp_source.current_slice(), msg.as_bytes());
+                tcp_source.consume(msg.len());
+            }
+            let info = tcp_source
+                .load(None)
+                .await
+                .expect("reload failed")
+                .expect("reload info expected");
+            assert_eq!(info.newly_loaded_bytes, 0);
+            assert_eq!(info.available_bytes, 0);
+        }
+
+        assert!(send_handle.await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sde_text_and_dlt_control() {
+        static SERVER: &str = "127.0.0.1:4010";
+        let listener = TcpListener::bind(&SERVER).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut received = vec![0u8; 4 + 27];
+            stream.read_exact(&mut received).await.unwrap();
+            received
+        });
+
+        let mut tcp_source = TcpSource::new(SERVER, None, None).await.unwrap();
+        let response = tcp_source
+            .income(stypes::SdeRequest::WriteText(String::from("ping")))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 4);
+
+        let request = stypes::DltControlRequest {
+            ecu_id: String::from("ECU1"),
+            command: stypes::DltControlCommand::SetDefaultTraceStatus(true),
+        };
+        let response = tcp_source
+            .income(stypes::SdeRequest::DltControl(request.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 27);
+
+        let received = server.await.unwrap();
+        assert_eq!(&received[..4], b"ping");
+        assert_eq!(
+            received[4..],
+            dlt::encode_control_message(&request, 0).unwrap()
+        );
+        assert_eq!(tcp_source.dlt_counter, 1);
+    }
 }
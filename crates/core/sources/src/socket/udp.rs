@@ -1,8 +1,11 @@
 use bufread::DeqBuffer;
 use log::trace;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use thiserror::Error;
-use tokio::net::{ToSocketAddrs, UdpSocket};
+use tokio::{
+    net::{ToSocketAddrs, UdpSocket},
+    sync::mpsc,
+};
 
 use super::{MAX_BUFF_SIZE, MAX_DATAGRAM_SIZE};
 use crate::{
@@ -24,17 +27,53 @@ pub enum UdpSourceError {
     Config(stypes::NetError),
 }
 
+/// Sender whose datagrams are accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllowedSender {
+    /// Any port on the host with this address.
+    Host(IpAddr),
+    /// Exactly this address and port.
+    Socket(SocketAddr),
+}
+
+impl AllowedSender {
+    /// Parses a sender given as an IP address or as a socket address including the port.
+    pub fn parse(sender: &str) -> Result<Self, std::net::AddrParseError> {
+        let sender = sender.trim();
+        match sender.parse::<IpAddr>() {
+            Ok(addr) => Ok(Self::Host(addr)),
+            Err(_) => sender.parse::<SocketAddr>().map(Self::Socket),
+        }
+    }
+
+    pub fn matches(&self, addr: &SocketAddr) -> bool {
+        match self {
+            AllowedSender::Host(ip) => addr.ip() == *ip,
+            AllowedSender::Socket(socket) => addr == socket,
+        }
+    }
+}
+
 pub struct UdpSource {
     buffer: DeqBuffer,
     socket: UdpSocket,
     tmp_buffer: Vec<u8>,
+    /// Datagrams of all senders are accepted if empty.
+    allowed_senders: Vec<AllowedSender>,
+    last_sender: Option<SocketAddr>,
 }
 
 impl UdpSource {
     pub async fn new<A: ToSocketAddrs>(
         addr: A,
         multicast: Vec<stypes::MulticastInfo>,
+        allowed_senders: &[String],
     ) -> Result<Self, UdpSourceError> {
+        let allowed_senders = allowed_senders
+            .iter()
+            .map(|sender| AllowedSender::parse(sender))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(UdpSourceError::ParseAddr)?;
         let socket = UdpSocket::bind(addr).await.map_err(UdpSourceError::Io)?;
         for multicast_info in &multicast {
             let multi_addr = multicast_info
@@ -71,8 +110,40 @@ impl UdpSource {
             buffer: DeqBuffer::new(MAX_BUFF_SIZE),
             socket,
             tmp_buffer: vec![0u8; MAX_DATAGRAM_SIZE],
+            allowed_senders,
+            last_sender: None,
         })
     }
+
+    /// Address of the sender of the most recently received datagram.
+    pub fn last_sender(&self) -> Option<SocketAddr> {
+        self.last_sender
+    }
+
+    /// Waits for the next datagram of an allowed sender, returning its content along with
+    /// the address of its sender. Datagrams of other senders are dropped.
+    ///
+    /// The datagram isn't added to the internal buffer of the source, which makes it possible
+    /// to dispatch the datagrams of each sender to their own [`UdpSenderSource`].
+    pub async fn recv_datagram(&mut self) -> Result<(&[u8], SocketAddr), SourceError> {
+        loop {
+            let (len, remote_addr) = self
+                .socket
+                .recv_from(&mut self.tmp_buffer)
+                .await
+                .map_err(|e| SourceError::Setup(format!("{e}")))?;
+            if self.allowed_senders.is_empty()
+                || self
+                    .allowed_senders
+                    .iter()
+                    .any(|sender| sender.matches(&remote_addr))
+            {
+                self.last_sender = Some(remote_addr);
+                return Ok((&self.tmp_buffer[..len], remote_addr));
+            }
+            trace!("Dropping {len} bytes from not allowed sender {remote_addr}");
+        }
+    }
 }
 
 impl ByteSource for UdpSource {
@@ -92,16 +163,13 @@ impl ByteSource for UdpSource {
         }
 
         // TODO use filter
-        let (len, remote_addr) = self
-            .socket
-            .recv_from(&mut self.tmp_buffer)
-            .await
-            .map_err(|e| SourceError::Setup(format!("{e}")))?;
+        let (datagram, remote_addr) = self.recv_datagram().await?;
+        let len = datagram.len();
         trace!(
             "---> Received {} bytes from {:?}: {}",
             len,
             remote_addr,
-            String::from_utf8_lossy(&self.tmp_buffer[..len])
+            String::from_utf8_lossy(datagram)
         );
         if len > 0 {
             let added = self.buffer.write_from(&self.tmp_buffer[..len]);
@@ -130,6 +198,72 @@ impl ByteSource for UdpSource {
     }
 }
 
+/// Source delivering the datagrams of one sender, which are received by a [`UdpSource`]
+/// shared with other senders and dispatched over a channel.
+pub struct UdpSenderSource {
+    buffer: DeqBuffer,
+    rx_datagrams: mpsc::Receiver<Vec<u8>>,
+    sender: SocketAddr,
+}
+
+impl UdpSenderSource {
+    pub fn new(sender: SocketAddr, rx_datagrams: mpsc::Receiver<Vec<u8>>) -> Self {
+        Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            rx_datagrams,
+            sender,
+        }
+    }
+
+    /// Address of the sender of the datagrams.
+    pub fn sender(&self) -> SocketAddr {
+        self.sender
+    }
+}
+
+impl ByteSource for UdpSenderSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        match handle_buff_capacity(&mut self.buffer) {
+            BuffCapacityState::CanLoad => {}
+            BuffCapacityState::AlmostFull => {
+                let available_bytes = self.len();
+                return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+            }
+        }
+
+        // Dispatching ends once the socket is closed.
+        let Some(datagram) = self.rx_datagrams.recv().await else {
+            return Ok(None);
+        };
+        let len = datagram.len();
+        let added = self.buffer.write_from(&datagram);
+        if added < len {
+            return Err(SourceError::Unrecoverable(
+                "Internal buffer maximum capcity reached.".into(),
+            ));
+        }
+
+        let available_bytes = self.buffer.read_available();
+
+        Ok(Some(ReloadInfo::new(len, available_bytes, 0, None)))
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.read_available()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -154,7 +288,7 @@ mod tests {
                     .expect("could not send on socket");
             }
         });
-        let mut udp_source = UdpSource::new(RECEIVER, vec![]).await?;
+        let mut udp_source = UdpSource::new(RECEIVER, vec![], &[]).await?;
         let receive_handle = tokio::spawn(async move {
             for msg in MESSAGES {
                 udp_source.load(None).await.unwrap();
@@ -186,7 +320,7 @@ mod tests {
                     .expect("could not send on socket");
             }
         });
-        let mut udp_source = UdpSource::new(RECEIVER, vec![]).await.unwrap();
+        let mut udp_source = UdpSource::new(RECEIVER, vec![], &[]).await.unwrap();
 
         general_source_reload_test(&mut udp_source).await;
     }
@@ -227,7 +361,7 @@ mod tests {
             }
         });
 
-        let mut udp_source = UdpSource::new(RECEIVER, vec![]).await.unwrap();
+        let mut udp_source = UdpSource::new(RECEIVER, vec![], &[]).await.unwrap();
 
         while let Ok(Some(info)) = udp_source.load(None).await {
             if info.newly_loaded_bytes == 0 {
@@ -241,4 +375,37 @@ mod tests {
             udp_source.consume(info.available_bytes.min(CONSUME_LEN));
         }
     }
+
+    #[test]
+    fn parse_allowed_senders() {
+        let host = AllowedSender::parse("192.168.0.10").unwrap();
+        assert!(host.matches(&"192.168.0.10:3490".parse().unwrap()));
+        assert!(!host.matches(&"192.168.0.11:3490".parse().unwrap()));
+
+        let socket = AllowedSender::parse(" 192.168.0.10:3490 ").unwrap();
+        assert!(socket.matches(&"192.168.0.10:3490".parse().unwrap()));
+        assert!(!socket.matches(&"192.168.0.10:3491".parse().unwrap()));
+
+        assert!(AllowedSender::parse("[::1]:3490").is_ok());
+        assert!(AllowedSender::parse("ecu").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_udp_allowed_senders() {
+        static ALLOWED: &str = "127.0.0.1:4003";
+        static OTHER: &str = "127.0.0.1:4004";
+        static RECEIVER: &str = "127.0.0.1:5003";
+        let mut udp_source = UdpSource::new(RECEIVER, vec![], &[ALLOWED.to_owned()])
+            .await
+            .unwrap();
+
+        let other_socket = UdpSocket::bind(OTHER).await.unwrap();
+        other_socket.send_to(b"dropped", RECEIVER).await.unwrap();
+        let allowed_socket = UdpSocket::bind(ALLOWED).await.unwrap();
+        allowed_socket.send_to(b"accepted", RECEIVER).await.unwrap();
+
+        udp_source.load(None).await.unwrap();
+        assert_eq!(udp_source.current_slice(), b"accepted");
+        assert_eq!(udp_source.last_sender(), Some(ALLOWED.parse().unwrap()));
+    }
 }
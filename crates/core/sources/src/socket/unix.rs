@@ -0,0 +1,264 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+use bufread::DeqBuffer;
+use tokio::{
+    io::AsyncWriteExt,
+    net::{UnixDatagram, UnixStream, unix::SocketAddr},
+};
+
+use super::{BuffCapacityState, MAX_BUFF_SIZE, MAX_DATAGRAM_SIZE, handle_buff_capacity};
+
+enum UnixSocket {
+    /// Connection to a listening stream socket.
+    Stream(UnixStream),
+    /// Datagram socket bound to a path by this source.
+    Datagram {
+        socket: UnixDatagram,
+        /// Path of the socket file, which is removed once the source is dropped.
+        bound_path: PathBuf,
+        /// Address of the last sender, used as destination for SDE writes.
+        last_peer: Option<SocketAddr>,
+    },
+}
+
+/// Source reading from Unix domain sockets.
+///
+/// * Stream mode: Connects to a stream socket listening on the given path.
+/// * Datagram mode: Binds a datagram socket on the given path and receives the datagrams
+///   sent to it. The socket file is removed once the source is dropped.
+pub struct UnixSocketSource {
+    buffer: DeqBuffer,
+    socket: UnixSocket,
+    tmp_buffer: Vec<u8>,
+}
+
+impl UnixSocketSource {
+    /// Connects to the stream socket listening on the given path.
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let socket = UnixStream::connect(path).await?;
+        Ok(Self::with_socket(UnixSocket::Stream(socket)))
+    }
+
+    /// Binds a datagram socket on the given path, which must not exist yet.
+    pub fn bind_datagram(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let path = path.as_ref();
+        let socket = UnixDatagram::bind(path).map_err(|err| {
+            if err.kind() == std::io::ErrorKind::AddrInUse {
+                std::io::Error::new(
+                    err.kind(),
+                    format!("Socket file {} exists already", path.display()),
+                )
+            } else {
+                err
+            }
+        })?;
+        Ok(Self::with_socket(UnixSocket::Datagram {
+            socket,
+            bound_path: path.to_path_buf(),
+            last_peer: None,
+        }))
+    }
+
+    fn with_socket(socket: UnixSocket) -> Self {
+        Self {
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            socket,
+            tmp_buffer: vec![0u8; MAX_DATAGRAM_SIZE],
+        }
+    }
+
+    async fn write(&mut self, bytes: &[u8]) -> Result<usize, SourceError> {
+        match &mut self.socket {
+            UnixSocket::Stream(socket) => {
+                socket.write_all(bytes).await.map_err(SourceError::Io)?;
+                Ok(bytes.len())
+            }
+            UnixSocket::Datagram {
+                socket, last_peer, ..
+            } => {
+                let Some(peer) = last_peer.as_ref().and_then(|peer| peer.as_pathname()) else {
+                    return Err(SourceError::Setup(String::from(
+                        "No sender with a socket path is known to send data to",
+                    )));
+                };
+                socket.send_to(bytes, peer).await.map_err(SourceError::Io)
+            }
+        }
+    }
+}
+
+impl Drop for UnixSocketSource {
+    fn drop(&mut self) {
+        if let UnixSocket::Datagram { bound_path, .. } = &self.socket
+            && let Err(err) = fs::remove_file(bound_path)
+        {
+            warn!("Fail to remove socket file {}: {err}", bound_path.display());
+        }
+    }
+}
+
+impl ByteSource for UnixSocketSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        // If buffer is almost full then skip loading and return the available bytes.
+        // This can happen because some parsers will parse the first item of the provided slice
+        // while the producer will call load on each iteration making data accumulate.
+        match handle_buff_capacity(&mut self.buffer) {
+            BuffCapacityState::CanLoad => {}
+            BuffCapacityState::AlmostFull => {
+                let available_bytes = self.len();
+                return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+            }
+        }
+
+        let len = match &mut self.socket {
+            UnixSocket::Stream(socket) => loop {
+                socket
+                    .readable()
+                    .await
+                    .map_err(|e| SourceError::Unrecoverable(format!("{e}")))?;
+                match socket.try_read(&mut self.tmp_buffer) {
+                    Ok(len) => break len,
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => continue,
+                    Err(e) => {
+                        return Err(SourceError::Unrecoverable(format!(
+                            "Reading from Unix socket failed: {e}"
+                        )));
+                    }
+                }
+            },
+            UnixSocket::Datagram {
+                socket, last_peer, ..
+            } => {
+                let (len, peer) = socket
+                    .recv_from(&mut self.tmp_buffer)
+                    .await
+                    .map_err(|e| SourceError::Setup(format!("{e}")))?;
+                *last_peer = Some(peer);
+                len
+            }
+        };
+        trace!("---> Received {len} bytes");
+
+        // Zero bytes on stream sockets means that the other side has closed the connection.
+        if len > 0 {
+            let added = self.buffer.write_from(&self.tmp_buffer[..len]);
+            if added < len {
+                return Err(SourceError::Unrecoverable(format!(
+                    "Internal buffer maximum capcity reached.\
+                    Read from socket: {len}, Copied to buffer: {added}"
+                )));
+            }
+        }
+        let available_bytes = self.buffer.read_available();
+
+        Ok(Some(ReloadInfo::new(len, available_bytes, 0, None)))
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.read_available()
+    }
+
+    async fn income(
+        &mut self,
+        request: stypes::SdeRequest,
+    ) -> Result<stypes::SdeResponse, SourceError> {
+        let bytes = match request {
+            stypes::SdeRequest::WriteText(text) => self.write(text.as_bytes()).await?,
+            stypes::SdeRequest::WriteBytes(bytes) => self.write(&bytes).await?,
+            stypes::SdeRequest::Replay(_) | stypes::SdeRequest::DltControl(_) => {
+                return Err(SourceError::NotSupported);
+            }
+        };
+        Ok(stypes::SdeResponse { bytes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::general_source_reload_test;
+    use tokio::{
+        io::AsyncReadExt,
+        net::UnixListener,
+        time::{Duration, sleep},
+    };
+
+    static MESSAGES: &[&str] = &["one", "two", "three"];
+
+    fn socket_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("chipmunk_{name}_{}.sock", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_stream_reload_and_sde() {
+        let path = socket_path("stream");
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            for msg in MESSAGES {
+                stream.write_all(msg.as_bytes()).await.unwrap();
+                sleep(Duration::from_millis(10)).await;
+            }
+            let mut received = [0u8; 4];
+            stream.read_exact(&mut received).await.unwrap();
+            received
+        });
+
+        let mut source = UnixSocketSource::connect(&path).await.unwrap();
+        general_source_reload_test(&mut source).await;
+
+        let response = source
+            .income(stypes::SdeRequest::WriteText(String::from("ping")))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 4);
+        assert_eq!(&server.await.unwrap(), b"ping");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_datagram_reload_and_sde() {
+        let path = socket_path("datagram");
+        let sender_path = socket_path("sender");
+        let mut source = UnixSocketSource::bind_datagram(&path).unwrap();
+
+        let sender = UnixDatagram::bind(&sender_path).unwrap();
+        for msg in MESSAGES {
+            sender.send_to(msg.as_bytes(), &path).await.unwrap();
+        }
+        general_source_reload_test(&mut source).await;
+
+        let response = source
+            .income(stypes::SdeRequest::WriteBytes(b"ping".to_vec()))
+            .await
+            .unwrap();
+        assert_eq!(response.bytes, 4);
+        let mut received = [0u8; 4];
+        sender.recv(&mut received).await.unwrap();
+        assert_eq!(&received, b"ping");
+
+        drop(source);
+        assert!(
+            !path.exists(),
+            "Socket file must be removed with the source"
+        );
+        fs::remove_file(&sender_path).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+//! Detection and extraction of the members of zip and tar archives.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read, Result},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::compression::DecompressedReader;
+
+/// Offset of the `ustar` magic within the header of tar archives.
+const TAR_MAGIC_OFFSET: usize = 257;
+const TAR_MAGIC: &[u8] = b"ustar";
+
+/// Archive formats whose members can be extracted and opened as separate files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    /// Tar archive, which can be compressed itself (e.g. `.tar.gz`).
+    Tar,
+}
+
+impl ArchiveKind {
+    /// Detects whether the file at the given path is an archive, looking inside the
+    /// decompressed content for compressed files.
+    pub fn detect(file_path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let file_path = file_path.as_ref();
+
+        let mut header = Vec::with_capacity(4);
+        File::open(file_path)?.take(4).read_to_end(&mut header)?;
+        // Empty zip archives start with the end of central directory signature.
+        if header == b"PK\x03\x04" || header == b"PK\x05\x06" {
+            return Ok(Some(Self::Zip));
+        }
+
+        let mut header = Vec::with_capacity(TAR_MAGIC_OFFSET + TAR_MAGIC.len());
+        DecompressedReader::open(file_path)?
+            .take((TAR_MAGIC_OFFSET + TAR_MAGIC.len()) as u64)
+            .read_to_end(&mut header)?;
+        if header.get(TAR_MAGIC_OFFSET..) == Some(TAR_MAGIC) {
+            return Ok(Some(Self::Tar));
+        }
+
+        Ok(None)
+    }
+}
+
+/// Extracts the regular file members of the archive into `dest_dir`, returning the paths
+/// of the extracted files in their order within the archive.
+///
+/// Absolute member paths are extracted relative to `dest_dir`, and members with paths
+/// pointing outside of `dest_dir` are skipped.
+pub fn extract_members(
+    file_path: impl AsRef<Path>,
+    kind: ArchiveKind,
+    dest_dir: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>> {
+    let dest_dir = dest_dir.as_ref();
+    fs::create_dir_all(dest_dir)?;
+
+    match kind {
+        ArchiveKind::Zip => extract_zip(file_path.as_ref(), dest_dir),
+        ArchiveKind::Tar => extract_tar(file_path.as_ref(), dest_dir),
+    }
+}
+
+fn extract_zip(file_path: &Path, dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = zip::ZipArchive::new(File::open(file_path)?).map_err(io::Error::other)?;
+    let mut extracted = Vec::new();
+    for idx in 0..archive.len() {
+        let mut member = archive.by_index(idx).map_err(io::Error::other)?;
+        if !member.is_file() {
+            continue;
+        }
+        let Some(member_path) = member.enclosed_name() else {
+            continue;
+        };
+        let target = dest_dir.join(member_path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut member, &mut File::create(&target)?)?;
+        extracted.push(target);
+    }
+
+    Ok(extracted)
+}
+
+fn extract_tar(file_path: &Path, dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = tar::Archive::new(DecompressedReader::open(file_path)?);
+    let mut extracted = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(target) = tar_target(dest_dir, &entry.path()?) else {
+            continue;
+        };
+        // `unpack_in` refuses to write outside of the destination directory.
+        if entry.unpack_in(dest_dir)? {
+            extracted.push(target);
+        }
+    }
+
+    Ok(extracted)
+}
+
+/// Returns the path `unpack_in` extracts the tar member to, which drops the root of
+/// absolute member paths, or `None` if the member path leaves `dest_dir`.
+fn tar_target(dest_dir: &Path, member_path: &Path) -> Option<PathBuf> {
+    let mut target = dest_dir.to_path_buf();
+    for component in member_path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => return None,
+            Component::Normal(part) => target.push(part),
+        }
+    }
+    Some(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const MEMBERS: &[(&str, &[u8])] = &[
+        ("first.log", b"first\n"),
+        ("nested/second.log", b"second\n"),
+    ];
+
+    fn write_tar(writer: impl Write) {
+        let mut builder = tar::Builder::new(writer);
+        for (name, content) in MEMBERS {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *content).unwrap();
+        }
+        builder.into_inner().unwrap().flush().unwrap();
+    }
+
+    fn assert_extracted(extracted: &[PathBuf], dest_dir: &Path) {
+        assert_eq!(extracted.len(), MEMBERS.len());
+        for (path, (name, content)) in extracted.iter().zip(MEMBERS) {
+            assert_eq!(path, &dest_dir.join(name));
+            assert_eq!(fs::read(path).unwrap(), *content);
+        }
+    }
+
+    #[test]
+    fn extract_zip_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("bundle.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        for (name, content) in MEMBERS {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap();
+
+        assert_eq!(
+            ArchiveKind::detect(&archive_path).unwrap(),
+            Some(ArchiveKind::Zip)
+        );
+
+        let dest_dir = dir.path().join("out");
+        let extracted = extract_members(&archive_path, ArchiveKind::Zip, &dest_dir).unwrap();
+        assert_extracted(&extracted, &dest_dir);
+    }
+
+    #[test]
+    fn extract_compressed_tar_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("bundle.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        write_tar(encoder);
+
+        assert_eq!(
+            ArchiveKind::detect(&archive_path).unwrap(),
+            Some(ArchiveKind::Tar)
+        );
+
+        let dest_dir = dir.path().join("out");
+        let extracted = extract_members(&archive_path, ArchiveKind::Tar, &dest_dir).unwrap();
+        assert_extracted(&extracted, &dest_dir);
+    }
+
+    #[test]
+    fn extract_absolute_tar_members_into_dest_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("bundle.tar");
+        let mut builder = tar::Builder::new(File::create(&archive_path).unwrap());
+        let content = b"absolute\n";
+        let mut header = tar::Header::new_old();
+        // `set_path` refuses absolute paths, which other tools still write.
+        let name = b"/var/log/app.log";
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &content[..]).unwrap();
+        builder.into_inner().unwrap().flush().unwrap();
+
+        let dest_dir = dir.path().join("out");
+        let extracted = extract_members(&archive_path, ArchiveKind::Tar, &dest_dir).unwrap();
+        assert_eq!(extracted, vec![dest_dir.join("var/log/app.log")]);
+        assert_eq!(fs::read(&extracted[0]).unwrap(), content);
+    }
+
+    #[test]
+    fn plain_file_is_not_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        fs::write(&path, b"plain text").unwrap();
+
+        assert_eq!(ArchiveKind::detect(&path).unwrap(), None);
+    }
+}
@@ -0,0 +1,225 @@
+//! Detection and transparent decompression of compressed files.
+
+use std::{
+    fs::File,
+    io::{BufReader, Read, Result},
+    path::{Path, PathBuf},
+};
+
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use xz2::read::XzDecoder;
+
+/// Count of bytes needed to detect all supported compression formats.
+const MAGIC_LEN: usize = 10;
+
+/// Compression formats which can be decompressed on the fly while reading files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Xz,
+    Bzip2,
+}
+
+impl Compression {
+    /// Detects the compression format from the magic bytes at the beginning of the content.
+    pub fn from_magic(header: &[u8]) -> Option<Self> {
+        const BZIP2_BLOCK: [u8; 6] = [0x31, 0x41, 0x59, 0x26, 0x53, 0x59];
+        const BZIP2_EOS: [u8; 6] = [0x17, 0x72, 0x45, 0x38, 0x50, 0x90];
+
+        match header {
+            [0x1f, 0x8b, ..] => Some(Self::Gzip),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Self::Zstd),
+            [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => Some(Self::Xz),
+            // Check the magic of the first block too since text files could start with `BZh`.
+            [b'B', b'Z', b'h', b'1'..=b'9', rest @ ..]
+                if rest.starts_with(&BZIP2_BLOCK) || rest.starts_with(&BZIP2_EOS) =>
+            {
+                Some(Self::Bzip2)
+            }
+            _ => None,
+        }
+    }
+
+    /// Detects the compression format of the file at the given path from its content.
+    pub fn detect(file_path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let mut header = Vec::with_capacity(MAGIC_LEN);
+        File::open(file_path)?
+            .take(MAGIC_LEN as u64)
+            .read_to_end(&mut header)?;
+
+        Ok(Self::from_magic(&header))
+    }
+
+    /// Returns the file extensions commonly used for this compression format.
+    pub const fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Compression::Gzip => &["gz", "gzip"],
+            Compression::Zstd => &["zst", "zstd"],
+            Compression::Xz => &["xz"],
+            Compression::Bzip2 => &["bz2", "bzip2"],
+        }
+    }
+}
+
+/// Returns the path of the file without its compression extension (`trace.dlt.gz` becomes
+/// `trace.dlt`), which can be used to detect the format of the decompressed content.
+///
+/// The path is returned unchanged if it doesn't end with a known compression extension.
+pub fn strip_compression_extension(file_path: &Path) -> PathBuf {
+    let is_compression_ext = file_path.extension().is_some_and(|ext| {
+        [
+            Compression::Gzip,
+            Compression::Zstd,
+            Compression::Xz,
+            Compression::Bzip2,
+        ]
+        .iter()
+        .flat_map(|compression| compression.extensions())
+        .any(|known| ext.eq_ignore_ascii_case(known))
+    });
+
+    if is_compression_ext {
+        file_path.with_extension("")
+    } else {
+        file_path.to_path_buf()
+    }
+}
+
+/// Reader over the content of a file, decompressing it on the fly if the file is compressed.
+pub enum DecompressedReader {
+    Plain(File),
+    Gzip(MultiGzDecoder<BufReader<File>>),
+    Zstd(zstd::stream::read::Decoder<'static, BufReader<File>>),
+    Xz(XzDecoder<BufReader<File>>),
+    Bzip2(MultiBzDecoder<BufReader<File>>),
+}
+
+impl DecompressedReader {
+    /// Opens the file at the given path, detecting its compression from its content.
+    pub fn open(file_path: impl AsRef<Path>) -> Result<Self> {
+        let file_path = file_path.as_ref();
+        let compression = Compression::detect(file_path)?;
+        let file = File::open(file_path)?;
+
+        let reader = match compression {
+            None => Self::Plain(file),
+            Some(Compression::Gzip) => Self::Gzip(MultiGzDecoder::new(BufReader::new(file))),
+            Some(Compression::Zstd) => Self::Zstd(zstd::stream::read::Decoder::new(file)?),
+            Some(Compression::Xz) => Self::Xz(XzDecoder::new_multi_decoder(BufReader::new(file))),
+            Some(Compression::Bzip2) => Self::Bzip2(MultiBzDecoder::new(BufReader::new(file))),
+        };
+
+        Ok(reader)
+    }
+
+    /// Returns the compression of the underlying file if any.
+    pub fn compression(&self) -> Option<Compression> {
+        match self {
+            DecompressedReader::Plain(_) => None,
+            DecompressedReader::Gzip(_) => Some(Compression::Gzip),
+            DecompressedReader::Zstd(_) => Some(Compression::Zstd),
+            DecompressedReader::Xz(_) => Some(Compression::Xz),
+            DecompressedReader::Bzip2(_) => Some(Compression::Bzip2),
+        }
+    }
+}
+
+impl Read for DecompressedReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            DecompressedReader::Plain(reader) => reader.read(buf),
+            DecompressedReader::Gzip(reader) => reader.read(buf),
+            DecompressedReader::Zstd(reader) => reader.read(buf),
+            DecompressedReader::Xz(reader) => reader.read(buf),
+            DecompressedReader::Bzip2(reader) => reader.read(buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const CONTENT: &[u8] = b"first line\nsecond line\n";
+
+    fn compress(compression: Compression) -> Vec<u8> {
+        match compression {
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(CONTENT).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Zstd => zstd::encode_all(CONTENT, 0).unwrap(),
+            Compression::Xz => {
+                let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+                encoder.write_all(CONTENT).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Bzip2 => {
+                let mut encoder =
+                    bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+                encoder.write_all(CONTENT).unwrap();
+                encoder.finish().unwrap()
+            }
+        }
+    }
+
+    #[test]
+    fn decompress_all_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        for compression in [
+            Compression::Gzip,
+            Compression::Zstd,
+            Compression::Xz,
+            Compression::Bzip2,
+        ] {
+            let path = dir
+                .path()
+                .join(format!("log.{}", compression.extensions()[0]));
+            std::fs::write(&path, compress(compression)).unwrap();
+
+            assert_eq!(Compression::detect(&path).unwrap(), Some(compression));
+
+            let mut reader = DecompressedReader::open(&path).unwrap();
+            assert_eq!(reader.compression(), Some(compression));
+            let mut content = Vec::new();
+            reader.read_to_end(&mut content).unwrap();
+            assert_eq!(content, CONTENT);
+        }
+    }
+
+    #[test]
+    fn plain_file_is_read_as_is() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        // Text starting with bzip2 signature must not be detected as compressed.
+        std::fs::write(&path, b"BZh9 plain text").unwrap();
+
+        let mut reader = DecompressedReader::open(&path).unwrap();
+        assert_eq!(reader.compression(), None);
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).unwrap();
+        assert_eq!(content, b"BZh9 plain text");
+    }
+
+    #[test]
+    fn strip_extensions() {
+        assert_eq!(
+            strip_compression_extension(Path::new("trace.dlt.gz")),
+            PathBuf::from("trace.dlt")
+        );
+        assert_eq!(
+            strip_compression_extension(Path::new("capture.pcap.ZST")),
+            PathBuf::from("capture.pcap")
+        );
+        assert_eq!(
+            strip_compression_extension(Path::new("trace.dlt")),
+            PathBuf::from("trace.dlt")
+        );
+    }
+}
@@ -1,12 +1,19 @@
 //! Utilities for lightweight file content classification.
+//!
+//! Compressed files are classified by their decompressed content.
 
 use std::{
-    fs::File,
     io::{Read, Result},
     path::Path,
     str::from_utf8,
 };
 
+pub mod archive;
+pub mod compression;
+
+pub use archive::{ArchiveKind, extract_members};
+pub use compression::{Compression, DecompressedReader, strip_compression_extension};
+
 const BYTES_TO_READ: u64 = 10240;
 
 /// Returns whether the beginning of the file is valid UTF-8 text.
@@ -24,7 +31,7 @@ pub fn is_binary(file_path: impl AsRef<Path>) -> Result<bool> {
 
 fn fetch_starting_chunk(file_path: &Path) -> Result<Vec<u8>> {
     let mut buffer = Vec::new();
-    File::open(file_path)?
+    DecompressedReader::open(file_path)?
         .take(BYTES_TO_READ)
         .read_to_end(&mut buffer)?;
     Ok(buffer)
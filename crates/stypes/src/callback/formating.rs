@@ -11,12 +11,16 @@ impl std::fmt::Display for CallbackEvent {
     /// - `FileRead` - Indicates that a file has been read.
     /// - `SearchUpdated(found)` - Shows the number of search results found.
     /// - `IndexedMapUpdated(len)` - Displays the number of indexed map entries.
+    /// - `HiddenRowsUpdated(len)` - Displays the number of rows hidden from the main view.
+    /// - `TimeIndexUpdated(len)` - Displays the number of rows with indexed timestamps.
     /// - `SearchMapUpdated` - Indicates that the search map has been updated.
     /// - `SearchValuesUpdated` - Indicates that search values have been updated.
     /// - `AttachmentsUpdated: {len}` - Displays the total count of the session attachments.
     /// - `OperationError: {uuid}: {error}` - Displays the UUID of the operation and the error details.
     /// - `OperationStarted: {uuid}` - Displays the UUID of a started operation.
     /// - `OperationProcessing: {uuid}` - Displays the UUID of an operation in progress.
+    /// - `SourceStateChanged: {uuid}: {state}` - Displays the UUID of an operation and the state
+    ///   of its source.
     /// - `OperationDone: {info.uuid}` - Displays the UUID of a completed operation.
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -24,6 +28,8 @@ impl std::fmt::Display for CallbackEvent {
             Self::FileRead => write!(f, "FileRead"),
             Self::SearchUpdated { found, stat: _ } => write!(f, "SearchUpdated({found})"),
             Self::IndexedMapUpdated { len } => write!(f, "IndexedMapUpdated({len})"),
+            Self::HiddenRowsUpdated { len } => write!(f, "HiddenRowsUpdated({len})"),
+            Self::TimeIndexUpdated { len } => write!(f, "TimeIndexUpdated({len})"),
             Self::SearchMapUpdated(_) => write!(f, "SearchMapUpdated"),
             Self::SearchValuesUpdated(_) => write!(f, "SearchValuesUpdated"),
             Self::AttachmentsUpdated {
@@ -37,6 +43,9 @@ impl std::fmt::Display for CallbackEvent {
             }
             Self::OperationStarted(uuid) => write!(f, "OperationStarted: {uuid}"),
             Self::OperationProcessing(uuid) => write!(f, "OperationProcessing: {uuid}"),
+            Self::SourceStateChanged { uuid, state } => {
+                write!(f, "SourceStateChanged: {uuid}: {state}")
+            }
             Self::OperationDone(info) => write!(f, "OperationDone: {}", info.uuid),
         }
     }
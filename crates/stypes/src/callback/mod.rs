@@ -43,6 +43,18 @@ pub enum CallbackEvent {
         len: u64,
     },
 
+    /// Triggered when the rows hidden from the main view by exclusion filters change.
+    HiddenRowsUpdated {
+        /// The number of session rows hidden from the main view.
+        len: u64,
+    },
+
+    /// Triggered while the timestamps of the rows of a linked text file are indexed.
+    TimeIndexUpdated {
+        /// The number of session rows with indexed timestamps.
+        len: u64,
+    },
+
     /// Always triggered immediately after `SearchUpdated`. Contains data about
     /// the search conditions that matched, along with the indices of log entries where matches were found.
     /// - `Option<FilterMatchList>`: The list of matches with log entry indices.
@@ -84,6 +96,15 @@ pub enum CallbackEvent {
     /// - `Uuid`: The unique identifier of the operation.
     OperationProcessing(Uuid),
 
+    /// Triggered when the connection state of an observed source changes. This event is
+    /// only triggered for sources reconnecting on their own once their connection is lost.
+    SourceStateChanged {
+        /// The unique identifier of the operation observing the source.
+        uuid: Uuid,
+        /// The current connection state.
+        state: SourceConnectionState,
+    },
+
     /// Triggered upon the successful completion of an operation.
     /// - `OperationDone`: The results of the completed operation.
     OperationDone(OperationDone),
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use crate::*;
 
 impl GrabbedElement {
@@ -23,3 +25,32 @@ impl FilterMatch {
         Self { index, filters }
     }
 }
+
+impl DltLogLevel {
+    /// All log levels ordered by their value.
+    pub const ALL: [DltLogLevel; 7] = [
+        DltLogLevel::Off,
+        DltLogLevel::Fatal,
+        DltLogLevel::Error,
+        DltLogLevel::Warn,
+        DltLogLevel::Info,
+        DltLogLevel::Debug,
+        DltLogLevel::Verbose,
+    ];
+}
+
+impl Display for DltLogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            DltLogLevel::Off => "Off",
+            DltLogLevel::Fatal => "Fatal",
+            DltLogLevel::Error => "Error",
+            DltLogLevel::Warn => "Warn",
+            DltLogLevel::Info => "Info",
+            DltLogLevel::Debug => "Debug",
+            DltLogLevel::Verbose => "Verbose",
+        };
+
+        f.write_str(content)
+    }
+}
@@ -36,6 +36,54 @@ pub enum SdeRequest {
     WriteText(String),
     /// Sends raw bytes.
     WriteBytes(Vec<u8>),
+    /// Controls the playback of replayed traces.
+    Replay(ReplayControl),
+    /// Sends a DLT control message to the ECU behind the source.
+    DltControl(DltControlRequest),
+}
+
+/// A DLT control message addressed to the DLT daemon of an ECU.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct DltControlRequest {
+    /// The ID of the ECU receiving the message, with up to 4 ASCII characters.
+    pub ecu_id: String,
+    pub command: DltControlCommand,
+}
+
+/// The services of DLT control messages which can be requested.
+///
+/// Application and context IDs have up to 4 ASCII characters, where empty IDs
+/// address all applications or contexts.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum DltControlCommand {
+    /// Sets the log level of the given contexts.
+    SetLogLevel {
+        app_id: String,
+        context_id: String,
+        level: DltLogLevel,
+    },
+    /// Requests the registered applications and contexts with their log levels
+    /// and trace status.
+    GetLogInfo {
+        app_id: String,
+        context_id: String,
+        /// Includes the descriptions of applications and contexts in the response.
+        with_descriptions: bool,
+    },
+    /// Enables or disables the trace messages of contexts without an explicit trace status.
+    SetDefaultTraceStatus(bool),
+}
+
+/// The log levels of DLT messages, with the values used in control messages.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum DltLogLevel {
+    Off = 0,
+    Fatal = 1,
+    Error = 2,
+    Warn = 3,
+    Info = 4,
+    Debug = 5,
+    Verbose = 6,
 }
 
 /// The response from a source to a sent `SdeRequest`. Note that sending data
@@ -61,6 +109,40 @@ pub struct GrabbedElement {
     /// - `BOOKMARK`: Nature = Nature(1 << 1)
     /// - `SEARCH | BOOKMARK`: Nature = Nature(3)
     pub nature: u8,
+    /// The matches of the search filters in `content`, available for search results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<MatchRange>,
+    /// The run of consecutive duplicates of the log entry, available for the first entry
+    /// of a run while duplicates are collapsed in the main view.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<CollapsedRun>,
+}
+
+/// A run of consecutive duplicate log entries, which is shown as its first entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollapsedRun {
+    /// The number of entries in the run, including the first one.
+    pub count: u64,
+    /// The position of the last entry of the run in the overall stream.
+    pub last_pos: usize,
+    /// The timestamps of the first and the last entry in milliseconds since the Unix
+    /// epoch, if the entries carry timestamps.
+    pub time: Option<(u64, u64)>,
+    /// Whether all entries of the run are shown.
+    pub expanded: bool,
+}
+
+/// The byte range of a search filter match in the content of a log entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRange {
+    /// The identifier of the matched filter.
+    pub filter: u8,
+    /// The index of the capture group of the filter, where `0` is the whole match.
+    pub group: u8,
+    /// The start of the match in bytes.
+    pub start: usize,
+    /// The end of the match in bytes, exclusive.
+    pub end: usize,
 }
 
 /// A list of log entries.
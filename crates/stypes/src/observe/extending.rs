@@ -17,6 +17,7 @@ impl ObserveOptions {
         ObserveOptions {
             origin: ObserveOrigin::File(Uuid::new_v4().to_string(), file_origin, filename),
             parser,
+            recording: None,
         }
     }
 }
@@ -136,3 +137,147 @@ impl Display for FileFormat {
         f.write_str(content)
     }
 }
+
+impl Display for TcpMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            TcpMode::Connect => "Connect",
+            TcpMode::Listen => "Listen",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for DirWatchOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            DirWatchOrder::Name => "Name",
+            DirWatchOrder::Modified => "Modification time",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for UnixSocketMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            UnixSocketMode::Stream => "Stream",
+            UnixSocketMode::Datagram => "Datagram",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for MqttQos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            MqttQos::AtMostOnce => "At most once (0)",
+            MqttQos::AtLeastOnce => "At least once (1)",
+            MqttQos::ExactlyOnce => "Exactly once (2)",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for WebSocketMessageMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            WebSocketMessageMode::Lines => "Lines",
+            WebSocketMessageMode::Raw => "Raw",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for HttpStreamFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            HttpStreamFormat::Raw => "Raw",
+            HttpStreamFormat::ServerSentEvents => "Server-sent events",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for ProcessOutputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            ProcessOutputMode::Lines => "Lines",
+            ProcessOutputMode::Raw => "Raw bytes",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for ProcessRestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            ProcessRestartPolicy::Never => "Never",
+            ProcessRestartPolicy::OnFailure => "On failure",
+            ProcessRestartPolicy::Always => "Always",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for SerialReadMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            SerialReadMode::Lines => "Lines",
+            SerialReadMode::Raw => "Raw bytes",
+        };
+
+        f.write_str(content)
+    }
+}
+
+impl Display for SerialUsbId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vid, self.pid)?;
+        if let Some(serial_number) = &self.serial_number {
+            write!(f, " ({serial_number})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SourceConnectionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceConnectionState::Connected => f.write_str("Connected"),
+            SourceConnectionState::Reconnecting { attempts } => {
+                write!(f, "Reconnecting (attempt {attempts})")
+            }
+        }
+    }
+}
+
+impl Display for CanFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.extended {
+            write!(f, "{:08X}:{:08X}", self.id, self.mask)
+        } else {
+            write!(f, "{:03X}:{:03X}", self.id, self.mask)
+        }
+    }
+}
+
+impl Display for CanTimestampSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let content = match self {
+            CanTimestampSource::Software => "Software",
+            CanTimestampSource::Hardware => "Hardware",
+        };
+
+        f.write_str(content)
+    }
+}
@@ -71,6 +71,18 @@ pub enum Transport {
     UDP(UDPTransportConfig),
     /// Serial port connection.
     Serial(SerialTransportConfig),
+    /// Unix domain socket connection.
+    UnixSocket(UnixSocketTransportConfig),
+    /// Replay of a recorded trace according to its timestamps.
+    Replay(ReplayTransportConfig),
+    /// Subscription to the topics of an MQTT broker.
+    Mqtt(MqttTransportConfig),
+    /// WebSocket connection to a server.
+    WebSocket(WebSocketTransportConfig),
+    /// Long-lived HTTP response streamed in chunks.
+    HttpStream(HttpStreamTransportConfig),
+    /// Frames of a Linux SocketCAN interface.
+    SocketCan(SocketCanTransportConfig),
 }
 
 /// Configuration for executing terminal commands.
@@ -82,6 +94,43 @@ pub struct ProcessTransportConfig {
     pub command: String,
     /// Optional shell infos if user selected one other than the default shells
     pub shell: Option<ShellProfile>,
+    /// Additional environment variables for the command as name-value pairs.
+    #[serde(default)]
+    pub envs: Vec<(String, String)>,
+    /// How the output of the command is delivered to the parser.
+    #[serde(default)]
+    pub output: ProcessOutputMode,
+    /// Runs the command attached to a pseudo-terminal instead of pipes, for tools which
+    /// flush their output only when writing to a terminal. Supported on Unix only.
+    #[serde(default)]
+    pub pty: bool,
+    /// Defines when the command is started again once it exits.
+    #[serde(default)]
+    pub restart: ProcessRestartPolicy,
+}
+
+/// Describes how the output of a command is delivered to the parser.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessOutputMode {
+    /// Lines of stdout and stderr, suitable for text parsing.
+    #[default]
+    Lines,
+    /// Bytes of stdout unchanged, suitable for binary formats like DLT or pcap.
+    /// Lines of stderr are logged without being delivered to the parser.
+    Raw,
+}
+
+/// Defines when a command is started again once it exits. Restarts are delayed with a
+/// backoff growing with each consecutive restart.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessRestartPolicy {
+    /// The session ends once the command exits.
+    #[default]
+    Never,
+    /// Restarts the command if it exits with a failure status.
+    OnFailure,
+    /// Restarts the command whenever it exits.
+    Always,
 }
 
 /// Configuration for serial port connections.
@@ -103,6 +152,59 @@ pub struct SerialTransportConfig {
     pub send_data_delay: u8,
     /// Whether the connection is exclusive.
     pub exclusive: bool,
+    /// How the received data are delivered to the parser.
+    #[serde(default)]
+    pub read_mode: SerialReadMode,
+    /// Whether and how to find the device again once it's disconnected.
+    #[serde(default)]
+    pub reconnect: SerialReconnect,
+}
+
+/// Describes how data received on a serial port are delivered to the parser.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SerialReadMode {
+    /// Complete lines of text, suitable for text parsing.
+    #[default]
+    Lines,
+    /// Received bytes unchanged, suitable for binary formats like DLT.
+    Raw,
+}
+
+/// Defines how a serial device is found again once it's disconnected (e.g. an unplugged
+/// USB adapter). The port is reopened repeatedly until the device is available again.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum SerialReconnect {
+    /// The connection ends once the device is disconnected.
+    #[default]
+    Disabled,
+    /// Reopens the port on the same path.
+    ByPath,
+    /// Reopens the port of the USB device with the given identity, which may appear on
+    /// another path once replugged.
+    ByUsbId(SerialUsbId),
+}
+
+/// Identity of a USB serial device.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SerialUsbId {
+    /// Vendor ID.
+    pub vid: u16,
+    /// Product ID.
+    pub pid: u16,
+    /// Serial number of the device, which distinguishes devices of the same model.
+    pub serial_number: Option<String>,
+}
+
+/// Connection state of a source reconnecting on its own once its connection is lost.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum SourceConnectionState {
+    /// Source is connected.
+    Connected,
+    /// Connection is lost and the source is trying to reconnect.
+    Reconnecting {
+        /// The count of attempts to reconnect so far.
+        attempts: usize,
+    },
 }
 
 /// Configuration for TCP connections.
@@ -110,6 +212,245 @@ pub struct SerialTransportConfig {
 pub struct TCPTransportConfig {
     /// The address to bind the TCP connection to.
     pub bind_addr: String,
+    /// Whether to connect to `bind_addr` or to listen on it for incoming clients.
+    #[serde(default)]
+    pub mode: TcpMode,
+}
+
+/// Describes how a TCP connection is established.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TcpMode {
+    /// Connects to a remote server as a client.
+    #[default]
+    Connect,
+    /// Listens on the address and accepts incoming clients.
+    /// Each accepted connection is observed as its own source.
+    Listen,
+}
+
+/// Configuration for Unix domain socket connections.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnixSocketTransportConfig {
+    /// The path of the socket file.
+    pub path: String,
+    /// Whether to connect to a stream socket or to receive datagrams on the path.
+    #[serde(default)]
+    pub mode: UnixSocketMode,
+}
+
+/// Describes the type of a Unix domain socket.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum UnixSocketMode {
+    /// Connects to a stream socket listening on the path.
+    #[default]
+    Stream,
+    /// Binds a datagram socket on the path and receives the datagrams sent to it.
+    /// The socket file must not exist and is removed once the session is closed.
+    Datagram,
+}
+
+/// Configuration for subscribing to the topics of an MQTT broker.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MqttTransportConfig {
+    /// The URL of the broker (e.g. `mqtt://localhost:1883`). The scheme `mqtts` connects
+    /// over TLS.
+    pub broker_url: String,
+    /// The client ID used to connect to the broker.
+    pub client_id: String,
+    /// Topic filters to subscribe to, which may contain the wildcards `+` and `#`.
+    pub topics: Vec<String>,
+    /// The quality of service of the subscriptions and of published messages.
+    #[serde(default)]
+    pub qos: MqttQos,
+    /// Local certificates used for TLS connections. The system root certificates are used
+    /// if not given.
+    #[serde(default)]
+    pub tls: Option<MqttTlsConfig>,
+    /// Whether the messages of each topic are observed as a separate source.
+    #[serde(default)]
+    pub split_topics: bool,
+    /// The topic data sent through SDE is published to.
+    #[serde(default)]
+    pub sde_topic: Option<String>,
+}
+
+/// Quality of service levels of MQTT.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MqttQos {
+    /// Messages are delivered at most once.
+    #[default]
+    AtMostOnce,
+    /// Messages are delivered at least once.
+    AtLeastOnce,
+    /// Messages are delivered exactly once.
+    ExactlyOnce,
+}
+
+/// Local certificates for TLS connections to MQTT brokers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MqttTlsConfig {
+    /// The PEM file of the certificate authority used to verify the broker.
+    pub ca_file: PathBuf,
+    /// The PEM files of the client certificate and its private key if the broker
+    /// authenticates its clients.
+    #[serde(default)]
+    pub client_auth: Option<(PathBuf, PathBuf)>,
+}
+
+/// Configuration for receiving the messages of a WebSocket server.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebSocketTransportConfig {
+    /// The URL of the server (e.g. `ws://localhost:8080/logs`). The scheme `wss` connects
+    /// over TLS.
+    pub url: String,
+    /// Additional headers of the opening handshake.
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+    /// Token sent as `Authorization: Bearer` header.
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+    /// How the payloads of the received messages are delivered.
+    #[serde(default)]
+    pub messages: WebSocketMessageMode,
+    /// Whether the connection is reopened once it's closed or lost.
+    #[serde(default)]
+    pub reconnect: bool,
+}
+
+/// Describes how the payloads of WebSocket messages are delivered.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WebSocketMessageMode {
+    /// Each message is delivered as one line, a line break is appended when it's missing.
+    #[default]
+    Lines,
+    /// Payloads are delivered unchanged, which suits binary protocols like DLT.
+    Raw,
+}
+
+/// Configuration for receiving the body of a long-lived HTTP response.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpStreamTransportConfig {
+    /// The URL requested with `GET` (e.g. `https://gateway/logs/stream`).
+    pub url: String,
+    /// Additional headers of the request.
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+    /// Token sent as `Authorization: Bearer` header.
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+    /// How the body of the response is read.
+    #[serde(default)]
+    pub format: HttpStreamFormat,
+    /// Whether the request is sent again once the response ended or the connection is lost.
+    #[serde(default)]
+    pub reconnect: bool,
+}
+
+/// Describes the content of the body of streamed HTTP responses.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HttpStreamFormat {
+    /// The body is delivered unchanged.
+    #[default]
+    Raw,
+    /// The body consists of server-sent events. The data of each event is delivered as
+    /// one line.
+    ServerSentEvents,
+}
+
+/// Header of HTTP requests.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// Configuration for receiving the frames of a Linux SocketCAN interface.
+///
+/// Frames are delivered as lines in the log format of `candump`, e.g.
+/// `(1700000000.123456) can0 123#DEADBEEF`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SocketCanTransportConfig {
+    /// The name of the interface (e.g. `can0`, or `vcan0` for virtual interfaces).
+    pub interface: String,
+    /// Filters applied by the kernel. A frame is received if it matches any of them.
+    /// All frames are received if empty.
+    #[serde(default)]
+    pub filters: Vec<CanFilter>,
+    /// Whether CAN FD frames are received and can be sent.
+    #[serde(default)]
+    pub fd_frames: bool,
+    /// Whether the error frames of the interface are received.
+    #[serde(default)]
+    pub error_frames: bool,
+    /// The clock the frames are timestamped with.
+    #[serde(default)]
+    pub timestamps: CanTimestampSource,
+}
+
+/// Filter of CAN frames, matching frames with `frame_id & mask == id & mask`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CanFilter {
+    pub id: u32,
+    pub mask: u32,
+    /// Whether the filter matches frames with 29-bit extended IDs instead of 11-bit
+    /// standard IDs.
+    #[serde(default)]
+    pub extended: bool,
+}
+
+/// Describes the clock CAN frames are timestamped with.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CanTimestampSource {
+    /// The kernel timestamps the frames once they are received.
+    #[default]
+    Software,
+    /// The controller timestamps the frames. Falls back to software timestamps for
+    /// frames without hardware timestamp.
+    Hardware,
+}
+
+/// Configuration for replaying recorded traces as streams.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplayTransportConfig {
+    /// The path of the recorded trace.
+    pub path: PathBuf,
+    /// The format of the trace. Supported are pcap and pcapng files as well as DLT files
+    /// with storage headers (`FileFormat::Binary`).
+    pub format: FileFormat,
+    /// The speed at which the trace is replayed initially.
+    #[serde(default)]
+    pub speed: ReplaySpeed,
+}
+
+/// Describes how fast recorded traces are replayed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Delays between the records are divided by the given factor, where `1.0` replays
+    /// the trace at its original speed.
+    Scaled(f64),
+    /// Records are delivered without any delays.
+    Unlimited,
+}
+
+impl Default for ReplaySpeed {
+    fn default() -> Self {
+        Self::Scaled(1.0)
+    }
+}
+
+/// Controls the playback of a replayed trace while it's observed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ReplayControl {
+    /// Stops delivering records until the replay is resumed.
+    Pause,
+    /// Continues delivering records after a pause.
+    Resume,
+    /// Changes the speed of the replay.
+    SetSpeed(ReplaySpeed),
+    /// Continues the replay at the given offset in milliseconds from the first record.
+    /// Records are delivered again on seeking backwards, since already observed records
+    /// stay in the session.
+    Seek(u64),
 }
 
 /// Configuration for UDP connections.
@@ -119,6 +460,13 @@ pub struct UDPTransportConfig {
     pub bind_addr: String,
     /// A list of multicast configurations.
     pub multicast: Vec<MulticastInfo>,
+    /// Senders whose datagrams are accepted, given as IP addresses or as socket addresses
+    /// including the port. Datagrams of all senders are accepted if empty.
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+    /// Whether the datagrams of each sender are observed as a separate source.
+    #[serde(default)]
+    pub split_senders: bool,
 }
 
 /// Supported file formats for observation.
@@ -133,6 +481,30 @@ pub enum FileFormat {
     Binary,
 }
 
+/// Order in which the files of a watched directory are appended to the session.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirWatchOrder {
+    /// Files are ordered by their names.
+    #[default]
+    Name,
+    /// Files are ordered by their last modification time.
+    Modified,
+}
+
+/// Configuration for watching a directory for new files.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct DirWatchConfig {
+    /// The directory to watch. Subdirectories aren't included.
+    pub dir: PathBuf,
+    /// Glob pattern the file names must match (e.g. `*.dlt`).
+    pub pattern: String,
+    /// The format of the matching files.
+    pub format: FileFormat,
+    /// The order in which the files are appended to the session.
+    #[serde(default)]
+    pub order: DirWatchOrder,
+}
+
 /// Describes the source of data for observation.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ObserveOrigin {
@@ -142,6 +514,11 @@ pub enum ObserveOrigin {
     Concat(Vec<(String, FileFormat, PathBuf)>),
     /// The source is a stream.
     Stream(String, Transport),
+    /// The source is a directory where files are appended to the session as they appear.
+    /// Each file is added as its own source.
+    DirWatch(String, DirWatchConfig),
+    /// The source is a byte-source plugin delivering the bytes to parse.
+    Plugin(String, PluginByteSourceSettings),
 }
 
 /// Options for observing data within a session.
@@ -151,4 +528,24 @@ pub struct ObserveOptions {
     pub origin: ObserveOrigin,
     /// The parser configuration to be applied.
     pub parser: ParserType,
+    /// Records the raw bytes of stream sources to files if provided. It's ignored for other
+    /// sources since their raw bytes are available already.
+    #[serde(default)]
+    pub recording: Option<StreamRecording>,
+}
+
+/// Configuration to record the raw bytes of a stream to files, which can be opened later as
+/// file sources.
+///
+/// The format of the files depends on the stream and its parser. DLT messages are written
+/// with storage headers to `.dlt` files, network payloads are written as frames with
+/// timestamps to `.pcapng` files and any other data is written unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StreamRecording {
+    /// Directory to write the recording files into.
+    pub dir: PathBuf,
+    /// Recording continues in a new file once the current one reaches this size in bytes.
+    pub max_file_size: Option<u64>,
+    /// Recording continues in a new file once the current one covers this count of seconds.
+    pub max_file_duration: Option<u64>,
 }
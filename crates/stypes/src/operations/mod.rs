@@ -38,3 +38,88 @@ pub struct ResultU64(pub u64);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResultBool(pub bool);
+
+/// A template of similar log messages, in which the parts the messages differ in
+/// are variable slots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogTemplate {
+    /// The tokens of the template, with variable slots shown as `<*>`.
+    pub template: String,
+    /// A regular expression matching the messages of the template.
+    pub pattern: String,
+    /// The number of messages of the template.
+    pub count: u64,
+    /// The row of the first message of the template.
+    pub first_row: u64,
+    /// The row of the last message of the template.
+    pub last_row: u64,
+    /// The earliest timestamp of the messages, in milliseconds since the Unix epoch.
+    pub first_time: Option<u64>,
+    /// The latest timestamp of the messages, in milliseconds since the Unix epoch.
+    pub last_time: Option<u64>,
+    /// The number of messages in each bucket of `LogTemplates::time`.
+    pub histogram: Vec<u64>,
+}
+
+/// The templates mined from the messages of a session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogTemplates {
+    /// The templates, the most frequent first.
+    pub templates: Vec<LogTemplate>,
+    /// The time range split into the buckets of the histograms, if the session has timestamps.
+    pub time: Option<(u64, u64)>,
+    /// The number of mined messages.
+    pub rows: u64,
+    /// The number of messages left out after the limit of templates was reached.
+    pub unclustered: u64,
+}
+
+/// How the messages of a template differ between two compared logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogDiffKind {
+    /// The messages are only in the other log.
+    Added,
+    /// The messages are only in the compared log.
+    Removed,
+    /// The messages are in both logs, but their number differs.
+    Changed,
+    /// The messages are in both logs as often, but first appear in a different order
+    /// relative to the other messages.
+    Moved,
+}
+
+/// The occurrences of the messages of a template in one of the compared logs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogDiffSide {
+    /// The number of messages.
+    pub count: u64,
+    /// The row of the first message.
+    pub first_row: u64,
+    /// The row of the last message.
+    pub last_row: u64,
+}
+
+/// A template of messages which differs between the compared logs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogDiffEntry {
+    pub kind: LogDiffKind,
+    /// The normalized message, with numbers, ids and timestamps shown as `<*>`.
+    pub template: String,
+    /// The messages in the compared log, if any.
+    pub left: Option<LogDiffSide>,
+    /// The messages in the other log, if any.
+    pub right: Option<LogDiffSide>,
+}
+
+/// The differences between a log and another log it is compared with.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogDiff {
+    /// The differing templates, grouped by their kind.
+    pub entries: Vec<LogDiffEntry>,
+    /// The number of compared rows of the compared log.
+    pub left_rows: u64,
+    /// The number of compared rows of the other log.
+    pub right_rows: u64,
+    /// The number of templates equal in both logs.
+    pub unchanged: u64,
+}
@@ -26,6 +26,8 @@ pub struct PluginParserGeneralSettings {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginByteSourceSettings {
     pub plugin_path: PathBuf,
+    // General setting doesn't exist in front-end since it doesn't have real fields yet.
+    #[serde(default)]
     pub general_settings: PluginByteSourceGeneralSettings,
     pub plugin_configs: Vec<PluginConfigItem>,
 }